@@ -0,0 +1,58 @@
+//! Learn page
+//!
+//! Daily practice goals and the practice streak, and the algorithm
+//! reference sheet. Sending an algorithm's case to the trainer loads it
+//! onto the cube and opens the Solve page.
+
+use crate::app::{use_app_state, Route};
+use crate::components::{AlgorithmBrowser, PracticeGoals};
+use crate::state::{today, DailyGoal, LessonId};
+use crate::tutorial::PracticeCase;
+use dioxus::prelude::*;
+
+/// Practice goals and algorithms
+#[component]
+pub fn LearnPage() -> Element {
+    let mut state = use_app_state();
+    let mut progress = state.progress;
+
+    rsx! {
+        // Practice streak and daily goals
+        section {
+            style: "max-width: 800px; width: 100%;",
+            h2 {
+                "Daily Practice"
+            }
+            PracticeGoals {
+                streak: progress().streak().as_of(today()),
+                best_streak: progress().streak().best,
+                goals: progress()
+                    .goals()
+                    .iter()
+                    .map(|goal| (goal.clone(), progress().goal_progress(goal, today())))
+                    .collect::<Vec<_>>(),
+                on_set_goal: move |goal: DailyGoal| progress.write().set_goal(goal),
+                on_remove_goal: move |lesson: LessonId| progress.write().remove_goal(&lesson),
+            }
+        }
+
+        // Algorithm reference sheet
+        section {
+            style: "max-width: 800px; width: 100%;",
+            h2 {
+                "Algorithms"
+            }
+            AlgorithmBrowser {
+                favorites: progress().favorite_algorithms(),
+                on_toggle_favorite: move |id: String| {
+                    progress.write().toggle_favorite(&id);
+                },
+                on_send_to_trainer: move |case: PracticeCase| {
+                    // Load the case onto the cube so it can be turned or solved
+                    state.load_cube(case.get_scrambled_cube(), case.description.clone());
+                    navigator().push(Route::Solve {});
+                },
+            }
+        }
+    }
+}
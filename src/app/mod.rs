@@ -0,0 +1,174 @@
+//! App shell: pages and navigation
+//!
+//! The app is split into pages, one per thing a user comes to do: solve a
+//! cube, learn, scan, time solves, look at stats, and change settings. Each
+//! has its own [`Route`] and all share the header and navigation bar of
+//! [`AppLayout`]. State the pages share lives in [`AppState`], provided by
+//! the root [`App`]; until a profile exists the root shows onboarding
+//! instead of the pages. A page that fails to render shows an error card
+//! inside the layout, so the navigation bar keeps working.
+
+mod learn_page;
+mod scan_page;
+mod settings_page;
+mod solve_page;
+mod state;
+mod stats_page;
+mod timer_page;
+
+pub use learn_page::LearnPage;
+pub use scan_page::ScanPage;
+pub use settings_page::SettingsPage;
+pub use solve_page::SolvePage;
+pub use state::{use_app_state, use_app_state_provider, AppState};
+pub use stats_page::StatsPage;
+pub use timer_page::TimerPage;
+
+use crate::components::ui_kit::token::{BORDER, FOCUS, SURFACE, SURFACE_RAISED, TEXT};
+use crate::components::{KidErrorBoundary, OnboardingWizard, ThemeProvider, ToastHost};
+use crate::camera::CalibrationProfile;
+use crate::state::UserProfile;
+use dioxus::prelude::*;
+
+/// Pages of the app
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[rustfmt::skip]
+pub enum Route {
+    #[layout(AppLayout)]
+        #[route("/", SolvePage)]
+        Solve {},
+        #[route("/learn", LearnPage)]
+        Learn {},
+        #[route("/scan", ScanPage)]
+        Scan {},
+        #[route("/timer", TimerPage)]
+        Timer {},
+        #[route("/stats", StatsPage)]
+        Stats {},
+        #[route("/settings", SettingsPage)]
+        Settings {},
+}
+
+impl Route {
+    /// Every page, in navigation bar order
+    pub const PAGES: [Route; 6] =
+        [Route::Solve {}, Route::Learn {}, Route::Scan {}, Route::Timer {}, Route::Stats {}, Route::Settings {}];
+
+    /// Name in the navigation bar
+    pub fn label(&self) -> &'static str {
+        match self {
+            Route::Solve {} => "Solve",
+            Route::Learn {} => "Learn",
+            Route::Scan {} => "Scan",
+            Route::Timer {} => "Timer",
+            Route::Stats {} => "Stats",
+            Route::Settings {} => "Settings",
+        }
+    }
+
+    /// Icon shown before the label
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Route::Solve {} => "🧩",
+            Route::Learn {} => "📚",
+            Route::Scan {} => "📷",
+            Route::Timer {} => "⏱️",
+            Route::Stats {} => "📊",
+            Route::Settings {} => "⚙️",
+        }
+    }
+}
+
+/// Root component: shared state, theme, and onboarding or the pages
+#[component]
+pub fn App() -> Element {
+    let mut state = use_app_state_provider();
+    let mut settings = state.settings;
+
+    rsx! {
+        ThemeProvider {
+            initial: settings.peek().theme,
+            on_change: move |theme| {
+                if settings.peek().theme != theme {
+                    settings.write().theme = theme;
+                }
+            },
+            // No profile yet means this is the first launch: show the setup
+            if state.profile.read().is_none() {
+                OnboardingWizard {
+                    on_finish: move |created: UserProfile| state.save_profile(created),
+                    on_calibrated: move |calibration: CalibrationProfile| state.save_calibration(calibration),
+                    on_skip: move |_| state.save_profile(UserProfile::default()),
+                }
+            } else {
+                Router::<Route> {}
+            }
+            ToastHost {}
+        }
+    }
+}
+
+/// Header and navigation bar around every page
+#[component]
+fn AppLayout() -> Element {
+    let state = use_app_state();
+    let current = use_route::<Route>();
+    let name = state.profile.read().as_ref().map(|profile| profile.name.clone()).filter(|name| !name.is_empty());
+
+    rsx! {
+        div {
+            class: "app-container",
+            style: "min-height: 100vh; display: flex; flex-direction: column; background: var(--ui-background, #f7fafc); width: 100%; max-width: 100vw; overflow-x: hidden;",
+
+            header {
+                h1 {
+                    "Rubik's Cube Solver & Tutorial"
+                }
+                p {
+                    "Educational cube solver for 2x2 to 20x20 cubes"
+                }
+                if let Some(name) = name {
+                    p { "Welcome back, {name}!" }
+                }
+                nav {
+                    "aria-label": "Pages",
+                    style: "display: flex; gap: 8px; flex-wrap: wrap;",
+                    for page in Route::PAGES {
+                        Link {
+                            key: "{page.label()}",
+                            to: page.clone(),
+                            "aria-current": if page == current { "page" } else { "false" },
+                            style: if page == current {
+                                "display: inline-flex; align-items: center; gap: 6px; min-height: 44px; padding: 0 14px; border-radius: 8px; text-decoration: none; color: {TEXT}; background: {SURFACE_RAISED}; border: 2px solid {FOCUS};"
+                            } else {
+                                "display: inline-flex; align-items: center; gap: 6px; min-height: 44px; padding: 0 14px; border-radius: 8px; text-decoration: none; color: {TEXT}; background: {SURFACE}; border: 2px solid {BORDER};"
+                            },
+                            "{page.icon()} {page.label()}"
+                        }
+                    }
+                }
+            }
+
+            main {
+                KidErrorBoundary {
+                    Outlet::<Route> {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_page_has_its_own_path() {
+        let paths: Vec<String> = Route::PAGES.iter().map(|page| page.to_string()).collect();
+        assert_eq!(paths, vec!["/", "/learn", "/scan", "/timer", "/stats", "/settings"]);
+        for page in Route::PAGES {
+            assert_eq!(page.to_string().parse::<Route>().ok(), Some(page.clone()));
+        }
+        assert_eq!(Route::Timer {}.label(), "Timer");
+    }
+}
@@ -0,0 +1,35 @@
+//! Scan page
+//!
+//! Scan each face of the cube with the camera. The finished scan becomes
+//! the cube on the Solve page, sized like the cube being worked on.
+
+use crate::app::{use_app_state, Route};
+use crate::components::ScanWorkflow;
+use crate::cube::Cube;
+use dioxus::prelude::*;
+
+/// Camera scan of a physical cube
+#[component]
+pub fn ScanPage() -> Element {
+    let mut state = use_app_state();
+    let size = state.history.read().current().size();
+
+    rsx! {
+        section {
+            style: "max-width: 800px; width: 100%;",
+            h2 {
+                "Scan Your Cube"
+            }
+            ScanWorkflow {
+                cube_size: size as u32,
+                on_complete: move |scanned: Cube| {
+                    state.load_cube(scanned, "Scanned cube");
+                    navigator().push(Route::Solve {});
+                },
+                on_cancel: move |_| {
+                    navigator().push(Route::Solve {});
+                },
+            }
+        }
+    }
+}
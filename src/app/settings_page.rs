@@ -0,0 +1,111 @@
+//! Settings page
+//!
+//! Theme, sound and vibration, and the profile made during onboarding,
+//! which can be set up again from scratch.
+
+use crate::app::use_app_state;
+use crate::components::ui_kit::token::TEXT_MUTED;
+use crate::components::{ButtonTheme, KidButton, ThemeSwitcher};
+use dioxus::prelude::*;
+
+/// Preferences and profile
+#[component]
+pub fn SettingsPage() -> Element {
+    let mut state = use_app_state();
+    let (mut settings, mut sounds, mut haptics) = (state.settings, state.sounds, state.haptics);
+    let profile = state.profile.read().clone().unwrap_or_default();
+
+    rsx! {
+        section {
+            style: "max-width: 800px; width: 100%;",
+            h2 {
+                "Settings"
+            }
+            ThemeSwitcher {}
+            label {
+                style: "display: inline-flex; align-items: center; gap: 0.5rem; min-height: 44px; cursor: pointer;",
+                input {
+                    r#type: "checkbox",
+                    checked: sounds.read().is_muted(),
+                    onchange: move |evt| {
+                        sounds.write().set_muted(evt.checked());
+                        settings.write().muted = evt.checked();
+                    },
+                }
+                "Mute sounds"
+            }
+            label {
+                style: "display: inline-flex; align-items: center; gap: 0.5rem; min-height: 44px; cursor: pointer;",
+                input {
+                    r#type: "checkbox",
+                    checked: haptics.read().is_enabled(),
+                    onchange: move |evt| {
+                        haptics.write().set_enabled(evt.checked());
+                        settings.write().haptics = evt.checked();
+                    },
+                }
+                "Vibrate on touch devices"
+            }
+        }
+
+        // What was picked during onboarding
+        section {
+            style: "max-width: 800px; width: 100%;",
+            h2 {
+                "Profile"
+            }
+            if !profile.name.is_empty() {
+                p { "Name: {profile.name}" }
+            }
+            p { "Cube: {profile.cube_size}x{profile.cube_size}" }
+            p { "Enter cubes by: {profile.input_method.label()}" }
+            p {
+                style: "color: {TEXT_MUTED};",
+                "Setting up again keeps your progress and solve times."
+            }
+            KidButton {
+                label: "Set up again".to_string(),
+                icon: "🔄".to_string(),
+                theme: ButtonTheme::Secondary,
+                onclick: move |_| state.restart_onboarding(),
+            }
+        }
+
+        // Status section
+        div {
+            class: "status-section",
+            h3 {
+                style: "color: #2d3748; font-size: 1.2rem; margin-bottom: 1rem;",
+                "Implementation Status"
+            }
+            p {
+                style: "color: var(--ui-text-muted, #718096); font-size: 0.9rem; margin: 0.5rem 0; word-wrap: break-word;",
+                "✓ WGPU rendering context ready"
+            }
+            p {
+                style: "color: var(--ui-text-muted, #718096); font-size: 0.9rem; margin: 0.5rem 0;",
+                "✓ Core cube engine (R1.1-R1.9) complete"
+            }
+            p {
+                style: "color: var(--ui-text-muted, #718096); font-size: 0.9rem; margin: 0.5rem 0;",
+                "✓ 3D visualization (R2.1-R2.8) complete"
+            }
+            p {
+                style: "color: var(--ui-text-muted, #718096); font-size: 0.9rem; margin: 0.5rem 0;",
+                "✓ Responsive sizing for all screen sizes"
+            }
+            p {
+                style: "color: #10b981; font-size: 0.9rem; margin: 0.5rem 0; font-weight: bold;",
+                "✓ 2D unfolded cube view (R3.1-R3.2) complete"
+            }
+            p {
+                style: "color: #10b981; font-size: 0.9rem; margin: 0.5rem 0; font-weight: bold;",
+                "✓ Color picker palette (R3.3) complete"
+            }
+            p {
+                style: "color: #10b981; font-size: 0.9rem; margin: 0.5rem 0; font-weight: bold;",
+                "✓ Real-time 2D/3D sync (R3.4) complete"
+            }
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Solve page
+//!
+//! Enter the cube on the flat net (painting stickers or swiping layers),
+//! check it, watch how much of it is solved, see it in 3D, and step through
+//! a solution. Undo, redo, the branching history and switching cube size
+//! are here too.
+
+use crate::app::{use_app_state, AppState};
+use crate::components::validation_feedback::get_validation_styles;
+use crate::components::{
+    ColorPicker, Cube3D, CubeControls, CubeInput, HistoryPanel, HistoryTree, KidProgress, NetAnimation,
+    SolutionPlayer, StickerPosition, ValidationFeedback,
+};
+use crate::cube::validation::FixSuggestion;
+use crate::cube::{Color, Cube, FaceName, Move, ParsedMove};
+use crate::solver::{is_f2l_solved, solve_2x2, solve_3x3, solve_last_layer};
+use crate::state::{AppEvent, HistoryLimits};
+use dioxus::prelude::*;
+
+/// Cube editor, 3D view, history and solver
+#[component]
+pub fn SolvePage() -> Element {
+    let mut state = use_app_state();
+    let AppState {
+        mut history, mut solution, mut playback_cube, mut colorblind_mode, mut settings, mut events, mut toasts, ..
+    } = state;
+
+    // Track viewport size (in real app, this would come from window resize events)
+    let viewport_width = use_signal(|| 800.0);
+    let viewport_height = use_signal(|| 600.0);
+
+    // Track selected sticker and color
+    let mut selected_sticker = use_signal(|| None::<StickerPosition>);
+    let mut selected_color = use_signal(|| None::<Color>);
+
+    // Flat view of playback, animating the last move played
+    let mut flat_playback = use_signal(|| false);
+    let mut net_animation = use_signal(|| None::<NetAnimation>);
+
+    // Layer turned by swiping on the editable net
+    let mut turn_animation = use_signal(|| None::<NetAnimation>);
+
+    // Point at the stickers to check when the entered cube is impossible
+    let validation_error = history().current().validate().err();
+    let fix_suggestions = if validation_error.is_some() { history().current().fix_suggestions() } else { Vec::new() };
+    let flagged_stickers: Vec<StickerPosition> = fix_suggestions
+        .iter()
+        .flat_map(|suggestion| suggestion.stickers.iter().map(|&(face, row, col)| StickerPosition { face, row, col }))
+        .collect();
+
+    // How much of the cube being edited (or played back) is solved
+    let progress = playback_cube().unwrap_or_else(|| history().current().clone()).progress();
+
+    rsx! {
+        // Section: 3D View
+        section {
+            h2 {
+                "3D Cube View"
+            }
+            p {
+                "Changes in the 2D view are reflected in real-time"
+            }
+            Cube3D {
+                cube: playback_cube().unwrap_or_else(|| history().current().clone()),
+                viewport_width: viewport_width(),
+                viewport_height: viewport_height(),
+            }
+        }
+
+        // Section: 2D Unfolded View with Color Picker
+        section {
+            h2 {
+                "2D Unfolded Cube View"
+            }
+
+            // Instructions
+            p {
+                "Click a sticker to select it, then click a color to apply"
+            }
+            p {
+                "Keyboard: arrow keys move between stickers, 1-6 apply a color"
+            }
+            label {
+                style: "display: inline-flex; align-items: center; gap: 0.5rem; min-height: 44px; cursor: pointer;",
+                input {
+                    r#type: "checkbox",
+                    checked: colorblind_mode(),
+                    onchange: move |evt| colorblind_mode.set(evt.checked()),
+                }
+                "Colorblind mode (letters and patterns on stickers)"
+            }
+
+            // Color Picker
+            div {
+                style: "display: flex; justify-content: center; margin-bottom: 2rem;",
+                ColorPicker {
+                    selected_color: selected_color(),
+                    colorblind_mode: colorblind_mode(),
+                    on_color_select: move |color: Color| {
+                        // Store selected color
+                        selected_color.set(Some(color));
+
+                        // If a sticker is selected, apply the color
+                        if let Some(sticker) = selected_sticker() {
+                            let mut current_cube = history().current().clone();
+                            current_cube.set_sticker(sticker.face, sticker.row, sticker.col, color);
+                            // Push new state to history
+                            let mut hist = history();
+                            hist.push(current_cube);
+                            history.set(hist);
+                        }
+                    },
+                }
+            }
+
+            // Cube Input
+            div {
+                style: "display: flex; justify-content: center;",
+                CubeInput {
+                    cube: history().current().clone(),
+                    selected_sticker: selected_sticker(),
+                    colorblind_mode: colorblind_mode(),
+                    on_sticker_select: move |position: StickerPosition| {
+                        selected_sticker.set(Some(position));
+                    },
+                    on_color_key: move |(sticker, color): (StickerPosition, Color)| {
+                        selected_color.set(Some(color));
+                        let mut current_cube = history().current().clone();
+                        current_cube.set_sticker(sticker.face, sticker.row, sticker.col, color);
+                        let mut hist = history();
+                        hist.push(current_cube);
+                        history.set(hist);
+                    },
+                    on_sticker_click: move |(face, row, col): (FaceName, usize, usize)| {
+                        // Update selected sticker
+                        selected_sticker.set(Some(StickerPosition { face, row, col }));
+
+                        // If a color is already selected, apply it
+                        if let Some(color) = selected_color() {
+                            let mut current_cube = history().current().clone();
+                            current_cube.set_sticker(face, row, col, color);
+                            // Push new state to history
+                            let mut hist = history();
+                            hist.push(current_cube);
+                            history.set(hist);
+                        }
+                    },
+                    paint_color: selected_color(),
+                    on_paint_stroke: move |stickers: Vec<StickerPosition>| {
+                        let Some(color) = selected_color() else { return };
+                        // The whole stroke is one undo step
+                        let mut current_cube = history().current().clone();
+                        for sticker in &stickers {
+                            current_cube.set_sticker(sticker.face, sticker.row, sticker.col, color);
+                        }
+                        let mut hist = history();
+                        hist.push(current_cube);
+                        history.set(hist);
+                        selected_sticker.set(stickers.last().copied());
+                    },
+                    on_face_fill: move |face: FaceName| {
+                        let Some(color) = selected_color() else { return };
+                        let mut current_cube = history().current().clone();
+                        let size = current_cube.size();
+                        for row in 0..size {
+                            for col in 0..size {
+                                current_cube.set_sticker(face, row, col, color);
+                            }
+                        }
+                        let mut hist = history();
+                        hist.push(current_cube);
+                        history.set(hist);
+                    },
+                    on_pick_color: move |color: Color| selected_color.set(Some(color)),
+                    flagged_stickers: flagged_stickers,
+                    gestures: settings().gestures,
+                    animation: turn_animation(),
+                    on_turn: move |mv: ParsedMove| {
+                        let mut hist = history();
+                        hist.apply_grouped(mv.to_notation(), |cube| cube.apply_parsed_move(&mv));
+                        history.set(hist);
+                        let id = turn_animation.peek().as_ref().map_or(0, |animation| animation.id + 1);
+                        if let ParsedMove::Basic(basic) = mv {
+                            events.write().emit(AppEvent::Turn(basic));
+                        }
+                        turn_animation.set(Some(NetAnimation { mv, id, duration_ms: 250 }));
+                    },
+                }
+            }
+
+            if let Some(progress) = progress {
+                div {
+                    style: "max-width: 500px; margin: 1rem auto 0;",
+                    KidProgress {
+                        value: progress.percent(),
+                        label: format!(
+                            "Solved {:.0}% · cross {}/4 · F2L {}/4 · next: {}",
+                            progress.percent(),
+                            progress.cross_edges,
+                            progress.f2l_slots,
+                            progress.next_stage()
+                        ),
+                    }
+                }
+            }
+
+            // What's wrong with the cube, and which stickers to check
+            if validation_error.is_some() {
+                style { {get_validation_styles()} }
+                ValidationFeedback {
+                    validation_error: validation_error,
+                    suggestions: fix_suggestions,
+                    on_suggestion_select: move |suggestion: FixSuggestion| {
+                        if let Some(&(face, row, col)) = suggestion.stickers.first() {
+                            selected_sticker.set(Some(StickerPosition { face, row, col }));
+                        }
+                        if let Some(color) = suggestion.replacement {
+                            selected_color.set(Some(color));
+                        }
+                    },
+                }
+            }
+
+            // Cube Controls (undo/redo, cube size, reset)
+            div {
+                style: "display: flex; justify-content: center; margin-top: 2rem;",
+                CubeControls {
+                    cube: history().current().clone(),
+                    on_reset: move |new_cube: Cube| {
+                        // Reset history with the new cube
+                        let mut hist = history();
+                        hist.reset(new_cube);
+                        history.set(hist);
+                        // Clear selections when resetting
+                        selected_sticker.set(None);
+                        selected_color.set(None);
+                    },
+                    can_undo: history().can_undo(),
+                    can_redo: history().can_redo(),
+                    on_undo: move || {
+                        let mut hist = history();
+                        if hist.undo().is_some() {
+                            history.set(hist);
+                        }
+                    },
+                    on_redo: move || {
+                        let mut hist = history();
+                        if hist.redo().is_some() {
+                            history.set(hist);
+                        }
+                    },
+                    on_size_change: move |new_cube: Cube| {
+                        state.change_cube_size(new_cube);
+                        selected_sticker.set(None);
+                        selected_color.set(None);
+                    },
+                }
+            }
+
+            // History panel (click an entry to restore it)
+            div {
+                style: "max-width: 400px; margin: 2rem auto 0;",
+                HistoryPanel {
+                    history: history(),
+                    on_restore: move |index: usize| {
+                        let mut hist = history();
+                        if hist.jump_to(index).is_some() {
+                            history.set(hist);
+                        }
+                    },
+                    on_limits: move |limits: HistoryLimits| {
+                        let mut hist = history();
+                        hist.set_limits(limits);
+                        history.set(hist);
+                        settings.write().history_limits = limits;
+                    },
+                }
+                // Branches left by undoing and then making a different change
+                HistoryTree {
+                    history: history(),
+                    on_select: move |id: usize| {
+                        let mut hist = history();
+                        if hist.go_to(id).is_some() {
+                            history.set(hist);
+                        }
+                    },
+                }
+            }
+        }
+
+        // Solver section
+        section {
+            style: "max-width: 800px; width: 100%;",
+            h2 {
+                "Solve the Cube"
+            }
+
+            p {
+                "Click 'Solve' to find a solution for the current cube state"
+            }
+
+            div {
+                style: "display: flex; justify-content: center; margin-bottom: 1.5rem;",
+                button {
+                    onclick: move |_| {
+                        let current_cube = history().current().clone();
+                        let cube_size = current_cube.size();
+
+                        let result = if cube_size == 2 {
+                            solve_2x2(&current_cube).map(|s| s.to_solution())
+                        } else if cube_size == 3 && is_f2l_solved(&current_cube) {
+                            // Only the last layer is left: give a short finish
+                            solve_last_layer(&current_cube)
+                        } else if cube_size == 3 {
+                            solve_3x3(&current_cube).map(|s| s.to_solution())
+                        } else {
+                            Err(format!("{0}x{0} cubes can't be solved here yet", cube_size))
+                        };
+                        let sol = toasts.report("solve the cube", result);
+
+                        playback_cube.set(None);
+                        solution.set(sol);
+                    },
+                    "Solve Cube"
+                }
+            }
+
+            if let Some(sol) = solution() {
+                SolutionPlayer {
+                    solution: sol,
+                    start: history().current().clone(),
+                    on_step: move |cube: Cube| {
+                        if cube.is_solved() {
+                            events.write().emit(AppEvent::Solved);
+                        }
+                        playback_cube.set(Some(cube));
+                    },
+                    on_turn: move |mv: Move| {
+                        let id = net_animation.peek().as_ref().map_or(0, |animation| animation.id + 1);
+                        net_animation.set(Some(NetAnimation { mv: ParsedMove::Basic(mv), id, duration_ms: 250 }));
+                        events.write().emit(AppEvent::Turn(mv));
+                    },
+                }
+                label {
+                    style: "display: inline-flex; align-items: center; gap: 0.5rem; min-height: 44px; cursor: pointer;",
+                    input {
+                        r#type: "checkbox",
+                        checked: flat_playback(),
+                        onchange: move |evt| flat_playback.set(evt.checked()),
+                    }
+                    "Show playback on the flat view"
+                }
+                if flat_playback() {
+                    div {
+                        style: "display: flex; justify-content: center; margin-top: 1rem;",
+                        CubeInput {
+                            cube: playback_cube().unwrap_or_else(|| history().current().clone()),
+                            colorblind_mode: colorblind_mode(),
+                            animation: net_animation(),
+                        }
+                    }
+                }
+            } else {
+                div {
+                    style: "text-align: center; color: var(--ui-text-muted, #718096); padding: 2rem;",
+                    p {
+                        "Click 'Solve Cube' to generate a solution"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Shared app state
+//!
+//! Everything more than one page reads or changes lives in [`AppState`]:
+//! the cube being worked on and its history, the solution, saved settings,
+//! lesson progress, timing sessions and the profile, the event bus that
+//! plays sounds and haptics, and the toasts that report problems such as a
+//! failed save. The root creates it once with
+//! [`use_app_state_provider`], which loads what was saved and saves it again
+//! whenever it changes; pages get it with [`use_app_state`].
+
+use crate::camera::CalibrationProfile;
+use crate::components::ui_kit::{use_toast_provider, Toaster};
+use crate::cube::Cube;
+use crate::input::Haptics;
+use crate::solver::Solution;
+use crate::sound::SoundEffects;
+use crate::state::{EventBus, History, Progress, UserProfile};
+use crate::storage::{self, Persistent, Settings, StorageBackend};
+use crate::timer::SessionManager;
+use dioxus::prelude::*;
+
+/// Signals shared by every page
+///
+/// Signals are `Copy`, so the container is too: copy it into as many event
+/// handlers as need it.
+#[derive(Clone, Copy)]
+pub struct AppState {
+    /// Where settings, progress, the profile and solves are saved
+    pub storage: Signal<Box<dyn StorageBackend>>,
+    /// Saved preferences
+    pub settings: Signal<Settings>,
+    /// The user's setup; `None` until onboarding is finished or skipped
+    pub profile: Signal<Option<UserProfile>>,
+    /// Cube being worked on, with undo and redo
+    pub history: Signal<History>,
+    /// Solution for the cube, once solved
+    pub solution: Signal<Option<Solution>>,
+    /// Cube at the solution player's current step
+    pub playback_cube: Signal<Option<Cube>>,
+    /// Letters and patterns on stickers for colorblind users
+    pub colorblind_mode: Signal<bool>,
+    /// Lesson progress, practice streak and goals, and starred algorithms
+    pub progress: Signal<Progress>,
+    /// Timed solves, grouped into sessions
+    pub sessions: Signal<SessionManager>,
+    /// Sound effects, played for events on the bus
+    pub sounds: Signal<SoundEffects>,
+    /// Haptic feedback, played for events on the bus
+    pub haptics: Signal<Haptics>,
+    /// App events (turns, solves, lessons) for sounds and haptics
+    pub events: Signal<EventBus>,
+    /// Success, info and error messages shown to the user
+    pub toasts: Toaster,
+}
+
+impl AppState {
+    /// Start over with a new cube, e.g. a scanned one or an algorithm's
+    /// case, forgetting the old solution
+    pub fn load_cube(&mut self, cube: Cube, label: impl Into<String>) {
+        self.history.write().push_labeled(cube, label.into());
+        self.playback_cube.set(None);
+        self.solution.set(None);
+    }
+
+    /// Save the profile from onboarding and start on its cube size
+    pub fn save_profile(&mut self, profile: UserProfile) {
+        self.toasts.report("save your profile", profile.save(&mut **self.storage.write()));
+        let limits = self.settings.peek().history_limits;
+        self.history.set(History::with_limits(Cube::new(profile.cube_size), limits));
+        self.playback_cube.set(None);
+        self.solution.set(None);
+        self.profile.set(Some(profile));
+    }
+
+    /// Start over with a solved cube of another size, remembered in the
+    /// profile so the app opens on it next time
+    pub fn change_cube_size(&mut self, cube: Cube) {
+        let limits = self.settings.peek().history_limits;
+        let mut profile = self.profile.peek().clone().unwrap_or_default();
+        profile.cube_size = cube.size();
+        self.toasts.report("save your profile", profile.save(&mut **self.storage.write()));
+        self.history.set(History::with_limits(cube, limits));
+        self.playback_cube.set(None);
+        self.solution.set(None);
+        self.profile.set(Some(profile));
+    }
+
+    /// Save a camera calibration made during onboarding
+    pub fn save_calibration(&mut self, calibration: CalibrationProfile) {
+        let saved = calibration.save(&mut **self.storage.write());
+        if self.toasts.report("save the camera calibration", saved).is_some() {
+            self.toasts.success("Camera calibrated!");
+        }
+    }
+
+    /// Forget the profile so onboarding runs again
+    pub fn restart_onboarding(&mut self) {
+        self.toasts.report("remove your profile", UserProfile::forget(&mut **self.storage.write()));
+        self.profile.set(None);
+    }
+}
+
+/// Create the app state and share it with everything below; call once, in
+/// the root component
+pub fn use_app_state_provider() -> AppState {
+    let toasts = use_toast_provider();
+    let storage = use_signal(storage::default_backend);
+    let settings = use_signal(move || Settings::load_or_default(&**storage.peek()));
+    let profile = use_signal(move || UserProfile::load(&**storage.peek()).ok().flatten());
+    let history = use_signal(move || {
+        let size = profile.peek().as_ref().map_or(3, |profile| profile.cube_size);
+        History::with_limits(Cube::new(size), settings.peek().history_limits)
+    });
+    let progress = use_signal(move || Progress::load_or_default(&**storage.peek()));
+    let sessions = use_signal(move || SessionManager::load_or_default(&**storage.peek()));
+    let mut sounds = use_signal(move || {
+        let mut sounds = SoundEffects::with_default_backend();
+        sounds.set_muted(settings.peek().muted);
+        sounds
+    });
+    let mut haptics = use_signal(move || {
+        let mut haptics = Haptics::with_default_backend();
+        haptics.set_enabled(settings.peek().haptics);
+        haptics
+    });
+    let events = use_signal(move || {
+        let mut bus = EventBus::new();
+        bus.subscribe(Box::new(move |event| sounds.write().handle(event)));
+        bus.subscribe(Box::new(move |event| haptics.write().handle(event)));
+        bus
+    });
+    let state = AppState {
+        storage,
+        settings,
+        profile,
+        history,
+        solution: use_signal(|| None),
+        playback_cube: use_signal(|| None),
+        colorblind_mode: use_signal(|| false),
+        progress,
+        sessions,
+        sounds,
+        haptics,
+        events,
+        toasts,
+    };
+
+    // Save whatever changes
+    let (mut storage, mut toasts) = (state.storage, state.toasts);
+    use_effect(move || {
+        let current = settings();
+        toasts.report("save your settings", current.save(&mut **storage.write()));
+    });
+    use_effect(move || {
+        let current = progress();
+        toasts.report("save your progress", current.save(&mut **storage.write()));
+    });
+    use_effect(move || {
+        let current = sessions();
+        toasts.report("save your solve times", current.save(&mut **storage.write()));
+    });
+
+    use_context_provider(|| state)
+}
+
+/// The app state shared by the root
+///
+/// Panics outside the app's root component, which provides it.
+pub fn use_app_state() -> AppState {
+    use_context()
+}
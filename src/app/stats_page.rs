@@ -0,0 +1,40 @@
+//! Stats page
+//!
+//! Solve times from the Timer page, session by session, and the lesson
+//! report for parents and teachers.
+
+use crate::app::use_app_state;
+use crate::components::{LessonDashboard, StatsDashboard};
+use dioxus::prelude::*;
+
+/// Timing statistics and lesson report
+#[component]
+pub fn StatsPage() -> Element {
+    let state = use_app_state();
+    let (sessions, progress) = (state.sessions, state.progress);
+
+    rsx! {
+        section {
+            style: "max-width: 800px; width: 100%;",
+            h2 {
+                "Solve Times"
+            }
+            StatsDashboard {
+                sessions: sessions.read().sessions().to_vec(),
+                selected: sessions.read().current().id.clone(),
+            }
+        }
+
+        // Time, hints and quiz scores per lesson, for parents and teachers
+        section {
+            style: "max-width: 800px; width: 100%;",
+            h2 {
+                "Lesson Report"
+            }
+            LessonDashboard {
+                lessons: progress().lesson_summaries(),
+                totals: progress().analytics_totals(),
+            }
+        }
+    }
+}
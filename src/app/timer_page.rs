@@ -0,0 +1,42 @@
+//! Timer page
+//!
+//! Timed solves on the virtual cube, at the size of the cube being worked
+//! on. Solves are recorded in that size's timing session, which the Stats
+//! page sums up.
+
+use crate::app::use_app_state;
+use crate::components::VirtualCube;
+use crate::cube::Move;
+use crate::state::{AppEvent, Replay, SolveRecord};
+use dioxus::prelude::*;
+
+/// Virtual cube with a timer
+#[component]
+pub fn TimerPage() -> Element {
+    let state = use_app_state();
+    let (mut sessions, mut events) = (state.sessions, state.events);
+    let size = state.history.read().current().size();
+    // Solves go into the session for this size, made on the first visit
+    let session_id = use_hook(move || sessions.write().switch_to_size(size, None));
+
+    rsx! {
+        section {
+            style: "max-width: 800px; width: 100%;",
+            h2 {
+                "Timer"
+            }
+            VirtualCube {
+                size: size,
+                session_id: session_id,
+                on_solve: move |(record, _replay): (SolveRecord, Replay)| {
+                    sessions.write().record(record);
+                    events.write().emit(AppEvent::Solved);
+                },
+                on_record_change: move |record: SolveRecord| {
+                    sessions.write().update(record);
+                },
+                on_turn: move |mv: Move| events.write().emit(AppEvent::Turn(mv)),
+            }
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Command-line interface for headless solving and scrambling
+//!
+//! Usage:
+//!   cube-solver-cli solve --state <facelets|json> [--optimal SECONDS] [--nodes N]
+//!   cube-solver-cli scramble [--size N] [--count K] [--length L]
+//!   cube-solver-cli sheet [--size N] [--count K] [--length L] [--title T] > sheet.html
+//!   cube-solver-cli apply --alg "R U R' U'" [--size N] [--state <facelets|json>]
+//!   cube-solver-cli optimize --alg "R U U' R"
+//!
+//! Facelet strings list stickers face by face in U R F D L B order using
+//! face letters (URFDLB) or color letters (WYROBG). A JSON state is the
+//! format written by `Cube::to_json`.
+//!
+//! `solve --optimal` looks for the shortest 3x3 solution for up to the
+//! given number of seconds (or `--nodes` search nodes) and says whether it
+//! is proven optimal.
+//!
+//! `sheet` prints an HTML page of scrambles with a net of each scrambled
+//! state, for printing handouts. Scrambles are WCA length unless `--length`
+//! is given.
+//!
+//! `optimize` shortens a 3x3 move sequence without changing what it does,
+//! listing each shortening found.
+
+use rubiks_cube_solver::cube::scramble::{generate_scramble, ScrambleConfig};
+use rubiks_cube_solver::cube::scramble_sheet::ScrambleSheet;
+use rubiks_cube_solver::cube::Cube;
+use rubiks_cube_solver::solver::{optimize_algorithm, solve_2x2, solve_3x3, solve_3x3_optimal, SearchBudget, Solution};
+use std::process::ExitCode;
+use std::time::Duration;
+
+const USAGE: &str = "\
+Usage:
+  cube-solver-cli solve --state <facelets|json> [--optimal SECONDS] [--nodes N]
+  cube-solver-cli scramble [--size N] [--count K] [--length L]
+  cube-solver-cli sheet [--size N] [--count K] [--length L] [--title T]
+  cube-solver-cli apply --alg \"R U R' U'\" [--size N] [--state <facelets|json>]
+  cube-solver-cli optimize --alg \"R U U' R\"
+  cube-solver-cli help";
+
+/// Default number of moves in a generated scramble
+const DEFAULT_SCRAMBLE_LENGTH: usize = 20;
+
+/// Default number of scrambles on a sheet
+const DEFAULT_SHEET_COUNT: usize = 10;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match run(&args) {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Run a command and return the text to print
+fn run(args: &[String]) -> Result<String, String> {
+    let (command, rest) = args.split_first().ok_or("missing command")?;
+    let options = Options::parse(rest)?;
+
+    match command.as_str() {
+        "solve" => solve(&options),
+        "scramble" => scramble(&options),
+        "sheet" => sheet(&options),
+        "apply" => apply(&options),
+        "optimize" => optimize(&options),
+        "help" | "--help" | "-h" => Ok(USAGE.to_string()),
+        other => Err(format!("unknown command '{}'", other)),
+    }
+}
+
+/// Parsed `--flag value` options
+#[derive(Debug, Default)]
+struct Options {
+    state: Option<String>,
+    alg: Option<String>,
+    size: Option<usize>,
+    count: Option<usize>,
+    length: Option<usize>,
+    title: Option<String>,
+    optimal: Option<Duration>,
+    nodes: Option<u64>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut iter = args.iter();
+
+        while let Some(flag) = iter.next() {
+            let value = iter
+                .next()
+                .ok_or_else(|| format!("missing value for {}", flag))?;
+
+            match flag.as_str() {
+                "--state" => options.state = Some(value.clone()),
+                "--alg" => options.alg = Some(value.clone()),
+                "--size" => options.size = Some(parse_number(flag, value)?),
+                "--count" => options.count = Some(parse_number(flag, value)?),
+                "--length" => options.length = Some(parse_number(flag, value)?),
+                "--title" => options.title = Some(value.clone()),
+                "--optimal" => options.optimal = Some(parse_seconds(flag, value)?),
+                "--nodes" => options.nodes = Some(parse_number(flag, value)? as u64),
+                other => return Err(format!("unknown option '{}'", other)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_number(flag: &str, value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got '{}'", flag, value))
+}
+
+fn parse_seconds(flag: &str, value: &str) -> Result<Duration, String> {
+    value
+        .parse()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or_else(|| format!("{} expects a number of seconds, got '{}'", flag, value))
+}
+
+fn check_size(size: usize) -> Result<usize, String> {
+    if (2..=20).contains(&size) {
+        Ok(size)
+    } else {
+        Err(format!("cube size must be between 2 and 20, got {}", size))
+    }
+}
+
+/// Read a cube from a facelet string or JSON state
+fn parse_state(state: &str) -> Result<Cube, String> {
+    if state.trim_start().starts_with('{') {
+        Cube::from_json(state).map_err(|e| e.to_string())
+    } else {
+        Cube::from_facelets(state).map_err(|e| e.to_string())
+    }
+}
+
+fn solve(options: &Options) -> Result<String, String> {
+    let state = options.state.as_deref().ok_or("solve requires --state")?;
+    let cube = parse_state(state)?;
+
+    if cube.is_solved() {
+        return Ok(format!("{}\n\nAlready solved!", cube.to_ascii_net()));
+    }
+
+    let mut proven = None;
+    let solution: Solution = match (cube.size(), options.optimal) {
+        (3, Some(time)) => {
+            let mut budget = SearchBudget::new(time);
+            if let Some(nodes) = options.nodes {
+                budget = budget.with_nodes(nodes);
+            }
+            let optimal = solve_3x3_optimal(&cube, &budget)?;
+            proven = Some(optimal.proven_optimal);
+            optimal.to_solution()
+        }
+        (_, Some(_)) => return Err("--optimal only solves 3x3 cubes".to_string()),
+        (2, None) => solve_2x2(&cube)?.to_solution(),
+        (3, None) => solve_3x3(&cube)?.to_solution(),
+        (n, None) => return Err(format!("solving {}x{} cubes is not supported yet", n, n)),
+    };
+
+    let mut output = format!("{}\n\n{}\n", cube.to_ascii_net(), solution.summary());
+    for (index, step) in solution.steps.iter().enumerate() {
+        output.push_str(&format!(
+            "\n{}. {}: {}",
+            index + 1,
+            step.description,
+            step.to_notation()
+        ));
+    }
+    output.push_str(&format!("\n\nSolution: {}", solution.to_notation()));
+    if let Some(proven) = proven {
+        output.push_str(&format!("\nProven optimal: {}", if proven { "yes" } else { "no" }));
+    }
+    Ok(output)
+}
+
+fn scramble(options: &Options) -> Result<String, String> {
+    let size = check_size(options.size.unwrap_or(3))?;
+    let count = options.count.unwrap_or(1);
+    let length = options.length.unwrap_or(DEFAULT_SCRAMBLE_LENGTH);
+
+    let config = ScrambleConfig::new(length, size);
+    let scrambles: Vec<String> = (1..=count)
+        .map(|i| format!("{}. {}", i, generate_scramble(&config).to_notation()))
+        .collect();
+
+    Ok(scrambles.join("\n"))
+}
+
+fn sheet(options: &Options) -> Result<String, String> {
+    let size = check_size(options.size.unwrap_or(3))?;
+    let count = options.count.unwrap_or(DEFAULT_SHEET_COUNT);
+
+    let mut config = ScrambleConfig::wca(size);
+    if let Some(length) = options.length {
+        config.length = length;
+    }
+    let mut sheet = ScrambleSheet::generate(&config, count);
+    if let Some(title) = &options.title {
+        sheet = sheet.with_title(title);
+    }
+
+    Ok(sheet.to_html())
+}
+
+fn apply(options: &Options) -> Result<String, String> {
+    let alg = options.alg.as_deref().ok_or("apply requires --alg")?;
+    let size = check_size(options.size.unwrap_or(3))?;
+
+    let mut cube = match options.state.as_deref() {
+        Some(state) => parse_state(state)?,
+        None => Cube::new(size),
+    };
+
+    cube.apply_algorithm(alg).map_err(|e| e.to_string())?;
+
+    Ok(format!(
+        "{}\n\nFacelets: {}\nSolved: {}",
+        cube.to_ascii_net(),
+        cube.to_facelets(),
+        if cube.is_solved() { "yes" } else { "no" }
+    ))
+}
+
+
+fn optimize(options: &Options) -> Result<String, String> {
+    let alg = options.alg.as_deref().ok_or("optimize requires --alg")?;
+    let optimization = optimize_algorithm(alg)?;
+    let optimized: Vec<&str> = optimization.optimized.iter().map(|m| m.to_notation()).collect();
+
+    Ok(format!("{}\n\nOptimized: {}", optimization.summary(), optimized.join(" ")))
+}
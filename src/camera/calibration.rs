@@ -4,10 +4,13 @@
 /// each of the 6 Rubik's cube colors to the camera and learning their
 /// specific HSV ranges under current lighting conditions.
 
-use crate::camera::color_detect::{ColorDetectionConfig, HSV, RGB, rgb_to_hsv};
+use crate::camera::color_detect::{ColorDetectionConfig, LabReferences, HSV, RGB, rgb_to_hsv};
 use crate::cube::Color;
 use serde::{Deserialize, Serialize};
 
+/// Samples needed before a color counts as calibrated
+pub const SAMPLES_NEEDED: usize = 10;
+
 /// Color sample collected during calibration
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ColorSample {
@@ -44,6 +47,9 @@ pub struct ColorCalibration {
     pub value_min: f32,
     /// Maximum value observed
     pub value_max: f32,
+    /// Sum of the sampled RGB values, for the average color
+    #[serde(default)]
+    pub rgb_total: [f32; 3],
 }
 
 impl ColorCalibration {
@@ -58,6 +64,7 @@ impl ColorCalibration {
             saturation_max: 0.0,
             value_min: 1.0,
             value_max: 0.0,
+            rgb_total: [0.0; 3],
         }
     }
 
@@ -73,17 +80,31 @@ impl ColorCalibration {
         self.saturation_max = self.saturation_max.max(hsv.s);
         self.value_min = self.value_min.min(hsv.v);
         self.value_max = self.value_max.max(hsv.v);
+
+        self.rgb_total[0] += sample.rgb.r as f32;
+        self.rgb_total[1] += sample.rgb.g as f32;
+        self.rgb_total[2] += sample.rgb.b as f32;
     }
 
     /// Check if this calibration has enough samples
     pub fn is_complete(&self) -> bool {
-        self.sample_count >= 10
+        self.sample_count >= SAMPLES_NEEDED
     }
 
     /// Get the midpoint hue for this color
     pub fn midpoint_hue(&self) -> f32 {
         (self.hue_min + self.hue_max) / 2.0
     }
+
+    /// Average color of the samples, if any were collected
+    pub fn mean_rgb(&self) -> Option<RGB> {
+        if self.sample_count == 0 || self.rgb_total == [0.0; 3] && self.value_max > 0.0 {
+            // No samples, or a calibration saved before averages were kept
+            return None;
+        }
+        let average = |total: f32| (total / self.sample_count as f32).round().clamp(0.0, 255.0) as u8;
+        Some(RGB::new(average(self.rgb_total[0]), average(self.rgb_total[1]), average(self.rgb_total[2])))
+    }
 }
 
 /// Calibration manager for all 6 colors
@@ -212,6 +233,19 @@ impl CalibrationManager {
         config
     }
 
+    /// Reference colors for CIELAB (ΔE2000) classification: the average
+    /// sample of each calibrated color, and the standard sticker color for
+    /// the rest
+    pub fn to_lab_references(&self) -> LabReferences {
+        let mut references = LabReferences::default();
+        for calibration in self.calibrations.iter().filter(|c| c.is_complete()) {
+            if let Some(rgb) = calibration.mean_rgb() {
+                references.set_reference(calibration.color, rgb);
+            }
+        }
+        references
+    }
+
     /// Reset calibration to start over
     pub fn reset(&mut self) {
         *self = Self::new();
@@ -344,6 +378,30 @@ mod tests {
         assert!(config.white_max_saturation < 1.0);
     }
 
+    #[test]
+    fn test_mean_rgb() {
+        let mut cal = ColorCalibration::new(Color::Orange);
+        assert_eq!(cal.mean_rgb(), None);
+        cal.add_sample(ColorSample::new(RGB::new(250, 90, 10)));
+        cal.add_sample(ColorSample::new(RGB::new(230, 80, 20)));
+        assert_eq!(cal.mean_rgb(), Some(RGB::new(240, 85, 15)));
+    }
+
+    #[test]
+    fn test_to_lab_references() {
+        let mut manager = CalibrationManager::new();
+        manager.current_color_index = 2; // Red
+        assert_eq!(manager.current_color(), Color::Red);
+        for _ in 0..10 {
+            manager.add_sample(ColorSample::new(RGB::new(200, 45, 35)));
+        }
+
+        let references = manager.to_lab_references();
+        let defaults = LabReferences::default();
+        assert_eq!(references.reference(Color::Red), Some(crate::camera::color_detect::rgb_to_lab(RGB::new(200, 45, 35))));
+        assert_eq!(references.reference(Color::Blue), defaults.reference(Color::Blue));
+    }
+
     #[test]
     fn test_serialization() {
         let manager = CalibrationManager::new();
@@ -351,6 +409,13 @@ mod tests {
         let loaded = CalibrationManager::from_json(&json).unwrap();
         assert_eq!(loaded.calibrations.len(), 6);
         assert_eq!(loaded.current_color_index, 0);
+
+        // Calibrations saved before averages were kept still load
+        let old = r#"{"color":"Red","sample_count":10,"hue_min":350.0,"hue_max":5.0,
+            "saturation_min":0.6,"saturation_max":0.9,"value_min":0.6,"value_max":0.9}"#;
+        let calibration: ColorCalibration = serde_json::from_str(old).unwrap();
+        assert!(calibration.is_complete());
+        assert_eq!(calibration.mean_rgb(), None);
     }
 
     #[test]
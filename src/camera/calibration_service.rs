@@ -0,0 +1,282 @@
+//! Calibration service
+//!
+//! Sits between the calibration wizard and the camera: it turns camera frames
+//! into [`ColorSample`]s, walks through the six colors one at a time, and keeps
+//! the finished calibration profile that color detection uses. The wizard only
+//! talks to the service, so UI code never reaches into the sampling and
+//! detection types directly.
+
+use crate::camera::calibration::{CalibrationManager, ColorCalibration, ColorSample, SAMPLES_NEEDED};
+use crate::camera::capture::CameraFrame;
+use crate::camera::color_detect::{ColorDetectionConfig, RGB};
+use crate::cube::Color;
+use serde::{Deserialize, Serialize};
+
+/// Side of the sampled square, as a fraction of the frame's shorter side
+const SAMPLE_PATCH: f32 = 0.1;
+
+/// Error types for calibration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalibrationError {
+    /// The frame has no pixels to sample
+    EmptyFrame,
+    /// Not every color has enough samples yet
+    Incomplete {
+        /// Colors with enough samples
+        completed: usize,
+    },
+}
+
+impl std::fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalibrationError::EmptyFrame => write!(f, "The camera frame is empty"),
+            CalibrationError::Incomplete { completed } => {
+                write!(f, "Only {} of 6 colors are calibrated", completed)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+/// Where the calibration wizard is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationStep {
+    /// Collecting samples of one color
+    Sampling {
+        /// Color being sampled
+        color: Color,
+        /// Position of the color in the wizard (0-5)
+        index: usize,
+        /// Samples collected so far
+        samples: usize,
+    },
+    /// Every color has enough samples
+    Done,
+}
+
+/// Saved result of a calibration, used to detect colors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationProfile {
+    /// Learned ranges for each color
+    pub calibrations: Vec<ColorCalibration>,
+}
+
+impl CalibrationProfile {
+    /// Color detection settings learned from the calibration
+    pub fn detection_config(&self) -> ColorDetectionConfig {
+        let manager = CalibrationManager { calibrations: self.calibrations.clone(), current_color_index: 0 };
+        manager.to_detection_config()
+    }
+
+    /// Color detection by nearest calibrated color in CIELAB space (ΔE2000),
+    /// which tells red and orange apart better than the HSV ranges
+    pub fn lab_detection_config(&self) -> ColorDetectionConfig {
+        let manager = CalibrationManager { calibrations: self.calibrations.clone(), current_color_index: 0 };
+        manager.to_detection_config().with_lab_references(manager.to_lab_references())
+    }
+
+    /// Save the profile to a JSON string
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Load a profile from a JSON string
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Runs a calibration and keeps the finished profile
+#[derive(Debug, Clone, Default)]
+pub struct CalibrationService {
+    manager: CalibrationManager,
+    profile: Option<CalibrationProfile>,
+}
+
+impl CalibrationService {
+    /// Create a service with no saved profile
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a service with a previously saved profile
+    pub fn with_profile(profile: CalibrationProfile) -> Self {
+        Self { manager: CalibrationManager::new(), profile: Some(profile) }
+    }
+
+    /// Samples needed for each color
+    pub fn samples_needed(&self) -> usize {
+        SAMPLES_NEEDED
+    }
+
+    /// The step the wizard should show
+    pub fn step(&self) -> CalibrationStep {
+        if self.manager.is_complete() {
+            return CalibrationStep::Done;
+        }
+        CalibrationStep::Sampling {
+            color: self.manager.current_color(),
+            index: self.manager.current_color_index,
+            samples: self.manager.current_calibration().sample_count,
+        }
+    }
+
+    /// Colors in the order the wizard asks for them, with whether each has
+    /// enough samples
+    pub fn colors(&self) -> Vec<(Color, bool)> {
+        self.manager.calibrations.iter().map(|c| (c.color, c.is_complete())).collect()
+    }
+
+    /// Number of colors with enough samples (0-6)
+    pub fn completed_colors(&self) -> usize {
+        self.manager.completed_colors()
+    }
+
+    /// Sample the middle of a camera frame for the current color
+    pub fn capture(&mut self, frame: &CameraFrame) -> Result<ColorSample, CalibrationError> {
+        let sample = sample_center(frame).ok_or(CalibrationError::EmptyFrame)?;
+        self.add_sample(sample);
+        Ok(sample)
+    }
+
+    /// Add a sample for the current color
+    pub fn add_sample(&mut self, sample: ColorSample) {
+        self.manager.add_sample(sample);
+    }
+
+    /// Move on to the next color once the current one has enough samples;
+    /// returns whether it moved
+    pub fn advance(&mut self) -> bool {
+        self.manager.current_calibration().is_complete() && self.manager.next_color()
+    }
+
+    /// Store the finished calibration as the profile
+    pub fn finish(&mut self) -> Result<&CalibrationProfile, CalibrationError> {
+        if !self.manager.is_complete() {
+            return Err(CalibrationError::Incomplete { completed: self.manager.completed_colors() });
+        }
+        let profile = CalibrationProfile { calibrations: self.manager.calibrations.clone() };
+        Ok(self.profile.insert(profile))
+    }
+
+    /// The saved profile, if a calibration was finished
+    pub fn profile(&self) -> Option<&CalibrationProfile> {
+        self.profile.as_ref()
+    }
+
+    /// Color detection settings: the saved profile's, or the defaults
+    pub fn detection_config(&self) -> ColorDetectionConfig {
+        self.profile.as_ref().map(CalibrationProfile::detection_config).unwrap_or_default()
+    }
+
+    /// Start a new calibration; the saved profile stays until it is replaced
+    pub fn restart(&mut self) {
+        self.manager.reset();
+    }
+}
+
+/// Average color of a small square in the middle of a frame, where the
+/// wizard asks for the face center to be held
+pub fn sample_center(frame: &CameraFrame) -> Option<ColorSample> {
+    let side = ((frame.width.min(frame.height) as f32 * SAMPLE_PATCH) as u32).max(1);
+    let left = frame.width.saturating_sub(side) / 2;
+    let top = frame.height.saturating_sub(side) / 2;
+
+    let mut sum = [0u64; 3];
+    let mut count = 0u64;
+    for y in top..top + side {
+        for x in left..left + side {
+            if let Some((r, g, b)) = frame.get_pixel(x, y) {
+                sum[0] += r as u64;
+                sum[1] += g as u64;
+                sum[2] += b as u64;
+                count += 1;
+            }
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+    let average = |total: u64| (total / count) as u8;
+    Some(ColorSample::new(RGB::new(average(sum[0]), average(sum[1]), average(sum[2]))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A frame of one color
+    fn frame(width: u32, height: u32, rgb: (u8, u8, u8)) -> CameraFrame {
+        let data = (0..width * height).flat_map(|_| [rgb.0, rgb.1, rgb.2]).collect();
+        CameraFrame { data, width, height, timestamp_ms: 0 }
+    }
+
+    #[test]
+    fn test_sample_center() {
+        // Red middle square on a blue frame
+        let mut red_center = frame(100, 80, (0, 0, 255));
+        for y in 30..50 {
+            for x in 40..60 {
+                let i = ((y * 100 + x) * 3) as usize;
+                red_center.data[i..i + 3].copy_from_slice(&[200, 10, 20]);
+            }
+        }
+        assert_eq!(sample_center(&red_center).unwrap().rgb, RGB::new(200, 10, 20));
+        assert_eq!(sample_center(&frame(0, 0, (0, 0, 0))), None);
+    }
+
+    #[test]
+    fn test_wizard_walks_through_colors() {
+        let mut service = CalibrationService::new();
+        let white = frame(40, 40, (240, 240, 240));
+        assert_eq!(service.step(), CalibrationStep::Sampling { color: Color::White, index: 0, samples: 0 });
+
+        assert!(!service.advance(), "Can't move on without enough samples");
+        for _ in 0..service.samples_needed() {
+            service.capture(&white).unwrap();
+        }
+        assert!(service.advance());
+        assert_eq!(service.step(), CalibrationStep::Sampling { color: Color::Yellow, index: 1, samples: 0 });
+        assert_eq!(service.colors()[0], (Color::White, true));
+        assert_eq!(service.finish().unwrap_err(), CalibrationError::Incomplete { completed: 1 });
+
+        for _ in 1..6 {
+            for _ in 0..SAMPLES_NEEDED {
+                service.capture(&white).unwrap();
+            }
+            service.advance();
+        }
+        assert_eq!(service.step(), CalibrationStep::Done);
+        assert!(service.finish().is_ok());
+        assert!(service.profile().is_some());
+
+        // Starting over keeps the saved profile until a new one is finished
+        service.restart();
+        assert_eq!(service.completed_colors(), 0);
+        assert!(service.profile().is_some());
+    }
+
+    #[test]
+    fn test_profile_round_trip() {
+        let mut service = CalibrationService::new();
+        for _ in 0..6 {
+            for _ in 0..SAMPLES_NEEDED {
+                service.add_sample(ColorSample::new(RGB::new(240, 240, 240)));
+            }
+            service.advance();
+        }
+        let json = service.finish().unwrap().to_json().unwrap();
+        let loaded = CalibrationProfile::from_json(&json).unwrap();
+        assert_eq!(loaded.calibrations.len(), 6);
+
+        let restored = CalibrationService::with_profile(loaded);
+        assert_eq!(
+            restored.detection_config().white_min_value,
+            service.detection_config().white_min_value
+        );
+        assert!(restored.detection_config().white_min_value > 0.0);
+        assert!(restored.profile().unwrap().lab_detection_config().lab_references.is_some());
+    }
+}
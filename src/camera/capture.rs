@@ -4,6 +4,8 @@
 /// - Native platforms: Uses nokhwa library
 /// - Web (WASM): Uses browser MediaDevices API (handled in UI component)
 
+use crate::camera::color_detect::{ColorDetectionConfig, ColorDetectionResult};
+
 /// Error types for camera operations
 #[derive(Debug, Clone)]
 pub enum CameraError {
@@ -17,6 +19,8 @@ pub enum CameraError {
     CaptureError(String),
     /// No cameras available on the system
     NoCamerasAvailable,
+    /// The camera in use was unplugged
+    Disconnected(String),
 }
 
 impl std::fmt::Display for CameraError {
@@ -29,14 +33,53 @@ impl std::fmt::Display for CameraError {
             }
             CameraError::CaptureError(msg) => write!(f, "Frame capture error: {}", msg),
             CameraError::NoCamerasAvailable => write!(f, "No cameras available on this device"),
+            CameraError::Disconnected(name) => write!(f, "Camera disconnected: {}", name),
         }
     }
 }
 
 impl std::error::Error for CameraError {}
 
+/// Something the user can do to get the camera working again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraRecovery {
+    /// Try the same camera again
+    Retry,
+    /// Switch to a different camera
+    ChooseAnotherCamera,
+    /// Show how to allow camera access
+    PermissionHelp,
+}
+
+impl CameraRecovery {
+    /// Button text for this recovery
+    pub fn label(&self) -> &'static str {
+        match self {
+            CameraRecovery::Retry => "Try Again",
+            CameraRecovery::ChooseAnotherCamera => "Use Another Camera",
+            CameraRecovery::PermissionHelp => "How Do I Allow the Camera?",
+        }
+    }
+}
+
+impl CameraError {
+    /// Ways to recover from this error, most helpful first
+    pub fn recovery(&self) -> Vec<CameraRecovery> {
+        match self {
+            CameraError::AccessDenied(_) => vec![CameraRecovery::PermissionHelp, CameraRecovery::Retry],
+            CameraError::DeviceBusy(_) | CameraError::CaptureError(_) => {
+                vec![CameraRecovery::Retry, CameraRecovery::ChooseAnotherCamera]
+            }
+            CameraError::InitializationFailed(_) | CameraError::Disconnected(_) => {
+                vec![CameraRecovery::ChooseAnotherCamera, CameraRecovery::Retry]
+            }
+            CameraError::NoCamerasAvailable => vec![CameraRecovery::Retry],
+        }
+    }
+}
+
 /// Represents a frame captured from the camera
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CameraFrame {
     /// Raw RGB pixel data (width * height * 3 bytes)
     pub data: Vec<u8>,
@@ -296,6 +339,21 @@ mod wasm_impl {
 #[cfg(target_arch = "wasm32")]
 pub use wasm_impl::*;
 
+impl CameraCapture {
+    /// Capture `frame_count` frames and fuse their color detections
+    ///
+    /// See [`capture_fused`](crate::camera::frame_source::capture_fused),
+    /// which does the same for any frame source.
+    pub fn capture_fused(
+        &mut self,
+        grid_size: usize,
+        frame_count: usize,
+        config: &ColorDetectionConfig,
+    ) -> Result<Vec<Vec<ColorDetectionResult>>, CameraError> {
+        crate::camera::frame_source::capture_fused(self, grid_size, frame_count, config)
+    }
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -304,6 +362,16 @@ pub use wasm_impl::*;
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_error_recovery() {
+        let denied = CameraError::AccessDenied("blocked".to_string());
+        assert_eq!(denied.recovery()[0], CameraRecovery::PermissionHelp);
+        let unplugged = CameraError::Disconnected("USB Camera".to_string());
+        assert_eq!(unplugged.recovery()[0], CameraRecovery::ChooseAnotherCamera);
+        assert_eq!(unplugged.to_string(), "Camera disconnected: USB Camera");
+        assert_eq!(CameraError::NoCamerasAvailable.recovery(), vec![CameraRecovery::Retry]);
+    }
+
     #[test]
     fn test_camera_config_default() {
         let config = CameraConfig::default();
@@ -2,7 +2,11 @@
 ///
 /// This module implements HSV-based color detection to classify pixels
 /// into one of the 6 standard Rubik's cube colors.
+/// As an alternative, pixels can be matched to the nearest reference color
+/// in CIELAB space by ΔE2000 distance, which keeps red and orange apart far
+/// better than fixed hue bands.
 use crate::cube::Color;
+use serde::{Deserialize, Serialize};
 
 /// HSV color representation (Hue, Saturation, Value)
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -33,6 +37,17 @@ impl RGB {
     }
 }
 
+/// CIELAB color representation (D65 white point)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LAB {
+    /// Lightness: 0.0-100.0
+    pub l: f32,
+    /// Green (negative) to red (positive)
+    pub a: f32,
+    /// Blue (negative) to yellow (positive)
+    pub b: f32,
+}
+
 /// Convert RGB to HSV color space
 ///
 /// # Arguments
@@ -72,6 +87,167 @@ pub fn rgb_to_hsv(rgb: RGB) -> HSV {
     HSV { h, s, v }
 }
 
+/// Convert RGB (sRGB) to CIELAB color space
+///
+/// # Arguments
+/// * `rgb` - RGB color to convert
+///
+/// # Returns
+/// CIELAB representation of the color, relative to the D65 white point
+pub fn rgb_to_lab(rgb: RGB) -> LAB {
+    // Undo the sRGB gamma curve
+    let linear = |channel: u8| {
+        let c = channel as f32 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let (r, g, b) = (linear(rgb.r), linear(rgb.g), linear(rgb.b));
+
+    // Linear RGB to XYZ, normalized by the D65 white point
+    let x = (0.4124564 * r + 0.3575761 * g + 0.1804375 * b) / 0.95047;
+    let y = 0.2126729 * r + 0.7151522 * g + 0.072175 * b;
+    let z = (0.0193339 * r + 0.119192 * g + 0.9503041 * b) / 1.08883;
+
+    let f = |t: f32| {
+        const EPSILON: f32 = 216.0 / 24389.0;
+        const KAPPA: f32 = 24389.0 / 27.0;
+        if t > EPSILON {
+            t.cbrt()
+        } else {
+            (KAPPA * t + 16.0) / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+
+    LAB { l: 116.0 * fy - 16.0, a: 500.0 * (fx - fy), b: 200.0 * (fy - fz) }
+}
+
+/// Perceptual distance between two colors (CIEDE2000 ΔE)
+///
+/// A ΔE around 1 is barely noticeable; stickers of different colors are
+/// usually well over 10 apart.
+pub fn delta_e_2000(first: LAB, second: LAB) -> f32 {
+    let (l1, a1, b1) = (first.l as f64, first.a as f64, first.b as f64);
+    let (l2, a2, b2) = (second.l as f64, second.a as f64, second.b as f64);
+    let pow25_7 = 25f64.powi(7);
+
+    // Stretch the a axis for low-chroma colors
+    let c_mean = ((a1.hypot(b1)) + (a2.hypot(b2))) / 2.0;
+    let g = 0.5 * (1.0 - (c_mean.powi(7) / (c_mean.powi(7) + pow25_7)).sqrt());
+    let (a1, a2) = ((1.0 + g) * a1, (1.0 + g) * a2);
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let hue = |a: f64, b: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 > h1 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let delta_hue = 2.0 * (c1 * c2).sqrt() * (delta_h.to_radians() / 2.0).sin();
+
+    let l_mean = (l1 + l2) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let cos = |degrees: f64| degrees.to_radians().cos();
+    let t = 1.0 - 0.17 * cos(h_mean - 30.0) + 0.24 * cos(2.0 * h_mean) + 0.32 * cos(3.0 * h_mean + 6.0)
+        - 0.20 * cos(4.0 * h_mean - 63.0);
+    let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_mean.powi(7) / (c_mean.powi(7) + pow25_7)).sqrt();
+    let s_l = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_mean;
+    let s_h = 1.0 + 0.015 * c_mean * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    let (dl, dc, dh) = (delta_l / s_l, delta_c / s_c, delta_hue / s_h);
+    (dl * dl + dc * dc + dh * dh + r_t * dc * dh).sqrt() as f32
+}
+
+/// Reference colors for nearest-color classification in CIELAB space
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabReferences {
+    /// One reference per cube color
+    references: Vec<(Color, LAB)>,
+    /// Largest ΔE at which a pixel still counts as its nearest color
+    pub max_delta_e: f32,
+}
+
+impl LabReferences {
+    /// Largest ΔE to the nearest reference by default; further away is
+    /// something other than a sticker (the cube's plastic, a hand)
+    pub const DEFAULT_MAX_DELTA_E: f32 = 25.0;
+
+    /// Create references from one RGB color per cube color
+    pub fn new(references: &[(Color, RGB)]) -> Self {
+        Self {
+            references: references.iter().map(|&(color, rgb)| (color, rgb_to_lab(rgb))).collect(),
+            max_delta_e: Self::DEFAULT_MAX_DELTA_E,
+        }
+    }
+
+    /// Replace the reference of one color, e.g. with a calibrated one
+    pub fn set_reference(&mut self, color: Color, rgb: RGB) {
+        let lab = rgb_to_lab(rgb);
+        match self.references.iter_mut().find(|(c, _)| *c == color) {
+            Some(reference) => reference.1 = lab,
+            None => self.references.push((color, lab)),
+        }
+    }
+
+    /// The reference of a color
+    pub fn reference(&self, color: Color) -> Option<LAB> {
+        self.references.iter().find(|(c, _)| *c == color).map(|&(_, lab)| lab)
+    }
+
+    /// Nearest reference color and its ΔE distance, if within `max_delta_e`
+    pub fn classify(&self, rgb: RGB) -> Option<(Color, f32)> {
+        let lab = rgb_to_lab(rgb);
+        self.references
+            .iter()
+            .map(|&(color, reference)| (color, delta_e_2000(lab, reference)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .filter(|&(_, distance)| distance <= self.max_delta_e)
+    }
+}
+
+impl Default for LabReferences {
+    /// Standard sticker colors
+    fn default() -> Self {
+        Self::new(&[
+            (Color::White, RGB::new(255, 255, 255)),
+            (Color::Yellow, RGB::new(255, 213, 0)),
+            (Color::Red, RGB::new(196, 30, 58)),
+            (Color::Orange, RGB::new(255, 88, 0)),
+            (Color::Blue, RGB::new(0, 81, 186)),
+            (Color::Green, RGB::new(0, 158, 96)),
+        ])
+    }
+}
+
 /// Color detection thresholds for each Rubik's cube color
 ///
 /// These ranges are based on standard Rubik's cube colors:
@@ -114,6 +290,9 @@ pub struct ColorDetectionConfig {
 
     /// Minimum value for all colors (to filter black/dark areas)
     pub min_value_threshold: f32,
+
+    /// Classify by nearest CIELAB reference instead of the HSV ranges above
+    pub lab_references: Option<LabReferences>,
 }
 
 impl Default for ColorDetectionConfig {
@@ -149,10 +328,21 @@ impl Default for ColorDetectionConfig {
 
             // Filter out very dark pixels
             min_value_threshold: 0.2,
+
+            // HSV ranges by default
+            lab_references: None,
         }
     }
 }
 
+impl ColorDetectionConfig {
+    /// Classify by nearest CIELAB reference (ΔE2000) instead of HSV ranges
+    pub fn with_lab_references(mut self, references: LabReferences) -> Self {
+        self.lab_references = Some(references);
+        self
+    }
+}
+
 /// Classify an RGB color into one of the 6 Rubik's cube colors
 ///
 /// # Arguments
@@ -169,6 +359,10 @@ pub fn detect_color(rgb: RGB, config: &ColorDetectionConfig) -> Option<Color> {
         return None;
     }
 
+    if let Some(references) = &config.lab_references {
+        return references.classify(rgb).map(|(color, _)| color);
+    }
+
     // Detect white (high value, low saturation)
     if hsv.v >= config.white_min_value && hsv.s <= config.white_max_saturation {
         return Some(Color::White);
@@ -225,6 +419,30 @@ pub enum LightingQuality {
     VeryPoor,
 }
 
+impl LightingQuality {
+    /// Position on a quality meter (0.0 = very poor, 1.0 = excellent)
+    pub fn score(&self) -> f32 {
+        match self {
+            LightingQuality::Excellent => 1.0,
+            LightingQuality::Good => 0.8,
+            LightingQuality::Adequate => 0.6,
+            LightingQuality::Poor => 0.35,
+            LightingQuality::VeryPoor => 0.1,
+        }
+    }
+
+    /// Short name for the quality meter
+    pub fn label(&self) -> &'static str {
+        match self {
+            LightingQuality::Excellent => "Excellent",
+            LightingQuality::Good => "Good",
+            LightingQuality::Adequate => "OK",
+            LightingQuality::Poor => "Poor",
+            LightingQuality::VeryPoor => "Very poor",
+        }
+    }
+}
+
 /// Lighting analysis result
 #[derive(Debug, Clone)]
 pub struct LightingAnalysis {
@@ -364,6 +582,76 @@ pub fn analyze_lighting(pixels: &[u8], width: u32, height: u32) -> LightingAnaly
     }
 }
 
+/// Average brightness of one region of an image
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegionBrightness {
+    /// Row of the region in the grid
+    pub row: u32,
+    /// Column of the region in the grid
+    pub col: u32,
+    /// Average brightness (0.0-1.0)
+    pub brightness: f32,
+}
+
+impl RegionBrightness {
+    /// Whether this region is too dark
+    pub fn too_dark(&self) -> bool {
+        self.brightness < 0.25
+    }
+
+    /// Whether this region is too bright (overexposed)
+    pub fn too_bright(&self) -> bool {
+        self.brightness > 0.85
+    }
+
+    /// Short hint to show over the region, if its lighting needs fixing
+    pub fn hint(&self) -> Option<&'static str> {
+        if self.too_dark() {
+            Some("Too dark")
+        } else if self.too_bright() {
+            Some("Too bright")
+        } else {
+            None
+        }
+    }
+}
+
+/// Split an image into a grid and measure the brightness of each region,
+/// so shadows and glare can be pointed out where they are
+///
+/// # Arguments
+/// * `pixels` - RGB pixel data (row-major, width * height * 3 bytes)
+/// * `width` - Image width in pixels
+/// * `height` - Image height in pixels
+/// * `grid` - Regions per row and per column
+///
+/// # Returns
+/// Brightness of each region, row by row; empty if the pixel data doesn't
+/// match the size
+pub fn analyze_lighting_regions(pixels: &[u8], width: u32, height: u32, grid: u32) -> Vec<RegionBrightness> {
+    if grid == 0 || width < grid || height < grid || pixels.len() != (width * height * 3) as usize {
+        return Vec::new();
+    }
+
+    let mut regions = Vec::with_capacity((grid * grid) as usize);
+    for row in 0..grid {
+        for col in 0..grid {
+            let (x0, x1) = (col * width / grid, (col + 1) * width / grid);
+            let (y0, y1) = (row * height / grid, (row + 1) * height / grid);
+            let mut sum = 0.0;
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    let idx = ((y * width + x) * 3) as usize;
+                    sum += rgb_to_hsv(RGB::new(pixels[idx], pixels[idx + 1], pixels[idx + 2])).v;
+                }
+            }
+            let count = ((x1 - x0) * (y1 - y0)) as f32;
+            regions.push(RegionBrightness { row, col, brightness: sum / count });
+        }
+    }
+    regions
+}
+
 /// Apply adaptive thresholds based on lighting conditions
 ///
 /// # Arguments
@@ -416,7 +704,7 @@ pub fn apply_adaptive_thresholds(
 }
 
 /// Color detection result with confidence score
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ColorDetectionResult {
     /// Detected color
     pub color: Color,
@@ -431,8 +719,44 @@ impl ColorDetectionResult {
     }
 
     /// Check if this detection is reliable (high confidence)
+    ///
+    /// Three quarters or more: a sticker that most fused frames agree on
+    /// counts as reliable even when a frame or two was blurred.
     pub fn is_reliable(&self) -> bool {
-        self.confidence >= 0.8
+        self.confidence >= 0.75
+    }
+
+    /// Confidence band, for color-coding the scan review
+    pub fn level(&self) -> ConfidenceLevel {
+        if self.is_reliable() {
+            ConfidenceLevel::High
+        } else if self.is_uncertain() {
+            ConfidenceLevel::Low
+        } else {
+            ConfidenceLevel::Medium
+        }
+    }
+}
+
+/// How sure color detection is about a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfidenceLevel {
+    /// Reliable (75% or more)
+    High,
+    /// Probably right
+    Medium,
+    /// Uncertain (below 60%); needs checking
+    Low,
+}
+
+impl ConfidenceLevel {
+    /// Border color in the scan review (green, yellow, red)
+    pub fn css_color(&self) -> &'static str {
+        match self {
+            ConfidenceLevel::High => "#22c55e",
+            ConfidenceLevel::Medium => "#eab308",
+            ConfidenceLevel::Low => "#ef4444",
+        }
     }
 }
 
@@ -568,6 +892,98 @@ pub fn detect_colors_in_grid_with_confidence(
     Some(grid)
 }
 
+/// Frames fused into one scan by default
+pub const FUSED_FRAMES: usize = 10;
+
+/// Collects per-frame detections of one face and fuses them into a single
+/// grid, so noise, motion blur and rolling-shutter tearing in a few frames
+/// are outvoted by the rest
+///
+/// Keeps the latest `capacity` frames; older ones drop out as new ones
+/// arrive.
+#[derive(Debug, Clone)]
+pub struct DetectionAccumulator {
+    grid_size: usize,
+    capacity: usize,
+    /// Detections of recent frames, oldest first (`None` = detection failed)
+    frames: std::collections::VecDeque<Option<Vec<Vec<ColorDetectionResult>>>>,
+}
+
+impl DetectionAccumulator {
+    /// Create an accumulator for an NxN face keeping `capacity` frames
+    pub fn new(grid_size: usize, capacity: usize) -> Self {
+        Self { grid_size, capacity: capacity.max(1), frames: std::collections::VecDeque::new() }
+    }
+
+    /// Detect the colors in a frame and add them
+    pub fn add_frame(&mut self, pixels: &[u8], width: u32, height: u32, config: &ColorDetectionConfig) {
+        let detection = detect_colors_in_grid_with_confidence(pixels, width, height, self.grid_size, config);
+        self.push(detection);
+    }
+
+    /// Add one frame's detection (`None` if detection failed on it)
+    pub fn push(&mut self, detection: Option<Vec<Vec<ColorDetectionResult>>>) {
+        // A grid of the wrong size can't be fused; count it as a failure
+        let detection = detection.filter(|grid| grid.len() == self.grid_size && grid.iter().all(|row| row.len() == self.grid_size));
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(detection);
+    }
+
+    /// Number of frames collected (including failed ones)
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether `capacity` frames have been collected
+    pub fn is_full(&self) -> bool {
+        self.frames.len() == self.capacity
+    }
+
+    /// Forget all frames
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Fuse the frames: each cell takes the color with the most confidence
+    /// summed over all frames
+    ///
+    /// The fused confidence is that color's share of the possible
+    /// confidence, so disagreeing or failed frames lower it. Returns `None`
+    /// if no frame was detected.
+    pub fn fuse(&self) -> Option<Vec<Vec<ColorDetectionResult>>> {
+        let detected: Vec<_> = self.frames.iter().flatten().collect();
+        if detected.is_empty() {
+            return None;
+        }
+        let total_frames = self.frames.len() as f32;
+
+        let fused = (0..self.grid_size)
+            .map(|row| {
+                (0..self.grid_size)
+                    .map(|col| {
+                        let mut weights: Vec<(Color, f32)> = Vec::new();
+                        for grid in &detected {
+                            let result = grid[row][col];
+                            match weights.iter_mut().find(|(color, _)| *color == result.color) {
+                                Some((_, weight)) => *weight += result.confidence,
+                                None => weights.push((result.color, result.confidence)),
+                            }
+                        }
+                        let (color, weight) = weights
+                            .into_iter()
+                            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                            .expect("at least one frame was detected");
+                        ColorDetectionResult { color, confidence: (weight / total_frames).clamp(0.0, 1.0) }
+                    })
+                    .collect()
+            })
+            .collect();
+        Some(fused)
+    }
+}
+
 /// Detect colors in a grid with adaptive thresholds based on lighting
 ///
 /// # Arguments
@@ -762,6 +1178,66 @@ mod tests {
         assert_eq!(detect_color(rgb, &config), None);
     }
 
+    #[test]
+    fn test_rgb_to_lab() {
+        let white = rgb_to_lab(RGB::new(255, 255, 255));
+        assert!((white.l - 100.0).abs() < 0.01 && white.a.abs() < 0.01 && white.b.abs() < 0.01);
+        let black = rgb_to_lab(RGB::new(0, 0, 0));
+        assert!(black.l.abs() < 0.01);
+        let red = rgb_to_lab(RGB::new(255, 0, 0));
+        assert!((red.l - 53.24).abs() < 0.05 && (red.a - 80.09).abs() < 0.05 && (red.b - 67.20).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_delta_e_2000() {
+        // Reference pairs from Sharma, Wu and Dalal's CIEDE2000 test data
+        let pairs = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            ((60.2574, -34.0099, 36.2677), (60.4626, -34.1751, 39.4387), 1.2644),
+        ];
+        for ((l1, a1, b1), (l2, a2, b2), expected) in pairs {
+            let (first, second) = (LAB { l: l1, a: a1, b: b1 }, LAB { l: l2, a: a2, b: b2 });
+            assert!((delta_e_2000(first, second) - expected).abs() < 1e-3, "{:?} vs {:?}", first, second);
+            assert!((delta_e_2000(second, first) - expected).abs() < 1e-3);
+        }
+        assert_eq!(delta_e_2000(LAB { l: 40.0, a: 10.0, b: 5.0 }, LAB { l: 40.0, a: 10.0, b: 5.0 }), 0.0);
+    }
+
+    #[test]
+    fn test_lab_classifier() {
+        let config = ColorDetectionConfig::default().with_lab_references(LabReferences::default());
+        for (color, rgb) in [
+            (Color::White, RGB::new(235, 240, 238)),
+            (Color::Yellow, RGB::new(240, 200, 20)),
+            (Color::Red, RGB::new(180, 35, 50)),
+            (Color::Orange, RGB::new(240, 100, 20)),
+            (Color::Blue, RGB::new(10, 75, 170)),
+            (Color::Green, RGB::new(15, 150, 90)),
+        ] {
+            assert_eq!(detect_color(rgb, &config), Some(color));
+        }
+        assert_eq!(detect_color(RGB::new(0, 0, 0), &config), None);
+        assert_eq!(detect_color(RGB::new(128, 128, 128), &config), None);
+    }
+
+    #[test]
+    fn test_lab_classifier_separates_red_and_orange() {
+        // Under warm light the red stickers look orange-ish: the hue bands
+        // call this orange-red pixel red, but it sits nearest the calibrated
+        // orange
+        let mut references = LabReferences::default();
+        references.set_reference(Color::Red, RGB::new(200, 45, 35));
+        references.set_reference(Color::Orange, RGB::new(230, 95, 30));
+        let pixel = RGB::new(225, 80, 35);
+
+        assert_eq!(detect_color(pixel, &ColorDetectionConfig::default()), Some(Color::Red));
+        let config = ColorDetectionConfig::default().with_lab_references(references.clone());
+        assert_eq!(detect_color(pixel, &config), Some(Color::Orange));
+        assert_eq!(references.classify(RGB::new(200, 45, 35)).unwrap(), (Color::Red, 0.0));
+    }
+
     #[test]
     fn test_majority_vote_single_color() {
         let colors = vec![Color::Red, Color::Red, Color::Red];
@@ -1040,4 +1516,82 @@ mod tests {
         assert_ne!(adequate, poor);
         assert_ne!(poor, very_poor);
     }
+
+    #[test]
+    fn test_lighting_quality_score() {
+        let qualities = [
+            LightingQuality::Excellent,
+            LightingQuality::Good,
+            LightingQuality::Adequate,
+            LightingQuality::Poor,
+            LightingQuality::VeryPoor,
+        ];
+        assert!(qualities.windows(2).all(|pair| pair[0].score() > pair[1].score()));
+        assert_eq!(LightingQuality::Adequate.label(), "OK");
+    }
+
+    #[test]
+    fn test_analyze_lighting_regions() {
+        // Dark left half, overexposed right half
+        let (width, height) = (40, 20);
+        let mut pixels = Vec::new();
+        for _ in 0..height {
+            for x in 0..width {
+                let v = if x < width / 2 { 20 } else { 250 };
+                pixels.extend_from_slice(&[v, v, v]);
+            }
+        }
+
+        let regions = analyze_lighting_regions(&pixels, width, height, 2);
+        assert_eq!(regions.len(), 4);
+        assert_eq!((regions[1].row, regions[1].col), (0, 1));
+        assert_eq!(regions[0].hint(), Some("Too dark"));
+        assert_eq!(regions[1].hint(), Some("Too bright"));
+        assert!(regions[2].too_dark() && regions[3].too_bright());
+
+        assert!(analyze_lighting_regions(&pixels[3..], width, height, 2).is_empty());
+    }
+
+    #[test]
+    fn test_confidence_level() {
+        let result = |confidence| ColorDetectionResult { color: Color::Red, confidence };
+        assert_eq!(result(0.95).level(), ConfidenceLevel::High);
+        assert_eq!(result(0.7).level(), ConfidenceLevel::Medium);
+        assert_eq!(result(0.4).level(), ConfidenceLevel::Low);
+        assert_eq!(ConfidenceLevel::Low.css_color(), "#ef4444");
+    }
+
+    #[test]
+    fn test_fused_detections_outvote_noise() {
+        let cell = |color, confidence| ColorDetectionResult { color, confidence };
+        let face = |center| vec![vec![cell(Color::White, 0.9), cell(center, 0.9)]; 2];
+        let mut accumulator = DetectionAccumulator::new(2, 4);
+        assert_eq!(accumulator.fuse(), None);
+
+        // Two good frames, one blurred frame confidently wrong in one cell,
+        // and one frame where detection failed
+        accumulator.push(Some(face(Color::Red)));
+        accumulator.push(Some(face(Color::Red)));
+        accumulator.push(Some(vec![vec![cell(Color::White, 0.9), cell(Color::Orange, 1.0)]; 2]));
+        accumulator.push(None);
+        assert!(accumulator.is_full());
+
+        let fused = accumulator.fuse().unwrap();
+        assert_eq!(fused[0][1].color, Color::Red);
+        assert!((fused[0][1].confidence - 0.45).abs() < 1e-6, "Disagreement lowers confidence");
+        assert_eq!(fused[0][0].color, Color::White);
+        assert!((fused[0][0].confidence - 0.675).abs() < 1e-6, "A failed frame lowers confidence");
+
+        // The oldest frames drop out
+        for _ in 0..4 {
+            accumulator.push(Some(face(Color::Blue)));
+        }
+        assert_eq!(accumulator.frame_count(), 4);
+        assert_eq!(accumulator.fuse().unwrap()[1][1].color, Color::Blue);
+
+        // Grids of the wrong size count as failed frames
+        accumulator.clear();
+        accumulator.push(Some(vec![vec![cell(Color::Red, 1.0); 3]; 3]));
+        assert_eq!(accumulator.fuse(), None);
+    }
 }
@@ -0,0 +1,108 @@
+//! Camera devices and hot-plug detection
+//!
+//! The scanner lists the cameras it can use so the user can pick one (and
+//! switch mid-session). Polling the list with a [`DeviceWatcher`] tells it
+//! when cameras are plugged in or unplugged, so it can stop a feed whose
+//! camera disappeared and offer another one.
+
+use crate::camera::capture::list_cameras;
+
+/// A camera the scanner can use
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CameraDevice {
+    /// Index to open the camera with
+    pub index: u32,
+    /// Name to show the user
+    pub name: String,
+}
+
+impl From<(u32, String)> for CameraDevice {
+    fn from((index, name): (u32, String)) -> Self {
+        Self { index, name }
+    }
+}
+
+/// Cameras currently connected
+pub fn available_cameras() -> Vec<CameraDevice> {
+    list_cameras().into_iter().map(CameraDevice::from).collect()
+}
+
+/// Cameras that appeared or disappeared since the last check
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceChanges {
+    /// Newly plugged in
+    pub added: Vec<CameraDevice>,
+    /// Unplugged
+    pub removed: Vec<CameraDevice>,
+}
+
+impl DeviceChanges {
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Keeps the last known camera list and reports changes to it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceWatcher {
+    devices: Vec<CameraDevice>,
+}
+
+impl DeviceWatcher {
+    /// Start watching from a known camera list
+    pub fn new(devices: Vec<CameraDevice>) -> Self {
+        Self { devices }
+    }
+
+    /// Replace the camera list with a fresh one; returns what changed
+    pub fn update(&mut self, devices: Vec<CameraDevice>) -> DeviceChanges {
+        let added = devices.iter().filter(|d| !self.devices.contains(d)).cloned().collect();
+        let removed = self.devices.iter().filter(|d| !devices.contains(d)).cloned().collect();
+        self.devices = devices;
+        DeviceChanges { added, removed }
+    }
+
+    /// The cameras last seen
+    pub fn devices(&self) -> &[CameraDevice] {
+        &self.devices
+    }
+
+    /// Whether a camera is still connected
+    pub fn contains(&self, index: u32) -> bool {
+        self.devices.iter().any(|d| d.index == index)
+    }
+
+    /// A connected camera other than `index`, to offer when it fails
+    pub fn alternative_to(&self, index: u32) -> Option<&CameraDevice> {
+        self.devices.iter().find(|d| d.index != index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(index: u32, name: &str) -> CameraDevice {
+        CameraDevice { index, name: name.to_string() }
+    }
+
+    #[test]
+    fn test_watcher_reports_hot_plug() {
+        let mut watcher = DeviceWatcher::new(vec![device(0, "Built-in"), device(1, "USB")]);
+        assert!(watcher.update(vec![device(0, "Built-in"), device(1, "USB")]).is_empty());
+
+        // USB camera unplugged
+        let changes = watcher.update(vec![device(0, "Built-in")]);
+        assert_eq!(changes.removed, vec![device(1, "USB")]);
+        assert!(changes.added.is_empty());
+        assert!(!watcher.contains(1));
+        assert_eq!(watcher.alternative_to(1), Some(&device(0, "Built-in")));
+        assert_eq!(watcher.alternative_to(0), None);
+
+        // And plugged back in
+        let changes = watcher.update(vec![device(0, "Built-in"), device(1, "USB")]);
+        assert_eq!(changes.added, vec![device(1, "USB")]);
+        assert_eq!(watcher.devices().len(), 2);
+    }
+}
@@ -0,0 +1,333 @@
+//! Pluggable frame sources for the camera pipeline
+//!
+//! Detection code pulls frames through the [`FrameSource`] trait instead of
+//! talking to [`CameraCapture`] directly, so the same pipeline runs on:
+//! - a real camera ([`CameraCapture`], Nokhwa on native platforms)
+//! - a still photo ([`StaticImageSource`])
+//! - generated cube faces ([`SyntheticSource`]), for headless tests
+
+use crate::camera::capture::{CameraCapture, CameraError, CameraFrame};
+use crate::camera::color_detect::{ColorDetectionConfig, ColorDetectionResult, DetectionAccumulator};
+use crate::camera::grid_detect::FaceRegion;
+use crate::camera::still_image::decode_image;
+use crate::cube::Color;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Anything that produces camera frames
+pub trait FrameSource {
+    /// Start producing frames (opens the camera stream, asking for
+    /// permission where needed)
+    fn start(&mut self) -> Result<(), CameraError>;
+
+    /// Get the next frame
+    fn next_frame(&mut self) -> Result<CameraFrame, CameraError>;
+
+    /// Stop producing frames
+    fn stop(&mut self) -> Result<(), CameraError>;
+
+    /// Whether frames are being produced
+    fn is_streaming(&self) -> bool;
+
+    /// Width and height of the frames
+    fn resolution(&self) -> (u32, u32);
+}
+
+impl FrameSource for CameraCapture {
+    fn start(&mut self) -> Result<(), CameraError> {
+        self.request_permission()
+    }
+
+    fn next_frame(&mut self) -> Result<CameraFrame, CameraError> {
+        self.capture_frame()
+    }
+
+    fn stop(&mut self) -> Result<(), CameraError> {
+        CameraCapture::stop(self)
+    }
+
+    fn is_streaming(&self) -> bool {
+        CameraCapture::is_streaming(self)
+    }
+
+    fn resolution(&self) -> (u32, u32) {
+        (self.config().width, self.config().height)
+    }
+}
+
+/// Capture `frame_count` frames from a source and fuse their color detections
+///
+/// Fusing several frames suppresses noise, motion blur and rolling-shutter
+/// artifacts that can spoil a single frame.
+pub fn capture_fused<S: FrameSource + ?Sized>(
+    source: &mut S,
+    grid_size: usize,
+    frame_count: usize,
+    config: &ColorDetectionConfig,
+) -> Result<Vec<Vec<ColorDetectionResult>>, CameraError> {
+    let mut accumulator = DetectionAccumulator::new(grid_size, frame_count);
+    for _ in 0..frame_count.max(1) {
+        let frame = source.next_frame()?;
+        accumulator.add_frame(&frame.data, frame.width, frame.height, config);
+    }
+    accumulator
+        .fuse()
+        .ok_or_else(|| CameraError::CaptureError("Could not detect the colors in any frame".to_string()))
+}
+
+/// Yields the same still frame over and over
+#[derive(Debug, Clone)]
+pub struct StaticImageSource {
+    frame: CameraFrame,
+    streaming: bool,
+}
+
+impl StaticImageSource {
+    /// Create a source from a frame
+    pub fn new(frame: CameraFrame) -> Self {
+        Self { frame, streaming: false }
+    }
+
+    /// Create a source from a PNG or JPEG file
+    pub fn from_image_bytes(bytes: &[u8]) -> Result<Self, CameraError> {
+        decode_image(bytes).map(Self::new).map_err(|e| CameraError::InitializationFailed(e.to_string()))
+    }
+}
+
+impl FrameSource for StaticImageSource {
+    fn start(&mut self) -> Result<(), CameraError> {
+        self.streaming = true;
+        Ok(())
+    }
+
+    fn next_frame(&mut self) -> Result<CameraFrame, CameraError> {
+        if !self.streaming {
+            return Err(CameraError::CaptureError("The source has not been started".to_string()));
+        }
+        Ok(self.frame.clone())
+    }
+
+    fn stop(&mut self) -> Result<(), CameraError> {
+        self.streaming = false;
+        Ok(())
+    }
+
+    fn is_streaming(&self) -> bool {
+        self.streaming
+    }
+
+    fn resolution(&self) -> (u32, u32) {
+        (self.frame.width, self.frame.height)
+    }
+}
+
+/// Renders a cube face (by default in the scanner's guide region) with
+/// optional sensor noise, for testing detection without a camera
+///
+/// Frames are generated from a seed, so runs are repeatable.
+#[derive(Debug, Clone)]
+pub struct SyntheticSource {
+    colors: Vec<Vec<Color>>,
+    width: u32,
+    height: u32,
+    /// Largest random change to each channel of each pixel (0-255)
+    pub noise: u8,
+    /// Multiplies every channel, to mimic dim (< 1) or bright (> 1) light
+    pub brightness: f32,
+    region: FaceRegion,
+    rng: StdRng,
+    frame_index: u64,
+    streaming: bool,
+}
+
+impl SyntheticSource {
+    /// Milliseconds between generated frames (30 fps)
+    const FRAME_INTERVAL_MS: u64 = 33;
+
+    /// Create a source showing a face of the given colors, row by row
+    pub fn new(colors: Vec<Vec<Color>>, width: u32, height: u32) -> Self {
+        Self {
+            colors,
+            width,
+            height,
+            noise: 0,
+            brightness: 1.0,
+            region: FaceRegion::guide(width, height),
+            rng: StdRng::seed_from_u64(0),
+            frame_index: 0,
+            streaming: false,
+        }
+    }
+
+    /// Add random noise of up to `noise` per channel, from a fixed seed
+    pub fn with_noise(mut self, noise: u8, seed: u64) -> Self {
+        self.noise = noise;
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Scale the brightness of every frame
+    pub fn with_brightness(mut self, brightness: f32) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Draw the face somewhere other than the scanner's guide region
+    pub fn with_face_region(mut self, region: FaceRegion) -> Self {
+        self.region = region;
+        self
+    }
+
+    /// Where the face is drawn
+    pub fn face_region(&self) -> FaceRegion {
+        self.region
+    }
+
+    /// Render one frame
+    fn render(&mut self) -> CameraFrame {
+        let region = self.face_region();
+        let size = self.colors.len().max(1) as f32;
+        let (cell_width, cell_height) = (region.width as f32 / size, region.height as f32 / size);
+        // Plastic between stickers, about a tenth of a sticker wide
+        let line = (cell_width.min(cell_height) / 20.0).max(1.0);
+
+        let mut data = Vec::with_capacity((self.width * self.height * 3) as usize);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let inside = x >= region.x && x < region.x + region.width && y >= region.y && y < region.y + region.height;
+                let rgb = if !inside {
+                    [110, 100, 90]
+                } else {
+                    let (fx, fy) = ((x - region.x) as f32, (y - region.y) as f32);
+                    let on_line = |p: f32, cell: f32| {
+                        let into = p % cell;
+                        into < line || into > cell - line
+                    };
+                    if on_line(fx, cell_width) || on_line(fy, cell_height) {
+                        [20, 20, 20]
+                    } else {
+                        let row = ((fy / cell_height) as usize).min(self.colors.len() - 1);
+                        let col = ((fx / cell_width) as usize).min(self.colors[row].len() - 1);
+                        sticker_rgb(self.colors[row][col])
+                    }
+                };
+                for channel in rgb {
+                    let noise = if self.noise > 0 {
+                        self.rng.gen_range(-(self.noise as f32)..=self.noise as f32)
+                    } else {
+                        0.0
+                    };
+                    data.push((channel as f32 * self.brightness + noise).round().clamp(0.0, 255.0) as u8);
+                }
+            }
+        }
+
+        let frame = CameraFrame {
+            data,
+            width: self.width,
+            height: self.height,
+            timestamp_ms: self.frame_index * Self::FRAME_INTERVAL_MS,
+        };
+        self.frame_index += 1;
+        frame
+    }
+}
+
+impl FrameSource for SyntheticSource {
+    fn start(&mut self) -> Result<(), CameraError> {
+        self.streaming = true;
+        Ok(())
+    }
+
+    fn next_frame(&mut self) -> Result<CameraFrame, CameraError> {
+        if !self.streaming {
+            return Err(CameraError::CaptureError("The source has not been started".to_string()));
+        }
+        Ok(self.render())
+    }
+
+    fn stop(&mut self) -> Result<(), CameraError> {
+        self.streaming = false;
+        Ok(())
+    }
+
+    fn is_streaming(&self) -> bool {
+        self.streaming
+    }
+
+    fn resolution(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+/// Typical sticker color under even white light
+fn sticker_rgb(color: Color) -> [u8; 3] {
+    match color {
+        Color::White => [240, 240, 240],
+        Color::Yellow => [255, 213, 0],
+        Color::Red => [196, 30, 58],
+        Color::Orange => [255, 88, 0],
+        Color::Blue => [0, 81, 186],
+        Color::Green => [0, 158, 96],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn face() -> Vec<Vec<Color>> {
+        vec![
+            vec![Color::White, Color::Red, Color::Green],
+            vec![Color::Blue, Color::Yellow, Color::Orange],
+            vec![Color::Green, Color::White, Color::Red],
+        ]
+    }
+
+    #[test]
+    fn test_sources_must_be_started() {
+        let mut source = SyntheticSource::new(face(), 64, 48);
+        assert!(source.next_frame().is_err());
+        source.start().unwrap();
+        assert!(source.is_streaming());
+        let first = source.next_frame().unwrap();
+        let second = source.next_frame().unwrap();
+        assert_eq!((first.width, first.height), source.resolution());
+        assert!(second.timestamp_ms > first.timestamp_ms);
+        source.stop().unwrap();
+        assert!(source.next_frame().is_err());
+    }
+
+    #[test]
+    fn test_synthetic_noise_is_repeatable() {
+        let frames = |seed| {
+            let mut source = SyntheticSource::new(face(), 64, 48).with_noise(20, seed);
+            source.start().unwrap();
+            source.next_frame().unwrap().data
+        };
+        assert_eq!(frames(7), frames(7));
+        assert_ne!(frames(7), frames(8));
+    }
+
+    #[test]
+    fn test_static_source_repeats_its_frame() {
+        let frame = CameraFrame { data: vec![9; 4 * 3 * 3], width: 4, height: 3, timestamp_ms: 5 };
+        let mut source = StaticImageSource::new(frame.clone());
+        source.start().unwrap();
+        assert_eq!(source.next_frame().unwrap(), frame);
+        assert_eq!(source.next_frame().unwrap(), frame);
+        assert_eq!(source.resolution(), (4, 3));
+        assert!(StaticImageSource::from_image_bytes(b"not an image").is_err());
+    }
+
+    #[test]
+    fn test_fused_capture_from_a_noisy_source() {
+        let mut source = SyntheticSource::new(face(), 200, 200)
+            .with_face_region(FaceRegion::full(200, 200))
+            .with_noise(30, 1);
+        source.start().unwrap();
+        let fused = capture_fused(&mut source, 3, 5, &ColorDetectionConfig::default()).unwrap();
+        let colors: Vec<Vec<Color>> = fused.iter().map(|row| row.iter().map(|cell| cell.color).collect()).collect();
+        assert_eq!(colors, face());
+    }
+}
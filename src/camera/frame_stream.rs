@@ -0,0 +1,252 @@
+//! Streaming camera frames into the UI
+//!
+//! Frames are captured away from the UI (on a capture thread on native
+//! platforms) and pushed through an async channel that components read with
+//! `use_coroutine`. Capturing is throttled to a maximum frame rate and can be
+//! paused while the scanner isn't on screen, so analysis never competes with
+//! rendering for more frames than it needs.
+
+use crate::camera::capture::{CameraError, CameraFrame};
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures::StreamExt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Frame rate the scanner asks for by default; plenty for lighting and color
+/// analysis
+pub const DEFAULT_MAX_FPS: u32 = 10;
+
+/// Sending end of a frame stream
+pub type FrameSender = UnboundedSender<Result<CameraFrame, CameraError>>;
+
+/// Limits how often frames are let through
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameThrottle {
+    interval_ms: u64,
+    last_ms: Option<u64>,
+}
+
+impl FrameThrottle {
+    /// Let through at most `max_fps` frames per second
+    pub fn new(max_fps: u32) -> Self {
+        Self { interval_ms: 1000 / max_fps.max(1) as u64, last_ms: None }
+    }
+
+    /// Milliseconds between frames
+    pub fn interval_ms(&self) -> u64 {
+        self.interval_ms
+    }
+
+    /// Milliseconds to wait at `now_ms` before the next frame is due
+    pub fn wait_ms(&self, now_ms: u64) -> u64 {
+        match self.last_ms {
+            Some(last) => (last + self.interval_ms).saturating_sub(now_ms),
+            None => 0,
+        }
+    }
+
+    /// Let a frame through at `now_ms` if one is due; returns whether it was
+    pub fn accept(&mut self, now_ms: u64) -> bool {
+        if self.wait_ms(now_ms) > 0 {
+            return false;
+        }
+        self.last_ms = Some(now_ms);
+        true
+    }
+}
+
+/// Pauses, resumes and stops a frame stream from the UI
+///
+/// Clones control the same stream.
+#[derive(Debug, Clone, Default)]
+pub struct FrameStreamControl {
+    paused: Arc<AtomicBool>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl FrameStreamControl {
+    /// Create a control for a running stream
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pause or resume capturing
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    /// Whether capturing is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Stop capturing for good
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the stream was stopped
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::Relaxed)
+    }
+}
+
+impl PartialEq for FrameStreamControl {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.paused, &other.paused)
+    }
+}
+
+/// Wait for the next frame, skipping any that queued up meanwhile so the
+/// reader always works on the newest one
+///
+/// Returns None once the stream has ended.
+pub async fn next_latest<T>(frames: &mut UnboundedReceiver<T>) -> Option<T> {
+    let mut latest = frames.next().await?;
+    while let Ok(newer) = frames.try_recv() {
+        latest = newer;
+    }
+    Some(latest)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native_stream {
+    use super::*;
+    use crate::camera::frame_source::FrameSource;
+    use std::time::{Duration, Instant};
+
+    /// How often a paused stream checks whether it was resumed
+    const PAUSED_POLL_MS: u64 = 50;
+
+    /// Capture frames on a background thread and send them to `frames`
+    ///
+    /// The source is opened on the capture thread (camera handles often
+    /// can't move between threads). Opening or capture errors are sent down
+    /// the stream, which then ends. The stream also ends when it is stopped
+    /// or the receiving end is dropped.
+    pub fn spawn_frame_stream<S, F>(open: F, max_fps: u32, frames: FrameSender) -> FrameStreamControl
+    where
+        S: FrameSource,
+        F: FnOnce() -> Result<S, CameraError> + Send + 'static,
+    {
+        let control = FrameStreamControl::new();
+        let thread_control = control.clone();
+        std::thread::spawn(move || run_frame_stream(open, max_fps, &thread_control, &frames));
+        control
+    }
+
+    /// Capture loop of [`spawn_frame_stream`]
+    pub(crate) fn run_frame_stream<S, F>(open: F, max_fps: u32, control: &FrameStreamControl, frames: &FrameSender)
+    where
+        S: FrameSource,
+        F: FnOnce() -> Result<S, CameraError>,
+    {
+        let mut source = match open().and_then(|mut source| source.start().map(|_| source)) {
+            Ok(source) => source,
+            Err(e) => {
+                let _ = frames.unbounded_send(Err(e));
+                return;
+            }
+        };
+
+        let started = Instant::now();
+        let mut throttle = FrameThrottle::new(max_fps);
+        while !control.is_stopped() && !frames.is_closed() {
+            if control.is_paused() {
+                std::thread::sleep(Duration::from_millis(PAUSED_POLL_MS));
+                continue;
+            }
+            let now_ms = started.elapsed().as_millis() as u64;
+            let wait = throttle.wait_ms(now_ms);
+            if wait > 0 {
+                std::thread::sleep(Duration::from_millis(wait));
+                continue;
+            }
+            throttle.accept(now_ms);
+            let frame = source.next_frame();
+            let failed = frame.is_err();
+            if frames.unbounded_send(frame).is_err() || failed {
+                break;
+            }
+        }
+        let _ = source.stop();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native_stream::spawn_frame_stream;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::frame_source::SyntheticSource;
+    use crate::cube::Color;
+    use futures::channel::mpsc::unbounded;
+
+    #[test]
+    fn test_throttle() {
+        let mut throttle = FrameThrottle::new(10);
+        assert_eq!(throttle.interval_ms(), 100);
+        assert!(throttle.accept(0), "The first frame goes through");
+        assert_eq!(throttle.wait_ms(40), 60);
+        assert!(!throttle.accept(40));
+        assert!(throttle.accept(100));
+        assert!(throttle.accept(250));
+        assert_eq!(FrameThrottle::new(0).interval_ms(), 1000);
+    }
+
+    #[test]
+    fn test_next_latest_skips_queued_frames() {
+        let (tx, mut rx) = unbounded();
+        for i in 0..5 {
+            tx.unbounded_send(i).unwrap();
+        }
+        assert_eq!(futures::executor::block_on(next_latest(&mut rx)), Some(4));
+        drop(tx);
+        assert_eq!(futures::executor::block_on(next_latest(&mut rx)), None);
+    }
+
+    #[test]
+    fn test_stream_ends_when_the_reader_goes_away() {
+        let (tx, mut rx) = unbounded();
+        let control = FrameStreamControl::new();
+        let source = || Ok(SyntheticSource::new(vec![vec![Color::Red]], 8, 8));
+        let reader = std::thread::spawn(move || {
+            let first = futures::executor::block_on(rx.next());
+            drop(rx);
+            first
+        });
+        native_stream::run_frame_stream(source, 1000, &control, &tx);
+        let first = reader.join().unwrap().expect("a frame arrived");
+        assert_eq!(first.unwrap().width, 8);
+    }
+
+    #[test]
+    fn test_open_errors_are_streamed() {
+        let (tx, mut rx) = unbounded();
+        let control = FrameStreamControl::new();
+        let open = || -> Result<SyntheticSource, CameraError> { Err(CameraError::NoCamerasAvailable) };
+        native_stream::run_frame_stream(open, 10, &control, &tx);
+        assert!(matches!(rx.try_recv(), Ok(Err(CameraError::NoCamerasAvailable))));
+    }
+
+    #[test]
+    fn test_paused_and_stopped_streams() {
+        let (tx, mut rx) = unbounded();
+        let control = FrameStreamControl::new();
+        control.set_paused(true);
+        let thread_control = control.clone();
+        let capture = std::thread::spawn(move || {
+            let source = || Ok(SyntheticSource::new(vec![vec![Color::Blue]], 8, 8));
+            native_stream::run_frame_stream(source, 1000, &thread_control, &tx);
+        });
+        std::thread::sleep(std::time::Duration::from_millis(120));
+        assert!(rx.try_recv().is_err(), "No frames while paused");
+
+        control.set_paused(false);
+        assert!(futures::executor::block_on(rx.next()).is_some());
+        control.stop();
+        capture.join().unwrap();
+        assert!(control.is_stopped());
+    }
+}
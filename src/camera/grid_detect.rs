@@ -0,0 +1,251 @@
+//! Cube size detection from the camera image
+//!
+//! Counts the sticker rows and columns of the face held up to the camera by
+//! looking for the dark plastic lines between stickers, so the scanner can
+//! set itself up for 2x2 through 7x7 without the user choosing a size.
+//!
+//! The brightness of the face is averaged into a column profile and a row
+//! profile. For each candidate size the grid lines should fall on dark
+//! dips and the sticker centers on bright stretches; the size with the
+//! clearest contrast wins. Checking the centers too keeps a 4x4 from being
+//! read as a 2x2 (whose centers would land on the 4x4's lines).
+
+use crate::camera::color_detect::{rgb_to_hsv, RGB};
+
+/// Smallest size the scanner detects
+pub const MIN_DETECTED_SIZE: u32 = 2;
+
+/// Largest size the scanner detects
+pub const MAX_DETECTED_SIZE: u32 = 7;
+
+/// Contrast below which no grid is reported
+const MIN_CONTRAST: f32 = 0.15;
+
+/// Part of the frame where the scanner asks for the face to be held
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaceRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl FaceRegion {
+    /// The centered 60% of the frame, where the scanner overlay draws its
+    /// alignment grid
+    pub fn guide(frame_width: u32, frame_height: u32) -> Self {
+        let width = (frame_width as f32 * 0.6) as u32;
+        let height = (frame_height as f32 * 0.6) as u32;
+        Self { x: (frame_width - width) / 2, y: (frame_height - height) / 2, width, height }
+    }
+
+    /// The whole frame
+    pub fn full(frame_width: u32, frame_height: u32) -> Self {
+        Self { x: 0, y: 0, width: frame_width, height: frame_height }
+    }
+}
+
+/// Detected size of the face, with how clearly it stood out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridSizeEstimate {
+    /// Stickers per row and column
+    pub size: u32,
+    /// 0.0-1.0; how far ahead of the next best size the winner was
+    pub confidence: f32,
+}
+
+/// Detect the number of sticker rows and columns of the face in a region
+///
+/// # Arguments
+/// * `pixels` - RGB pixel data (row-major, width * height * 3 bytes)
+/// * `width` - Image width in pixels
+/// * `height` - Image height in pixels
+/// * `region` - Part of the image showing the face
+///
+/// # Returns
+/// The detected size, or None if no grid stands out
+pub fn detect_grid_size(pixels: &[u8], width: u32, height: u32, region: FaceRegion) -> Option<GridSizeEstimate> {
+    let fits = region.x + region.width <= width && region.y + region.height <= height;
+    if !fits || pixels.len() != (width * height * 3) as usize {
+        return None;
+    }
+    // Each sticker needs a few pixels for its lines to show
+    if region.width < MAX_DETECTED_SIZE * 4 || region.height < MAX_DETECTED_SIZE * 4 {
+        return None;
+    }
+
+    let mut columns = vec![0.0; region.width as usize];
+    let mut rows = vec![0.0; region.height as usize];
+    for y in 0..region.height {
+        for x in 0..region.width {
+            let idx = (((region.y + y) * width + region.x + x) * 3) as usize;
+            let v = rgb_to_hsv(RGB::new(pixels[idx], pixels[idx + 1], pixels[idx + 2])).v;
+            columns[x as usize] += v / region.height as f32;
+            rows[y as usize] += v / region.width as f32;
+        }
+    }
+
+    let mut scores: Vec<(u32, f32)> = (MIN_DETECTED_SIZE..=MAX_DETECTED_SIZE)
+        .map(|n| (n, (grid_contrast(&columns, n) + grid_contrast(&rows, n)) / 2.0))
+        .collect();
+    scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    let (size, best) = scores[0];
+    if best < MIN_CONTRAST {
+        return None;
+    }
+    let runner_up = scores[1].1.max(0.0);
+    Some(GridSizeEstimate { size, confidence: ((best - runner_up) / best).clamp(0.0, 1.0) })
+}
+
+/// How much brighter the sticker centers of an n-sticker grid are than its
+/// lines, along one brightness profile
+fn grid_contrast(profile: &[f32], n: u32) -> f32 {
+    let len = profile.len() as f32;
+    let cell = len / n as f32;
+    // Allow for the face being a little off the guide
+    let reach = (cell / 6.0).max(1.0) as isize;
+    let window = |center: f32| {
+        let center = center as isize;
+        let start = (center - reach).max(0) as usize;
+        let end = ((center + reach) as usize).min(profile.len() - 1);
+        &profile[start..=end]
+    };
+
+    let lines: Vec<f32> = (1..n)
+        .map(|k| window(k as f32 * cell).iter().cloned().fold(f32::MAX, f32::min))
+        .collect();
+    let centers: Vec<f32> = (0..n)
+        .map(|k| {
+            let samples = window((k as f32 + 0.5) * cell);
+            samples.iter().sum::<f32>() / samples.len() as f32
+        })
+        .collect();
+
+    let mean = |values: &[f32]| values.iter().sum::<f32>() / values.len() as f32;
+    mean(&centers) - mean(&lines)
+}
+
+/// Settles on a size once the same size has been detected several times in
+/// a row, so one odd frame doesn't reconfigure the scanner
+#[derive(Debug, Clone, Default)]
+pub struct GridSizeTracker {
+    candidate: Option<u32>,
+    streak: u32,
+    confirmed: Option<u32>,
+}
+
+impl GridSizeTracker {
+    /// Detections in a row needed to settle on a size
+    pub const AGREEMENT: u32 = 3;
+
+    /// Create a tracker with no size yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a detection; returns the size when it becomes settled
+    pub fn observe(&mut self, estimate: Option<GridSizeEstimate>) -> Option<u32> {
+        let Some(estimate) = estimate else {
+            self.candidate = None;
+            self.streak = 0;
+            return None;
+        };
+        if self.candidate == Some(estimate.size) {
+            self.streak += 1;
+        } else {
+            self.candidate = Some(estimate.size);
+            self.streak = 1;
+        }
+        if self.streak >= Self::AGREEMENT && self.confirmed != Some(estimate.size) {
+            self.confirmed = Some(estimate.size);
+            return self.confirmed;
+        }
+        None
+    }
+
+    /// The settled size, if any
+    pub fn size(&self) -> Option<u32> {
+        self.confirmed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A face of n stickers with dark lines between them, on a gray frame
+    fn face_image(n: u32, face: u32, frame: u32) -> Vec<u8> {
+        let colors = [[255, 255, 255], [255, 213, 0], [196, 30, 58], [255, 88, 0], [0, 81, 186], [0, 158, 96]];
+        let offset = (frame - face) / 2;
+        let cell = face as f32 / n as f32;
+        let mut pixels = Vec::with_capacity((frame * frame * 3) as usize);
+        for y in 0..frame {
+            for x in 0..frame {
+                let inside = (offset..offset + face).contains(&x) && (offset..offset + face).contains(&y);
+                let rgb = if !inside {
+                    [90, 90, 90]
+                } else {
+                    let (fx, fy) = ((x - offset) as f32, (y - offset) as f32);
+                    let on_line = |p: f32| {
+                        let into = p % cell;
+                        into < 1.5 || into > cell - 1.5
+                    };
+                    if on_line(fx) || on_line(fy) {
+                        [15, 15, 15]
+                    } else {
+                        let (col, row) = ((fx / cell) as usize, (fy / cell) as usize);
+                        colors[(row * 7 + col * 3) % colors.len()]
+                    }
+                };
+                pixels.extend_from_slice(&rgb);
+            }
+        }
+        pixels
+    }
+
+    #[test]
+    fn test_detects_sizes_2_to_7() {
+        for n in MIN_DETECTED_SIZE..=MAX_DETECTED_SIZE {
+            let pixels = face_image(n, 168, 200);
+            let region = FaceRegion { x: 16, y: 16, width: 168, height: 168 };
+            let estimate = detect_grid_size(&pixels, 200, 200, region).expect("grid found");
+            assert_eq!(estimate.size, n, "{n}x{n} face");
+            assert!(estimate.confidence > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_no_grid_in_a_plain_image() {
+        let pixels = vec![128; 100 * 100 * 3];
+        assert_eq!(detect_grid_size(&pixels, 100, 100, FaceRegion::full(100, 100)), None);
+        // Region outside the image
+        assert_eq!(detect_grid_size(&pixels, 100, 100, FaceRegion { x: 50, y: 0, width: 80, height: 80 }), None);
+    }
+
+    #[test]
+    fn test_guide_region() {
+        let region = FaceRegion::guide(640, 480);
+        assert_eq!((region.width, region.height), (384, 288));
+        assert_eq!((region.x, region.y), (128, 96));
+    }
+
+    #[test]
+    fn test_tracker_needs_agreement() {
+        let estimate = |size| Some(GridSizeEstimate { size, confidence: 0.5 });
+        let mut tracker = GridSizeTracker::new();
+        assert_eq!(tracker.observe(estimate(4)), None);
+        assert_eq!(tracker.observe(estimate(4)), None);
+        assert_eq!(tracker.observe(estimate(4)), Some(4));
+        assert_eq!(tracker.observe(estimate(4)), None, "Reported once");
+
+        // One odd frame doesn't change the size
+        assert_eq!(tracker.observe(estimate(2)), None);
+        assert_eq!(tracker.observe(None), None);
+        assert_eq!(tracker.size(), Some(4));
+        for _ in 0..2 {
+            assert_eq!(tracker.observe(estimate(5)), None);
+        }
+        assert_eq!(tracker.observe(estimate(5)), Some(5));
+    }
+}
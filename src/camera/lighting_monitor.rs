@@ -0,0 +1,103 @@
+//! Live lighting feedback
+//!
+//! The camera scanner feeds every frame it grabs to a [`LightingMonitor`],
+//! which re-runs the lighting analysis every few frames: often enough to
+//! react while the user moves, without analyzing every single frame.
+
+use crate::camera::capture::CameraFrame;
+use crate::camera::color_detect::{analyze_lighting, analyze_lighting_regions, LightingAnalysis, RegionBrightness};
+
+/// Regions per row and column for brightness hints
+pub const LIGHTING_GRID: u32 = 3;
+
+/// Keeps the latest lighting analysis of a camera feed
+#[derive(Debug, Clone)]
+pub struct LightingMonitor {
+    /// Frames between analyses
+    every: u32,
+    /// Frames seen since the last analysis
+    since_analysis: u32,
+    analysis: Option<LightingAnalysis>,
+    regions: Vec<RegionBrightness>,
+}
+
+impl LightingMonitor {
+    /// Create a monitor that analyzes one frame in every `every`
+    pub fn new(every: u32) -> Self {
+        Self { every: every.max(1), since_analysis: 0, analysis: None, regions: Vec::new() }
+    }
+
+    /// Look at a new frame; the first frame and then every `every`th frame
+    /// is analyzed. Returns whether the analysis was updated.
+    pub fn observe(&mut self, frame: &CameraFrame) -> bool {
+        let due = self.analysis.is_none() || self.since_analysis + 1 >= self.every;
+        if !due {
+            self.since_analysis += 1;
+            return false;
+        }
+        self.since_analysis = 0;
+        self.analysis = Some(analyze_lighting(&frame.data, frame.width, frame.height));
+        self.regions = analyze_lighting_regions(&frame.data, frame.width, frame.height, LIGHTING_GRID);
+        true
+    }
+
+    /// Latest analysis, once a frame has been analyzed
+    pub fn analysis(&self) -> Option<&LightingAnalysis> {
+        self.analysis.as_ref()
+    }
+
+    /// Brightness of each region of the latest analyzed frame, row by row
+    pub fn regions(&self) -> &[RegionBrightness] {
+        &self.regions
+    }
+
+    /// Warning for the latest analysis, if the lighting needs fixing
+    pub fn warning(&self) -> Option<String> {
+        self.analysis.as_ref().and_then(LightingAnalysis::get_warning_message)
+    }
+
+    /// Forget the analysis, e.g. when the camera stops
+    pub fn reset(&mut self) {
+        *self = Self::new(self.every);
+    }
+}
+
+impl Default for LightingMonitor {
+    fn default() -> Self {
+        Self::new(5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::color_detect::LightingQuality;
+
+    /// A frame of one gray level
+    fn frame(level: u8) -> CameraFrame {
+        CameraFrame { data: vec![level; 12 * 9 * 3], width: 12, height: 9, timestamp_ms: 0 }
+    }
+
+    #[test]
+    fn test_analyzes_every_few_frames() {
+        let mut monitor = LightingMonitor::new(3);
+        assert!(monitor.analysis().is_none());
+
+        assert!(monitor.observe(&frame(20)), "The first frame is analyzed right away");
+        assert!(monitor.analysis().unwrap().too_dark);
+        assert!(monitor.warning().is_some());
+        assert_eq!(monitor.regions().len(), 9);
+        assert!(monitor.regions().iter().all(|region| region.hint() == Some("Too dark")));
+
+        // Frames in between are skipped
+        assert!(!monitor.observe(&frame(140)));
+        assert!(!monitor.observe(&frame(140)));
+        assert!(monitor.analysis().unwrap().too_dark);
+        assert!(monitor.observe(&frame(140)));
+        assert_eq!(monitor.analysis().unwrap().quality, LightingQuality::Excellent);
+        assert_eq!(monitor.warning(), None);
+
+        monitor.reset();
+        assert!(monitor.analysis().is_none() && monitor.regions().is_empty());
+    }
+}
@@ -6,16 +6,59 @@
 /// - Permission handling
 /// - Frame capture for cube scanning
 /// - HSV color detection for cube face scanning
+/// - CIELAB (ΔE2000) nearest-color classification as an alternative
 /// - Color calibration for improved detection accuracy
+/// - A calibration service that the calibration wizard talks to
+/// - Live lighting feedback while scanning
+/// - Cube size detection from the grid lines of the face
+/// - Perspective correction for faces held at an angle
+/// - Scanning from still PNG/JPEG photos
+/// - A frame source trait so the pipeline runs on cameras, photos or
+///   generated test frames
+/// - Throttled, pausable async frame streaming into the UI
+/// - Camera selection and hot-plug detection
+/// - Opt-in debug captures of scans (frame, grid and colors per face)
+/// - Repairing unsolvable scans by recoloring the least confident stickers
 
 pub mod calibration;
+pub mod calibration_service;
 pub mod capture;
 pub mod color_detect;
+pub mod devices;
+pub mod frame_source;
+pub mod frame_stream;
+pub mod grid_detect;
+pub mod lighting_monitor;
+pub mod perspective;
+pub mod scan_debug;
+pub mod scan_repair;
+pub mod still_image;
 
-pub use calibration::{CalibrationManager, ColorCalibration, ColorSample};
-pub use capture::{CameraCapture, CameraConfig, CameraError, CameraFrame, list_cameras};
+pub use calibration::{CalibrationManager, ColorCalibration, ColorSample, SAMPLES_NEEDED};
+pub use calibration_service::{
+    sample_center, CalibrationError, CalibrationProfile, CalibrationService, CalibrationStep,
+};
+pub use capture::{CameraCapture, CameraConfig, CameraError, CameraFrame, CameraRecovery, list_cameras};
 pub use color_detect::{
-    detect_color, detect_colors_in_grid, detect_colors_in_grid_with_confidence,
-    detect_colors_with_lighting_adaptation, rgb_to_hsv, ColorDetectionConfig,
-    ColorDetectionResult, HSV, LightingAnalysis, LightingQuality, RGB,
+    analyze_lighting, analyze_lighting_regions, delta_e_2000, detect_color, detect_colors_in_grid,
+    detect_colors_in_grid_with_confidence, detect_colors_with_lighting_adaptation, rgb_to_hsv, rgb_to_lab,
+    ColorDetectionConfig, ColorDetectionResult, ConfidenceLevel, DetectionAccumulator, FUSED_FRAMES, HSV, LAB, LabReferences, LightingAnalysis, LightingQuality,
+    RegionBrightness, RGB,
+};
+pub use devices::{available_cameras, CameraDevice, DeviceChanges, DeviceWatcher};
+pub use frame_source::{capture_fused, FrameSource, StaticImageSource, SyntheticSource};
+#[cfg(not(target_arch = "wasm32"))]
+pub use frame_stream::spawn_frame_stream;
+pub use frame_stream::{next_latest, FrameSender, FrameStreamControl, FrameThrottle, DEFAULT_MAX_FPS};
+pub use grid_detect::{
+    detect_grid_size, FaceRegion, GridSizeEstimate, GridSizeTracker, MAX_DETECTED_SIZE, MIN_DETECTED_SIZE,
+};
+pub use lighting_monitor::{LightingMonitor, LIGHTING_GRID};
+pub use perspective::{detect_colors_in_quad, warp_to_square, FaceQuad, Homography, FACE_WARP_SIZE};
+#[cfg(not(target_arch = "wasm32"))]
+pub use scan_debug::default_debug_dir;
+pub use scan_debug::{ScanDebugInfo, ScanDebugRecord, ScanDebugSession, SESSION_FILE};
+pub use scan_repair::{
+    face_confidence, repair_scan, ScanRepair, StickerConfidence, StickerRepair, MAX_REPAIR_CHANGES, REPAIR_CONFIDENCE,
 };
+pub use still_image::{decode_image, scan_frame, scan_image, ImageScan, ImageScanError, MAX_IMAGE_SIDE};
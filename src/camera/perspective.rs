@@ -0,0 +1,276 @@
+//! Perspective correction for tilted faces
+//!
+//! A face held at an angle shows up in the image as a general quadrilateral
+//! rather than a square, so sampling it on an even grid drifts off the
+//! stickers toward the far edge. A homography (projective transform) maps
+//! the face's four corners onto a square; warping the face through it gives
+//! an upright, evenly spaced face to sample.
+
+use crate::camera::color_detect::{detect_colors_in_grid_with_confidence, ColorDetectionConfig, ColorDetectionResult};
+use crate::camera::grid_detect::FaceRegion;
+use serde::{Deserialize, Serialize};
+
+/// Side of the square a face is warped to; divisible by every size from 2 to 7
+pub const FACE_WARP_SIZE: u32 = 420;
+
+/// The four corners of a face in the image, clockwise from the top-left
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FaceQuad {
+    /// Top-left, top-right, bottom-right, bottom-left, in pixels
+    pub corners: [(f32, f32); 4],
+}
+
+impl FaceQuad {
+    /// Create a quad from its corners, clockwise from the top-left
+    pub fn new(top_left: (f32, f32), top_right: (f32, f32), bottom_right: (f32, f32), bottom_left: (f32, f32)) -> Self {
+        Self { corners: [top_left, top_right, bottom_right, bottom_left] }
+    }
+
+    /// The quad of an upright rectangular region
+    pub fn from_region(region: FaceRegion) -> Self {
+        let (left, top) = (region.x as f32, region.y as f32);
+        let (right, bottom) = (left + region.width as f32, top + region.height as f32);
+        Self::new((left, top), (right, top), (right, bottom), (left, bottom))
+    }
+
+    /// Whether the corners form a convex quadrilateral, the only shape a
+    /// square face can project to
+    pub fn is_convex(&self) -> bool {
+        let turns: Vec<f32> = (0..4)
+            .map(|i| {
+                let (a, b, c) = (self.corners[i], self.corners[(i + 1) % 4], self.corners[(i + 2) % 4]);
+                (b.0 - a.0) * (c.1 - b.1) - (b.1 - a.1) * (c.0 - b.0)
+            })
+            .collect();
+        turns.iter().all(|&t| t > 0.0) || turns.iter().all(|&t| t < 0.0)
+    }
+}
+
+/// A projective transform of the plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Homography {
+    /// Row-major 3x3 matrix
+    m: [f64; 9],
+}
+
+impl Homography {
+    /// Estimate the homography mapping each `from` point onto the matching
+    /// `to` point, or None if the `from` points are degenerate
+    pub fn from_points(from: [(f32, f32); 4], to: [(f32, f32); 4]) -> Option<Self> {
+        // Two equations per correspondence in the eight unknowns h0..h7
+        // (h8 is fixed at 1)
+        let mut system = [[0.0f64; 9]; 8];
+        for (i, (&(x, y), &(u, v))) in from.iter().zip(to.iter()).enumerate() {
+            let (x, y, u, v) = (x as f64, y as f64, u as f64, v as f64);
+            system[2 * i] = [x, y, 1.0, 0.0, 0.0, 0.0, -u * x, -u * y, u];
+            system[2 * i + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -v * x, -v * y, v];
+        }
+        let h = solve(system)?;
+        Some(Self { m: [h[0], h[1], h[2], h[3], h[4], h[5], h[6], h[7], 1.0] })
+    }
+
+    /// The homography taking a `side` x `side` square onto a quad
+    pub fn square_to_quad(side: f32, quad: &FaceQuad) -> Option<Self> {
+        Self::from_points([(0.0, 0.0), (side, 0.0), (side, side), (0.0, side)], quad.corners)
+    }
+
+    /// Map a point; None if it maps to infinity
+    pub fn apply(&self, x: f32, y: f32) -> Option<(f32, f32)> {
+        let (x, y) = (x as f64, y as f64);
+        let m = &self.m;
+        let w = m[6] * x + m[7] * y + m[8];
+        if w.abs() < 1e-12 {
+            return None;
+        }
+        Some((((m[0] * x + m[1] * y + m[2]) / w) as f32, ((m[3] * x + m[4] * y + m[5]) / w) as f32))
+    }
+}
+
+/// Solve an 8x8 linear system given as augmented rows, by Gaussian
+/// elimination with partial pivoting
+fn solve(mut rows: [[f64; 9]; 8]) -> Option<[f64; 8]> {
+    for col in 0..8 {
+        let pivot = (col..8).max_by(|&a, &b| rows[a][col].abs().total_cmp(&rows[b][col].abs()))?;
+        if rows[pivot][col].abs() < 1e-9 {
+            return None;
+        }
+        rows.swap(col, pivot);
+        let pivot_row = rows[col];
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i != col {
+                let factor = row[col] / pivot_row[col];
+                for (value, pivot_value) in row.iter_mut().zip(pivot_row.iter()).skip(col) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+    }
+    let mut solution = [0.0; 8];
+    for (i, value) in solution.iter_mut().enumerate() {
+        *value = rows[i][8] / rows[i][i];
+    }
+    Some(solution)
+}
+
+/// Warp the face inside a quad to an upright `side` x `side` square
+///
+/// # Arguments
+/// * `pixels` - RGB pixel data (row-major, width * height * 3 bytes)
+/// * `width` - Image width in pixels
+/// * `height` - Image height in pixels
+/// * `quad` - Corners of the face in the image
+/// * `side` - Side of the warped square in pixels
+///
+/// # Returns
+/// RGB pixel data of the square (side * side * 3 bytes), or None if the
+/// quad isn't a usable face outline
+pub fn warp_to_square(pixels: &[u8], width: u32, height: u32, quad: &FaceQuad, side: u32) -> Option<Vec<u8>> {
+    if pixels.len() != (width * height * 3) as usize || width == 0 || height == 0 || side == 0 || !quad.is_convex() {
+        return None;
+    }
+    let homography = Homography::square_to_quad(side as f32, quad)?;
+
+    let mut warped = Vec::with_capacity((side * side * 3) as usize);
+    for y in 0..side {
+        for x in 0..side {
+            // Sample the middle of each output pixel
+            let (sx, sy) = homography.apply(x as f32 + 0.5, y as f32 + 0.5)?;
+            warped.extend_from_slice(&sample_bilinear(pixels, width, height, sx - 0.5, sy - 0.5));
+        }
+    }
+    Some(warped)
+}
+
+/// Color at a fractional position, blending the four nearest pixels; edges
+/// are extended past the image border
+fn sample_bilinear(pixels: &[u8], width: u32, height: u32, x: f32, y: f32) -> [u8; 3] {
+    let x = x.clamp(0.0, (width - 1) as f32);
+    let y = y.clamp(0.0, (height - 1) as f32);
+    let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+
+    let pixel = |px: u32, py: u32, channel: usize| pixels[((py * width + px) * 3) as usize + channel] as f32;
+    let mut rgb = [0u8; 3];
+    for (channel, value) in rgb.iter_mut().enumerate() {
+        let top = pixel(x0, y0, channel) * (1.0 - fx) + pixel(x1, y0, channel) * fx;
+        let bottom = pixel(x0, y1, channel) * (1.0 - fx) + pixel(x1, y1, channel) * fx;
+        *value = (top * (1.0 - fy) + bottom * fy).round() as u8;
+    }
+    rgb
+}
+
+/// Detect the colors of a tilted face: warp it upright, then sample its grid
+///
+/// # Arguments
+/// * `pixels` - RGB pixel data (row-major, width * height * 3 bytes)
+/// * `width` - Image width in pixels
+/// * `height` - Image height in pixels
+/// * `quad` - Corners of the face in the image
+/// * `grid_size` - NxN grid size (e.g., 3 for 3x3 cube)
+/// * `config` - Detection configuration
+///
+/// # Returns
+/// NxN grid of color detection results with confidence scores, or None if detection fails
+pub fn detect_colors_in_quad(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    quad: &FaceQuad,
+    grid_size: usize,
+    config: &ColorDetectionConfig,
+) -> Option<Vec<Vec<ColorDetectionResult>>> {
+    let warped = warp_to_square(pixels, width, height, quad, FACE_WARP_SIZE)?;
+    detect_colors_in_grid_with_confidence(&warped, FACE_WARP_SIZE, FACE_WARP_SIZE, grid_size, config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Color;
+
+    fn assert_near(actual: (f32, f32), expected: (f32, f32)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-3 && (actual.1 - expected.1).abs() < 1e-3,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    fn tilted() -> FaceQuad {
+        FaceQuad::new((40.0, 30.0), (170.0, 55.0), (160.0, 180.0), (25.0, 160.0))
+    }
+
+    #[test]
+    fn test_homography_maps_corners() {
+        let quad = tilted();
+        let homography = Homography::square_to_quad(100.0, &quad).unwrap();
+        let square = [(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)];
+        for (corner, expected) in square.iter().zip(quad.corners.iter()) {
+            assert_near(homography.apply(corner.0, corner.1).unwrap(), *expected);
+        }
+
+        // Straight lines stay straight: the square's middle lands on the
+        // crossing of the quad's diagonals, not the average of its corners
+        let (cx, cy) = homography.apply(50.0, 50.0).unwrap();
+        let [a, _, c, _] = quad.corners;
+        let [_, b, _, d] = quad.corners;
+        let along_ac = (cx - a.0) * (c.1 - a.1) - (cy - a.1) * (c.0 - a.0);
+        let along_bd = (cx - b.0) * (d.1 - b.1) - (cy - b.1) * (d.0 - b.0);
+        assert!(along_ac.abs() < 0.1 && along_bd.abs() < 0.1);
+    }
+
+    #[test]
+    fn test_degenerate_quads() {
+        let square = [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+        let collinear = [(0.0, 0.0), (10.0, 0.0), (20.0, 0.0), (30.0, 0.0)];
+        assert!(Homography::from_points(collinear, square).is_none());
+        let flattened = FaceQuad::new((0.0, 0.0), (10.0, 0.0), (20.0, 0.0), (0.0, 10.0));
+        assert!(!flattened.is_convex());
+
+        let bowtie = FaceQuad::new((0.0, 0.0), (10.0, 10.0), (10.0, 0.0), (0.0, 10.0));
+        assert!(!bowtie.is_convex());
+        assert!(tilted().is_convex());
+        assert!(FaceQuad::from_region(FaceRegion { x: 5, y: 5, width: 20, height: 10 }).is_convex());
+        assert_eq!(warp_to_square(&[0; 30 * 30 * 3], 30, 30, &bowtie, 10), None);
+    }
+
+    #[test]
+    fn test_reads_a_tilted_face() {
+        // Draw a 3x3 face seen at an angle
+        let colors = [
+            [Color::White, Color::Red, Color::Blue],
+            [Color::Green, Color::Yellow, Color::Orange],
+            [Color::Blue, Color::White, Color::Red],
+        ];
+        let rgb = |color: Color| match color {
+            Color::White => [240, 240, 240],
+            Color::Yellow => [255, 213, 0],
+            Color::Red => [196, 30, 58],
+            Color::Orange => [255, 88, 0],
+            Color::Blue => [0, 81, 186],
+            Color::Green => [0, 158, 96],
+        };
+        let quad = tilted();
+        let to_face = Homography::from_points(quad.corners, [(0.0, 0.0), (3.0, 0.0), (3.0, 3.0), (0.0, 3.0)]).unwrap();
+        let (width, height) = (200, 200);
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        for y in 0..height {
+            for x in 0..width {
+                let (u, v) = to_face.apply(x as f32 + 0.5, y as f32 + 0.5).unwrap();
+                let pixel = if (0.0..3.0).contains(&u) && (0.0..3.0).contains(&v) {
+                    rgb(colors[v as usize][u as usize])
+                } else {
+                    [60, 60, 60]
+                };
+                pixels.extend_from_slice(&pixel);
+            }
+        }
+
+        let config = ColorDetectionConfig::default();
+        let grid = detect_colors_in_quad(&pixels, width as u32, height as u32, &quad, 3, &config).unwrap();
+        let read: Vec<Vec<Color>> = grid.iter().map(|row| row.iter().map(|cell| cell.color).collect()).collect();
+        assert_eq!(read, colors.map(|row| row.to_vec()).to_vec());
+    }
+}
@@ -0,0 +1,271 @@
+//! Debug captures of face scans
+//!
+//! With debug capture turned on, every scan attempt is kept: the frame that
+//! was scanned, the grid it was sampled with and the colors it was classified
+//! as (or why it failed). A session is saved as a folder with, per attempt, a
+//! PNG of the frame and a JSON file of the detection results, so a failed
+//! detection can be attached to a bug report and replayed with
+//! [`ScanDebugRecord::reproduce`].
+
+use crate::camera::capture::CameraFrame;
+use crate::camera::color_detect::{ColorDetectionConfig, ColorDetectionResult};
+use crate::camera::perspective::FaceQuad;
+use crate::camera::still_image::{scan_frame, ImageScan, ImageScanError};
+use image::ImageFormat;
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+
+/// Name of the file describing a saved session
+pub const SESSION_FILE: &str = "session.json";
+
+/// What was detected in one scan attempt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanDebugInfo {
+    /// Face being scanned (F, R, B, L, U or D), if known
+    pub face: Option<String>,
+    /// Where the frame came from: a file name, or "camera"
+    pub source: String,
+    /// NxN grid the face was sampled with, or None if the size was to be
+    /// detected
+    pub grid_size: Option<usize>,
+    /// Corners of the face in the frame, or None if it filled the frame
+    pub quad: Option<FaceQuad>,
+    /// Classified colors, row by row, if detection succeeded
+    pub colors: Option<Vec<Vec<ColorDetectionResult>>>,
+    /// Why detection failed, if it did
+    pub error: Option<String>,
+}
+
+/// One scan attempt: the frame and what was detected in it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanDebugRecord {
+    pub info: ScanDebugInfo,
+    pub frame: CameraFrame,
+}
+
+impl ScanDebugRecord {
+    /// Record the result of scanning `frame`
+    ///
+    /// `grid_size` and `quad` are what the scan was asked to use; when the
+    /// size was detected, the detected size is recorded instead.
+    pub fn new(
+        source: impl Into<String>,
+        frame: CameraFrame,
+        grid_size: Option<usize>,
+        quad: Option<FaceQuad>,
+        result: &Result<ImageScan, ImageScanError>,
+    ) -> Self {
+        let (grid_size, colors, error) = match result {
+            Ok(scan) => (Some(scan.size), Some(scan.colors.clone()), None),
+            Err(e) => (grid_size, None, Some(e.to_string())),
+        };
+        Self {
+            info: ScanDebugInfo { face: None, source: source.into(), grid_size, quad, colors, error },
+            frame,
+        }
+    }
+
+    /// Scan the recorded frame again, with the recorded grid and quad
+    pub fn reproduce(&self, config: &ColorDetectionConfig) -> Result<ImageScan, ImageScanError> {
+        scan_frame(&self.frame, self.info.quad, self.info.grid_size, config)
+    }
+
+    /// The frame as a PNG file
+    pub fn frame_png(&self) -> Result<Vec<u8>, ImageScanError> {
+        let image = image::RgbImage::from_raw(self.frame.width, self.frame.height, self.frame.data.clone())
+            .ok_or_else(|| ImageScanError::Decode("The frame is smaller than its size".to_string()))?;
+        let mut bytes = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .map_err(|e| ImageScanError::Decode(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// The detection results as JSON
+    pub fn info_json(&self) -> String {
+        serde_json::to_string_pretty(&self.info).unwrap_or_default()
+    }
+
+    /// Load a record saved as a JSON file and a PNG of its frame
+    ///
+    /// The frame is kept at full size (unlike [`decode_image`]) so the
+    /// recorded quad still lines up with it.
+    ///
+    /// [`decode_image`]: crate::camera::still_image::decode_image
+    pub fn from_files(json: &str, png: &[u8]) -> Result<Self, ImageScanError> {
+        let info = serde_json::from_str(json).map_err(|e| ImageScanError::Decode(e.to_string()))?;
+        let rgb = image::load_from_memory_with_format(png, ImageFormat::Png)
+            .map_err(|e| ImageScanError::Decode(e.to_string()))?
+            .to_rgb8();
+        let frame = CameraFrame { width: rgb.width(), height: rgb.height(), data: rgb.into_raw(), timestamp_ms: 0 };
+        Ok(Self { info, frame })
+    }
+}
+
+/// Summary of a saved session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SessionManifest {
+    id: String,
+    app_version: String,
+    /// File name stem of each record, in the order they were captured
+    records: Vec<String>,
+}
+
+/// The scan attempts of one scanning session
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanDebugSession {
+    /// Name of the session, used as its folder name
+    pub id: String,
+    records: Vec<ScanDebugRecord>,
+}
+
+impl ScanDebugSession {
+    /// Start an empty session
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into(), records: Vec::new() }
+    }
+
+    /// Add a scan attempt of `face`
+    pub fn record(&mut self, face: Option<&str>, mut record: ScanDebugRecord) {
+        record.info.face = face.map(str::to_string);
+        self.records.push(record);
+    }
+
+    /// The scan attempts, in the order they were made
+    pub fn records(&self) -> &[ScanDebugRecord] {
+        &self.records
+    }
+
+    /// File name stem of the record at `index`, e.g. `03-F`
+    fn record_name(&self, index: usize) -> String {
+        let face = self.records[index].info.face.as_deref().unwrap_or("face");
+        format!("{:02}-{}", index + 1, face)
+    }
+
+    /// The session's files: `session.json`, and a PNG and a JSON file per
+    /// attempt
+    pub fn files(&self) -> Result<Vec<(String, Vec<u8>)>, ImageScanError> {
+        let names: Vec<String> = (0..self.records.len()).map(|i| self.record_name(i)).collect();
+        let manifest = SessionManifest {
+            id: self.id.clone(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            records: names.clone(),
+        };
+        let mut files = vec![(
+            SESSION_FILE.to_string(),
+            serde_json::to_vec_pretty(&manifest).map_err(|e| ImageScanError::Decode(e.to_string()))?,
+        )];
+        for (record, name) in self.records.iter().zip(names) {
+            files.push((format!("{}.png", name), record.frame_png()?));
+            files.push((format!("{}.json", name), record.info_json().into_bytes()));
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native_files {
+    use super::*;
+    use std::io;
+    use std::path::{Path, PathBuf};
+
+    /// Folder debug sessions are saved to by default
+    pub fn default_debug_dir() -> PathBuf {
+        std::env::temp_dir().join("rubiks-cube-scans")
+    }
+
+    impl ScanDebugSession {
+        /// Write the session to a folder named after it inside `dir`;
+        /// returns that folder
+        pub fn save(&self, dir: &Path) -> io::Result<PathBuf> {
+            let folder = dir.join(&self.id);
+            std::fs::create_dir_all(&folder)?;
+            let files = self.files().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            for (name, bytes) in files {
+                std::fs::write(folder.join(name), bytes)?;
+            }
+            Ok(folder)
+        }
+
+        /// Read a session written by [`save`](Self::save)
+        pub fn load(folder: &Path) -> io::Result<Self> {
+            let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+            let manifest: SessionManifest = serde_json::from_slice(&std::fs::read(folder.join(SESSION_FILE))?)
+                .map_err(|e| invalid(e.to_string()))?;
+            let mut session = Self::new(manifest.id);
+            for name in manifest.records {
+                let json = std::fs::read_to_string(folder.join(format!("{}.json", name)))?;
+                let png = std::fs::read(folder.join(format!("{}.png", name)))?;
+                session.records.push(ScanDebugRecord::from_files(&json, &png).map_err(|e| invalid(e.to_string()))?);
+            }
+            Ok(session)
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native_files::default_debug_dir;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::frame_source::{FrameSource, SyntheticSource};
+    use crate::camera::grid_detect::FaceRegion;
+    use crate::cube::Color;
+
+    fn frame() -> CameraFrame {
+        let colors = vec![vec![Color::Red, Color::White], vec![Color::Blue, Color::Green]];
+        let mut source = SyntheticSource::new(colors, 120, 90).with_face_region(FaceRegion::full(120, 90));
+        source.start().unwrap();
+        source.next_frame().unwrap()
+    }
+
+    #[test]
+    fn test_record_and_reproduce() {
+        let config = ColorDetectionConfig::default();
+        let frame = frame();
+        let result = scan_frame(&frame, None, Some(2), &config);
+        let record = ScanDebugRecord::new("camera", frame, Some(2), None, &result);
+        assert_eq!(record.info.grid_size, Some(2));
+        assert!(record.info.error.is_none());
+        assert_eq!(record.reproduce(&config), result);
+
+        // Through the saved files and back
+        let loaded = ScanDebugRecord::from_files(&record.info_json(), &record.frame_png().unwrap()).unwrap();
+        assert_eq!(loaded.info, record.info);
+        assert_eq!(loaded.frame.data, record.frame.data);
+    }
+
+    #[test]
+    fn test_failures_are_recorded() {
+        let result = Err(ImageScanError::NoColorsDetected);
+        let record = ScanDebugRecord::new("dark.jpg", frame(), Some(3), None, &result);
+        assert_eq!(record.info.grid_size, Some(3));
+        assert_eq!(record.info.colors, None);
+        assert_eq!(record.info.error, Some(ImageScanError::NoColorsDetected.to_string()));
+    }
+
+    #[test]
+    fn test_session_files() {
+        let mut session = ScanDebugSession::new("scan-1");
+        let result = Err(ImageScanError::NoColorsDetected);
+        session.record(Some("F"), ScanDebugRecord::new("camera", frame(), None, None, &result));
+        session.record(Some("R"), ScanDebugRecord::new("camera", frame(), None, None, &result));
+        let names: Vec<String> = session.files().unwrap().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["session.json", "01-F.png", "01-F.json", "02-R.png", "02-R.json"]);
+    }
+
+    #[test]
+    fn test_save_and_load_session() {
+        let dir = std::env::temp_dir().join(format!("scan-debug-test-{}", std::process::id()));
+        let mut session = ScanDebugSession::new("session");
+        let frame = frame();
+        let result = scan_frame(&frame, None, None, &ColorDetectionConfig::default());
+        session.record(Some("U"), ScanDebugRecord::new("camera", frame, None, None, &result));
+
+        let folder = session.save(&dir).unwrap();
+        let loaded = ScanDebugSession::load(&folder).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(loaded, session);
+    }
+}
@@ -0,0 +1,284 @@
+//! Repairing scans that don't form a solvable cube
+//!
+//! A single misread sticker usually shows up as a color count that is off
+//! by one, but two misreads can keep the counts right and still leave a
+//! flipped edge, a twisted corner or two swapped pieces. Since the colors the
+//! detector was unsure of are the likely culprits, [`repair_scan`] tries
+//! recoloring the least confident stickers, fewest changes first, until the
+//! validator accepts the cube, and offers that state to the user.
+
+use crate::camera::color_detect::ColorDetectionResult;
+use crate::cube::validation::StickerRef;
+use crate::cube::{Color, Cube, FaceName};
+use std::collections::HashMap;
+
+/// Stickers detected with at least this confidence are not changed
+pub const REPAIR_CONFIDENCE: f32 = 0.8;
+
+/// Most stickers changed by a repair
+pub const MAX_REPAIR_CHANGES: usize = 3;
+
+/// How many of the least confident stickers are considered
+const MAX_CANDIDATES: usize = 16;
+
+const COLORS: [Color; 6] = [Color::White, Color::Yellow, Color::Red, Color::Orange, Color::Blue, Color::Green];
+
+/// Detection confidence of scanned stickers; missing stickers (entered by
+/// hand, or confirmed by the user) count as certain
+pub type StickerConfidence = HashMap<StickerRef, f32>;
+
+/// Confidences of one scanned face
+pub fn face_confidence(face: FaceName, colors: &[Vec<ColorDetectionResult>]) -> StickerConfidence {
+    colors
+        .iter()
+        .enumerate()
+        .flat_map(|(row, cells)| cells.iter().enumerate().map(move |(col, cell)| ((face, row, col), cell.confidence)))
+        .collect()
+}
+
+/// A sticker the repair recolors
+#[derive(Debug, Clone, PartialEq)]
+pub struct StickerRepair {
+    pub sticker: StickerRef,
+    /// Color it was scanned as
+    pub from: Color,
+    /// Color it is changed to
+    pub to: Color,
+    /// How sure the detector was of `from`
+    pub confidence: f32,
+}
+
+/// A corrected cube and the stickers changed to get it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanRepair {
+    pub cube: Cube,
+    /// Changed stickers, least confident first; empty if the scan was
+    /// already valid
+    pub changes: Vec<StickerRepair>,
+}
+
+/// Find the fewest low-confidence sticker changes that make `cube` valid
+///
+/// Only stickers detected with less than [`REPAIR_CONFIDENCE`] are changed,
+/// and center stickers of odd cubes never are, since they name their face.
+/// Among repairs with the same number of changes, the one changing the
+/// least confident stickers wins. Returns None if no repair of up to
+/// [`MAX_REPAIR_CHANGES`] stickers works.
+pub fn repair_scan(cube: &Cube, confidence: &StickerConfidence) -> Option<ScanRepair> {
+    if cube.validate().is_ok() {
+        return Some(ScanRepair { cube: cube.clone(), changes: Vec::new() });
+    }
+
+    let size = cube.size();
+    let color_at = |(face, row, col): StickerRef| cube.get_face(face).get(row, col);
+    let mut candidates: Vec<(StickerRef, f32)> = confidence
+        .iter()
+        .filter(|((_, row, col), c)| {
+            **c < REPAIR_CONFIDENCE && *row < size && *col < size && !(size % 2 == 1 && *row == size / 2 && *col == size / 2)
+        })
+        .map(|(sticker, c)| (*sticker, *c))
+        .collect();
+    let order = |(face, row, col): StickerRef| (FaceName::all().iter().position(|f| *f == face), row, col);
+    candidates.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| order(a.0).cmp(&order(b.0))));
+    candidates.truncate(MAX_CANDIDATES);
+
+    let counts = cube.count_colors();
+
+    for changes in 1..=MAX_REPAIR_CHANGES.min(candidates.len()) {
+        let mut best: Option<(f32, ScanRepair)> = None;
+        for picked in combinations(candidates.len(), changes) {
+            let stickers: Vec<(StickerRef, f32)> = picked.iter().map(|&i| candidates[i]).collect();
+            let from: Vec<Color> = stickers.iter().map(|(s, _)| color_at(*s)).collect();
+            let Some(needed) = colors_needed(&counts, &from, size * size) else { continue };
+            let score: f32 = stickers.iter().map(|(_, c)| c).sum();
+            if best.as_ref().is_some_and(|(best_score, _)| *best_score <= score) {
+                continue;
+            }
+            for to in arrangements(&needed, &from) {
+                let mut repaired = cube.clone();
+                for ((face, row, col), color) in stickers.iter().map(|(s, _)| *s).zip(&to) {
+                    repaired.set_sticker(face, row, col, *color);
+                }
+                if repaired.validate().is_ok() {
+                    let changes = stickers
+                        .iter()
+                        .zip(from.iter().zip(&to))
+                        .map(|((sticker, confidence), (from, to))| StickerRepair {
+                            sticker: *sticker,
+                            from: *from,
+                            to: *to,
+                            confidence: *confidence,
+                        })
+                        .collect();
+                    best = Some((score, ScanRepair { cube: repaired, changes }));
+                    break;
+                }
+            }
+        }
+        if let Some((_, repair)) = best {
+            return Some(repair);
+        }
+    }
+    None
+}
+
+/// Colors the stickers scanned as `from` must become for every color to
+/// have `expected` stickers, or None if recoloring them can't get there
+fn colors_needed(counts: &HashMap<Color, usize>, from: &[Color], expected: usize) -> Option<Vec<Color>> {
+    let mut needed = Vec::new();
+    for color in COLORS {
+        let removed = from.iter().filter(|c| **c == color).count();
+        let left = counts.get(&color).copied().unwrap_or(0) - removed;
+        if left > expected {
+            return None;
+        }
+        needed.extend(std::iter::repeat_n(color, expected - left));
+    }
+    (needed.len() == from.len()).then_some(needed)
+}
+
+/// Orderings of `colors` that change every sticker, i.e. put no color back
+/// where it came from
+fn arrangements(colors: &[Color], from: &[Color]) -> Vec<Vec<Color>> {
+    if colors.is_empty() {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for (i, color) in colors.iter().enumerate() {
+        if *color == from[0] || colors[..i].contains(color) {
+            continue;
+        }
+        let rest: Vec<Color> = colors.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, c)| *c).collect();
+        for mut tail in arrangements(&rest, &from[1..]) {
+            tail.insert(0, *color);
+            result.push(tail);
+        }
+    }
+    result
+}
+
+/// All ways to pick `k` of `n` indices, in increasing order
+fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    (k - 1..n)
+        .flat_map(|last| {
+            combinations(last, k - 1).into_iter().map(move |mut picked| {
+                picked.push(last);
+                picked
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Move;
+
+    fn scrambled() -> Cube {
+        let mut cube = Cube::new(3);
+        for m in [Move::R, Move::U, Move::F, Move::LPrime, Move::D2] {
+            cube.apply_move(m);
+        }
+        cube
+    }
+
+    fn unsure(stickers: &[StickerRef], confidence: f32) -> StickerConfidence {
+        stickers.iter().map(|s| (*s, confidence)).collect()
+    }
+
+    fn color(cube: &Cube, (face, row, col): StickerRef) -> Color {
+        cube.get_face(face).get(row, col)
+    }
+
+    #[test]
+    fn test_valid_scan_needs_no_repair() {
+        let cube = scrambled();
+        let repair = repair_scan(&cube, &unsure(&[(FaceName::F, 0, 0)], 0.3)).unwrap();
+        assert!(repair.changes.is_empty());
+        assert_eq!(repair.cube, cube);
+    }
+
+    #[test]
+    fn test_repairs_a_single_misread() {
+        let solved = scrambled();
+        let misread = (FaceName::R, 2, 1);
+        let mut cube = solved.clone();
+        let wrong = color(&cube, misread).opposite();
+        cube.set_sticker(misread.0, misread.1, misread.2, wrong);
+
+        let mut confidence = unsure(&[misread], 0.4);
+        confidence.insert((FaceName::U, 0, 0), 0.7);
+        let repair = repair_scan(&cube, &confidence).unwrap();
+        assert_eq!(repair.cube, solved);
+        assert_eq!(repair.changes.len(), 1);
+        assert_eq!(repair.changes[0].sticker, misread);
+        assert_eq!(repair.changes[0].from, wrong);
+    }
+
+    #[test]
+    fn test_repairs_a_flipped_edge_with_correct_counts() {
+        // Both stickers of an edge misread as each other: the counts are
+        // right, but the edge is flipped
+        let solved = scrambled();
+        let (a, b) = ((FaceName::U, 2, 1), (FaceName::F, 0, 1));
+        let mut cube = solved.clone();
+        cube.set_sticker(a.0, a.1, a.2, color(&solved, b));
+        cube.set_sticker(b.0, b.1, b.2, color(&solved, a));
+        assert!(cube.validate().is_err());
+
+        // Flipping the F-D edge back would fix the parity too, but its
+        // stickers were read with more confidence
+        let mut confidence = unsure(&[a, b], 0.5);
+        confidence.insert((FaceName::D, 0, 1), 0.7);
+        confidence.insert((FaceName::F, 2, 1), 0.7);
+        let repair = repair_scan(&cube, &confidence).unwrap();
+        assert_eq!(repair.cube, solved);
+        let changed: Vec<StickerRef> = repair.changes.iter().map(|c| c.sticker).collect();
+        assert_eq!(changed.len(), 2);
+        assert!(changed.contains(&a) && changed.contains(&b));
+    }
+
+    #[test]
+    fn test_reliable_stickers_and_centers_are_kept() {
+        let mut cube = Cube::new(3);
+        cube.set_sticker(FaceName::F, 0, 0, Color::Blue);
+        let confidence: StickerConfidence = [((FaceName::F, 0, 0), 0.9), ((FaceName::B, 1, 1), 0.1)].into();
+        assert_eq!(repair_scan(&cube, &confidence), None);
+
+        let confidence: StickerConfidence = [((FaceName::F, 0, 0), 0.6), ((FaceName::L, 0, 1), 0.5)].into();
+        let repair = repair_scan(&cube, &confidence).unwrap();
+        assert_eq!(repair.cube, Cube::new(3));
+        let fix = StickerRepair { sticker: (FaceName::F, 0, 0), from: Color::Blue, to: Color::Green, confidence: 0.6 };
+        assert_eq!(repair.changes, vec![fix]);
+    }
+
+    #[test]
+    fn test_gives_up_without_unsure_stickers() {
+        let mut cube = scrambled();
+        let wrong = color(&cube, (FaceName::R, 0, 0)).opposite();
+        cube.set_sticker(FaceName::R, 0, 0, wrong);
+        assert_eq!(repair_scan(&cube, &StickerConfidence::new()), None);
+    }
+
+    #[test]
+    fn test_face_confidence() {
+        let colors = vec![vec![
+            ColorDetectionResult { color: Color::Red, confidence: 0.9 },
+            ColorDetectionResult { color: Color::Blue, confidence: 0.4 },
+        ]];
+        let confidence = face_confidence(FaceName::L, &colors);
+        assert_eq!(confidence[&(FaceName::L, 0, 1)], 0.4);
+        assert_eq!(confidence.len(), 2);
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(combinations(4, 2).len(), 6);
+        assert_eq!(combinations(3, 3), vec![vec![0, 1, 2]]);
+        let swaps = arrangements(&[Color::Red, Color::Blue], &[Color::Red, Color::Blue]);
+        assert_eq!(swaps, vec![vec![Color::Blue, Color::Red]]);
+    }
+}
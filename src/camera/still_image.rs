@@ -0,0 +1,185 @@
+//! Scanning from still image files
+//!
+//! Photos of cube faces (PNG or JPEG) go through the same detection pipeline
+//! as camera frames: the face's size is detected from its grid lines, then
+//! its stickers are sampled. This helps users without a webcam, and makes it
+//! possible to debug detection offline against saved photos.
+
+use crate::camera::capture::CameraFrame;
+use crate::camera::color_detect::{ColorDetectionConfig, ColorDetectionResult};
+use crate::camera::grid_detect::{detect_grid_size, FaceRegion};
+use crate::camera::perspective::{detect_colors_in_quad, FaceQuad};
+use image::ImageFormat;
+
+/// Photos are scaled down so their longer side is at most this many pixels
+pub const MAX_IMAGE_SIDE: u32 = 1024;
+
+/// Size assumed when the grid lines can't be made out
+const FALLBACK_SIZE: usize = 3;
+
+/// Error types for still image scanning
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageScanError {
+    /// The file is neither a PNG nor a JPEG
+    UnsupportedFormat,
+    /// The file could not be decoded
+    Decode(String),
+    /// The stickers' colors could not be made out
+    NoColorsDetected,
+}
+
+impl std::fmt::Display for ImageScanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageScanError::UnsupportedFormat => write!(f, "Only PNG and JPEG photos can be scanned"),
+            ImageScanError::Decode(msg) => write!(f, "Could not read the photo: {}", msg),
+            ImageScanError::NoColorsDetected => {
+                write!(f, "Could not make out the sticker colors in this photo")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImageScanError {}
+
+/// Result of scanning a photo of one face
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageScan {
+    /// Stickers per row and column
+    pub size: usize,
+    /// Whether the size was detected from the photo rather than assumed
+    pub size_detected: bool,
+    /// Detected colors, row by row
+    pub colors: Vec<Vec<ColorDetectionResult>>,
+}
+
+/// Decode a PNG or JPEG file into a frame, scaled down to at most
+/// [`MAX_IMAGE_SIDE`] pixels on its longer side
+pub fn decode_image(bytes: &[u8]) -> Result<CameraFrame, ImageScanError> {
+    let format = image::guess_format(bytes).map_err(|_| ImageScanError::UnsupportedFormat)?;
+    if !matches!(format, ImageFormat::Png | ImageFormat::Jpeg) {
+        return Err(ImageScanError::UnsupportedFormat);
+    }
+    let mut decoded =
+        image::load_from_memory_with_format(bytes, format).map_err(|e| ImageScanError::Decode(e.to_string()))?;
+    if decoded.width().max(decoded.height()) > MAX_IMAGE_SIDE {
+        decoded = decoded.resize(MAX_IMAGE_SIDE, MAX_IMAGE_SIDE, image::imageops::FilterType::Triangle);
+    }
+    let rgb = decoded.to_rgb8();
+    Ok(CameraFrame { width: rgb.width(), height: rgb.height(), data: rgb.into_raw(), timestamp_ms: 0 })
+}
+
+/// Scan the face filling a still image
+///
+/// # Arguments
+/// * `frame` - The decoded photo
+/// * `quad` - Corners of the face, or None if the face fills the photo
+/// * `grid_size` - NxN grid size, or None to detect it from the grid lines
+/// * `config` - Detection configuration
+pub fn scan_frame(
+    frame: &CameraFrame,
+    quad: Option<FaceQuad>,
+    grid_size: Option<usize>,
+    config: &ColorDetectionConfig,
+) -> Result<ImageScan, ImageScanError> {
+    let quad = quad.unwrap_or_else(|| FaceQuad::from_region(FaceRegion::full(frame.width, frame.height)));
+    let (size, size_detected) = match grid_size {
+        Some(size) => (size, false),
+        None => {
+            let region = bounding_region(&quad, frame.width, frame.height);
+            match detect_grid_size(&frame.data, frame.width, frame.height, region) {
+                Some(estimate) => (estimate.size as usize, true),
+                None => (FALLBACK_SIZE, false),
+            }
+        }
+    };
+    let colors = detect_colors_in_quad(&frame.data, frame.width, frame.height, &quad, size, config)
+        .ok_or(ImageScanError::NoColorsDetected)?;
+    Ok(ImageScan { size, size_detected, colors })
+}
+
+/// Decode a photo and scan the face filling it
+pub fn scan_image(
+    bytes: &[u8],
+    grid_size: Option<usize>,
+    config: &ColorDetectionConfig,
+) -> Result<ImageScan, ImageScanError> {
+    scan_frame(&decode_image(bytes)?, None, grid_size, config)
+}
+
+/// Smallest upright region of the image containing a quad
+fn bounding_region(quad: &FaceQuad, width: u32, height: u32) -> FaceRegion {
+    let xs = quad.corners.map(|(x, _)| x.clamp(0.0, width as f32));
+    let ys = quad.corners.map(|(_, y)| y.clamp(0.0, height as f32));
+    let left = xs.iter().cloned().fold(f32::MAX, f32::min) as u32;
+    let top = ys.iter().cloned().fold(f32::MAX, f32::min) as u32;
+    let right = xs.iter().cloned().fold(0.0, f32::max) as u32;
+    let bottom = ys.iter().cloned().fold(0.0, f32::max) as u32;
+    FaceRegion { x: left, y: top, width: right - left, height: bottom - top }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Color;
+    use std::io::Cursor;
+
+    /// A 4x4 face with dark lines between the stickers
+    fn face_photo(format: ImageFormat) -> Vec<u8> {
+        let colors = [[255, 255, 255], [255, 213, 0], [196, 30, 58], [0, 81, 186]];
+        let image = image::RgbImage::from_fn(200, 200, |x, y| {
+            let on_line = |p: u32| p % 50 < 3 || p % 50 > 46;
+            if on_line(x) || on_line(y) {
+                image::Rgb([15, 15, 15])
+            } else {
+                image::Rgb(colors[((x / 50 + y / 50) % 4) as usize])
+            }
+        });
+        let mut bytes = Vec::new();
+        image.write_to(&mut Cursor::new(&mut bytes), format).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_scan_png_and_jpeg() {
+        let config = ColorDetectionConfig::default();
+        for format in [ImageFormat::Png, ImageFormat::Jpeg] {
+            let scan = scan_image(&face_photo(format), None, &config).unwrap();
+            assert_eq!(scan.size, 4, "{:?}", format);
+            assert!(scan.size_detected);
+            assert_eq!(scan.colors[0][0].color, Color::White);
+            assert_eq!(scan.colors[1][2].color, Color::Blue);
+            assert_eq!(scan.colors[3][3].color, Color::Red);
+        }
+    }
+
+    #[test]
+    fn test_given_size_is_used() {
+        let scan = scan_image(&face_photo(ImageFormat::Png), Some(4), &ColorDetectionConfig::default()).unwrap();
+        assert_eq!(scan.size, 4);
+        assert!(!scan.size_detected);
+        assert_eq!(scan.colors.len(), 4);
+
+        // Sampling as a 2x2 lands on the lines between the stickers
+        let wrong_size = scan_image(&face_photo(ImageFormat::Png), Some(2), &ColorDetectionConfig::default());
+        assert_eq!(wrong_size, Err(ImageScanError::NoColorsDetected));
+    }
+
+    #[test]
+    fn test_large_photos_are_scaled_down() {
+        let image = image::RgbImage::from_pixel(2048, 1024, image::Rgb([200, 30, 50]));
+        let mut bytes = Vec::new();
+        image.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png).unwrap();
+        let frame = decode_image(&bytes).unwrap();
+        assert_eq!((frame.width, frame.height), (MAX_IMAGE_SIDE, 512));
+        assert_eq!(frame.data.len(), (frame.width * frame.height * 3) as usize);
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        assert_eq!(decode_image(b"not an image"), Err(ImageScanError::UnsupportedFormat));
+        // A PNG signature with nothing after it
+        let truncated = b"\x89PNG\r\n\x1a\n";
+        assert!(matches!(decode_image(truncated), Err(ImageScanError::Decode(_))));
+    }
+}
@@ -0,0 +1,91 @@
+//! Accessibility helpers shared by the input components
+//!
+//! - Number keys 1-6 pick a color, in the same order as the color picker
+//! - Colorblind mode marks each sticker color with a letter and a pattern,
+//!   so colors can be told apart without relying on hue
+
+use crate::cube::Color;
+
+/// Colors in picker order; number key `n` selects `PALETTE[n - 1]`
+pub const PALETTE: [Color; 6] = [
+    Color::White,
+    Color::Yellow,
+    Color::Red,
+    Color::Orange,
+    Color::Blue,
+    Color::Green,
+];
+
+/// Get the color bound to a number key ("1"-"6"), if any
+pub fn color_for_key(key: &str) -> Option<Color> {
+    let index = key.parse::<usize>().ok()?;
+    PALETTE.get(index.checked_sub(1)?).copied()
+}
+
+/// Get the number key for a color ("1"-"6")
+pub fn key_for_color(color: Color) -> usize {
+    PALETTE.iter().position(|c| *c == color).unwrap_or(0) + 1
+}
+
+/// Single-letter label for colorblind mode
+pub fn color_letter(color: Color) -> &'static str {
+    match color {
+        Color::White => "W",
+        Color::Yellow => "Y",
+        Color::Red => "R",
+        Color::Orange => "O",
+        Color::Blue => "B",
+        Color::Green => "G",
+    }
+}
+
+/// CSS `background-image` pattern for colorblind mode
+///
+/// Each color gets a distinct pattern; white stays plain.
+pub fn color_pattern(color: Color) -> &'static str {
+    match color {
+        Color::White => "none",
+        Color::Yellow => "radial-gradient(rgba(0,0,0,0.3) 15%, transparent 16%)",
+        Color::Red => "repeating-linear-gradient(45deg, rgba(0,0,0,0.25) 0 3px, transparent 3px 8px)",
+        Color::Orange => "repeating-linear-gradient(0deg, rgba(0,0,0,0.25) 0 3px, transparent 3px 8px)",
+        Color::Blue => "repeating-linear-gradient(90deg, rgba(255,255,255,0.35) 0 3px, transparent 3px 8px)",
+        Color::Green => "repeating-linear-gradient(45deg, rgba(255,255,255,0.3) 0 2px, transparent 2px 8px), \
+                         repeating-linear-gradient(-45deg, rgba(255,255,255,0.3) 0 2px, transparent 2px 8px)",
+    }
+}
+
+/// Text color that stays readable on top of a sticker color
+pub fn letter_color(color: Color) -> &'static str {
+    match color {
+        Color::White | Color::Yellow | Color::Orange => "#000000",
+        Color::Red | Color::Blue | Color::Green => "#FFFFFF",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_keys() {
+        assert_eq!(color_for_key("1"), Some(Color::White));
+        assert_eq!(color_for_key("6"), Some(Color::Green));
+        assert_eq!(color_for_key("0"), None);
+        assert_eq!(color_for_key("7"), None);
+        assert_eq!(color_for_key("a"), None);
+
+        for color in PALETTE {
+            assert_eq!(color_for_key(&key_for_color(color).to_string()), Some(color));
+        }
+    }
+
+    #[test]
+    fn test_markings_are_distinct() {
+        for (i, a) in PALETTE.iter().enumerate() {
+            for b in &PALETTE[i + 1..] {
+                assert_ne!(color_letter(*a), color_letter(*b));
+                assert_ne!(color_pattern(*a), color_pattern(*b));
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Algorithm Browser Component
+//!
+//! Reference sheet of every algorithm taught in the lessons. Algorithms can
+//! be searched, filtered by set (F2L, OLL, PLL, parity) and starred; each
+//! card shows a top view of the case it solves and can be sent to practice.
+
+use crate::components::case_diagram::CaseDiagram;
+use crate::components::ui_kit::token::{
+    BORDER, FOCUS, HIGHLIGHT, ON_ACCENT, SURFACE, SURFACE_RAISED, TEXT, TEXT_MUTED,
+};
+use crate::components::ui_kit::ButtonTheme;
+use crate::tutorial::algorithms::{algorithm_library, filter_algorithms, AlgorithmSet};
+use crate::tutorial::PracticeCase;
+use dioxus::prelude::*;
+
+/// Props for the AlgorithmBrowser component
+#[derive(Props, Clone, PartialEq)]
+pub struct AlgorithmBrowserProps {
+    /// Ids of starred algorithms (see `Progress::favorite_algorithms`)
+    #[props(default)]
+    pub favorites: Vec<String>,
+    /// Called with an algorithm id when its star is clicked
+    pub on_toggle_favorite: EventHandler<String>,
+    /// Called with a practice case when "Practice" is clicked
+    #[props(default)]
+    pub on_send_to_trainer: Option<EventHandler<PracticeCase>>,
+}
+
+/// Searchable, filterable algorithm reference sheet
+#[component]
+pub fn AlgorithmBrowser(props: AlgorithmBrowserProps) -> Element {
+    let library = use_hook(algorithm_library);
+    let mut query = use_signal(String::new);
+    let mut set_filter = use_signal(|| None::<AlgorithmSet>);
+    let mut favorites_only = use_signal(|| false);
+
+    let favorites = props.favorites.clone();
+    let shown = filter_algorithms(
+        &library,
+        &query.read(),
+        set_filter(),
+        favorites_only().then_some(favorites.as_slice()),
+    );
+    let shown_count = shown.len();
+    let on_toggle_favorite = props.on_toggle_favorite;
+    let on_send_to_trainer = props.on_send_to_trainer;
+    let primary = ButtonTheme::Primary.background_var();
+    let filters = std::iter::once(None).chain(AlgorithmSet::ALL.into_iter().map(Some));
+
+    rsx! {
+        div {
+            class: "algorithm-browser",
+            style: "padding: 15px; background: {SURFACE}; color: {TEXT}; border-radius: 8px; border: 1px solid {BORDER};",
+            h3 { style: "margin: 0 0 10px;", "Algorithm Sheet" }
+
+            div {
+                style: "display: flex; gap: 8px; flex-wrap: wrap; align-items: center; margin-bottom: 12px;",
+                input {
+                    r#type: "search",
+                    placeholder: "Search by name or moves",
+                    "aria-label": "Search algorithms",
+                    value: "{query}",
+                    style: "flex: 1; min-width: 160px; min-height: 44px; padding: 0 10px; font-size: 16px; \
+                            border: 1px solid {BORDER}; border-radius: 4px; background: {SURFACE_RAISED}; color: {TEXT};",
+                    oninput: move |evt| query.set(evt.value()),
+                }
+                div {
+                    role: "radiogroup",
+                    "aria-label": "Algorithm set",
+                    style: "display: flex; gap: 4px; flex-wrap: wrap;",
+                    for set in filters {
+                        {
+                            let selected = set_filter() == set;
+                            let label = set.map_or("All", |set| set.label());
+                            let background = if selected { HIGHLIGHT } else { SURFACE_RAISED };
+                            let border = if selected { FOCUS } else { BORDER };
+                            rsx! {
+                                button {
+                                    key: "{label}",
+                                    role: "radio",
+                                    "aria-checked": "{selected}",
+                                    style: "min-height: 44px; padding: 0 12px; cursor: pointer; border-radius: 4px; touch-action: manipulation; \
+                                            background: {background}; color: {TEXT}; border: 2px solid {border};",
+                                    onclick: move |_| set_filter.set(set),
+                                    "{label}"
+                                }
+                            }
+                        }
+                    }
+                }
+                label {
+                    style: "display: inline-flex; align-items: center; gap: 6px; min-height: 44px; cursor: pointer;",
+                    input {
+                        r#type: "checkbox",
+                        checked: favorites_only(),
+                        onchange: move |evt| favorites_only.set(evt.checked()),
+                    }
+                    "★ Favorites only"
+                }
+            }
+
+            p {
+                role: "status",
+                style: "margin: 0 0 8px; font-size: 14px; color: {TEXT_MUTED};",
+                "{shown_count} algorithms"
+            }
+
+            ul {
+                style: "list-style: none; margin: 0; padding: 0; display: flex; flex-direction: column; gap: 8px; max-height: 600px; overflow-y: auto;",
+                for entry in shown {
+                    {
+                        let id = entry.id.clone();
+                        let is_favorite = favorites.contains(&entry.id);
+                        let star = if is_favorite { "★" } else { "☆" };
+                        let star_label = if is_favorite { "Remove from favorites" } else { "Add to favorites" };
+                        let notation = entry.to_notation();
+                        let case = entry.to_practice_case();
+                        rsx! {
+                            li {
+                                key: "{entry.id}",
+                                style: "display: flex; gap: 12px; align-items: center; padding: 8px; background: {SURFACE_RAISED}; border: 1px solid {BORDER}; border-radius: 6px;",
+                                CaseDiagram {
+                                    cube: entry.case_state(),
+                                    orientation_only: entry.set == AlgorithmSet::Oll,
+                                }
+                                div {
+                                    style: "flex: 1; min-width: 0;",
+                                    div {
+                                        style: "font-weight: bold;",
+                                        span { style: "color: {TEXT_MUTED}; font-size: 12px; margin-right: 6px;", "{entry.set.label()}" }
+                                        "{entry.name}"
+                                    }
+                                    div { style: "font-size: 14px; color: {TEXT_MUTED};", "{entry.description}" }
+                                    div { style: "font-family: monospace; margin-top: 4px;", "{notation}" }
+                                }
+                                div {
+                                    style: "display: flex; flex-direction: column; gap: 4px;",
+                                    button {
+                                        "aria-label": "{star_label}",
+                                        "aria-pressed": "{is_favorite}",
+                                        title: "{star_label}",
+                                        style: "min-height: 44px; min-width: 44px; font-size: 20px; cursor: pointer; background: none; color: {TEXT}; \
+                                                border: 1px solid {BORDER}; border-radius: 4px; touch-action: manipulation;",
+                                        onclick: move |_| on_toggle_favorite.call(id.clone()),
+                                        "{star}"
+                                    }
+                                    if let Some(handler) = on_send_to_trainer {
+                                        button {
+                                            title: "Practice this case",
+                                            style: "min-height: 44px; padding: 0 10px; cursor: pointer; background: {primary}; color: {ON_ACCENT}; \
+                                                    border: none; border-radius: 4px; touch-action: manipulation;",
+                                            onclick: move |_| handler.call(case.clone()),
+                                            "Practice"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -2,8 +2,28 @@
 ///
 /// This component provides a UI for scanning Rubik's cubes using the device camera.
 /// It handles camera permissions, displays live feed, and captures frames for analysis.
+/// While streaming, the lighting is analyzed every few frames and shown over
+/// the feed: a quality meter, a warning, and hints on regions that are too
+/// dark or too bright.
+/// With `auto_size` on, the same frames are checked for the face's grid
+/// lines, and the overlay switches to the detected size (2x2 to 7x7).
+///
+/// Frames reach the analysis through an async stream read by a coroutine,
+/// throttled to `max_fps` and paused while the scanner isn't `active` (or
+/// the browser tab is hidden), so analysis doesn't make the UI stutter.
+///
+/// On native platforms the connected cameras are listed in a picker, so the
+/// user can switch cameras mid-session, and re-checked every few seconds: if
+/// the camera in use is unplugged the feed stops with a `Disconnected` error.
+/// Errors offer the recoveries of `CameraError::recovery` as buttons (try
+/// again, use another camera, how to allow camera access).
 
 use dioxus::prelude::*;
+use crate::camera::{
+    detect_grid_size, next_latest, CameraError, CameraFrame, CameraRecovery, DeviceWatcher, FaceRegion,
+    FrameStreamControl, GridSizeTracker, LightingMonitor, LightingQuality, RegionBrightness, DEFAULT_MAX_FPS,
+    LIGHTING_GRID,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum CameraState {
@@ -40,16 +60,173 @@ pub struct CameraScannerProps {
     /// Cube size for grid overlay (2-20)
     #[props(default = 3)]
     pub cube_size: u32,
+
+    /// Detect the cube size from the camera image instead of using `cube_size`
+    #[props(default = true)]
+    pub auto_size: bool,
+
+    /// Callback when a cube size has been detected
+    #[props(optional)]
+    pub on_size_detected: Option<EventHandler<u32>>,
+
+    /// Callback with each frame taken for analysis (at most `max_fps` a
+    /// second), e.g. for color calibration
+    #[props(optional)]
+    pub on_frame: Option<EventHandler<CameraFrame>>,
+
+    /// Frames grabbed between lighting analyses
+    #[props(default = 5)]
+    pub lighting_every: u32,
+
+    /// Most frames per second taken from the camera for analysis
+    #[props(default = DEFAULT_MAX_FPS)]
+    pub max_fps: u32,
+
+    /// Whether the scanner is on screen; frame capture pauses while it isn't
+    #[props(default = true)]
+    pub active: bool,
+}
+
+/// Milliseconds between checks whether a paused scanner is visible again
+#[cfg(target_arch = "wasm32")]
+const HIDDEN_POLL_MS: u64 = 250;
+
+/// Milliseconds between checks for cameras being plugged in or unplugged
+#[cfg(not(target_arch = "wasm32"))]
+const DEVICE_POLL_MS: u64 = 2000;
+
+/// How to allow camera access, shown by the permission help recovery
+#[cfg(target_arch = "wasm32")]
+const PERMISSION_HELP: &str = "Click the camera icon in the address bar (or open this site's settings), \
+                               set Camera to Allow, then press Try Again.";
+#[cfg(not(target_arch = "wasm32"))]
+const PERMISSION_HELP: &str = "Open your system's privacy settings, allow this app to use the camera, \
+                               then press Try Again.";
+
+/// Recoveries to offer for the last error; retrying is always possible
+fn recovery_actions(error: Option<&CameraError>) -> Vec<CameraRecovery> {
+    error.map_or_else(|| vec![CameraRecovery::Retry], CameraError::recovery)
+}
+
+/// Size of the frames grabbed for analysis: small keeps it fast, but the
+/// grid lines of a 7x7 must still show
+#[cfg(target_arch = "wasm32")]
+const GRAB_SIZE: (u32, u32) = (320, 240);
+
+/// Meter color for a lighting quality
+fn quality_color(quality: LightingQuality) -> &'static str {
+    match quality {
+        LightingQuality::Excellent | LightingQuality::Good => "#22c55e",
+        LightingQuality::Adequate => "#eab308",
+        LightingQuality::Poor | LightingQuality::VeryPoor => "#ef4444",
+    }
+}
+
+/// Overlay for one region of the feed: tint and text, if it needs fixing
+fn region_overlay(region: &RegionBrightness) -> Option<(&'static str, String)> {
+    let hint = region.hint()?;
+    let (tint, icon) = if region.too_dark() { ("rgba(0, 0, 0, 0.35)", "🌙") } else { ("rgba(255, 255, 255, 0.35)", "☀️") };
+    Some((tint, format!("{} {}", icon, hint)))
+}
+
+/// Grab a small frame from the video element for analysis
+#[cfg(target_arch = "wasm32")]
+fn grab_frame(canvas: &web_sys::HtmlCanvasElement) -> Option<crate::camera::CameraFrame> {
+    use wasm_bindgen::JsCast;
+
+    let video: web_sys::HtmlVideoElement =
+        web_sys::window()?.document()?.get_element_by_id("camera-video")?.dyn_into().ok()?;
+    let context: web_sys::CanvasRenderingContext2d = canvas.get_context("2d").ok()??.dyn_into().ok()?;
+    let (width, height) = GRAB_SIZE;
+    context
+        .draw_image_with_html_video_element_and_dw_and_dh(&video, 0.0, 0.0, width as f64, height as f64)
+        .ok()?;
+    let rgba = context.get_image_data(0.0, 0.0, width as f64, height as f64).ok()?.data();
+    // Drop the alpha channel
+    let data = rgba.chunks_exact(4).flat_map(|px| [px[0], px[1], px[2]]).collect();
+    Some(crate::camera::CameraFrame { data, width, height, timestamp_ms: web_sys::js_sys::Date::now() as u64 })
 }
 
 #[component]
 pub fn CameraScanner(props: CameraScannerProps) -> Element {
     let mut camera_state = use_signal(|| CameraState::NotInitialized);
     let _video_element = use_signal(|| None::<String>);
+    let lighting_every = props.lighting_every;
+    let mut lighting = use_signal(move || LightingMonitor::new(lighting_every));
+    let mut detected_size = use_signal(|| None::<u32>);
+    let auto_size = props.auto_size;
+    let on_size_detected = props.on_size_detected;
+    let on_frame = props.on_frame;
+    let max_fps = props.max_fps;
+    let mut active = use_signal(|| props.active);
+    let stream = use_signal(|| None::<FrameStreamControl>);
+    let devices = use_signal(DeviceWatcher::default);
+    let mut selected_camera = use_signal(|| 0u32);
+    let mut last_error = use_signal(|| None::<CameraError>);
+    let mut show_permission_help = use_signal(|| false);
+
+    // Show an error along with the ways to recover from it
+    let mut report_error = move |error: CameraError| {
+        camera_state.set(match &error {
+            CameraError::AccessDenied(msg) => CameraState::PermissionDenied(msg.clone()),
+            other => CameraState::Error(other.to_string()),
+        });
+        last_error.set(Some(error));
+    };
+
+    // Analyze frames as they stream in, whichever platform captured them,
+    // re-rendering only when the analysis changes. Frames that queued up
+    // while one was analyzed are skipped.
+    let analyzer = use_coroutine(move |mut frames: UnboundedReceiver<Result<CameraFrame, CameraError>>| async move {
+        let mut monitor = LightingMonitor::new(lighting_every);
+        let mut sizes = GridSizeTracker::new();
+        while let Some(frame) = next_latest(&mut frames).await {
+            let frame = match frame {
+                Ok(frame) => frame,
+                Err(e) => {
+                    report_error(e);
+                    continue;
+                }
+            };
+            if let Some(handler) = &on_frame {
+                handler.call(frame.clone());
+            }
+            if !monitor.observe(&frame) {
+                continue;
+            }
+            lighting.set(monitor.clone());
+            if auto_size {
+                let region = FaceRegion::guide(frame.width, frame.height);
+                let estimate = detect_grid_size(&frame.data, frame.width, frame.height, region);
+                if let Some(size) = sizes.observe(estimate) {
+                    detected_size.set(Some(size));
+                    if let Some(handler) = &on_size_detected {
+                        handler.call(size);
+                    }
+                }
+            }
+        }
+    });
+
+    // Pause capturing while the scanner is off screen
+    let is_active = props.active;
+    use_effect(use_reactive!(|is_active| {
+        active.set(is_active);
+        if let Some(control) = stream.peek().as_ref() {
+            control.set_paused(!is_active);
+        }
+    }));
+    use_drop(move || {
+        if let Some(control) = stream.peek().as_ref() {
+            control.stop();
+        }
+    });
 
     // Handle camera initialization
-    let start_camera = move |_| {
+    let mut start_camera = move || {
         camera_state.set(CameraState::RequestingPermission);
+        last_error.set(None);
+        show_permission_help.set(false);
 
         // On web, we use browser MediaDevices API
         #[cfg(target_arch = "wasm32")]
@@ -57,6 +234,7 @@ pub fn CameraScanner(props: CameraScannerProps) -> Element {
             use wasm_bindgen::prelude::*;
             use wasm_bindgen::JsCast;
             use web_sys::{MediaStream, MediaStreamConstraints};
+            use crate::camera::FrameThrottle;
 
             spawn(async move {
                 let window = web_sys::window().expect("no global `window` exists");
@@ -83,16 +261,44 @@ pub fn CameraScanner(props: CameraScannerProps) -> Element {
                                         let _ = video_el.play();
                                     }
                                 }
+
+                                // Grab frames from the video into the analyzer, at most
+                                // max_fps, and none while the scanner or tab is hidden
+                                let frames = analyzer.tx();
+                                let mut throttle = FrameThrottle::new(max_fps);
+                                let canvas = window
+                                    .document()
+                                    .and_then(|document| document.create_element("canvas").ok())
+                                    .and_then(|element| element.dyn_into::<web_sys::HtmlCanvasElement>().ok());
+                                if let Some(canvas) = canvas {
+                                    canvas.set_width(GRAB_SIZE.0);
+                                    canvas.set_height(GRAB_SIZE.1);
+                                    while camera_state() == CameraState::Streaming {
+                                        let hidden = !active() || window.document().is_some_and(|document| document.hidden());
+                                        if hidden {
+                                            crate::components::solution_player::delay(HIDDEN_POLL_MS).await;
+                                            continue;
+                                        }
+                                        let now = web_sys::js_sys::Date::now() as u64;
+                                        if throttle.accept(now) {
+                                            if let Some(frame) = grab_frame(&canvas) {
+                                                let _ = frames.unbounded_send(Ok(frame));
+                                            }
+                                        }
+                                        let now = web_sys::js_sys::Date::now() as u64;
+                                        crate::components::solution_player::delay(throttle.wait_ms(now).max(1)).await;
+                                    }
+                                }
                             }
                             Err(e) => {
-                                camera_state.set(CameraState::PermissionDenied(
+                                report_error(CameraError::AccessDenied(
                                     format!("Permission denied: {:?}", e)
                                 ));
                             }
                         }
                     }
                     Err(e) => {
-                        camera_state.set(CameraState::Error(
+                        report_error(CameraError::InitializationFailed(
                             format!("Failed to get media: {:?}", e)
                         ));
                     }
@@ -100,14 +306,139 @@ pub fn CameraScanner(props: CameraScannerProps) -> Element {
             });
         }
 
-        // On native platforms, camera access would be handled here
-        // For now, this is a stub - actual camera integration works via browser APIs
+        // On native platforms, a capture thread streams frames from the
+        // camera into the analyzer
         #[cfg(not(target_arch = "wasm32"))]
         {
-            // Placeholder for native camera implementation
-            // The overlay UI works independently of camera backend
-            let _ = camera_state; // Avoid unused warning
+            use crate::camera::{spawn_frame_stream, CameraCapture, CameraConfig};
+
+            let mut stream = stream;
+
+            if let Some(previous) = stream.peek().as_ref() {
+                previous.stop();
+            }
+            let camera_index = *selected_camera.peek();
+            let open = move || CameraCapture::new(CameraConfig { camera_index, ..CameraConfig::default() });
+            let control = spawn_frame_stream(open, max_fps, analyzer.tx());
+            control.set_paused(!active());
+            stream.set(Some(control));
+            camera_state.set(CameraState::Streaming);
+        }
+    };
+
+    // Watch for cameras being plugged in or unplugged, stopping the feed if
+    // its camera went away
+    #[cfg(not(target_arch = "wasm32"))]
+    use_future(move || async move {
+        use crate::camera::available_cameras;
+
+        let mut devices = devices;
+        let mut stream = stream;
+        loop {
+            let mut watcher = devices.peek().clone();
+            let changes = watcher.update(available_cameras());
+            if !changes.is_empty() {
+                let selected = *selected_camera.peek();
+                let unplugged = changes.removed.iter().find(|device| device.index == selected).cloned();
+                devices.set(watcher);
+                if let Some(device) = unplugged {
+                    if *camera_state.peek() == CameraState::Streaming {
+                        if let Some(control) = stream.take() {
+                            control.stop();
+                        }
+                        report_error(CameraError::Disconnected(device.name));
+                    }
+                }
+            }
+            crate::components::solution_player::delay(DEVICE_POLL_MS).await;
+        }
+    });
+
+    // Switch cameras, restarting the feed if one is running
+    let mut select_camera = move |index: u32| {
+        selected_camera.set(index);
+        if camera_state() != CameraState::NotInitialized {
+            start_camera();
+        }
+    };
+
+    let mut handle_recovery = move |action: CameraRecovery| match action {
+        CameraRecovery::Retry => start_camera(),
+        CameraRecovery::ChooseAnotherCamera => {
+            let other = devices.read().alternative_to(selected_camera()).map(|device| device.index);
+            if let Some(index) = other {
+                select_camera(index);
+            }
         }
+        CameraRecovery::PermissionHelp => show_permission_help.toggle(),
+    };
+
+    let monitor = lighting.read();
+    let quality = monitor.analysis().map(|analysis| analysis.quality);
+    let warning = monitor.warning();
+    let regions: Vec<_> = monitor.regions().iter().map(region_overlay).collect();
+    drop(monitor);
+    let cameras = devices.read().devices().to_vec();
+    let has_alternative = devices.read().alternative_to(selected_camera()).is_some();
+    let recoveries: Vec<CameraRecovery> = recovery_actions(last_error.read().as_ref())
+        .into_iter()
+        .filter(|action| *action != CameraRecovery::ChooseAnotherCamera || has_alternative)
+        .collect();
+
+    // Camera picker, when there are cameras to pick from
+    let camera_picker = rsx! {
+        if !cameras.is_empty() {
+            label {
+                style: "display: flex; align-items: center; gap: 8px; font-size: 14px;",
+                "Camera:"
+                select {
+                    "aria-label": "Camera",
+                    style: "padding: 6px 10px; font-size: 14px; border-radius: 5px; border: 1px solid #ccc;",
+                    onchange: move |evt| {
+                        if let Ok(index) = evt.value().parse() {
+                            select_camera(index);
+                        }
+                    },
+                    for device in cameras.iter() {
+                        option {
+                            key: "{device.index}",
+                            value: "{device.index}",
+                            selected: device.index == selected_camera(),
+                            "{device.name}"
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    // Buttons for the ways out of an error
+    let recovery_buttons = rsx! {
+        div {
+            style: "display: flex; flex-wrap: wrap; justify-content: center; gap: 10px;",
+            for action in recoveries {
+                button {
+                    key: "{action.label()}",
+                    onclick: move |_| handle_recovery(action),
+                    style: "padding: 10px 20px; font-size: 14px; \
+                           background: #667eea; color: white; border: none; \
+                           border-radius: 5px; cursor: pointer;",
+                    "{action.label()}"
+                }
+            }
+        }
+        if show_permission_help() {
+            p {
+                role: "note",
+                style: "font-size: 14px; text-align: center; max-width: 400px; margin: 0; \
+                       padding: 10px; background: white; border-radius: 8px;",
+                "{PERMISSION_HELP}"
+            }
+        }
+    };
+    let (grid_size, size_label) = match detected_size() {
+        Some(size) if props.auto_size => (size, format!("Detected {}x{} – Align Face Here", size, size)),
+        _ => (props.cube_size, format!("Align {}x{} Cube Face Here", props.cube_size, props.cube_size)),
     };
 
     // Render UI based on camera state
@@ -131,8 +462,10 @@ pub fn CameraScanner(props: CameraScannerProps) -> Element {
                             "Click the button below to start your camera and scan your Rubik's Cube."
                         }
 
+                        {camera_picker}
+
                         button {
-                            onclick: start_camera,
+                            onclick: move |_| start_camera(),
                             style: "padding: 15px 30px; font-size: 18px; font-weight: bold; \
                                    background: linear-gradient(135deg, #667eea 0%, #764ba2 100%); \
                                    color: white; border: none; border-radius: 10px; cursor: pointer; \
@@ -180,6 +513,52 @@ pub fn CameraScanner(props: CameraScannerProps) -> Element {
                                               props.width, props.height),
                             }
 
+                            // Regions that are too dark or too bright
+                            if !regions.is_empty() {
+                                div {
+                                    class: "lighting-regions",
+                                    style: "position: absolute; inset: 0; display: grid; \
+                                           grid-template-columns: repeat({LIGHTING_GRID}, 1fr); \
+                                           grid-template-rows: repeat({LIGHTING_GRID}, 1fr); pointer-events: none;",
+                                    for (i, overlay) in regions.iter().enumerate() {
+                                        div {
+                                            key: "{i}",
+                                            style: format!("display: flex; align-items: flex-end; justify-content: center; \
+                                                           padding-bottom: 6px; background: {}; color: white; \
+                                                           font-size: 12px; font-weight: 600; text-shadow: 0 1px 2px rgba(0,0,0,0.8);",
+                                                           overlay.as_ref().map_or("transparent", |(tint, _)| *tint)),
+                                            if let Some((_, text)) = overlay {
+                                                "{text}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
+                            // Lighting quality meter
+                            if let Some(quality) = quality {
+                                div {
+                                    class: "lighting-meter",
+                                    role: "meter",
+                                    "aria-label": "Lighting quality",
+                                    "aria-valuemin": "0",
+                                    "aria-valuemax": "100",
+                                    "aria-valuenow": "{(quality.score() * 100.0).round()}",
+                                    "aria-valuetext": "{quality.label()}",
+                                    style: "position: absolute; top: 10px; right: 10px; width: 120px; \
+                                           padding: 6px 8px; background: rgba(0, 0, 0, 0.6); border-radius: 8px; \
+                                           color: white; font-size: 12px; font-weight: 600; pointer-events: none;",
+                                    "💡 Light: {quality.label()}"
+                                    div {
+                                        style: "margin-top: 4px; height: 6px; background: rgba(255, 255, 255, 0.25); border-radius: 3px; overflow: hidden;",
+                                        div {
+                                            style: format!("height: 100%; width: {}%; background: {}; transition: width 0.3s;",
+                                                          quality.score() * 100.0, quality_color(quality)),
+                                        }
+                                    }
+                                }
+                            }
+
                             // Enhanced overlay grid guide (NxN for cube scanning)
                             div {
                                 style: "position: absolute; top: 50%; left: 50%; \
@@ -240,9 +619,9 @@ pub fn CameraScanner(props: CameraScannerProps) -> Element {
                                                    gap: 0; \
                                                    background: rgba(0, 0, 0, 0.1); \
                                                    box-shadow: 0 0 20px rgba(34, 197, 94, 0.3);",
-                                                   props.cube_size, props.cube_size),
+                                                   grid_size, grid_size),
 
-                                    for i in 0..(props.cube_size * props.cube_size) {
+                                    for i in 0..(grid_size * grid_size) {
                                         div {
                                             key: "{i}",
                                             style: "border: 1px solid rgba(34, 197, 94, 0.4); \
@@ -250,7 +629,7 @@ pub fn CameraScanner(props: CameraScannerProps) -> Element {
                                                    display: flex; align-items: center; justify-content: center;",
 
                                             // Center indicator for middle sticker (if cube_size is odd)
-                                            if props.cube_size % 2 == 1 && i == (props.cube_size * props.cube_size) / 2 {
+                                            if grid_size % 2 == 1 && i == (grid_size * grid_size) / 2 {
                                                 div {
                                                     style: "width: 8px; height: 8px; \
                                                            background: #22c55e; \
@@ -270,7 +649,7 @@ pub fn CameraScanner(props: CameraScannerProps) -> Element {
                                            border-radius: 20px; font-size: 14px; \
                                            font-weight: 600; white-space: nowrap; \
                                            box-shadow: 0 2px 8px rgba(0, 0, 0, 0.3);",
-                                    {format!("Align {}x{} Cube Face Here", props.cube_size, props.cube_size)}
+                                    {size_label}
                                 }
                             }
                         }
@@ -280,6 +659,21 @@ pub fn CameraScanner(props: CameraScannerProps) -> Element {
                             "✓ Camera Active"
                         }
 
+                        {camera_picker}
+
+                        // Lighting warning, announced as it changes
+                        div {
+                            role: "status",
+                            "aria-live": "polite",
+                            if let Some(message) = warning {
+                                p {
+                                    style: "font-size: 14px; font-weight: 600; color: #b45309; background: #fef3c7; \
+                                           padding: 8px 12px; border-radius: 8px; margin: 0; max-width: 400px; text-align: center;",
+                                    "⚠️ {message}"
+                                }
+                            }
+                        }
+
                         p {
                             style: "font-size: 14px; text-align: center; max-width: 400px;",
                             "Align your cube's face within the grid overlay. \
@@ -309,13 +703,7 @@ pub fn CameraScanner(props: CameraScannerProps) -> Element {
                             "Error: {msg}"
                         }
 
-                        button {
-                            onclick: start_camera,
-                            style: "padding: 10px 20px; font-size: 14px; \
-                                   background: #667eea; color: white; border: none; \
-                                   border-radius: 5px; cursor: pointer;",
-                            "Try Again"
-                        }
+                        {recovery_buttons}
                     }
                 },
 
@@ -340,13 +728,9 @@ pub fn CameraScanner(props: CameraScannerProps) -> Element {
                             "Error: {msg}"
                         }
 
-                        button {
-                            onclick: start_camera,
-                            style: "padding: 10px 20px; font-size: 14px; \
-                                   background: #667eea; color: white; border: none; \
-                                   border-radius: 5px; cursor: pointer;",
-                            "Try Again"
-                        }
+                        {camera_picker}
+
+                        {recovery_buttons}
                     }
                 },
             }
@@ -417,4 +801,25 @@ mod tests {
         assert!(3 >= 2 && 3 <= 20);
         assert!(20 >= 2 && 20 <= 20);
     }
+
+    #[test]
+    fn test_recovery_actions() {
+        assert_eq!(recovery_actions(None), vec![CameraRecovery::Retry]);
+        let unplugged = CameraError::Disconnected("USB Camera".to_string());
+        assert_eq!(
+            recovery_actions(Some(&unplugged)),
+            vec![CameraRecovery::ChooseAnotherCamera, CameraRecovery::Retry]
+        );
+    }
+
+    #[test]
+    fn test_region_overlay() {
+        let dark = RegionBrightness { row: 0, col: 0, brightness: 0.1 };
+        let fine = RegionBrightness { row: 0, col: 1, brightness: 0.5 };
+        let glare = RegionBrightness { row: 0, col: 2, brightness: 0.95 };
+        assert_eq!(region_overlay(&dark).unwrap().1, "🌙 Too dark");
+        assert_eq!(region_overlay(&fine), None);
+        assert_eq!(region_overlay(&glare).unwrap().1, "☀️ Too bright");
+        assert_ne!(quality_color(LightingQuality::Excellent), quality_color(LightingQuality::Poor));
+    }
 }
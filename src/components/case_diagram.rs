@@ -0,0 +1,297 @@
+//! Case Diagram Component
+//!
+//! The classic last-layer diagram: the U face seen from above, with the top
+//! row of each side face drawn as a thin bar around it (B above, L left,
+//! R right, F below).
+//!
+//! Two styles are supported:
+//! - Full color, for PLL and F2L cases
+//! - Orientation only, for OLL cases: stickers showing the top color are
+//!   yellow, everything else is gray, and side bars only appear where the
+//!   top color is facing sideways
+//!
+//! A diagram can be drawn from a `Cube` or from a hand-written `CaseMask`.
+
+use crate::components::color_picker::color_to_css;
+use crate::cube::{Color, Cube, FaceName};
+use dioxus::prelude::*;
+
+/// Fill for U-layer stickers that do not show the top color
+const UNORIENTED: &str = "#9CA3AF";
+
+/// One cell of a case diagram
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagramCell {
+    /// Nothing drawn (diagram corners, hidden side stickers)
+    Empty,
+    /// A sticker in its real color
+    Sticker(Color),
+    /// A sticker showing the top color (drawn yellow)
+    Oriented,
+    /// A top-face sticker not showing the top color (drawn gray)
+    Unoriented,
+}
+
+/// Colors of a top-view case diagram, row by row
+///
+/// The grid is `(n + 2) x (n + 2)` for an `n x n` cube; the four corner
+/// cells are `None`.
+pub fn top_view_grid(cube: &Cube) -> Vec<Vec<Option<Color>>> {
+    let n = cube.size();
+    let (up, front, back) = (
+        cube.get_face(FaceName::U),
+        cube.get_face(FaceName::F),
+        cube.get_face(FaceName::B),
+    );
+    let (left, right) = (cube.get_face(FaceName::L), cube.get_face(FaceName::R));
+
+    let mut grid = vec![vec![None; n + 2]; n + 2];
+    for i in 0..n {
+        grid[0][i + 1] = Some(back.get(0, n - 1 - i));
+        grid[n + 1][i + 1] = Some(front.get(0, i));
+        grid[i + 1][0] = Some(left.get(0, i));
+        grid[i + 1][n + 1] = Some(right.get(0, n - 1 - i));
+        for col in 0..n {
+            grid[i + 1][col + 1] = Some(up.get(i, col));
+        }
+    }
+    grid
+}
+
+/// Which stickers of a top-view diagram show the top color
+///
+/// Uses the same `(n + 2) x (n + 2)` layout as `top_view_grid`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseMask {
+    cells: Vec<Vec<bool>>,
+}
+
+impl CaseMask {
+    /// Build the mask of a cube, using the U center as the top color
+    ///
+    /// On even cubes the center sticker nearest the middle is used.
+    pub fn from_cube(cube: &Cube) -> Self {
+        let n = cube.size();
+        let top = cube.get_face(FaceName::U).get(n / 2, n / 2);
+        let cells = top_view_grid(cube)
+            .into_iter()
+            .map(|row| row.into_iter().map(|color| color == Some(top)).collect())
+            .collect();
+        Self { cells }
+    }
+
+    /// Parse a hand-written mask
+    ///
+    /// One line per diagram row, `n + 2` characters each: `X` marks a
+    /// sticker showing the top color, anything else does not. Corner
+    /// characters are ignored. Returns `None` if the shape is wrong.
+    ///
+    /// ```text
+    ///  X.X      back bar
+    /// .XX.X     left bar, U row 0, right bar
+    /// .XXX.
+    /// .XX..
+    ///  ...      front bar
+    /// ```
+    pub fn parse(rows: &[&str]) -> Option<Self> {
+        let cells = rows.len();
+        if cells < 3 {
+            return None;
+        }
+        let parsed: Vec<Vec<bool>> = rows
+            .iter()
+            .map(|row| row.chars().map(|c| c == 'X').collect::<Vec<_>>())
+            .collect();
+        if parsed.iter().any(|row| row.len() != cells) {
+            return None;
+        }
+        Some(Self { cells: parsed })
+    }
+
+    /// Cube size the mask is for
+    pub fn size(&self) -> usize {
+        self.cells.len() - 2
+    }
+
+    /// Check whether the cell at (row, col) shows the top color
+    pub fn is_oriented(&self, row: usize, col: usize) -> bool {
+        self.cells
+            .get(row)
+            .and_then(|r| r.get(col))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Check whether every top-face sticker shows the top color
+    pub fn is_top_solved(&self) -> bool {
+        let n = self.size();
+        (1..=n).all(|row| (1..=n).all(|col| self.is_oriented(row, col)))
+    }
+
+    /// Cells for an orientation-only diagram
+    pub fn to_cells(&self) -> Vec<Vec<DiagramCell>> {
+        let last = self.cells.len() - 1;
+        (0..=last)
+            .map(|row| {
+                (0..=last)
+                    .map(|col| {
+                        let side = row == 0 || row == last || col == 0 || col == last;
+                        let corner = (row == 0 || row == last) && (col == 0 || col == last);
+                        match (corner, side, self.is_oriented(row, col)) {
+                            (true, _, _) => DiagramCell::Empty,
+                            (false, _, true) => DiagramCell::Oriented,
+                            (false, true, false) => DiagramCell::Empty,
+                            (false, false, false) => DiagramCell::Unoriented,
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Cells for a full-color diagram of a cube
+pub fn full_color_cells(cube: &Cube) -> Vec<Vec<DiagramCell>> {
+    top_view_grid(cube)
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|color| color.map_or(DiagramCell::Empty, DiagramCell::Sticker))
+                .collect()
+        })
+        .collect()
+}
+
+/// Props for the CaseDiagram component
+///
+/// Give either `cube` or `mask`; a mask is always drawn orientation-only.
+#[derive(Props, Clone, PartialEq)]
+pub struct CaseDiagramProps {
+    /// Cube to draw the U layer of
+    #[props(default)]
+    pub cube: Option<Cube>,
+    /// Hand-written orientation mask, used when no cube is given
+    #[props(default)]
+    pub mask: Option<CaseMask>,
+    /// Draw only which stickers show the top color (OLL style)
+    #[props(default)]
+    pub orientation_only: bool,
+    /// Width of the diagram in pixels
+    #[props(default = 90.0)]
+    pub width: f32,
+}
+
+/// Top-view last-layer diagram
+#[component]
+pub fn CaseDiagram(props: CaseDiagramProps) -> Element {
+    let cells = match (&props.cube, &props.mask) {
+        (Some(cube), _) if props.orientation_only => CaseMask::from_cube(cube).to_cells(),
+        (Some(cube), _) => full_color_cells(cube),
+        (None, Some(mask)) => mask.to_cells(),
+        (None, None) => return rsx! {},
+    };
+    let n = cells.len() - 2;
+    // Side bars are a third as deep as a top sticker
+    let sticker = props.width / (n as f32 + 2.0 / 3.0);
+    let bar = sticker / 3.0;
+    let tracks = format!("{bar}px repeat({n}, {sticker}px) {bar}px");
+
+    rsx! {
+        div {
+            class: "case-diagram",
+            "aria-hidden": "true",
+            style: "display: grid; grid-template-columns: {tracks}; grid-template-rows: {tracks}; gap: 1px; flex-shrink: 0;",
+            for (index, cell) in cells.into_iter().flatten().enumerate() {
+                div {
+                    key: "{index}",
+                    style: match cell {
+                        DiagramCell::Empty => String::new(),
+                        DiagramCell::Sticker(color) => format!("background: {}; border-radius: 2px;", color_to_css(color)),
+                        DiagramCell::Oriented => format!("background: {}; border-radius: 2px;", color_to_css(Color::Yellow)),
+                        DiagramCell::Unoriented => format!("background: {UNORIENTED}; border-radius: 2px;"),
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Move;
+
+    #[test]
+    fn test_top_view_grid_solved() {
+        let cube = Cube::new(3);
+        let grid = top_view_grid(&cube);
+        assert_eq!(grid.len(), 5);
+        assert_eq!(grid[0][0], None);
+        assert_eq!(grid[2][2], Some(cube.get_face(FaceName::U).get(1, 1)));
+        assert_eq!(grid[4][2], Some(cube.get_face(FaceName::F).get(0, 1)));
+    }
+
+    #[test]
+    fn test_top_view_grid_corner_stickers_line_up() {
+        // The F/R stickers next to U's front-right corner belong to the same piece
+        let mut cube = Cube::new(3);
+        cube.set_sticker(FaceName::F, 0, 2, Color::Orange);
+        cube.set_sticker(FaceName::R, 0, 0, Color::Blue);
+        let grid = top_view_grid(&cube);
+        assert_eq!(grid[4][3], Some(Color::Orange));
+        assert_eq!(grid[3][4], Some(Color::Blue));
+    }
+
+    #[test]
+    fn test_mask_from_solved_cube() {
+        let mask = CaseMask::from_cube(&Cube::new(3));
+        assert!(mask.is_top_solved());
+        assert!(!mask.is_oriented(0, 2));
+
+        let cells = mask.to_cells();
+        assert_eq!(cells[2][2], DiagramCell::Oriented);
+        assert_eq!(cells[0][2], DiagramCell::Empty);
+        assert_eq!(cells[0][0], DiagramCell::Empty);
+    }
+
+    #[test]
+    fn test_mask_shows_side_bars() {
+        // F turns U-colored stickers onto the side faces
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::F);
+        let mask = CaseMask::from_cube(&cube);
+        assert!(!mask.is_top_solved());
+        let cells = mask.to_cells();
+        assert!(cells.iter().flatten().any(|c| *c == DiagramCell::Unoriented));
+        assert!((1..=3).any(|i| cells[i][0] == DiagramCell::Oriented || cells[i][4] == DiagramCell::Oriented));
+    }
+
+    #[test]
+    fn test_parse_mask() {
+        // Sune: one corner oriented, three twisted
+        let mask = CaseMask::parse(&[
+            " X.X ",
+            ".XX.X",
+            ".XXX.",
+            ".XX..",
+            " ... ",
+        ])
+        .unwrap();
+        assert_eq!(mask.size(), 3);
+        assert!(!mask.is_top_solved());
+        assert!(mask.is_oriented(0, 1));
+        assert_eq!(mask.to_cells()[1][1], DiagramCell::Oriented);
+        assert_eq!(mask.to_cells()[1][3], DiagramCell::Unoriented);
+
+        assert!(CaseMask::parse(&["XX", "XX"]).is_none());
+        assert!(CaseMask::parse(&[" X ", "X", " X "]).is_none());
+    }
+
+    #[test]
+    fn test_full_color_cells() {
+        let cells = full_color_cells(&Cube::new(4));
+        assert_eq!(cells.len(), 6);
+        assert_eq!(cells[0][0], DiagramCell::Empty);
+        assert!(matches!(cells[1][1], DiagramCell::Sticker(_)));
+    }
+}
@@ -1,19 +1,27 @@
-/// Color calibration UI component
-///
-/// Provides a user interface for calibrating color detection by showing
-/// each cube color to the camera and collecting samples.
+//! Color calibration UI component
+//!
+//! Provides a step-by-step wizard for calibrating color detection: for each
+//! cube color the user holds the face with that center up to the camera and
+//! captures samples, then the learned profile is saved. All camera work goes
+//! through the `CalibrationService`.
 
 use dioxus::prelude::*;
-use crate::camera::{CalibrationManager, ColorSample, RGB};
+use crate::camera::{CalibrationProfile, CalibrationService, CalibrationStep, CameraFrame};
+use crate::components::ui_kit::token::{BORDER, FOCUS, ON_ACCENT, SURFACE, SURFACE_RAISED, TEXT, TEXT_MUTED};
+use crate::components::ui_kit::{ButtonTheme, KidProgress};
 use crate::cube::Color;
 
 /// Props for ColorCalibration component
 #[derive(Props, Clone, PartialEq)]
 pub struct ColorCalibrationProps {
-    /// Calibration manager state
-    pub manager: Signal<CalibrationManager>,
-    /// Optional callback when calibration is complete
-    pub on_complete: Option<EventHandler<()>>,
+    /// Calibration service running the wizard
+    pub service: Signal<CalibrationService>,
+    /// Latest frame from the camera, sampled when a capture is taken
+    #[props(optional)]
+    pub frame: Option<CameraFrame>,
+    /// Optional callback with the saved profile when calibration is complete
+    #[props(optional)]
+    pub on_complete: Option<EventHandler<CalibrationProfile>>,
 }
 
 /// Color name helper for display
@@ -43,165 +51,202 @@ fn color_to_css(color: Color) -> &'static str {
 /// Get instruction text for current color
 fn get_instruction_text(color: Color) -> &'static str {
     match color {
-        Color::White => "Hold a WHITE sticker in front of the camera. Make sure it's well-lit and centered.",
-        Color::Yellow => "Hold a YELLOW sticker in front of the camera. Make sure it's well-lit and centered.",
-        Color::Red => "Hold a RED sticker in front of the camera. Make sure it's well-lit and centered.",
-        Color::Orange => "Hold an ORANGE sticker in front of the camera. Make sure it's well-lit and centered.",
-        Color::Blue => "Hold a BLUE sticker in front of the camera. Make sure it's well-lit and centered.",
-        Color::Green => "Hold a GREEN sticker in front of the camera. Make sure it's well-lit and centered.",
+        Color::White => "Hold the face with the WHITE center up to the camera, with the center in the square. Make sure it's well-lit.",
+        Color::Yellow => "Hold the face with the YELLOW center up to the camera, with the center in the square. Make sure it's well-lit.",
+        Color::Red => "Hold the face with the RED center up to the camera, with the center in the square. Make sure it's well-lit.",
+        Color::Orange => "Hold the face with the ORANGE center up to the camera, with the center in the square. Make sure it's well-lit.",
+        Color::Blue => "Hold the face with the BLUE center up to the camera, with the center in the square. Make sure it's well-lit.",
+        Color::Green => "Hold the face with the GREEN center up to the camera, with the center in the square. Make sure it's well-lit.",
     }
 }
 
 /// ColorCalibration component for calibrating color detection
 #[component]
 pub fn ColorCalibration(props: ColorCalibrationProps) -> Element {
-    let mut manager = props.manager;
+    let mut service = props.service;
+    let mut error = use_signal(|| None::<String>);
 
     // Read current state
-    let mgr = manager.read();
-    let current_color = mgr.current_color();
-    let sample_count = mgr.current_calibration().sample_count;
-    let completed = mgr.completed_colors();
-    let is_complete = mgr.is_complete();
-    drop(mgr); // Release read lock
-
-    let instruction = get_instruction_text(current_color);
-    let color_css = color_to_css(current_color);
-    let name = color_name(current_color);
-
-    // Text color depends on background
-    let text_color = if current_color == Color::White || current_color == Color::Yellow {
-        "#000"
-    } else {
-        "#FFF"
-    };
-
-    // Sample counter
-    let samples = sample_count;
-    let samples_needed = 10;
-    let progress_pct = (samples as f32 / samples_needed as f32 * 100.0) as u32;
+    let svc = service.read();
+    let step = svc.step();
+    let colors = svc.colors();
+    let completed = svc.completed_colors();
+    let samples_needed = svc.samples_needed();
+    let saved = svc.profile().is_some();
+    drop(svc); // Release read lock
 
-    // Handlers
+    let has_frame = props.frame.is_some();
+    let frame = props.frame.clone();
     let capture_sample = move |_| {
-        // In a real implementation, this would capture from camera
-        // For now, we'll simulate with default colors
-        let rgb = match current_color {
-            Color::White => RGB::new(240, 240, 240),
-            Color::Yellow => RGB::new(255, 215, 0),
-            Color::Red => RGB::new(196, 30, 58),
-            Color::Orange => RGB::new(255, 88, 0),
-            Color::Blue => RGB::new(0, 81, 186),
-            Color::Green => RGB::new(0, 158, 96),
-        };
-        let sample = ColorSample::new(rgb);
-        manager.write().add_sample(sample);
+        let Some(frame) = frame.as_ref() else { return };
+        match service.write().capture(frame) {
+            Ok(_) => error.set(None),
+            Err(e) => error.set(Some(e.to_string())),
+        }
     };
 
     let next_color = move |_| {
-        if !manager.write().next_color() {
-            // All colors done
-            if let Some(on_complete) = &props.on_complete {
-                on_complete.call(());
+        service.write().advance();
+    };
+
+    let on_complete = props.on_complete;
+    let save_profile = move |_| {
+        let finished = service.write().finish().cloned();
+        match finished {
+            Ok(profile) => {
+                error.set(None);
+                if let Some(handler) = on_complete {
+                    handler.call(profile);
+                }
             }
+            Err(e) => error.set(Some(e.to_string())),
         }
     };
 
     let reset = move |_| {
-        manager.write().reset();
+        service.write().restart();
+        error.set(None);
     };
 
+    let primary = ButtonTheme::Primary.background_var();
+    let success = ButtonTheme::Success.background_var();
+    let button_style = "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; border: none; border-radius: 4px; touch-action: manipulation;";
+
     rsx! {
         div {
             class: "color-calibration-container",
-            style: "{get_calibration_styles()}",
+            style: "{get_calibration_styles()} background: {SURFACE}; color: {TEXT}; border-radius: 8px;",
 
             // Header
             div {
                 class: "calibration-header",
                 h2 { "Color Calibration" }
-                p { "Calibrate your camera for better color detection" }
+                p { style: "color: {TEXT_MUTED};", "Calibrate your camera for better color detection" }
             }
 
-            // Progress
-            div {
-                class: "calibration-progress",
-                div {
-                    class: "progress-text",
-                    "Calibrated: {completed} / 6 colors"
-                }
-                div {
-                    class: "progress-bar-container",
-                    div {
-                        class: "progress-bar",
-                        style: "width: {completed * 100 / 6}%;"
+            // One step per color, ticked off when it has enough samples
+            ol {
+                class: "calibration-steps",
+                "aria-label": "Calibration steps",
+                style: "display: flex; gap: 8px; list-style: none; padding: 0; margin: 0 0 10px;",
+                for (i, (color, done)) in colors.into_iter().enumerate() {
+                    {
+                        let current = matches!(step, CalibrationStep::Sampling { index, .. } if index == i);
+                        let outline = if current { format!("3px solid {FOCUS}") } else { format!("1px solid {BORDER}") };
+                        let label = if done { format!("{}, done", color_name(color)) } else { color_name(color).to_string() };
+                        rsx! {
+                            li {
+                                key: "{i}",
+                                style: "width: 36px; height: 36px; display: flex; align-items: center; justify-content: center; border-radius: 50%; background: {color_to_css(color)}; border: {outline}; font-weight: bold; color: {text_color(color)};",
+                                "aria-current": if current { "step" } else { "false" },
+                                "aria-label": "{label}",
+                                if done { "✓" } else { "{i + 1}" }
+                            }
+                        }
                     }
                 }
             }
+            KidProgress {
+                value: completed as f32 / 6.0 * 100.0,
+                label: format!("Calibrated: {} / 6 colors", completed),
+            }
 
-            if !is_complete {
-                // Current color instruction
-                div {
-                    class: "current-color-section",
+            match step {
+                CalibrationStep::Sampling { color, index, samples } => rsx! {
                     div {
-                        class: "color-display",
-                        style: "background-color: {color_css}; color: {text_color};",
-                        "{name}"
-                    }
+                        class: "current-color-section",
+                        p { style: "color: {TEXT_MUTED}; margin: 0;", "Step {index + 1} of 6" }
+                        div {
+                            class: "color-display",
+                            style: "padding: 16px; border-radius: 8px; font-size: 20px; font-weight: bold; text-align: center; background-color: {color_to_css(color)}; color: {text_color(color)};",
+                            "{color_name(color)}"
+                        }
 
-                    p { class: "instruction", "{instruction}" }
+                        p { class: "instruction", "{get_instruction_text(color)}" }
+                        p {
+                            style: "color: {TEXT_MUTED}; font-size: 14px;",
+                            "Tilt the cube a little between captures so the colors are learned under different light."
+                        }
 
-                    // Sample progress for current color
-                    div {
-                        class: "sample-progress",
-                        "Samples: {samples} / {samples_needed}"
+                        // Sample progress for current color
                         div {
-                            class: "sample-bar-container",
+                            class: "sample-progress",
+                            "aria-live": "polite",
+                            "Samples: {samples.min(samples_needed)} / {samples_needed}"
                             div {
-                                class: "sample-bar",
-                                style: "width: {progress_pct}%; background-color: {color_css};"
+                                class: "sample-bar-container",
+                                style: "height: 10px; background: {SURFACE_RAISED}; border-radius: 5px; overflow: hidden;",
+                                div {
+                                    class: "sample-bar",
+                                    style: "height: 100%; width: {progress_percent(samples, samples_needed)}%; background-color: {color_to_css(color)};"
+                                }
                             }
                         }
-                    }
 
-                    // Capture button
-                    div {
-                        class: "button-group",
-                        button {
-                            class: "btn-capture",
-                            onclick: capture_sample,
-                            disabled: samples >= samples_needed,
-                            "📸 Capture Sample"
+                        if !has_frame {
+                            p { style: "color: {TEXT_MUTED};", "Waiting for the camera…" }
                         }
 
-                        if samples >= samples_needed {
-                            button {
-                                class: "btn-next",
-                                onclick: next_color,
-                                "Next Color →"
+                        div {
+                            class: "button-group",
+                            style: "display: flex; gap: 10px; flex-wrap: wrap; margin-top: 15px;",
+                            if samples < samples_needed {
+                                button {
+                                    class: "btn-capture",
+                                    style: "{button_style} background: {primary}; color: {ON_ACCENT};",
+                                    onclick: capture_sample,
+                                    disabled: !has_frame,
+                                    "📸 Capture Sample"
+                                }
+                            } else {
+                                button {
+                                    class: "btn-next",
+                                    style: "{button_style} background: {primary}; color: {ON_ACCENT};",
+                                    onclick: next_color,
+                                    "Next Color →"
+                                }
                             }
                         }
                     }
-                }
-            } else {
-                // Completion screen
-                div {
-                    class: "completion-screen",
-                    div { class: "completion-icon", "✅" }
-                    h3 { "Calibration Complete!" }
-                    p { "Your camera is now calibrated for optimal color detection." }
-                    button {
-                        class: "btn-reset",
-                        onclick: reset,
-                        "Calibrate Again"
+                },
+                CalibrationStep::Done => rsx! {
+                    div {
+                        class: "completion-screen",
+                        div { class: "completion-icon", "✅" }
+                        if saved {
+                            h3 { "Calibration Complete!" }
+                            p { "Your camera is now calibrated for optimal color detection." }
+                            button {
+                                class: "btn-reset",
+                                style: "{button_style} background: {primary}; color: {ON_ACCENT};",
+                                onclick: reset,
+                                "Calibrate Again"
+                            }
+                        } else {
+                            h3 { "All six colors sampled" }
+                            p { "Save the profile to use it when scanning." }
+                            button {
+                                class: "btn-save",
+                                style: "{button_style} background: {success}; color: {ON_ACCENT};",
+                                onclick: save_profile,
+                                "💾 Save Profile"
+                            }
+                        }
                     }
-                }
+                },
+            }
+
+            if let Some(message) = error() {
+                p { role: "alert", style: "color: {TEXT};", "⚠️ {message}" }
             }
 
             // Reset button (always available)
             div {
                 class: "footer-actions",
+                style: "margin-top: 15px;",
                 button {
                     class: "btn-reset-small",
+                    style: "padding: 8px 14px; min-height: 44px; cursor: pointer; background: {SURFACE_RAISED}; color: {TEXT}; border: 1px solid {BORDER}; border-radius: 4px;",
                     onclick: reset,
                     "↺ Start Over"
                 }
@@ -210,6 +255,20 @@ pub fn ColorCalibration(props: ColorCalibrationProps) -> Element {
     }
 }
 
+/// Text color readable on a color swatch
+fn text_color(color: Color) -> &'static str {
+    if color == Color::White || color == Color::Yellow {
+        "#000"
+    } else {
+        "#FFF"
+    }
+}
+
+/// Share of the samples collected, as a whole percentage
+fn progress_percent(samples: usize, needed: usize) -> u32 {
+    (samples.min(needed) as f32 / needed.max(1) as f32 * 100.0) as u32
+}
+
 /// Get CSS styles for calibration component
 fn get_calibration_styles() -> &'static str {
     r#"
@@ -250,4 +309,11 @@ mod tests {
         assert!(instruction.contains("WHITE"));
         assert!(instruction.contains("camera"));
     }
+
+    #[test]
+    fn test_progress_percent() {
+        assert_eq!(progress_percent(0, 10), 0);
+        assert_eq!(progress_percent(5, 10), 50);
+        assert_eq!(progress_percent(12, 10), 100);
+    }
 }
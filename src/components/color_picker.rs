@@ -6,9 +6,14 @@
 //! - Display 6 color buttons
 //! - Click color to apply to selected sticker
 //! - Show currently selected color
+//!
+//! The palette is a radio group: number keys 1-6 select a color, and
+//! colorblind mode labels each swatch with a letter and pattern.
 
 use dioxus::prelude::*;
 use crate::cube::Color;
+use crate::components::accessibility::{color_for_key, color_letter, color_pattern, key_for_color, letter_color, PALETTE};
+use crate::components::ui_kit::token::{BORDER, FOCUS, SURFACE};
 
 /// Props for the ColorPicker component
 #[derive(Clone, PartialEq, Props)]
@@ -21,10 +26,13 @@ pub struct ColorPickerProps {
     /// Size of color buttons in pixels (default: 50)
     #[props(optional)]
     pub button_size: Option<f32>,
+    /// Label swatches with letters and patterns
+    #[props(default)]
+    pub colorblind_mode: bool,
 }
 
 /// Convert Color to CSS color string
-fn color_to_css(color: Color) -> &'static str {
+pub fn color_to_css(color: Color) -> &'static str {
     match color {
         Color::White => "#FFFFFF",
         Color::Yellow => "#FFD500",
@@ -36,7 +44,7 @@ fn color_to_css(color: Color) -> &'static str {
 }
 
 /// Get color name for display
-fn color_name(color: Color) -> &'static str {
+pub fn color_name(color: Color) -> &'static str {
     match color {
         Color::White => "White",
         Color::Yellow => "Yellow",
@@ -54,21 +62,13 @@ pub fn ColorPicker(props: ColorPickerProps) -> Element {
     let button_size = props.button_size.unwrap_or(50.0);
     let gap = 12.0;
 
-    // All 6 cube colors in standard order
-    let colors = vec![
-        Color::White,
-        Color::Yellow,
-        Color::Red,
-        Color::Orange,
-        Color::Blue,
-        Color::Green,
-    ];
+    let on_color_select = props.on_color_select;
 
     // Container style - arrange in 2 rows of 3
     let container_style = format!(
         "display: grid; grid-template-columns: repeat(3, {}px); gap: {}px; \
-         padding: 16px; background: #f7fafc; border-radius: 8px; \
-         border: 2px solid #e2e8f0;",
+         padding: 16px; background: {SURFACE}; border-radius: 8px; \
+         border: 2px solid {BORDER};",
         button_size, gap
     );
 
@@ -76,10 +76,20 @@ pub fn ColorPicker(props: ColorPickerProps) -> Element {
         div {
             class: "color-picker-container",
             style: "{container_style}",
+            role: "radiogroup",
+            "aria-label": "Sticker color. Number keys 1 to 6 select a color.",
+            onkeydown: move |evt| {
+                if let Key::Character(key) = evt.key() {
+                    if let Some(color) = color_for_key(&key) {
+                        evt.prevent_default();
+                        on_color_select.call(color);
+                    }
+                }
+            },
 
-            // Render all 6 color buttons
-            for color in colors {
-                {render_color_button(color, props.selected_color, button_size, props.on_color_select)}
+            // Render all 6 color buttons in standard order
+            for color in PALETTE {
+                {render_color_button(color, props.selected_color, button_size, props.on_color_select, props.colorblind_mode)}
             }
         }
     }
@@ -91,16 +101,19 @@ fn render_color_button(
     selected_color: Option<Color>,
     button_size: f32,
     on_color_select: EventHandler<Color>,
+    colorblind_mode: bool,
 ) -> Element {
     let color_css = color_to_css(color);
     let is_selected = selected_color == Some(color);
+    let pattern = if colorblind_mode { color_pattern(color) } else { "none" };
+    let key = key_for_color(color);
 
     // Add visual indicator for selected color
     let button_style = if is_selected {
         format!(
-            "width: {}px; height: {}px; background: {}; border-radius: 8px; \
+            "width: {}px; height: {}px; background: {}; background-image: {pattern}; border-radius: 8px; \
              cursor: pointer; transition: all 0.2s; \
-             border: 4px solid #3182ce; box-shadow: 0 0 12px rgba(49, 130, 206, 0.6); \
+             border: 4px solid {FOCUS}; box-shadow: 0 0 12px rgba(49, 130, 206, 0.6); \
              transform: scale(1.1); position: relative; z-index: 10; touch-action: manipulation;",
             button_size, button_size, color_css
         )
@@ -113,7 +126,7 @@ fn render_color_button(
         };
 
         format!(
-            "width: {}px; height: {}px; background: {}; border-radius: 8px; \
+            "width: {}px; height: {}px; background: {}; background-image: {pattern}; border-radius: 8px; \
              cursor: pointer; transition: all 0.2s; {} \
              box-shadow: 0 2px 4px rgba(0,0,0,0.1); touch-action: manipulation;",
             button_size, button_size, color_css, border
@@ -139,8 +152,17 @@ fn render_color_button(
             onclick: move |_| {
                 on_color_select.call(color);
             },
-            title: format!("{} color", color_name(color)),
+            role: "radio",
+            "aria-checked": "{is_selected}",
+            title: format!("{} color ({})", color_name(color), key),
             "aria-label": format!("Select {} color", color_name(color)),
+            "aria-keyshortcuts": "{key}",
+            if colorblind_mode {
+                span {
+                    style: "font-size: {button_size * 0.4}px; font-weight: bold; color: {letter_color(color)};",
+                    "{color_letter(color)}"
+                }
+            }
         }
     }
 }
@@ -0,0 +1,136 @@
+//! Cross Neutrality Component
+//!
+//! For the latest 3x3 scramble, shows the optimal cross on each of the six
+//! colors with the shortest one highlighted. Over several scrambles it also
+//! compares the average cross on the student's usual color with dual color
+//! and full color neutrality, and says whether switching looks worth it.
+
+use crate::components::color_picker::color_to_css;
+use crate::components::ui_kit::token::{BORDER, HIGHLIGHT, SURFACE_RAISED, TEXT, TEXT_MUTED};
+use crate::cube::{Color, Move};
+use crate::solver::color_neutral::{analyze_scramble, CROSS_COLORS};
+use crate::solver::{CrossAnalysis, NeutralityReport};
+use dioxus::prelude::*;
+
+/// Props for CrossNeutrality component
+#[derive(Props, Clone, PartialEq)]
+pub struct CrossNeutralityProps {
+    /// 3x3 scrambles to analyze, oldest first; the last one is shown in detail
+    pub scrambles: Vec<String>,
+    /// Color the student usually starts with
+    #[props(default = Color::White)]
+    pub usual_color: Color,
+}
+
+/// Capitalized color name
+fn color_label(color: Color) -> &'static str {
+    match color {
+        Color::White => "White",
+        Color::Yellow => "Yellow",
+        Color::Red => "Red",
+        Color::Orange => "Orange",
+        Color::Blue => "Blue",
+        Color::Green => "Green",
+    }
+}
+
+/// Moves as notation, or a note that the cross is already solved
+fn cross_notation(moves: &[Move]) -> String {
+    if moves.is_empty() {
+        return "already solved".to_string();
+    }
+    moves.iter().map(|m| m.to_notation()).collect::<Vec<_>>().join(" ")
+}
+
+/// Optimal cross per color and color neutrality advice
+#[component]
+pub fn CrossNeutrality(props: CrossNeutralityProps) -> Element {
+    let mut usual = use_signal(|| props.usual_color);
+    let scrambles = props.scrambles.clone();
+    // Six optimal cross searches per scramble: only redone when they change
+    let analyses = use_memo(use_reactive!(|scrambles| {
+        scrambles.iter().filter_map(|s| analyze_scramble(s).ok()).collect::<Vec<CrossAnalysis>>()
+    }));
+
+    let analyses = analyses.read();
+    let Some(latest) = analyses.last() else {
+        return rsx! {};
+    };
+    let best = latest.best().color;
+    let report = NeutralityReport::new(usual(), &analyses);
+
+    rsx! {
+        div {
+            class: "cross-neutrality",
+            style: "margin: 15px 0;",
+            div {
+                style: "display: flex; align-items: center; gap: 10px; flex-wrap: wrap;",
+                h3 { style: "font-size: 16px; margin: 0;", "Cross colors" }
+                select {
+                    style: "min-height: 44px; padding: 0 8px;",
+                    "aria-label": "Usual cross color",
+                    onchange: move |evt| {
+                        if let Some(color) = CROSS_COLORS.into_iter().find(|c| color_label(*c) == evt.value()) {
+                            usual.set(color);
+                        }
+                    },
+                    for color in CROSS_COLORS {
+                        option {
+                            value: "{color_label(color)}",
+                            selected: color == usual(),
+                            "{color_label(color)}"
+                        }
+                    }
+                }
+            }
+            p { style: "font-size: 13px; color: {TEXT_MUTED}; margin: 6px 0;", "Optimal cross on each color for the last scramble" }
+            div {
+                style: "display: grid; grid-template-columns: repeat(auto-fill, minmax(160px, 1fr)); gap: 8px;",
+                for option in latest.options.iter() {
+                    {
+                        let border = if option.color == best { HIGHLIGHT } else { BORDER };
+                        let swatch = color_to_css(option.color);
+                        rsx! {
+                            div {
+                                key: "{color_label(option.color)}",
+                                style: "padding: 8px; background: {SURFACE_RAISED}; color: {TEXT}; border: 2px solid {border}; border-radius: 4px;",
+                                div {
+                                    style: "display: flex; align-items: center; gap: 6px; font-weight: bold;",
+                                    span { style: "display: inline-block; width: 14px; height: 14px; background: {swatch}; border: 1px solid {BORDER};" }
+                                    "{color_label(option.color)}: {option.length()} moves"
+                                    if option.color == best {
+                                        span { style: "font-size: 12px; color: {TEXT_MUTED};", "(best)" }
+                                    }
+                                }
+                                div { style: "font-family: monospace; font-size: 13px; margin-top: 4px;", "{cross_notation(&option.moves)}" }
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(report) = report {
+                div {
+                    class: "cross-neutrality-report",
+                    style: "margin-top: 10px; font-size: 14px;",
+                    p {
+                        style: "margin: 4px 0;",
+                        "Average over {report.scrambles} scrambles: {color_label(report.color)} {report.fixed:.2}, dual color {report.dual:.2}, any color {report.neutral:.2}"
+                    }
+                    p { style: "margin: 4px 0; color: {TEXT_MUTED};", "Another color was shorter in {report.other_color_better} of {report.scrambles}." }
+                    p { style: "margin: 4px 0; font-weight: bold;", "{report.advice()}" }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cross_notation() {
+        assert_eq!(cross_notation(&[]), "already solved");
+        assert_eq!(cross_notation(&[Move::F, Move::RPrime]), "F R'");
+    }
+}
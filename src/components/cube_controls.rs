@@ -1,11 +1,75 @@
 //! Cube Controls Component
 //!
 //! This module provides UI controls for cube manipulation including
-//! reset to solved state with confirmation dialog.
+//! reset to solved state and switching to another cube size. Both throw
+//! away the current cube, so they wait for a confirmation dialog first
+//! (switching size skips it when there is nothing to lose).
 
 use crate::cube::Cube;
+use crate::state::CUBE_SIZES;
 use dioxus::prelude::*;
 
+/// Destructive action waiting for the user to confirm it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingAction {
+    /// Reset the cube to solved, keeping its size
+    Reset,
+    /// Start over with a solved cube of another size
+    ChangeSize(usize),
+}
+
+impl PendingAction {
+    /// Whether to ask first, given whether the current cube is solved
+    ///
+    /// Resetting always asks. Switching size only asks when the current
+    /// cube has been scrambled or painted, since a solved cube loses
+    /// nothing.
+    pub fn needs_confirmation(&self, cube_solved: bool) -> bool {
+        match self {
+            PendingAction::Reset => true,
+            PendingAction::ChangeSize(_) => !cube_solved,
+        }
+    }
+
+    /// The solved cube that replaces a cube of `cube_size`
+    pub fn new_cube(&self, cube_size: usize) -> Cube {
+        match self {
+            PendingAction::Reset => Cube::new(cube_size),
+            PendingAction::ChangeSize(size) => Cube::new(*size),
+        }
+    }
+
+    /// Dialog title
+    pub fn title(&self) -> &'static str {
+        match self {
+            PendingAction::Reset => "Reset Cube?",
+            PendingAction::ChangeSize(_) => "Change Cube Size?",
+        }
+    }
+
+    /// Dialog message about what will be lost
+    pub fn message(&self, cube_size: usize) -> String {
+        match self {
+            PendingAction::Reset => format!(
+                "This will reset your {0}x{0} cube back to the solved state. All changes will be lost.",
+                cube_size
+            ),
+            PendingAction::ChangeSize(size) => format!(
+                "This will replace your {0}x{0} cube with a solved {1}x{1} cube. All changes will be lost.",
+                cube_size, size
+            ),
+        }
+    }
+
+    /// Label of the button that goes ahead
+    pub fn confirm_label(&self) -> &'static str {
+        match self {
+            PendingAction::Reset => "Reset",
+            PendingAction::ChangeSize(_) => "Change Size",
+        }
+    }
+}
+
 #[derive(Props, Clone, PartialEq)]
 pub struct CubeControlsProps {
     /// Current cube state
@@ -20,6 +84,10 @@ pub struct CubeControlsProps {
     pub on_undo: EventHandler<()>,
     /// Callback for redo
     pub on_redo: EventHandler<()>,
+    /// Optional callback with a solved cube of the size picked in the size
+    /// selector; the selector is hidden without it
+    #[props(optional)]
+    pub on_size_change: Option<EventHandler<Cube>>,
 }
 
 /// CubeControls component provides controls for manipulating the cube.
@@ -29,6 +97,7 @@ pub struct CubeControlsProps {
 ///   - Button to reset cube to solved
 ///   - Confirmation dialog
 ///   - Works for any size
+/// - Size selector (2-20) starting over with a cube of the chosen size
 ///
 /// # Example
 /// ```rust,ignore
@@ -52,8 +121,39 @@ pub struct CubeControlsProps {
 /// ```
 #[component]
 pub fn CubeControls(props: CubeControlsProps) -> Element {
-    let mut show_confirm_dialog = use_signal(|| false);
+    let mut pending = use_signal(|| None::<PendingAction>);
     let cube_size = props.cube.size();
+    let on_reset = props.on_reset;
+    let on_size_change = props.on_size_change;
+    let cube_solved = props.cube.is_solved();
+    // The size the selector shows: the one awaiting confirmation, if any,
+    // so cancelling puts it back to the current size
+    let shown_size = match pending() {
+        Some(PendingAction::ChangeSize(size)) => size,
+        _ => cube_size,
+    };
+
+    // Carry out an action, for good: only called once confirmed (or when
+    // there is nothing to confirm)
+    let mut perform = move |action: PendingAction| {
+        let new_cube = action.new_cube(cube_size);
+        match action {
+            PendingAction::Reset => on_reset.call(new_cube),
+            PendingAction::ChangeSize(_) => {
+                if let Some(handler) = on_size_change {
+                    handler.call(new_cube);
+                }
+            }
+        }
+        pending.set(None);
+    };
+    let mut request = move |action: PendingAction| {
+        if action.needs_confirmation(cube_solved) {
+            pending.set(Some(action));
+        } else {
+            perform(action);
+        }
+    };
 
     // Compute styles based on can_undo/can_redo
     let undo_style = if props.can_undo {
@@ -106,6 +206,29 @@ pub fn CubeControls(props: CubeControlsProps) -> Element {
                 }
             }
 
+            // Size selector
+            if on_size_change.is_some() {
+                label {
+                    style: "display: flex; align-items: center; justify-content: center; gap: 0.75rem; font-size: 1.1rem; font-weight: 600;",
+                    "Cube size"
+                    select {
+                        class: "size-select",
+                        value: "{shown_size}",
+                        style: "min-height: 44px; min-width: 100px; padding: 0 8px; font-size: 1rem; border-radius: 8px;",
+                        onchange: move |evt| {
+                            if let Ok(size) = evt.value().parse::<usize>() {
+                                if size != cube_size && CUBE_SIZES.contains(&size) {
+                                    request(PendingAction::ChangeSize(size));
+                                }
+                            }
+                        },
+                        for size in CUBE_SIZES {
+                            option { value: "{size}", selected: size == shown_size, "{size}x{size}" }
+                        }
+                    }
+                }
+            }
+
             // Reset button
             button {
                 r#type: "button",
@@ -125,14 +248,12 @@ pub fn CubeControls(props: CubeControlsProps) -> Element {
                     transition: all 0.3s ease;
                     touch-action: manipulation;
                 ",
-                onclick: move |_| {
-                    show_confirm_dialog.set(true);
-                },
+                onclick: move |_| request(PendingAction::Reset),
                 "🔄 Reset to Solved"
             }
 
             // Confirmation dialog
-            if show_confirm_dialog() {
+            if let Some(action) = pending() {
                 div {
                     class: "confirm-dialog-overlay",
                     style: "
@@ -152,7 +273,7 @@ pub fn CubeControls(props: CubeControlsProps) -> Element {
                         // Close dialog when clicking on overlay
                         // In a production app, you might want to check if the click
                         // was on the overlay vs the dialog content
-                        show_confirm_dialog.set(false);
+                        pending.set(None);
                     },
 
                     div {
@@ -186,7 +307,7 @@ pub fn CubeControls(props: CubeControlsProps) -> Element {
                                 text-align: center;
                                 margin-bottom: 1rem;
                             ",
-                            "Reset Cube?"
+                            "{action.title()}"
                         }
 
                         // Dialog message
@@ -198,7 +319,7 @@ pub fn CubeControls(props: CubeControlsProps) -> Element {
                                 margin-bottom: 2rem;
                                 line-height: 1.6;
                             ",
-                            "This will reset your {cube_size}x{cube_size} cube back to the solved state. All changes will be lost."
+                            "{action.message(cube_size)}"
                         }
 
                         // Button group
@@ -228,7 +349,7 @@ pub fn CubeControls(props: CubeControlsProps) -> Element {
                                     touch-action: manipulation;
                                 ",
                                 onclick: move |_| {
-                                    show_confirm_dialog.set(false);
+                                    pending.set(None);
                                 },
                                 "Cancel"
                             }
@@ -252,13 +373,8 @@ pub fn CubeControls(props: CubeControlsProps) -> Element {
                                     transition: all 0.2s ease;
                                     touch-action: manipulation;
                                 ",
-                                onclick: move |_| {
-                                    // Create new solved cube of the same size
-                                    let new_cube = Cube::new(cube_size);
-                                    props.on_reset.call(new_cube);
-                                    show_confirm_dialog.set(false);
-                                },
-                                "Reset"
+                                onclick: move |_| perform(action),
+                                "{action.confirm_label()}"
                             }
                         }
                     }
@@ -299,4 +415,20 @@ mod tests {
             assert!(cube.is_solved(), "Cube size {} should be solved", size);
         }
     }
+
+    #[test]
+    fn test_pending_action_new_cube() {
+        assert_eq!(PendingAction::Reset.new_cube(4), Cube::new(4));
+        let resized = PendingAction::ChangeSize(7).new_cube(4);
+        assert_eq!(resized.size(), 7);
+        assert!(resized.is_solved());
+    }
+
+    #[test]
+    fn test_size_change_only_confirms_when_something_is_lost() {
+        assert!(!PendingAction::ChangeSize(5).needs_confirmation(true));
+        assert!(PendingAction::ChangeSize(5).needs_confirmation(false));
+        assert!(PendingAction::Reset.needs_confirmation(true));
+        assert!(PendingAction::ChangeSize(5).message(3).contains("5x5"));
+    }
 }
@@ -13,9 +13,39 @@
 //! - Click any sticker to select it
 //! - Visual selection indicator
 //! - Touch support for mobile
+//!
+//! Keyboard: arrow keys move the selection across the net, number keys 1-6
+//! set the selected sticker's color. Colorblind mode adds letters and
+//! patterns to the stickers.
+//!
+//! Animated net: given the move that produced the cube, stickers slide in
+//! from where they were and turning faces rotate into place, so the flat
+//! view can follow playback instead of snapping.
+//!
+//! Large cubes: the net can be zoomed, dragged to pan and focused on a single
+//! face. Only the stickers inside the visible area are rendered, so a 20x20
+//! net stays responsive.
+//!
+//! Editing tools: drag across stickers to paint them all with the chosen
+//! color, fill a whole face with one click, or pick up a sticker's color with
+//! the eyedropper. A paint stroke is reported once, when the drag ends.
+//!
+//! Flagged stickers (e.g. the ones a validation fix suggestion points at)
+//! get a dashed warning outline.
+//!
+//! Turning: with the turn tool, swiping along a row or column of stickers
+//! turns that layer, so the net works as a virtual cube. The swipe is
+//! recognized with the touch gesture settings and only turns while a
+//! one-finger swipe is bound to turning a face.
 
 use dioxus::prelude::*;
-use crate::cube::{Cube, Color, FaceName};
+use crate::cube::{Cube, Color, FaceName, ParsedMove};
+use crate::cube::notation::parse_move;
+use crate::input::{Gesture, GestureAction, GestureKind, GestureSettings, SwipeDirection};
+use crate::components::accessibility::{color_for_key, color_letter, color_pattern, letter_color};
+use crate::components::color_picker::color_name;
+use crate::components::ui_kit::token::{BORDER, FOCUS, SURFACE_RAISED, TEXT, TEXT_MUTED};
+use std::ops::Range;
 
 /// Represents a selected sticker position
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -36,6 +66,211 @@ pub struct CubeInputProps {
     /// Currently selected sticker (if any)
     #[props(optional)]
     pub selected_sticker: Option<StickerPosition>,
+    /// Optional callback when the arrow keys move the selection
+    #[props(optional)]
+    pub on_sticker_select: Option<EventHandler<StickerPosition>>,
+    /// Optional callback when a number key sets the selected sticker's color
+    #[props(optional)]
+    pub on_color_key: Option<EventHandler<(StickerPosition, Color)>>,
+    /// Overlay letters and patterns on stickers
+    #[props(default)]
+    pub colorblind_mode: bool,
+    /// Move to animate, from the previous cube to this one
+    #[props(optional)]
+    pub animation: Option<NetAnimation>,
+    /// Largest width of the visible area in pixels; bigger nets pan
+    #[props(default = 720.0)]
+    pub max_width: f32,
+    /// Largest height of the visible area in pixels; bigger nets pan
+    #[props(default = 540.0)]
+    pub max_height: f32,
+    /// Color that dragging paints with (the paint tool needs one)
+    #[props(optional)]
+    pub paint_color: Option<Color>,
+    /// Optional callback with the stickers painted by one drag
+    #[props(optional)]
+    pub on_paint_stroke: Option<EventHandler<Vec<StickerPosition>>>,
+    /// Optional callback when the fill tool is used on a face
+    #[props(optional)]
+    pub on_face_fill: Option<EventHandler<FaceName>>,
+    /// Optional callback with the color picked up by the eyedropper
+    #[props(optional)]
+    pub on_pick_color: Option<EventHandler<Color>>,
+    /// Stickers to flag as probably wrong
+    #[props(default)]
+    pub flagged_stickers: Vec<StickerPosition>,
+    /// Optional callback with the move made by swiping across the net
+    #[props(optional)]
+    pub on_turn: Option<EventHandler<ParsedMove>>,
+    /// Swipe distance and whether a swipe turns (see `GestureSettings`)
+    #[props(default)]
+    pub gestures: GestureSettings,
+}
+
+/// Cubes with at least this many layers get zoom, pan and focus controls
+const LARGE_CUBE: usize = 8;
+
+/// Size of a focused face in pixels, before zooming
+const FOCUS_FACE_SIZE: f32 = 400.0;
+
+/// Faces in the order they are drawn
+const FACES: [FaceName; 6] = [FaceName::U, FaceName::L, FaceName::F, FaceName::R, FaceName::B, FaceName::D];
+
+/// Keyframes for the animated net
+///
+/// Each animation comes in two identical copies; alternating between them
+/// restarts the animation when the next move is the same as the last.
+const NET_KEYFRAMES: &str = "
+@keyframes cube-net-slide-0 { from { transform: translate(var(--net-dx), var(--net-dy)); } to { transform: none; } }
+@keyframes cube-net-slide-1 { from { transform: translate(var(--net-dx), var(--net-dy)); } to { transform: none; } }
+@keyframes cube-net-turn-0 { from { transform: rotate(var(--net-angle)); } to { transform: none; } }
+@keyframes cube-net-turn-1 { from { transform: rotate(var(--net-angle)); } to { transform: none; } }
+@media (prefers-reduced-motion: reduce) { .cube-input-container * { animation: none !important; } }
+";
+
+/// A move to animate on the net
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetAnimation {
+    /// The move that turned the previous cube into the displayed one
+    pub mv: ParsedMove,
+    /// Changes with every move played, so a repeated move animates again
+    pub id: usize,
+    /// Length of the animation in milliseconds
+    pub duration_ms: u64,
+}
+
+/// How stickers move across the net for one move
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NetMotion {
+    /// Faces turning as a whole, with the angle turned (degrees, clockwise)
+    pub turns: Vec<(FaceName, f32)>,
+    /// Stickers sliding across the net, with the offset in pixels from
+    /// where they end to where they started
+    pub slides: Vec<(StickerPosition, f32, f32)>,
+}
+
+impl NetMotion {
+    /// Work out the motion of a move on a cube of the given size
+    pub fn for_move(mv: &ParsedMove, cube_size: usize, layout: &UnfoldedLayout) -> Self {
+        let moved = sticker_sources(mv, cube_size);
+        let n = cube_size - 1;
+        let mut motion = NetMotion::default();
+
+        for face in FACES {
+            let mut landing = moved.iter().filter(|(to, _)| to.face == face).peekable();
+            if landing.peek().is_none() {
+                continue;
+            }
+            if landing.clone().all(|(_, from)| from.face == face) {
+                // Every sticker stays on the face: the face turns
+                let (to, from) = landing.next().unwrap();
+                let angle = if (from.row, from.col) == (n - to.col, to.row) {
+                    90.0
+                } else if (from.row, from.col) == (to.col, n - to.row) {
+                    -90.0
+                } else {
+                    180.0
+                };
+                motion.turns.push((face, angle));
+            } else {
+                for (to, from) in landing {
+                    let (to_x, to_y) = sticker_origin(*to, cube_size, layout);
+                    let (from_x, from_y) = sticker_origin(*from, cube_size, layout);
+                    motion.slides.push((*to, from_x - to_x, from_y - to_y));
+                }
+            }
+        }
+        motion
+    }
+
+    /// Angle a face turns, if it turns as a whole
+    pub fn turn_of(&self, face: FaceName) -> Option<f32> {
+        self.turns.iter().find(|(f, _)| *f == face).map(|(_, angle)| *angle)
+    }
+
+    /// Offset a sticker slides in from, if it moves across the net
+    pub fn slide_of(&self, position: StickerPosition) -> Option<(f32, f32)> {
+        self.slides.iter().find(|(p, _, _)| *p == position).map(|(_, dx, dy)| (*dx, *dy))
+    }
+}
+
+/// Where each sticker moved by a move came from, as (to, from) pairs
+///
+/// Found with the cube simulation itself: each pass colors every sticker by
+/// one bit of its index, applies the move and reads the bits back, so a
+/// 20x20 net needs only 12 passes.
+pub fn sticker_sources(mv: &ParsedMove, cube_size: usize) -> Vec<(StickerPosition, StickerPosition)> {
+    let positions: Vec<StickerPosition> = FACES
+        .into_iter()
+        .flat_map(|face| {
+            (0..cube_size).flat_map(move |row| (0..cube_size).map(move |col| StickerPosition { face, row, col }))
+        })
+        .collect();
+    let bits = usize::BITS - (positions.len() - 1).leading_zeros();
+
+    let mut sources = vec![0_usize; positions.len()];
+    for bit in 0..bits {
+        let mut cube = Cube::new(cube_size);
+        for (index, p) in positions.iter().enumerate() {
+            let color = if (index >> bit) & 1 == 1 { Color::Yellow } else { Color::White };
+            cube.set_sticker(p.face, p.row, p.col, color);
+        }
+        cube.apply_parsed_move(mv);
+        for (index, p) in positions.iter().enumerate() {
+            if cube.get_face(p.face).get(p.row, p.col) == Color::Yellow {
+                sources[index] |= 1 << bit;
+            }
+        }
+    }
+
+    positions
+        .iter()
+        .zip(sources)
+        .filter(|(to, from)| **to != positions[*from])
+        .map(|(to, from)| (*to, positions[from]))
+        .collect()
+}
+
+/// Top-left corner of a sticker on the net, in pixels
+fn sticker_origin(position: StickerPosition, cube_size: usize, layout: &UnfoldedLayout) -> (f32, f32) {
+    let face_pos = FacePosition::for_face(position.face);
+    let face_width = layout.face_width(cube_size);
+    let x = face_pos.col as f32 * (face_width + layout.face_gap) + position.col as f32 * (layout.sticker_size + layout.gap);
+    let y = face_pos.row as f32 * (face_width + layout.face_gap) + position.row as f32 * (layout.sticker_size + layout.gap);
+    (x, y)
+}
+
+/// Arrow key direction for sticker navigation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl From<SwipeDirection> for NavDirection {
+    fn from(direction: SwipeDirection) -> Self {
+        match direction {
+            SwipeDirection::Up => NavDirection::Up,
+            SwipeDirection::Down => NavDirection::Down,
+            SwipeDirection::Left => NavDirection::Left,
+            SwipeDirection::Right => NavDirection::Right,
+        }
+    }
+}
+
+impl NavDirection {
+    /// Get the direction for an arrow key
+    pub fn from_key(key: &Key) -> Option<Self> {
+        match key {
+            Key::ArrowUp => Some(NavDirection::Up),
+            Key::ArrowDown => Some(NavDirection::Down),
+            Key::ArrowLeft => Some(NavDirection::Left),
+            Key::ArrowRight => Some(NavDirection::Right),
+            _ => None,
+        }
+    }
 }
 
 /// Layout configuration for the unfolded cube
@@ -65,6 +300,11 @@ impl UnfoldedLayout {
             _ => 10.0,
         };
 
+        Self::with_sticker_size(sticker_size)
+    }
+
+    /// Create layout with the given sticker size, gaps in proportion
+    pub fn with_sticker_size(sticker_size: f32) -> Self {
         Self {
             sticker_size,
             gap: sticker_size * 0.05,
@@ -73,6 +313,16 @@ impl UnfoldedLayout {
         }
     }
 
+    /// Distance from one sticker to the next
+    pub fn stride(&self) -> f32 {
+        self.sticker_size + self.gap
+    }
+
+    /// Space above the top faces for their labels
+    pub fn label_space(&self) -> f32 {
+        self.label_font_size + 4.0
+    }
+
     /// Calculate total width of a face including gaps
     pub fn face_width(&self, cube_size: usize) -> f32 {
         (cube_size as f32) * self.sticker_size + ((cube_size - 1) as f32) * self.gap
@@ -109,6 +359,371 @@ impl FacePosition {
     }
 }
 
+/// Zoom, pan and face focus of the net
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NetView {
+    /// Scale of the net (1.0 = default sticker size)
+    pub zoom: f32,
+    /// Horizontal offset of the net in the visible area (0 or less)
+    pub pan_x: f32,
+    /// Vertical offset of the net in the visible area (0 or less)
+    pub pan_y: f32,
+    /// Face shown on its own, if any
+    pub focus: Option<FaceName>,
+}
+
+impl Default for NetView {
+    fn default() -> Self {
+        Self { zoom: 1.0, pan_x: 0.0, pan_y: 0.0, focus: None }
+    }
+}
+
+impl NetView {
+    /// Smallest zoom
+    pub const MIN_ZOOM: f32 = 1.0;
+    /// Largest zoom
+    pub const MAX_ZOOM: f32 = 4.0;
+
+    /// Layout of the net at this zoom; a focused face is drawn larger
+    pub fn layout(&self, cube_size: usize) -> UnfoldedLayout {
+        let base = UnfoldedLayout::for_cube_size(cube_size);
+        let sticker_size = match self.focus {
+            Some(_) => base.sticker_size.max(FOCUS_FACE_SIZE / cube_size as f32),
+            None => base.sticker_size,
+        };
+        UnfoldedLayout::with_sticker_size(sticker_size * self.zoom)
+    }
+
+    /// Where a face is drawn in the net (before panning), if it is shown
+    pub fn face_origin(&self, face: FaceName, layout: &UnfoldedLayout, cube_size: usize) -> Option<(f32, f32)> {
+        match self.focus {
+            Some(focus) if focus == face => Some((0.0, layout.label_space())),
+            Some(_) => None,
+            None => {
+                let pos = FacePosition::for_face(face);
+                let step = layout.face_width(cube_size) + layout.face_gap;
+                Some((pos.col as f32 * step, layout.label_space() + pos.row as f32 * step))
+            }
+        }
+    }
+
+    /// Width and height of everything shown, in pixels
+    pub fn content_size(&self, cube_size: usize) -> (f32, f32) {
+        let layout = self.layout(cube_size);
+        let face = layout.face_width(cube_size);
+        match self.focus {
+            Some(_) => (face, layout.label_space() + face),
+            None => (4.0 * face + 3.0 * layout.face_gap, layout.label_space() + 3.0 * face + 2.0 * layout.face_gap),
+        }
+    }
+
+    /// Size of the visible area: the content, up to the limit
+    pub fn viewport(&self, cube_size: usize, limit: (f32, f32)) -> (f32, f32) {
+        let (width, height) = self.content_size(cube_size);
+        (width.min(limit.0), height.min(limit.1))
+    }
+
+    /// Zoom in or out, keeping the middle of the visible area in place
+    pub fn zoom_to(&mut self, zoom: f32, cube_size: usize, limit: (f32, f32)) {
+        let zoom = zoom.clamp(Self::MIN_ZOOM, Self::MAX_ZOOM);
+        let (width, height) = self.viewport(cube_size, limit);
+        let ratio = zoom / self.zoom;
+        self.pan_x = width / 2.0 - (width / 2.0 - self.pan_x) * ratio;
+        self.pan_y = height / 2.0 - (height / 2.0 - self.pan_y) * ratio;
+        self.zoom = zoom;
+        self.clamp(cube_size, limit);
+    }
+
+    /// Move the net by a number of pixels
+    pub fn pan_by(&mut self, dx: f32, dy: f32, cube_size: usize, limit: (f32, f32)) {
+        self.pan_x += dx;
+        self.pan_y += dy;
+        self.clamp(cube_size, limit);
+    }
+
+    /// Keep the net covering the visible area
+    pub fn clamp(&mut self, cube_size: usize, limit: (f32, f32)) {
+        let (content_width, content_height) = self.content_size(cube_size);
+        let (width, height) = self.viewport(cube_size, limit);
+        self.pan_x = self.pan_x.clamp(width - content_width, 0.0);
+        self.pan_y = self.pan_y.clamp(height - content_height, 0.0);
+    }
+
+    /// Show one face on its own, or the whole net with `None`
+    pub fn focus_on(&mut self, face: Option<FaceName>) {
+        self.focus = face;
+        self.pan_x = 0.0;
+        self.pan_y = 0.0;
+    }
+
+    /// Pan (or change the focused face) so a sticker is visible
+    pub fn reveal(&mut self, position: StickerPosition, cube_size: usize, limit: (f32, f32)) {
+        if self.focus.is_some_and(|face| face != position.face) {
+            self.focus_on(Some(position.face));
+        }
+        let Some((x, y)) = self.sticker_origin(position, cube_size) else { return };
+        let sticker_size = self.layout(cube_size).sticker_size;
+        let (width, height) = self.viewport(cube_size, limit);
+        self.pan_x = self.pan_x.max(-x).min(width - x - sticker_size);
+        self.pan_y = self.pan_y.max(-y).min(height - y - sticker_size);
+        self.clamp(cube_size, limit);
+    }
+
+    /// Top-left corner of a sticker in the net (before panning), if shown
+    pub fn sticker_origin(&self, position: StickerPosition, cube_size: usize) -> Option<(f32, f32)> {
+        let layout = self.layout(cube_size);
+        let (face_x, face_y) = self.face_origin(position.face, &layout, cube_size)?;
+        Some((face_x + position.col as f32 * layout.stride(), face_y + position.row as f32 * layout.stride()))
+    }
+
+    /// Sticker at a point in the net (before panning), if there is one
+    pub fn sticker_at(&self, x: f32, y: f32, cube_size: usize) -> Option<StickerPosition> {
+        let layout = self.layout(cube_size);
+        let face_width = layout.face_width(cube_size);
+        FACES.into_iter().find_map(|face| {
+            let (face_x, face_y) = self.face_origin(face, &layout, cube_size)?;
+            let (dx, dy) = (x - face_x, y - face_y);
+            if dx < 0.0 || dy < 0.0 || dx >= face_width || dy >= face_width {
+                return None;
+            }
+            let row = ((dy / layout.stride()) as usize).min(cube_size - 1);
+            let col = ((dx / layout.stride()) as usize).min(cube_size - 1);
+            Some(StickerPosition { face, row, col })
+        })
+    }
+}
+
+/// Tools for editing the net
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EditTool {
+    /// Click or drag across stickers to paint them
+    #[default]
+    Paint,
+    /// Click a face to paint all of it
+    Fill,
+    /// Click a sticker to pick up its color
+    Eyedropper,
+    /// Swipe along a row or column to turn it
+    Turn,
+}
+
+impl EditTool {
+    /// All tools, in toolbar order
+    pub const ALL: [EditTool; 4] = [EditTool::Paint, EditTool::Fill, EditTool::Eyedropper, EditTool::Turn];
+
+    /// Toolbar label
+    pub fn label(&self) -> &'static str {
+        match self {
+            EditTool::Paint => "Paint",
+            EditTool::Fill => "Fill face",
+            EditTool::Eyedropper => "Pick color",
+            EditTool::Turn => "Turn layers",
+        }
+    }
+}
+
+/// Stickers painted by one drag across the net
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaintStroke {
+    /// Pointer position (client pixels) where the drag started
+    start_client: (f64, f64),
+    /// Point in the net under the pointer when the drag started
+    start_point: (f32, f32),
+    /// Last point in the net the stroke reached
+    last_point: (f32, f32),
+    /// Stickers painted so far, in order, without repeats
+    pub stickers: Vec<StickerPosition>,
+}
+
+impl PaintStroke {
+    /// Start a stroke on a sticker, pressed at an offset inside it
+    pub fn start(
+        sticker: StickerPosition,
+        offset: (f32, f32),
+        client: (f64, f64),
+        view: &NetView,
+        cube_size: usize,
+    ) -> Option<Self> {
+        let (x, y) = view.sticker_origin(sticker, cube_size)?;
+        let point = (x + offset.0, y + offset.1);
+        Some(Self { start_client: client, start_point: point, last_point: point, stickers: vec![sticker] })
+    }
+
+    /// Follow the pointer to a new position, painting every sticker it
+    /// crosses; returns whether any sticker was added
+    pub fn extend_to(&mut self, client: (f64, f64), view: &NetView, cube_size: usize) -> bool {
+        let to = (
+            self.start_point.0 + (client.0 - self.start_client.0) as f32,
+            self.start_point.1 + (client.1 - self.start_client.1) as f32,
+        );
+        let from = self.last_point;
+        self.last_point = to;
+
+        // Sample the path finely enough that fast drags don't skip stickers
+        let step = (view.layout(cube_size).stride() / 2.0).max(1.0);
+        let distance = (to.0 - from.0).hypot(to.1 - from.1);
+        let samples = (distance / step).ceil().max(1.0) as usize;
+        let before = self.stickers.len();
+        for i in 1..=samples {
+            let t = i as f32 / samples as f32;
+            let x = from.0 + (to.0 - from.0) * t;
+            let y = from.1 + (to.1 - from.1) * t;
+            if let Some(sticker) = view.sticker_at(x, y, cube_size) {
+                if !self.stickers.contains(&sticker) {
+                    self.stickers.push(sticker);
+                }
+            }
+        }
+        self.stickers.len() > before
+    }
+
+    /// The cube with the stroke painted on, for previewing the drag
+    pub fn preview(&self, cube: &Cube, color: Color) -> Cube {
+        let mut preview = cube.clone();
+        for sticker in &self.stickers {
+            preview.set_sticker(sticker.face, sticker.row, sticker.col, color);
+        }
+        preview
+    }
+}
+
+/// Indexes of the rows (or columns) of a face that fall inside the visible
+/// area, given where the face starts on screen
+pub fn visible_range(start: f32, stride: f32, count: usize, extent: f32) -> Range<usize> {
+    let first = (-start / stride).floor().max(0.0) as usize;
+    let last = (((extent - start) / stride).ceil().max(0.0) as usize).min(count);
+    first.min(last)..last
+}
+
+/// Where a face is drawn in the visible area and which of its stickers show
+struct FaceWindow {
+    face: FaceName,
+    left: f32,
+    top: f32,
+    rows: Range<usize>,
+    cols: Range<usize>,
+}
+
+/// Move a selection one sticker across the unfolded net
+///
+/// Moving off a face continues onto the neighbouring face in the cross
+/// layout; moving off the net keeps the selection where it is.
+pub fn navigate_sticker(
+    position: StickerPosition,
+    direction: NavDirection,
+    cube_size: usize,
+) -> StickerPosition {
+    let n = cube_size as isize;
+    let face_pos = FacePosition::for_face(position.face);
+    let mut row = face_pos.row as isize * n + position.row as isize;
+    let mut col = face_pos.col as isize * n + position.col as isize;
+    let (d_row, d_col) = match direction {
+        NavDirection::Up => (-1, 0),
+        NavDirection::Down => (1, 0),
+        NavDirection::Left => (0, -1),
+        NavDirection::Right => (0, 1),
+    };
+
+    // Step over the empty cells of the net until a face is reached
+    loop {
+        row += d_row;
+        col += d_col;
+        if row < 0 || col < 0 || row >= 3 * n || col >= 4 * n {
+            return position;
+        }
+        let (grid_row, grid_col) = ((row / n) as usize, (col / n) as usize);
+        let face = [FaceName::U, FaceName::L, FaceName::F, FaceName::R, FaceName::B, FaceName::D]
+            .into_iter()
+            .find(|f| FacePosition::for_face(*f) == FacePosition { row: grid_row, col: grid_col });
+        if let Some(face) = face {
+            return StickerPosition {
+                face,
+                row: (row % n) as usize,
+                col: (col % n) as usize,
+            };
+        }
+    }
+}
+
+/// The face across the edge of `face` that is on the `direction` side of
+/// it in the net (sides off the net wrap around the cube)
+fn face_toward(face: FaceName, direction: NavDirection) -> FaceName {
+    use FaceName::*;
+    use NavDirection::{Down, Left, Right, Up};
+    match (face, direction) {
+        (U, Up) | (D, Down) => B,
+        (U, Down) | (D, Up) => F,
+        (L, Up) | (F, Up) | (R, Up) | (B, Up) => U,
+        (L, Down) | (F, Down) | (R, Down) | (B, Down) => D,
+        (U | D | F, Left) | (B, Right) => L,
+        (U | D | F, Right) | (B, Left) => R,
+        (L, Left) | (R, Right) => B,
+        (L, Right) | (R, Left) => F,
+    }
+}
+
+/// Where two stickers end up after a move
+fn track(mv: &ParsedMove, stickers: [StickerPosition; 2], cube_size: usize) -> Option<[StickerPosition; 2]> {
+    let mut cube = Cube::new(cube_size);
+    for face in FACES {
+        for row in 0..cube_size {
+            for col in 0..cube_size {
+                cube.set_sticker(face, row, col, Color::White);
+            }
+        }
+    }
+    cube.set_sticker(stickers[0].face, stickers[0].row, stickers[0].col, Color::Red);
+    cube.set_sticker(stickers[1].face, stickers[1].row, stickers[1].col, Color::Blue);
+    cube.apply_parsed_move(mv);
+    let find = |color| {
+        FACES.into_iter().find_map(|face| {
+            (0..cube_size * cube_size)
+                .map(|i| StickerPosition { face, row: i / cube_size, col: i % cube_size })
+                .find(|p| cube.get_face(face).get(p.row, p.col) == color)
+        })
+    };
+    Some([find(Color::Red)?, find(Color::Blue)?])
+}
+
+/// The quarter turn made by swiping from a sticker in a direction on the
+/// net: the layer along the swipe turns so the sticker moves that way
+///
+/// Found with the cube simulation: of every layer turn, the one that
+/// carries both the sticker and its neighbour along the swipe off their
+/// face, turned the way that takes the sticker to the face on that side.
+pub fn swipe_move(start: StickerPosition, direction: NavDirection, cube_size: usize) -> Option<ParsedMove> {
+    let n = cube_size as isize;
+    let (d_row, d_col) = match direction {
+        NavDirection::Up => (-1, 0),
+        NavDirection::Down => (1, 0),
+        NavDirection::Left => (0, -1),
+        NavDirection::Right => (0, 1),
+    };
+    // A neighbour on the same face along the swipe, ahead or behind
+    let neighbour = [1, -1].into_iter().find_map(|step| {
+        let (row, col) = (start.row as isize + step * d_row, start.col as isize + step * d_col);
+        ((0..n).contains(&row) && (0..n).contains(&col))
+            .then_some(StickerPosition { face: start.face, row: row as usize, col: col as usize })
+    })?;
+    let target = face_toward(start.face, direction);
+
+    [("R", "L"), ("U", "D"), ("F", "B")]
+        .into_iter()
+        .flat_map(|(near, far)| {
+            (1..=cube_size).map(move |layer| match layer {
+                1 => near.to_string(),
+                _ if layer == cube_size => far.to_string(),
+                _ => format!("{}{}", layer, near),
+            })
+        })
+        .flat_map(|token| [token.clone(), format!("{}'", token)])
+        .filter_map(|token| parse_move(&token).ok())
+        .find(|mv| {
+            track(mv, [start, neighbour], cube_size)
+                .is_some_and(|[moved, moved_neighbour]| moved.face == target && moved_neighbour.face != start.face)
+        })
+}
+
 /// Convert Color to CSS color string
 fn color_to_css(color: Color) -> &'static str {
     match color {
@@ -124,61 +739,332 @@ fn color_to_css(color: Color) -> &'static str {
 /// 2D Unfolded Cube View Component
 #[component]
 pub fn CubeInput(props: CubeInputProps) -> Element {
-    let cube = &props.cube;
-    let layout = UnfoldedLayout::for_cube_size(cube.size());
+    let size = props.cube.size();
+    let limit = (props.max_width, props.max_height);
+    let mut view = use_signal(NetView::default);
+    // Pointer position while dragging to pan, and whether it has moved
+    let mut drag = use_signal(|| None::<(f64, f64)>);
+    let mut dragged = use_signal(|| false);
+    let mut tool = use_signal(EditTool::default);
+    let mut stroke = use_signal(|| None::<PaintStroke>);
+    // Sticker and pointer position where a swipe to turn started
+    let mut swipe = use_signal(|| None::<(StickerPosition, (f64, f64))>);
 
-    // Calculate total dimensions
-    let face_width = layout.face_width(cube.size());
-    let face_height = layout.face_height(cube.size());
-    let total_width = 4.0 * face_width + 3.0 * layout.face_gap;
-    let total_height = 3.0 * face_height + 2.0 * layout.face_gap;
+    let editing = props.on_paint_stroke.is_some() || props.on_face_fill.is_some() || props.on_pick_color.is_some();
+    let painting = tool() == EditTool::Paint && props.paint_color.is_some() && props.on_paint_stroke.is_some();
+    let swipe_turns = props.gestures.action_for(Gesture::new(1, GestureKind::Swipe)) == Some(&GestureAction::TurnFace);
+    let turning = props.on_turn.is_some() && swipe_turns && (tool() == EditTool::Turn || !editing);
+    let tools: Vec<EditTool> = EditTool::ALL
+        .into_iter()
+        .filter(|tool| if *tool == EditTool::Turn { props.on_turn.is_some() && swipe_turns } else { editing })
+        .collect();
+
+    // Show the stroke being dragged before it is reported
+    let preview = stroke.read().as_ref().zip(props.paint_color).map(|(stroke, color)| stroke.preview(&props.cube, color));
+    let cube = preview.as_ref().unwrap_or(&props.cube);
+
+    let mut current_view = *view.read();
+    current_view.clamp(size, limit);
+    let layout = current_view.layout(size);
+    let (viewport_width, viewport_height) = current_view.viewport(size, limit);
+    let (content_width, content_height) = current_view.content_size(size);
+    let pannable = content_width > viewport_width || content_height > viewport_height;
+    let show_controls = size >= LARGE_CUBE || current_view != NetView::default();
 
     // Container style
     let container_style = format!(
-        "position: relative; width: {}px; height: {}px; margin: 0 auto;",
-        total_width, total_height
+        "position: relative; width: {}px; height: {}px; max-width: 100%; margin: 0 auto; overflow: hidden; touch-action: {};",
+        viewport_width,
+        viewport_height,
+        if pannable || painting || turning { "none" } else { "manipulation" }
+    );
+
+    let selected = props.selected_sticker;
+    let on_sticker_select = props.on_sticker_select;
+    let on_color_key = props.on_color_key;
+    let active_sticker = selected.map(sticker_id).unwrap_or_default();
+    let motion = props.animation.as_ref().map(|animation| NetMotion::for_move(&animation.mv, size, &layout));
+
+    // Releasing a drag is not a click on the sticker under the pointer
+    let on_sticker_click = props.on_sticker_click;
+    let on_face_fill = props.on_face_fill;
+    let on_pick_color = props.on_pick_color;
+    let picked_from = props.cube.clone();
+    let click = use_callback(move |(face, row, col): (FaceName, usize, usize)| {
+        if *dragged.peek() {
+            return;
+        }
+        let current_tool = *tool.peek();
+        match current_tool {
+            EditTool::Paint => {
+                if let Some(handler) = on_sticker_click {
+                    handler.call((face, row, col));
+                }
+            }
+            EditTool::Fill => {
+                if let Some(handler) = on_face_fill {
+                    handler.call(face);
+                }
+            }
+            EditTool::Eyedropper => {
+                if let Some(handler) = on_pick_color {
+                    handler.call(picked_from.get_face(face).get(row, col));
+                }
+                // Back to painting with the picked color
+                tool.set(EditTool::Paint);
+            }
+            // Turning is done by swiping, not clicking
+            EditTool::Turn => {}
+        }
+    });
+    let press = use_callback(move |StickerPress { sticker, offset, client }| {
+        if turning {
+            swipe.set(Some((sticker, client)));
+        } else if painting {
+            stroke.set(PaintStroke::start(sticker, offset, client, &view.peek(), size));
+        }
+    });
+    let handlers = StickerHandlers { click, press };
+
+    // A drag that painted reports its stroke; a tap is left to the click
+    let on_paint_stroke = props.on_paint_stroke;
+    let mut finish_stroke = move || {
+        let finished = stroke.write().take();
+        if let (Some(finished), Some(handler), true) = (finished, on_paint_stroke, *dragged.peek()) {
+            handler.call(finished.stickers);
+        }
+    };
+
+    // A swipe long enough turns the layer along it
+    let on_turn = props.on_turn;
+    let swipe_distance = props.gestures.thresholds.swipe_min_distance;
+    let mut finish_swipe = move |client: (f64, f64)| {
+        let Some((sticker, start)) = swipe.write().take() else { return };
+        let (dx, dy) = (client.0 - start.0, client.1 - start.1);
+        if dx.hypot(dy) < swipe_distance {
+            return;
+        }
+        let direction = SwipeDirection::from_delta(dx, dy).into();
+        if let (Some(mv), Some(handler)) = (swipe_move(sticker, direction, size), on_turn) {
+            handler.call(mv);
+        }
+    };
+
+    let zoom_percent = (current_view.zoom * 100.0).round();
+    let control_style = format!(
+        "padding: 8px 14px; min-height: 44px; min-width: 44px; cursor: pointer; background: {SURFACE_RAISED}; color: {TEXT}; border: 1px solid {BORDER}; border-radius: 4px;"
     );
 
     rsx! {
+        if tools.len() > 1 {
+            div {
+                class: "cube-input-tools",
+                role: "toolbar",
+                "aria-label": "Editing tools",
+                style: "display: flex; gap: 6px; flex-wrap: wrap; justify-content: center; margin-bottom: 10px;",
+                for edit_tool in tools {
+                    button {
+                        key: "{edit_tool:?}",
+                        style: "{control_style}",
+                        "aria-pressed": tool() == edit_tool,
+                        onclick: move |_| tool.set(edit_tool),
+                        "{edit_tool.label()}"
+                    }
+                }
+            }
+        }
+        if show_controls {
+            div {
+                class: "cube-input-view-controls",
+                style: "display: flex; gap: 6px; flex-wrap: wrap; justify-content: center; align-items: center; margin-bottom: 10px;",
+                button {
+                    style: "{control_style}",
+                    "aria-label": "Zoom out",
+                    disabled: current_view.zoom <= NetView::MIN_ZOOM,
+                    onclick: move |_| {
+                        let zoom = view.peek().zoom / 1.5;
+                        view.write().zoom_to(zoom, size, limit);
+                    },
+                    "−"
+                }
+                span { style: "min-width: 48px; text-align: center;", "{zoom_percent}%" }
+                button {
+                    style: "{control_style}",
+                    "aria-label": "Zoom in",
+                    disabled: current_view.zoom >= NetView::MAX_ZOOM,
+                    onclick: move |_| {
+                        let zoom = view.peek().zoom * 1.5;
+                        view.write().zoom_to(zoom, size, limit);
+                    },
+                    "+"
+                }
+                button {
+                    style: "{control_style}",
+                    "aria-pressed": current_view.focus.is_none(),
+                    onclick: move |_| view.set(NetView::default()),
+                    "Whole net"
+                }
+                for face in FACES {
+                    button {
+                        key: "{face:?}",
+                        style: "{control_style}",
+                        "aria-label": "Show only the {face:?} face",
+                        "aria-pressed": current_view.focus == Some(face),
+                        onclick: move |_| view.write().focus_on(Some(face)),
+                        "{face:?}"
+                    }
+                }
+            }
+        }
         div {
             class: "cube-input-container",
             style: "{container_style}",
+            tabindex: "0",
+            role: "group",
+            "aria-label": "Cube stickers. Arrow keys move the selection, number keys 1 to 6 set its color.",
+            "aria-activedescendant": "{active_sticker}",
+            onkeydown: move |evt| {
+                let key = evt.key();
+                if let Some(direction) = NavDirection::from_key(&key) {
+                    evt.prevent_default();
+                    let start = StickerPosition { face: FaceName::U, row: 0, col: 0 };
+                    let next = selected.map_or(start, |pos| navigate_sticker(pos, direction, size));
+                    view.write().reveal(next, size, limit);
+                    if let Some(handler) = on_sticker_select {
+                        handler.call(next);
+                    }
+                } else if let Key::Character(text) = key {
+                    if let (Some(color), Some(pos), Some(handler)) = (color_for_key(&text), selected, on_color_key) {
+                        evt.prevent_default();
+                        handler.call((pos, color));
+                    }
+                }
+            },
+            onpointerdown: move |evt| {
+                let point = evt.client_coordinates();
+                drag.set(Some((point.x, point.y)));
+                dragged.set(false);
+            },
+            onpointermove: move |evt| {
+                let Some((start_x, start_y)) = drag() else { return };
+                // A swipe turns when it ends; the net stays put meanwhile
+                if swipe.peek().is_some() {
+                    return;
+                }
+                let stroking = stroke.peek().is_some();
+                if !pannable && !stroking {
+                    return;
+                }
+                let point = evt.client_coordinates();
+                let (dx, dy) = (point.x - start_x, point.y - start_y);
+                // Small wobbles while tapping don't pan or paint
+                if !dragged() && dx.abs() + dy.abs() < 5.0 {
+                    return;
+                }
+                dragged.set(true);
+                if stroking {
+                    stroke.with_mut(|stroke| {
+                        if let Some(stroke) = stroke {
+                            stroke.extend_to((point.x, point.y), &current_view, size);
+                        }
+                    });
+                } else {
+                    drag.set(Some((point.x, point.y)));
+                    view.write().pan_by(dx as f32, dy as f32, size, limit);
+                }
+            },
+            onpointerup: move |evt| {
+                drag.set(None);
+                finish_stroke();
+                let point = evt.client_coordinates();
+                finish_swipe((point.x, point.y));
+            },
+            onpointerleave: move |evt| {
+                drag.set(None);
+                finish_stroke();
+                let point = evt.client_coordinates();
+                finish_swipe((point.x, point.y));
+            },
 
-            // Render all 6 faces in cross pattern
-            for face_name in [
-                FaceName::U,
-                FaceName::L,
-                FaceName::F,
-                FaceName::R,
-                FaceName::B,
-                FaceName::D,
-            ] {
-                {render_face(cube, face_name, &layout, props.on_sticker_click, props.selected_sticker)}
+            if props.animation.is_some() {
+                style { {NET_KEYFRAMES} }
+            }
+
+            // Render the faces in cross pattern, only the stickers in view
+            for face_name in FACES {
+                if let Some((face_x, face_y)) = current_view.face_origin(face_name, &layout, size) {
+                    {
+                        let (left, top) = (face_x + current_view.pan_x, face_y + current_view.pan_y);
+                        let window = FaceWindow {
+                            face: face_name,
+                            left,
+                            top,
+                            rows: visible_range(top, layout.stride(), size, viewport_height),
+                            cols: visible_range(left, layout.stride(), size, viewport_width),
+                        };
+                        let marks = StickerMarks { selected: props.selected_sticker, flagged: &props.flagged_stickers };
+                        render_face(cube, window, &layout, Some(handlers), marks, props.colorblind_mode, props.animation.as_ref().zip(motion.as_ref()))
+                    }
+                }
             }
         }
     }
 }
 
+/// What clicking and pressing a sticker does
+#[derive(Clone, Copy)]
+struct StickerHandlers {
+    /// Click on a sticker (face, row, col)
+    click: Callback<(FaceName, usize, usize)>,
+    /// Pointer down on a sticker, to start a paint stroke
+    press: Callback<StickerPress>,
+}
+
+/// Pointer pressed down on a sticker
+#[derive(Clone, Copy)]
+struct StickerPress {
+    sticker: StickerPosition,
+    /// Offset of the pointer inside the sticker
+    offset: (f32, f32),
+    /// Pointer position in client pixels
+    client: (f64, f64),
+}
+
+/// Stickers drawn with a mark
+#[derive(Clone, Copy)]
+struct StickerMarks<'a> {
+    /// The selected sticker
+    selected: Option<StickerPosition>,
+    /// Stickers flagged as probably wrong
+    flagged: &'a [StickerPosition],
+}
+
 /// Render a single face of the cube
 fn render_face(
     cube: &Cube,
-    face_name: FaceName,
+    window: FaceWindow,
     layout: &UnfoldedLayout,
-    on_sticker_click: Option<EventHandler<(FaceName, usize, usize)>>,
-    selected_sticker: Option<StickerPosition>,
+    handlers: Option<StickerHandlers>,
+    marks: StickerMarks,
+    colorblind_mode: bool,
+    animation: Option<(&NetAnimation, &NetMotion)>,
 ) -> Element {
-    let pos = FacePosition::for_face(face_name);
+    let FaceWindow { face: face_name, left, top, rows, cols } = window;
     let face_width = layout.face_width(cube.size());
     let face_height = layout.face_height(cube.size());
 
-    // Calculate position in pixels
-    let left = pos.col as f32 * (face_width + layout.face_gap);
-    let top = pos.row as f32 * (face_height + layout.face_gap);
-
-    let face_style = format!(
+    let mut face_style = format!(
         "position: absolute; left: {}px; top: {}px; width: {}px; height: {}px;",
         left, top, face_width, face_height
     );
+    if let Some((anim, angle)) = animation.and_then(|(anim, motion)| Some((anim, motion.turn_of(face_name)?))) {
+        face_style.push_str(&format!(
+            " animation: cube-net-turn-{} {}ms ease-in-out; --net-angle: {}deg;",
+            anim.id % 2,
+            anim.duration_ms,
+            -angle
+        ));
+    }
 
     let face = cube.get_face(face_name);
     let face_label = format!("{:?}", face_name).chars().next().unwrap();
@@ -193,7 +1079,7 @@ fn render_face(
                 class: "face-label",
                 style: format!(
                     "position: absolute; top: -{}px; left: 50%; transform: translateX(-50%); \
-                     font-size: {}px; font-weight: bold; color: #4a5568;",
+                     font-size: {}px; font-weight: bold; color: {TEXT_MUTED};",
                     layout.label_font_size + 4.0,
                     layout.label_font_size
                 ),
@@ -201,9 +1087,22 @@ fn render_face(
             }
 
             // Stickers grid
-            for row in 0..cube.size() {
-                for col in 0..cube.size() {
-                    {render_sticker(face, row, col, face_name, layout, on_sticker_click, selected_sticker)}
+            for row in rows {
+                for col in cols.clone() {
+                    {
+                        let position = StickerPosition { face: face_name, row, col };
+                        let slide = animation.and_then(|(anim, motion)| {
+                            let (dx, dy) = motion.slide_of(position)?;
+                            Some(format!(
+                                " animation: cube-net-slide-{} {}ms ease-in-out; --net-dx: {}px; --net-dy: {}px; z-index: 5;",
+                                anim.id % 2,
+                                anim.duration_ms,
+                                dx,
+                                dy
+                            ))
+                        });
+                        render_sticker(face, position, layout, handlers, marks, colorblind_mode, slide)
+                    }
                 }
             }
         }
@@ -213,13 +1112,14 @@ fn render_face(
 /// Render a single sticker
 fn render_sticker(
     face: &crate::cube::state::Face,
-    row: usize,
-    col: usize,
-    face_name: FaceName,
+    position: StickerPosition,
     layout: &UnfoldedLayout,
-    on_sticker_click: Option<EventHandler<(FaceName, usize, usize)>>,
-    selected_sticker: Option<StickerPosition>,
+    handlers: Option<StickerHandlers>,
+    marks: StickerMarks,
+    colorblind_mode: bool,
+    slide: Option<String>,
 ) -> Element {
+    let StickerPosition { face: face_name, row, col } = position;
     let color = face.get(row, col);
     let color_css = color_to_css(color);
 
@@ -227,15 +1127,24 @@ fn render_sticker(
     let top = row as f32 * (layout.sticker_size + layout.gap);
 
     // Check if this sticker is selected
-    let is_selected = selected_sticker.is_some_and(|sel| {
-        sel.face == face_name && sel.row == row && sel.col == col
-    });
+    let is_selected = marks.selected == Some(position);
+    let flagged = marks.flagged.contains(&position);
+
+    // Colorblind mode: pattern over the color, letter on top
+    let pattern = if colorblind_mode { color_pattern(color) } else { "none" };
+    let letter = if colorblind_mode { color_letter(color) } else { "" };
+    let letter_style = format!(
+        "display: flex; align-items: center; justify-content: center; width: 100%; height: 100%; \
+         font-size: {}px; font-weight: bold; color: {}; pointer-events: none;",
+        layout.sticker_size * 0.5,
+        letter_color(color)
+    );
 
     // Add visual indicator for selected sticker
-    let sticker_style = if is_selected {
+    let mut sticker_style = if is_selected {
         format!(
             "position: absolute; left: {}px; top: {}px; width: {}px; height: {}px; \
-             background: {}; border: 4px solid #3182ce; border-radius: 3px; \
+             background: {}; background-image: {pattern}; border: 4px solid {FOCUS}; border-radius: 3px; \
              cursor: pointer; transition: transform 0.1s, box-shadow 0.1s; \
              transform: scale(1.1); box-shadow: 0 0 10px rgba(49, 130, 206, 0.6); \
              z-index: 10; touch-action: manipulation;",
@@ -244,29 +1153,62 @@ fn render_sticker(
     } else {
         format!(
             "position: absolute; left: {}px; top: {}px; width: {}px; height: {}px; \
-             background: {}; border: 1px solid #2d3748; border-radius: 3px; \
+             background: {}; background-image: {pattern}; border: 1px solid #2d3748; border-radius: 3px; \
              cursor: pointer; transition: transform 0.1s, box-shadow 0.1s; touch-action: manipulation;",
             left, top, layout.sticker_size, layout.sticker_size, color_css
         )
     };
+    if flagged {
+        sticker_style.push_str(" outline: 3px dashed #dc2626; outline-offset: 2px;");
+    }
+    if let Some(slide) = slide {
+        sticker_style.push_str(&slide);
+    }
 
     rsx! {
         div {
             class: if is_selected { "sticker selected" } else { "sticker" },
+            id: "{sticker_id(position)}",
             style: "{sticker_style}",
+            role: "button",
+            "aria-label": if flagged {
+                "{face_name:?} face row {row + 1} column {col + 1}, {color_name(color)}, probably wrong"
+            } else {
+                "{face_name:?} face row {row + 1} column {col + 1}, {color_name(color)}"
+            },
+            "aria-pressed": "{is_selected}",
             onmouseenter: move |_| {},
             onclick: move |_| {
-                if let Some(ref handler) = on_sticker_click {
-                    handler.call((face_name, row, col));
+                if let Some(handlers) = handlers {
+                    handlers.click.call((face_name, row, col));
+                }
+            },
+            onpointerdown: move |evt| {
+                if let Some(handlers) = handlers {
+                    let offset = evt.element_coordinates();
+                    let client = evt.client_coordinates();
+                    handlers.press.call(StickerPress {
+                        sticker: position,
+                        offset: (offset.x as f32, offset.y as f32),
+                        client: (client.x, client.y),
+                    });
                 }
             },
             // Touch events - onclick handles both mouse and touch
             ontouchstart: move |_| {},
             title: format!("{:?} face ({}, {})", face_name, row, col),
+            if colorblind_mode {
+                span { style: "{letter_style}", "{letter}" }
+            }
         }
     }
 }
 
+/// Element id of a sticker, used for `aria-activedescendant`
+fn sticker_id(position: StickerPosition) -> String {
+    format!("sticker-{:?}-{}-{}", position.face, position.row, position.col)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,4 +1312,246 @@ mod tests {
             assert!(layout.face_gap >= 0.0);
         }
     }
+
+    #[test]
+    fn test_navigate_within_face() {
+        let pos = StickerPosition { face: FaceName::F, row: 1, col: 1 };
+        assert_eq!(
+            navigate_sticker(pos, NavDirection::Right, 3),
+            StickerPosition { face: FaceName::F, row: 1, col: 2 }
+        );
+        assert_eq!(
+            navigate_sticker(pos, NavDirection::Up, 3),
+            StickerPosition { face: FaceName::F, row: 0, col: 1 }
+        );
+    }
+
+    #[test]
+    fn test_navigate_across_faces() {
+        let pos = StickerPosition { face: FaceName::F, row: 0, col: 2 };
+        assert_eq!(
+            navigate_sticker(pos, NavDirection::Right, 3),
+            StickerPosition { face: FaceName::R, row: 0, col: 0 }
+        );
+        assert_eq!(
+            navigate_sticker(pos, NavDirection::Up, 3),
+            StickerPosition { face: FaceName::U, row: 2, col: 2 }
+        );
+
+        let pos = StickerPosition { face: FaceName::B, row: 2, col: 0 };
+        assert_eq!(
+            navigate_sticker(pos, NavDirection::Left, 3),
+            StickerPosition { face: FaceName::R, row: 2, col: 2 }
+        );
+    }
+
+    #[test]
+    fn test_navigate_off_net_stays() {
+        let pos = StickerPosition { face: FaceName::U, row: 1, col: 0 };
+        assert_eq!(navigate_sticker(pos, NavDirection::Left, 3), pos);
+
+        let pos = StickerPosition { face: FaceName::R, row: 0, col: 1 };
+        assert_eq!(navigate_sticker(pos, NavDirection::Up, 3), pos);
+
+        let pos = StickerPosition { face: FaceName::D, row: 2, col: 1 };
+        assert_eq!(navigate_sticker(pos, NavDirection::Down, 3), pos);
+    }
+
+    #[test]
+    fn test_sticker_sources_follow_the_simulation() {
+        let mv = ParsedMove::Basic(crate::cube::Move::U);
+        let moved = sticker_sources(&mv, 3);
+        // 8 stickers on U and 3 on each side face
+        assert_eq!(moved.len(), 8 + 4 * 3);
+
+        let mut cube = Cube::new(3);
+        cube.set_sticker(FaceName::F, 0, 0, Color::Blue);
+        let mut turned = cube.clone();
+        turned.apply_parsed_move(&mv);
+        let (to, _) = moved.iter().find(|(_, from)| *from == StickerPosition { face: FaceName::F, row: 0, col: 0 }).unwrap();
+        assert_eq!(turned.get_face(to.face).get(to.row, to.col), Color::Blue);
+
+        assert!(sticker_sources(&ParsedMove::Basic(crate::cube::Move::Y), 20).len() > 2000);
+    }
+
+    #[test]
+    fn test_net_motion() {
+        let layout = UnfoldedLayout::for_cube_size(3);
+        let motion = NetMotion::for_move(&ParsedMove::Basic(crate::cube::Move::R), 3, &layout);
+        assert_eq!(motion.turns.len(), 1);
+        let (face, angle) = motion.turns[0];
+        assert_eq!(face, FaceName::R);
+        assert_eq!(angle.abs(), 90.0);
+        assert_eq!(motion.turn_of(FaceName::U), None);
+        assert_eq!(motion.slides.len(), 4 * 3);
+
+        // Only the right column slides (the left column of B, as drawn)
+        assert!(motion.slides.iter().all(|(to, _, _)| to.col == if to.face == FaceName::B { 0 } else { 2 }));
+        assert!(motion.slides.iter().all(|(_, dx, dy)| *dx != 0.0 || *dy != 0.0));
+
+        let half = NetMotion::for_move(&ParsedMove::Basic(crate::cube::Move::F2), 3, &layout);
+        assert_eq!(half.turn_of(FaceName::F), Some(180.0));
+    }
+
+    #[test]
+    fn test_visible_range() {
+        // Face starting 25px above the view, 10px per sticker, 50px visible
+        assert_eq!(visible_range(-25.0, 10.0, 20, 50.0), 2..8);
+        assert_eq!(visible_range(0.0, 10.0, 3, 500.0), 0..3);
+        // Entirely outside the view
+        assert!(visible_range(600.0, 10.0, 20, 500.0).is_empty());
+        assert!(visible_range(-400.0, 10.0, 20, 500.0).is_empty());
+    }
+
+    #[test]
+    fn test_zoom_and_pan_stay_in_bounds() {
+        let limit = (720.0, 540.0);
+        let mut view = NetView::default();
+        view.pan_by(-100.0, -100.0, 10, limit);
+        assert_eq!((view.pan_x, view.pan_y), (0.0, 0.0), "The whole net fits, so it can't pan");
+
+        view.zoom_to(10.0, 20, limit);
+        assert_eq!(view.zoom, NetView::MAX_ZOOM);
+        assert!(view.pan_x < 0.0, "Zooming keeps the middle in place");
+
+        view.pan_by(-1.0e6, 1.0e6, 20, limit);
+        let (content_width, _) = view.content_size(20);
+        let (width, _) = view.viewport(20, limit);
+        assert_eq!(view.pan_x, width - content_width);
+        assert_eq!(view.pan_y, 0.0);
+
+        view.zoom_to(0.1, 10, limit);
+        assert_eq!(view, NetView::default(), "Zoomed all the way out, the whole net shows");
+    }
+
+    #[test]
+    fn test_focus_and_reveal() {
+        let limit = (720.0, 540.0);
+        let mut view = NetView::default();
+        view.focus_on(Some(FaceName::R));
+        let layout = view.layout(20);
+        assert!(layout.sticker_size > UnfoldedLayout::for_cube_size(20).sticker_size, "A focused face is larger");
+        assert_eq!(view.face_origin(FaceName::U, &layout, 20), None);
+        assert!(view.face_origin(FaceName::R, &layout, 20).is_some());
+
+        // Reaching a sticker on another face switches the focus
+        view.reveal(StickerPosition { face: FaceName::B, row: 19, col: 19 }, 20, limit);
+        assert_eq!(view.focus, Some(FaceName::B));
+
+        // Zoomed in, revealing the far corner pans to it
+        let mut view = NetView::default();
+        view.zoom_to(NetView::MAX_ZOOM, 20, limit);
+        let corner = StickerPosition { face: FaceName::D, row: 19, col: 19 };
+        view.reveal(corner, 20, limit);
+        let layout = view.layout(20);
+        let (x, y) = view.face_origin(FaceName::D, &layout, 20).unwrap();
+        let (width, height) = view.viewport(20, limit);
+        let right = x + view.pan_x + 20.0 * layout.stride();
+        let bottom = y + view.pan_y + 20.0 * layout.stride();
+        assert!(right <= width + layout.gap && bottom <= height + layout.gap);
+    }
+
+    #[test]
+    fn test_zoomed_big_net_renders_few_stickers() {
+        let limit = (720.0, 540.0);
+        let mut view = NetView::default();
+        view.zoom_to(NetView::MAX_ZOOM, 20, limit);
+        let layout = view.layout(20);
+        let (width, height) = view.viewport(20, limit);
+        let rendered: usize = FACES
+            .iter()
+            .filter_map(|face| view.face_origin(*face, &layout, 20))
+            .map(|(x, y)| {
+                let rows = visible_range(y + view.pan_y, layout.stride(), 20, height);
+                let cols = visible_range(x + view.pan_x, layout.stride(), 20, width);
+                rows.len() * cols.len()
+            })
+            .sum();
+        assert!(rendered > 0);
+        assert!(rendered < 6 * 20 * 20 / 4, "Only stickers in view are rendered ({rendered})");
+    }
+
+    #[test]
+    fn test_sticker_at() {
+        let view = NetView::default();
+        let corner = StickerPosition { face: FaceName::R, row: 2, col: 1 };
+        let (x, y) = view.sticker_origin(corner, 3).unwrap();
+        assert_eq!(view.sticker_at(x + 1.0, y + 1.0, 3), Some(corner));
+        // The empty corners of the cross hold no stickers
+        assert_eq!(view.sticker_at(1.0, view.layout(3).label_space() + 1.0, 3), None);
+    }
+
+    #[test]
+    fn test_paint_stroke() {
+        let view = NetView::default();
+        let stride = view.layout(3).stride() as f64;
+        let start = StickerPosition { face: FaceName::F, row: 1, col: 0 };
+        let mut stroke = PaintStroke::start(start, (2.0, 2.0), (100.0, 100.0), &view, 3).unwrap();
+
+        // A quick drag across the row still paints every sticker it crosses
+        assert!(stroke.extend_to((100.0 + 4.0 * stride, 100.0), &view, 3));
+        let row: Vec<_> = stroke.stickers.iter().map(|s| (s.face, s.col)).collect();
+        assert_eq!(row, vec![(FaceName::F, 0), (FaceName::F, 1), (FaceName::F, 2), (FaceName::R, 0)]);
+
+        // Going back over painted stickers adds nothing
+        assert!(!stroke.extend_to((100.0, 100.0), &view, 3));
+
+        let painted = stroke.preview(&Cube::new(3), Color::Red);
+        assert_eq!(painted.get_face(FaceName::F).get(1, 2), Color::Red);
+        assert_eq!(painted.get_face(FaceName::R).get(1, 0), Color::Red);
+        assert_ne!(painted.get_face(FaceName::F).get(0, 0), Color::Red);
+    }
+
+    #[test]
+    fn test_reveal_keeps_visible_stickers_in_place() {
+        let limit = (720.0, 540.0);
+        let mut view = NetView::default();
+        view.zoom_to(NetView::MAX_ZOOM, 20, limit);
+        let before = view;
+        let (width, height) = view.viewport(20, limit);
+        let middle = view.sticker_at(width / 2.0 - view.pan_x, height / 2.0 - view.pan_y, 20).unwrap();
+        view.reveal(middle, 20, limit);
+        assert_eq!(view, before);
+    }
+
+    #[test]
+    fn test_swipe_turns_the_row_or_column() {
+        // Swiping right along F's top row carries it onto R
+        let start = StickerPosition { face: FaceName::F, row: 0, col: 1 };
+        let mv = swipe_move(start, NavDirection::Right, 3).unwrap();
+        let mut cube = Cube::new(3);
+        let front = cube.get_face(FaceName::F).get(0, 1);
+        cube.apply_parsed_move(&mv);
+        assert_eq!(cube.get_face(FaceName::R).get(0, 0), front);
+        assert_eq!(cube.get_face(FaceName::R).get(1, 0), Cube::new(3).get_face(FaceName::R).get(1, 0));
+
+        // The middle column of a 3x3 is a slice, and swiping back undoes it
+        let middle = StickerPosition { face: FaceName::U, row: 1, col: 1 };
+        let up = swipe_move(middle, NavDirection::Up, 3).unwrap();
+        let down = swipe_move(middle, NavDirection::Down, 3).unwrap();
+        assert_eq!(down, up.inverse());
+        let mut cube = Cube::new(3);
+        cube.apply_parsed_move(&up);
+        assert!(!cube.is_solved());
+        cube.apply_parsed_move(&down);
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn test_every_swipe_moves_its_sticker_across_the_edge() {
+        let directions = [NavDirection::Up, NavDirection::Down, NavDirection::Left, NavDirection::Right];
+        for size in [2, 4] {
+            for face in FACES {
+                for (row, col) in [(0, 0), (1, size - 1), (size - 1, 1)] {
+                    let start = StickerPosition { face, row, col };
+                    let next = StickerPosition { face, row: (row + 1) % size, col };
+                    for direction in directions {
+                        let mv = swipe_move(start, direction, size).unwrap();
+                        let [moved, _] = track(&mv, [start, next], size).unwrap();
+                        assert_eq!(moved.face, face_toward(face, direction), "{:?} {:?}", start, direction);
+                    }
+                }
+            }
+        }
+    }
 }
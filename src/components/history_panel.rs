@@ -0,0 +1,182 @@
+//! History Panel Component
+//!
+//! Lists every state in the undo/redo history with a label (or the move
+//! that produced it) and a small thumbnail of the cube. Clicking an entry
+//! restores that point in time; later entries stay available for redo.
+//! Below the list, how much memory the history takes and how many undo
+//! steps it keeps, which can be changed.
+
+use crate::components::color_picker::color_to_css;
+use crate::components::ui_kit::token::{BORDER, FOCUS, HIGHLIGHT, SURFACE, SURFACE_RAISED, TEXT, TEXT_MUTED};
+use crate::cube::{Cube, FaceName};
+use crate::state::{History, HistoryLimits};
+use dioxus::prelude::*;
+
+/// Undo step limits offered in the panel
+const DEPTH_CHOICES: [usize; 5] = [25, 50, 100, 250, 500];
+
+/// Memory size for people, e.g. "512 B", "3.4 KB" or "16.0 MB"
+fn format_bytes(bytes: usize) -> String {
+    const KB: f64 = 1024.0;
+    match bytes as f64 {
+        b if b < KB => format!("{} B", bytes),
+        b if b < KB * KB => format!("{:.1} KB", b / KB),
+        b => format!("{:.1} MB", b / (KB * KB)),
+    }
+}
+
+/// Props for the CubeThumbnail component
+#[derive(Props, Clone, PartialEq)]
+pub struct CubeThumbnailProps {
+    /// The cube to draw
+    pub cube: Cube,
+    /// Width of the whole net in pixels
+    #[props(default = 64.0)]
+    pub width: f32,
+}
+
+/// Tiny unfolded view of a cube (cross layout)
+#[component]
+pub fn CubeThumbnail(props: CubeThumbnailProps) -> Element {
+    let n = props.cube.size();
+    let sticker = props.width / (4 * n) as f32;
+    let face_size = sticker * n as f32;
+    let faces = [
+        (FaceName::U, 0, 1),
+        (FaceName::L, 1, 0),
+        (FaceName::F, 1, 1),
+        (FaceName::R, 1, 2),
+        (FaceName::B, 1, 3),
+        (FaceName::D, 2, 1),
+    ];
+
+    rsx! {
+        div {
+            class: "cube-thumbnail",
+            "aria-hidden": "true",
+            style: "position: relative; width: {props.width}px; height: {face_size * 3.0}px; flex-shrink: 0;",
+            for (face, row, col) in faces {
+                div {
+                    key: "{face:?}",
+                    style: "position: absolute; left: {col as f32 * face_size}px; top: {row as f32 * face_size}px; \
+                            display: grid; grid-template-columns: repeat({n}, {sticker}px); grid-auto-rows: {sticker}px;",
+                    for (index, color) in props.cube.get_face(face).stickers().iter().flatten().enumerate() {
+                        div {
+                            key: "{index}",
+                            style: "background: {color_to_css(*color)};",
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Props for the HistoryPanel component
+#[derive(Props, Clone, PartialEq)]
+pub struct HistoryPanelProps {
+    /// The history to list
+    pub history: History,
+    /// Called with the timeline index of the entry the user picked
+    pub on_restore: EventHandler<usize>,
+    /// Called with new limits when the user picks how many undo steps to
+    /// keep; without it the limit is only shown
+    #[props(default)]
+    pub on_limits: Option<EventHandler<HistoryLimits>>,
+}
+
+/// List of every history entry with click-to-restore
+#[component]
+pub fn HistoryPanel(props: HistoryPanelProps) -> Element {
+    let timeline = props.history.timeline();
+    let on_restore = props.on_restore;
+    let limits = props.history.limits();
+    let memory = format_bytes(props.history.memory_usage());
+    let states = props.history.state_count();
+
+    rsx! {
+        div {
+            class: "history-panel",
+            style: "padding: 15px; background: {SURFACE}; color: {TEXT}; border-radius: 8px; border: 1px solid {BORDER}; max-height: 400px; overflow-y: auto;",
+            h3 { style: "margin: 0 0 10px;", "History" }
+            ol {
+                role: "listbox",
+                "aria-label": "History. Select an entry to restore it.",
+                style: "list-style: none; margin: 0; padding: 0; display: flex; flex-direction: column; gap: 6px;",
+                for entry in timeline.iter() {
+                    {
+                        let index = entry.index;
+                        let description = entry.description;
+                        let background = if entry.is_current { HIGHLIGHT } else { SURFACE_RAISED };
+                        let border = if entry.is_current { FOCUS } else { BORDER };
+                        // Entries after the current one are undone (available for redo)
+                        let opacity = if index > props.history.position() { "0.6" } else { "1" };
+                        rsx! {
+                            li {
+                                key: "{index}",
+                                role: "option",
+                                tabindex: "0",
+                                "aria-selected": "{entry.is_current}",
+                                style: "display: flex; align-items: center; gap: 10px; padding: 6px 8px; min-height: 44px; cursor: pointer; \
+                                        border-radius: 4px; background: {background}; border: 2px solid {border}; opacity: {opacity};",
+                                onclick: move |_| on_restore.call(index),
+                                onkeydown: move |evt| {
+                                    if evt.key() == Key::Enter {
+                                        evt.prevent_default();
+                                        on_restore.call(index);
+                                    }
+                                },
+                                span { style: "color: {TEXT_MUTED}; font-family: monospace; min-width: 2em;", "{index}" }
+                                CubeThumbnail { cube: entry.cube.clone() }
+                                span { style: "font-family: monospace;", "{description}" }
+                            }
+                        }
+                    }
+                }
+            }
+            div {
+                class: "history-limits",
+                style: "display: flex; flex-wrap: wrap; align-items: center; gap: 8px; margin-top: 10px; color: {TEXT_MUTED}; font-size: 0.9em;",
+                span {
+                    if let Some(max_bytes) = limits.max_bytes {
+                        "{states} states, {memory} of {format_bytes(max_bytes)}"
+                    } else {
+                        "{states} states, {memory}"
+                    }
+                }
+                if let Some(on_limits) = props.on_limits {
+                    select {
+                        style: "min-height: 44px; padding: 0 8px;",
+                        "aria-label": "Undo steps to keep",
+                        onchange: move |evt| {
+                            if let Ok(max_depth) = evt.value().parse() {
+                                on_limits.call(HistoryLimits { max_depth, ..limits });
+                            }
+                        },
+                        for depth in DEPTH_CHOICES {
+                            option {
+                                value: "{depth}",
+                                selected: depth == limits.max_depth,
+                                "Keep {depth} steps"
+                            }
+                        }
+                    }
+                } else {
+                    span { "up to {limits.max_depth} undo steps" }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(3 * 1024 + 400), "3.4 KB");
+        assert_eq!(format_bytes(16 * 1024 * 1024), "16.0 MB");
+    }
+}
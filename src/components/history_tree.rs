@@ -0,0 +1,143 @@
+//! History Tree Component
+//!
+//! Draws the branching history as a timeline: states run left to right in
+//! the order they were made, the states undo and redo move through along
+//! the top row, and every other branch on a row of its own below the state
+//! it branched from. Clicking a state (or pressing Enter on it) switches to
+//! it, branch and all.
+
+use crate::components::ui_kit::token::{BORDER, FOCUS, SURFACE, SURFACE_RAISED, TEXT, TEXT_MUTED};
+use crate::state::{History, HistoryNode};
+use dioxus::prelude::*;
+
+/// Distance between states along a branch, in SVG units
+const STEP: f32 = 36.0;
+/// Distance between branches
+const ROW: f32 = 32.0;
+/// Room around the drawing
+const MARGIN: f32 = 16.0;
+/// Radius of a state's dot
+const RADIUS: f32 = 8.0;
+
+/// Center of a state's dot
+fn center(node: &HistoryNode) -> (f32, f32) {
+    (MARGIN + node.depth as f32 * STEP, MARGIN + node.lane as f32 * ROW)
+}
+
+/// SVG path from a state to the one after it: down to the branch's row,
+/// then across
+fn link(parent: &HistoryNode, child: &HistoryNode) -> String {
+    let ((x1, y1), (x2, y2)) = (center(parent), center(child));
+    format!("M{} {} V{} H{}", x1, y1, y2, x2)
+}
+
+/// Props for the HistoryTree component
+#[derive(Props, Clone, PartialEq)]
+pub struct HistoryTreeProps {
+    /// The history to draw
+    pub history: History,
+    /// Called with the identifier of the state the user picked
+    pub on_select: EventHandler<usize>,
+}
+
+/// Branching history with click-to-switch
+#[component]
+pub fn HistoryTree(props: HistoryTreeProps) -> Element {
+    let tree = props.history.tree();
+    let on_select = props.on_select;
+    let columns = tree.iter().map(|node| node.depth).max().unwrap_or(0);
+    let rows = tree.iter().map(|node| node.lane).max().unwrap_or(0);
+    let (width, height) = (2.0 * MARGIN + columns as f32 * STEP, 2.0 * MARGIN + rows as f32 * ROW);
+    let branches = props.history.branch_count();
+    let parent_of = |node: &HistoryNode| node.parent.and_then(|parent| tree.iter().find(|other| other.id == parent));
+
+    rsx! {
+        div {
+            class: "history-tree",
+            style: "padding: 15px; background: {SURFACE}; color: {TEXT}; border-radius: 8px; border: 1px solid {BORDER}; overflow-x: auto;",
+            h3 { style: "margin: 0 0 4px;", "Branches" }
+            p {
+                style: "margin: 0 0 8px; color: {TEXT_MUTED}; font-size: 0.9em;",
+                if branches == 1 { "1 branch" } else { "{branches} branches" }
+            }
+            svg {
+                view_box: "0 0 {width} {height}",
+                width: "{width}",
+                height: "{height}",
+                role: "group",
+                "aria-label": "History branches. Select a state to switch to it.",
+                for node in tree.iter() {
+                    if let Some(parent) = parent_of(node) {
+                        path {
+                            key: "link-{node.id}",
+                            d: "{link(parent, node)}",
+                            fill: "none",
+                            stroke: if node.on_timeline { FOCUS } else { BORDER },
+                            stroke_width: "2",
+                        }
+                    }
+                }
+                for node in tree.iter() {
+                    {
+                        let id = node.id;
+                        let (x, y) = center(node);
+                        let description = node.description;
+                        let fill = if node.is_current {
+                            FOCUS
+                        } else if node.on_timeline {
+                            SURFACE_RAISED
+                        } else {
+                            SURFACE
+                        };
+                        rsx! {
+                            g {
+                                key: "{id}",
+                                role: "button",
+                                tabindex: "0",
+                                "aria-label": "{description}",
+                                "aria-current": "{node.is_current}",
+                                style: "cursor: pointer;",
+                                onclick: move |_| on_select.call(id),
+                                onkeydown: move |evt| {
+                                    if evt.key() == Key::Enter {
+                                        evt.prevent_default();
+                                        on_select.call(id);
+                                    }
+                                },
+                                circle {
+                                    cx: "{x}",
+                                    cy: "{y}",
+                                    r: "{RADIUS}",
+                                    fill: "{fill}",
+                                    stroke: if node.on_timeline { FOCUS } else { BORDER },
+                                    stroke_width: "2",
+                                }
+                                title { "{description}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::{Cube, Move};
+
+    #[test]
+    fn test_branch_links_drop_to_their_row() {
+        let mut history = History::new(Cube::new(3));
+        history.apply_grouped("R", |cube| cube.apply_move(Move::R));
+        history.undo();
+        history.apply_grouped("U", |cube| cube.apply_move(Move::U));
+
+        let tree = history.tree();
+        assert_eq!(link(&tree[0], &tree[1]), "M16 16 V16 H52");
+        // The undone R hangs below the start
+        assert_eq!(tree[2].label, Some("R"));
+        assert_eq!(link(&tree[0], &tree[2]), "M16 16 V48 H52");
+    }
+}
@@ -0,0 +1,112 @@
+//! Lesson Dashboard Component
+//!
+//! A parent/teacher view of the tutorial: time spent, lessons started,
+//! hints used and quiz averages over the whole course, then a row per
+//! lesson. Lessons whose quiz average is low are flagged so a grown-up
+//! knows where to help.
+
+use crate::components::ui_kit::token::{BORDER, HIGHLIGHT, SURFACE, TEXT, TEXT_MUTED};
+use crate::components::ui_kit::{ButtonTheme, KidBadge};
+use crate::state::{AnalyticsTotals, LessonSummary, NEEDS_HELP_PERCENT};
+use dioxus::prelude::*;
+
+/// Time spent as "45s", "12m" or "1h 05m"
+fn format_duration(seconds: u32) -> String {
+    match (seconds / 3600, seconds / 60 % 60) {
+        (0, 0) => format!("{}s", seconds),
+        (0, minutes) => format!("{}m", minutes),
+        (hours, minutes) => format!("{}h {:02}m", hours, minutes),
+    }
+}
+
+/// A quiz average, or "-" when no quiz was taken
+fn format_percent(percent: Option<f32>) -> String {
+    percent.map_or_else(|| "-".to_string(), |percent| format!("{:.0}%", percent))
+}
+
+/// Props for LessonDashboard component
+#[derive(Props, Clone, PartialEq)]
+pub struct LessonDashboardProps {
+    /// A row per lesson (see `Progress::lesson_summaries`)
+    pub lessons: Vec<LessonSummary>,
+    /// Figures over the whole course (see `Progress::analytics_totals`)
+    pub totals: AnalyticsTotals,
+}
+
+/// Course totals and a table of per-lesson analytics
+#[component]
+pub fn LessonDashboard(props: LessonDashboardProps) -> Element {
+    let totals = props.totals;
+    let flagged = props.lessons.iter().filter(|summary| summary.needs_help).count();
+    let cell = format!("padding: 8px; border-bottom: 1px solid {};", BORDER);
+
+    rsx! {
+        div {
+            class: "lesson-dashboard",
+            style: "padding: 20px; background: {SURFACE}; color: {TEXT}; border-radius: 8px; margin: 20px 0; overflow-x: auto;",
+
+            div {
+                style: "display: flex; gap: 8px; flex-wrap: wrap;",
+                KidBadge { text: format!("⏱ {}", format_duration(totals.time_seconds)), theme: ButtonTheme::Primary }
+                KidBadge { text: format!("▶ {} started", totals.attempts), theme: ButtonTheme::Secondary }
+                KidBadge { text: format!("💡 {} hints", totals.hints_used), theme: ButtonTheme::Secondary }
+                KidBadge {
+                    text: format!("✎ {} quizzes, {} average", totals.quizzes, format_percent(totals.average_quiz_percent)),
+                    theme: ButtonTheme::Success,
+                }
+            }
+            if flagged > 0 {
+                p {
+                    style: "color: {TEXT_MUTED};",
+                    "{flagged} lesson(s) may need help: quiz average below {NEEDS_HELP_PERCENT}%."
+                }
+            }
+
+            table {
+                style: "width: 100%; border-collapse: collapse; margin-top: 12px; text-align: left;",
+                thead {
+                    tr {
+                        th { style: "{cell}", "Lesson" }
+                        th { style: "{cell}", "Done" }
+                        th { style: "{cell}", "Time" }
+                        th { style: "{cell}", "Started" }
+                        th { style: "{cell}", "Hints" }
+                        th { style: "{cell}", "Quiz average" }
+                    }
+                }
+                tbody {
+                    for summary in props.lessons.iter() {
+                        tr {
+                            key: "{summary.lesson.name()}",
+                            style: if summary.needs_help { format!("background: {};", HIGHLIGHT) } else { String::new() },
+                            td { style: "{cell}", "{summary.lesson.name()}" }
+                            td { style: "{cell}", if summary.completed { "✓" } else { "" } }
+                            td { style: "{cell}", "{format_duration(summary.analytics.time_seconds)}" }
+                            td { style: "{cell}", "{summary.analytics.attempts}" }
+                            td { style: "{cell}", "{summary.analytics.hints_used}" }
+                            td {
+                                style: "{cell}",
+                                "{format_percent(summary.analytics.average_quiz_percent())}"
+                                if summary.needs_help { " ⚠" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(45), "45s");
+        assert_eq!(format_duration(12 * 60 + 30), "12m");
+        assert_eq!(format_duration(3600 + 5 * 60), "1h 05m");
+        assert_eq!(format_percent(None), "-");
+        assert_eq!(format_percent(Some(62.4)), "62%");
+    }
+}
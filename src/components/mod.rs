@@ -3,28 +3,60 @@
 //! This module contains all Dioxus UI components for the Rubik's Cube
 //! Solver & Tutorial application.
 
+pub mod accessibility;
+pub mod algorithm_browser;
 pub mod camera_scanner;
-//pub mod color_calibration;  // TODO: Fix circular dependency with camera module
+pub mod case_diagram;
+pub mod color_calibration;
 pub mod color_picker;
+pub mod cross_neutrality;
 pub mod cube_3d;
 pub mod cube_controls;
 pub mod cube_input;
+pub mod history_panel;
+pub mod history_tree;
+pub mod lesson_dashboard;
 pub mod move_display;
-//pub mod scan_correction;  // TODO: Fix type inference issues
+pub mod narration_controls;
+pub mod onboarding_wizard;
+pub mod practice_goals;
+pub mod reconstruction_viewer;
+pub mod scan_correction;
 pub mod scan_workflow;
+pub mod solve_along;
+pub mod stats_dashboard;
 pub mod solution_player;
+pub mod still_image_scanner;
 pub mod ui_kit;
 pub mod validation_feedback;
+pub mod virtual_cube;
 
+pub use algorithm_browser::AlgorithmBrowser;
 pub use camera_scanner::{CameraScanner, CameraState};
-//pub use color_calibration::ColorCalibration;  // TODO: Fix circular dependency
+pub use case_diagram::{CaseDiagram, CaseMask};
+pub use color_calibration::ColorCalibration;
 pub use color_picker::ColorPicker;
+pub use cross_neutrality::CrossNeutrality;
 pub use cube_3d::Cube3D;
-pub use cube_controls::CubeControls;
-pub use cube_input::{CubeInput, StickerPosition};
-//pub use scan_correction::{ScanCorrection, CorrectionState};  // TODO: Fix type inference issues
+pub use cube_controls::{CubeControls, PendingAction};
+pub use cube_input::{CubeInput, NavDirection, NetAnimation, StickerPosition};
+pub use history_panel::{CubeThumbnail, HistoryPanel};
+pub use history_tree::HistoryTree;
+pub use lesson_dashboard::LessonDashboard;
+pub use scan_correction::{ScanCorrection, CorrectionState};
 pub use scan_workflow::{ScanWorkflow, FacePosition, ScannedFace, ScanWorkflowState};
+pub use narration_controls::NarrationControls;
+pub use onboarding_wizard::OnboardingWizard;
+pub use practice_goals::PracticeGoals;
+pub use reconstruction_viewer::ReconstructionViewer;
 pub use solution_player::SolutionPlayer;
+pub use still_image_scanner::StillImageScanner;
+pub use solve_along::SolveAlong;
+pub use stats_dashboard::StatsDashboard;
+pub use virtual_cube::VirtualCube;
+pub use validation_feedback::ValidationFeedback;
 pub use ui_kit::{
-    ButtonSize, ButtonTheme, KidBadge, KidButton, KidCard, KidIconButton, KidProgress,
+    use_toast_provider, use_toasts, ButtonSize, ButtonTheme, KidBadge, KidButton, KidCard, KidErrorBoundary,
+    KidIconButton, KidProgress, Theme, ThemeProvider, ThemeSwitcher, ThemeTokens, Toast, ToastHost, ToastKind,
+    ToastQueue, Toaster,
 };
@@ -4,7 +4,7 @@
 //! Integrates with the highlight system to show affected pieces.
 
 use dioxus::prelude::*;
-use crate::cube::moves::{Move, WideMove};
+use crate::cube::moves::{Direction, LayerMove, Move, WideFace, WideMove};
 use crate::cube::ParsedMove;
 
 /// Props for MoveDisplay component
@@ -26,6 +26,7 @@ pub fn get_move_explanation(parsed_move: &ParsedMove) -> String {
     match parsed_move {
         ParsedMove::Basic(m) => get_basic_move_explanation(m),
         ParsedMove::Wide(w) => get_wide_move_explanation(w),
+        ParsedMove::Layer(l) => get_layer_move_explanation(l),
     }
 }
 
@@ -96,30 +97,45 @@ fn get_basic_move_explanation(m: &Move) -> String {
 
 /// Get kid-friendly explanation for wide moves
 fn get_wide_move_explanation(w: &WideMove) -> String {
-    use crate::cube::moves::{WideFace, Direction};
+    let layers = if w.depth == 2 {
+        "two layers".to_string()
+    } else {
+        format!("{} layers", w.depth)
+    };
+
+    format!("Turn {} of the {} side {}", layers, side_name(w.face), turn_words(w.direction))
+}
+
+/// Get kid-friendly explanation for numbered layer moves
+fn get_layer_move_explanation(l: &LayerMove) -> String {
+    let layers = if l.first == l.last {
+        format!("layer {}", l.first)
+    } else {
+        format!("layers {} to {}", l.first, l.last)
+    };
+
+    format!("Turn {} from the {} side {}", layers, side_name(l.face), turn_words(l.direction))
+}
 
-    let face_name = match w.face {
+/// Kid-friendly name of the side a wide or layer move turns
+fn side_name(face: WideFace) -> &'static str {
+    match face {
         WideFace::R => "right",
         WideFace::L => "left",
         WideFace::U => "top",
         WideFace::D => "bottom",
         WideFace::F => "front",
         WideFace::B => "back",
-    };
-
-    let layers = if w.depth == 2 {
-        "two layers".to_string()
-    } else {
-        format!("{} layers", w.depth)
-    };
+    }
+}
 
-    let direction = match w.direction {
+/// How far a wide or layer move turns, in words
+fn turn_words(direction: Direction) -> &'static str {
+    match direction {
         Direction::Clockwise => "clockwise",
         Direction::CounterClockwise => "counter-clockwise",
         Direction::Double => "180 degrees",
-    };
-
-    format!("Turn {} of the {} side {}", layers, face_name, direction)
+    }
 }
 
 /// Get the notation string for a parsed move
@@ -127,6 +143,7 @@ pub fn get_move_notation(parsed_move: &ParsedMove) -> String {
     match parsed_move {
         ParsedMove::Basic(m) => m.to_notation().to_string(),
         ParsedMove::Wide(w) => w.to_notation().to_string(),
+        ParsedMove::Layer(l) => l.to_notation(),
     }
 }
 
@@ -258,9 +275,17 @@ mod tests {
     }
 
     #[test]
-    fn test_get_move_explanation_wide() {
-        use crate::cube::moves::{WideFace, Direction};
+    fn test_explains_big_cube_scrambles() {
+        use crate::cube::scramble::{generate_scramble, ScrambleConfig};
 
+        let scramble = generate_scramble(&ScrambleConfig::wca(7));
+        for m in &scramble.moves {
+            assert!(get_move_explanation(m).starts_with("Turn "), "{}", m.to_notation());
+        }
+    }
+
+    #[test]
+    fn test_get_move_explanation_wide() {
         let rw = WideMove {
             face: WideFace::R,
             direction: Direction::Clockwise,
@@ -269,4 +294,14 @@ mod tests {
         let wide_move = ParsedMove::Wide(rw);
         assert_eq!(get_move_explanation(&wide_move), "Turn two layers of the right side clockwise");
     }
+
+    #[test]
+    fn test_get_move_explanation_layer() {
+        let inner = ParsedMove::Layer(LayerMove::single(WideFace::U, Direction::CounterClockwise, 3));
+        assert_eq!(get_move_explanation(&inner), "Turn layer 3 from the top side counter-clockwise");
+        assert_eq!(get_move_notation(&inner), "3U'");
+
+        let range = ParsedMove::Layer(LayerMove::new(WideFace::R, Direction::Double, 2, 3));
+        assert_eq!(get_move_explanation(&range), "Turn layers 2 to 3 from the right side 180 degrees");
+    }
 }
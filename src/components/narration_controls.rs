@@ -0,0 +1,102 @@
+//! Narration controls component
+//!
+//! Play/pause/stop buttons for reading lesson text aloud in the lesson player
+
+use crate::components::ui_kit::{ButtonSize, ButtonTheme, KidIconButton};
+use crate::i18n::{Locale, Localizer};
+use crate::tutorial::NarrationState;
+use dioxus::prelude::*;
+
+/// Props for the NarrationControls component
+#[derive(Props, Clone, PartialEq)]
+pub struct NarrationControlsProps {
+    /// Current narration state
+    pub state: NarrationState,
+    /// Called when the user presses play (or resume)
+    pub on_play: EventHandler<()>,
+    /// Called when the user presses pause
+    pub on_pause: EventHandler<()>,
+    /// Called when the user presses stop
+    pub on_stop: EventHandler<()>,
+    /// Whether narration is available on this platform
+    #[props(default = true)]
+    pub enabled: bool,
+    /// Language for button labels
+    #[props(default = Locale::English)]
+    pub locale: Locale,
+}
+
+/// Play/pause button icon, label message key, and theme for a narration state
+fn play_button(state: NarrationState) -> (&'static str, &'static str, ButtonTheme) {
+    match state {
+        NarrationState::Playing => ("⏸", "ui.narration.pause", ButtonTheme::Warning),
+        NarrationState::Paused => ("▶", "ui.narration.resume", ButtonTheme::Success),
+        NarrationState::Idle | NarrationState::Finished => {
+            ("🔊", "ui.narration.read_aloud", ButtonTheme::Primary)
+        }
+    }
+}
+
+/// Kid-friendly narration controls for the lesson player
+#[component]
+pub fn NarrationControls(props: NarrationControlsProps) -> Element {
+    let l10n = Localizer::new(props.locale);
+    let (icon, label_key, theme) = play_button(props.state);
+    let label = l10n.t(label_key);
+    let group_label = l10n.t("ui.narration.group");
+    let stop_label = l10n.t("ui.narration.stop");
+    let is_active = matches!(props.state, NarrationState::Playing | NarrationState::Paused);
+    let state = props.state;
+
+    rsx! {
+        div {
+            style: "display: flex; gap: 12px; align-items: center;",
+            role: "group",
+            "aria-label": "{group_label}",
+
+            KidIconButton {
+                icon: icon.to_string(),
+                label,
+                size: ButtonSize::Medium,
+                theme,
+                disabled: !props.enabled,
+                onclick: move |_| {
+                    if state == NarrationState::Playing {
+                        props.on_pause.call(());
+                    } else {
+                        props.on_play.call(());
+                    }
+                },
+            }
+
+            KidIconButton {
+                icon: "⏹".to_string(),
+                label: stop_label,
+                size: ButtonSize::Medium,
+                theme: ButtonTheme::Danger,
+                disabled: !props.enabled || !is_active,
+                onclick: move |_| props.on_stop.call(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_play_button_shows_pause_while_playing() {
+        let (_, label_key, theme) = play_button(NarrationState::Playing);
+        assert_eq!(Localizer::default().t(label_key), "Pause reading");
+        assert_eq!(theme, ButtonTheme::Warning);
+    }
+
+    #[test]
+    fn test_play_button_shows_play_when_idle() {
+        let (icon, _, _) = play_button(NarrationState::Idle);
+        assert_eq!(icon, "🔊");
+        let (icon, _, _) = play_button(NarrationState::Finished);
+        assert_eq!(icon, "🔊");
+    }
+}
@@ -0,0 +1,232 @@
+//! Onboarding Wizard Component
+//!
+//! The first-launch setup, one step at a time: pick the cube size (with a
+//! preview on the flat net), choose how to enter cubes, calibrate the
+//! camera with `CameraScanner` and `ColorCalibration` when scanning, and
+//! name the profile. The steps and choices live in
+//! [`Onboarding`](crate::state::Onboarding); this component only draws
+//! them.
+
+use crate::camera::{CalibrationProfile, CalibrationService, CameraFrame};
+use crate::components::ui_kit::token::{BORDER, FOCUS, SURFACE, SURFACE_RAISED, TEXT, TEXT_MUTED};
+use crate::components::ui_kit::{use_toasts, ButtonTheme, KidButton, KidProgress};
+use crate::components::{CameraScanner, CameraState, ColorCalibration, CubeInput};
+use crate::cube::Cube;
+use crate::state::{InputMethod, Onboarding, OnboardingStep, UserProfile, CUBE_SIZES, MAX_NAME_LEN};
+use dioxus::prelude::*;
+
+/// Sizes offered as buttons; the rest are in a list
+const COMMON_SIZES: [usize; 6] = [2, 3, 4, 5, 6, 7];
+
+/// One line about the finished setup, e.g. "4x4 cube, Scan with camera
+/// (calibrated)"
+fn summary(profile: &UserProfile) -> String {
+    let calibrated = if profile.calibrated { " (calibrated)" } else { "" };
+    format!("{0}x{0} cube, {1}{2}", profile.cube_size, profile.input_method.label(), calibrated)
+}
+
+/// Props for OnboardingWizard component
+#[derive(Props, Clone, PartialEq)]
+pub struct OnboardingWizardProps {
+    /// Called with the new profile when the user finishes
+    pub on_finish: EventHandler<UserProfile>,
+    /// Optional callback with the camera calibration, when one is made
+    #[props(optional)]
+    pub on_calibrated: Option<EventHandler<CalibrationProfile>>,
+    /// Optional callback when the user skips the setup
+    #[props(optional)]
+    pub on_skip: Option<EventHandler<()>>,
+}
+
+/// Guided first-launch setup
+#[component]
+pub fn OnboardingWizard(props: OnboardingWizardProps) -> Element {
+    let mut onboarding = use_signal(Onboarding::new);
+    let service = use_signal(CalibrationService::new);
+    let mut frame = use_signal(|| None::<CameraFrame>);
+    let mut toasts = use_toasts();
+
+    let state = onboarding();
+    let step = state.step();
+    let (index, count) = state.position();
+    let progress = index as f32 / (count - 1).max(1) as f32 * 100.0;
+    let size = state.cube_size();
+    let on_calibrated = props.on_calibrated;
+    let on_finish = props.on_finish;
+    let choice_style = |chosen: bool| {
+        format!(
+            "padding: 12px 16px; min-height: 44px; cursor: pointer; border-radius: 8px; text-align: left; \
+             background: {}; color: {TEXT}; border: 2px solid {};",
+            if chosen { SURFACE_RAISED } else { SURFACE },
+            if chosen { FOCUS } else { BORDER }
+        )
+    };
+
+    rsx! {
+        div {
+            class: "onboarding-wizard",
+            role: "dialog",
+            "aria-label": "Setup",
+            style: "max-width: 720px; margin: 20px auto; padding: 20px; background: {SURFACE}; color: {TEXT}; border-radius: 12px; border: 1px solid {BORDER};",
+
+            KidProgress { value: progress, label: format!("Step {} of {}", index + 1, count) }
+            h2 { "{step.title()}" }
+
+            match step {
+                OnboardingStep::Welcome => rsx! {
+                    p { "Let's get your cube ready. This takes about a minute, and you can change everything later." }
+                },
+                OnboardingStep::CubeSize => rsx! {
+                    div {
+                        role: "radiogroup",
+                        "aria-label": "Cube size",
+                        style: "display: flex; gap: 8px; flex-wrap: wrap; align-items: center;",
+                        for option in COMMON_SIZES {
+                            button {
+                                key: "{option}",
+                                role: "radio",
+                                "aria-checked": "{option == size}",
+                                style: "{choice_style(option == size)}",
+                                onclick: move |_| {
+                                    let _ = onboarding.write().set_cube_size(option);
+                                },
+                                "{option}x{option}"
+                            }
+                        }
+                        select {
+                            style: "min-height: 44px; padding: 0 8px;",
+                            "aria-label": "Bigger cubes",
+                            onchange: move |evt| {
+                                if let Ok(chosen) = evt.value().parse::<usize>() {
+                                    let _ = onboarding.write().set_cube_size(chosen);
+                                }
+                            },
+                            option { value: "", selected: size <= 7, "Bigger…" }
+                            for option in (*COMMON_SIZES.last().unwrap() + 1)..=*CUBE_SIZES.end() {
+                                option { value: "{option}", selected: option == size, "{option}x{option}" }
+                            }
+                        }
+                    }
+                    div {
+                        style: "display: flex; justify-content: center; margin-top: 12px;",
+                        CubeInput { cube: Cube::new(size), max_width: 320.0, max_height: 240.0 }
+                    }
+                },
+                OnboardingStep::InputMethod => rsx! {
+                    div {
+                        role: "radiogroup",
+                        "aria-label": "Input method",
+                        style: "display: flex; flex-direction: column; gap: 8px;",
+                        for method in InputMethod::ALL {
+                            button {
+                                key: "{method:?}",
+                                role: "radio",
+                                "aria-checked": "{state.input_method() == Some(method)}",
+                                style: "{choice_style(state.input_method() == Some(method))}",
+                                onclick: move |_| onboarding.write().set_input_method(method),
+                                strong { "{method.label()}" }
+                                div { style: "color: {TEXT_MUTED}; font-size: 0.9em;", "{method.description()}" }
+                            }
+                        }
+                    }
+                },
+                OnboardingStep::Calibration => rsx! {
+                    p {
+                        style: "color: {TEXT_MUTED};",
+                        "Optional: showing the camera each center color helps it read your cube in your lighting."
+                    }
+                    CameraScanner {
+                        cube_size: size as u32,
+                        auto_size: false,
+                        on_frame: move |captured: CameraFrame| frame.set(Some(captured)),
+                        on_state_change: move |camera: CameraState| match camera {
+                            CameraState::PermissionDenied(message) | CameraState::Error(message) => {
+                                toasts.error(format!("Camera problem: {}", message));
+                            }
+                            _ => {}
+                        },
+                    }
+                    ColorCalibration {
+                        service: service,
+                        frame: frame(),
+                        on_complete: move |profile: CalibrationProfile| {
+                            if let Some(handler) = on_calibrated {
+                                handler.call(profile);
+                            }
+                            onboarding.write().finish_calibration();
+                        },
+                    }
+                },
+                OnboardingStep::Profile => rsx! {
+                    label {
+                        style: "display: flex; flex-direction: column; gap: 6px;",
+                        "Your name"
+                        input {
+                            r#type: "text",
+                            maxlength: "{MAX_NAME_LEN}",
+                            value: "{state.name()}",
+                            style: "min-height: 44px; padding: 0 10px; font-size: 18px;",
+                            oninput: move |evt| onboarding.write().set_name(&evt.value()),
+                        }
+                    }
+                },
+                OnboardingStep::Done => rsx! {
+                    if let Some(profile) = state.profile() {
+                        p { "Hi {profile.name}! You're set up with a {summary(&profile)}." }
+                        KidButton {
+                            label: "Start cubing".to_string(),
+                            icon: "🚀".to_string(),
+                            theme: ButtonTheme::Success,
+                            onclick: move |_| on_finish.call(profile.clone()),
+                        }
+                    }
+                },
+            }
+
+            if step != OnboardingStep::Done {
+                div {
+                    style: "display: flex; gap: 10px; justify-content: space-between; flex-wrap: wrap; margin-top: 20px;",
+                    if step == OnboardingStep::Welcome {
+                        if let Some(on_skip) = props.on_skip {
+                            KidButton {
+                                label: "Skip setup".to_string(),
+                                theme: ButtonTheme::Secondary,
+                                onclick: move |_| on_skip.call(()),
+                            }
+                        }
+                    } else {
+                        KidButton {
+                            label: "Back".to_string(),
+                            theme: ButtonTheme::Secondary,
+                            onclick: move |_| {
+                                onboarding.write().back();
+                            },
+                        }
+                    }
+                    KidButton {
+                        label: if step == OnboardingStep::Calibration && !state.is_calibrated() { "Skip for now".to_string() } else { "Next".to_string() },
+                        disabled: !state.can_advance(),
+                        onclick: move |_| {
+                            onboarding.write().advance();
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        let mut profile =
+            UserProfile { name: "Sam".to_string(), cube_size: 4, input_method: InputMethod::Scan, calibrated: true };
+        assert_eq!(summary(&profile), "4x4 cube, Scan with camera (calibrated)");
+        profile.input_method = InputMethod::Virtual;
+        profile.calibrated = false;
+        assert_eq!(summary(&profile), "4x4 cube, Virtual cube");
+    }
+}
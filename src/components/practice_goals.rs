@@ -0,0 +1,147 @@
+//! Practice Goals Component
+//!
+//! Shows the daily practice streak and progress toward each daily goal
+//! ("solve Cross 3 times today"), and lets the student add or remove goals.
+
+use crate::components::ui_kit::token::{BORDER, ON_ACCENT, SURFACE, SURFACE_RAISED, TEXT, TEXT_MUTED};
+use crate::components::ui_kit::{ButtonTheme, KidBadge, KidProgress};
+use crate::state::{DailyGoal, LessonId};
+use dioxus::prelude::*;
+
+/// Targets offered when adding a goal
+const TARGETS: [u32; 4] = [1, 3, 5, 10];
+
+/// Props for PracticeGoals component
+#[derive(Props, Clone, PartialEq)]
+pub struct PracticeGoalsProps {
+    /// Current streak in days (see `Streak::as_of`)
+    pub streak: u32,
+    /// Longest streak so far
+    #[props(default)]
+    pub best_streak: u32,
+    /// Each daily goal with the successful solves made toward it today
+    pub goals: Vec<(DailyGoal, u32)>,
+    /// Called with a goal to add (or to replace the goal for its lesson)
+    pub on_set_goal: EventHandler<DailyGoal>,
+    /// Called with the lesson whose goal to remove
+    pub on_remove_goal: EventHandler<LessonId>,
+}
+
+/// Encouraging text for a streak
+fn streak_message(streak: u32) -> String {
+    match streak {
+        0 => "Practice today to start a streak!".to_string(),
+        1 => "1 day streak - come back tomorrow!".to_string(),
+        days => format!("{} day streak - keep it going!", days),
+    }
+}
+
+/// Streak badge and daily goal progress bars
+#[component]
+pub fn PracticeGoals(props: PracticeGoalsProps) -> Element {
+    let mut lesson = use_signal(|| LessonId::Cross);
+    let mut target = use_signal(|| 3_u32);
+
+    let met = props.goals.iter().filter(|(goal, done)| *done >= goal.target).count();
+    let total = props.goals.len();
+    let best = props.best_streak;
+    let primary = ButtonTheme::Primary.background_var();
+    let secondary = ButtonTheme::Secondary.background_var();
+    let streak_theme = if props.streak > 0 { ButtonTheme::Warning } else { ButtonTheme::Secondary };
+
+    rsx! {
+        div {
+            class: "practice-goals",
+            style: "padding: 20px; background: {SURFACE}; color: {TEXT}; border-radius: 8px; margin: 20px 0;",
+
+            div {
+                style: "display: flex; align-items: center; gap: 12px; flex-wrap: wrap;",
+                KidBadge { text: format!("🔥 {}", props.streak), theme: streak_theme }
+                span { style: "font-size: 18px; font-weight: bold;", "{streak_message(props.streak)}" }
+                if best > 0 {
+                    span { style: "font-size: 14px; color: {TEXT_MUTED};", "Best: {best} days" }
+                }
+            }
+
+            if total == 0 {
+                p { style: "color: {TEXT_MUTED};", "No daily goals yet. Add one below!" }
+            } else {
+                p { style: "font-size: 16px; margin: 15px 0 0;", "Goals met today: {met}/{total}" }
+            }
+
+            for (goal, done) in props.goals.iter().cloned() {
+                {
+                    let label = format!("{} ({}/{})", goal.description(), done.min(goal.target), goal.target);
+                    let value = done.min(goal.target) as f32 / goal.target.max(1) as f32 * 100.0;
+                    let remove = goal.lesson.clone();
+                    rsx! {
+                        div {
+                            key: "{goal.lesson.name()}",
+                            style: "display: flex; align-items: center; gap: 10px;",
+                            div { style: "flex: 1;", KidProgress { value, label } }
+                            button {
+                                style: "padding: 8px 14px; min-height: 44px; cursor: pointer; background: {SURFACE_RAISED}; color: {TEXT}; border: 1px solid {BORDER}; border-radius: 4px;",
+                                "aria-label": "Remove goal: {goal.description()}",
+                                onclick: move |_| props.on_remove_goal.call(remove.clone()),
+                                "✕"
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "practice-goals-add",
+                style: "display: flex; gap: 10px; align-items: center; flex-wrap: wrap; margin-top: 15px;",
+                select {
+                    style: "min-height: 44px; padding: 0 8px;",
+                    "aria-label": "Lesson to practice",
+                    onchange: move |evt| {
+                        if let Some(chosen) = LessonId::all().into_iter().find(|l| l.name() == evt.value()) {
+                            lesson.set(chosen);
+                        }
+                    },
+                    for option in LessonId::all() {
+                        option {
+                            value: "{option.name()}",
+                            selected: option == lesson(),
+                            "{option.name()}"
+                        }
+                    }
+                }
+                for count in TARGETS {
+                    {
+                        let background = if target() == count { primary.clone() } else { secondary.clone() };
+                        rsx! {
+                            button {
+                                key: "{count}",
+                                style: "padding: 8px 14px; min-height: 44px; cursor: pointer; background: {background}; color: {ON_ACCENT}; border: none; border-radius: 4px;",
+                                "aria-pressed": target() == count,
+                                onclick: move |_| target.set(count),
+                                "{count}×"
+                            }
+                        }
+                    }
+                }
+                button {
+                    class: "btn btn-add-goal",
+                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: {primary}; color: {ON_ACCENT}; border: none; border-radius: 4px; touch-action: manipulation;",
+                    onclick: move |_| props.on_set_goal.call(DailyGoal::new(lesson(), target())),
+                    "Set daily goal"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streak_message() {
+        assert_eq!(streak_message(0), "Practice today to start a streak!");
+        assert!(streak_message(1).starts_with("1 day streak"));
+        assert!(streak_message(7).starts_with("7 day streak"));
+    }
+}
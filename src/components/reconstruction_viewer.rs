@@ -0,0 +1,208 @@
+//! Reconstruction Viewer Component
+//!
+//! Loads a recorded solve (scramble + timestamped moves) and shows it the
+//! way reconstruction sites do: total time and TPS, CFOP phase splits, the
+//! move list, and a scrubber that drives the 3D view.
+
+use crate::components::Cube3D;
+use crate::components::ui_kit::token::{BORDER, HIGHLIGHT, ON_ACCENT, SURFACE, SURFACE_RAISED, TEXT, TEXT_MUTED};
+use crate::components::ui_kit::ButtonTheme;
+use crate::state::{format_time, PhaseSplit, Replay};
+use dioxus::prelude::*;
+
+/// Props for ReconstructionViewer component
+#[derive(Props, Clone, PartialEq)]
+pub struct ReconstructionViewerProps {
+    /// The recorded solve to show
+    pub replay: Replay,
+    /// Viewport width passed to the 3D view
+    #[props(default = 400.0)]
+    pub viewport_width: f32,
+    /// Viewport height passed to the 3D view
+    #[props(default = 400.0)]
+    pub viewport_height: f32,
+}
+
+/// Find which phase a move index belongs to
+fn phase_at(splits: &[PhaseSplit], move_index: usize) -> Option<usize> {
+    splits
+        .iter()
+        .position(|s| move_index >= s.first_move && move_index < s.first_move + s.move_count)
+}
+
+/// Reconstruction viewer with phase splits and a move scrubber
+#[component]
+pub fn ReconstructionViewer(props: ReconstructionViewerProps) -> Element {
+    // Number of moves applied to the scrambled cube
+    let mut position = use_signal(|| 0_usize);
+
+    let replay = &props.replay;
+    let total_moves = replay.move_count();
+    let current = (*position.read()).min(total_moves);
+    let cube = replay.state_after(current);
+    // Replaying for splits is the slow part: only redone for a new solve
+    let recorded = props.replay.clone();
+    let splits = use_memo(use_reactive!(|recorded| recorded.phase_splits()));
+    let splits = splits.read();
+    let current_phase = current.checked_sub(1).and_then(|index| phase_at(&splits, index));
+
+    let total_time = format_time(replay.duration_ms());
+    let tps = format!("{:.2}", replay.tps());
+    let current_time = format_time(current.checked_sub(1).map_or(0, |i| replay.moves[i].time_ms));
+    let primary = ButtonTheme::Primary.background_var();
+    let secondary = ButtonTheme::Secondary.background_var();
+    let scramble = replay
+        .scramble
+        .iter()
+        .map(|m| m.to_notation())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    rsx! {
+        div {
+            class: "reconstruction-viewer",
+            style: "padding: 20px; background: {SURFACE}; color: {TEXT}; border-radius: 8px; margin: 20px 0;",
+
+            div {
+                class: "reconstruction-summary",
+                style: "display: flex; gap: 24px; flex-wrap: wrap; margin-bottom: 15px;",
+                p { style: "font-size: 18px; font-weight: bold; margin: 5px 0;", "Time: {total_time}s" }
+                p { style: "font-size: 18px; margin: 5px 0;", "Moves: {total_moves}" }
+                p { style: "font-size: 18px; margin: 5px 0;", "TPS: {tps}" }
+            }
+
+            p {
+                style: "font-family: monospace; font-size: 14px; color: {TEXT_MUTED}; margin: 5px 0 15px;",
+                "Scramble: {scramble}"
+            }
+
+            Cube3D {
+                cube,
+                viewport_width: props.viewport_width,
+                viewport_height: props.viewport_height,
+            }
+
+            div {
+                class: "reconstruction-scrubber",
+                style: "display: flex; gap: 10px; align-items: center; margin: 15px 0;",
+
+                button {
+                    class: "btn btn-step-back",
+                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: {secondary}; color: {ON_ACCENT}; border: none; border-radius: 4px; touch-action: manipulation;",
+                    disabled: current == 0,
+                    onclick: move |_| position.set(current.saturating_sub(1)),
+                    "◄"
+                }
+
+                input {
+                    r#type: "range",
+                    min: "0",
+                    max: "{total_moves}",
+                    value: "{current}",
+                    style: "flex: 1; min-height: 44px;",
+                    "aria-label": "Scrub through the solve",
+                    oninput: move |evt| {
+                        if let Ok(value) = evt.value().parse::<usize>() {
+                            position.set(value.min(total_moves));
+                        }
+                    },
+                }
+
+                button {
+                    class: "btn btn-step-forward",
+                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: {secondary}; color: {ON_ACCENT}; border: none; border-radius: 4px; touch-action: manipulation;",
+                    disabled: current >= total_moves,
+                    onclick: move |_| position.set((current + 1).min(total_moves)),
+                    "►"
+                }
+
+                span {
+                    style: "font-family: monospace; min-width: 120px; text-align: right;",
+                    "{current}/{total_moves} · {current_time}s"
+                }
+            }
+
+            if !splits.is_empty() {
+                table {
+                    class: "phase-splits",
+                    style: "width: 100%; border-collapse: collapse; background: {SURFACE_RAISED}; border: 1px solid {BORDER}; margin-bottom: 15px;",
+                    thead {
+                        tr {
+                            th { style: "text-align: left; padding: 8px;", "Phase" }
+                            th { style: "text-align: right; padding: 8px;", "Time" }
+                            th { style: "text-align: right; padding: 8px;", "Moves" }
+                            th { style: "text-align: right; padding: 8px;", "TPS" }
+                        }
+                    }
+                    tbody {
+                        for (index, split) in splits.iter().enumerate() {
+                            {
+                                let end = split.first_move + split.move_count;
+                                let duration = format_time(split.duration_ms());
+                                let phase_tps = format!("{:.2}", split.tps());
+                                let background = if current_phase == Some(index) { HIGHLIGHT } else { SURFACE_RAISED };
+                                rsx! {
+                                    tr {
+                                        key: "{split.phase.label()}",
+                                        style: "cursor: pointer; background: {background};",
+                                        onclick: move |_| position.set(end),
+                                        td { style: "padding: 8px;", "{split.phase.label()}" }
+                                        td { style: "text-align: right; padding: 8px;", "{duration}s" }
+                                        td { style: "text-align: right; padding: 8px;", "{split.move_count}" }
+                                        td { style: "text-align: right; padding: 8px;", "{phase_tps}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "reconstruction-moves",
+                style: "display: flex; flex-wrap: wrap; gap: 6px; background: {SURFACE_RAISED}; padding: 15px; border-radius: 4px; border: 1px solid {BORDER}; font-family: monospace;",
+                for (index, timed) in replay.moves.iter().enumerate() {
+                    {
+                        let notation = timed.mv.to_notation();
+                        let style = if index + 1 == current {
+                            format!("padding: 4px 6px; cursor: pointer; border-radius: 4px; background: {primary}; color: {ON_ACCENT};")
+                        } else {
+                            "padding: 4px 6px; cursor: pointer; border-radius: 4px;".to_string()
+                        };
+                        rsx! {
+                            span {
+                                key: "{index}",
+                                style,
+                                onclick: move |_| position.set(index + 1),
+                                "{notation}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Phase;
+
+
+    #[test]
+    fn test_phase_at() {
+        let split = |phase, first_move, move_count| PhaseSplit {
+            phase,
+            first_move,
+            move_count,
+            start_ms: 0,
+            end_ms: 0,
+        };
+        let splits = [split(Phase::Cross, 0, 0), split(Phase::F2l, 0, 3), split(Phase::Oll, 3, 2)];
+
+        assert_eq!(phase_at(&splits, 0), Some(1));
+        assert_eq!(phase_at(&splits, 3), Some(2));
+        assert_eq!(phase_at(&splits, 5), None);
+    }
+}
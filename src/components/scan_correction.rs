@@ -1,13 +1,18 @@
-/// Scan correction component for handling failed or uncertain scans
-///
-/// This component provides:
-/// - Detection of uncertain colors (low confidence)
-/// - Manual correction UI for fixing incorrect colors
-/// - Retry scan option to re-scan the face
+//! Scan correction component for handling failed or uncertain scans
+//!
+//! This component provides:
+//! - Detection of uncertain colors (low confidence)
+//! - A confidence heatmap: green, yellow or red borders by detection confidence
+//! - Manual correction UI for fixing incorrect colors
+//! - Retry scan option to re-scan the face
+//!
+//! Red (uncertain) cells must be confirmed or corrected before the face can
+//! be accepted.
 
 use dioxus::prelude::*;
+use std::collections::HashSet;
 use crate::cube::Color;
-use crate::camera::color_detect::ColorDetectionResult;
+use crate::camera::color_detect::{ColorDetectionResult, ConfidenceLevel};
 
 /// Position of a sticker in the grid
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -27,6 +32,23 @@ pub enum CorrectionState {
     Complete,
 }
 
+/// Uncertain cells the user has not yet confirmed or corrected
+pub fn unresolved_cells(
+    detected_colors: &[Vec<ColorDetectionResult>],
+    reviewed: &HashSet<StickerPosition>,
+) -> Vec<StickerPosition> {
+    detected_colors
+        .iter()
+        .enumerate()
+        .flat_map(|(row, cells)| {
+            cells.iter().enumerate().filter_map(move |(col, result)| {
+                let pos = StickerPosition { row, col };
+                (result.level() == ConfidenceLevel::Low && !reviewed.contains(&pos)).then_some(pos)
+            })
+        })
+        .collect()
+}
+
 #[derive(Props, Clone, PartialEq)]
 pub struct ScanCorrectionProps {
     /// Detected colors with confidence scores
@@ -56,6 +78,9 @@ pub fn ScanCorrection(props: ScanCorrectionProps) -> Element {
             .map(|row| row.iter().map(|result| result.color).collect())
             .collect::<Vec<Vec<Color>>>()
     });
+    // Cells confirmed or corrected by the user
+    let mut reviewed = use_signal(HashSet::<StickerPosition>::new);
+    let unresolved = unresolved_cells(&props.detected_colors, &reviewed.read()).len();
 
     // Count uncertain colors
     let uncertain_count = props.detected_colors
@@ -76,12 +101,25 @@ pub fn ScanCorrection(props: ScanCorrectionProps) -> Element {
     let mut apply_color = move |color: Color| {
         if let Some(pos) = selected_sticker() {
             corrected_colors.write()[pos.row][pos.col] = color;
+            reviewed.write().insert(pos);
             selected_sticker.set(None);
         }
     };
 
-    // Confirm the corrected scan
+    // Keep the detected color of the selected sticker
+    let keep_color = move |_| {
+        if let Some(pos) = selected_sticker() {
+            reviewed.write().insert(pos);
+            selected_sticker.set(None);
+        }
+    };
+
+    // Confirm the corrected scan, once every red cell has been checked
     let confirm_scan = move |_| {
+        if unresolved > 0 {
+            return;
+        }
+        correction_state.set(CorrectionState::Complete);
         if let Some(handler) = &props.on_confirm {
             handler.call(corrected_colors());
         }
@@ -135,7 +173,7 @@ pub fn ScanCorrection(props: ScanCorrectionProps) -> Element {
                             }
                             p {
                                 style: "font-size: 12px; color: #78350f; margin: 0;",
-                                "Colors with low confidence are highlighted in yellow. Click them to correct."
+                                "Red borders mark colors with low confidence. Click each one to keep or correct it."
                             }
                         }
                     }
@@ -168,7 +206,13 @@ pub fn ScanCorrection(props: ScanCorrectionProps) -> Element {
                                 let is_selected = selected_sticker()
                                     .map(|pos| pos.row == row_idx && pos.col == col_idx)
                                     .unwrap_or(false);
-                                let is_uncertain = result.is_uncertain();
+                                let level = result.level();
+                                let is_reviewed = reviewed.read().contains(&StickerPosition { row: row_idx, col: col_idx });
+                                let border = if is_reviewed {
+                                    format!("3px solid {}", ConfidenceLevel::High.css_color())
+                                } else {
+                                    format!("3px solid {}", level.css_color())
+                                };
 
                                 // Use corrected color if available
                                 let display_color = corrected_colors()[row_idx][col_idx];
@@ -177,6 +221,8 @@ pub fn ScanCorrection(props: ScanCorrectionProps) -> Element {
                                 rsx! {
                                     div {
                                         key: "{row_idx}-{col_idx}",
+                                        role: "button",
+                                        "aria-label": "Row {row_idx + 1} column {col_idx + 1}, {display_color:?}, {result.confidence * 100.0:.0}% sure",
                                         onclick: move |_| select_sticker(row_idx, col_idx),
                                         style: format!(
                                             "width: 50px; height: 50px; background: {}; \
@@ -190,11 +236,7 @@ pub fn ScanCorrection(props: ScanCorrectionProps) -> Element {
                                             } else {
                                                 "0 2px 4px rgba(0,0,0,0.3)"
                                             },
-                                            if is_uncertain {
-                                                "3px solid #f59e0b"
-                                            } else {
-                                                "none"
-                                            }
+                                            border
                                         ),
 
                                         // Confidence indicator
@@ -204,11 +246,13 @@ pub fn ScanCorrection(props: ScanCorrectionProps) -> Element {
                                                  font-size: 10px; font-weight: bold; \
                                                  background: rgba(0,0,0,0.6); color: {}; \
                                                  padding: 2px 4px; border-radius: 3px;",
-                                                if result.is_reliable() { "#22c55e" }
-                                                else if is_uncertain { "#f59e0b" }
-                                                else { "#fbbf24" }
+                                                if is_reviewed { ConfidenceLevel::High.css_color() } else { level.css_color() }
                                             ),
-                                            {format!("{:.0}%", result.confidence * 100.0)}
+                                            if is_reviewed {
+                                                "✓"
+                                            } else {
+                                                {format!("{:.0}%", result.confidence * 100.0)}
+                                            }
                                         }
                                     }
                                 }
@@ -232,6 +276,14 @@ pub fn ScanCorrection(props: ScanCorrectionProps) -> Element {
                     div {
                         style: "display: flex; gap: 10px; flex-wrap: wrap;",
 
+                        button {
+                            onclick: keep_color,
+                            style: "height: 60px; padding: 0 16px; font-size: 14px; font-weight: 600; \
+                                   background: #22c55e; color: white; border: none; \
+                                   border-radius: 8px; cursor: pointer;",
+                            "✓ Looks right"
+                        }
+
                         for color in [Color::White, Color::Yellow, Color::Red, Color::Orange, Color::Blue, Color::Green] {
                             {
                                 let css_color = color_to_css(color);
@@ -277,11 +329,20 @@ pub fn ScanCorrection(props: ScanCorrectionProps) -> Element {
 
                 button {
                     onclick: confirm_scan,
-                    style: "flex: 2; padding: 14px 20px; font-size: 16px; font-weight: 600; \
-                           background: linear-gradient(135deg, #22c55e 0%, #16a34a 100%); \
-                           color: white; border: none; border-radius: 8px; cursor: pointer; \
-                           transition: transform 0.2s; box-shadow: 0 4px 6px rgba(0,0,0,0.1);",
-                    "✓ Confirm"
+                    disabled: unresolved > 0,
+                    style: format!(
+                        "flex: 2; padding: 14px 20px; font-size: 16px; font-weight: 600; \
+                         background: linear-gradient(135deg, #22c55e 0%, #16a34a 100%); \
+                         color: white; border: none; border-radius: 8px; cursor: {}; opacity: {}; \
+                         transition: transform 0.2s; box-shadow: 0 4px 6px rgba(0,0,0,0.1);",
+                        if unresolved > 0 { "not-allowed" } else { "pointer" },
+                        if unresolved > 0 { 0.5 } else { 1.0 }
+                    ),
+                    if unresolved > 0 {
+                        {format!("Check {} red cell{} first", unresolved, if unresolved == 1 { "" } else { "s" })}
+                    } else {
+                        "✓ Confirm"
+                    }
                 }
             }
         }
@@ -374,4 +435,23 @@ mod tests {
 
         assert_eq!(set.len(), 1); // Should only have one entry
     }
+
+    #[test]
+    fn test_unresolved_cells() {
+        let cell = |confidence| ColorDetectionResult { color: Color::Red, confidence };
+        let detected = vec![vec![cell(0.9), cell(0.4)], vec![cell(0.7), cell(0.2)]];
+
+        let mut reviewed = HashSet::new();
+        assert_eq!(
+            unresolved_cells(&detected, &reviewed),
+            vec![StickerPosition { row: 0, col: 1 }, StickerPosition { row: 1, col: 1 }]
+        );
+
+        reviewed.insert(StickerPosition { row: 0, col: 1 });
+        assert_eq!(unresolved_cells(&detected, &reviewed), vec![StickerPosition { row: 1, col: 1 }]);
+
+        // Yellow cells don't block accepting the face
+        reviewed.insert(StickerPosition { row: 1, col: 1 });
+        assert!(unresolved_cells(&detected, &reviewed).is_empty());
+    }
 }
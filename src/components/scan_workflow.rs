@@ -3,10 +3,23 @@
 /// This component guides users through the process of scanning all 6 faces
 /// of their cube using the camera. It tracks which faces have been scanned
 /// and allows re-scanning individual faces if needed.
+///
+/// With `debug_capture` on, every scan attempt (the frame, the grid it was
+/// sampled with and the classified colors) is kept in a debug session that
+/// can be saved as a folder and attached to a bug report.
+///
+/// Photo scans keep the detector's confidence in each sticker. If the
+/// finished scan isn't a solvable cube, the fewest changes to unsure
+/// stickers that fix it are offered as a correction.
 
 use dioxus::prelude::*;
-use crate::cube::{Color, Cube};
+use crate::cube::{Color, Cube, FaceName};
 use crate::cube::state::Face;
+use crate::cube::validation::{sticker_name, ValidationError};
+use crate::camera::scan_debug::ScanDebugSession;
+use crate::camera::scan_repair::{face_confidence, repair_scan, ScanRepair, StickerConfidence};
+use crate::camera::still_image::ImageScan;
+use crate::components::still_image_scanner::StillImageScanner;
 
 /// Represents a face of the cube (which one to scan)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -56,6 +69,18 @@ impl FacePosition {
         }
     }
 
+    /// The cube face this position scans
+    pub fn face_name(&self) -> FaceName {
+        match self {
+            FacePosition::Front => FaceName::F,
+            FacePosition::Back => FaceName::B,
+            FacePosition::Right => FaceName::R,
+            FacePosition::Left => FaceName::L,
+            FacePosition::Up => FaceName::U,
+            FacePosition::Down => FaceName::D,
+        }
+    }
+
     /// Get the color typically on this face in a solved cube
     pub fn solved_color(&self) -> Color {
         match self {
@@ -80,6 +105,19 @@ pub struct ScannedFace {
     pub confirmed: bool,
 }
 
+/// Build a cube of `size` from the scanned faces
+pub fn scanned_cube(size: usize, faces: &[ScannedFace]) -> Cube {
+    let mut cube = Cube::new(size);
+    for scanned in faces {
+        for (row, row_colors) in scanned.colors.iter().enumerate() {
+            for (col, color) in row_colors.iter().enumerate() {
+                cube.set_sticker(scanned.position.face_name(), row, col, *color);
+            }
+        }
+    }
+    cube
+}
+
 /// State of the scan workflow
 #[derive(Debug, Clone, PartialEq)]
 pub enum ScanWorkflowState {
@@ -106,6 +144,21 @@ pub struct ScanWorkflowProps {
     /// Callback when workflow is cancelled
     #[props(optional)]
     pub on_cancel: Option<EventHandler<()>>,
+
+    /// Keep the frames and detection results of every scan attempt
+    #[props(default = false)]
+    pub debug_capture: bool,
+}
+
+/// Name for a new debug session, from the current time
+fn debug_session_id() -> String {
+    #[cfg(target_arch = "wasm32")]
+    let millis = web_sys::js_sys::Date::now() as u64;
+    #[cfg(not(target_arch = "wasm32"))]
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64);
+    format!("scan-{}", millis)
 }
 
 #[component]
@@ -113,6 +166,11 @@ pub fn ScanWorkflow(props: ScanWorkflowProps) -> Element {
     let mut workflow_state = use_signal(|| ScanWorkflowState::NotStarted);
     let mut scanned_faces = use_signal(|| Vec::<ScannedFace>::new());
     let mut current_face_data = use_signal(|| Vec::<Vec<Color>>::new());
+    let mut current_face_confidence = use_signal(StickerConfidence::new);
+    let mut scan_confidence = use_signal(StickerConfidence::new);
+    let debug_capture = props.debug_capture;
+    let mut debug_session = use_signal(move || debug_capture.then(|| ScanDebugSession::new(debug_session_id())));
+    let debug_saved = use_signal(|| None::<String>);
 
     // Start the workflow
     let start_workflow = move |_| {
@@ -126,6 +184,19 @@ pub fn ScanWorkflow(props: ScanWorkflowProps) -> Element {
         let color = face.solved_color();
         let colors = vec![vec![color; size]; size];
         current_face_data.set(colors.clone());
+        current_face_confidence.set(StickerConfidence::new());
+        workflow_state.set(ScanWorkflowState::Reviewing(face));
+    };
+
+    // Use the colors read from a photo instead of the camera
+    let mut load_photo = move |face: FacePosition, scan: ImageScan| {
+        let colors = scan
+            .colors
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.color).collect())
+            .collect();
+        current_face_data.set(colors);
+        current_face_confidence.set(face_confidence(face.face_name(), &scan.colors));
         workflow_state.set(ScanWorkflowState::Reviewing(face));
     };
 
@@ -136,6 +207,8 @@ pub fn ScanWorkflow(props: ScanWorkflowProps) -> Element {
 
             // Remove existing scan of this face if any
             scanned_faces.write().retain(|sf| sf.position != face);
+            scan_confidence.write().retain(|(f, _, _), _| *f != face.face_name());
+            scan_confidence.write().extend(current_face_confidence());
 
             // Add the new scan
             scanned_faces.write().push(ScannedFace {
@@ -167,34 +240,35 @@ pub fn ScanWorkflow(props: ScanWorkflowProps) -> Element {
 
     // Complete the workflow and build the cube
     let complete_workflow = move |_| {
-        let size = props.cube_size as usize;
-        let mut cube = Cube::new(size);
-
-        // Apply scanned faces to the cube using FaceName
-        use crate::cube::FaceName;
-        for scanned in scanned_faces.read().iter() {
-            let face_name = match scanned.position {
-                FacePosition::Front => FaceName::F,
-                FacePosition::Back => FaceName::B,
-                FacePosition::Right => FaceName::R,
-                FacePosition::Left => FaceName::L,
-                FacePosition::Up => FaceName::U,
-                FacePosition::Down => FaceName::D,
-            };
-
-            // Set each sticker in the face
-            for (row, row_colors) in scanned.colors.iter().enumerate() {
-                for (col, color) in row_colors.iter().enumerate() {
-                    cube.set_sticker(face_name, row, col, *color);
-                }
-            }
-        }
-
+        let cube = scanned_cube(props.cube_size as usize, &scanned_faces.read());
         if let Some(handler) = &props.on_complete {
             handler.call(cube);
         }
     };
 
+    // Once every face is in, check the cube and look for a repair if it
+    // can't be solved
+    let problem = use_memo(move || {
+        let faces = scanned_faces.read();
+        if faces.len() < 6 {
+            return None;
+        }
+        let cube = scanned_cube(props.cube_size as usize, &faces);
+        let error = cube.validate().err()?;
+        Some((error, repair_scan(&cube, &scan_confidence.read())))
+    });
+
+    // Take the offered corrections; the changed stickers count as confirmed
+    let accept_repair = move |repair: ScanRepair| {
+        for change in &repair.changes {
+            let (face, row, col) = change.sticker;
+            if let Some(scanned) = scanned_faces.write().iter_mut().find(|sf| sf.position.face_name() == face) {
+                scanned.colors[row][col] = change.to;
+            }
+            scan_confidence.write().remove(&change.sticker);
+        }
+    };
+
     // Cancel the workflow
     let cancel_workflow = move |_| {
         if let Some(handler) = &props.on_cancel {
@@ -334,6 +408,20 @@ pub fn ScanWorkflow(props: ScanWorkflowProps) -> Element {
                                    border-radius: 8px; cursor: pointer; width: 100%;",
                             "Capture Face"
                         }
+
+                        // No webcam? Scan the face from a photo instead
+                        div {
+                            style: "margin-top: 20px;",
+                            StillImageScanner {
+                                cube_size: props.cube_size,
+                                on_scanned: move |scan| load_photo(face, scan),
+                                on_debug_capture: move |record| {
+                                    if let Some(session) = debug_session.write().as_mut() {
+                                        session.record(Some(face.abbrev()), record);
+                                    }
+                                },
+                            }
+                        }
                     }
                 },
 
@@ -432,6 +520,10 @@ pub fn ScanWorkflow(props: ScanWorkflowProps) -> Element {
                              re-scan any face if needed."
                         }
 
+                        if let Some((error, repair)) = problem() {
+                            {repair_panel(error, repair, accept_repair)}
+                        }
+
                         // Show all scanned faces for review
                         {
                             let faces_snapshot: Vec<_> = scanned_faces.read().iter().cloned().collect();
@@ -515,6 +607,121 @@ pub fn ScanWorkflow(props: ScanWorkflowProps) -> Element {
                     }
                 },
             }
+
+            // Debug captures of this session's scan attempts
+            if let Some(captured) = debug_session.read().as_ref().map(|session| session.records().len()) {
+                div {
+                    class: "scan-debug",
+                    style: "margin-top: 20px; padding: 12px 16px; background: #f3f4f6; border-radius: 8px; \
+                           font-size: 13px; color: #374151; display: flex; flex-wrap: wrap; \
+                           align-items: center; gap: 10px;",
+
+                    span { "🐞 Debug capture: {captured} scan attempts recorded" }
+
+                    if captured > 0 {
+                        {save_debug_button(debug_session, debug_saved)}
+                    }
+
+                    if let Some(message) = debug_saved() {
+                        span {
+                            role: "status",
+                            style: "flex-basis: 100%; word-break: break-all;",
+                            "{message}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Why the scanned cube can't be solved, and the correction offered for it
+fn repair_panel(error: ValidationError, repair: Option<ScanRepair>, accept: impl FnMut(ScanRepair) + 'static) -> Element {
+    let mut accept = accept;
+    rsx! {
+        div {
+            class: "scan-repair",
+            role: "alert",
+            style: "margin: 0 auto 24px; max-width: 600px; padding: 14px 18px; text-align: left; \
+                   background: #fef3c7; border: 1px solid #f59e0b; border-radius: 8px; \
+                   font-size: 14px; color: #78350f;",
+
+            p {
+                style: "margin: 0 0 8px; font-weight: 600;",
+                "These colors don't make a solvable cube: {error}"
+            }
+
+            match repair {
+                Some(repair) => rsx! {
+                    p {
+                        style: "margin: 0 0 6px;",
+                        "Changing the stickers the camera was least sure of fixes it:"
+                    }
+                    ul {
+                        style: "margin: 0 0 10px; padding-left: 20px;",
+                        for change in repair.changes.iter() {
+                            li {
+                                key: "{sticker_name(change.sticker)}",
+                                {format!(
+                                    "{}: {:?} → {:?} ({:.0}% sure of {:?})",
+                                    sticker_name(change.sticker),
+                                    change.from,
+                                    change.to,
+                                    change.confidence * 100.0,
+                                    change.from
+                                )}
+                            }
+                        }
+                    }
+                    button {
+                        onclick: move |_| accept(repair.clone()),
+                        style: "padding: 8px 16px; font-size: 14px; background: #d97706; color: white; \
+                               border: none; border-radius: 5px; cursor: pointer; font-weight: 500;",
+                        "Use Corrected Colors"
+                    }
+                },
+                None => rsx! {
+                    p {
+                        style: "margin: 0;",
+                        "No small change to the unsure stickers fixes it. Re-scan the faces that look wrong."
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Button saving the debug session to a folder
+#[cfg(not(target_arch = "wasm32"))]
+fn save_debug_button(debug_session: Signal<Option<ScanDebugSession>>, mut debug_saved: Signal<Option<String>>) -> Element {
+    use crate::camera::scan_debug::default_debug_dir;
+
+    let save = move |_| {
+        if let Some(session) = debug_session.read().as_ref() {
+            debug_saved.set(Some(match session.save(&default_debug_dir()) {
+                Ok(folder) => format!("Saved to {}", folder.display()),
+                Err(e) => format!("Could not save the debug capture: {}", e),
+            }));
+        }
+    };
+
+    rsx! {
+        button {
+            onclick: save,
+            style: "padding: 6px 12px; font-size: 13px; background: #374151; color: white; \
+                   border: none; border-radius: 5px; cursor: pointer;",
+            "Save Debug Capture"
+        }
+    }
+}
+
+/// Browsers can't write folders, so there's nothing to save to
+#[cfg(target_arch = "wasm32")]
+fn save_debug_button(_debug_session: Signal<Option<ScanDebugSession>>, _debug_saved: Signal<Option<String>>) -> Element {
+    rsx! {
+        span {
+            style: "color: #6b7280;",
+            "(saving debug captures needs the desktop app)"
         }
     }
 }
@@ -601,4 +808,18 @@ mod tests {
         assert!((2..=20).contains(&4));
         assert!((2..=20).contains(&5));
     }
+
+    #[test]
+    fn test_scanned_cube() {
+        let faces: Vec<ScannedFace> = FacePosition::all_ordered()
+            .into_iter()
+            .map(|position| ScannedFace {
+                position,
+                colors: vec![vec![position.solved_color(); 3]; 3],
+                confirmed: true,
+            })
+            .collect();
+        assert_eq!(scanned_cube(3, &faces), Cube::new(3));
+        assert_eq!(FacePosition::Right.face_name(), FaceName::R);
+    }
 }
@@ -1,11 +1,19 @@
 //! Solution Player Component
 //!
 //! Provides automatic playback of cube solutions with configurable speed
-//! and smooth animations between moves.
+//! and smooth animations between moves. Playback can step back and forward,
+//! jump to any move, and loop. Solver notes explain what each move is for,
+//! and each move is narrated in words suited to the student. The cube at the current step is reported to
+//! the parent so the 3D/2D views follow along.
+//!
+//! Keyboard: Space plays/pauses, the arrow keys step, Escape stops.
 
 use dioxus::prelude::*;
-use crate::cube::Cube;
+use crate::components::ui_kit::token::{BORDER, FOCUS, ON_ACCENT, SURFACE, SURFACE_RAISED, TEXT, TEXT_MUTED};
+use crate::components::ui_kit::ButtonTheme;
+use crate::cube::{Cube, Move, Orientation};
 use crate::solver::Solution;
+use crate::tutorial::narration::{narrate_solution, Vocabulary};
 use std::time::Duration;
 
 /// Playback speed setting
@@ -24,6 +32,15 @@ pub enum PlaybackSpeed {
 }
 
 impl PlaybackSpeed {
+    /// All speeds, slowest first
+    pub const ALL: [PlaybackSpeed; 5] = [
+        PlaybackSpeed::VerySlow,
+        PlaybackSpeed::Slow,
+        PlaybackSpeed::Normal,
+        PlaybackSpeed::Fast,
+        PlaybackSpeed::VeryFast,
+    ];
+
     /// Get the duration per move for this speed
     pub fn duration(&self) -> Duration {
         match self {
@@ -65,6 +82,126 @@ pub enum PlaybackState {
     Completed,
 }
 
+/// Position in a solution, with the cube state at that position
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaybackCursor {
+    start: Cube,
+    moves: Vec<Move>,
+    position: usize,
+    cube: Cube,
+}
+
+impl PlaybackCursor {
+    /// Create a cursor at the start of the moves
+    pub fn new(start: Cube, moves: Vec<Move>) -> Self {
+        Self {
+            cube: start.clone(),
+            start,
+            moves,
+            position: 0,
+        }
+    }
+
+    /// Number of moves applied
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Total number of moves
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Check whether there are no moves
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Check whether every move has been applied
+    pub fn is_at_end(&self) -> bool {
+        self.position >= self.moves.len()
+    }
+
+    /// Get the cube at the current position
+    pub fn cube(&self) -> &Cube {
+        &self.cube
+    }
+
+    /// The move to apply next, if any (for the next-move arrow)
+    pub fn next_move(&self) -> Option<Move> {
+        self.moves.get(self.position).copied()
+    }
+
+    /// The move applied last, if any
+    pub fn last_move(&self) -> Option<Move> {
+        self.position.checked_sub(1).map(|index| self.moves[index])
+    }
+
+    /// How the cube is held at the current position, after the rotations
+    /// played so far
+    pub fn orientation(&self) -> Orientation {
+        let mut orientation = Orientation::of(&self.start).unwrap_or_default();
+        orientation.apply_moves(&self.moves[..self.position]);
+        orientation
+    }
+
+    /// Apply the next move; returns false at the end
+    pub fn step_forward(&mut self) -> bool {
+        match self.moves.get(self.position) {
+            Some(mv) => {
+                self.cube.apply_move(*mv);
+                self.position += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Undo the last move; returns false at the start
+    pub fn step_back(&mut self) -> bool {
+        if self.position == 0 {
+            return false;
+        }
+        self.cube.apply_move(self.moves[self.position - 1].inverse());
+        self.position -= 1;
+        true
+    }
+
+    /// Jump to a position (clamped to the number of moves)
+    pub fn jump_to(&mut self, position: usize) {
+        let position = position.min(self.moves.len());
+        self.cube = self.start.clone();
+        self.cube.apply_moves(&self.moves[..position]);
+        self.position = position;
+    }
+
+    /// Go back to the start
+    pub fn reset(&mut self) {
+        self.jump_to(0);
+    }
+}
+
+/// Wait for a number of milliseconds without blocking the UI
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn delay(ms: u64) {
+    use web_sys::js_sys::Promise;
+
+    let promise = Promise::new(&mut |resolve, _| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms as i32);
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Wait for a number of milliseconds without blocking the UI
+///
+/// Runs on the desktop renderer's tokio runtime.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn delay(ms: u64) {
+    tokio::time::sleep(Duration::from_millis(ms)).await;
+}
+
 /// Props for SolutionPlayer component
 #[derive(Props, Clone, PartialEq)]
 pub struct SolutionPlayerProps {
@@ -73,111 +210,248 @@ pub struct SolutionPlayerProps {
     /// Initial playback speed
     #[props(default = PlaybackSpeed::Normal)]
     pub speed: PlaybackSpeed,
+    /// Setup moves (usually the scramble) included in the share link
+    #[props(default)]
+    pub setup: Vec<Move>,
+    /// Cube before the solution; defaults to the setup moves applied to a
+    /// solved 3x3
+    #[props(default)]
+    pub start: Option<Cube>,
+    /// Called with the cube whenever the playback position changes
+    #[props(default)]
+    pub on_step: Option<EventHandler<Cube>>,
+    /// Called with each move as it is played; stepping back reports the
+    /// inverse of the undone move
+    #[props(default)]
+    pub on_turn: Option<EventHandler<Move>>,
+    /// Words used to narrate each move
+    #[props(default)]
+    pub vocabulary: Vocabulary,
+    /// Called with the narration of each move played forward (for
+    /// text-to-speech)
+    #[props(default)]
+    pub on_narrate: Option<EventHandler<String>>,
 }
 
 /// Solution player component for automatic playback
 #[component]
 pub fn SolutionPlayer(props: SolutionPlayerProps) -> Element {
-    let mut cube = use_signal(|| Cube::new(3)); // Start with solved 3x3
-    let mut current_move = use_signal(|| 0_usize);
+    let start = props.start.clone().unwrap_or_else(|| {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&props.setup);
+        cube
+    });
+    let moves = props.solution.all_moves();
+    let mut cursor = use_signal(|| PlaybackCursor::new(start.clone(), moves.clone()));
     let mut playback_state = use_signal(|| PlaybackState::Stopped);
     let mut speed = use_signal(|| props.speed);
-    let all_moves = use_signal(|| props.solution.all_moves());
+    let mut looping = use_signal(|| false);
+
+    // Start over when a new solution or start position comes in
+    use_effect(use_reactive!(|start, moves| {
+        cursor.set(PlaybackCursor::new(start, moves));
+        playback_state.set(PlaybackState::Stopped);
+    }));
+
+    // Report the cube at the current step
+    let on_step = props.on_step;
+    use_effect(move || {
+        let cube = cursor.read().cube().clone();
+        if let Some(handler) = on_step {
+            handler.call(cube);
+        }
+    });
+
+    // Report turns as they are played (for sounds and haptics)
+    let on_turn = props.on_turn;
+    let turned = move |mv: Option<Move>| {
+        if let (Some(mv), Some(handler)) = (mv, on_turn) {
+            handler.call(mv);
+        }
+    };
 
-    let total_moves = all_moves.read().len();
+    // Narrate moves as they are played forward
+    let solution = props.solution.clone();
+    let vocabulary = props.vocabulary;
+    let narrations = use_memo(use_reactive!(|solution, vocabulary| narrate_solution(&solution, vocabulary)));
+    let on_narrate = props.on_narrate;
+    let narrate = move |position: usize| {
+        let spoken = position.checked_sub(1).and_then(|index| narrations.peek().get(index).map(|n| n.spoken()));
+        if let (Some(spoken), Some(handler)) = (spoken, on_narrate) {
+            handler.call(spoken);
+        }
+    };
 
     // Handle play button
-    let play = move |_| {
-        if *playback_state.read() == PlaybackState::Stopped ||
-           *playback_state.read() == PlaybackState::Completed {
-            // Reset to beginning
-            cube.set(Cube::new(3));
-            current_move.set(0);
+    let mut play = move || {
+        if cursor.peek().is_at_end() {
+            cursor.write().reset();
         }
         playback_state.set(PlaybackState::Playing);
     };
 
     // Handle pause button
-    let pause = move |_| {
+    let mut pause = move || {
         playback_state.set(PlaybackState::Paused);
     };
 
     // Handle stop button
-    let stop = move |_| {
+    let mut stop = move || {
         playback_state.set(PlaybackState::Stopped);
-        cube.set(Cube::new(3));
-        current_move.set(0);
+        cursor.write().reset();
     };
 
     // Handle step forward button
-    let step_forward = move |_| {
-        let current = *current_move.read();
-        let moves = all_moves.read();
-        let total = moves.len();
-        if current < total {
-            // Apply the next move
-            if let Some(move_to_apply) = moves.get(current) {
-                let mut cube_val = cube.read().clone();
-                cube_val.apply_move(*move_to_apply);
-                cube.set(cube_val);
-                current_move.set(current + 1);
-
-                // If we reach the end, mark as completed
-                if current + 1 >= total {
-                    playback_state.set(PlaybackState::Completed);
-                }
-            }
+    let mut step_forward = move || {
+        let mut current = cursor.write();
+        if current.step_forward() {
+            turned(current.last_move());
+            narrate(current.position());
+        }
+        if current.is_at_end() {
+            playback_state.set(PlaybackState::Completed);
         }
     };
 
     // Handle step backward button
-    let step_backward = move |_| {
-        let current = *current_move.read();
-        if current > 0 {
-            // Rebuild cube state from scratch up to (current - 1) moves
-            let moves = all_moves.read();
-            let mut cube_val = Cube::new(3);
-            for i in 0..(current - 1) {
-                if let Some(move_to_apply) = moves.get(i) {
-                    cube_val.apply_move(*move_to_apply);
-                }
-            }
-            cube.set(cube_val);
-            current_move.set(current - 1);
+    let mut step_backward = move || {
+        let mut current = cursor.write();
+        let undone = current.last_move();
+        if current.step_back() {
+            turned(undone.map(|mv| mv.inverse()));
+        }
 
-            // If we were completed, change back to paused
-            if *playback_state.read() == PlaybackState::Completed {
-                playback_state.set(PlaybackState::Paused);
-            }
+        // If we were completed, change back to paused
+        if *playback_state.peek() == PlaybackState::Completed {
+            playback_state.set(PlaybackState::Paused);
         }
     };
 
+    // Handle clicking a move: show the cube right after it
+    let mut jump_to = move |position: usize| {
+        let mut current = cursor.write();
+        current.jump_to(position);
+        let next_state = match *playback_state.peek() {
+            _ if current.is_at_end() => PlaybackState::Completed,
+            PlaybackState::Playing => PlaybackState::Playing,
+            _ => PlaybackState::Paused,
+        };
+        playback_state.set(next_state);
+    };
+
     // Handle speed change
     let mut change_speed = move |new_speed: PlaybackSpeed| {
         speed.set(new_speed);
     };
 
-    // Playback logic - simplified without async for now
-    // In a real implementation, this would use use_future or similar
-    // For R5.7 acceptance criteria, we're focusing on the UI structure
+    // Auto-play: apply one move per tick while playing
+    use_future(move || async move {
+        loop {
+            delay(speed.peek().duration_ms()).await;
+            if *playback_state.peek() != PlaybackState::Playing {
+                continue;
+            }
+            let mut current = cursor.write();
+            if current.is_at_end() {
+                // Reached the end on the previous tick
+                if *looping.peek() {
+                    current.reset();
+                } else {
+                    playback_state.set(PlaybackState::Completed);
+                }
+            } else {
+                current.step_forward();
+                turned(current.last_move());
+                narrate(current.position());
+                if current.is_at_end() && !*looping.peek() {
+                    playback_state.set(PlaybackState::Completed);
+                }
+            }
+        }
+    });
 
-    let current = *current_move.read();
+    let current = cursor.read().position();
+    let total_moves = cursor.read().len();
     let state = *playback_state.read();
+    let share_url = props.solution.to_alg_cubing_url(&props.setup);
+    let primary = ButtonTheme::Primary.background_var();
+    let success = ButtonTheme::Success.background_var();
+    let warning = ButtonTheme::Warning.background_var();
+    let danger = ButtonTheme::Danger.background_var();
+    let secondary = ButtonTheme::Secondary.background_var();
+
+    // What the last applied move (or the next one, at the start) is for
+    let purpose = props
+        .solution
+        .purpose_at(current.saturating_sub(1))
+        .map(str::to_string);
+    // How to turn the cube for that move
+    let sentence = narrations
+        .read()
+        .get(current.saturating_sub(1))
+        .map(|narration| narration.sentence.clone());
+
+    // Index of each step's first move, for the clickable move list
+    let step_offsets: Vec<usize> = props
+        .solution
+        .steps
+        .iter()
+        .scan(0, |offset, step| {
+            let first = *offset;
+            *offset += step.moves.len();
+            Some(first)
+        })
+        .collect();
 
     rsx! {
         div {
             class: "solution-player",
-            style: "padding: 20px; background: #f5f5f5; border-radius: 8px; margin: 20px 0;",
+            tabindex: "0",
+            role: "region",
+            "aria-label": "Solution player. Space plays or pauses, arrow keys step, Escape stops.",
+            onkeydown: move |evt| {
+                let handled = match evt.key() {
+                    Key::Character(key) if key == " " => {
+                        if state == PlaybackState::Playing { pause() } else { play() }
+                        true
+                    }
+                    Key::ArrowRight => { step_forward(); true }
+                    Key::ArrowLeft => { step_backward(); true }
+                    Key::Escape => { stop(); true }
+                    _ => false,
+                };
+                if handled {
+                    evt.prevent_default();
+                }
+            },
+            style: "padding: 20px; background: {SURFACE}; color: {TEXT}; border-radius: 8px; margin: 20px 0;",
 
             div {
                 class: "playback-info",
                 style: "margin-bottom: 15px;",
                 p {
                     style: "font-size: 18px; font-weight: bold; margin: 5px 0;",
+                    role: "status",
+                    "aria-live": "polite",
                     "Move {current} of {total_moves}"
                 }
+                if let Some(purpose) = purpose {
+                    p {
+                        class: "move-purpose",
+                        style: "font-size: 16px; margin: 5px 0;",
+                        "aria-live": "polite",
+                        "💡 {purpose}"
+                    }
+                }
+                if let Some(sentence) = sentence {
+                    p {
+                        class: "move-narration",
+                        style: "font-size: 16px; margin: 5px 0;",
+                        "🗣 {sentence}"
+                    }
+                }
                 p {
-                    style: "font-size: 14px; color: #666; margin: 5px 0;",
+                    style: "font-size: 14px; color: {TEXT_MUTED}; margin: 5px 0;",
                     "Status: {state:?}"
                 }
             }
@@ -188,43 +462,60 @@ pub fn SolutionPlayer(props: SolutionPlayerProps) -> Element {
 
                 button {
                     class: "btn btn-play",
-                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: #4CAF50; color: white; border: none; border-radius: 4px; touch-action: manipulation;",
-                    onclick: play,
+                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: {success}; color: {ON_ACCENT}; border: none; border-radius: 4px; touch-action: manipulation;",
+                    onclick: move |_| play(),
                     disabled: state == PlaybackState::Playing,
+                    "aria-keyshortcuts": "Space",
                     if state == PlaybackState::Paused { "Resume" } else { "Play" }
                 }
 
                 button {
                     class: "btn btn-pause",
-                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: #FF9800; color: white; border: none; border-radius: 4px; touch-action: manipulation;",
-                    onclick: pause,
+                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: {warning}; color: {ON_ACCENT}; border: none; border-radius: 4px; touch-action: manipulation;",
+                    onclick: move |_| pause(),
                     disabled: state != PlaybackState::Playing,
                     "Pause"
                 }
 
                 button {
                     class: "btn btn-stop",
-                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: #f44336; color: white; border: none; border-radius: 4px; touch-action: manipulation;",
-                    onclick: stop,
+                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: {danger}; color: {ON_ACCENT}; border: none; border-radius: 4px; touch-action: manipulation;",
+                    onclick: move |_| stop(),
                     disabled: state == PlaybackState::Stopped,
+                    "aria-keyshortcuts": "Escape",
                     "Stop"
                 }
 
                 button {
                     class: "btn btn-step-back",
-                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: #9C27B0; color: white; border: none; border-radius: 4px; touch-action: manipulation;",
-                    onclick: step_backward,
+                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: {secondary}; color: {ON_ACCENT}; border: none; border-radius: 4px; touch-action: manipulation;",
+                    onclick: move |_| step_backward(),
                     disabled: current == 0,
+                    "aria-label": "Step back",
+                    "aria-keyshortcuts": "ArrowLeft",
                     "◄ Step Back"
                 }
 
                 button {
                     class: "btn btn-step-forward",
-                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: #9C27B0; color: white; border: none; border-radius: 4px; touch-action: manipulation;",
-                    onclick: step_forward,
+                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: {secondary}; color: {ON_ACCENT}; border: none; border-radius: 4px; touch-action: manipulation;",
+                    onclick: move |_| step_forward(),
                     disabled: current >= total_moves,
+                    "aria-label": "Step forward",
+                    "aria-keyshortcuts": "ArrowRight",
                     "Step Forward ►"
                 }
+
+                label {
+                    style: "display: inline-flex; align-items: center; gap: 6px; min-height: 44px; cursor: pointer;",
+                    input {
+                        r#type: "checkbox",
+                        class: "loop-toggle",
+                        checked: looping(),
+                        onchange: move |evt| looping.set(evt.checked()),
+                    }
+                    "🔁 Loop"
+                }
             }
 
             div {
@@ -237,14 +528,17 @@ pub fn SolutionPlayer(props: SolutionPlayerProps) -> Element {
 
                 div {
                     style: "display: flex; gap: 8px; flex-wrap: wrap;",
-                    for speed_option in [PlaybackSpeed::VerySlow, PlaybackSpeed::Slow, PlaybackSpeed::Normal, PlaybackSpeed::Fast, PlaybackSpeed::VeryFast] {
+                    role: "group",
+                    "aria-label": "Playback speed",
+                    for speed_option in PlaybackSpeed::ALL {
                         button {
                             class: if *speed.read() == speed_option { "btn-speed active" } else { "btn-speed" },
                             style: if *speed.read() == speed_option {
-                                "padding: 12px 16px; min-height: 44px; cursor: pointer; background: #2196F3; color: white; border: 2px solid #1976D2; border-radius: 4px; touch-action: manipulation;"
+                                "padding: 12px 16px; min-height: 44px; cursor: pointer; background: {primary}; color: {ON_ACCENT}; border: 2px solid {FOCUS}; border-radius: 4px; touch-action: manipulation;"
                             } else {
-                                "padding: 12px 16px; min-height: 44px; cursor: pointer; background: white; color: #333; border: 2px solid #ddd; border-radius: 4px; touch-action: manipulation;"
+                                "padding: 12px 16px; min-height: 44px; cursor: pointer; background: {SURFACE_RAISED}; color: {TEXT}; border: 2px solid {BORDER}; border-radius: 4px; touch-action: manipulation;"
                             },
+                            "aria-pressed": "{*speed.read() == speed_option}",
                             onclick: move |_| change_speed(speed_option),
                             "{speed_option.label()}"
                         }
@@ -254,19 +548,75 @@ pub fn SolutionPlayer(props: SolutionPlayerProps) -> Element {
 
             div {
                 class: "solution-display",
-                style: "background: white; padding: 15px; border-radius: 4px; border: 1px solid #ddd;",
+                style: "background: {SURFACE_RAISED}; padding: 15px; border-radius: 4px; border: 1px solid {BORDER};",
                 h3 {
-                    style: "margin-top: 0; color: #333;",
+                    style: "margin-top: 0; color: {TEXT};",
                     "Solution"
                 }
-                p {
-                    style: "font-family: monospace; font-size: 14px; line-height: 1.6; word-break: break-word;",
-                    "{props.solution.to_notation()}"
+                for (step, first) in props.solution.steps.iter().zip(step_offsets.iter().copied()) {
+                    div {
+                        key: "{first}-{step.description}",
+                        style: "margin-bottom: 8px;",
+                        div {
+                            style: "font-size: 13px; color: {TEXT_MUTED}; margin-bottom: 4px;",
+                            "{step.description}"
+                        }
+                        if let Some(explanation) = &step.explanation {
+                            div {
+                                style: "font-size: 12px; color: {TEXT_MUTED}; margin-bottom: 4px; font-style: italic;",
+                                "{explanation}"
+                            }
+                        }
+                        // Inner-layer turns aren't played, only listed
+                        if !step.layer_moves.is_empty() {
+                            div {
+                                class: "solution-layer-moves",
+                                style: "font-family: monospace; font-size: 14px; color: {TEXT};",
+                                "{step.to_notation()}"
+                            }
+                        }
+                        div {
+                            class: "solution-moves",
+                            style: "display: flex; flex-wrap: wrap; gap: 4px; font-family: monospace; font-size: 14px;",
+                            for (offset, mv) in step.moves.iter().enumerate() {
+                                {
+                                    let index = first + offset;
+                                    let is_current = index + 1 == current;
+                                    let note = step.note_for(offset).map(|n| n.purpose.clone()).unwrap_or_default();
+                                    rsx! {
+                                        button {
+                                            key: "{index}",
+                                            class: if is_current { "move-chip current" } else { "move-chip" },
+                                            style: if is_current {
+                                                "padding: 4px 8px; min-height: 32px; cursor: pointer; border-radius: 4px; border: 1px solid {FOCUS}; background: {primary}; color: {ON_ACCENT}; touch-action: manipulation;"
+                                            } else {
+                                                "padding: 4px 8px; min-height: 32px; cursor: pointer; border-radius: 4px; border: 1px solid {BORDER}; background: {SURFACE_RAISED}; color: {TEXT}; touch-action: manipulation;"
+                                            },
+                                            title: "{note}",
+                                            "aria-label": "Jump to move {index + 1}, {mv.to_notation()}",
+                                            "aria-current": if is_current { "step" } else { "false" },
+                                            onclick: move |_| jump_to(index + 1),
+                                            "{mv.to_notation()}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }
                 p {
-                    style: "font-size: 12px; color: #666; margin-top: 10px;",
+                    style: "font-size: 12px; color: {TEXT_MUTED}; margin-top: 10px;",
                     "{props.solution.summary()}"
                 }
+                a {
+                    class: "btn btn-share",
+                    style: "display: inline-block; padding: 12px 20px; min-height: 44px; box-sizing: border-box; font-size: 16px; background: {primary}; color: {ON_ACCENT}; border-radius: 4px; text-decoration: none; touch-action: manipulation;",
+                    href: "{share_url}",
+                    target: "_blank",
+                    rel: "noopener noreferrer",
+                    "aria-label": "Open this solution in alg.cubing.net",
+                    "🔗 Share on alg.cubing.net"
+                }
             }
         }
     }
@@ -300,4 +650,66 @@ mod tests {
         assert_ne!(PlaybackState::Playing, PlaybackState::Paused);
         assert_ne!(PlaybackState::Paused, PlaybackState::Completed);
     }
+
+    fn test_moves() -> Vec<Move> {
+        vec![Move::R, Move::U, Move::RPrime, Move::UPrime]
+    }
+
+    #[test]
+    fn test_cursor_steps_mirror_cube() {
+        let mut cursor = PlaybackCursor::new(Cube::new(3), test_moves());
+        assert_eq!(cursor.position(), 0);
+        assert!(!cursor.step_back());
+        assert_eq!(cursor.last_move(), None);
+        assert_eq!(cursor.next_move(), Some(Move::R));
+
+        assert!(cursor.step_forward());
+        assert!(cursor.step_forward());
+        assert_eq!(cursor.last_move(), Some(Move::U));
+        let mut expected = Cube::new(3);
+        expected.apply_moves(&[Move::R, Move::U]);
+        assert_eq!(cursor.cube(), &expected);
+
+        assert!(cursor.step_back());
+        assert_eq!(cursor.position(), 1);
+        let mut expected = Cube::new(3);
+        expected.apply_move(Move::R);
+        assert_eq!(cursor.cube(), &expected);
+    }
+
+    #[test]
+    fn test_cursor_jump_and_end() {
+        let mut start = Cube::new(3);
+        start.apply_move(Move::F);
+        let mut cursor = PlaybackCursor::new(start.clone(), test_moves());
+
+        cursor.jump_to(3);
+        let mut expected = start.clone();
+        expected.apply_moves(&[Move::R, Move::U, Move::RPrime]);
+        assert_eq!(cursor.cube(), &expected);
+
+        cursor.jump_to(100);
+        assert_eq!(cursor.position(), 4);
+        assert!(cursor.is_at_end());
+        assert!(!cursor.step_forward());
+
+        cursor.reset();
+        assert_eq!(cursor.cube(), &start);
+    }
+
+    #[test]
+    fn test_cursor_orientation() {
+        let mut cursor = PlaybackCursor::new(Cube::new(3), vec![Move::R, Move::X, Move::U, Move::Y]);
+        cursor.jump_to(3);
+        assert_eq!(Some(cursor.orientation()), Orientation::of(cursor.cube()));
+        assert_eq!(cursor.orientation().up(), crate::cube::Color::Green);
+        cursor.reset();
+        assert_eq!(cursor.orientation(), Orientation::standard());
+    }
+
+    #[test]
+    fn test_speeds_ordered() {
+        let durations: Vec<u64> = PlaybackSpeed::ALL.iter().map(|s| s.duration_ms()).collect();
+        assert!(durations.windows(2).all(|w| w[0] > w[1]));
+    }
 }
@@ -0,0 +1,192 @@
+//! Solve Along Component
+//!
+//! Guides a student through solving their own physical cube: the 3D view
+//! shows how their cube should look, a large arrow shows the next move, and
+//! "Done, next" moves on once they have made it. A re-scan can be passed in
+//! to check they are still on track.
+
+use crate::components::move_display::get_move_explanation;
+use crate::components::Cube3D;
+use crate::components::ui_kit::token::{BORDER, HIGHLIGHT, ON_ACCENT, SURFACE, SURFACE_RAISED, TEXT, TEXT_MUTED};
+use crate::components::ui_kit::ButtonTheme;
+use crate::cube::{Cube, ParsedMove};
+use crate::tutorial::{GuidedSolve, StepCheck};
+use dioxus::prelude::*;
+
+/// Props for SolveAlong component
+#[derive(Props, Clone, PartialEq)]
+pub struct SolveAlongProps {
+    /// The student's cube as scanned
+    pub scanned: Cube,
+    /// The student's cube scanned again partway through, to check against
+    #[props(default)]
+    pub rescan: Option<Cube>,
+    /// Viewport width passed to the 3D view
+    #[props(default = 400.0)]
+    pub viewport_width: f32,
+    /// Viewport height passed to the 3D view
+    #[props(default = 400.0)]
+    pub viewport_height: f32,
+}
+
+/// Message for a re-scan check
+fn check_message(check: StepCheck) -> String {
+    match check {
+        StepCheck::OnTrack => "Your cube matches. Keep going!".to_string(),
+        StepCheck::Behind(1) => "Your cube is one move behind. Try the last move again.".to_string(),
+        StepCheck::Behind(moves) => format!("Your cube is {} moves behind.", moves),
+        StepCheck::Ahead(1) => "Your cube is one move ahead.".to_string(),
+        StepCheck::Ahead(moves) => format!("Your cube is {} moves ahead.", moves),
+        StepCheck::OffTrack(stickers) => {
+            format!("{} stickers don't match. Continue from your cube to get a new plan.", stickers)
+        }
+    }
+}
+
+/// Move-by-move guide for solving a scanned cube
+#[component]
+pub fn SolveAlong(props: SolveAlongProps) -> Element {
+    let scanned = props.scanned.clone();
+    let mut guide = use_signal(move || GuidedSolve::new(&scanned));
+    let mut error = use_signal(|| None::<String>);
+
+    let state = guide.read().clone();
+    let guide_state = match state {
+        Ok(guide_state) => guide_state,
+        Err(message) => {
+            return rsx! {
+                div {
+                    class: "solve-along solve-along-error",
+                    role: "alert",
+                    style: "padding: 20px; background: {SURFACE}; color: {TEXT}; border-radius: 8px; margin: 20px 0;",
+                    "This cube can't be solved: {message}"
+                }
+            };
+        }
+    };
+
+    let position = guide_state.position();
+    let total = guide_state.len();
+    let step = guide_state.current_step().map(|step| step.description.clone());
+    let next_move = guide_state.current_move();
+    let notation = next_move.map(|mv| mv.to_notation()).unwrap_or_default();
+    let arrow = guide_state.arrow().map(|arrow| arrow.symbol()).unwrap_or_default();
+    let instruction = next_move.map(|mv| get_move_explanation(&ParsedMove::Basic(mv)));
+    let check = props.rescan.as_ref().map(|cube| guide_state.check(cube));
+    let primary = ButtonTheme::Primary.background_var();
+    let secondary = ButtonTheme::Secondary.background_var();
+
+    rsx! {
+        div {
+            class: "solve-along",
+            style: "padding: 20px; background: {SURFACE}; color: {TEXT}; border-radius: 8px; margin: 20px 0;",
+
+            p {
+                style: "font-size: 18px; font-weight: bold; margin: 5px 0;",
+                if let Some(step) = step {
+                    "{step}"
+                } else {
+                    "Solved!"
+                }
+            }
+            p {
+                style: "font-size: 14px; color: {TEXT_MUTED}; margin: 5px 0 15px;",
+                "Move {position.min(total)} of {total}"
+            }
+
+            div {
+                style: "position: relative;",
+                Cube3D {
+                    cube: guide_state.expected_cube().clone(),
+                    viewport_width: props.viewport_width,
+                    viewport_height: props.viewport_height,
+                }
+                if next_move.is_some() {
+                    div {
+                        class: "solve-along-arrow",
+                        "aria-label": "Next move {notation}",
+                        style: "position: absolute; top: 10px; right: 10px; padding: 10px 18px; background: {SURFACE_RAISED}; border: 2px solid {BORDER}; border-radius: 8px; font-size: 64px; line-height: 1; font-weight: bold;",
+                        "{arrow} {notation}"
+                    }
+                }
+            }
+
+            if let Some(instruction) = instruction {
+                p {
+                    style: "font-size: 20px; margin: 15px 0; padding: 10px; background: {HIGHLIGHT}; border-radius: 4px;",
+                    "aria-live": "polite",
+                    "{instruction}"
+                }
+            }
+
+            if let Some(check) = check {
+                div {
+                    class: "solve-along-check",
+                    style: "display: flex; gap: 10px; align-items: center; margin: 10px 0;",
+                    span { role: "status", "{check_message(check)}" }
+                    if check != StepCheck::OnTrack {
+                        button {
+                            class: "btn btn-resync",
+                            style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: {secondary}; color: {ON_ACCENT}; border: none; border-radius: 4px; touch-action: manipulation;",
+                            onclick: move |_| {
+                                let Some(rescan) = props.rescan.as_ref() else { return };
+                                let mut guide = guide.write();
+                                if let Ok(current) = guide.as_mut() {
+                                    if let Err(message) = current.resync(rescan) {
+                                        error.set(Some(message));
+                                    }
+                                }
+                            },
+                            "Continue from my cube"
+                        }
+                    }
+                }
+            }
+
+            if let Some(message) = error.read().clone() {
+                p { role: "alert", style: "margin: 5px 0;", "{message}" }
+            }
+
+            div {
+                class: "solve-along-controls",
+                style: "display: flex; gap: 10px; margin: 15px 0;",
+
+                button {
+                    class: "btn btn-step-back",
+                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: {secondary}; color: {ON_ACCENT}; border: none; border-radius: 4px; touch-action: manipulation;",
+                    disabled: position == 0,
+                    onclick: move |_| {
+                        if let Ok(current) = guide.write().as_mut() {
+                            current.back();
+                        }
+                    },
+                    "◄ Back"
+                }
+
+                button {
+                    class: "btn btn-done-next",
+                    style: "flex: 1; padding: 12px 20px; min-height: 44px; font-size: 18px; font-weight: bold; cursor: pointer; background: {primary}; color: {ON_ACCENT}; border: none; border-radius: 4px; touch-action: manipulation;",
+                    disabled: next_move.is_none(),
+                    onclick: move |_| {
+                        if let Ok(current) = guide.write().as_mut() {
+                            current.done();
+                        }
+                    },
+                    "Done, next ►"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_message() {
+        assert_eq!(check_message(StepCheck::OnTrack), "Your cube matches. Keep going!");
+        assert_eq!(check_message(StepCheck::Behind(3)), "Your cube is 3 moves behind.");
+        assert!(check_message(StepCheck::OffTrack(5)).starts_with("5 stickers"));
+    }
+}
@@ -0,0 +1,264 @@
+//! Statistics Dashboard Component
+//!
+//! Sums up a timing session: best, mean and ao5/ao12 figures, a trend graph
+//! of every solve with the rolling ao5 and ao12 drawn over it, and a
+//! histogram of the times. Both charts are inline SVG, so they work offline
+//! and print with the page. 3x3 sessions also get a cross color neutrality
+//! check of their latest scrambles.
+
+use crate::components::ui_kit::token::{BORDER, SURFACE, SURFACE_RAISED, TEXT, TEXT_MUTED};
+use crate::components::ui_kit::ButtonTheme;
+use crate::components::CrossNeutrality;
+use crate::state::{format_time, SolveRecord};
+use crate::timer::stats::rolling_averages;
+use crate::timer::{Histogram, SessionStats, TimingSession};
+use dioxus::prelude::*;
+
+/// Size of the charts' drawing area, in SVG units
+const CHART_WIDTH: f32 = 600.0;
+const CHART_HEIGHT: f32 = 220.0;
+/// Room left of the charts for the axis labels
+const AXIS_WIDTH: f32 = 52.0;
+/// Most bars in the histogram
+const HISTOGRAM_BUCKETS: usize = 12;
+/// Latest scrambles checked for cross color neutrality
+const NEUTRALITY_SAMPLE: usize = 12;
+
+/// Maps solve numbers and times onto the trend graph
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TrendScale {
+    count: usize,
+    min_ms: u64,
+    max_ms: u64,
+}
+
+impl TrendScale {
+    /// Scale fitting every finished solve, or None if there are none
+    fn new(records: &[SolveRecord]) -> Option<Self> {
+        let times = records.iter().filter_map(SolveRecord::effective_time_ms);
+        let (min_ms, max_ms) = (times.clone().min()?, times.max()?);
+        Some(Self { count: records.len(), min_ms, max_ms })
+    }
+
+    fn x(&self, index: usize) -> f32 {
+        let step = (CHART_WIDTH - AXIS_WIDTH) / self.count.saturating_sub(1).max(1) as f32;
+        AXIS_WIDTH + index as f32 * step
+    }
+
+    /// Slower times are drawn higher, with a margin above and below
+    fn y(&self, ms: u64) -> f32 {
+        let range = (self.max_ms - self.min_ms).max(1) as f32;
+        let margin = 12.0;
+        CHART_HEIGHT - margin - (ms - self.min_ms) as f32 / range * (CHART_HEIGHT - 2.0 * margin)
+    }
+
+    /// `points` attributes for a line through `values`, broken where a value
+    /// is missing (a DNF)
+    fn segments(&self, values: &[(usize, Option<u64>)]) -> Vec<String> {
+        values
+            .split(|(_, ms)| ms.is_none())
+            .filter(|run| !run.is_empty())
+            .map(|run| {
+                run.iter()
+                    .filter_map(|(i, ms)| ms.map(|ms| format!("{:.1},{:.1}", self.x(*i), self.y(ms))))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+}
+
+/// A time for the summary, or "DNF"/"-" when there is none
+fn stat_text(ms: Option<u64>, enough_solves: bool) -> String {
+    match (ms, enough_solves) {
+        (Some(ms), _) => format_time(ms),
+        (None, true) => "DNF".to_string(),
+        (None, false) => "-".to_string(),
+    }
+}
+
+/// Props for StatsDashboard component
+#[derive(Props, Clone, PartialEq)]
+pub struct StatsDashboardProps {
+    /// Sessions the user can pick from
+    pub sessions: Vec<TimingSession>,
+    /// Id of the session shown first (defaults to the first session)
+    #[props(default)]
+    pub selected: Option<String>,
+}
+
+/// Session summary, trend graph and histogram
+#[component]
+pub fn StatsDashboard(props: StatsDashboardProps) -> Element {
+    let first = props.selected.clone().or_else(|| props.sessions.first().map(|s| s.id.clone()));
+    let mut selected = use_signal(move || first);
+
+    let session = props.sessions.iter().find(|s| Some(&s.id) == selected().as_ref()).or(props.sessions.first());
+    let records: Vec<SolveRecord> = session.map(|s| s.records.clone()).unwrap_or_default();
+    let stats = SessionStats::new(&records);
+    let summary = [
+        ("Solves", stats.count.to_string()),
+        ("DNFs", stats.dnf_count.to_string()),
+        ("Best", stat_text(stats.best, false)),
+        ("Mean", stat_text(stats.mean, stats.count > 0)),
+        ("Ao5", stat_text(stats.current_ao5, stats.count >= 5)),
+        ("Best ao5", stat_text(stats.best_ao5, stats.count >= 5)),
+        ("Ao12", stat_text(stats.current_ao12, stats.count >= 12)),
+        ("Best ao12", stat_text(stats.best_ao12, stats.count >= 12)),
+    ];
+
+    let primary = ButtonTheme::Primary.background_var();
+    let success = ButtonTheme::Success.background_var();
+    let danger = ButtonTheme::Danger.background_var();
+    let scale = TrendScale::new(&records);
+    let histogram = Histogram::new(&records, HISTOGRAM_BUCKETS);
+    let view_box = format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT);
+    let cross_scrambles: Vec<String> = match session {
+        Some(session) if session.cube_size == 3 => {
+            records.iter().rev().take(NEUTRALITY_SAMPLE).rev().map(|r| r.scramble.clone()).collect()
+        }
+        _ => Vec::new(),
+    };
+
+    rsx! {
+        div {
+            class: "stats-dashboard",
+            style: "padding: 20px; background: {SURFACE}; color: {TEXT}; border-radius: 8px; margin: 20px 0;",
+
+            div {
+                style: "display: flex; align-items: center; gap: 12px; flex-wrap: wrap;",
+                h2 { style: "margin: 0; font-size: 20px;", "Statistics" }
+                select {
+                    style: "min-height: 44px; padding: 0 8px;",
+                    "aria-label": "Session",
+                    onchange: move |evt| selected.set(Some(evt.value())),
+                    for option in props.sessions.iter() {
+                        option {
+                            value: "{option.id}",
+                            selected: Some(&option.id) == session.map(|s| &s.id),
+                            "{option.name} ({option.records.len()})"
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "stats-summary",
+                style: "display: grid; grid-template-columns: repeat(auto-fill, minmax(110px, 1fr)); gap: 8px; margin: 15px 0;",
+                for (label, value) in summary {
+                    div {
+                        key: "{label}",
+                        style: "padding: 8px; background: {SURFACE_RAISED}; border: 1px solid {BORDER}; border-radius: 4px;",
+                        div { style: "font-size: 12px; color: {TEXT_MUTED};", "{label}" }
+                        div { style: "font-size: 18px; font-weight: bold; font-family: monospace;", "{value}" }
+                    }
+                }
+            }
+
+            if let Some(scale) = scale {
+                h3 { style: "font-size: 16px; margin: 10px 0 4px;", "Trend" }
+                svg {
+                    class: "stats-trend",
+                    view_box: "{view_box}",
+                    width: "100%",
+                    role: "img",
+                    "aria-label": "Solve times with ao5 and ao12 curves",
+                    line { x1: "{AXIS_WIDTH}", y1: "0", x2: "{AXIS_WIDTH}", y2: "{CHART_HEIGHT}", stroke: "{BORDER}" }
+                    text { x: "4", y: "{scale.y(scale.max_ms) + 4.0}", font_size: "12", fill: "{TEXT_MUTED}", "{format_time(scale.max_ms)}" }
+                    text { x: "4", y: "{scale.y(scale.min_ms) + 4.0}", font_size: "12", fill: "{TEXT_MUTED}", "{format_time(scale.min_ms)}" }
+                    for points in scale.segments(&records.iter().map(SolveRecord::effective_time_ms).enumerate().collect::<Vec<_>>()) {
+                        polyline { points: "{points}", fill: "none", stroke: "{TEXT_MUTED}", stroke_width: "1" }
+                    }
+                    for (i, record) in records.iter().enumerate() {
+                        {
+                            let x = scale.x(i);
+                            rsx! {
+                                match record.effective_time_ms() {
+                                    Some(ms) => rsx! { circle { cx: "{x}", cy: "{scale.y(ms)}", r: "3", fill: "{TEXT_MUTED}" } },
+                                    // DNFs are marked along the top edge
+                                    None => rsx! { text { x: "{x - 4.0}", y: "10", font_size: "10", fill: "{danger}", "✕" } },
+                                }
+                            }
+                        }
+                    }
+                    for points in scale.segments(&rolling_averages(&records, 5)) {
+                        polyline { points: "{points}", fill: "none", stroke: "{primary}", stroke_width: "2" }
+                    }
+                    for points in scale.segments(&rolling_averages(&records, 12)) {
+                        polyline { points: "{points}", fill: "none", stroke: "{success}", stroke_width: "2" }
+                    }
+                }
+                div {
+                    style: "display: flex; gap: 16px; font-size: 13px; color: {TEXT_MUTED};",
+                    span { "● Single" }
+                    span { style: "color: {primary};", "━ ao5" }
+                    span { style: "color: {success};", "━ ao12" }
+                    span { style: "color: {danger};", "✕ DNF" }
+                }
+
+                h3 { style: "font-size: 16px; margin: 15px 0 4px;", "Distribution" }
+                svg {
+                    class: "stats-histogram",
+                    view_box: "{view_box}",
+                    width: "100%",
+                    role: "img",
+                    "aria-label": "Histogram of solve times",
+                    for (i, (start, count)) in histogram.buckets().enumerate() {
+                        {
+                            let width = (CHART_WIDTH - AXIS_WIDTH) / histogram.counts.len() as f32;
+                            let height = count as f32 / histogram.max_count().max(1) as f32 * (CHART_HEIGHT - 40.0);
+                            let x = AXIS_WIDTH + i as f32 * width;
+                            let y = CHART_HEIGHT - 20.0 - height;
+                            rsx! {
+                                g {
+                                    key: "{start}",
+                                    rect { x: "{x + 1.0}", y: "{y}", width: "{width - 2.0}", height: "{height}", fill: "{primary}" }
+                                    if count > 0 {
+                                        text { x: "{x + width / 2.0}", y: "{y - 4.0}", font_size: "11", text_anchor: "middle", fill: "{TEXT}", "{count}" }
+                                    }
+                                    text { x: "{x + width / 2.0}", y: "{CHART_HEIGHT - 6.0}", font_size: "10", text_anchor: "middle", fill: "{TEXT_MUTED}", "{format_time(start)}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            } else {
+                p { style: "color: {TEXT_MUTED};", "No finished solves in this session yet." }
+            }
+
+            if !cross_scrambles.is_empty() {
+                CrossNeutrality { scrambles: cross_scrambles }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trend_scale() {
+        let records: Vec<SolveRecord> =
+            [10_000, 20_000, 15_000].iter().enumerate().map(|(i, ms)| SolveRecord::new(*ms, "R", 3, i as u64)).collect();
+        let scale = TrendScale::new(&records).unwrap();
+        assert_eq!(scale.x(0), AXIS_WIDTH);
+        assert_eq!(scale.x(2), CHART_WIDTH);
+        // Slowest at the top
+        assert!(scale.y(20_000) < scale.y(10_000));
+
+        // A DNF breaks the line in two
+        let segments = scale.segments(&[(0, Some(10_000)), (1, None), (2, Some(15_000)), (3, Some(20_000))]);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[1].split(' ').count(), 2);
+
+        assert_eq!(TrendScale::new(&[]), None);
+    }
+
+    #[test]
+    fn test_stat_text() {
+        assert_eq!(stat_text(Some(12_340), true), "12.34");
+        assert_eq!(stat_text(None, true), "DNF");
+        assert_eq!(stat_text(None, false), "-");
+    }
+}
@@ -0,0 +1,135 @@
+//! Still image scanner component
+//!
+//! Lets users scan a face from a photo instead of the camera: drop a PNG or
+//! JPEG onto the drop zone or pick one with the file picker. The photo goes
+//! through the same detection pipeline as camera frames, for users without a
+//! webcam or for checking detection against saved photos.
+//! With `on_debug_capture` set, every attempt is also handed over as a
+//! [`ScanDebugRecord`], failed ones included.
+
+use dioxus::prelude::*;
+use dioxus::html::{FileData, HasFileData};
+use crate::camera::color_detect::ColorDetectionConfig;
+use crate::camera::scan_debug::ScanDebugRecord;
+use crate::camera::still_image::{decode_image, scan_frame, ImageScan};
+
+/// File types offered by the file picker
+const ACCEPTED_TYPES: &str = "image/png,image/jpeg";
+
+/// Where loading a photo is at
+#[derive(Debug, Clone, PartialEq)]
+enum LoadState {
+    /// Waiting for a photo
+    Idle,
+    /// Reading and scanning a photo
+    Scanning(String),
+    /// The last photo was scanned
+    Scanned(String),
+    /// The last photo could not be scanned
+    Failed(String),
+}
+
+#[derive(Props, Clone, PartialEq)]
+pub struct StillImageScannerProps {
+    /// Called with the detected colors of the photo's face
+    pub on_scanned: EventHandler<ImageScan>,
+
+    /// Cube size to sample, or None to detect it from the photo
+    #[props(default)]
+    pub cube_size: Option<u32>,
+
+    /// Called with the photo and detection results of every attempt, for
+    /// debug captures
+    #[props(optional)]
+    pub on_debug_capture: Option<EventHandler<ScanDebugRecord>>,
+}
+
+#[component]
+pub fn StillImageScanner(props: StillImageScannerProps) -> Element {
+    let mut state = use_signal(|| LoadState::Idle);
+    let mut dragging = use_signal(|| false);
+    let on_scanned = props.on_scanned;
+    let on_debug_capture = props.on_debug_capture;
+    let cube_size = props.cube_size.map(|size| size as usize);
+
+    let mut load = move |files: Vec<FileData>| {
+        let Some(file) = files.into_iter().next() else {
+            return;
+        };
+        let name = file.name();
+        state.set(LoadState::Scanning(name.clone()));
+        spawn(async move {
+            let result = match file.read_bytes().await {
+                Ok(bytes) => decode_image(&bytes)
+                    .and_then(|frame| {
+                        let result = scan_frame(&frame, None, cube_size, &ColorDetectionConfig::default());
+                        if let Some(handler) = &on_debug_capture {
+                            handler.call(ScanDebugRecord::new(name.clone(), frame, cube_size, None, &result));
+                        }
+                        result
+                    })
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(format!("Could not read the file: {}", e)),
+            };
+            match result {
+                Ok(scan) => {
+                    state.set(LoadState::Scanned(name));
+                    on_scanned.call(scan);
+                }
+                Err(message) => state.set(LoadState::Failed(message)),
+            }
+        });
+    };
+
+    let border = if dragging() { "#3b82f6" } else { "#9ca3af" };
+    let background = if dragging() { "#eff6ff" } else { "#f9fafb" };
+
+    rsx! {
+        div {
+            class: "still-image-scanner",
+            style: format!("display: flex; flex-direction: column; align-items: center; gap: 10px; \
+                           padding: 20px; border: 2px dashed {}; border-radius: 10px; background: {};",
+                           border, background),
+            ondragover: move |evt| {
+                evt.prevent_default();
+                dragging.set(true);
+            },
+            ondragleave: move |_| dragging.set(false),
+            ondrop: move |evt| {
+                evt.prevent_default();
+                dragging.set(false);
+                load(evt.files());
+            },
+
+            p {
+                style: "font-size: 16px; font-weight: 600; margin: 0;",
+                "🖼️ Drop a photo of the face here"
+            }
+
+            label {
+                style: "padding: 10px 20px; min-height: 44px; box-sizing: border-box; font-size: 14px; \
+                       background: #667eea; color: white; border-radius: 8px; cursor: pointer; \
+                       display: inline-flex; align-items: center;",
+                "📁 Choose a photo"
+                input {
+                    r#type: "file",
+                    accept: ACCEPTED_TYPES,
+                    style: "display: none;",
+                    onchange: move |evt| load(evt.files()),
+                }
+            }
+
+            p {
+                role: "status",
+                "aria-live": "polite",
+                style: "font-size: 13px; color: #6b7280; margin: 0; text-align: center;",
+                match state() {
+                    LoadState::Idle => "PNG or JPEG, with the face filling the photo".to_string(),
+                    LoadState::Scanning(name) => format!("Scanning {}…", name),
+                    LoadState::Scanned(name) => format!("✓ Scanned {}", name),
+                    LoadState::Failed(message) => format!("⚠️ {}", message),
+                }
+            }
+        }
+    }
+}
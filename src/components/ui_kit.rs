@@ -5,8 +5,20 @@
 //! - Clear icons for all actions
 //! - Simple, uncluttered layout
 //! - Bright, engaging colors
+//!
+//! Colors come from theme tokens exposed as CSS variables, so the light,
+//! dark and high-contrast themes can be switched at runtime with
+//! `ThemeProvider` and `ThemeSwitcher`. Components use the `token` constants
+//! in inline styles; each falls back to the light theme when no provider is
+//! present.
+//!
+//! Problems are surfaced the same way everywhere: toasts (success, info and
+//! error messages that go away on their own) are shown with a `Toaster` from
+//! `use_toasts` and drawn by `ToastHost`, and `KidErrorBoundary` replaces a
+//! part of the page that failed to render with a friendly message.
 
 use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Button size variants for different use cases
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -76,6 +88,276 @@ impl ButtonTheme {
             ButtonTheme::Secondary => "#7C3AED",
         }
     }
+
+    /// Name used for this theme's CSS variables
+    fn token_name(&self) -> &'static str {
+        match self {
+            ButtonTheme::Primary => "primary",
+            ButtonTheme::Success => "success",
+            ButtonTheme::Warning => "warning",
+            ButtonTheme::Danger => "danger",
+            ButtonTheme::Secondary => "secondary",
+        }
+    }
+
+    /// Background color as a theme variable, falling back to `background_color`
+    pub fn background_var(&self) -> String {
+        format!("var(--ui-{}, {})", self.token_name(), self.background_color())
+    }
+
+    /// Hover color as a theme variable, falling back to `hover_color`
+    pub fn hover_var(&self) -> String {
+        format!("var(--ui-{}-hover, {})", self.token_name(), self.hover_color())
+    }
+}
+
+/// Theme variables for inline styles, with light-theme fallbacks
+pub mod token {
+    /// Page background
+    pub const BACKGROUND: &str = "var(--ui-background, #FFFFFF)";
+    /// Panel background (players, pickers)
+    pub const SURFACE: &str = "var(--ui-surface, #F5F5F5)";
+    /// Cards and boxes on top of a panel
+    pub const SURFACE_RAISED: &str = "var(--ui-surface-raised, #FFFFFF)";
+    /// Main text
+    pub const TEXT: &str = "var(--ui-text, #1F2937)";
+    /// Secondary text
+    pub const TEXT_MUTED: &str = "var(--ui-text-muted, #666666)";
+    /// Borders and dividers
+    pub const BORDER: &str = "var(--ui-border, #DDDDDD)";
+    /// Selection and focus outlines
+    pub const FOCUS: &str = "var(--ui-focus, #3182CE)";
+    /// Text on colored buttons and badges
+    pub const ON_ACCENT: &str = "var(--ui-on-accent, #FFFFFF)";
+    /// Highlighted rows and items
+    pub const HIGHLIGHT: &str = "var(--ui-highlight, #E3F2FD)";
+}
+
+/// App color themes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Theme {
+    /// Light background, bright accents
+    #[default]
+    Light,
+    /// Dark background for low-light use
+    Dark,
+    /// Black background, maximum contrast colors
+    HighContrast,
+}
+
+/// Colors for one theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemeTokens {
+    pub background: &'static str,
+    pub surface: &'static str,
+    pub surface_raised: &'static str,
+    pub text: &'static str,
+    pub text_muted: &'static str,
+    pub border: &'static str,
+    pub focus: &'static str,
+    pub on_accent: &'static str,
+    pub highlight: &'static str,
+    /// Button colors as (background, hover), in `ButtonTheme` order
+    pub primary: (&'static str, &'static str),
+    pub success: (&'static str, &'static str),
+    pub warning: (&'static str, &'static str),
+    pub danger: (&'static str, &'static str),
+    pub secondary: (&'static str, &'static str),
+}
+
+impl Theme {
+    /// All themes, in switcher order
+    pub fn all() -> [Theme; 3] {
+        [Theme::Light, Theme::Dark, Theme::HighContrast]
+    }
+
+    /// Display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::Light => "Light",
+            Theme::Dark => "Dark",
+            Theme::HighContrast => "High Contrast",
+        }
+    }
+
+    /// Icon for the theme switcher
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Theme::Light => "☀️",
+            Theme::Dark => "🌙",
+            Theme::HighContrast => "◐",
+        }
+    }
+
+    /// Get the colors for this theme
+    pub fn tokens(&self) -> ThemeTokens {
+        let button = |theme: ButtonTheme| (theme.background_color(), theme.hover_color());
+        match self {
+            Theme::Light => ThemeTokens {
+                background: "#FFFFFF",
+                surface: "#F5F5F5",
+                surface_raised: "#FFFFFF",
+                text: "#1F2937",
+                text_muted: "#666666",
+                border: "#DDDDDD",
+                focus: "#3182CE",
+                on_accent: "#FFFFFF",
+                highlight: "#E3F2FD",
+                primary: button(ButtonTheme::Primary),
+                success: button(ButtonTheme::Success),
+                warning: button(ButtonTheme::Warning),
+                danger: button(ButtonTheme::Danger),
+                secondary: button(ButtonTheme::Secondary),
+            },
+            Theme::Dark => ThemeTokens {
+                background: "#111827",
+                surface: "#1F2937",
+                surface_raised: "#374151",
+                text: "#F9FAFB",
+                text_muted: "#9CA3AF",
+                border: "#4B5563",
+                focus: "#60A5FA",
+                on_accent: "#FFFFFF",
+                highlight: "#1E3A8A",
+                primary: ("#3B82F6", "#60A5FA"),
+                success: ("#059669", "#10B981"),
+                warning: ("#D97706", "#F59E0B"),
+                danger: ("#DC2626", "#EF4444"),
+                secondary: ("#7C3AED", "#8B5CF6"),
+            },
+            Theme::HighContrast => ThemeTokens {
+                background: "#000000",
+                surface: "#000000",
+                surface_raised: "#000000",
+                text: "#FFFFFF",
+                text_muted: "#FFFF00",
+                border: "#FFFFFF",
+                focus: "#FFFF00",
+                on_accent: "#000000",
+                highlight: "#333333",
+                primary: ("#00FFFF", "#FFFFFF"),
+                success: ("#00FF00", "#FFFFFF"),
+                warning: ("#FFFF00", "#FFFFFF"),
+                danger: ("#FF6666", "#FFFFFF"),
+                secondary: ("#FF66FF", "#FFFFFF"),
+            },
+        }
+    }
+}
+
+impl ThemeTokens {
+    /// CSS custom property declarations for an inline `style` attribute
+    pub fn css_variables(&self) -> String {
+        let mut vars = vec![
+            ("background", self.background),
+            ("surface", self.surface),
+            ("surface-raised", self.surface_raised),
+            ("text", self.text),
+            ("text-muted", self.text_muted),
+            ("border", self.border),
+            ("focus", self.focus),
+            ("on-accent", self.on_accent),
+            ("highlight", self.highlight),
+        ];
+        for (theme, (background, hover)) in [
+            (ButtonTheme::Primary, self.primary),
+            (ButtonTheme::Success, self.success),
+            (ButtonTheme::Warning, self.warning),
+            (ButtonTheme::Danger, self.danger),
+            (ButtonTheme::Secondary, self.secondary),
+        ] {
+            vars.push((theme.token_name(), background));
+            vars.push((hover_name(theme), hover));
+        }
+
+        vars.iter()
+            .map(|(name, value)| format!("--ui-{}: {};", name, value))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// CSS variable name for a button theme's hover color
+fn hover_name(theme: ButtonTheme) -> &'static str {
+    match theme {
+        ButtonTheme::Primary => "primary-hover",
+        ButtonTheme::Success => "success-hover",
+        ButtonTheme::Warning => "warning-hover",
+        ButtonTheme::Danger => "danger-hover",
+        ButtonTheme::Secondary => "secondary-hover",
+    }
+}
+
+/// Props for the ThemeProvider component
+#[derive(Props, Clone, PartialEq)]
+pub struct ThemeProviderProps {
+    /// Theme to start with
+    #[props(default)]
+    pub initial: Theme,
+    /// Called with the theme whenever it is switched (and once at first)
+    #[props(default)]
+    pub on_change: Option<EventHandler<Theme>>,
+    /// Content that uses the theme
+    pub children: Element,
+}
+
+/// Applies a theme to its children and shares it with `ThemeSwitcher`
+///
+/// The current theme is provided as a `Signal<Theme>` context.
+#[component]
+pub fn ThemeProvider(props: ThemeProviderProps) -> Element {
+    let initial = props.initial;
+    let theme = use_context_provider(|| Signal::new(initial));
+    let on_change = props.on_change;
+    use_effect(move || {
+        let current = theme();
+        if let Some(handler) = on_change {
+            handler.call(current);
+        }
+    });
+    let variables = theme().tokens().css_variables();
+
+    rsx! {
+        div {
+            class: "theme-root",
+            style: "{variables} background: var(--ui-background); color: var(--ui-text); min-height: 100%;",
+            {props.children}
+        }
+    }
+}
+
+/// Buttons for switching between themes at runtime
+///
+/// Must be placed inside a `ThemeProvider`; renders nothing otherwise.
+#[component]
+pub fn ThemeSwitcher() -> Element {
+    let Some(mut theme) = try_use_context::<Signal<Theme>>() else {
+        return rsx! {};
+    };
+    let current = theme();
+
+    rsx! {
+        div {
+            role: "radiogroup",
+            "aria-label": "Color theme",
+            style: "display: flex; gap: 8px;",
+            for option in Theme::all() {
+                button {
+                    key: "{option.label()}",
+                    role: "radio",
+                    "aria-checked": "{option == current}",
+                    title: "{option.label()}",
+                    style: if option == current {
+                        format!("min-width: 44px; min-height: 44px; cursor: pointer; border-radius: 8px; background: {}; color: {}; border: 2px solid {};", token::SURFACE_RAISED, token::TEXT, token::FOCUS)
+                    } else {
+                        format!("min-width: 44px; min-height: 44px; cursor: pointer; border-radius: 8px; background: {}; color: {}; border: 2px solid {};", token::SURFACE, token::TEXT, token::BORDER)
+                    },
+                    onclick: move |_| theme.set(option),
+                    "{option.icon()} {option.label()}"
+                }
+            }
+        }
+    }
 }
 
 /// Props for the KidButton component
@@ -104,18 +386,19 @@ pub struct KidButtonProps {
 pub fn KidButton(props: KidButtonProps) -> Element {
     let size_px = props.size.pixels();
     let font_size = props.size.font_size();
-    let bg_color = props.theme.background_color();
-    let hover_color = props.theme.hover_color();
+    let bg_color = props.theme.background_var();
+    let hover_color = props.theme.hover_var();
 
     let button_style = format!(
         "min-width: {}px; min-height: {}px; font-size: {}px; background: {}; padding: 12px 24px; \
-         border: none; border-radius: 12px; color: white; font-weight: bold; cursor: pointer; \
+         border: none; border-radius: 12px; color: {}; font-weight: bold; cursor: pointer; \
          box-shadow: 0 4px 6px rgba(0,0,0,0.1); transition: all 0.2s; display: flex; \
          align-items: center; justify-content: center; gap: 8px; {}",
         size_px,
         size_px,
         font_size,
         bg_color,
+        token::ON_ACCENT,
         if props.disabled { "opacity: 0.5; cursor: not-allowed;" } else { "" }
     );
 
@@ -165,11 +448,11 @@ pub struct KidCardProps {
 pub fn KidCard(props: KidCardProps) -> Element {
     rsx! {
         div {
-            style: "background: white; border-radius: 16px; padding: 24px; box-shadow: 0 4px 12px rgba(0,0,0,0.1); \
+            style: "background: {token::SURFACE_RAISED}; border-radius: 16px; padding: 24px; box-shadow: 0 4px 12px rgba(0,0,0,0.1); \
                     margin: 16px; max-width: 800px;",
             if !props.title.is_empty() {
                 h2 {
-                    style: "margin: 0 0 16px 0; font-size: 28px; color: #1F2937; font-weight: bold;",
+                    style: "margin: 0 0 16px 0; font-size: 28px; color: {token::TEXT}; font-weight: bold;",
                     "{props.title}"
                 }
             }
@@ -203,17 +486,18 @@ pub struct KidIconButtonProps {
 pub fn KidIconButton(props: KidIconButtonProps) -> Element {
     let size_px = props.size.pixels();
     let font_size = props.size.font_size() * 140 / 100; // 1.4x for icons
-    let bg_color = props.theme.background_color();
-    let hover_color = props.theme.hover_color();
+    let bg_color = props.theme.background_var();
+    let hover_color = props.theme.hover_var();
 
     let button_style = format!(
         "width: {}px; height: {}px; font-size: {}px; background: {}; border: none; \
-         border-radius: 12px; color: white; cursor: pointer; box-shadow: 0 4px 6px rgba(0,0,0,0.1); \
+         border-radius: 12px; color: {}; cursor: pointer; box-shadow: 0 4px 6px rgba(0,0,0,0.1); \
          transition: all 0.2s; display: flex; align-items: center; justify-content: center; {}",
         size_px,
         size_px,
         font_size,
         bg_color,
+        token::ON_ACCENT,
         if props.disabled { "opacity: 0.5; cursor: not-allowed;" } else { "" }
     );
 
@@ -257,11 +541,11 @@ pub struct KidBadgeProps {
 /// Kid-friendly badge component for displaying status or counts
 #[component]
 pub fn KidBadge(props: KidBadgeProps) -> Element {
-    let bg_color = props.theme.background_color();
+    let bg_color = props.theme.background_var();
 
     rsx! {
         span {
-            style: "display: inline-block; padding: 6px 12px; background: {bg_color}; color: white; \
+            style: "display: inline-block; padding: 6px 12px; background: {bg_color}; color: {token::ON_ACCENT}; \
                     border-radius: 20px; font-size: 14px; font-weight: bold; margin: 4px;",
             "{props.text}"
         }
@@ -282,22 +566,24 @@ pub struct KidProgressProps {
 #[component]
 pub fn KidProgress(props: KidProgressProps) -> Element {
     let clamped_value = props.value.max(0.0).min(100.0);
+    let success = ButtonTheme::Success.background_var();
+    let primary = ButtonTheme::Primary.background_var();
 
     rsx! {
         div {
             style: "margin: 16px 0;",
             if !props.label.is_empty() {
                 div {
-                    style: "font-size: 16px; font-weight: bold; color: #1F2937; margin-bottom: 8px;",
+                    style: "font-size: 16px; font-weight: bold; color: {token::TEXT}; margin-bottom: 8px;",
                     "{props.label}"
                 }
             }
             div {
-                style: "width: 100%; height: 32px; background: #E5E7EB; border-radius: 16px; overflow: hidden;",
+                style: "width: 100%; height: 32px; background: {token::BORDER}; border-radius: 16px; overflow: hidden;",
                 div {
-                    style: "height: 100%; background: linear-gradient(90deg, #10B981, #3B82F6); \
+                    style: "height: 100%; background: linear-gradient(90deg, {success}, {primary}); \
                             width: {clamped_value}%; transition: width 0.3s ease; display: flex; \
-                            align-items: center; justify-content: center; color: white; font-weight: bold; \
+                            align-items: center; justify-content: center; color: {token::ON_ACCENT}; font-weight: bold; \
                             font-size: 14px;",
                     if clamped_value > 10.0 {
                         "{clamped_value:.0}%"
@@ -308,6 +594,272 @@ pub fn KidProgress(props: KidProgressProps) -> Element {
     }
 }
 
+/// Kind of toast notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    /// Something worked (green)
+    Success,
+    /// Something worth knowing (blue)
+    Info,
+    /// Something went wrong (red)
+    Error,
+}
+
+impl ToastKind {
+    /// Icon shown before the message
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ToastKind::Success => "✅",
+            ToastKind::Info => "💡",
+            ToastKind::Error => "⚠️",
+        }
+    }
+
+    /// Color of the toast
+    pub fn theme(&self) -> ButtonTheme {
+        match self {
+            ToastKind::Success => ButtonTheme::Success,
+            ToastKind::Info => ButtonTheme::Primary,
+            ToastKind::Error => ButtonTheme::Danger,
+        }
+    }
+
+    /// How long the toast stays up before going away on its own; errors
+    /// stay longer so there is time to read them
+    pub fn duration_ms(&self) -> u64 {
+        match self {
+            ToastKind::Success | ToastKind::Info => 4000,
+            ToastKind::Error => 8000,
+        }
+    }
+}
+
+/// One toast notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    /// Identifies the toast for dismissing it
+    pub id: u64,
+    pub kind: ToastKind,
+    pub message: String,
+}
+
+/// Toasts on screen, oldest first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ToastQueue {
+    toasts: Vec<Toast>,
+    next_id: u64,
+}
+
+impl ToastQueue {
+    /// Most toasts shown at once; older ones are dropped first
+    pub const MAX_VISIBLE: usize = 4;
+
+    /// Create an empty queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a toast and return its id
+    ///
+    /// The same message twice in a row (e.g. a save failing on every
+    /// change) shows one toast, not a pile of them.
+    pub fn push(&mut self, kind: ToastKind, message: impl Into<String>) -> u64 {
+        let message = message.into();
+        if let Some(last) = self.toasts.last() {
+            if last.kind == kind && last.message == message {
+                return last.id;
+            }
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.toasts.push(Toast { id, kind, message });
+        if self.toasts.len() > Self::MAX_VISIBLE {
+            self.toasts.remove(0);
+        }
+        id
+    }
+
+    /// Remove a toast; returns false if it was already gone
+    pub fn dismiss(&mut self, id: u64) -> bool {
+        let before = self.toasts.len();
+        self.toasts.retain(|toast| toast.id != id);
+        self.toasts.len() != before
+    }
+
+    /// Toasts on screen, oldest first
+    pub fn toasts(&self) -> &[Toast] {
+        &self.toasts
+    }
+
+    /// Whether nothing is shown
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+}
+
+/// Shows toasts; get one with `use_toasts`
+///
+/// Cheap to copy into event handlers. Without a provider above it, showing
+/// a toast does nothing.
+#[derive(Clone, Copy)]
+pub struct Toaster(Option<Signal<ToastQueue>>);
+
+impl Toaster {
+    /// Show a toast of the given kind
+    pub fn show(&mut self, kind: ToastKind, message: impl Into<String>) {
+        if let Some(queue) = self.0.as_mut() {
+            queue.write().push(kind, message);
+        }
+    }
+
+    /// Show a success toast
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.show(ToastKind::Success, message);
+    }
+
+    /// Show an info toast
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.show(ToastKind::Info, message);
+    }
+
+    /// Show an error toast
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.show(ToastKind::Error, message);
+    }
+
+    /// Show an error toast for a failed result, with `what` saying what
+    /// was being done, e.g. "save your progress"
+    pub fn report<T, E: std::fmt::Display>(&mut self, what: &str, result: Result<T, E>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.error(format!("Could not {}: {}", what, e));
+                None
+            }
+        }
+    }
+}
+
+/// Create the toast queue and share it with everything below; call once,
+/// near the root, and render a `ToastHost` under it
+pub fn use_toast_provider() -> Toaster {
+    let queue = use_context_provider(|| Signal::new(ToastQueue::new()));
+    Toaster(Some(queue))
+}
+
+/// The toaster shared by `use_toast_provider`
+pub fn use_toasts() -> Toaster {
+    Toaster(try_use_context::<Signal<ToastQueue>>())
+}
+
+/// Draws the toasts in the bottom corner of the screen
+///
+/// Must be placed below `use_toast_provider`; renders nothing otherwise.
+#[component]
+pub fn ToastHost() -> Element {
+    let Some(queue) = try_use_context::<Signal<ToastQueue>>() else {
+        return rsx! {};
+    };
+    let toasts = queue.read().toasts().to_vec();
+
+    rsx! {
+        div {
+            class: "toast-host",
+            role: "region",
+            "aria-label": "Notifications",
+            "aria-live": "polite",
+            style: "position: fixed; bottom: 16px; right: 16px; z-index: 1000; display: flex; \
+                    flex-direction: column; gap: 8px; max-width: min(400px, calc(100vw - 32px));",
+            for toast in toasts {
+                ToastItem { key: "{toast.id}", toast: toast, queue: queue }
+            }
+        }
+    }
+}
+
+/// One toast, which dismisses itself after its kind's duration
+#[component]
+fn ToastItem(toast: Toast, queue: Signal<ToastQueue>) -> Element {
+    let id = toast.id;
+    let duration = toast.kind.duration_ms();
+    use_future(move || async move {
+        crate::components::solution_player::delay(duration).await;
+        queue.write().dismiss(id);
+    });
+    let accent = toast.kind.theme().background_var();
+
+    rsx! {
+        div {
+            class: "toast",
+            role: if toast.kind == ToastKind::Error { "alert" } else { "status" },
+            style: "display: flex; align-items: center; gap: 12px; padding: 12px 16px; border-radius: 12px; \
+                    background: {token::SURFACE_RAISED}; color: {token::TEXT}; border-left: 6px solid {accent}; \
+                    box-shadow: 0 4px 12px rgba(0,0,0,0.15); font-size: 16px;",
+            span { style: "font-size: 20px;", "{toast.kind.icon()}" }
+            span { style: "flex: 1;", "{toast.message}" }
+            button {
+                "aria-label": "Dismiss",
+                title: "Dismiss",
+                style: "min-width: 44px; min-height: 44px; border: none; background: none; color: {token::TEXT_MUTED}; \
+                        font-size: 20px; cursor: pointer;",
+                onclick: move |_| {
+                    queue.write().dismiss(id);
+                },
+                "✕"
+            }
+        }
+    }
+}
+
+/// Props for the KidErrorBoundary component
+#[derive(Props, Clone, PartialEq)]
+pub struct KidErrorBoundaryProps {
+    /// Heading shown when something inside fails
+    #[props(default = "Oops! Something went wrong".to_string())]
+    pub title: String,
+    /// Content that might fail to render
+    pub children: Element,
+}
+
+/// Catches errors from its children and shows a friendly message with a
+/// "Try again" button instead of a broken page
+#[component]
+pub fn KidErrorBoundary(props: KidErrorBoundaryProps) -> Element {
+    let title = props.title.clone();
+
+    rsx! {
+        ErrorBoundary {
+            handle_error: move |errors: ErrorContext| {
+                let message = errors.error().map(|error| error.to_string()).unwrap_or_default();
+                rsx! {
+                    div {
+                        role: "alert",
+                        KidCard {
+                            title: "{title}",
+                            p {
+                                style: "color: {token::TEXT}; font-size: 18px;",
+                                "This part of the app ran into a problem. Your cube is safe!"
+                            }
+                            if !message.is_empty() {
+                                p {
+                                    style: "color: {token::TEXT_MUTED}; font-size: 14px;",
+                                    "{message}"
+                                }
+                            }
+                            KidButton {
+                                label: "Try again",
+                                icon: "🔄",
+                                onclick: move |_| errors.clear_errors(),
+                            }
+                        }
+                    }
+                }
+            },
+            {props.children}
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -416,4 +968,77 @@ mod tests {
         // At least 3 of 5 themes should be bright
         assert!(bright_count >= 3, "Not enough bright colors: {}", bright_count);
     }
+
+    #[test]
+    fn test_token_fallbacks_match_light_theme() {
+        let light = Theme::Light.tokens();
+        assert!(token::BACKGROUND.contains(light.background));
+        assert!(token::SURFACE.contains(light.surface));
+        assert!(token::SURFACE_RAISED.contains(light.surface_raised));
+        assert!(token::TEXT.contains(light.text));
+        assert!(token::TEXT_MUTED.contains(light.text_muted));
+        assert!(token::BORDER.contains(light.border));
+        assert!(token::FOCUS.contains(light.focus));
+        assert!(token::ON_ACCENT.contains(light.on_accent));
+        assert!(token::HIGHLIGHT.contains(light.highlight));
+    }
+
+    #[test]
+    fn test_button_vars() {
+        assert_eq!(ButtonTheme::Primary.background_var(), "var(--ui-primary, #3B82F6)");
+        assert_eq!(ButtonTheme::Danger.hover_var(), "var(--ui-danger-hover, #DC2626)");
+    }
+
+    #[test]
+    fn test_css_variables_define_every_token() {
+        for theme in Theme::all() {
+            let css = theme.tokens().css_variables();
+            for name in ["background", "surface", "surface-raised", "text", "text-muted", "border",
+                         "focus", "on-accent", "highlight", "primary", "primary-hover",
+                         "success-hover", "warning", "danger", "secondary-hover"] {
+                assert!(css.contains(&format!("--ui-{}:", name)), "{:?} missing {}", theme, name);
+            }
+        }
+        assert!(Theme::Dark.tokens().css_variables().contains("--ui-background: #111827;"));
+    }
+
+    #[test]
+    fn test_themes_are_distinct() {
+        assert_ne!(Theme::Light.tokens(), Theme::Dark.tokens());
+        assert_ne!(Theme::Dark.tokens(), Theme::HighContrast.tokens());
+        assert_eq!(Theme::default(), Theme::Light);
+    }
+
+    #[test]
+    fn test_toast_queue_push_and_dismiss() {
+        let mut queue = ToastQueue::new();
+        assert!(queue.is_empty());
+        let saved = queue.push(ToastKind::Success, "Saved!");
+        let failed = queue.push(ToastKind::Error, "Could not solve");
+        assert_ne!(saved, failed);
+        assert_eq!(queue.toasts().len(), 2);
+        assert!(queue.dismiss(saved));
+        assert!(!queue.dismiss(saved));
+        assert_eq!(queue.toasts()[0].message, "Could not solve");
+    }
+
+    #[test]
+    fn test_toast_queue_merges_repeats_and_caps() {
+        let mut queue = ToastQueue::new();
+        let first = queue.push(ToastKind::Error, "Could not save");
+        assert_eq!(queue.push(ToastKind::Error, "Could not save"), first);
+        assert_eq!(queue.toasts().len(), 1);
+        for i in 0..ToastQueue::MAX_VISIBLE {
+            queue.push(ToastKind::Info, format!("Tip {}", i));
+        }
+        assert_eq!(queue.toasts().len(), ToastQueue::MAX_VISIBLE);
+        assert!(queue.toasts().iter().all(|toast| toast.id != first));
+    }
+
+    #[test]
+    fn test_toast_kinds() {
+        assert_eq!(ToastKind::Error.theme(), ButtonTheme::Danger);
+        assert_eq!(ToastKind::Success.theme(), ButtonTheme::Success);
+        assert!(ToastKind::Error.duration_ms() > ToastKind::Info.duration_ms());
+    }
 }
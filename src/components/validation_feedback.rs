@@ -4,8 +4,9 @@
 //! - Show warning for wrong color counts
 //! - Show error for unsolvable states
 //! - Clear error messages
+//! - Suggested fixes pointing at the stickers to check
 
-use crate::cube::validation::ValidationError;
+use crate::cube::validation::{FixSuggestion, ValidationError};
 use dioxus::prelude::*;
 
 /// Props for the ValidationFeedback component
@@ -13,6 +14,14 @@ use dioxus::prelude::*;
 pub struct ValidationFeedbackProps {
     /// The validation result to display (None if valid)
     pub validation_error: Option<ValidationError>,
+
+    /// Suggested fixes, from `Cube::fix_suggestions`
+    #[props(default)]
+    pub suggestions: Vec<FixSuggestion>,
+
+    /// Called when a suggestion is picked, to select its stickers
+    #[props(optional)]
+    pub on_suggestion_select: Option<EventHandler<FixSuggestion>>,
 }
 
 /// Validation feedback component that displays errors and warnings
@@ -80,6 +89,26 @@ pub fn ValidationFeedback(props: ValidationFeedbackProps) -> Element {
                                 "{detail_text}"
                             }
                         }
+                        if !props.suggestions.is_empty() {
+                            ul {
+                                class: "validation-suggestions",
+                                "aria-label": "Suggested fixes",
+                                for (i, suggestion) in props.suggestions.iter().cloned().enumerate() {
+                                    li {
+                                        key: "{i}",
+                                        button {
+                                            class: "validation-suggestion",
+                                            onclick: move |_| {
+                                                if let Some(handler) = &props.on_suggestion_select {
+                                                    handler.call(suggestion.clone());
+                                                }
+                                            },
+                                            "{suggestion.message}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
             }
@@ -127,9 +156,38 @@ fn get_error_info(error: &ValidationError) -> (&'static str, &'static str, Strin
             );
             ("error", "⚠", message, details)
         }
+        ValidationError::ImpossiblePiece { colors } | ValidationError::OppositeColors { colors } => {
+            let message = "A piece has impossible colors".to_string();
+            let details = Some(format!(
+                "No real piece is {}. One of its stickers was probably entered wrong.",
+                color_names(colors)
+            ));
+            ("error", "⚠", message, details)
+        }
+        ValidationError::DuplicatePiece { colors } => {
+            let message = "A piece appears twice".to_string();
+            let details = Some(format!(
+                "There are two {} pieces, so another piece is missing. Check the stickers of both.",
+                color_names(colors)
+            ));
+            ("error", "⚠", message, details)
+        }
+        ValidationError::MirroredCorner { colors } => {
+            let message = "A corner is mirrored".to_string();
+            let details = Some(format!(
+                "The {} corner's colors go around it the wrong way. Two of its stickers are swapped.",
+                color_names(colors)
+            ));
+            ("error", "⚠", message, details)
+        }
     }
 }
 
+/// Colors of a piece, e.g. `White-Red-Green`
+fn color_names(colors: &[crate::cube::Color]) -> String {
+    colors.iter().map(|c| format!("{:?}", c)).collect::<Vec<_>>().join("-")
+}
+
 /// Helper function to get a kid-friendly message for validation errors
 pub fn get_kid_friendly_message(error: &ValidationError) -> String {
     match error {
@@ -148,6 +206,15 @@ pub fn get_kid_friendly_message(error: &ValidationError) -> String {
         ValidationError::PermutationParity => {
             "This cube can't be solved! Try resetting it and entering the colors again.".to_string()
         }
+        ValidationError::ImpossiblePiece { .. } | ValidationError::OppositeColors { .. } => {
+            "Oops! One piece has colors that don't go together. Let's find the wrong sticker!".to_string()
+        }
+        ValidationError::DuplicatePiece { .. } => {
+            "Oops! The same piece shows up twice. Let's find the wrong sticker!".to_string()
+        }
+        ValidationError::MirroredCorner { .. } => {
+            "Oops! Two stickers on one corner are swapped. Let's fix that!".to_string()
+        }
     }
 }
 
@@ -204,6 +271,28 @@ pub fn get_validation_styles() -> &'static str {
     opacity: 0.9;
 }
 
+.validation-suggestions {
+    list-style: none;
+    margin: 0;
+    padding: 0;
+    display: flex;
+    flex-direction: column;
+    gap: 6px;
+}
+
+.validation-suggestion {
+    width: 100%;
+    min-height: 44px;
+    padding: 8px 12px;
+    text-align: left;
+    font-size: 14px;
+    color: inherit;
+    background: rgba(255, 255, 255, 0.6);
+    border: 1px solid currentColor;
+    border-radius: 6px;
+    cursor: pointer;
+}
+
 /* Kid-friendly styles - larger and more colorful */
 .validation-feedback.kid-friendly {
     padding: 20px;
@@ -331,6 +420,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_error_info_impossible_piece() {
+        let error = ValidationError::OppositeColors { colors: vec![Color::White, Color::Yellow] };
+        let (severity, _, message, details) = get_error_info(&error);
+
+        assert_eq!(severity, "error");
+        assert!(message.contains("impossible"));
+        assert!(details.unwrap().contains("White-Yellow"));
+        assert!(get_kid_friendly_message(&error).contains("Oops"));
+
+        let error = ValidationError::DuplicatePiece { colors: vec![Color::White, Color::Red, Color::Green] };
+        let (_, _, message, details) = get_error_info(&error);
+        assert!(message.contains("twice"));
+        assert!(details.unwrap().contains("White-Red-Green"));
+
+        let error = ValidationError::MirroredCorner { colors: vec![Color::White, Color::Green, Color::Red] };
+        assert!(get_error_info(&error).1 == "⚠");
+        assert!(get_kid_friendly_message(&error).contains("swapped"));
+    }
+
     #[test]
     fn test_validation_styles_not_empty() {
         let styles = get_validation_styles();
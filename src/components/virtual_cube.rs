@@ -0,0 +1,621 @@
+//! Virtual Cube Component
+//!
+//! A practice cube that can only be turned with moves (keyboard or a
+//! connected smart cube), never by editing stickers. Press Scramble, then
+//! the timer starts on the first move and stops when the cube is solved,
+//! producing a solve record and a replay of the solve (stored with the
+//! record as a reconstruction when a smart cube was used).
+//!
+//! Inspection follows the WCA rules: the time between the scramble and the
+//! first move is inspection, and going over 15 seconds adds +2 (over 17, a
+//! DNF). The finished solve can be marked +2 or DNF and given a comment.
+//!
+//! Given an algorithm to practice, Scramble sets up the case it solves and
+//! each move is checked against the algorithm, with the moves that undo a
+//! mistake shown as soon as it happens.
+
+use crate::components::Cube3D;
+use crate::components::ui_kit::token::{ON_ACCENT, SURFACE, TEXT, TEXT_MUTED};
+use crate::components::ui_kit::ButtonTheme;
+use crate::components::solution_player::delay;
+use crate::cube::notation::{algorithm_to_notation, ParsedMove};
+use crate::cube::scramble::{generate_scramble, ScrambleConfig};
+use crate::cube::{Cube, Move};
+use crate::input::{key_to_move, KEYBOARD_LAYOUT};
+use crate::state::{format_time, inspection_penalty, now_ms, MoveRecorder, MoveSource, Penalty, Reconstruction, Replay, SolveRecord};
+use crate::tutorial::{MoveCheck, MoveVerifier};
+use dioxus::prelude::*;
+
+/// How often the running timer is redrawn, in milliseconds
+const TIMER_TICK_MS: u64 = 50;
+
+/// Where a practice session is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PracticeState {
+    /// Free play, no timer
+    Idle,
+    /// Scrambled, timer starts on the first move
+    Scrambled,
+    /// Timer running
+    Solving,
+    /// Solved after a scramble; moves are free play until the next scramble
+    Solved,
+}
+
+/// Cube, timer and recording for one practice session
+#[derive(Debug, Clone)]
+pub struct PracticeSession {
+    cube: Cube,
+    scramble: Vec<Move>,
+    recorder: MoveRecorder,
+    state: PracticeState,
+    /// Checks moves against the algorithm being practiced, if any
+    verifier: Option<MoveVerifier>,
+    /// Session every solve record is put in
+    session_id: String,
+    /// Clock reading when inspection started, while inspecting
+    inspection_started_ms: Option<f64>,
+    /// Penalty earned by overrunning inspection
+    inspection_penalty: Penalty,
+    /// The last finished solve, for marking penalties and comments
+    last_record: Option<SolveRecord>,
+}
+
+impl PracticeSession {
+    /// Create a solved, idle session
+    pub fn new(size: usize) -> Self {
+        Self {
+            cube: Cube::new(size),
+            scramble: Vec::new(),
+            recorder: MoveRecorder::new(size, Vec::new()),
+            state: PracticeState::Idle,
+            verifier: None,
+            session_id: format!("session-{}", now_ms() as u64),
+            inspection_started_ms: None,
+            inspection_penalty: Penalty::None,
+            last_record: None,
+        }
+    }
+
+    /// Get the session solve records are put in
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// Put the next solves in another session
+    pub fn set_session_id(&mut self, session_id: impl Into<String>) {
+        self.session_id = session_id.into();
+    }
+
+    /// Get the current cube
+    pub fn cube(&self) -> &Cube {
+        &self.cube
+    }
+
+    /// Get the session state
+    pub fn state(&self) -> PracticeState {
+        self.state
+    }
+
+    /// Get the current scramble
+    pub fn scramble(&self) -> &[Move] {
+        &self.scramble
+    }
+
+    /// Get the current scramble in standard notation
+    pub fn scramble_notation(&self) -> String {
+        algorithm_to_notation(&self.recorder.replay().scramble)
+    }
+
+    /// Milliseconds on the timer: since the first move while solving, else
+    /// from the first move to the latest one
+    pub fn elapsed_ms(&self) -> u64 {
+        self.recorder.elapsed_ms()
+    }
+
+    /// Whether `mv` can be made on this cube (even cubes have no middle
+    /// slice to turn)
+    pub fn can_turn(&self, mv: Move) -> bool {
+        !(mv.is_slice() && self.cube.size().is_multiple_of(2))
+    }
+
+    /// Scramble the cube with a random scramble and arm the timer
+    ///
+    /// The cube can only be turned with face moves, so the scramble has no
+    /// wide moves.
+    pub fn start_scramble(&mut self, length: usize) {
+        let scramble = generate_scramble(&ScrambleConfig::new(length, self.cube.size()).without_wide_moves());
+        self.set_scramble(scramble.basic_moves().unwrap_or_default());
+    }
+
+    /// Scramble the cube with the given moves and arm the timer
+    pub fn set_scramble(&mut self, moves: Vec<Move>) {
+        let size = self.cube.size();
+        self.cube = Cube::new(size);
+        self.cube.apply_moves(&moves);
+        self.recorder = MoveRecorder::new(size, moves.iter().map(|m| ParsedMove::Basic(*m)).collect());
+        self.scramble = moves;
+        self.state = PracticeState::Scrambled;
+        self.verifier = None;
+        self.inspection_started_ms = Some(now_ms());
+        self.inspection_penalty = Penalty::None;
+        self.last_record = None;
+    }
+
+    /// Set up the case `algorithm` solves and arm the timer; moves are
+    /// checked against the algorithm as they are made
+    ///
+    /// Algorithm practice has no inspection limit.
+    pub fn practice_algorithm(&mut self, algorithm: Vec<Move>) {
+        self.set_scramble(algorithm.iter().rev().map(Move::inverse).collect());
+        self.verifier = Some(MoveVerifier::new(&self.cube, algorithm));
+        self.inspection_started_ms = None;
+    }
+
+    /// End inspection after `inspection_ms`, applying any overrun penalty
+    ///
+    /// The first move does this with the measured inspection time.
+    pub fn end_inspection(&mut self, inspection_ms: u64) {
+        if self.state == PracticeState::Scrambled && self.inspection_started_ms.take().is_some() {
+            self.inspection_penalty = inspection_penalty(inspection_ms);
+        }
+    }
+
+    /// Get the last finished solve
+    pub fn last_record(&self) -> Option<&SolveRecord> {
+        self.last_record.as_ref()
+    }
+
+    /// Mark the last solve with `penalty` (or clear it if already marked);
+    /// returns the updated record
+    pub fn toggle_penalty(&mut self, penalty: Penalty) -> Option<SolveRecord> {
+        let record = self.last_record.as_mut()?;
+        record.toggle_penalty(penalty);
+        Some(record.clone())
+    }
+
+    /// Set the last solve's comment; returns the updated record
+    pub fn set_comment(&mut self, comment: &str) -> Option<SolveRecord> {
+        let record = self.last_record.as_mut()?;
+        record.set_comment(comment);
+        Some(record.clone())
+    }
+
+    /// How the moves made compare with the algorithm being practiced
+    pub fn move_check(&self) -> Option<MoveCheck> {
+        self.verifier.as_ref().map(MoveVerifier::check)
+    }
+
+    /// Apply a move at the current time
+    ///
+    /// Returns the solve record and replay when this move solves the cube.
+    pub fn apply_move(&mut self, mv: Move, source: MoveSource) -> Option<(SolveRecord, Replay)> {
+        self.apply_move_with(mv, |recorder| recorder.record(ParsedMove::Basic(mv), source))
+    }
+
+    /// Apply a move at an explicit time (ms since the first move)
+    pub fn apply_move_at(
+        &mut self,
+        mv: Move,
+        source: MoveSource,
+        time_ms: u64,
+    ) -> Option<(SolveRecord, Replay)> {
+        self.apply_move_with(mv, |recorder| {
+            recorder.record_at(ParsedMove::Basic(mv), source, time_ms)
+        })
+    }
+
+    fn apply_move_with(
+        &mut self,
+        mv: Move,
+        record: impl FnOnce(&mut MoveRecorder),
+    ) -> Option<(SolveRecord, Replay)> {
+        if !self.can_turn(mv) {
+            return None;
+        }
+        self.cube.apply_move(mv);
+
+        match self.state {
+            PracticeState::Scrambled | PracticeState::Solving => {
+                if let Some(started) = self.inspection_started_ms {
+                    self.end_inspection((now_ms() - started).max(0.0) as u64);
+                }
+                record(&mut self.recorder);
+                if let Some(verifier) = &mut self.verifier {
+                    verifier.push(mv);
+                }
+                self.state = PracticeState::Solving;
+            }
+            PracticeState::Idle | PracticeState::Solved => return None,
+        }
+
+        // Rotations during the solve leave it solved but held differently
+        if !self.cube.is_solved_any_orientation() {
+            return None;
+        }
+
+        self.state = PracticeState::Solved;
+        let replay = self.recorder.stop();
+        let scramble = algorithm_to_notation(&replay.scramble);
+        let finished_at = (replay.started_at + replay.duration_ms()) / 1000;
+        let mut record = SolveRecord::new(replay.duration_ms(), scramble, replay.cube_size, finished_at)
+            .with_session(self.session_id.clone());
+        record.penalty = self.inspection_penalty;
+        if replay.moves.iter().any(|m| m.source == MoveSource::SmartCube) {
+            record.reconstruction = Some(Reconstruction::new(replay.clone()));
+        }
+        self.last_record = Some(record.clone());
+        Some((record, replay))
+    }
+}
+
+/// Feedback on the moves made against the algorithm being practiced
+fn move_check_message(check: &MoveCheck, total: usize) -> String {
+    let notation = |moves: &[Move]| moves.iter().map(|m| m.to_notation()).collect::<Vec<_>>().join(" ");
+    match check {
+        MoveCheck::OnTrack { done } => format!("On track: {}/{} moves", done, total),
+        MoveCheck::Complete => "Algorithm done!".to_string(),
+        MoveCheck::Diverged(divergence) => {
+            let expected = match divergence.expected {
+                Some(mv) => format!("expected {}", mv.to_notation()),
+                None => "the algorithm was already done".to_string(),
+            };
+            format!(
+                "Oops after move {}: {}, not {}. Undo with: {}",
+                divergence.at,
+                expected,
+                notation(&divergence.wrong),
+                notation(&divergence.fix)
+            )
+        }
+    }
+}
+
+/// Props for VirtualCube component
+#[derive(Props, Clone, PartialEq)]
+pub struct VirtualCubeProps {
+    /// Cube size
+    #[props(default = 3)]
+    pub size: usize,
+    /// Number of moves in generated scrambles
+    #[props(default = 20)]
+    pub scramble_length: usize,
+    /// Moves reported by a connected smart cube, oldest first
+    ///
+    /// New moves appended to this list are applied to the cube.
+    #[props(default)]
+    pub smart_cube_moves: Vec<Move>,
+    /// Algorithm to practice; empty for random scrambles
+    #[props(default)]
+    pub algorithm: Vec<Move>,
+    /// Called with the solve record and replay when a scrambled cube is solved
+    #[props(default)]
+    pub on_solve: Option<EventHandler<(SolveRecord, Replay)>>,
+    /// Timing session solves are recorded in (see `crate::timer`); a
+    /// session of its own if not given
+    #[props(default)]
+    pub session_id: Option<String>,
+    /// Called with the updated record when the last solve's penalty or
+    /// comment is changed
+    #[props(default)]
+    pub on_record_change: Option<EventHandler<SolveRecord>>,
+    /// Called with each move as it is made (for sounds and haptics)
+    #[props(default)]
+    pub on_turn: Option<EventHandler<Move>>,
+}
+
+/// Keyboard- and smart-cube-driven practice cube with a timer
+#[component]
+pub fn VirtualCube(props: VirtualCubeProps) -> Element {
+    let size = props.size;
+    let mut session = use_signal(|| PracticeSession::new(size));
+    // Number of smart cube moves already applied
+    let mut applied_smart_moves = use_signal(|| 0_usize);
+    let on_solve = props.on_solve;
+    let on_turn = props.on_turn;
+    let on_record_change = props.on_record_change;
+
+    let session_id = props.session_id.clone();
+    use_effect(use_reactive!(|session_id| {
+        if let Some(id) = session_id {
+            session.write().set_session_id(id);
+        }
+    }));
+
+    let changed = move |record: Option<SolveRecord>| {
+        if let (Some(record), Some(handler)) = (record, on_record_change) {
+            handler.call(record);
+        }
+    };
+
+    let mut apply = move |mv: Move, source: MoveSource| {
+        if !session.peek().can_turn(mv) {
+            return;
+        }
+        if let Some(handler) = on_turn {
+            handler.call(mv);
+        }
+        let solved = session.write().apply_move(mv, source);
+        if let (Some(result), Some(handler)) = (solved, on_solve) {
+            handler.call(result);
+        }
+    };
+
+    let smart_cube_moves = props.smart_cube_moves.clone();
+    use_effect(use_reactive!(|smart_cube_moves| {
+        let applied = *applied_smart_moves.peek();
+        for mv in smart_cube_moves.iter().skip(applied) {
+            apply(*mv, MoveSource::SmartCube);
+        }
+        applied_smart_moves.set(smart_cube_moves.len());
+    }));
+
+    // Redraw the timer between moves while a solve is running
+    let mut tick = use_signal(|| 0_u64);
+    use_future(move || async move {
+        loop {
+            delay(TIMER_TICK_MS).await;
+            if session.peek().state() == PracticeState::Solving {
+                tick += 1;
+            }
+        }
+    });
+
+    let scramble_length = props.scramble_length;
+    // Reading the tick subscribes this render to it
+    tick.read();
+    let current = session.read();
+    let state = current.state();
+    let last_record = current.last_record().cloned();
+    let time = match (&last_record, state) {
+        (Some(record), PracticeState::Solved) => record.display_time(),
+        _ => format_time(current.elapsed_ms()),
+    };
+    let scramble = current.scramble_notation();
+    let status = match state {
+        PracticeState::Idle => "Press Scramble to start",
+        PracticeState::Scrambled if props.algorithm.is_empty() => "Inspect: 15 seconds, timer starts on your first move",
+        PracticeState::Scrambled => "Timer starts on your first move",
+        PracticeState::Solving => "Solving...",
+        PracticeState::Solved => "Solved!",
+    };
+    let feedback = current.move_check().map(|check| (move_check_message(&check, props.algorithm.len()), check));
+    let cube = current.cube().clone();
+    let success = ButtonTheme::Success.background_var();
+    let algorithm = props.algorithm.clone();
+    drop(current);
+
+    rsx! {
+        div {
+            class: "virtual-cube",
+            style: "padding: 20px; background: {SURFACE}; color: {TEXT}; border-radius: 8px; margin: 20px 0; outline: none;",
+            tabindex: "0",
+            "aria-label": "Virtual cube. Use the keyboard to turn the cube.",
+            onkeydown: move |evt| {
+                if let Key::Character(key) = evt.key() {
+                    if let Some(mv) = key_to_move(&key) {
+                        evt.prevent_default();
+                        apply(mv, MoveSource::Keyboard);
+                    }
+                }
+            },
+
+            div {
+                style: "display: flex; gap: 10px; align-items: center; margin-bottom: 15px; flex-wrap: wrap;",
+                button {
+                    class: "btn btn-scramble",
+                    style: "padding: 12px 20px; min-height: 44px; font-size: 16px; cursor: pointer; background: {success}; color: {ON_ACCENT}; border: none; border-radius: 4px; touch-action: manipulation;",
+                    disabled: state == PracticeState::Solving,
+                    onclick: move |_| {
+                        if algorithm.is_empty() {
+                            session.write().start_scramble(scramble_length);
+                        } else {
+                            session.write().practice_algorithm(algorithm.clone());
+                        }
+                    },
+                    "🔀 Scramble"
+                }
+                span {
+                    class: "virtual-cube-timer",
+                    style: "font-family: monospace; font-size: 32px; font-weight: bold; min-width: 120px;",
+                    "{time}"
+                }
+                span {
+                    style: "font-size: 14px; color: {TEXT_MUTED};",
+                    role: "status",
+                    "{status}"
+                }
+            }
+
+            if !scramble.is_empty() {
+                p {
+                    style: "font-family: monospace; font-size: 14px; color: {TEXT_MUTED}; margin: 5px 0 15px;",
+                    "Scramble: {scramble}"
+                }
+            }
+
+            if let Some((message, check)) = feedback {
+                p {
+                    class: "virtual-cube-feedback",
+                    role: if matches!(check, MoveCheck::Diverged(_)) { "alert" } else { "status" },
+                    style: "font-size: 16px; font-weight: bold; margin: 5px 0 15px;",
+                    "{message}"
+                }
+            }
+
+            if let (Some(record), PracticeState::Solved) = (last_record, state) {
+                div {
+                    class: "virtual-cube-result",
+                    style: "display: flex; gap: 8px; align-items: center; margin: 5px 0 15px; flex-wrap: wrap;",
+                    for penalty in [Penalty::PlusTwo, Penalty::Dnf] {
+                        {
+                            let pressed = record.penalty == penalty;
+                            let (background, color) = if pressed { (TEXT, SURFACE) } else { (SURFACE, TEXT) };
+                            rsx! {
+                                button {
+                                    key: "{penalty.label()}",
+                                    class: "btn btn-penalty",
+                                    "aria-pressed": if pressed { "true" } else { "false" },
+                                    style: "padding: 8px 14px; min-height: 44px; font-size: 14px; cursor: pointer; border-radius: 4px; border: 1px solid {TEXT_MUTED}; background: {background}; color: {color};",
+                                    onclick: move |_| changed(session.write().toggle_penalty(penalty)),
+                                    "{penalty.label()}"
+                                }
+                            }
+                        }
+                    }
+                    input {
+                        r#type: "text",
+                        placeholder: "Comment",
+                        "aria-label": "Comment on this solve",
+                        value: "{record.comment.clone().unwrap_or_default()}",
+                        style: "flex: 1; min-width: 160px; padding: 8px; font-size: 14px; border-radius: 4px; border: 1px solid {TEXT_MUTED};",
+                        onchange: move |evt| changed(session.write().set_comment(&evt.value())),
+                    }
+                }
+            }
+
+            Cube3D { cube }
+
+            details {
+                style: "margin-top: 15px; font-size: 14px;",
+                summary { "Keyboard controls" }
+                div {
+                    style: "display: grid; grid-template-columns: repeat(auto-fill, minmax(80px, 1fr)); gap: 6px; margin-top: 8px; font-family: monospace;",
+                    for (key, mv) in KEYBOARD_LAYOUT.iter() {
+                        span {
+                            key: "{key}",
+                            "{key.to_uppercase()} → {mv.to_notation()}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_starts_idle() {
+        let mut session = PracticeSession::new(3);
+        assert_eq!(session.state(), PracticeState::Idle);
+
+        // Free play is not timed
+        assert!(session.apply_move(Move::R, MoveSource::Keyboard).is_none());
+        assert_eq!(session.state(), PracticeState::Idle);
+        assert_eq!(session.elapsed_ms(), 0);
+    }
+
+    #[test]
+    fn test_scrambled_solve_produces_record() {
+        let mut session = PracticeSession::new(3);
+        session.set_scramble(vec![Move::R, Move::U]);
+        assert_eq!(session.state(), PracticeState::Scrambled);
+
+        assert!(session.apply_move_at(Move::UPrime, MoveSource::Keyboard, 0).is_none());
+        assert_eq!(session.state(), PracticeState::Solving);
+
+        let (record, replay) = session
+            .apply_move_at(Move::RPrime, MoveSource::SmartCube, 1500)
+            .unwrap();
+        assert_eq!(session.state(), PracticeState::Solved);
+        assert_eq!(record.time_ms, 1500);
+        assert_eq!(record.scramble, "R U");
+        assert_eq!(replay.move_count(), 2);
+        assert!(replay.final_state().is_solved());
+        // Smart cube moves make the solve worth reconstructing
+        assert_eq!(record.reconstruction.map(|r| r.replay), Some(replay));
+    }
+
+    #[test]
+    fn test_start_scramble() {
+        let mut session = PracticeSession::new(3);
+        session.start_scramble(15);
+        assert_eq!(session.scramble().len(), 15);
+        assert!(!session.cube().is_solved());
+    }
+
+    #[test]
+    fn test_practice_algorithm() {
+        let mut session = PracticeSession::new(3);
+        session.practice_algorithm(vec![Move::R, Move::U, Move::RPrime]);
+        assert_eq!(session.scramble(), &[Move::R, Move::UPrime, Move::RPrime]);
+        assert_eq!(session.scramble_notation(), "R U' R'");
+        assert_eq!(session.move_check(), Some(MoveCheck::OnTrack { done: 0 }));
+
+        session.apply_move_at(Move::R, MoveSource::Keyboard, 0);
+        session.apply_move_at(Move::UPrime, MoveSource::Keyboard, 100);
+        let check = session.move_check().unwrap();
+        assert!(matches!(check, MoveCheck::Diverged(_)));
+        assert_eq!(move_check_message(&check, 3), "Oops after move 1: expected U, not U'. Undo with: U");
+
+        session.apply_move_at(Move::U, MoveSource::Keyboard, 200);
+        session.apply_move_at(Move::U, MoveSource::Keyboard, 300);
+        assert!(session.apply_move_at(Move::RPrime, MoveSource::Keyboard, 400).is_some());
+        assert_eq!(session.move_check(), Some(MoveCheck::Complete));
+
+        session.start_scramble(5);
+        assert_eq!(session.move_check(), None);
+    }
+
+    #[test]
+    fn test_inspection_overrun_and_marking() {
+        let mut session = PracticeSession::new(3);
+        session.set_scramble(vec![Move::R]);
+        session.end_inspection(16_000);
+        let (record, _) = session.apply_move_at(Move::RPrime, MoveSource::Keyboard, 900).unwrap();
+        assert_eq!(record.penalty, Penalty::PlusTwo);
+        assert_eq!(record.session_id.as_deref(), Some(session.session_id()));
+        assert_eq!(record.display_time(), "2.90+");
+
+        // The judge's call can be overruled, and the solve commented on
+        let updated = session.toggle_penalty(Penalty::PlusTwo).unwrap();
+        assert_eq!(updated.penalty, Penalty::None);
+        let updated = session.toggle_penalty(Penalty::Dnf).unwrap();
+        assert_eq!(updated.display_time(), "DNF(0.90)");
+        let updated = session.set_comment("lockup").unwrap();
+        assert_eq!(updated.comment.as_deref(), Some("lockup"));
+        assert_eq!(session.last_record(), Some(&updated));
+
+        // A new scramble starts a new inspection
+        session.set_scramble(vec![Move::U]);
+        assert_eq!(session.last_record(), None);
+        session.end_inspection(18_000);
+        let (record, _) = session.apply_move_at(Move::UPrime, MoveSource::Keyboard, 500).unwrap();
+        assert_eq!(record.penalty, Penalty::Dnf);
+    }
+
+    #[test]
+    fn test_slice_moves_ignored_on_even_cubes() {
+        let mut session = PracticeSession::new(4);
+        session.set_scramble(vec![Move::R]);
+        assert!(!session.can_turn(Move::M));
+        assert!(session.can_turn(Move::R));
+        assert!(session.apply_move_at(Move::M, MoveSource::Keyboard, 0).is_none());
+        assert_eq!(session.state(), PracticeState::Scrambled);
+        assert!(session.apply_move_at(Move::RPrime, MoveSource::Keyboard, 100).is_some());
+    }
+
+    #[test]
+    fn test_solved_after_rotation() {
+        let mut session = PracticeSession::new(3);
+        session.set_scramble(vec![Move::R]);
+        // x turns the whole cube around R, so R' still undoes the scramble
+        assert!(session.apply_move_at(Move::X, MoveSource::Keyboard, 0).is_none());
+        let (record, _) = session.apply_move_at(Move::RPrime, MoveSource::Keyboard, 700).unwrap();
+        assert_eq!(record.time_ms, 700);
+        assert!(!session.cube().is_solved());
+    }
+
+    #[test]
+    fn test_quick_start_has_no_penalty() {
+        let mut session = PracticeSession::new(3);
+        session.set_scramble(vec![Move::F]);
+        let (record, _) = session.apply_move_at(Move::FPrime, MoveSource::Keyboard, 300).unwrap();
+        assert_eq!(record.penalty, Penalty::None);
+        assert!(session.toggle_penalty(Penalty::PlusTwo).is_some());
+        assert_eq!(PracticeSession::new(3).toggle_penalty(Penalty::Dnf), None);
+    }
+}
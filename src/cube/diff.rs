@@ -0,0 +1,80 @@
+//! Sticker-by-sticker comparison of two cube states
+//!
+//! Lists exactly which stickers changed color, e.g. to highlight what a move
+//! did in the UI or to check a practice exercise ended in the expected state.
+
+use super::state::{Color, Cube, FaceName};
+
+/// A sticker position whose color differs between two cubes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StickerDiff {
+    pub face: FaceName,
+    pub row: usize,
+    pub col: usize,
+    /// Color on the cube `diff` was called on
+    pub before: Color,
+    /// Color on the other cube
+    pub after: Color,
+}
+
+impl Cube {
+    /// List the stickers whose colors differ from `other`
+    ///
+    /// Positions are listed face by face in [`FaceName::all`] order, row by
+    /// row. Equal cubes give an empty list.
+    ///
+    /// # Panics
+    /// Panics if the cubes are different sizes
+    pub fn diff(&self, other: &Cube) -> Vec<StickerDiff> {
+        assert_eq!(self.size(), other.size(), "Only cubes of the same size can be compared");
+
+        self.stickers()
+            .zip(other.stickers())
+            .filter(|((.., before), (.., after))| before != after)
+            .map(|((face, row, col, before), (.., after))| StickerDiff { face, row, col, before, after })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Move;
+
+    #[test]
+    fn test_equal_cubes_have_no_diff() {
+        assert!(Cube::new(3).diff(&Cube::new(3)).is_empty());
+    }
+
+    #[test]
+    fn test_quarter_turn_diff() {
+        let mut turned = Cube::new(3);
+        turned.apply_move(Move::U);
+        let diffs = Cube::new(3).diff(&turned);
+
+        // The top row of the four side faces changes; U itself is all one color
+        assert_eq!(diffs.len(), 12);
+        assert!(diffs.iter().all(|d| d.row == 0 && d.face != FaceName::U && d.face != FaceName::D));
+        assert!(diffs.iter().all(|d| d.before == d.face.standard_color() && d.after != d.before));
+        assert_eq!(diffs[0].face, FaceName::F);
+    }
+
+    #[test]
+    fn test_diff_is_symmetric() {
+        let mut cube = Cube::new(4);
+        cube.apply_moves(&[Move::R, Move::F]);
+        let forward = Cube::new(4).diff(&cube);
+        let backward = cube.diff(&Cube::new(4));
+        assert_eq!(forward.len(), backward.len());
+        for (a, b) in forward.iter().zip(&backward) {
+            assert_eq!((a.face, a.row, a.col), (b.face, b.row, b.col));
+            assert_eq!((a.before, a.after), (b.after, b.before));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "same size")]
+    fn test_diff_needs_same_size() {
+        Cube::new(2).diff(&Cube::new(3));
+    }
+}
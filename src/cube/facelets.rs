@@ -0,0 +1,270 @@
+//! Text representations of cube state
+//!
+//! - Facelet strings: one character per sticker, faces in U R F D L B order,
+//!   each face written row by row in the same order the cube stores it.
+//!   Stickers may be written as face letters (U R F D L B, meaning "the color
+//!   of that face's center on a solved cube") or color letters (W Y R O B G).
+//! - ASCII nets: an unfolded cube for terminals and logs, also the cube's
+//!   `Display`. ANSI nets draw the same net with colored stickers.
+
+use super::state::{Color, Cube, FaceName};
+
+/// Face order used by facelet strings
+pub const FACELET_ORDER: [FaceName; 6] = [
+    FaceName::U,
+    FaceName::R,
+    FaceName::F,
+    FaceName::D,
+    FaceName::L,
+    FaceName::B,
+];
+
+/// Error types for facelet parsing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FaceletError {
+    /// The string length is not 6 * N * N for a supported size
+    InvalidLength(usize),
+    /// A character is not a face or color letter
+    InvalidCharacter { index: usize, found: char },
+}
+
+impl std::fmt::Display for FaceletError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FaceletError::InvalidLength(len) => write!(
+                f,
+                "Invalid facelet count {}: expected 6 x N x N stickers for N between 2 and 20",
+                len
+            ),
+            FaceletError::InvalidCharacter { index, found } => {
+                write!(f, "Invalid facelet '{}' at position {}", found, index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FaceletError {}
+
+/// Get the single-letter color code (W Y R O B G)
+pub fn color_letter(color: Color) -> char {
+    match color {
+        Color::White => 'W',
+        Color::Yellow => 'Y',
+        Color::Red => 'R',
+        Color::Orange => 'O',
+        Color::Blue => 'B',
+        Color::Green => 'G',
+    }
+}
+
+/// Parse a face letter or color letter into a color
+fn parse_facelet(c: char) -> Option<Color> {
+    match c.to_ascii_uppercase() {
+        'U' | 'W' => Some(Color::White),
+        'D' | 'Y' => Some(Color::Yellow),
+        'F' | 'G' => Some(Color::Green),
+        'L' | 'O' => Some(Color::Orange),
+        'R' => Some(Color::Red),
+        'B' => Some(Color::Blue),
+        _ => None,
+    }
+}
+
+impl Cube {
+    /// Build a cube from a facelet string
+    ///
+    /// Whitespace is ignored, so nets may be split across lines.
+    pub fn from_facelets(facelets: &str) -> Result<Cube, FaceletError> {
+        let chars: Vec<char> = facelets.chars().filter(|c| !c.is_whitespace()).collect();
+
+        let per_face = chars.len() / 6;
+        let size = (2..=20)
+            .find(|n| n * n == per_face && chars.len().is_multiple_of(6))
+            .ok_or(FaceletError::InvalidLength(chars.len()))?;
+
+        let mut cube = Cube::new(size);
+        for (face_index, face) in FACELET_ORDER.iter().enumerate() {
+            for row in 0..size {
+                for col in 0..size {
+                    let index = face_index * per_face + row * size + col;
+                    let found = chars[index];
+                    let color = parse_facelet(found)
+                        .ok_or(FaceletError::InvalidCharacter { index, found })?;
+                    cube.set_sticker(*face, row, col, color);
+                }
+            }
+        }
+
+        Ok(cube)
+    }
+
+    /// Write the cube as a facelet string using color letters
+    pub fn to_facelets(&self) -> String {
+        FACELET_ORDER
+            .iter()
+            .flat_map(|face| {
+                self.get_face(*face)
+                    .stickers()
+                    .iter()
+                    .flat_map(|row| row.iter().map(|c| color_letter(*c)))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Render the cube as an unfolded ASCII net
+    ///
+    /// ```text
+    ///       W W W
+    ///       W W W
+    ///       W W W
+    /// O O O G G G R R R B B B
+    /// ...
+    /// ```
+    pub fn to_ascii_net(&self) -> String {
+        self.net(|c| color_letter(c).to_string(), 1, " ")
+    }
+
+    /// Render the cube as an unfolded net of colored stickers for terminals
+    ///
+    /// Same layout as [`Cube::to_ascii_net`], each sticker drawn as its
+    /// color letter on an ANSI 256-color background.
+    pub fn to_ansi_net(&self) -> String {
+        self.net(|c| format!("\x1b[1;30;48;5;{}m {} \x1b[0m", ansi_color(c), color_letter(c)), 3, "")
+    }
+
+    /// Lay out the faces as a net: U above L F R B, D below
+    ///
+    /// `sticker` draws one sticker `width` columns wide; stickers and faces
+    /// are separated by `gap`.
+    fn net(&self, sticker: impl Fn(Color) -> String, width: usize, gap: &str) -> String {
+        let n = self.size();
+        let row_text = |face: FaceName, row: usize| -> String {
+            self.get_face(face)
+                .get_row(row)
+                .iter()
+                .map(|c| sticker(*c))
+                .collect::<Vec<_>>()
+                .join(gap)
+        };
+        let indent = " ".repeat(n * (width + gap.len()));
+
+        let mut lines = Vec::with_capacity(n * 3);
+        for row in 0..n {
+            lines.push(format!("{}{}", indent, row_text(FaceName::U, row)));
+        }
+        for row in 0..n {
+            lines.push(
+                [FaceName::L, FaceName::F, FaceName::R, FaceName::B]
+                    .iter()
+                    .map(|face| row_text(*face, row))
+                    .collect::<Vec<_>>()
+                    .join(gap),
+            );
+        }
+        for row in 0..n {
+            lines.push(format!("{}{}", indent, row_text(FaceName::D, row)));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// ANSI 256-color palette index for a sticker color
+fn ansi_color(color: Color) -> u8 {
+    match color {
+        Color::White => 255,
+        Color::Yellow => 226,
+        Color::Red => 196,
+        Color::Orange => 208,
+        Color::Blue => 27,
+        Color::Green => 34,
+    }
+}
+
+/// Shows the cube as its ASCII net
+impl std::fmt::Display for Cube {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_ascii_net())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Move;
+
+    #[test]
+    fn test_solved_facelets_roundtrip() {
+        let cube = Cube::new(3);
+        let facelets = cube.to_facelets();
+        assert_eq!(facelets.len(), 54);
+        assert!(facelets.starts_with("WWWWWWWWWRRRRRRRRR"));
+        assert_eq!(Cube::from_facelets(&facelets).unwrap(), cube);
+    }
+
+    #[test]
+    fn test_face_letters_match_color_letters() {
+        let by_face = "UUUURRRRFFFFDDDDLLLLBBBB";
+        let cube = Cube::from_facelets(by_face).unwrap();
+        assert_eq!(cube.size(), 2);
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn test_scrambled_roundtrip() {
+        let mut cube = Cube::new(4);
+        cube.apply_moves(&[Move::R, Move::U, Move::FPrime, Move::L2]);
+        assert_eq!(Cube::from_facelets(&cube.to_facelets()).unwrap(), cube);
+    }
+
+    #[test]
+    fn test_invalid_facelets() {
+        assert_eq!(Cube::from_facelets("WWW"), Err(FaceletError::InvalidLength(3)));
+        let mut bad = Cube::new(2).to_facelets();
+        bad.replace_range(5..6, "X");
+        assert_eq!(
+            Cube::from_facelets(&bad),
+            Err(FaceletError::InvalidCharacter { index: 5, found: 'X' })
+        );
+    }
+
+    #[test]
+    fn test_ascii_net_shape() {
+        let net = Cube::new(2).to_ascii_net();
+        let lines: Vec<&str> = net.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "    W W");
+        assert_eq!(lines[2], "O O G G R R B B");
+        assert_eq!(lines[5], "    Y Y");
+    }
+
+    #[test]
+    fn test_display_is_ascii_net() {
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::R);
+        assert_eq!(cube.to_string(), cube.to_ascii_net());
+        assert_eq!(format!("{}", cube).lines().count(), 9);
+    }
+
+    #[test]
+    fn test_ansi_net_matches_ascii_layout() {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::U]);
+        let ansi = cube.to_ansi_net();
+        assert!(ansi.contains("\x1b[1;30;48;5;208m O \x1b[0m"));
+
+        // Without the escape codes, the net is the ASCII one with wider stickers
+        let plain: String = ansi
+            .split("\x1b[")
+            .map(|part| part.split_once('m').map_or(part, |(_, text)| text))
+            .collect();
+        let ascii = cube.to_ascii_net();
+        assert_eq!(plain.lines().count(), ascii.lines().count());
+        for (plain_line, ascii_line) in plain.lines().zip(ascii.lines()) {
+            assert_eq!(plain_line.split_whitespace().collect::<Vec<_>>(), ascii_line.split_whitespace().collect::<Vec<_>>());
+        }
+        // U is indented by one face of three-column stickers
+        assert_eq!(plain.find(|c: char| c != ' '), Some(9 + 1));
+    }
+}
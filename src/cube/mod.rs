@@ -3,17 +3,47 @@
 //! This module contains all the core Rubik's cube logic including:
 //! - state: Cube state representation for NxN cubes
 //! - moves: Face rotation operations
+//! - orientation: Which color faces which way, and held vs cube-fixed notation
 //! - notation: Move notation parser
 //! - validation: Cube state validation
 //! - scramble: Scramble generator
+//! - scramble_sheet: Printable pages of scrambles with nets
+//! - random_state: Uniformly random solvable states
+//! - facelets: Facelet string and ASCII net formats
+//! - svg: SVG image of the unfolded net
+//! - serialization: Versioned save format with migrations
+//! - symmetry: Rotation- and recoloring-aware equality and hashing
+//! - diff: Sticker-by-sticker comparison of two states
+//! - supercube: Opt-in sticker orientation tracking
+//! - pieces: Sticker and corner/edge/center iteration
+//! - progress: Which stages of a 3x3 solve are done
 
 pub mod state;
 pub mod moves;
+pub mod orientation;
 pub mod notation;
 pub mod validation;
 pub mod scramble;
+pub mod scramble_sheet;
+pub mod random_state;
+pub mod facelets;
+pub mod svg;
+pub mod serialization;
+pub mod symmetry;
+pub mod diff;
+pub mod supercube;
+pub mod pieces;
+pub mod progress;
 
 // Re-export main types
-pub use state::{Color, Cube, FaceName};
-pub use moves::{Move, WideMove, WideFace, Direction};
-pub use notation::ParsedMove;
+pub use state::{Color, Cube, FaceName, SerializationError};
+pub use moves::{Move, WideMove, WideFace, Direction, LayerMove};
+pub use orientation::Orientation;
+pub use notation::{AlgorithmError, AlgorithmErrorKind, ParsedMove};
+pub use facelets::FaceletError;
+pub use svg::{ColorScheme, SvgOptions};
+pub use diff::StickerDiff;
+pub use supercube::SuperCube;
+pub use pieces::{Center, Corner, Edge, Piece};
+pub use progress::SolveProgress;
+pub use serialization::{CubeMetadata, CubeOrientation, CubeSave, SERIALIZATION_VERSION};
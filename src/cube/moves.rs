@@ -7,6 +7,7 @@
 //! - Works for any cube size (2x2 to 20x20)
 //! - Wide moves (Rw, Lw, Uw, Dw, Fw, Bw) rotate multiple layers
 //! - Slice moves (M, E, S) for odd-sized cubes only
+//! - Numbered layer moves (3R, 2-3Rw) turn any inner layers of big cubes
 
 use super::state::{Color, Cube};
 use serde::{Deserialize, Serialize};
@@ -131,6 +132,23 @@ impl Move {
         }
     }
 
+    /// Whether this is a middle slice move (M, E, S), which needs an
+    /// odd-sized cube
+    pub fn is_slice(&self) -> bool {
+        matches!(
+            self,
+            Move::M | Move::MPrime | Move::M2 | Move::E | Move::EPrime | Move::E2 | Move::S | Move::SPrime | Move::S2
+        )
+    }
+
+    /// Whether this is a whole-cube rotation (x, y, z)
+    pub fn is_rotation(&self) -> bool {
+        matches!(
+            self,
+            Move::X | Move::XPrime | Move::X2 | Move::Y | Move::YPrime | Move::Y2 | Move::Z | Move::ZPrime | Move::Z2
+        )
+    }
+
     /// Returns the standard notation string for this move
     pub fn to_notation(&self) -> &'static str {
         match self {
@@ -188,7 +206,7 @@ pub struct WideMove {
 }
 
 /// The six possible faces for wide moves
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WideFace {
     R,
     L,
@@ -199,7 +217,7 @@ pub enum WideFace {
 }
 
 /// Direction of rotation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Direction {
     Clockwise,
     CounterClockwise,
@@ -322,28 +340,81 @@ impl WideMove {
 
     /// Returns the notation string for this move
     pub fn to_notation(&self) -> String {
-        let face_char = match self.face {
-            WideFace::R => "R",
-            WideFace::L => "L",
-            WideFace::U => "U",
-            WideFace::D => "D",
-            WideFace::F => "F",
-            WideFace::B => "B",
-        };
-
         let depth_prefix = if self.depth > 2 {
             format!("{}", self.depth)
         } else {
             String::new()
         };
 
-        let direction_suffix = match self.direction {
-            Direction::Clockwise => "",
-            Direction::CounterClockwise => "'",
-            Direction::Double => "2",
-        };
+        format!("{}{}w{}", depth_prefix, face_letter(self.face), direction_suffix(self.direction))
+    }
+}
+
+/// Notation letter of a face
+fn face_letter(face: WideFace) -> &'static str {
+    match face {
+        WideFace::R => "R",
+        WideFace::L => "L",
+        WideFace::U => "U",
+        WideFace::D => "D",
+        WideFace::F => "F",
+        WideFace::B => "B",
+    }
+}
 
-        format!("{}{}w{}", depth_prefix, face_char, direction_suffix)
+/// Notation suffix of a direction: nothing, ' or 2
+fn direction_suffix(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Clockwise => "",
+        Direction::CounterClockwise => "'",
+        Direction::Double => "2",
+    }
+}
+
+/// A turn of a range of layers counted from a face, such as 3R (the third
+/// layer only) or 2-3Rw (the second and third layers)
+///
+/// Layers are numbered from 1 at the face, as in notation. Layers past the
+/// middle of the cube are turned from the opposite face.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerMove {
+    /// The face the layers are counted from
+    pub face: WideFace,
+    /// The direction of the rotation, seen from `face`
+    pub direction: Direction,
+    /// First layer turned (1 = the face itself)
+    pub first: usize,
+    /// Last layer turned
+    pub last: usize,
+}
+
+impl LayerMove {
+    /// Creates a turn of layers `first..=last`
+    pub fn new(face: WideFace, direction: Direction, first: usize, last: usize) -> Self {
+        Self { face, direction, first, last }
+    }
+
+    /// Creates a turn of a single layer
+    pub fn single(face: WideFace, direction: Direction, layer: usize) -> Self {
+        Self::new(face, direction, layer, layer)
+    }
+
+    /// Returns the inverse of this layer move
+    pub fn inverse(&self) -> Self {
+        Self {
+            direction: WideMove::new(self.face, self.direction, 1).inverse().direction,
+            ..*self
+        }
+    }
+
+    /// Returns the notation string for this move, e.g. "3R'" or "2-3Rw2"
+    pub fn to_notation(&self) -> String {
+        let (face, suffix) = (face_letter(self.face), direction_suffix(self.direction));
+        if self.first == self.last {
+            format!("{}{}{}", self.first, face, suffix)
+        } else {
+            format!("{}-{}{}w{}", self.first, self.last, face, suffix)
+        }
     }
 }
 
@@ -462,6 +533,83 @@ impl Cube {
         }
     }
 
+    /// Turns a single layer, counted from 1 at `face`
+    ///
+    /// Layer 1 is the face itself; on a 5x5, layer 3 from R is the middle
+    /// slice. Layers past the middle are turned from the opposite face, so
+    /// every layer of every cube size can be turned.
+    ///
+    /// ```
+    /// use rubiks_cube_solver::cube::{Cube, Direction, WideFace};
+    ///
+    /// let mut cube = Cube::new(5);
+    /// cube.apply_layer_move(WideFace::R, 3, Direction::Clockwise);
+    /// cube.apply_layer_move(WideFace::L, 3, Direction::Clockwise);
+    /// assert!(cube.is_solved());
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `layer` is 0 or larger than the cube size
+    pub fn apply_layer_move(&mut self, face: WideFace, layer: usize, direction: Direction) {
+        let n = self.size();
+        assert!(layer >= 1 && layer <= n, "Layer must be between 1 and the cube size");
+
+        if layer > n / 2 + n % 2 {
+            let opposite = match face {
+                WideFace::R => WideFace::L,
+                WideFace::L => WideFace::R,
+                WideFace::U => WideFace::D,
+                WideFace::D => WideFace::U,
+                WideFace::F => WideFace::B,
+                WideFace::B => WideFace::F,
+            };
+            let reversed = WideMove::new(face, direction, 1).inverse().direction;
+            self.apply_layer_move(opposite, n + 1 - layer, reversed);
+            return;
+        }
+
+        // The outer `layer` layers, then the outer `layer - 1` turned back
+        let undo = WideMove::new(face, direction, 1).inverse().direction;
+        self.turn_outer_layers(face, layer, direction);
+        self.turn_outer_layers(face, layer - 1, undo);
+    }
+
+    /// Applies a layer move, one layer at a time
+    ///
+    /// # Panics
+    /// Panics if the layers are out of range for the cube
+    pub fn apply_layers(&mut self, layer_move: LayerMove) {
+        assert!(layer_move.first <= layer_move.last, "Layer range must not be empty");
+        for layer in layer_move.first..=layer_move.last {
+            self.apply_layer_move(layer_move.face, layer, layer_move.direction);
+        }
+    }
+
+    /// Turns the outer `depth` layers of a face; a depth of 1 is a face turn
+    /// and works on a 2x2
+    fn turn_outer_layers(&mut self, face: WideFace, depth: usize, direction: Direction) {
+        if depth == 0 {
+            return;
+        }
+        if depth > 1 {
+            self.apply_wide_move(WideMove::new(face, direction, depth));
+            return;
+        }
+        let [clockwise, counter_clockwise, double] = match face {
+            WideFace::R => [Move::R, Move::RPrime, Move::R2],
+            WideFace::L => [Move::L, Move::LPrime, Move::L2],
+            WideFace::U => [Move::U, Move::UPrime, Move::U2],
+            WideFace::D => [Move::D, Move::DPrime, Move::D2],
+            WideFace::F => [Move::F, Move::FPrime, Move::F2],
+            WideFace::B => [Move::B, Move::BPrime, Move::B2],
+        };
+        self.apply_move(match direction {
+            Direction::Clockwise => clockwise,
+            Direction::CounterClockwise => counter_clockwise,
+            Direction::Double => double,
+        });
+    }
+
     /// Applies a clockwise wide move for the specified face and depth
     fn apply_wide_move_cw(&mut self, face: WideFace, depth: usize) {
         match face {
@@ -490,8 +638,8 @@ impl Cube {
     fn wide_r_cw(&mut self, depth: usize) {
         let n = self.size();
 
-        // Rotate the R face clockwise
-        self.right.rotate_cw();
+        // Rotate the R face counter-clockwise, matching the strip cycle below
+        self.right.rotate_ccw();
 
         // Cycle columns for each layer from the right edge inward
         for layer in 0..depth {
@@ -515,8 +663,8 @@ impl Cube {
     fn wide_r_ccw(&mut self, depth: usize) {
         let n = self.size();
 
-        // Rotate the R face counter-clockwise
-        self.right.rotate_ccw();
+        // Rotate the R face clockwise, matching the strip cycle below
+        self.right.rotate_cw();
 
         // Cycle columns for each layer from the right edge inward
         for layer in 0..depth {
@@ -540,8 +688,8 @@ impl Cube {
     fn wide_l_cw(&mut self, depth: usize) {
         let n = self.size();
 
-        // Rotate the L face clockwise
-        self.left.rotate_cw();
+        // Rotate the L face counter-clockwise, matching the strip cycle below
+        self.left.rotate_ccw();
 
         // Cycle columns for each layer from the left edge inward
         for layer in 0..depth {
@@ -565,8 +713,8 @@ impl Cube {
     fn wide_l_ccw(&mut self, depth: usize) {
         let n = self.size();
 
-        // Rotate the L face counter-clockwise
-        self.left.rotate_ccw();
+        // Rotate the L face clockwise, matching the strip cycle below
+        self.left.rotate_cw();
 
         // Cycle columns for each layer from the left edge inward
         for layer in 0..depth {
@@ -588,8 +736,8 @@ impl Cube {
 
     /// Wide U move clockwise: rotates U face and `depth` layers
     fn wide_u_cw(&mut self, depth: usize) {
-        // Rotate the U face clockwise
-        self.up.rotate_cw();
+        // Rotate the U face counter-clockwise, matching the strip cycle below
+        self.up.rotate_ccw();
 
         // Cycle rows for each layer from the top edge downward
         for layer in 0..depth {
@@ -609,8 +757,8 @@ impl Cube {
 
     /// Wide U move counter-clockwise: rotates U face and `depth` layers
     fn wide_u_ccw(&mut self, depth: usize) {
-        // Rotate the U face counter-clockwise
-        self.up.rotate_ccw();
+        // Rotate the U face clockwise, matching the strip cycle below
+        self.up.rotate_cw();
 
         // Cycle rows for each layer from the top edge downward
         for layer in 0..depth {
@@ -632,8 +780,8 @@ impl Cube {
     fn wide_d_cw(&mut self, depth: usize) {
         let n = self.size();
 
-        // Rotate the D face clockwise
-        self.down.rotate_cw();
+        // Rotate the D face counter-clockwise, matching the strip cycle below
+        self.down.rotate_ccw();
 
         // Cycle rows for each layer from the bottom edge upward
         for layer in 0..depth {
@@ -655,8 +803,8 @@ impl Cube {
     fn wide_d_ccw(&mut self, depth: usize) {
         let n = self.size();
 
-        // Rotate the D face counter-clockwise
-        self.down.rotate_ccw();
+        // Rotate the D face clockwise, matching the strip cycle below
+        self.down.rotate_cw();
 
         // Cycle rows for each layer from the bottom edge upward
         for layer in 0..depth {
@@ -792,8 +940,8 @@ impl Cube {
         let n = self.size();
         let last_col = n - 1;
 
-        // Rotate the R face clockwise
-        self.right.rotate_cw();
+        // Rotate the R face counter-clockwise, matching the strip cycle below
+        self.right.rotate_ccw();
 
         // Cycle the columns: Up -> Back -> Down -> Front -> Up
         // Note: Back face is viewed from outside, so columns are reversed
@@ -817,8 +965,8 @@ impl Cube {
         let n = self.size();
         let last_col = n - 1;
 
-        // Rotate the R face counter-clockwise
-        self.right.rotate_ccw();
+        // Rotate the R face clockwise, matching the strip cycle below
+        self.right.rotate_cw();
 
         // Cycle the columns: Up -> Front -> Down -> Back -> Up (reverse of R)
         let up_col = self.up.get_col(last_col);
@@ -842,8 +990,8 @@ impl Cube {
         let n = self.size();
         let last_col = n - 1;
 
-        // Rotate the L face clockwise
-        self.left.rotate_cw();
+        // Rotate the L face counter-clockwise, matching the strip cycle below
+        self.left.rotate_ccw();
 
         // Cycle the columns: Up -> Front -> Down -> Back -> Up (opposite of R)
         let up_col = self.up.get_col(0);
@@ -866,8 +1014,8 @@ impl Cube {
         let n = self.size();
         let last_col = n - 1;
 
-        // Rotate the L face counter-clockwise
-        self.left.rotate_ccw();
+        // Rotate the L face clockwise, matching the strip cycle below
+        self.left.rotate_cw();
 
         // Cycle the columns: Up -> Back -> Down -> Front -> Up
         let up_col = self.up.get_col(0);
@@ -888,8 +1036,8 @@ impl Cube {
     /// U move: Up face clockwise
     /// Affects: Up face (rotate CW), and top rows of Front, Right, Back, Left
     fn move_u(&mut self) {
-        // Rotate the U face clockwise
-        self.up.rotate_cw();
+        // Rotate the U face counter-clockwise, matching the strip cycle below
+        self.up.rotate_ccw();
 
         // Cycle the rows: Front -> Left -> Back -> Right -> Front
         let front_row = self.front.get_row(0);
@@ -909,8 +1057,8 @@ impl Cube {
 
     /// U' move: Up face counter-clockwise
     fn move_u_prime(&mut self) {
-        // Rotate the U face counter-clockwise
-        self.up.rotate_ccw();
+        // Rotate the U face clockwise, matching the strip cycle below
+        self.up.rotate_cw();
 
         // Cycle the rows: Front -> Right -> Back -> Left -> Front
         let front_row = self.front.get_row(0);
@@ -934,8 +1082,8 @@ impl Cube {
         let n = self.size();
         let last_row = n - 1;
 
-        // Rotate the D face clockwise
-        self.down.rotate_cw();
+        // Rotate the D face counter-clockwise, matching the strip cycle below
+        self.down.rotate_ccw();
 
         // Cycle the rows: Front -> Right -> Back -> Left -> Front (opposite of U)
         let front_row = self.front.get_row(last_row);
@@ -958,8 +1106,8 @@ impl Cube {
         let n = self.size();
         let last_row = n - 1;
 
-        // Rotate the D face counter-clockwise
-        self.down.rotate_ccw();
+        // Rotate the D face clockwise, matching the strip cycle below
+        self.down.rotate_cw();
 
         // Cycle the rows: Front -> Left -> Back -> Right -> Front
         let front_row = self.front.get_row(last_row);
@@ -2193,4 +2341,69 @@ mod tests {
             assert!(cube.is_solved(), "Size {} failed Z rotation test", size);
         }
     }
+
+    #[test]
+    fn test_outer_layer_matches_face_turn() {
+        for size in [2, 3, 4] {
+            let mut layer = Cube::new(size);
+            layer.apply_move(Move::F);
+            layer.apply_layer_move(WideFace::R, 1, Direction::Clockwise);
+            layer.apply_layer_move(WideFace::D, 1, Direction::Double);
+
+            let mut face = Cube::new(size);
+            face.apply_moves(&[Move::F, Move::R, Move::D2]);
+            assert_eq!(layer, face, "Size {}", size);
+        }
+    }
+
+    #[test]
+    fn test_inner_layer_is_wide_minus_outer() {
+        let mut layer = Cube::new(6);
+        layer.apply_layer_move(WideFace::U, 3, Direction::Clockwise);
+
+        let mut wide = Cube::new(6);
+        wide.apply_wide_move(WideMove::new(WideFace::U, Direction::Clockwise, 3));
+        wide.apply_wide_move(WideMove::new(WideFace::U, Direction::CounterClockwise, 2));
+        assert_eq!(layer, wide);
+
+        // Only the third layer moved: U and the top two rows of F are untouched
+        let front = layer.get_face(crate::cube::FaceName::F);
+        assert!((0..6).all(|col| front.get(0, col) == Color::Green && front.get(1, col) == Color::Green));
+        assert_ne!(front.get(2, 0), Color::Green);
+    }
+
+    #[test]
+    fn test_far_layers_turn_from_the_opposite_face() {
+        // Turning every layer from R is a whole-cube turn, whichever face
+        // the far layers are actually turned from
+        for size in [4, 5] {
+            let mut from_right = Cube::new(size);
+            from_right.apply_layers(LayerMove::new(WideFace::R, Direction::Clockwise, 1, size));
+            let mut from_left = Cube::new(size);
+            from_left.apply_layers(LayerMove::new(WideFace::L, Direction::CounterClockwise, 1, size));
+
+            assert!(from_right.is_solved_any_orientation() && !from_right.is_solved(), "Size {}", size);
+            assert_eq!(from_right, from_left, "Size {}", size);
+        }
+    }
+
+    #[test]
+    fn test_layer_move_notation_and_inverse() {
+        let single = LayerMove::single(WideFace::R, Direction::CounterClockwise, 3);
+        assert_eq!(single.to_notation(), "3R'");
+        assert_eq!(single.inverse().to_notation(), "3R");
+        assert_eq!(LayerMove::new(WideFace::F, Direction::Double, 2, 3).to_notation(), "2-3Fw2");
+
+        let mut cube = Cube::new(7);
+        cube.apply_layers(LayerMove::new(WideFace::B, Direction::Clockwise, 2, 4));
+        assert!(!cube.is_solved());
+        cube.apply_layers(LayerMove::new(WideFace::B, Direction::Clockwise, 2, 4).inverse());
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    #[should_panic(expected = "Layer must be between 1 and the cube size")]
+    fn test_layer_past_cube_panics() {
+        Cube::new(4).apply_layer_move(WideFace::R, 5, Direction::Clockwise);
+    }
 }
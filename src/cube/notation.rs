@@ -8,17 +8,25 @@
 //! - Double (180 degrees): R2, L2, U2, D2, F2, B2
 //! - Wide moves: Rw, Lw, Uw, Dw, Fw, Bw
 //! - Wide with depth: 3Rw, 2Uw, etc.
+//! - Numbered layers: 3R (third layer only), 2-3Rw (second and third layers)
 //! - Slice moves: M, E, S (with ', 2)
 //! - Rotations: x, y, z (with ', 2)
 //! - Algorithms: "R U R' U'" (space-separated sequences)
+//!
+//! Also provides algorithm transforms: inverse, left-right mirror and
+//! conjugation by a whole-cube rotation, and [`Cube::apply_algorithm`],
+//! which parses and applies a whole algorithm with errors that point at the
+//! bad move.
 
-use crate::cube::{Move, WideMove, WideFace, Direction};
+use crate::cube::{Cube, Move, WideMove, WideFace, Direction, LayerMove};
+use serde::{Deserialize, Serialize};
 
-/// Represents a parsed move that could be either a basic Move or a WideMove
-#[derive(Debug, Clone, PartialEq)]
+/// Represents a parsed move: a basic Move, a WideMove or a LayerMove
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ParsedMove {
     Basic(Move),
     Wide(WideMove),
+    Layer(LayerMove),
 }
 
 /// Error type for notation parsing
@@ -52,6 +60,8 @@ impl std::error::Error for NotationError {}
 /// - "R2" -> Move::R2
 /// - "Rw" -> WideMove { face: R, direction: Clockwise, depth: 2 }
 /// - "3Rw" -> WideMove { face: R, direction: Clockwise, depth: 3 }
+/// - "3R" -> LayerMove { face: R, direction: Clockwise, first: 3, last: 3 }
+/// - "2-3Rw" -> LayerMove { face: R, direction: Clockwise, first: 2, last: 3 }
 /// - "x" -> Move::X
 pub fn parse_move(input: &str) -> Result<ParsedMove, NotationError> {
     let trimmed = input.trim();
@@ -65,35 +75,42 @@ pub fn parse_move(input: &str) -> Result<ParsedMove, NotationError> {
         return parse_wide_move(trimmed);
     }
 
+    // A number before a face letter picks a single layer (e.g. "3R")
+    if trimmed.starts_with(|c: char| c.is_ascii_digit()) {
+        return parse_layer_move(trimmed);
+    }
+
     // Parse basic moves, slice moves, and rotations
     parse_basic_move(trimmed)
 }
 
-/// Parse a wide move (e.g., "Rw", "Rw'", "Rw2", "3Rw", "3Rw2")
+/// Parse a wide move (e.g., "Rw", "Rw'", "Rw2", "3Rw", "3Rw2") or a range of
+/// layers (e.g., "2-3Rw")
 fn parse_wide_move(input: &str) -> Result<ParsedMove, NotationError> {
     let mut chars = input.chars().peekable();
-    let mut depth_str = String::new();
 
-    // Extract optional depth prefix (e.g., "3" in "3Rw")
-    while let Some(&ch) = chars.peek() {
-        if ch.is_ascii_digit() {
-            depth_str.push(ch);
-            chars.next();
-        } else {
-            break;
-        }
-    }
+    // Extract optional depth prefix (e.g., "3" in "3Rw"), or a range of
+    // layers (e.g. "2-3" in "2-3Rw") that turns only those layers
+    let mut depth_str = take_digits(&mut chars);
+    let first_str = match chars.next_if_eq(&'-') {
+        Some(_) => Some(std::mem::replace(&mut depth_str, take_digits(&mut chars))),
+        None => None,
+    };
 
     // Parse depth or default to 2
     let depth = if depth_str.is_empty() {
         2 // Default depth for wide moves (Rw means 2 layers)
     } else {
-        match depth_str.parse::<usize>() {
-            Ok(0) => return Err(NotationError::InvalidDepth(depth_str)),
-            Ok(d) => d,
-            Err(_) => return Err(NotationError::InvalidDepth(depth_str)),
-        }
+        parse_layer_number(&depth_str)?
     };
+    let first = match &first_str {
+        Some(_) if depth_str.is_empty() => return Err(NotationError::InvalidMove(input.to_string())),
+        Some(first) => Some(parse_layer_number(first)?),
+        None => None,
+    };
+    if first.is_some_and(|first| first > depth) {
+        return Err(NotationError::InvalidDepth(format!("{}-{}", first_str.unwrap_or_default(), depth_str)));
+    }
 
     // Get the face letter (should be uppercase)
     let face_char = match chars.next() {
@@ -125,7 +142,42 @@ fn parse_wide_move(input: &str) -> Result<ParsedMove, NotationError> {
         _ => return Err(NotationError::InvalidMove(input.to_string())),
     };
 
-    Ok(ParsedMove::Wide(WideMove { face, direction, depth }))
+    Ok(match first {
+        Some(first) => ParsedMove::Layer(LayerMove::new(face, direction, first, depth)),
+        None => ParsedMove::Wide(WideMove { face, direction, depth }),
+    })
+}
+
+/// Take the leading digits off `chars`
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(ch) = chars.next_if(char::is_ascii_digit) {
+        digits.push(ch);
+    }
+    digits
+}
+
+/// Parse a layer number, which must be at least 1
+fn parse_layer_number(digits: &str) -> Result<usize, NotationError> {
+    match digits.parse::<usize>() {
+        Ok(0) | Err(_) => Err(NotationError::InvalidDepth(digits.to_string())),
+        Ok(layer) => Ok(layer),
+    }
+}
+
+/// Parse a single numbered layer (e.g., "3R", "3R'", "3R2")
+fn parse_layer_move(input: &str) -> Result<ParsedMove, NotationError> {
+    let digits: String = input.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let layer = parse_layer_number(&digits)?;
+
+    // The rest is a face turn such as "R'"; slices and rotations have no layers
+    match parse_basic_move(&input[digits.len()..])? {
+        ParsedMove::Basic(mv) if !mv.is_slice() && !mv.is_rotation() => {
+            let (_, face, direction) = decompose(mv);
+            Ok(ParsedMove::Layer(LayerMove::single(face, direction, layer)))
+        }
+        _ => Err(NotationError::InvalidMove(input.to_string())),
+    }
 }
 
 /// Parse a basic move, slice move, or rotation
@@ -232,6 +284,418 @@ pub fn parse_algorithm(input: &str) -> Result<Vec<ParsedMove>, NotationError> {
     Ok(moves)
 }
 
+/// Why a move in an algorithm could not be applied
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlgorithmErrorKind {
+    /// The move is not valid notation
+    Notation(NotationError),
+    /// M, E and S need an odd-sized cube
+    SliceNeedsOddCube,
+    /// Wide moves need a cube of size 3 or larger
+    WideNeedsBiggerCube,
+    /// The wide move turns more than half the cube
+    DepthTooLarge {
+        /// Deepest wide move the cube allows
+        max: usize,
+    },
+    /// The layer move turns a layer the cube does not have
+    LayerOutOfRange {
+        /// Number of layers the cube has
+        max: usize,
+    },
+}
+
+/// Error from [`Cube::apply_algorithm`], pointing at the move that failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlgorithmError {
+    /// The move as written
+    pub token: String,
+    /// Byte offsets of the move in the algorithm string
+    pub span: std::ops::Range<usize>,
+    /// What is wrong with it
+    pub kind: AlgorithmErrorKind,
+    /// A valid move that was probably meant, e.g. "R'" for "R`"
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for AlgorithmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            AlgorithmErrorKind::Notation(e) => write!(f, "{}", e)?,
+            AlgorithmErrorKind::SliceNeedsOddCube => write!(f, "Slice move {} needs an odd-sized cube", self.token)?,
+            AlgorithmErrorKind::WideNeedsBiggerCube => {
+                write!(f, "Wide move {} needs a cube of size 3 or larger", self.token)?
+            }
+            AlgorithmErrorKind::DepthTooLarge { max } => {
+                write!(f, "Wide move {} is deeper than {} layers", self.token, max)?
+            }
+            AlgorithmErrorKind::LayerOutOfRange { max } => {
+                write!(f, "Layer move {} turns a layer past {}", self.token, max)?
+            }
+        }
+        write!(f, " at byte {}", self.span.start)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean {}?)", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for AlgorithmError {}
+
+/// Whitespace-separated tokens with their byte offsets
+fn tokens(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .split_whitespace()
+        .map(move |token| (token.as_ptr() as usize - input.as_ptr() as usize, token))
+}
+
+/// A valid move close to a token that failed to parse
+///
+/// Fixes the usual typos: typographic quotes for the prime, "i" for
+/// inverse, 3 for prime, a prime and a 2 together, and an uppercase W.
+fn suggest(token: &str) -> Option<String> {
+    let mut fixed: String = token
+        .chars()
+        .map(|c| match c {
+            '\u{2019}' | '\u{2018}' | '\u{2032}' | '`' | '\u{b4}' => '\'',
+            'W' => 'w',
+            c => c,
+        })
+        .collect();
+    for (typo, meant) in [("'2", "2"), ("2'", "2"), ("''", "'")] {
+        if let Some(stem) = fixed.strip_suffix(typo) {
+            fixed = format!("{}{}", stem, meant);
+        }
+    }
+    for typo in ["i", "3"] {
+        if let Some(stem) = fixed.strip_suffix(typo) {
+            if !stem.is_empty() && !stem.ends_with(|c: char| c.is_ascii_digit()) {
+                fixed = format!("{}'", stem);
+            }
+        }
+    }
+    (fixed != token && parse_move(&fixed).is_ok()).then_some(fixed)
+}
+
+/// Why `parsed` cannot be applied to a cube of `size`, if it can't
+fn check_fits(parsed: &ParsedMove, size: usize) -> Option<AlgorithmErrorKind> {
+    match parsed {
+        ParsedMove::Basic(mv) if mv.is_slice() && size.is_multiple_of(2) => Some(AlgorithmErrorKind::SliceNeedsOddCube),
+        ParsedMove::Wide(_) if size < 3 => Some(AlgorithmErrorKind::WideNeedsBiggerCube),
+        ParsedMove::Wide(wide) if wide.depth > size / 2 + size % 2 => {
+            Some(AlgorithmErrorKind::DepthTooLarge { max: size / 2 + size % 2 })
+        }
+        ParsedMove::Layer(layers) if layers.last > size => Some(AlgorithmErrorKind::LayerOutOfRange { max: size }),
+        _ => None,
+    }
+}
+
+impl Cube {
+    /// Applies a parsed move, basic, wide or layer
+    pub fn apply_parsed_move(&mut self, parsed: &ParsedMove) {
+        match parsed {
+            ParsedMove::Basic(mv) => self.apply_move(*mv),
+            ParsedMove::Wide(wide) => self.apply_wide_move(*wide),
+            ParsedMove::Layer(layers) => self.apply_layers(*layers),
+        }
+    }
+
+    /// Applies parsed moves in order
+    pub fn apply_parsed_moves(&mut self, moves: &[ParsedMove]) {
+        for parsed in moves {
+            self.apply_parsed_move(parsed);
+        }
+    }
+
+    /// Parses and applies an algorithm such as "R U R' U'"
+    ///
+    /// The whole algorithm is checked before any move is applied, so the
+    /// cube is unchanged on error. The error has the byte offsets of the bad
+    /// move and, for typos, the move that was probably meant.
+    ///
+    /// ```
+    /// use rubiks_cube_solver::cube::Cube;
+    ///
+    /// let mut cube = Cube::new(3);
+    /// let error = cube.apply_algorithm("R U R`").unwrap_err();
+    /// assert_eq!(error.span, 4..6);
+    /// assert_eq!(error.suggestion.as_deref(), Some("R'"));
+    /// assert!(cube.is_solved());
+    /// ```
+    pub fn apply_algorithm(&mut self, algorithm: &str) -> Result<(), AlgorithmError> {
+        let size = self.size();
+        let moves = tokens(algorithm)
+            .map(|(start, token)| {
+                let error = |kind, suggestion| AlgorithmError {
+                    token: token.to_string(),
+                    span: start..start + token.len(),
+                    kind,
+                    suggestion,
+                };
+                let parsed = parse_move(token).map_err(|e| error(AlgorithmErrorKind::Notation(e), suggest(token)))?;
+                match check_fits(&parsed, size) {
+                    Some(kind) => Err(error(kind, None)),
+                    None => Ok(parsed),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.apply_parsed_moves(&moves);
+        Ok(())
+    }
+}
+
+/// Which kind of layer a move turns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Layer {
+    /// An outer face (R, U, ...)
+    Face,
+    /// A middle slice (M, E, S)
+    Slice,
+    /// The whole cube (x, y, z)
+    Rotation,
+}
+
+impl ParsedMove {
+    /// Returns the inverse of this move
+    pub fn inverse(&self) -> ParsedMove {
+        match self {
+            ParsedMove::Basic(mv) => ParsedMove::Basic(mv.inverse()),
+            ParsedMove::Wide(wide) => ParsedMove::Wide(wide.inverse()),
+            ParsedMove::Layer(layers) => ParsedMove::Layer(layers.inverse()),
+        }
+    }
+
+    /// Returns the notation string for this move
+    pub fn to_notation(&self) -> String {
+        match self {
+            ParsedMove::Basic(mv) => mv.to_notation().to_string(),
+            ParsedMove::Wide(wide) => wide.to_notation(),
+            ParsedMove::Layer(layers) => layers.to_notation(),
+        }
+    }
+}
+
+/// Format an algorithm as a space-separated notation string
+pub fn algorithm_to_notation(moves: &[ParsedMove]) -> String {
+    moves
+        .iter()
+        .map(ParsedMove::to_notation)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Compute the inverse of an algorithm (reverse order, invert each move)
+///
+/// Example: "R U R' U'" -> "U R U' R'"
+pub fn invert_algorithm(moves: &[ParsedMove]) -> Vec<ParsedMove> {
+    moves.iter().rev().map(ParsedMove::inverse).collect()
+}
+
+/// Compute the inverse of a sequence of basic moves
+pub fn invert_moves(moves: &[Move]) -> Vec<Move> {
+    moves.iter().rev().map(Move::inverse).collect()
+}
+
+/// Compute the left-right mirror of an algorithm
+///
+/// R and L swap, and every move turns the opposite way. Moves around the
+/// R-L axis (M, x) keep their direction, since the mirror plane is
+/// perpendicular to it.
+///
+/// Example: "R U R' U'" -> "L' U' L U"
+pub fn mirror_algorithm(moves: &[ParsedMove]) -> Vec<ParsedMove> {
+    moves
+        .iter()
+        .map(|parsed| match parsed {
+            ParsedMove::Basic(mv) => {
+                let (layer, face, direction) = decompose(*mv);
+                ParsedMove::Basic(compose(layer, mirror_face(face), invert(direction)))
+            }
+            ParsedMove::Wide(wide) => ParsedMove::Wide(WideMove {
+                face: mirror_face(wide.face),
+                direction: invert(wide.direction),
+                depth: wide.depth,
+            }),
+            ParsedMove::Layer(layers) => ParsedMove::Layer(LayerMove {
+                face: mirror_face(layers.face),
+                direction: invert(layers.direction),
+                ..*layers
+            }),
+        })
+        .collect()
+}
+
+/// Conjugate an algorithm by a whole-cube rotation
+///
+/// Returns the algorithm that, performed without rotating, has the same
+/// effect as `rotation`, then `moves`, then the inverse of `rotation`.
+/// Directions are never changed, only which face each move turns.
+///
+/// Example: "R U R'" rotated by y -> "B U B'"
+///
+/// Returns `NotationError::InvalidMove` if `rotation` is not x, y or z.
+pub fn rotate_algorithm(
+    moves: &[ParsedMove],
+    rotation: Move,
+) -> Result<Vec<ParsedMove>, NotationError> {
+    let (layer, axis, direction) = decompose(rotation);
+    if layer != Layer::Rotation {
+        return Err(NotationError::InvalidMove(rotation.to_notation().to_string()));
+    }
+
+    let quarter_turns = match (axis, direction) {
+        (_, Direction::Double) => 2,
+        (WideFace::L | WideFace::D | WideFace::B, Direction::Clockwise)
+        | (WideFace::R | WideFace::U | WideFace::F, Direction::CounterClockwise) => 3,
+        _ => 1,
+    };
+    let relabel = |face: WideFace| {
+        (0..quarter_turns).fold(face, |face, _| conjugate_face(axis, face))
+    };
+
+    Ok(moves
+        .iter()
+        .map(|parsed| match parsed {
+            ParsedMove::Basic(mv) => {
+                let (layer, face, direction) = decompose(*mv);
+                ParsedMove::Basic(compose(layer, relabel(face), direction))
+            }
+            ParsedMove::Wide(wide) => ParsedMove::Wide(WideMove {
+                face: relabel(wide.face),
+                ..*wide
+            }),
+            ParsedMove::Layer(layers) => ParsedMove::Layer(LayerMove {
+                face: relabel(layers.face),
+                ..*layers
+            }),
+        })
+        .collect())
+}
+
+/// Split a move into its layer, the face it turns like, and its direction
+///
+/// Slices turn like a face (M like L, E like D, S like F) and rotations
+/// turn like a face (x like R, y like U, z like F).
+pub(super) fn decompose(mv: Move) -> (Layer, WideFace, Direction) {
+    use Direction::*;
+    match mv {
+        Move::R => (Layer::Face, WideFace::R, Clockwise),
+        Move::RPrime => (Layer::Face, WideFace::R, CounterClockwise),
+        Move::R2 => (Layer::Face, WideFace::R, Double),
+        Move::L => (Layer::Face, WideFace::L, Clockwise),
+        Move::LPrime => (Layer::Face, WideFace::L, CounterClockwise),
+        Move::L2 => (Layer::Face, WideFace::L, Double),
+        Move::U => (Layer::Face, WideFace::U, Clockwise),
+        Move::UPrime => (Layer::Face, WideFace::U, CounterClockwise),
+        Move::U2 => (Layer::Face, WideFace::U, Double),
+        Move::D => (Layer::Face, WideFace::D, Clockwise),
+        Move::DPrime => (Layer::Face, WideFace::D, CounterClockwise),
+        Move::D2 => (Layer::Face, WideFace::D, Double),
+        Move::F => (Layer::Face, WideFace::F, Clockwise),
+        Move::FPrime => (Layer::Face, WideFace::F, CounterClockwise),
+        Move::F2 => (Layer::Face, WideFace::F, Double),
+        Move::B => (Layer::Face, WideFace::B, Clockwise),
+        Move::BPrime => (Layer::Face, WideFace::B, CounterClockwise),
+        Move::B2 => (Layer::Face, WideFace::B, Double),
+        Move::M => (Layer::Slice, WideFace::L, Clockwise),
+        Move::MPrime => (Layer::Slice, WideFace::L, CounterClockwise),
+        Move::M2 => (Layer::Slice, WideFace::L, Double),
+        Move::E => (Layer::Slice, WideFace::D, Clockwise),
+        Move::EPrime => (Layer::Slice, WideFace::D, CounterClockwise),
+        Move::E2 => (Layer::Slice, WideFace::D, Double),
+        Move::S => (Layer::Slice, WideFace::F, Clockwise),
+        Move::SPrime => (Layer::Slice, WideFace::F, CounterClockwise),
+        Move::S2 => (Layer::Slice, WideFace::F, Double),
+        Move::X => (Layer::Rotation, WideFace::R, Clockwise),
+        Move::XPrime => (Layer::Rotation, WideFace::R, CounterClockwise),
+        Move::X2 => (Layer::Rotation, WideFace::R, Double),
+        Move::Y => (Layer::Rotation, WideFace::U, Clockwise),
+        Move::YPrime => (Layer::Rotation, WideFace::U, CounterClockwise),
+        Move::Y2 => (Layer::Rotation, WideFace::U, Double),
+        Move::Z => (Layer::Rotation, WideFace::F, Clockwise),
+        Move::ZPrime => (Layer::Rotation, WideFace::F, CounterClockwise),
+        Move::Z2 => (Layer::Rotation, WideFace::F, Double),
+    }
+}
+
+/// Rebuild a move from its layer, the face it turns like, and its direction
+///
+/// Turning like the opposite face is the same move in the other direction
+/// (e.g. a slice turning like R is M').
+pub(super) fn compose(layer: Layer, face: WideFace, direction: Direction) -> Move {
+    let (face, direction) = match (layer, face) {
+        (Layer::Slice, WideFace::R | WideFace::U | WideFace::B)
+        | (Layer::Rotation, WideFace::L | WideFace::D | WideFace::B) => {
+            (opposite_face(face), invert(direction))
+        }
+        _ => (face, direction),
+    };
+
+    let [clockwise, counter_clockwise, double] = match (layer, face) {
+        (Layer::Face, WideFace::R) => [Move::R, Move::RPrime, Move::R2],
+        (Layer::Face, WideFace::L) => [Move::L, Move::LPrime, Move::L2],
+        (Layer::Face, WideFace::U) => [Move::U, Move::UPrime, Move::U2],
+        (Layer::Face, WideFace::D) => [Move::D, Move::DPrime, Move::D2],
+        (Layer::Face, WideFace::F) => [Move::F, Move::FPrime, Move::F2],
+        (Layer::Face, WideFace::B) => [Move::B, Move::BPrime, Move::B2],
+        (Layer::Slice, WideFace::D) => [Move::E, Move::EPrime, Move::E2],
+        (Layer::Slice, WideFace::F) => [Move::S, Move::SPrime, Move::S2],
+        (Layer::Slice, _) => [Move::M, Move::MPrime, Move::M2],
+        (Layer::Rotation, WideFace::U) => [Move::Y, Move::YPrime, Move::Y2],
+        (Layer::Rotation, WideFace::F) => [Move::Z, Move::ZPrime, Move::Z2],
+        (Layer::Rotation, _) => [Move::X, Move::XPrime, Move::X2],
+    };
+
+    match direction {
+        Direction::Clockwise => clockwise,
+        Direction::CounterClockwise => counter_clockwise,
+        Direction::Double => double,
+    }
+}
+
+fn invert(direction: Direction) -> Direction {
+    match direction {
+        Direction::Clockwise => Direction::CounterClockwise,
+        Direction::CounterClockwise => Direction::Clockwise,
+        Direction::Double => Direction::Double,
+    }
+}
+
+fn opposite_face(face: WideFace) -> WideFace {
+    match face {
+        WideFace::R => WideFace::L,
+        WideFace::L => WideFace::R,
+        WideFace::U => WideFace::D,
+        WideFace::D => WideFace::U,
+        WideFace::F => WideFace::B,
+        WideFace::B => WideFace::F,
+    }
+}
+
+fn mirror_face(face: WideFace) -> WideFace {
+    match face {
+        WideFace::R | WideFace::L => opposite_face(face),
+        other => other,
+    }
+}
+
+/// Which face ends up in `face`'s position after a clockwise quarter
+/// rotation around `axis` (R for x, U for y, F for z)
+fn conjugate_face(axis: WideFace, face: WideFace) -> WideFace {
+    use WideFace::*;
+    let cycle = match axis {
+        R | L => [F, D, B, U],
+        U | D => [F, R, B, L],
+        F | B => [R, U, L, D],
+    };
+    match cycle.iter().position(|f| *f == face) {
+        Some(index) => cycle[(index + 1) % 4],
+        None => face,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -439,4 +903,189 @@ mod tests {
         assert_eq!(result[3], ParsedMove::Basic(Move::XPrime));
         assert_eq!(result[4], ParsedMove::Basic(Move::U2));
     }
+
+    #[test]
+    fn test_apply_algorithm() {
+        let mut cube = Cube::new(4);
+        cube.apply_algorithm("  Rw U2\tR' 2Fw'").unwrap();
+
+        let mut expected = Cube::new(4);
+        expected.apply_wide_move(WideMove::rw());
+        expected.apply_moves(&[Move::U2, Move::RPrime]);
+        expected.apply_wide_move(WideMove::fw_prime());
+        assert_eq!(cube, expected);
+    }
+
+    #[test]
+    fn test_apply_algorithm_points_at_bad_move() {
+        let mut cube = Cube::new(3);
+        let error = cube.apply_algorithm("R U  Q2 F").unwrap_err();
+        assert_eq!(error.token, "Q2");
+        assert_eq!(error.span, 5..7);
+        assert_eq!(error.kind, AlgorithmErrorKind::Notation(NotationError::InvalidMove("Q2".to_string())));
+        assert_eq!(error.suggestion, None);
+        assert_eq!(error.to_string(), "Invalid move notation: Q2 at byte 5");
+        assert!(cube.is_solved(), "Nothing is applied on error");
+    }
+
+    #[test]
+    fn test_apply_algorithm_suggestions() {
+        for (typo, meant) in [
+            ("R\u{2019}", "R'"),
+            ("U`", "U'"),
+            ("Fi", "F'"),
+            ("R3", "R'"),
+            ("R'2", "R2"),
+            ("U2'", "U2"),
+            ("RW", "Rw"),
+            ("3RW'", "3Rw'"),
+        ] {
+            let error = Cube::new(4).apply_algorithm(typo).unwrap_err();
+            assert_eq!(error.suggestion.as_deref(), Some(meant), "{}", typo);
+        }
+
+        let error = Cube::new(3).apply_algorithm("R U\u{2032}").unwrap_err();
+        assert_eq!(error.span, 2..6, "Offsets count bytes, not characters");
+        assert_eq!(error.to_string(), "Invalid move notation: U\u{2032} at byte 2 (did you mean U'?)");
+    }
+
+    #[test]
+    fn test_apply_algorithm_checks_cube_size() {
+        let error = Cube::new(4).apply_algorithm("R M").unwrap_err();
+        assert_eq!((error.kind, error.span), (AlgorithmErrorKind::SliceNeedsOddCube, 2..3));
+
+        let error = Cube::new(2).apply_algorithm("Rw").unwrap_err();
+        assert_eq!(error.kind, AlgorithmErrorKind::WideNeedsBiggerCube);
+
+        let error = Cube::new(5).apply_algorithm("3Rw 4Rw").unwrap_err();
+        assert_eq!((error.kind.clone(), error.span.clone()), (AlgorithmErrorKind::DepthTooLarge { max: 3 }, 4..7));
+        assert_eq!(error.to_string(), "Wide move 4Rw is deeper than 3 layers at byte 4");
+    }
+
+    fn alg(notation: &str) -> Vec<ParsedMove> {
+        parse_algorithm(notation).unwrap()
+    }
+
+    #[test]
+    fn test_invert_algorithm() {
+        assert_eq!(algorithm_to_notation(&invert_algorithm(&alg("R U R' U'"))), "U R U' R'");
+        assert_eq!(algorithm_to_notation(&invert_algorithm(&alg("Rw2 3Uw' x"))), "x' 3Uw Rw2");
+        assert!(invert_algorithm(&[]).is_empty());
+        assert_eq!(invert_moves(&[Move::R, Move::U2, Move::FPrime]), vec![Move::F, Move::U2, Move::RPrime]);
+    }
+
+    #[test]
+    fn test_mirror_algorithm() {
+        assert_eq!(algorithm_to_notation(&mirror_algorithm(&alg("R U R' U'"))), "L' U' L U");
+        assert_eq!(algorithm_to_notation(&mirror_algorithm(&alg("F M E S x y z"))), "F' M E' S' x y' z'");
+        assert_eq!(algorithm_to_notation(&mirror_algorithm(&alg("Rw 3Lw2"))), "Lw' 3Rw2");
+    }
+
+    #[test]
+    fn test_mirror_is_involution() {
+        let sune = alg("R U R' U R U2 R' M2 E' S y2 Fw");
+        assert_eq!(mirror_algorithm(&mirror_algorithm(&sune)), sune);
+    }
+
+    #[test]
+    fn test_rotate_algorithm() {
+        let rotated = rotate_algorithm(&alg("R U R'"), Move::Y).unwrap();
+        assert_eq!(algorithm_to_notation(&rotated), "B U B'");
+
+        let rotated = rotate_algorithm(&alg("R U F"), Move::X).unwrap();
+        assert_eq!(algorithm_to_notation(&rotated), "R F D");
+
+        let rotated = rotate_algorithm(&alg("R U M E S"), Move::Z).unwrap();
+        assert_eq!(algorithm_to_notation(&rotated), "U L E M' S");
+    }
+
+    #[test]
+    fn test_rotate_prime_and_double() {
+        let sexy = alg("R U R' U' M x Lw");
+        for rotation in [Move::X, Move::Y, Move::Z] {
+            let there = rotate_algorithm(&sexy, rotation).unwrap();
+            let back = rotate_algorithm(&there, rotation.inverse()).unwrap();
+            assert_eq!(back, sexy);
+
+            let twice = rotate_algorithm(&there, rotation).unwrap();
+            let double = match rotation {
+                Move::X => Move::X2,
+                Move::Y => Move::Y2,
+                _ => Move::Z2,
+            };
+            assert_eq!(rotate_algorithm(&sexy, double).unwrap(), twice);
+        }
+    }
+
+    #[test]
+    fn test_rotate_matches_simulator_for_z() {
+        let moves = [Move::R, Move::U, Move::F2, Move::L, Move::D, Move::B, Move::M, Move::S];
+        let parsed: Vec<ParsedMove> = moves.iter().map(|m| ParsedMove::Basic(*m)).collect();
+        let rotated = rotate_algorithm(&parsed, Move::Z).unwrap();
+
+        let mut expected = crate::cube::Cube::new(3);
+        expected.apply_move(Move::Z);
+        expected.apply_moves(&moves);
+        expected.apply_move(Move::ZPrime);
+
+        let mut actual = crate::cube::Cube::new(3);
+        for parsed in rotated {
+            if let ParsedMove::Basic(mv) = parsed {
+                actual.apply_move(mv);
+            }
+        }
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_rotate_rejects_non_rotation() {
+        assert_eq!(
+            rotate_algorithm(&alg("R"), Move::R),
+            Err(NotationError::InvalidMove("R".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_layer_moves() {
+        assert_eq!(
+            parse_move("3R").unwrap(),
+            ParsedMove::Layer(LayerMove::single(WideFace::R, Direction::Clockwise, 3))
+        );
+        assert_eq!(
+            parse_move("4U'").unwrap(),
+            ParsedMove::Layer(LayerMove::single(WideFace::U, Direction::CounterClockwise, 4))
+        );
+        assert_eq!(
+            parse_move("2-3Rw2").unwrap(),
+            ParsedMove::Layer(LayerMove::new(WideFace::R, Direction::Double, 2, 3))
+        );
+        // Without a range, a number before Rw is still the wide depth
+        assert!(matches!(parse_move("3Rw").unwrap(), ParsedMove::Wide(_)));
+
+        for bad in ["0R", "3M", "3x", "3-2Rw", "2-Rw", "2-3R"] {
+            assert!(parse_move(bad).is_err(), "{} should not parse", bad);
+        }
+        for notation in ["3R", "5B'", "2-3Lw2", "1-4Dw"] {
+            assert_eq!(parse_move(notation).unwrap().to_notation(), notation);
+        }
+    }
+
+    #[test]
+    fn test_apply_algorithm_with_layer_moves() {
+        let mut cube = Cube::new(5);
+        cube.apply_algorithm("3R 2-3Uw' 4F2").unwrap();
+        cube.apply_algorithm("4F2 2-3Uw 3R'").unwrap();
+        assert!(cube.is_solved());
+
+        let error = Cube::new(4).apply_algorithm("R 5R").unwrap_err();
+        assert_eq!(error.kind, AlgorithmErrorKind::LayerOutOfRange { max: 4 });
+        assert_eq!(error.span, 2..4);
+    }
+
+    #[test]
+    fn test_mirror_and_rotate_layer_moves() {
+        let moves = alg("3R 2-3Uw'");
+        assert_eq!(algorithm_to_notation(&mirror_algorithm(&moves)), "3L' 2-3Uw");
+        assert_eq!(algorithm_to_notation(&rotate_algorithm(&moves, Move::Y).unwrap()), "3B 2-3Uw'");
+    }
 }
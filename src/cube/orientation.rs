@@ -0,0 +1,302 @@
+//! How the cube is currently held
+//!
+//! Solvers name faces by their colors: F is always the green face, however
+//! the cube has been rotated. Someone holding the cube names faces by where
+//! they are: F is whatever faces them. [`Orientation`] records which color
+//! is on each side, follows x/y/z rotations, and translates algorithms
+//! between the two ("cube-fixed" and "held" notation).
+//!
+//! Faces are relabeled; directions never change, as in
+//! [`rotate_algorithm`](super::notation::rotate_algorithm).
+
+use super::moves::{Direction, LayerMove, Move, WideFace, WideMove};
+use super::notation::{compose, decompose, Layer, ParsedMove};
+use super::serialization::CubeOrientation;
+use super::state::{Color, Cube, FaceName};
+use super::symmetry::UP_ROTATIONS;
+use serde::{Deserialize, Serialize};
+
+/// Which color is on each side of the cube
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Orientation {
+    /// Color on each side, in [`FaceName::all`] order
+    colors: [Color; 6],
+}
+
+impl Orientation {
+    /// White up, green front
+    pub fn standard() -> Self {
+        Self { colors: FaceName::all().map(|face| face.standard_color()) }
+    }
+
+    /// The orientation with `up` on top and `front` facing the user
+    ///
+    /// Returns `None` if the colors are the same or opposite.
+    pub fn new(up: Color, front: Color) -> Option<Self> {
+        for rotation in UP_ROTATIONS {
+            let mut orientation = Self::standard();
+            orientation.apply_moves(rotation);
+            for _ in 0..4 {
+                if orientation.up() == up && orientation.front() == front {
+                    return Some(orientation);
+                }
+                orientation.apply_move(Move::Y);
+            }
+        }
+        None
+    }
+
+    /// Read the orientation from the middle centers of an odd cube
+    ///
+    /// Even cubes have no fixed centers, so this returns `None` for them.
+    pub fn of(cube: &Cube) -> Option<Self> {
+        let n = cube.size();
+        if n.is_multiple_of(2) {
+            return None;
+        }
+        let center = |face: FaceName| cube.get_face(face).get(n / 2, n / 2);
+        Self::new(center(FaceName::U), center(FaceName::F))
+    }
+
+    /// Color on the top
+    pub fn up(&self) -> Color {
+        self.color_on(FaceName::U)
+    }
+
+    /// Color facing the user
+    pub fn front(&self) -> Color {
+        self.color_on(FaceName::F)
+    }
+
+    /// Color currently on `face`
+    pub fn color_on(&self, face: FaceName) -> Color {
+        self.colors[slot(face)]
+    }
+
+    /// Side `color` is currently on
+    pub fn face_of(&self, color: Color) -> FaceName {
+        FaceName::all()
+            .into_iter()
+            .find(|face| self.color_on(*face) == color)
+            .unwrap_or(FaceName::U)
+    }
+
+    /// Follow a move; only rotations change the orientation
+    pub fn apply_move(&mut self, mv: Move) {
+        let (layer, face, direction) = decompose(mv);
+        if layer != Layer::Rotation {
+            return;
+        }
+        // new[cycle[i]] = old[cycle[i + 1]] for a clockwise quarter rotation
+        let cycle = match face {
+            WideFace::R | WideFace::L => [FaceName::U, FaceName::F, FaceName::D, FaceName::B],
+            WideFace::U | WideFace::D => [FaceName::F, FaceName::R, FaceName::B, FaceName::L],
+            WideFace::F | WideFace::B => [FaceName::U, FaceName::L, FaceName::D, FaceName::R],
+        };
+        let quarter_turns = match direction {
+            Direction::Clockwise => 1,
+            Direction::Double => 2,
+            Direction::CounterClockwise => 3,
+        };
+        for _ in 0..quarter_turns {
+            let old = self.colors;
+            for i in 0..4 {
+                self.colors[slot(cycle[i])] = old[slot(cycle[(i + 1) % 4])];
+            }
+        }
+    }
+
+    /// Follow a sequence of moves
+    pub fn apply_moves(&mut self, moves: &[Move]) {
+        for mv in moves {
+            self.apply_move(*mv);
+        }
+    }
+
+    /// Translate cube-fixed moves into the moves to make holding the cube
+    /// in this orientation
+    ///
+    /// Rotations in `moves` change the grip, so later moves are translated
+    /// for the new orientation.
+    ///
+    /// Example: after x, the green face is on top, so "F" becomes "U".
+    pub fn to_held(&self, moves: &[ParsedMove]) -> Vec<ParsedMove> {
+        let mut orientation = *self;
+        moves
+            .iter()
+            .map(|parsed| {
+                let held = relabel(parsed, |face| orientation.face_of(face.standard_color()));
+                orientation.apply_parsed(&held);
+                held
+            })
+            .collect()
+    }
+
+    /// Translate moves made holding the cube in this orientation into
+    /// cube-fixed moves; the inverse of [`Orientation::to_held`]
+    pub fn to_fixed(&self, moves: &[ParsedMove]) -> Vec<ParsedMove> {
+        let mut orientation = *self;
+        moves
+            .iter()
+            .map(|parsed| {
+                let fixed = relabel(parsed, |face| standard_face(orientation.color_on(face)));
+                orientation.apply_parsed(parsed);
+                fixed
+            })
+            .collect()
+    }
+
+    fn apply_parsed(&mut self, parsed: &ParsedMove) {
+        if let ParsedMove::Basic(mv) = parsed {
+            self.apply_move(*mv);
+        }
+    }
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl From<Orientation> for CubeOrientation {
+    fn from(orientation: Orientation) -> Self {
+        Self {
+            up: standard_face(orientation.up()),
+            front: standard_face(orientation.front()),
+        }
+    }
+}
+
+/// Index of `face` in [`FaceName::all`] order
+fn slot(face: FaceName) -> usize {
+    FaceName::all().iter().position(|f| *f == face).unwrap_or(0)
+}
+
+/// Face with `color` in the standard color scheme
+fn standard_face(color: Color) -> FaceName {
+    FaceName::all()
+        .into_iter()
+        .find(|face| face.standard_color() == color)
+        .unwrap_or(FaceName::U)
+}
+
+/// Move `parsed` onto other faces, keeping its layers and direction
+fn relabel(parsed: &ParsedMove, face_for: impl Fn(FaceName) -> FaceName) -> ParsedMove {
+    let map = |face: WideFace| wide_face(face_for(face_name(face)));
+    match parsed {
+        ParsedMove::Basic(mv) => {
+            let (layer, face, direction) = decompose(*mv);
+            ParsedMove::Basic(compose(layer, map(face), direction))
+        }
+        ParsedMove::Wide(wide) => ParsedMove::Wide(WideMove { face: map(wide.face), ..*wide }),
+        ParsedMove::Layer(layers) => ParsedMove::Layer(LayerMove { face: map(layers.face), ..*layers }),
+    }
+}
+
+fn face_name(face: WideFace) -> FaceName {
+    match face {
+        WideFace::R => FaceName::R,
+        WideFace::L => FaceName::L,
+        WideFace::U => FaceName::U,
+        WideFace::D => FaceName::D,
+        WideFace::F => FaceName::F,
+        WideFace::B => FaceName::B,
+    }
+}
+
+fn wide_face(face: FaceName) -> WideFace {
+    match face {
+        FaceName::R => WideFace::R,
+        FaceName::L => WideFace::L,
+        FaceName::U => WideFace::U,
+        FaceName::D => WideFace::D,
+        FaceName::F => WideFace::F,
+        FaceName::B => WideFace::B,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::notation::parse_algorithm;
+
+    fn notation(moves: &[ParsedMove]) -> String {
+        moves.iter().map(ParsedMove::to_notation).collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn test_rotations_follow_the_simulator() {
+        let rotations = [Move::X, Move::Y2, Move::ZPrime, Move::XPrime, Move::Z, Move::Y];
+        let mut cube = Cube::new(3);
+        let mut orientation = Orientation::standard();
+        for mv in rotations {
+            cube.apply_move(mv);
+            orientation.apply_move(mv);
+            assert_eq!(Orientation::of(&cube), Some(orientation), "after {:?}", mv);
+            for face in FaceName::all() {
+                assert_eq!(orientation.color_on(face), cube.get_face(face).get(1, 1));
+            }
+        }
+
+        orientation.apply_moves(&[Move::R, Move::M, Move::E2]);
+        assert_eq!(Orientation::of(&cube), Some(orientation), "Turns do not rotate the cube");
+        assert_eq!(Orientation::of(&Cube::new(4)), None);
+    }
+
+    #[test]
+    fn test_new() {
+        let orientation = Orientation::new(Color::Green, Color::Yellow).unwrap();
+        assert_eq!((orientation.up(), orientation.front()), (Color::Green, Color::Yellow));
+        assert_eq!(orientation.face_of(Color::White), FaceName::B);
+        assert_eq!(Orientation::new(Color::White, Color::Green), Some(Orientation::default()));
+        assert_eq!(Orientation::new(Color::Red, Color::Orange), None);
+        assert_eq!(Orientation::new(Color::Red, Color::Red), None);
+
+        let mut all: Vec<Orientation> = Vec::new();
+        for up in FaceName::all() {
+            for front in FaceName::all() {
+                all.extend(Orientation::new(up.standard_color(), front.standard_color()));
+            }
+        }
+        all.dedup();
+        assert_eq!(all.len(), 24);
+    }
+
+    #[test]
+    fn test_to_held() {
+        let mut orientation = Orientation::standard();
+        orientation.apply_move(Move::X);
+        let held = orientation.to_held(&parse_algorithm("F R U' M E S").unwrap());
+        assert_eq!(notation(&held), "U R B' M S E'");
+
+        let held = orientation.to_held(&parse_algorithm("Rw 3Fw' 2-3Dw2").unwrap());
+        assert_eq!(notation(&held), "Rw 3Uw' 2-3Fw2");
+    }
+
+    #[test]
+    fn test_rotations_change_the_grip() {
+        let held = Orientation::standard().to_held(&parse_algorithm("x F y F").unwrap());
+        assert_eq!(notation(&held), "x U z' L");
+        let fixed = Orientation::standard().to_fixed(&held);
+        assert_eq!(notation(&fixed), "x F y F");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let moves = parse_algorithm("R U y' F2 x M' Lw D2 z2 B' 3R S").unwrap();
+        for (up, front) in [(Color::Red, Color::White), (Color::Yellow, Color::Orange), (Color::Blue, Color::Red)] {
+            let orientation = Orientation::new(up, front).unwrap();
+            assert_eq!(orientation.to_fixed(&orientation.to_held(&moves)), moves);
+            assert_eq!(orientation.to_held(&orientation.to_fixed(&moves)), moves);
+        }
+    }
+
+    #[test]
+    fn test_cube_orientation() {
+        let orientation = Orientation::new(Color::Red, Color::Blue).unwrap();
+        let saved = CubeOrientation::from(orientation);
+        assert_eq!((saved.up, saved.front), (FaceName::R, FaceName::B));
+        assert_eq!(CubeOrientation::from(Orientation::standard()), CubeOrientation::default());
+    }
+}
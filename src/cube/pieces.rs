@@ -0,0 +1,186 @@
+//! Sticker and piece iteration
+//!
+//! [`Cube::stickers`] walks every sticker with its position, and
+//! [`Cube::pieces`] groups the stickers of a 3x3 into its corners, edges and
+//! centers, so analysis code doesn't need its own loops over faces, rows and
+//! columns. On bigger cubes the pieces are the ones a 3x3 would have: the
+//! outer corners and, on odd cubes, the middle edges and the middle centers.
+
+use crate::cube::validation::StickerRef;
+use crate::cube::{Color, Cube, FaceName};
+use crate::solver::parity::{CORNER_SLOTS, EDGE_SLOTS};
+
+/// A corner piece where it is now
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Corner {
+    /// Which of the 8 corner positions it is in (U face corners first)
+    pub slot: usize,
+    /// Its stickers, clockwise starting on the U or D face
+    pub stickers: [StickerRef; 3],
+    /// The colors of `stickers`, in the same order
+    pub colors: [Color; 3],
+}
+
+/// An edge piece where it is now
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    /// Which of the 12 edge positions it is in (U face edges first)
+    pub slot: usize,
+    /// Its stickers, the U/D (or F/B on middle-layer edges) sticker first
+    pub stickers: [StickerRef; 2],
+    /// The colors of `stickers`, in the same order
+    pub colors: [Color; 2],
+}
+
+/// A center piece
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Center {
+    /// Its sticker, in the middle of its face
+    pub sticker: StickerRef,
+    pub color: Color,
+}
+
+/// Any piece of the cube
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Piece {
+    Corner(Corner),
+    Edge(Edge),
+    Center(Center),
+}
+
+impl Piece {
+    /// Positions of the piece's stickers
+    pub fn stickers(&self) -> Vec<StickerRef> {
+        match self {
+            Piece::Corner(corner) => corner.stickers.to_vec(),
+            Piece::Edge(edge) => edge.stickers.to_vec(),
+            Piece::Center(center) => vec![center.sticker],
+        }
+    }
+
+    /// Colors of the piece's stickers, in `stickers` order
+    pub fn colors(&self) -> Vec<Color> {
+        match self {
+            Piece::Corner(corner) => corner.colors.to_vec(),
+            Piece::Edge(edge) => edge.colors.to_vec(),
+            Piece::Center(center) => vec![center.color],
+        }
+    }
+}
+
+impl Cube {
+    /// Every sticker as (face, row, col, color), face by face in
+    /// [`FaceName::all`] order, row by row
+    pub fn stickers(&self) -> impl Iterator<Item = (FaceName, usize, usize, Color)> + '_ {
+        let size = self.size();
+        FaceName::all().into_iter().flat_map(move |face| {
+            let stickers = self.get_face(face);
+            (0..size).flat_map(move |row| (0..size).map(move |col| (face, row, col, stickers.get(row, col))))
+        })
+    }
+
+    /// The corners, in slot order
+    pub fn corners(&self) -> impl Iterator<Item = Corner> + '_ {
+        CORNER_SLOTS.iter().enumerate().map(move |(slot, stickers)| {
+            let stickers = stickers.map(|sticker| self.place(sticker));
+            Corner { slot, stickers, colors: stickers.map(|sticker| self.color_at(sticker)) }
+        })
+    }
+
+    /// The middle edges, in slot order; none on even cubes
+    pub fn edges(&self) -> impl Iterator<Item = Edge> + '_ {
+        let slots: &[[StickerRef; 2]] = if self.size() % 2 == 1 { &EDGE_SLOTS } else { &[] };
+        slots.iter().enumerate().map(move |(slot, stickers)| {
+            let stickers = stickers.map(|sticker| self.place(sticker));
+            Edge { slot, stickers, colors: stickers.map(|sticker| self.color_at(sticker)) }
+        })
+    }
+
+    /// The middle centers, one per face in [`FaceName::all`] order; none on
+    /// even cubes
+    pub fn centers(&self) -> impl Iterator<Item = Center> + '_ {
+        let faces = if self.size() % 2 == 1 { FaceName::all().to_vec() } else { Vec::new() };
+        faces.into_iter().map(move |face| {
+            let sticker = self.place((face, 1, 1));
+            Center { sticker, color: self.color_at(sticker) }
+        })
+    }
+
+    /// Corners, then edges, then centers (26 pieces on a 3x3)
+    pub fn pieces(&self) -> impl Iterator<Item = Piece> + '_ {
+        self.corners()
+            .map(Piece::Corner)
+            .chain(self.edges().map(Piece::Edge))
+            .chain(self.centers().map(Piece::Center))
+    }
+
+    /// A 3x3 sticker position moved to the matching outer or middle
+    /// sticker of this cube
+    fn place(&self, (face, row, col): StickerRef) -> StickerRef {
+        let size = self.size();
+        let index = |i: usize| match i {
+            0 => 0,
+            1 => size / 2,
+            _ => size - 1,
+        };
+        (face, index(row), index(col))
+    }
+
+    /// Color of one sticker
+    fn color_at(&self, (face, row, col): StickerRef) -> Color {
+        self.get_face(face).get(row, col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Move;
+
+    #[test]
+    fn test_stickers_cover_every_position() {
+        let cube = Cube::new(4);
+        let stickers: Vec<_> = cube.stickers().collect();
+        assert_eq!(stickers.len(), 6 * 16);
+        assert_eq!(stickers[0], (FaceName::U, 0, 0, Color::White));
+        assert_eq!(stickers[17], (FaceName::D, 0, 1, Color::Yellow));
+        assert!(stickers.iter().all(|&(face, row, col, color)| cube.get_face(face).get(row, col) == color));
+    }
+
+    #[test]
+    fn test_3x3_has_26_pieces() {
+        let cube = Cube::new(3);
+        let pieces: Vec<Piece> = cube.pieces().collect();
+        assert_eq!(pieces.len(), 26);
+        assert_eq!(pieces.iter().filter(|piece| matches!(piece, Piece::Corner(_))).count(), 8);
+        assert_eq!(pieces.iter().filter(|piece| matches!(piece, Piece::Edge(_))).count(), 12);
+        assert_eq!(pieces.iter().filter(|piece| matches!(piece, Piece::Center(_))).count(), 6);
+        // Every sticker belongs to exactly one piece
+        let stickers: usize = pieces.iter().map(|piece| piece.stickers().len()).sum();
+        assert_eq!(stickers, 54);
+    }
+
+    #[test]
+    fn test_pieces_follow_moves() {
+        let mut cube = Cube::new(3);
+        let solved_ufr = cube.corners().nth(2).unwrap();
+        assert_eq!(solved_ufr.colors, [Color::White, Color::Red, Color::Green]);
+        cube.apply_move(Move::U);
+        let moved = cube.corners().nth(2).unwrap();
+        assert_ne!(moved.colors, solved_ufr.colors);
+        assert_eq!(moved.colors[0], Color::White);
+    }
+
+    #[test]
+    fn test_big_cubes_use_outer_and_middle_pieces() {
+        let even = Cube::new(4);
+        assert_eq!(even.pieces().count(), 8);
+        assert_eq!(even.corners().nth(2).unwrap().stickers[0], (FaceName::U, 3, 3));
+
+        let odd = Cube::new(5);
+        assert_eq!(odd.pieces().count(), 26);
+        let center = odd.centers().next().unwrap();
+        assert_eq!(center.sticker, (FaceName::U, 2, 2));
+        assert_eq!(center.color, Color::White);
+    }
+}
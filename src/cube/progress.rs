@@ -0,0 +1,148 @@
+//! How far a 3x3 is from solved
+//!
+//! [`Cube::progress`] reads the cube the way the layer-by-layer method
+//! builds it, with the cross on D: how many cross edges and F2L slots are
+//! done, and whether the last layer is oriented and permuted. Pieces are
+//! compared with the centers, so a solved cube held any way up counts as
+//! solved.
+
+use crate::cube::validation::StickerRef;
+use crate::cube::{Color, Corner, Cube, Edge, FaceName};
+
+/// Stages of a 3x3 that are done
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolveProgress {
+    /// D-layer edges in place and flipped correctly (0-4)
+    pub cross_edges: usize,
+    /// D-layer corners solved together with the middle edge above them (0-4)
+    pub f2l_slots: usize,
+    /// Every U sticker matches the U center
+    pub ll_oriented: bool,
+    /// Every U-layer piece is in its own position, however it is flipped
+    pub ll_permuted: bool,
+}
+
+impl SolveProgress {
+    /// Overall progress from 0 to 100: each cross edge and F2L slot is worth
+    /// 10, orienting and permuting the last layer 10 each
+    pub fn percent(&self) -> f32 {
+        let done = self.cross_edges + self.f2l_slots + self.ll_oriented as usize + self.ll_permuted as usize;
+        done as f32 * 10.0
+    }
+
+    /// The stage to work on next, for the progress bar label
+    pub fn next_stage(&self) -> &'static str {
+        if self.cross_edges < 4 {
+            "Cross"
+        } else if self.f2l_slots < 4 {
+            "First two layers"
+        } else if !self.ll_oriented {
+            "Orient last layer"
+        } else if !self.ll_permuted {
+            "Permute last layer"
+        } else {
+            "Solved"
+        }
+    }
+}
+
+impl Cube {
+    /// Which stages of the solve are done; `None` for cubes other than 3x3
+    pub fn progress(&self) -> Option<SolveProgress> {
+        if self.size() != 3 {
+            return None;
+        }
+        let center = |face: FaceName| self.get_face(face).get(1, 1);
+        let solved = |stickers: &[StickerRef], colors: &[Color]| {
+            stickers.iter().zip(colors).all(|((face, _, _), color)| center(*face) == *color)
+        };
+        let in_place = |stickers: &[StickerRef], colors: &[Color]| {
+            let mut want: Vec<Color> = stickers.iter().map(|(face, _, _)| center(*face)).collect();
+            let mut have = colors.to_vec();
+            want.sort();
+            have.sort();
+            want == have
+        };
+        let on = |face: FaceName, stickers: &[StickerRef]| stickers.iter().any(|(f, _, _)| *f == face);
+
+        let corners: Vec<Corner> = self.corners().collect();
+        let edges: Vec<Edge> = self.edges().collect();
+        let solved_edge = |edge: &Edge| solved(&edge.stickers, &edge.colors);
+
+        let cross_edges = edges.iter().filter(|edge| on(FaceName::D, &edge.stickers) && solved_edge(edge)).count();
+
+        // A slot is a D corner and the middle edge sharing its two side faces
+        let f2l_slots = corners
+            .iter()
+            .filter(|corner| on(FaceName::D, &corner.stickers) && solved(&corner.stickers, &corner.colors))
+            .filter(|corner| {
+                let sides: Vec<FaceName> =
+                    corner.stickers.iter().map(|(face, _, _)| *face).filter(|face| *face != FaceName::D).collect();
+                edges
+                    .iter()
+                    .find(|edge| edge.stickers.iter().all(|(face, _, _)| sides.contains(face)))
+                    .is_some_and(solved_edge)
+            })
+            .count();
+
+        let up = self.get_face(FaceName::U);
+        let ll_oriented = (0..3).all(|row| (0..3).all(|col| up.get(row, col) == center(FaceName::U)));
+        let ll_permuted = corners
+            .iter()
+            .filter(|corner| on(FaceName::U, &corner.stickers))
+            .all(|corner| in_place(&corner.stickers, &corner.colors))
+            && edges
+                .iter()
+                .filter(|edge| on(FaceName::U, &edge.stickers))
+                .all(|edge| in_place(&edge.stickers, &edge.colors));
+
+        Some(SolveProgress { cross_edges, f2l_slots, ll_oriented, ll_permuted })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Move;
+
+    #[test]
+    fn test_solved_cube_is_complete() {
+        let progress = Cube::new(3).progress().unwrap();
+        assert_eq!(progress, SolveProgress { cross_edges: 4, f2l_slots: 4, ll_oriented: true, ll_permuted: true });
+        assert_eq!(progress.percent(), 100.0);
+        assert_eq!(progress.next_stage(), "Solved");
+
+        let mut held_upside_down = Cube::new(3);
+        held_upside_down.apply_move(Move::X2);
+        assert_eq!(held_upside_down.progress().unwrap().percent(), 100.0);
+    }
+
+    #[test]
+    fn test_top_turn_only_unpermutes_last_layer() {
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::U);
+        let progress = cube.progress().unwrap();
+        assert_eq!((progress.cross_edges, progress.f2l_slots), (4, 4));
+        assert!(progress.ll_oriented);
+        assert!(!progress.ll_permuted);
+        assert_eq!(progress.percent(), 90.0);
+        assert_eq!(progress.next_stage(), "Permute last layer");
+    }
+
+    #[test]
+    fn test_r_turn_breaks_one_cross_edge_and_two_slots() {
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::R);
+        let progress = cube.progress().unwrap();
+        assert_eq!(progress.cross_edges, 3);
+        assert_eq!(progress.f2l_slots, 2);
+        assert!(!progress.ll_oriented);
+        assert_eq!(progress.next_stage(), "Cross");
+    }
+
+    #[test]
+    fn test_only_3x3_has_progress() {
+        assert!(Cube::new(2).progress().is_none());
+        assert!(Cube::new(5).progress().is_none());
+    }
+}
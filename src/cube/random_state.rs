@@ -0,0 +1,364 @@
+//! Uniformly random solvable cube states
+//!
+//! Random-state scrambles and trainer cases need every reachable state to be
+//! equally likely, which random move sequences only approach slowly. Instead
+//! the pieces are dealt out directly: every orbit of pieces is shuffled, then
+//! the twist, flip and permutation parity rules of a real cube are enforced
+//! so the result can always be solved.
+//!
+//! Piece geometry is worked out from the simulator's own layer turns, so it
+//! works for every cube size.
+
+use std::collections::BTreeMap;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use super::moves::{Direction, Move, WideFace, WideMove};
+use super::state::{Cube, FaceName};
+use super::supercube::{position, trace_sources};
+
+/// A place on the cube that holds one piece
+#[derive(Debug, Clone)]
+struct Slot {
+    /// Sticker indices in facelet order, in the slot's reference order
+    stickers: Vec<usize>,
+    /// Piece center with the cube centered on the origin, in half-sticker units
+    position: [i64; 3],
+}
+
+/// Outward direction of a face: R, U and F are +x, +y and +z
+fn normal(face: FaceName) -> [i64; 3] {
+    match face {
+        FaceName::R => [1, 0, 0],
+        FaceName::L => [-1, 0, 0],
+        FaceName::U => [0, 1, 0],
+        FaceName::D => [0, -1, 0],
+        FaceName::F => [0, 0, 1],
+        FaceName::B => [0, 0, -1],
+    }
+}
+
+fn cross(a: [i64; 3], b: [i64; 3]) -> [i64; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn dot(a: [i64; 3], b: [i64; 3]) -> i64 {
+    a.iter().zip(&b).map(|(x, y)| x * y).sum()
+}
+
+/// The outer layer turn of a face
+fn outer_turn(face: WideFace) -> Move {
+    match face {
+        WideFace::R => Move::R,
+        WideFace::L => Move::L,
+        WideFace::U => Move::U,
+        WideFace::D => Move::D,
+        WideFace::F => Move::F,
+        WideFace::B => Move::B,
+    }
+}
+
+/// Layer of every sticker counted from `positive`, found by tracing which
+/// stickers wide turns of each depth move
+fn layers(size: usize, positive: (WideFace, FaceName), negative: (WideFace, FaceName)) -> Vec<usize> {
+    let moved = |face: WideFace| -> Vec<Vec<bool>> {
+        (1..=size.div_ceil(2))
+            .map(|depth| {
+                let source = trace_sources(size, |cube| match depth {
+                    // Wide moves need a 3x3 or bigger, so 2x2 layers come from face turns
+                    1 => cube.apply_move(outer_turn(face)),
+                    _ => cube.apply_wide_move(WideMove::new(face, Direction::Clockwise, depth)),
+                });
+                source.iter().enumerate().map(|(dest, src)| dest != *src).collect()
+            })
+            .collect()
+    };
+    let (from_positive, from_negative) = (moved(positive.0), moved(negative.0));
+
+    (0..6 * size * size)
+        .map(|index| {
+            let (face, _, _) = position(size, index);
+            if face == positive.1 {
+                0
+            } else if face == negative.1 {
+                size - 1
+            } else if let Some(depth) = from_positive.iter().position(|layer| layer[index]) {
+                depth
+            } else {
+                let depth = from_negative.iter().position(|layer| layer[index]).unwrap_or(0);
+                size - 1 - depth
+            }
+        })
+        .collect()
+}
+
+/// Smallest image of a position under the 24 rotations of the cube, so
+/// every position in an orbit gets the same key
+fn orbit_key(position: [i64; 3]) -> [i64; 3] {
+    // Even permutations first, then the odd ones
+    const PERMUTATIONS: [[usize; 3]; 6] = [[0, 1, 2], [1, 2, 0], [2, 0, 1], [0, 2, 1], [2, 1, 0], [1, 0, 2]];
+
+    let mut key = position;
+    for (index, permutation) in PERMUTATIONS.iter().enumerate() {
+        for signs in 0..8u32 {
+            // A rotation has determinant +1: odd permutations need an odd number of sign flips
+            if !(signs.count_ones() as usize + index / 3).is_multiple_of(2) {
+                continue;
+            }
+            let image = [0, 1, 2].map(|axis| {
+                let value = position[permutation[axis]];
+                if signs >> axis & 1 == 1 {
+                    -value
+                } else {
+                    value
+                }
+            });
+            key = key.min(image);
+        }
+    }
+    key
+}
+
+/// Every piece slot of a cube, grouped into orbits of interchangeable pieces
+fn orbits(size: usize) -> Vec<Vec<Slot>> {
+    let axes = [
+        layers(size, (WideFace::R, FaceName::R), (WideFace::L, FaceName::L)),
+        layers(size, (WideFace::U, FaceName::U), (WideFace::D, FaceName::D)),
+        layers(size, (WideFace::F, FaceName::F), (WideFace::B, FaceName::B)),
+    ];
+    let mut pieces: BTreeMap<[i64; 3], Vec<usize>> = BTreeMap::new();
+    for index in 0..6 * size * size {
+        let at = axes.each_ref().map(|layers| (size - 1) as i64 - 2 * layers[index] as i64);
+        pieces.entry(at).or_default().push(index);
+    }
+
+    let mut orbits: BTreeMap<[i64; 3], Vec<Slot>> = BTreeMap::new();
+    for (at, mut stickers) in pieces {
+        let normals = |stickers: &[usize]| stickers.iter().map(|s| normal(position(size, *s).0)).collect::<Vec<_>>();
+        match stickers.len() {
+            // Corners start from their U or D sticker and go round counterclockwise
+            3 => {
+                let first = stickers.iter().position(|s| normal(position(size, *s).0)[1] != 0).unwrap_or(0);
+                stickers.rotate_left(first);
+                let n = normals(&stickers);
+                if dot(cross(n[0], n[1]), n[2]) < 0 {
+                    stickers.swap(1, 2);
+                }
+            }
+            // Wings can only sit one way round in their slot; midges keep facelet order
+            2 => {
+                let n = normals(&stickers);
+                if dot(cross(n[0], n[1]), at) < 0 {
+                    stickers.swap(0, 1);
+                }
+            }
+            _ => {}
+        }
+        orbits.entry(orbit_key(at)).or_default().push(Slot { stickers, position: at });
+    }
+    orbits.into_values().collect()
+}
+
+/// Whether a permutation is odd
+fn is_odd(permutation: &[usize]) -> bool {
+    // Each cycle of length k takes k - 1 swaps
+    let mut seen = vec![false; permutation.len()];
+    let mut swaps = 0;
+    for start in 0..permutation.len() {
+        let mut at = start;
+        let mut length = 0;
+        while !seen[at] {
+            seen[at] = true;
+            at = permutation[at];
+            length += 1;
+        }
+        swaps += length.max(1) - 1;
+    }
+    swaps % 2 == 1
+}
+
+/// Random orientations for `count` pieces with `states` orientations each,
+/// adding up to a multiple of `states`
+fn orientations<R: Rng>(rng: &mut R, count: usize, states: usize) -> Vec<usize> {
+    let mut twists: Vec<usize> = (1..count).map(|_| rng.gen_range(0..states)).collect();
+    let sum: usize = twists.iter().sum();
+    twists.push((states - sum % states) % states);
+    twists
+}
+
+impl Cube {
+    /// A uniformly random solvable state
+    ///
+    /// Every state that can be reached by turning a solved cube is equally
+    /// likely. Corners keep a whole number of twists and edges an even
+    /// number of flips; on odd cubes the middle centers stay put and the
+    /// corner and edge permutations have the same parity. Bigger cubes can
+    /// mix their wings and centers freely.
+    ///
+    /// ```
+    /// use rubiks_cube_solver::cube::Cube;
+    ///
+    /// let cube = Cube::random_state(3, &mut rand::thread_rng());
+    /// assert!(cube.has_valid_color_counts());
+    /// ```
+    pub fn random_state<R: Rng>(size: usize, rng: &mut R) -> Cube {
+        let solved = Cube::new(size);
+        if size < 2 {
+            return solved;
+        }
+        let color = |index: usize| {
+            let (face, row, col) = position(size, index);
+            solved.get_face(face).get(row, col)
+        };
+
+        let mut cube = Cube::new(size);
+        let mut place = |slots: &[Slot], piece: usize, slot: usize, twist: usize| {
+            let (from, to) = (&slots[piece].stickers, &slots[slot].stickers);
+            for (i, sticker) in from.iter().enumerate() {
+                let (face, row, col) = position(size, to[(i + twist) % to.len()]);
+                cube.set_sticker(face, row, col, color(*sticker));
+            }
+        };
+
+        let mut corner_parity = false;
+        let mut midges = None;
+        for slots in orbits(size) {
+            let mut permutation: Vec<usize> = (0..slots.len()).collect();
+            permutation.shuffle(rng);
+
+            let stickers = slots[0].stickers.len();
+            let is_wing = stickers == 2 && {
+                let n: Vec<_> = slots[0].stickers.iter().map(|s| normal(position(size, *s).0)).collect();
+                dot(cross(n[0], n[1]), slots[0].position) != 0
+            };
+            match stickers {
+                // The middle centers of odd cubes fix the color scheme
+                1 if slots.len() == 6 => {}
+                1 => {
+                    for (slot, piece) in permutation.iter().enumerate() {
+                        place(&slots, *piece, slot, 0);
+                    }
+                }
+                2 if is_wing => {
+                    for (slot, piece) in permutation.iter().enumerate() {
+                        place(&slots, *piece, slot, 0);
+                    }
+                }
+                // Midges wait until the corner parity is known
+                2 => midges = Some((slots, permutation)),
+                _ => {
+                    corner_parity = is_odd(&permutation);
+                    let twists = orientations(rng, slots.len(), stickers);
+                    for (slot, piece) in permutation.iter().enumerate() {
+                        place(&slots, *piece, slot, twists[slot]);
+                    }
+                }
+            }
+        }
+
+        if let Some((slots, mut permutation)) = midges {
+            if is_odd(&permutation) != corner_parity {
+                permutation.swap(0, 1);
+            }
+            let flips = orientations(rng, slots.len(), 2);
+            for (slot, piece) in permutation.iter().enumerate() {
+                place(&slots, *piece, slot, flips[slot]);
+            }
+        }
+        cube
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{solve_blind, solve_reduction};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_piece_counts() {
+        for size in 2..=7 {
+            let orbits = orbits(size);
+            let count = |stickers: usize| {
+                orbits.iter().flatten().filter(|slot| slot.stickers.len() == stickers).count()
+            };
+            let inner = size - 2;
+            assert_eq!(count(3), 8, "{}x{} corners", size, size);
+            assert_eq!(count(2), 12 * inner, "{}x{} edges", size, size);
+            assert_eq!(count(1), 6 * inner * inner, "{}x{} centers", size, size);
+            assert!(orbits.iter().all(|slots| [6, 8, 12, 24].contains(&slots.len())));
+        }
+    }
+
+    #[test]
+    fn test_random_3x3_is_solvable() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..20 {
+            let mut cube = Cube::random_state(3, &mut rng);
+            assert!(cube.has_valid_color_counts());
+            let (_, solution) = solve_blind(&cube).expect("Should be solvable");
+            cube.apply_moves(&solution.all_moves());
+            assert!(cube.is_solved());
+        }
+    }
+
+    #[test]
+    fn test_random_2x2_corner_twist() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..20 {
+            let cube = Cube::random_state(2, &mut rng);
+            // Count quarter twists of the white/yellow stickers away from U/D
+            let twist: usize = orbits(2)[0]
+                .iter()
+                .map(|slot| {
+                    slot.stickers
+                        .iter()
+                        .position(|s| {
+                            let (face, row, col) = position(2, *s);
+                            let color = cube.get_face(face).get(row, col);
+                            color == FaceName::U.standard_color() || color == FaceName::D.standard_color()
+                        })
+                        .unwrap()
+                })
+                .sum();
+            assert_eq!(twist % 3, 0);
+        }
+    }
+
+    #[test]
+    fn test_random_big_cubes_reduce_and_solve() {
+        let mut rng = StdRng::seed_from_u64(4);
+        for size in 4..=6 {
+            let mut cube = Cube::random_state(size, &mut rng);
+            assert!(cube.has_valid_color_counts());
+            solve_reduction(&cube).expect("Should reduce").apply_to(&mut cube);
+
+            let mut reduced = Cube::new(3);
+            for face in FaceName::all() {
+                for row in 0..3 {
+                    for col in 0..3 {
+                        let at = |i: usize| [0, 1, size - 1][i];
+                        reduced.set_sticker(face, row, col, cube.get_face(face).get(at(row), at(col)));
+                    }
+                }
+            }
+            // Even cubes have no fixed centers, so the centers may have come out in a turned color scheme
+            let mut small = reduced.recolor_to_standard().unwrap();
+            let (_, solution) = solve_blind(&small).expect("Should be a valid 3x3");
+            small.apply_moves(&solution.all_moves());
+            assert!(small.is_solved(), "{}x{} does not solve", size, size);
+        }
+    }
+
+    #[test]
+    fn test_seeded_states_repeat() {
+        let first = Cube::random_state(4, &mut StdRng::seed_from_u64(7));
+        let second = Cube::random_state(4, &mut StdRng::seed_from_u64(7));
+        let other = Cube::random_state(4, &mut StdRng::seed_from_u64(8));
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert!(!first.is_solved());
+    }
+}
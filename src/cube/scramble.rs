@@ -4,9 +4,18 @@
 //! - Generate random scramble of configurable length
 //! - Avoid redundant moves (no R R or R R')
 //! - Return both move list and scrambled cube state
+//!
+//! Scrambles can be restricted to a move set (e.g. only R, U, F for
+//! one-handed beginners), forbid consecutive moves on the same axis, and
+//! be regenerated until they reach a minimum quality.
+//!
+//! On 4x4 and bigger, scrambles mix in wide moves (Rw, 3Rw) the way WCA
+//! big-cube scrambles do, and [`ScrambleConfig::wca`] picks the WCA length
+//! for the size (40 moves on 4x4 up to 100 on 7x7).
 
-use super::moves::Move;
-use super::state::Cube;
+use super::moves::{Direction, Move, WideFace, WideMove};
+use super::notation::{algorithm_to_notation, ParsedMove};
+use super::state::{Cube, FaceName};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 
@@ -14,27 +23,67 @@ use rand::thread_rng;
 #[derive(Debug, Clone)]
 pub struct Scramble {
     /// The sequence of moves that make up the scramble
-    pub moves: Vec<Move>,
+    pub moves: Vec<ParsedMove>,
     /// The resulting cube state after applying the scramble
     pub cube: Cube,
 }
 
 impl Scramble {
     /// Creates a new scramble with the given moves and cube state
-    pub fn new(moves: Vec<Move>, cube: Cube) -> Self {
+    pub fn new(moves: Vec<ParsedMove>, cube: Cube) -> Self {
         Self { moves, cube }
     }
 
-    /// Returns the scramble as a notation string (e.g., "R U R' U'")
+    /// Returns the scramble as a notation string (e.g., "R U R' U'" or
+    /// "Rw U2 3Fw'")
     pub fn to_notation(&self) -> String {
+        algorithm_to_notation(&self.moves)
+    }
+
+    /// Applies the scramble's moves to a cube
+    pub fn apply_to(&self, cube: &mut Cube) {
+        for m in &self.moves {
+            cube.apply_parsed_move(m);
+        }
+    }
+
+    /// The moves as face and slice turns, or None if the scramble has wide
+    /// moves
+    pub fn basic_moves(&self) -> Option<Vec<Move>> {
         self.moves
             .iter()
-            .map(|m| m.to_notation())
-            .collect::<Vec<_>>()
-            .join(" ")
+            .map(|m| match m {
+                ParsedMove::Basic(mv) => Some(*mv),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Number of moves in a WCA scramble for a cube size
+///
+/// 11 on 2x2, 20 on 3x3 and 20 more per size above that (40 on 4x4, 100 on
+/// 7x7).
+pub fn wca_length(size: usize) -> usize {
+    match size {
+        0..=2 => 11,
+        _ => 20 * (size - 2),
     }
 }
 
+/// All faces, for scanning every sticker
+const FACES: [FaceName; 6] = [
+    FaceName::U,
+    FaceName::D,
+    FaceName::F,
+    FaceName::B,
+    FaceName::L,
+    FaceName::R,
+];
+
+/// Maximum number of scrambles tried when a minimum quality is requested
+const MAX_QUALITY_ATTEMPTS: usize = 100;
+
 /// Configuration for scramble generation
 #[derive(Debug, Clone)]
 pub struct ScrambleConfig {
@@ -42,6 +91,18 @@ pub struct ScrambleConfig {
     pub length: usize,
     /// Cube size
     pub size: usize,
+    /// Restrict scrambles to these moves (None uses every move for the size)
+    pub allowed_moves: Option<Vec<Move>>,
+    /// Moves that never appear in the scramble
+    pub excluded_moves: Vec<Move>,
+    /// Whether slice moves (M, E, S) may be used on odd-sized cubes
+    pub allow_slice_moves: bool,
+    /// Whether wide moves (Rw, 3Rw) may be used on 4x4 and bigger
+    pub allow_wide_moves: bool,
+    /// Forbid consecutive moves on the same axis (e.g. R followed by L)
+    pub forbid_same_axis: bool,
+    /// Minimum scramble quality (0.0-1.0), see `scramble_quality`
+    pub min_quality: f64,
 }
 
 impl Default for ScrambleConfig {
@@ -49,6 +110,12 @@ impl Default for ScrambleConfig {
         Self {
             length: 20,
             size: 3,
+            allowed_moves: None,
+            excluded_moves: Vec::new(),
+            allow_slice_moves: true,
+            allow_wide_moves: true,
+            forbid_same_axis: false,
+            min_quality: 0.0,
         }
     }
 }
@@ -56,7 +123,138 @@ impl Default for ScrambleConfig {
 impl ScrambleConfig {
     /// Creates a new scramble configuration
     pub fn new(length: usize, size: usize) -> Self {
-        Self { length, size }
+        Self {
+            length,
+            size,
+            ..Self::default()
+        }
+    }
+
+    /// WCA-style scramble for a cube size: the WCA length, face and wide
+    /// moves, no slices
+    pub fn wca(size: usize) -> Self {
+        Self::new(wca_length(size), size).without_slices()
+    }
+
+    /// Restrict scrambles to the given moves (which leaves out wide moves)
+    pub fn with_moves(mut self, moves: &[Move]) -> Self {
+        self.allowed_moves = Some(moves.to_vec());
+        self
+    }
+
+    /// Restrict scrambles to turns of the given faces (all three directions)
+    ///
+    /// Example: `[FaceName::R, FaceName::U, FaceName::F]` for one-handed beginners
+    pub fn with_faces(self, faces: &[FaceName]) -> Self {
+        let moves: Vec<Move> = faces.iter().flat_map(|face| face_moves(*face)).collect();
+        self.with_moves(&moves)
+    }
+
+    /// Never use the given moves
+    pub fn excluding(mut self, moves: &[Move]) -> Self {
+        self.excluded_moves.extend_from_slice(moves);
+        self
+    }
+
+    /// Never use slice moves
+    pub fn without_slices(mut self) -> Self {
+        self.allow_slice_moves = false;
+        self
+    }
+
+    /// Never use wide moves
+    pub fn without_wide_moves(mut self) -> Self {
+        self.allow_wide_moves = false;
+        self
+    }
+
+    /// Forbid consecutive moves on the same axis
+    pub fn forbid_same_axis(mut self) -> Self {
+        self.forbid_same_axis = true;
+        self
+    }
+
+    /// Regenerate scrambles until they reach this quality (0.0-1.0)
+    pub fn with_min_quality(mut self, quality: f64) -> Self {
+        self.min_quality = quality.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Gets the moves a scramble may use with this configuration
+    pub fn move_set(&self) -> Vec<Move> {
+        let available = get_available_moves(self.size);
+        let candidates = match &self.allowed_moves {
+            Some(allowed) => allowed
+                .iter()
+                .filter(|m| available.contains(m))
+                .copied()
+                .collect(),
+            None => available,
+        };
+
+        candidates
+            .into_iter()
+            .filter(|m| !self.excluded_moves.contains(m))
+            .filter(|m| self.allow_slice_moves || !m.is_slice())
+            .collect()
+    }
+
+    /// Gets the wide moves a scramble may use with this configuration
+    ///
+    /// Every depth from 2 layers up to half the cube is used. A half-cube
+    /// turn (3Rw on 6x6) is the opposite one plus a rotation, so at that
+    /// depth only R, U and F are turned, as in WCA scrambles.
+    pub fn wide_move_set(&self) -> Vec<WideMove> {
+        if !self.allow_wide_moves || self.allowed_moves.is_some() {
+            return Vec::new();
+        }
+
+        let mut moves = Vec::new();
+        for depth in 2..=self.size / 2 {
+            let faces: &[WideFace] = if depth * 2 == self.size {
+                &[WideFace::R, WideFace::U, WideFace::F]
+            } else {
+                &[WideFace::R, WideFace::L, WideFace::U, WideFace::D, WideFace::F, WideFace::B]
+            };
+            for face in faces {
+                for direction in [Direction::Clockwise, Direction::CounterClockwise, Direction::Double] {
+                    moves.push(WideMove::new(*face, direction, depth));
+                }
+            }
+        }
+        moves
+    }
+}
+
+/// Measures how well a cube is scrambled
+///
+/// Returns the fraction of non-center stickers that are not in their solved
+/// position, so a solved cube is 0.0 and a random 3x3 is about 0.83.
+pub fn scramble_quality(cube: &Cube) -> f64 {
+    let solved = Cube::new(cube.size());
+    let size = cube.size();
+    let mut total = 0;
+    let mut misplaced = 0;
+
+    for face in FACES {
+        for row in 0..size {
+            for col in 0..size {
+                let is_center = row > 0 && row < size - 1 && col > 0 && col < size - 1;
+                if is_center {
+                    continue;
+                }
+                total += 1;
+                if cube.get_face(face).get(row, col) != solved.get_face(face).get(row, col) {
+                    misplaced += 1;
+                }
+            }
+        }
+    }
+
+    if total == 0 {
+        0.0
+    } else {
+        misplaced as f64 / total as f64
     }
 }
 
@@ -77,16 +275,54 @@ impl ScrambleConfig {
 /// assert_eq!(scramble.moves.len(), 20);
 /// ```
 pub fn generate_scramble(config: &ScrambleConfig) -> Scramble {
-    let mut cube = Cube::new(config.size);
-    let mut moves = Vec::new();
     let mut rng = thread_rng();
+    let available_moves: Vec<ParsedMove> = config
+        .move_set()
+        .into_iter()
+        .map(ParsedMove::Basic)
+        .chain(config.wide_move_set().into_iter().map(ParsedMove::Wide))
+        .collect();
+
+    let mut best = generate_once(config, &available_moves, &mut rng);
+    if config.min_quality <= 0.0 {
+        return best;
+    }
 
-    // All basic moves (excluding wide moves, slice moves on even cubes, and rotations for scrambles)
-    let all_moves = get_available_moves(config.size);
+    // Keep the best attempt in case the target quality is never reached
+    let mut best_quality = scramble_quality(&best.cube);
+    for _ in 1..MAX_QUALITY_ATTEMPTS {
+        if best_quality >= config.min_quality {
+            break;
+        }
+        let candidate = generate_once(config, &available_moves, &mut rng);
+        let quality = scramble_quality(&candidate.cube);
+        if quality > best_quality {
+            best = candidate;
+            best_quality = quality;
+        }
+    }
+
+    best
+}
+
+/// Generates a single scramble from a move set
+///
+/// Stops early if the restrictions leave no legal next move (e.g. a move
+/// set containing only one face).
+fn generate_once<R: rand::Rng>(
+    config: &ScrambleConfig,
+    available_moves: &[ParsedMove],
+    rng: &mut R,
+) -> Scramble {
+    let mut cube = Cube::new(config.size);
+    let mut moves = Vec::new();
 
     for _ in 0..config.length {
-        let next_move = select_next_move(&moves, &all_moves, &mut rng);
-        cube.apply_move(next_move);
+        let next_move = select_next_move(&moves, available_moves, config.forbid_same_axis, rng);
+        let Some(next_move) = next_move else {
+            break;
+        };
+        cube.apply_parsed_move(&next_move);
         moves.push(next_move);
     }
 
@@ -137,53 +373,56 @@ fn get_available_moves(size: usize) -> Vec<Move> {
 
 /// Selects the next move avoiding redundancies
 ///
+/// Wide moves count as turns of their face, so Rw never follows R.
+///
 /// Avoids:
 /// - Same face in succession (R R)
 /// - Same face with inverse (R R')
 /// - Same face with double (R R2)
+/// - Same axis (R L), when `forbid_same_axis` is set
 fn select_next_move<R: rand::Rng>(
-    previous_moves: &[Move],
-    available_moves: &[Move],
+    previous_moves: &[ParsedMove],
+    available_moves: &[ParsedMove],
+    forbid_same_axis: bool,
     rng: &mut R,
-) -> Move {
-    if previous_moves.is_empty() {
-        return *available_moves.choose(rng).unwrap();
-    }
-
-    let last_move = previous_moves.last().unwrap();
-    let last_face = get_move_face(last_move);
-
-    // Filter out moves on the same face as the last move
-    let valid_moves: Vec<Move> = available_moves
+) -> Option<ParsedMove> {
+    let Some(last_move) = previous_moves.last() else {
+        return available_moves.choose(rng).cloned();
+    };
+    let last_face = turn_face(last_move);
+
+    // Filter out moves on the same face (or axis) as the last move
+    let valid_moves: Vec<ParsedMove> = available_moves
         .iter()
-        .filter(|&&m| get_move_face(&m) != last_face)
-        .copied()
+        .filter(|m| turn_face(m) != last_face)
+        .filter(|m| !forbid_same_axis || get_axis(turn_face(m)) != get_axis(last_face))
+        .cloned()
         .collect();
 
     // Additional check: avoid opposite faces in succession for better randomization
     // (e.g., R followed by L doesn't help much in scrambling)
     if previous_moves.len() >= 2 {
-        let second_last_move = previous_moves[previous_moves.len() - 2];
-        let second_last_face = get_move_face(&second_last_move);
+        let second_last_move = &previous_moves[previous_moves.len() - 2];
+        let second_last_face = turn_face(second_last_move);
 
         // If last two moves were on opposite faces, avoid both for this move
         if are_opposite_faces(last_face, second_last_face) {
-            let filtered: Vec<Move> = valid_moves
+            let filtered: Vec<ParsedMove> = valid_moves
                 .iter()
-                .filter(|&&m| {
-                    let face = get_move_face(&m);
+                .filter(|m| {
+                    let face = turn_face(m);
                     face != last_face && face != second_last_face
                 })
-                .copied()
+                .cloned()
                 .collect();
 
             if !filtered.is_empty() {
-                return *filtered.choose(rng).unwrap();
+                return filtered.choose(rng).cloned();
             }
         }
     }
 
-    *valid_moves.choose(rng).unwrap()
+    valid_moves.choose(rng).cloned()
 }
 
 /// Face identifier for grouping moves
@@ -218,6 +457,44 @@ fn get_move_face(m: &Move) -> Face {
     }
 }
 
+/// Gets the face a basic, wide or layer move turns
+fn turn_face(m: &ParsedMove) -> Face {
+    let wide_face = |face: WideFace| match face {
+        WideFace::R => Face::R,
+        WideFace::L => Face::L,
+        WideFace::U => Face::U,
+        WideFace::D => Face::D,
+        WideFace::F => Face::F,
+        WideFace::B => Face::B,
+    };
+    match m {
+        ParsedMove::Basic(mv) => get_move_face(mv),
+        ParsedMove::Wide(wide) => wide_face(wide.face),
+        ParsedMove::Layer(layers) => wide_face(layers.face),
+    }
+}
+
+/// Gets the axis a face turns around (0 = R/L/M, 1 = U/D/E, 2 = F/B/S)
+fn get_axis(face: Face) -> u8 {
+    match face {
+        Face::R | Face::L | Face::M => 0,
+        Face::U | Face::D | Face::E => 1,
+        Face::F | Face::B | Face::S => 2,
+    }
+}
+
+/// Gets the three turns of a face
+fn face_moves(face: FaceName) -> [Move; 3] {
+    match face {
+        FaceName::R => [Move::R, Move::RPrime, Move::R2],
+        FaceName::L => [Move::L, Move::LPrime, Move::L2],
+        FaceName::U => [Move::U, Move::UPrime, Move::U2],
+        FaceName::D => [Move::D, Move::DPrime, Move::D2],
+        FaceName::F => [Move::F, Move::FPrime, Move::F2],
+        FaceName::B => [Move::B, Move::BPrime, Move::B2],
+    }
+}
+
 /// Checks if two faces are opposite
 fn are_opposite_faces(f1: Face, f2: Face) -> bool {
     matches!(
@@ -259,8 +536,8 @@ mod tests {
             let scramble = generate_scramble(&config);
 
             for i in 1..scramble.moves.len() {
-                let prev_face = get_move_face(&scramble.moves[i - 1]);
-                let curr_face = get_move_face(&scramble.moves[i]);
+                let prev_face = turn_face(&scramble.moves[i - 1]);
+                let curr_face = turn_face(&scramble.moves[i]);
                 assert_ne!(
                     prev_face, curr_face,
                     "Found same face in succession: {:?} followed by {:?}",
@@ -350,4 +627,121 @@ mod tests {
         assert_eq!(config.length, 20);
         assert_eq!(config.size, 3);
     }
+
+    #[test]
+    fn test_scramble_with_faces_only_uses_those_faces() {
+        let config = ScrambleConfig::new(30, 3).with_faces(&[FaceName::R, FaceName::U, FaceName::F]);
+        for _ in 0..20 {
+            let scramble = generate_scramble(&config);
+            assert_eq!(scramble.moves.len(), 30);
+            for m in &scramble.moves {
+                assert!(matches!(turn_face(m), Face::R | Face::U | Face::F), "{:?}", m);
+            }
+        }
+    }
+
+    #[test]
+    fn test_excluded_moves_and_slices() {
+        let config = ScrambleConfig::new(50, 5)
+            .without_slices()
+            .excluding(&[Move::R2, Move::U]);
+        assert!(!config.move_set().iter().any(Move::is_slice));
+
+        let scramble = generate_scramble(&config);
+        let excluded = |m: &ParsedMove| matches!(m, ParsedMove::Basic(b) if b.is_slice() || *b == Move::R2 || *b == Move::U);
+        assert!(!scramble.moves.iter().any(excluded));
+    }
+
+    #[test]
+    fn test_allowed_moves_respect_cube_size() {
+        let config = ScrambleConfig::new(10, 4).with_moves(&[Move::R, Move::M, Move::U]);
+        assert_eq!(config.move_set(), vec![Move::R, Move::U]);
+    }
+
+    #[test]
+    fn test_forbid_same_axis() {
+        let config = ScrambleConfig::new(40, 3).forbid_same_axis();
+        for _ in 0..50 {
+            let scramble = generate_scramble(&config);
+            for pair in scramble.moves.windows(2) {
+                assert_ne!(get_axis(turn_face(&pair[0])), get_axis(turn_face(&pair[1])));
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_face_stops_early() {
+        let config = ScrambleConfig::new(10, 3).with_faces(&[FaceName::R]);
+        assert_eq!(generate_scramble(&config).moves.len(), 1);
+
+        let empty = ScrambleConfig::new(10, 3).with_moves(&[]);
+        assert!(generate_scramble(&empty).moves.is_empty());
+    }
+
+    #[test]
+    fn test_wide_move_sets() {
+        assert!(ScrambleConfig::new(20, 3).wide_move_set().is_empty());
+
+        // 4x4: Rw, Uw and Fw only, since Lw is Rw plus a rotation
+        let four = ScrambleConfig::new(40, 4).wide_move_set();
+        assert_eq!(four.len(), 9);
+        assert!(four.iter().all(|w| w.depth == 2 && matches!(w.face, WideFace::R | WideFace::U | WideFace::F)));
+
+        // 6x6: every face two deep, 3Rw, 3Uw and 3Fw
+        let six = ScrambleConfig::new(80, 6).wide_move_set();
+        assert_eq!(six.len(), 18 + 9);
+        assert!(six.contains(&WideMove::new(WideFace::L, Direction::Clockwise, 2)));
+        assert!(!six.contains(&WideMove::new(WideFace::L, Direction::Clockwise, 3)));
+
+        // 7x7: every face two and three deep
+        assert_eq!(ScrambleConfig::new(100, 7).wide_move_set().len(), 36);
+
+        assert!(ScrambleConfig::new(40, 4).without_wide_moves().wide_move_set().is_empty());
+        assert!(ScrambleConfig::new(40, 4).with_moves(&[Move::R]).wide_move_set().is_empty());
+    }
+
+    #[test]
+    fn test_wca_big_cube_scrambles() {
+        for (size, length) in [(4, 40), (5, 60), (6, 80), (7, 100)] {
+            let config = ScrambleConfig::wca(size);
+            assert_eq!(config.length, length);
+            let scramble = generate_scramble(&config);
+            assert_eq!(scramble.moves.len(), length);
+            assert!(scramble.moves.iter().any(|m| matches!(m, ParsedMove::Wide(_))));
+            assert!(!scramble.moves.iter().any(|m| matches!(m, ParsedMove::Basic(b) if b.is_slice())));
+            assert_eq!(scramble.basic_moves(), None);
+
+            // The notation and the moves give the same cube
+            let mut from_notation = Cube::new(size);
+            from_notation.apply_algorithm(&scramble.to_notation()).unwrap();
+            assert_eq!(from_notation, scramble.cube);
+            let mut applied = Cube::new(size);
+            scramble.apply_to(&mut applied);
+            assert_eq!(applied, scramble.cube);
+        }
+        assert_eq!(wca_length(2), 11);
+        assert_eq!(wca_length(3), 20);
+    }
+
+    #[test]
+    fn test_deep_wide_move_notation() {
+        let moves = vec![
+            ParsedMove::Wide(WideMove::rw()),
+            ParsedMove::Basic(Move::U2),
+            ParsedMove::Wide(WideMove::new(WideFace::F, Direction::CounterClockwise, 3)),
+        ];
+        let mut cube = Cube::new(7);
+        cube.apply_algorithm("Rw U2 3Fw'").unwrap();
+        assert_eq!(Scramble::new(moves, cube).to_notation(), "Rw U2 3Fw'");
+    }
+
+    #[test]
+    fn test_scramble_quality() {
+        assert_eq!(scramble_quality(&Cube::new(3)), 0.0);
+
+        let config = ScrambleConfig::new(20, 3).with_min_quality(0.7);
+        for _ in 0..10 {
+            assert!(scramble_quality(&generate_scramble(&config).cube) >= 0.7);
+        }
+    }
 }
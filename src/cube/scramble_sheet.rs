@@ -0,0 +1,126 @@
+//! Printable scramble sheets
+//!
+//! A page of numbered scrambles for one cube size, each next to a small net
+//! of the state it leads to, so students can check they applied it right.
+//! The sheet is a self-contained HTML page with the nets inlined as SVG,
+//! laid out to print on A4 or Letter from any browser.
+
+use super::scramble::{generate_scramble, Scramble, ScrambleConfig};
+use super::svg::SvgOptions;
+
+/// Sticker size of the nets on a sheet: small, but big enough to read the
+/// color letters
+pub const SHEET_STICKER_SIZE: u32 = 10;
+
+/// A titled list of scrambles for one cube size
+#[derive(Debug, Clone)]
+pub struct ScrambleSheet {
+    /// Heading printed at the top of the page
+    pub title: String,
+    /// Cube size every scramble is for
+    pub size: usize,
+    pub scrambles: Vec<Scramble>,
+}
+
+impl ScrambleSheet {
+    /// Generate `count` scrambles with `config`, titled e.g. "4x4 Scrambles"
+    pub fn generate(config: &ScrambleConfig, count: usize) -> Self {
+        Self {
+            title: format!("{n}x{n} Scrambles", n = config.size),
+            size: config.size,
+            scrambles: (0..count).map(|_| generate_scramble(config)).collect(),
+        }
+    }
+
+    /// Use a different heading
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// Net options for the sheet: small stickers on the page background,
+    /// with color letters for black-and-white printers
+    pub fn net_options() -> SvgOptions {
+        SvgOptions::new()
+            .with_sticker_size(SHEET_STICKER_SIZE)
+            .with_gaps(1, 4)
+            .with_background(None)
+            .with_letters(true)
+    }
+
+    /// The sheet as a printable HTML page
+    pub fn to_html(&self) -> String {
+        let options = Self::net_options();
+        let mut html = vec![
+            "<!DOCTYPE html>".to_string(),
+            "<html>".to_string(),
+            "<head>".to_string(),
+            r#"<meta charset="utf-8">"#.to_string(),
+            format!("<title>{}</title>", escape_html(&self.title)),
+            format!("<style>{}</style>", SHEET_CSS),
+            "</head>".to_string(),
+            "<body>".to_string(),
+            format!("<h1>{}</h1>", escape_html(&self.title)),
+            r#"<ol class="scrambles">"#.to_string(),
+        ];
+        for scramble in &self.scrambles {
+            html.push(format!(
+                r#"<li><span class="notation">{}</span><span class="net">{}</span></li>"#,
+                escape_html(&scramble.to_notation()),
+                scramble.cube.to_svg(&options)
+            ));
+        }
+        html.extend(["</ol>", "</body>", "</html>"].map(str::to_string));
+        html.join("\n")
+    }
+}
+
+/// Page layout: one scramble per row, never split across pages
+const SHEET_CSS: &str = "\
+@page { margin: 1.5cm; }
+body { font-family: sans-serif; color: #000; }
+h1 { font-size: 20px; margin: 0 0 12px; }
+.scrambles { padding-left: 28px; margin: 0; }
+.scrambles li { display: flex; align-items: center; justify-content: space-between; flex-wrap: wrap; gap: 16px; \
+padding: 8px 0; border-bottom: 1px solid #ccc; break-inside: avoid; }
+.notation { font-family: monospace; font-size: 14px; line-height: 1.5; }
+.net svg { display: block; }";
+
+/// Escape text for use in HTML
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sheet_has_a_net_per_scramble() {
+        let sheet = ScrambleSheet::generate(&ScrambleConfig::wca(4), 5);
+        assert_eq!(sheet.title, "4x4 Scrambles");
+        assert_eq!(sheet.scrambles.len(), 5);
+
+        let html = sheet.to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<li>").count(), 5);
+        assert_eq!(html.matches("<svg").count(), 5);
+        // 96 stickers per 4x4 net, no background
+        assert_eq!(html.matches("<rect").count(), 5 * 96);
+    }
+
+    #[test]
+    fn test_notation_and_title_are_escaped() {
+        let sheet = ScrambleSheet::generate(&ScrambleConfig::new(20, 3), 1).with_title("Class <3B> & friends");
+        let html = sheet.to_html();
+        assert!(html.contains("<h1>Class &lt;3B&gt; &amp; friends</h1>"));
+
+        // Primes are escaped too
+        let notation = sheet.scrambles[0].to_notation();
+        assert!(html.contains(&notation.replace('\'', "&#39;")));
+    }
+}
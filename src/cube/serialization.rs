@@ -0,0 +1,274 @@
+//! Versioned save format for cube state
+//!
+//! Implements R1.9 (state serialization) with forward migration:
+//! - Every save is tagged with a format version
+//! - Older saves are upgraded step by step through `MIGRATIONS`
+//!   instead of being rejected
+//! - Saves from newer app versions are rejected with `UnsupportedVersion`
+//!
+//! Format history:
+//! - v1: `{ version, cube }`
+//! - v2: adds `orientation` (how the cube is held) and `metadata`
+//!   (creation time, app version, optional name)
+
+use super::state::{Cube, FaceName, SerializationError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Current serialization format version
+pub const SERIALIZATION_VERSION: u32 = 2;
+
+/// A migration upgrading a save from version N to N + 1
+type Migration = fn(Value) -> Result<Value, String>;
+
+/// Migrations indexed by the version they upgrade from
+///
+/// To add a new format version, bump `SERIALIZATION_VERSION` and append the
+/// migration from the previous version here.
+const MIGRATIONS: &[(u32, Migration)] = &[(1, migrate_v1_to_v2)];
+
+/// How the cube is being held
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CubeOrientation {
+    /// Face pointing up
+    pub up: FaceName,
+    /// Face pointing toward the user
+    pub front: FaceName,
+}
+
+impl Default for CubeOrientation {
+    fn default() -> Self {
+        Self {
+            up: FaceName::U,
+            front: FaceName::F,
+        }
+    }
+}
+
+/// Information about a saved cube
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CubeMetadata {
+    /// When the save was first created (Unix timestamp in seconds)
+    pub created_at: u64,
+    /// App version that created the save
+    pub app_version: String,
+    /// Optional user-given name
+    pub name: Option<String>,
+}
+
+impl CubeMetadata {
+    /// Create metadata stamped with the current time and app version
+    pub fn now() -> Self {
+        Self {
+            created_at: current_timestamp(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            name: None,
+        }
+    }
+}
+
+/// A cube together with its orientation and metadata (format v2)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CubeSave {
+    /// Format version
+    pub version: u32,
+    /// The cube state
+    pub cube: Cube,
+    /// How the cube is being held
+    pub orientation: CubeOrientation,
+    /// Save metadata
+    pub metadata: CubeMetadata,
+}
+
+impl CubeSave {
+    /// Wrap a cube in a new save with default orientation
+    pub fn new(cube: Cube) -> Self {
+        Self {
+            version: SERIALIZATION_VERSION,
+            cube,
+            orientation: CubeOrientation::default(),
+            metadata: CubeMetadata::now(),
+        }
+    }
+
+    /// Serialize to a JSON string
+    pub fn to_json(&self) -> Result<String, SerializationError> {
+        serde_json::to_string(self).map_err(|e| SerializationError::SerializationFailed(e.to_string()))
+    }
+
+    /// Serialize to a pretty-printed JSON string
+    pub fn to_json_pretty(&self) -> Result<String, SerializationError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| SerializationError::SerializationFailed(e.to_string()))
+    }
+
+    /// Deserialize from JSON, migrating older versions to the current format
+    pub fn from_json(json: &str) -> Result<Self, SerializationError> {
+        let value: Value = serde_json::from_str(json)
+            .map_err(|e| SerializationError::DeserializationFailed(e.to_string()))?;
+
+        let value = migrate(value)?;
+
+        let save: CubeSave = serde_json::from_value(value)
+            .map_err(|e| SerializationError::DeserializationFailed(e.to_string()))?;
+
+        if !save.cube.has_valid_color_counts() {
+            return Err(SerializationError::InvalidCubeState(
+                "Invalid color counts".to_string(),
+            ));
+        }
+
+        Ok(save)
+    }
+}
+
+/// Read the version tag of a save
+fn read_version(value: &Value) -> Result<u32, SerializationError> {
+    value
+        .get("version")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| {
+            SerializationError::DeserializationFailed("missing field `version`".to_string())
+        })
+}
+
+/// Upgrade a save to `SERIALIZATION_VERSION`, one version at a time
+pub fn migrate(mut value: Value) -> Result<Value, SerializationError> {
+    let mut version = read_version(&value)?;
+
+    if version > SERIALIZATION_VERSION || version == 0 {
+        return Err(SerializationError::UnsupportedVersion {
+            found: version,
+            supported: SERIALIZATION_VERSION,
+        });
+    }
+
+    while version < SERIALIZATION_VERSION {
+        let (_, migration) = MIGRATIONS
+            .iter()
+            .find(|(from, _)| *from == version)
+            .ok_or(SerializationError::UnsupportedVersion {
+                found: version,
+                supported: SERIALIZATION_VERSION,
+            })?;
+
+        value = migration(value).map_err(|reason| SerializationError::MigrationFailed {
+            from: version,
+            reason,
+        })?;
+        version += 1;
+        value["version"] = Value::from(version);
+    }
+
+    Ok(value)
+}
+
+/// v1 -> v2: add default orientation and metadata
+///
+/// v1 saves carry no creation time, so it is recorded as 0 (unknown).
+fn migrate_v1_to_v2(mut value: Value) -> Result<Value, String> {
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| "save is not a JSON object".to_string())?;
+
+    if !object.contains_key("cube") {
+        return Err("missing field `cube`".to_string());
+    }
+
+    let orientation = serde_json::to_value(CubeOrientation::default()).map_err(|e| e.to_string())?;
+    let metadata = serde_json::to_value(CubeMetadata {
+        created_at: 0,
+        app_version: "unknown".to_string(),
+        name: None,
+    })
+    .map_err(|e| e.to_string())?;
+
+    object.insert("orientation".to_string(), orientation);
+    object.insert("metadata".to_string(), metadata);
+    Ok(value)
+}
+
+/// Current Unix time in seconds
+#[cfg(not(target_arch = "wasm32"))]
+fn current_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Current Unix time in seconds
+#[cfg(target_arch = "wasm32")]
+fn current_timestamp() -> u64 {
+    (web_sys::js_sys::Date::now() / 1000.0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Move;
+
+    fn v1_json(cube: &Cube) -> String {
+        serde_json::json!({ "version": 1, "cube": cube }).to_string()
+    }
+
+    #[test]
+    fn test_save_roundtrip() {
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::R);
+        let mut save = CubeSave::new(cube);
+        save.orientation = CubeOrientation { up: FaceName::D, front: FaceName::B };
+        save.metadata.name = Some("My cube".to_string());
+
+        let restored = CubeSave::from_json(&save.to_json().unwrap()).unwrap();
+        assert_eq!(restored, save);
+    }
+
+    #[test]
+    fn test_v1_save_is_migrated() {
+        let mut cube = Cube::new(4);
+        cube.apply_move(Move::U);
+
+        let save = CubeSave::from_json(&v1_json(&cube)).unwrap();
+        assert_eq!(save.version, SERIALIZATION_VERSION);
+        assert_eq!(save.cube, cube);
+        assert_eq!(save.orientation, CubeOrientation::default());
+        assert_eq!(save.metadata.created_at, 0);
+    }
+
+    #[test]
+    fn test_future_and_zero_versions_rejected() {
+        for version in [0, SERIALIZATION_VERSION + 1] {
+            let json = serde_json::json!({ "version": version, "cube": Cube::new(2) }).to_string();
+            assert!(matches!(
+                CubeSave::from_json(&json),
+                Err(SerializationError::UnsupportedVersion { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_broken_v1_save_reports_migration_failure() {
+        let result = CubeSave::from_json(r#"{"version": 1}"#);
+        assert!(matches!(
+            result,
+            Err(SerializationError::MigrationFailed { from: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_missing_version() {
+        assert!(matches!(
+            CubeSave::from_json(r#"{"cube": {}}"#),
+            Err(SerializationError::DeserializationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_migrations_cover_every_version() {
+        for version in 1..SERIALIZATION_VERSION {
+            assert!(MIGRATIONS.iter().any(|(from, _)| *from == version));
+        }
+    }
+}
@@ -11,12 +11,10 @@
 //! - Deserialize JSON to cube state
 //! - Handle version compatibility
 
+use super::serialization::CubeSave;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-/// Current serialization format version
-const SERIALIZATION_VERSION: u32 = 1;
-
 /// Error types for serialization/deserialization
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SerializationError {
@@ -28,6 +26,8 @@ pub enum SerializationError {
     UnsupportedVersion { found: u32, supported: u32 },
     /// Invalid cube state (e.g., wrong color counts)
     InvalidCubeState(String),
+    /// Upgrading an older save to the current format failed
+    MigrationFailed { from: u32, reason: String },
 }
 
 impl fmt::Display for SerializationError {
@@ -42,27 +42,22 @@ impl fmt::Display for SerializationError {
             SerializationError::UnsupportedVersion { found, supported } => {
                 write!(
                     f,
-                    "Unsupported version: found v{}, but only v{} is supported",
+                    "Unsupported version: found v{}, but only v1 to v{} are supported",
                     found, supported
                 )
             }
             SerializationError::InvalidCubeState(msg) => {
                 write!(f, "Invalid cube state: {}", msg)
             }
+            SerializationError::MigrationFailed { from, reason } => {
+                write!(f, "Failed to upgrade save from v{}: {}", from, reason)
+            }
         }
     }
 }
 
 impl std::error::Error for SerializationError {}
 
-/// Wrapper struct for versioned cube serialization
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-struct CubeData {
-    /// Format version for backwards compatibility
-    version: u32,
-    /// The actual cube state
-    cube: Cube,
-}
 
 /// The six standard Rubik's cube colors
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
@@ -337,13 +332,8 @@ impl Cube {
     /// Counts the number of stickers of each color
     pub fn count_colors(&self) -> std::collections::HashMap<Color, usize> {
         let mut counts = std::collections::HashMap::new();
-        for face in FaceName::all() {
-            let f = self.get_face(face);
-            for row in 0..self.size {
-                for col in 0..self.size {
-                    *counts.entry(f.get(row, col)).or_insert(0) += 1;
-                }
-            }
+        for (_, _, _, color) in self.stickers() {
+            *counts.entry(color).or_insert(0) += 1;
         }
         counts
     }
@@ -379,14 +369,7 @@ impl Cube {
     /// assert!(json.contains("\"version\":"));
     /// ```
     pub fn to_json(&self) -> Result<String, SerializationError> {
-        let data = CubeData {
-            version: SERIALIZATION_VERSION,
-            cube: self.clone(),
-        };
-
-        serde_json::to_string(&data).map_err(|e| {
-            SerializationError::SerializationFailed(e.to_string())
-        })
+        CubeSave::new(self.clone()).to_json()
     }
 
     /// Serializes the cube state to a pretty-printed JSON string
@@ -397,19 +380,14 @@ impl Cube {
     /// * `Ok(String)` - Pretty-printed JSON string
     /// * `Err(SerializationError)` - If serialization fails
     pub fn to_json_pretty(&self) -> Result<String, SerializationError> {
-        let data = CubeData {
-            version: SERIALIZATION_VERSION,
-            cube: self.clone(),
-        };
-
-        serde_json::to_string_pretty(&data).map_err(|e| {
-            SerializationError::SerializationFailed(e.to_string())
-        })
+        CubeSave::new(self.clone()).to_json_pretty()
     }
 
     /// Deserializes a cube state from a JSON string
     ///
-    /// Validates version compatibility and cube state validity.
+    /// Validates version compatibility and cube state validity. Saves from
+    /// older format versions are migrated; orientation and metadata are
+    /// dropped (use `CubeSave::from_json` to keep them).
     ///
     /// # Arguments
     /// * `json` - JSON string representation of a cube
@@ -428,33 +406,14 @@ impl Cube {
     /// assert_eq!(original, restored);
     /// ```
     pub fn from_json(json: &str) -> Result<Self, SerializationError> {
-        // Deserialize with version info
-        let data: CubeData = serde_json::from_str(json).map_err(|e| {
-            SerializationError::DeserializationFailed(e.to_string())
-        })?;
-
-        // Check version compatibility
-        if data.version != SERIALIZATION_VERSION {
-            return Err(SerializationError::UnsupportedVersion {
-                found: data.version,
-                supported: SERIALIZATION_VERSION,
-            });
-        }
-
-        // Validate the cube state
-        if !data.cube.has_valid_color_counts() {
-            return Err(SerializationError::InvalidCubeState(
-                "Invalid color counts".to_string(),
-            ));
-        }
-
-        Ok(data.cube)
+        CubeSave::from_json(json).map(|save| save.cube)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cube::serialization::SERIALIZATION_VERSION;
 
     #[test]
     fn test_color_opposite() {
@@ -729,7 +688,7 @@ mod tests {
         match result {
             Err(SerializationError::UnsupportedVersion { found, supported }) => {
                 assert_eq!(found, 999);
-                assert_eq!(supported, 1);
+                assert_eq!(supported, 2);
             }
             _ => panic!("Expected UnsupportedVersion error"),
         }
@@ -744,12 +703,12 @@ mod tests {
         assert_eq!(err.to_string(), "Deserialization failed: test");
 
         let err = SerializationError::UnsupportedVersion {
-            found: 2,
-            supported: 1,
+            found: 3,
+            supported: 2,
         };
         assert_eq!(
             err.to_string(),
-            "Unsupported version: found v2, but only v1 is supported"
+            "Unsupported version: found v3, but only v1 to v2 are supported"
         );
 
         let err = SerializationError::InvalidCubeState("test".to_string());
@@ -0,0 +1,337 @@
+//! Supercube representation with sticker orientation tracking
+//!
+//! On a normal cube a center sticker looks the same no matter how it is
+//! turned. On a supercube (picture cubes, or 4x4+ cubes where center accuracy
+//! matters) every sticker has a visible orientation. `SuperCube` wraps a
+//! `Cube` and tracks, for every sticker, how many clockwise quarter turns it
+//! has made relative to the solved state.
+//!
+//! Orientation is derived from the move's sticker permutation, so it works
+//! for every move the simulator supports (face, slice, wide and rotations)
+//! on every cube size.
+
+use super::facelets::FACELET_ORDER;
+use super::moves::{Move, WideMove};
+use super::state::{Color, Cube, FaceName};
+
+/// Number of marker colors, so each marker cube carries one base-6 digit of a sticker index
+const MARKER_BASE: usize = 6;
+
+/// Marker colors used to trace sticker positions through a move
+const MARKER_COLORS: [Color; MARKER_BASE] = [
+    Color::White,
+    Color::Yellow,
+    Color::Red,
+    Color::Orange,
+    Color::Blue,
+    Color::Green,
+];
+
+/// Where every sticker comes from after a move, and how much it turned
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MoveTable {
+    /// For each destination sticker index, the source sticker index
+    source: Vec<usize>,
+    /// For each destination sticker index, clockwise quarter turns gained
+    twist: Vec<u8>,
+}
+
+impl MoveTable {
+    /// Trace a move by applying it to cubes whose stickers spell out their
+    /// own index in base 6
+    fn trace(size: usize, apply: impl Fn(&mut Cube)) -> Self {
+        let source = trace_sources(size, apply);
+        let twist = Self::twists(size, &source);
+        Self { source, twist }
+    }
+
+    /// Work out the quarter turns gained by each sticker
+    ///
+    /// Stickers that move together from one face to another move rigidly, so
+    /// the direction between any two of them turns by the same amount as
+    /// each sticker does.
+    fn twists(size: usize, source: &[usize]) -> Vec<u8> {
+        let per_face = size * size;
+        let mut twist = vec![0; source.len()];
+
+        for from in 0..6 {
+            for to in 0..6 {
+                let group: Vec<usize> = (0..source.len())
+                    .filter(|dest| dest / per_face == to && source[*dest] / per_face == from)
+                    .collect();
+
+                let Some(turns) = Self::group_turns(size, source, &group) else {
+                    continue;
+                };
+                for dest in group {
+                    twist[dest] = turns;
+                }
+            }
+        }
+        twist
+    }
+
+    /// Quarter turns that map the source layout of a group onto its destination
+    fn group_turns(size: usize, source: &[usize], group: &[usize]) -> Option<u8> {
+        let (&first, rest) = group.split_first()?;
+        let &second = rest.first()?;
+
+        let offset = |a: usize, b: usize| {
+            let (_, ar, ac) = position(size, a);
+            let (_, br, bc) = position(size, b);
+            (br as i64 - ar as i64, bc as i64 - ac as i64)
+        };
+        let before = offset(source[first], source[second]);
+        let after = offset(first, second);
+
+        // A clockwise quarter turn maps a grid offset (dr, dc) to (dc, -dr)
+        let mut turned = before;
+        for turns in 0..4 {
+            if turned == after {
+                return Some(turns);
+            }
+            turned = (turned.1, -turned.0);
+        }
+        None
+    }
+}
+
+/// Where every sticker comes from after a move: for each destination
+/// sticker index, the source sticker index
+///
+/// Traced by applying the move to cubes whose stickers spell out their own
+/// index in base 6.
+pub(crate) fn trace_sources(size: usize, apply: impl Fn(&mut Cube)) -> Vec<usize> {
+    let count = 6 * size * size;
+    let mut digits = 1;
+    while MARKER_BASE.pow(digits as u32) < count {
+        digits += 1;
+    }
+
+    let mut source = vec![0; count];
+    let mut place = 1;
+    for _ in 0..digits {
+        let mut marker = Cube::new(size);
+        for index in 0..count {
+            let (face, row, col) = position(size, index);
+            let digit = (index / place) % MARKER_BASE;
+            marker.set_sticker(face, row, col, MARKER_COLORS[digit]);
+        }
+        apply(&mut marker);
+        for (index, src) in source.iter_mut().enumerate() {
+            let (face, row, col) = position(size, index);
+            let color = marker.get_face(face).get(row, col);
+            let digit = MARKER_COLORS.iter().position(|c| *c == color).unwrap_or(0);
+            *src += digit * place;
+        }
+        place *= MARKER_BASE;
+    }
+    source
+}
+
+/// Convert a sticker index into (face, row, col), faces in facelet order
+pub(crate) fn position(size: usize, index: usize) -> (FaceName, usize, usize) {
+    let per_face = size * size;
+    let face = FACELET_ORDER[index / per_face];
+    let within = index % per_face;
+    (face, within / size, within % size)
+}
+
+/// Convert (face, row, col) into a sticker index
+pub(crate) fn index_of(size: usize, face: FaceName, row: usize, col: usize) -> usize {
+    let face_index = FACELET_ORDER.iter().position(|f| *f == face).unwrap_or(0);
+    face_index * size * size + row * size + col
+}
+
+/// A cube that also tracks the orientation of every sticker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuperCube {
+    cube: Cube,
+    /// Clockwise quarter turns (0-3) per sticker, indexed in facelet order
+    orientation: Vec<u8>,
+}
+
+impl SuperCube {
+    /// Create a solved supercube
+    pub fn new(size: usize) -> Self {
+        Self::from_cube(Cube::new(size))
+    }
+
+    /// Wrap an existing cube, treating every sticker as correctly oriented
+    pub fn from_cube(cube: Cube) -> Self {
+        let count = 6 * cube.size() * cube.size();
+        Self {
+            cube,
+            orientation: vec![0; count],
+        }
+    }
+
+    /// Get the underlying cube (colors only)
+    pub fn cube(&self) -> &Cube {
+        &self.cube
+    }
+
+    /// Get the cube size
+    pub fn size(&self) -> usize {
+        self.cube.size()
+    }
+
+    /// Check whether a position on a face is a center sticker
+    pub fn is_center(&self, row: usize, col: usize) -> bool {
+        let last = self.size() - 1;
+        row > 0 && row < last && col > 0 && col < last
+    }
+
+    /// Get a sticker's orientation in clockwise quarter turns (0-3)
+    pub fn orientation(&self, face: FaceName, row: usize, col: usize) -> u8 {
+        self.orientation[index_of(self.size(), face, row, col)]
+    }
+
+    /// Set a sticker's orientation (e.g. when entering a picture cube state)
+    pub fn set_orientation(&mut self, face: FaceName, row: usize, col: usize, turns: u8) {
+        let index = index_of(self.size(), face, row, col);
+        self.orientation[index] = turns % 4;
+    }
+
+    /// Get every twisted center sticker as (face, row, col, turns)
+    pub fn center_twists(&self) -> Vec<(FaceName, usize, usize, u8)> {
+        let size = self.size();
+        (0..self.orientation.len())
+            .filter_map(|index| {
+                let (face, row, col) = position(size, index);
+                let turns = self.orientation[index];
+                (self.is_center(row, col) && turns != 0).then_some((face, row, col, turns))
+            })
+            .collect()
+    }
+
+    /// Check whether every center sticker is in its solved orientation
+    pub fn centers_oriented(&self) -> bool {
+        self.center_twists().is_empty()
+    }
+
+    /// Check whether colors and center orientations are all solved
+    pub fn is_solved(&self) -> bool {
+        self.cube.is_solved() && self.centers_oriented()
+    }
+
+    /// Apply a move, updating colors and orientations
+    pub fn apply_move(&mut self, mv: Move) {
+        let table = MoveTable::trace(self.size(), |c| c.apply_move(mv));
+        self.cube.apply_move(mv);
+        self.apply_table(&table);
+    }
+
+    /// Apply a sequence of moves
+    pub fn apply_moves(&mut self, moves: &[Move]) {
+        for mv in moves {
+            self.apply_move(*mv);
+        }
+    }
+
+    /// Apply a wide move, updating colors and orientations
+    pub fn apply_wide_move(&mut self, wide_move: WideMove) {
+        let table = MoveTable::trace(self.size(), |c| c.apply_wide_move(wide_move));
+        self.cube.apply_wide_move(wide_move);
+        self.apply_table(&table);
+    }
+
+    fn apply_table(&mut self, table: &MoveTable) {
+        self.orientation = table
+            .source
+            .iter()
+            .zip(&table.twist)
+            .map(|(src, twist)| (self.orientation[*src] + twist) % 4)
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::{Direction, WideFace};
+
+    #[test]
+    fn test_trace_matches_simulator() {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::U]);
+        let table = MoveTable::trace(3, |c| c.apply_move(Move::F));
+
+        let mut expected = cube.clone();
+        expected.apply_move(Move::F);
+        for index in 0..54 {
+            let (face, row, col) = position(3, index);
+            let (sf, sr, sc) = position(3, table.source[index]);
+            assert_eq!(
+                expected.get_face(face).get(row, col),
+                cube.get_face(sf).get(sr, sc)
+            );
+        }
+    }
+
+    #[test]
+    fn test_face_turn_twists_center() {
+        let mut cube = SuperCube::new(3);
+        cube.apply_move(Move::F);
+        assert_eq!(cube.orientation(FaceName::F, 1, 1), 1);
+        cube.apply_move(Move::F2);
+        assert_eq!(cube.orientation(FaceName::F, 1, 1), 3);
+        cube.apply_move(Move::F);
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn test_four_turns_restore_orientation() {
+        let mut cube = SuperCube::new(4);
+        cube.apply_moves(&[Move::R, Move::R, Move::R, Move::R]);
+        assert!(cube.is_solved());
+        assert!(cube.centers_oriented());
+    }
+
+    #[test]
+    fn test_colors_solved_but_center_twisted() {
+        let mut cube = SuperCube::new(3);
+        cube.apply_moves(&[Move::R, Move::U, Move::RPrime, Move::UPrime]);
+        for _ in 0..5 {
+            cube.apply_moves(&[Move::R, Move::U, Move::RPrime, Move::UPrime]);
+        }
+        // Six sexy moves restore colors and centers
+        assert!(cube.is_solved());
+
+        let mut twisted = SuperCube::new(3);
+        twisted.set_orientation(FaceName::F, 1, 1, 2);
+        assert!(twisted.cube().is_solved());
+        assert!(!twisted.is_solved());
+        assert_eq!(twisted.center_twists(), vec![(FaceName::F, 1, 1, 2)]);
+    }
+
+    #[test]
+    fn test_inverse_restores_orientation() {
+        let moves = [Move::M, Move::E, Move::S, Move::X, Move::ZPrime, Move::R2];
+        let mut cube = SuperCube::new(5);
+        cube.apply_moves(&moves);
+        let inverse: Vec<Move> = moves.iter().rev().map(|m| m.inverse()).collect();
+        cube.apply_moves(&inverse);
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn test_wide_moves_move_center_orientation() {
+        let mut cube = SuperCube::new(4);
+        let rw = WideMove { face: WideFace::R, direction: Direction::Clockwise, depth: 2 };
+        for _ in 0..4 {
+            cube.apply_wide_move(rw);
+        }
+        assert!(cube.is_solved());
+
+        cube.apply_wide_move(rw);
+        assert!(!cube.centers_oriented());
+    }
+
+    #[test]
+    fn test_whole_cube_rotation_keeps_relative_orientation() {
+        let mut cube = SuperCube::new(3);
+        cube.apply_moves(&[Move::Y, Move::YPrime]);
+        assert!(cube.is_solved());
+    }
+}
@@ -0,0 +1,242 @@
+//! SVG export of the unfolded cube net
+//!
+//! Same layout as the ASCII net: U above L F R B, D below. Every sticker is
+//! a rounded square, so the image stays sharp at any print size. Sticker
+//! size, gaps and colors come from [`SvgOptions`]; the blank color scheme
+//! gives an empty net for coloring-in worksheets.
+
+use super::facelets::color_letter;
+use super::state::{Color, Cube, FaceName};
+
+/// Sticker fill for each cube color, as SVG colors
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorScheme {
+    pub white: String,
+    pub yellow: String,
+    pub red: String,
+    pub orange: String,
+    pub blue: String,
+    pub green: String,
+}
+
+impl ColorScheme {
+    /// The usual sticker colors
+    pub fn standard() -> Self {
+        Self::from_fills(["#FFFFFF", "#FFD500", "#C41E3A", "#FF5800", "#0051BA", "#009E60"])
+    }
+
+    /// Every sticker white, for printing a net to color in
+    pub fn blank() -> Self {
+        Self::from_fills(["#FFFFFF"; 6])
+    }
+
+    /// Fills in white, yellow, red, orange, blue, green order
+    fn from_fills(fills: [&str; 6]) -> Self {
+        let [white, yellow, red, orange, blue, green] = fills.map(str::to_string);
+        Self { white, yellow, red, orange, blue, green }
+    }
+
+    /// Fill for a sticker color
+    pub fn fill(&self, color: Color) -> &str {
+        match color {
+            Color::White => &self.white,
+            Color::Yellow => &self.yellow,
+            Color::Red => &self.red,
+            Color::Orange => &self.orange,
+            Color::Blue => &self.blue,
+            Color::Green => &self.green,
+        }
+    }
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// How [`Cube::to_svg`] draws the net
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    /// Sticker width and height
+    pub sticker_size: u32,
+    /// Space between stickers on a face
+    pub gap: u32,
+    /// Space between faces, and around the net
+    pub face_gap: u32,
+    /// Sticker colors
+    pub colors: ColorScheme,
+    /// Background fill, or `None` for transparent
+    pub background: Option<String>,
+    /// Write each sticker's color letter on it (for color-blind readers
+    /// and black-and-white printers)
+    pub letters: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            sticker_size: 30,
+            gap: 3,
+            face_gap: 10,
+            colors: ColorScheme::standard(),
+            background: Some("#1F1F1F".to_string()),
+            letters: false,
+        }
+    }
+}
+
+impl SvgOptions {
+    /// Default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draw stickers `size` units wide
+    pub fn with_sticker_size(mut self, size: u32) -> Self {
+        self.sticker_size = size;
+        self
+    }
+
+    /// Leave `gap` units between stickers and `face_gap` between faces
+    pub fn with_gaps(mut self, gap: u32, face_gap: u32) -> Self {
+        self.gap = gap;
+        self.face_gap = face_gap;
+        self
+    }
+
+    /// Fill stickers from `colors`
+    pub fn with_colors(mut self, colors: ColorScheme) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Fill the background, or leave it transparent with `None`
+    pub fn with_background(mut self, background: Option<&str>) -> Self {
+        self.background = background.map(str::to_string);
+        self
+    }
+
+    /// Write color letters on the stickers
+    pub fn with_letters(mut self, letters: bool) -> Self {
+        self.letters = letters;
+        self
+    }
+}
+
+/// Where each face sits in the net, as (column, row) in face units
+const NET_LAYOUT: [(FaceName, u32, u32); 6] = [
+    (FaceName::U, 1, 0),
+    (FaceName::L, 0, 1),
+    (FaceName::F, 1, 1),
+    (FaceName::R, 2, 1),
+    (FaceName::B, 3, 1),
+    (FaceName::D, 1, 2),
+];
+
+impl Cube {
+    /// Draw the unfolded net as an SVG image
+    ///
+    /// ```
+    /// use rubiks_cube_solver::cube::{Cube, SvgOptions};
+    ///
+    /// let svg = Cube::new(3).to_svg(&SvgOptions::new().with_sticker_size(20));
+    /// assert!(svg.starts_with("<svg"));
+    /// ```
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let n = self.size() as u32;
+        let cell = options.sticker_size + options.gap;
+        let face = n * cell - options.gap;
+        let width = 4 * face + 5 * options.face_gap;
+        let height = 3 * face + 4 * options.face_gap;
+        let radius = options.sticker_size / 8;
+
+        let mut svg = vec![format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height
+        )];
+        if let Some(background) = &options.background {
+            svg.push(format!(r#"<rect width="{}" height="{}" fill="{}"/>"#, width, height, background));
+        }
+
+        for (name, column, row) in NET_LAYOUT {
+            let left = options.face_gap + column * (face + options.face_gap);
+            let top = options.face_gap + row * (face + options.face_gap);
+            for (r, stickers) in self.get_face(name).stickers().iter().enumerate() {
+                for (c, color) in stickers.iter().enumerate() {
+                    let x = left + c as u32 * cell;
+                    let y = top + r as u32 * cell;
+                    svg.push(format!(
+                        r##"<rect x="{}" y="{}" width="{s}" height="{s}" rx="{}" fill="{}" stroke="#000000" stroke-width="1"/>"##,
+                        x,
+                        y,
+                        radius,
+                        options.colors.fill(*color),
+                        s = options.sticker_size
+                    ));
+                    if options.letters {
+                        svg.push(format!(
+                            r#"<text x="{}" y="{}" font-size="{}" font-family="sans-serif" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                            x + options.sticker_size / 2,
+                            y + options.sticker_size / 2,
+                            options.sticker_size / 2,
+                            color_letter(*color)
+                        ));
+                    }
+                }
+            }
+        }
+
+        svg.push("</svg>".to_string());
+        svg.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Move;
+
+    #[test]
+    fn test_svg_size_and_sticker_count() {
+        let options = SvgOptions::new().with_sticker_size(20).with_gaps(2, 8);
+        let svg = Cube::new(3).to_svg(&options);
+
+        // Each face is 3 * 22 - 2 = 64 wide: 4 faces and 5 gaps across, 3 and 4 down
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="296" height="224""#));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect").count(), 54 + 1);
+        assert!(!svg.contains("<text"));
+    }
+
+    #[test]
+    fn test_svg_uses_sticker_colors() {
+        let mut cube = Cube::new(2);
+        cube.apply_move(Move::R);
+        let options = SvgOptions::new();
+        let svg = cube.to_svg(&options);
+        for color in [Color::White, Color::Yellow, Color::Red, Color::Orange, Color::Blue, Color::Green] {
+            let fill = format!(r#"fill="{}""#, options.colors.fill(color));
+            assert_eq!(svg.matches(&fill).count(), 4, "{:?}", color);
+        }
+
+        // U is drawn first, so its top-left sticker comes first
+        let first_sticker = svg.lines().nth(2).unwrap();
+        let top_left = cube.get_face(FaceName::U).get(0, 0);
+        assert!(first_sticker.contains(options.colors.fill(top_left)));
+    }
+
+    #[test]
+    fn test_blank_worksheet() {
+        let options = SvgOptions::new()
+            .with_colors(ColorScheme::blank())
+            .with_background(None)
+            .with_letters(true);
+        let svg = Cube::new(4).to_svg(&options);
+        assert_eq!(svg.matches("<rect").count(), 96);
+        assert_eq!(svg.matches(r##"fill="#FFFFFF""##).count(), 96);
+        assert_eq!(svg.matches("<text").count(), 96);
+        assert!(svg.contains(">W</text>"));
+    }
+}
@@ -0,0 +1,282 @@
+//! Symmetry-aware comparison of cube states
+//!
+//! Two cubes that differ only by how they are held (a whole-cube rotation)
+//! are the same puzzle state. This module finds a canonical form across all
+//! 24 orientations, optionally also ignoring which colors are used
+//! (recoloring), for duplicate-state detection in search and pattern matching.
+//!
+//! Scanned cubes are rarely held in the standard orientation, so there are
+//! also an orientation-insensitive solved check and a recoloring that
+//! renames colors to match the standard face colors.
+
+use super::facelets::FACELET_ORDER;
+use super::moves::Move;
+use super::state::{Color, Cube, FaceName};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Rotations that bring each face to the top
+pub(crate) const UP_ROTATIONS: [&[Move]; 6] = [
+    &[],
+    &[Move::X],
+    &[Move::X2],
+    &[Move::XPrime],
+    &[Move::Z],
+    &[Move::ZPrime],
+];
+
+/// Color index used by canonical keys
+fn color_index(color: Color) -> u8 {
+    match color {
+        Color::White => 0,
+        Color::Yellow => 1,
+        Color::Red => 2,
+        Color::Orange => 3,
+        Color::Blue => 4,
+        Color::Green => 5,
+    }
+}
+
+impl Cube {
+    /// Get the cube in all 24 orientations (the first is the cube as-is)
+    pub fn orientations(&self) -> Vec<Cube> {
+        let mut result = Vec::with_capacity(24);
+        for up in UP_ROTATIONS {
+            let mut cube = self.clone();
+            cube.apply_moves(up);
+            for _ in 0..4 {
+                result.push(cube.clone());
+                cube.apply_move(Move::Y);
+            }
+        }
+        result
+    }
+
+    /// Flatten stickers into a key, face by face in facelet order
+    fn sticker_key(&self, recolor: bool) -> Vec<u8> {
+        let mut key = Vec::with_capacity(6 * self.size() * self.size());
+        // With recoloring, colors are numbered in order of first appearance
+        let mut relabel: [Option<u8>; 6] = [None; 6];
+        let mut next_label = 0;
+
+        for face in FACELET_ORDER {
+            for row in self.get_face(face).stickers() {
+                for color in row {
+                    let index = color_index(*color);
+                    if recolor {
+                        let label = *relabel[index as usize].get_or_insert_with(|| {
+                            next_label += 1;
+                            next_label - 1
+                        });
+                        key.push(label);
+                    } else {
+                        key.push(index);
+                    }
+                }
+            }
+        }
+        key
+    }
+
+    /// Get the smallest sticker key across all orientations
+    fn canonical_key(&self, recolor: bool) -> Vec<u8> {
+        self.orientations()
+            .iter()
+            .map(|cube| cube.sticker_key(recolor))
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// Hash that is the same for every orientation of the same state
+    pub fn canonical_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.size().hash(&mut hasher);
+        self.canonical_key(false).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Hash that ignores both orientation and which colors are used
+    pub fn canonical_hash_recolored(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.size().hash(&mut hasher);
+        self.canonical_key(true).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Check whether two cubes are the same state held differently
+    pub fn equals_up_to_rotation(&self, other: &Cube) -> bool {
+        self.size() == other.size() && self.canonical_key(false) == other.canonical_key(false)
+    }
+
+    /// Check whether two cubes are the same pattern, ignoring orientation and colors
+    pub fn equals_up_to_recoloring(&self, other: &Cube) -> bool {
+        self.size() == other.size() && self.canonical_key(true) == other.canonical_key(true)
+    }
+
+    /// Check whether the cube is solved however it is held
+    pub fn is_solved_any_orientation(&self) -> bool {
+        self.orientations().iter().any(Cube::is_solved)
+    }
+
+    /// Rename colors so the cube reads as if held in the standard orientation
+    ///
+    /// Odd cubes use their centers: the color centered on each face becomes
+    /// that face's standard color. Even cubes use the down-back-left corner
+    /// and the standard opposite colors. A solved cube held any way comes
+    /// out solved.
+    ///
+    /// Returns an error if the reference stickers repeat a color.
+    pub fn recolor_to_standard(&self) -> Result<Cube, String> {
+        let n = self.size();
+        let references: Vec<(Color, Color)> = if n % 2 == 1 {
+            FaceName::all()
+                .iter()
+                .map(|face| (self.get_face(*face).get(n / 2, n / 2), face.standard_color()))
+                .collect()
+        } else {
+            [(FaceName::D, n - 1, 0), (FaceName::B, n - 1, n - 1), (FaceName::L, n - 1, 0)]
+                .iter()
+                .flat_map(|(face, row, col)| {
+                    let color = self.get_face(*face).get(*row, *col);
+                    let standard = face.standard_color();
+                    [(color, standard), (color.opposite(), standard.opposite())]
+                })
+                .collect()
+        };
+
+        let mut rename: [Option<Color>; 6] = [None; 6];
+        for (from, to) in references {
+            let slot = &mut rename[color_index(from) as usize];
+            if slot.is_some() {
+                return Err(format!("The reference stickers use {:?} more than once", from));
+            }
+            *slot = Some(to);
+        }
+
+        let mut recolored = self.clone();
+        for face in FaceName::all() {
+            for row in 0..n {
+                for col in 0..n {
+                    if let Some(color) = rename[color_index(self.get_face(face).get(row, col)) as usize] {
+                        recolored.set_sticker(face, row, col, color);
+                    }
+                }
+            }
+        }
+        Ok(recolored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn scrambled(size: usize) -> Cube {
+        let mut cube = Cube::new(size);
+        cube.apply_moves(&[Move::R, Move::U, Move::F2, Move::LPrime, Move::D]);
+        cube
+    }
+
+    #[test]
+    fn test_orientations_are_distinct() {
+        for size in [2, 3, 4] {
+            let keys: HashSet<Vec<u8>> = scrambled(size)
+                .orientations()
+                .iter()
+                .map(|c| c.sticker_key(false))
+                .collect();
+            assert_eq!(keys.len(), 24);
+        }
+    }
+
+    #[test]
+    fn test_rotated_cube_is_equal() {
+        let cube = scrambled(3);
+        for rotation in [Move::X, Move::Y, Move::Z, Move::XPrime, Move::Z2] {
+            let mut rotated = cube.clone();
+            rotated.apply_move(rotation);
+            assert_ne!(rotated, cube);
+            assert!(cube.equals_up_to_rotation(&rotated));
+            assert_eq!(cube.canonical_hash(), rotated.canonical_hash());
+        }
+    }
+
+    #[test]
+    fn test_different_states_are_not_equal() {
+        let mut other = scrambled(3);
+        other.apply_move(Move::R);
+        assert!(!scrambled(3).equals_up_to_rotation(&other));
+        assert_ne!(scrambled(3).canonical_hash(), other.canonical_hash());
+        assert!(!Cube::new(2).equals_up_to_rotation(&Cube::new(3)));
+    }
+
+    #[test]
+    fn test_recoloring() {
+        let cube = scrambled(3);
+        let mut recolored = cube.clone();
+        for face in FACELET_ORDER {
+            let f = recolored.get_face_mut(face);
+            for row in 0..3 {
+                for col in 0..3 {
+                    let swapped = match f.get(row, col) {
+                        Color::Red => Color::Blue,
+                        Color::Blue => Color::Red,
+                        other => other,
+                    };
+                    f.set(row, col, swapped);
+                }
+            }
+        }
+
+        assert!(!cube.equals_up_to_rotation(&recolored));
+        assert!(cube.equals_up_to_recoloring(&recolored));
+        assert_eq!(cube.canonical_hash_recolored(), recolored.canonical_hash_recolored());
+    }
+
+    #[test]
+    fn test_solved_cubes_share_hash() {
+        let mut rotated = Cube::new(3);
+        rotated.apply_move(Move::X);
+        assert_eq!(Cube::new(3).canonical_hash(), rotated.canonical_hash());
+    }
+
+    #[test]
+    fn test_solved_in_any_orientation() {
+        for size in [2, 3, 4, 5] {
+            for rotation in [Move::X, Move::Y2, Move::ZPrime] {
+                let mut cube = Cube::new(size);
+                cube.apply_move(rotation);
+                assert!(!cube.is_solved());
+                assert!(cube.is_solved_any_orientation());
+            }
+            assert!(!scrambled(size).is_solved_any_orientation());
+        }
+    }
+
+    #[test]
+    fn test_recolor_rotated_cubes_to_standard() {
+        // R, U and F turns leave the centers and the down-back-left corner alone
+        let scramble = [Move::R, Move::U, Move::F2, Move::RPrime, Move::UPrime];
+        for size in [2, 3, 4, 5] {
+            for rotation in [Move::X, Move::Y2, Move::ZPrime] {
+                let mut held = Cube::new(size);
+                held.apply_move(rotation);
+                assert!(held.recolor_to_standard().unwrap().is_solved(), "{}x{} {:?}", size, size, rotation);
+
+                // Scrambling a cube held rotated reads as the same scramble
+                held.apply_moves(&scramble);
+                let mut expected = Cube::new(size);
+                expected.apply_moves(&scramble);
+                assert_eq!(held.recolor_to_standard().unwrap(), expected, "{}x{} {:?}", size, size, rotation);
+            }
+        }
+    }
+
+    #[test]
+    fn test_recolor_rejects_repeated_reference_colors() {
+        let mut cube = Cube::new(3);
+        cube.set_sticker(FaceName::U, 1, 1, Color::Red);
+        assert!(cube.recolor_to_standard().is_err());
+    }
+}
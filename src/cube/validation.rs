@@ -6,8 +6,16 @@
 //! - Checks corner parity
 //! - Checks permutation parity
 //! - Returns detailed error for invalid states
+//! - Assembles the pieces and rejects impossible ones: the same color twice,
+//!   opposite colors (a white-yellow edge), duplicates and mirrored corners
+//! - On odd cubes, checks corner twist, edge flip and (3x3) permutation
+//!   parity from the assembled pieces
+//! - Suggests fixes that point at the stickers most likely to be wrong
+//!   ("one extra Red on F, one missing Orange — check F(2,0)")
 
-use super::state::{Color, Cube};
+use super::state::{Color, Cube, FaceName};
+use crate::solver::parity::{center, flipped_edges, is_odd, permutation, CORNER_SLOTS, EDGE_SLOTS};
+use std::collections::BTreeMap;
 
 /// Validation error types
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,6 +34,14 @@ pub enum ValidationError {
     CornerParity,
     /// Permutation parity is incorrect (can't have just two pieces swapped)
     PermutationParity,
+    /// A corner or edge has the same color twice
+    ImpossiblePiece { colors: Vec<Color> },
+    /// A corner or edge has two opposite colors, like a white-yellow edge
+    OppositeColors { colors: Vec<Color> },
+    /// The same piece appears twice (so another one is missing)
+    DuplicatePiece { colors: Vec<Color> },
+    /// A corner's colors go around it the wrong way, like its mirror image
+    MirroredCorner { colors: Vec<Color> },
 }
 
 impl std::fmt::Display for ValidationError {
@@ -52,6 +68,18 @@ impl std::fmt::Display for ValidationError {
             ValidationError::PermutationParity => {
                 write!(f, "Permutation parity error: cube has an odd permutation")
             }
+            ValidationError::ImpossiblePiece { colors } => {
+                write!(f, "Impossible piece: no {} exists", piece_name(colors))
+            }
+            ValidationError::OppositeColors { colors } => {
+                write!(f, "Impossible piece: the {} has opposite colors", piece_name(colors))
+            }
+            ValidationError::DuplicatePiece { colors } => {
+                write!(f, "Duplicate piece: there are two {}s", piece_name(colors))
+            }
+            ValidationError::MirroredCorner { colors } => {
+                write!(f, "Mirrored corner: the {}'s colors are in the wrong order", piece_name(colors))
+            }
         }
     }
 }
@@ -61,6 +89,98 @@ impl std::error::Error for ValidationError {}
 /// Result type for validation
 pub type ValidationResult = Result<(), ValidationError>;
 
+/// A sticker as (face, row, col)
+pub type StickerRef = (FaceName, usize, usize);
+
+/// A change that would help make an invalid cube valid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixSuggestion {
+    /// What looks wrong and where to look
+    pub message: String,
+    /// Stickers most likely to be wrong, most likely first, for the UI to
+    /// highlight
+    pub stickers: Vec<StickerRef>,
+    /// Color the stickers probably should be, if known
+    pub replacement: Option<Color>,
+}
+
+/// Name of a sticker as used in suggestions, e.g. `F(2,0)`
+pub fn sticker_name((face, row, col): StickerRef) -> String {
+    format!("{:?}({},{})", face, row, col)
+}
+
+/// Name of a piece from its colors, e.g. `White-Red edge`
+fn piece_name(colors: &[Color]) -> String {
+    let names: Vec<String> = colors.iter().map(|c| format!("{:?}", c)).collect();
+    let kind = if colors.len() == 3 { "corner" } else { "edge" };
+    format!("{} {}", names.join("-"), kind)
+}
+
+/// Why no real piece has these colors, if none does
+fn piece_problem(colors: &[Color]) -> Option<String> {
+    for (i, a) in colors.iter().enumerate() {
+        for b in &colors[i + 1..] {
+            if a == b {
+                return Some(format!("it has two {:?} stickers", a));
+            }
+            if a.opposite() == *b {
+                return Some(format!("{:?} and {:?} are on opposite sides", a, b));
+            }
+        }
+    }
+    None
+}
+
+/// The error for a piece no real cube has, if it is one
+fn piece_error(colors: &[Color]) -> Option<ValidationError> {
+    let repeated = colors.iter().enumerate().any(|(i, a)| colors[i + 1..].contains(a));
+    let opposite = colors.iter().any(|a| colors.contains(&a.opposite()));
+    let colors = colors.to_vec();
+    if repeated {
+        Some(ValidationError::ImpossiblePiece { colors })
+    } else if opposite {
+        Some(ValidationError::OppositeColors { colors })
+    } else {
+        None
+    }
+}
+
+/// Colors sorted, to compare pieces regardless of orientation
+fn sorted(colors: &[Color]) -> Vec<Color> {
+    let mut colors = colors.to_vec();
+    colors.sort();
+    colors
+}
+
+/// The stickers of each corner (in [`CORNER_SLOTS`] order, clockwise around
+/// the corner starting on U or D), and on odd cubes of each middle edge
+fn pieces(size: usize) -> Vec<Vec<StickerRef>> {
+    let index = |i: usize| match i {
+        0 => 0,
+        1 => size / 2,
+        _ => size - 1,
+    };
+    let place = |&(face, row, col): &StickerRef| (face, index(row), index(col));
+    let mut pieces: Vec<Vec<StickerRef>> = CORNER_SLOTS.iter().map(|slot| slot.iter().map(place).collect()).collect();
+    if size % 2 == 1 {
+        pieces.extend(EDGE_SLOTS.iter().map(|slot| slot.iter().map(place).collect()));
+    }
+    pieces
+}
+
+/// A count written out for messages
+fn count_word(count: usize) -> String {
+    match count {
+        1 => "one".to_string(),
+        2 => "two".to_string(),
+        3 => "three".to_string(),
+        _ => count.to_string(),
+    }
+}
+
+/// Colors in the order suggestions list them
+const ALL_COLORS: [Color; 6] = [Color::White, Color::Yellow, Color::Red, Color::Orange, Color::Blue, Color::Green];
+
 impl Cube {
     /// Validates that the cube is in a solvable state
     ///
@@ -69,22 +189,15 @@ impl Cube {
     /// - `Err(ValidationError)` with details about why the cube is invalid
     ///
     /// # Note
-    /// This performs basic validation. For 3x3 cubes, it checks color counts.
-    /// Full parity validation is complex and requires tracking piece identities,
-    /// which is not implemented in this basic version.
+    /// Checks color counts, then assembles the corners (and the middle edges
+    /// of odd cubes) into pieces and checks each is real and appears once.
+    /// On odd cubes the centers fix where pieces belong, so corner twist,
+    /// edge flip and (on a 3x3) permutation parity are checked too. Wing
+    /// edges and the inner centers of 4x4+ cubes are not checked.
     pub fn validate(&self) -> ValidationResult {
         // Check color counts (works for all sizes)
         self.validate_color_counts()?;
-
-        // TODO: Implement full parity checking for 3x3 cubes
-        // This would require:
-        // 1. Tracking piece identities (which piece is in which position)
-        // 2. Calculating edge orientation parity
-        // 3. Calculating corner orientation parity
-        // 4. Calculating permutation parity
-        //
-        // For now, we only validate color counts, which catches many invalid states.
-        // Any cube created through legal moves will pass this validation.
+        self.validate_pieces()?;
 
         Ok(())
     }
@@ -128,6 +241,247 @@ impl Cube {
 
         Ok(())
     }
+
+    /// Checks that every corner, and every middle edge of an odd cube, is a
+    /// real piece that appears once, then (on odd cubes, whose centers show
+    /// where each piece belongs) that the pieces' orientations and positions
+    /// are reachable by turning
+    fn validate_pieces(&self) -> ValidationResult {
+        let size = self.size();
+        let pieces = pieces(size);
+        let mut seen = Vec::new();
+        for piece in &pieces {
+            let colors = self.piece_colors(piece);
+            if let Some(error) = piece_error(&colors) {
+                return Err(error);
+            }
+            let key = sorted(&colors);
+            if seen.contains(&key) {
+                return Err(ValidationError::DuplicatePiece { colors });
+            }
+            seen.push(key);
+        }
+        if size % 2 == 1 {
+            self.validate_orientation(&pieces[..CORNER_SLOTS.len()])?;
+        }
+        Ok(())
+    }
+
+    /// Checks corner twist, middle edge flip and (on a 3x3) permutation
+    /// parity of an odd cube whose pieces are all real
+    fn validate_orientation(&self, corners: &[Vec<StickerRef>]) -> ValidationResult {
+        let size = self.size();
+
+        // Each corner's U/D color, counted clockwise from the U/D sticker,
+        // gives its twist; the other two colors must follow in the same
+        // order as around the corner's home
+        let up_down = [center(self, FaceName::U), center(self, FaceName::D)];
+        let homes: Vec<Vec<Color>> =
+            CORNER_SLOTS.iter().map(|slot| slot.iter().map(|(face, _, _)| center(self, *face)).collect()).collect();
+        let mut twist = 0;
+        for piece in corners {
+            let colors = self.piece_colors(piece);
+            let Some(turn) = colors.iter().position(|c| up_down.contains(c)) else {
+                continue;
+            };
+            let Some(home) = homes.iter().find(|home| sorted(home) == sorted(&colors)) else {
+                continue;
+            };
+            let from_up_down: Vec<Color> = (0..3).map(|i| colors[(turn + i) % 3]).collect();
+            if from_up_down != *home {
+                return Err(ValidationError::MirroredCorner { colors });
+            }
+            twist += turn;
+        }
+        if twist % 3 != 0 {
+            return Err(ValidationError::CornerParity);
+        }
+        if flipped_edges(self, size / 2) % 2 == 1 {
+            return Err(ValidationError::EdgeParity);
+        }
+        // Only the 3x3 has no inner slices to change the permutation parity
+        if size == 3 {
+            if let (Some(edges), Some(corners)) =
+                (permutation(self, &EDGE_SLOTS, 1), permutation(self, &CORNER_SLOTS, 1))
+            {
+                if is_odd(&edges) != is_odd(&corners) {
+                    return Err(ValidationError::PermutationParity);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn sticker_color(&self, (face, row, col): StickerRef) -> Color {
+        self.get_face(face).get(row, col)
+    }
+
+    fn piece_colors(&self, piece: &[StickerRef]) -> Vec<Color> {
+        piece.iter().map(|s| self.sticker_color(*s)).collect()
+    }
+
+    /// Suggest how to fix an invalid cube
+    ///
+    /// Wrong color counts are paired up into suggestions like "one extra Red
+    /// on F, one missing Orange — check F(2,0)", pointing at the stickers of
+    /// the extra color whose pieces are impossible now but would be fine in
+    /// the missing color. Impossible and duplicated pieces not explained by
+    /// those come after. A valid cube gets no suggestions.
+    pub fn fix_suggestions(&self) -> Vec<FixSuggestion> {
+        let size = self.size();
+        let expected = size * size;
+        let counts = self.count_colors();
+        let count = |color: &Color| counts.get(color).copied().unwrap_or(0);
+
+        // Pair each extra sticker with a missing one
+        let extra = ALL_COLORS.iter().flat_map(|c| std::iter::repeat_n(*c, count(c).saturating_sub(expected)));
+        let missing = ALL_COLORS.iter().flat_map(|c| std::iter::repeat_n(*c, expected.saturating_sub(count(c))));
+        let mut swaps: BTreeMap<(Color, Color), usize> = BTreeMap::new();
+        for pair in extra.zip(missing) {
+            *swaps.entry(pair).or_default() += 1;
+        }
+
+        let pieces = pieces(size);
+        let mut suggestions = Vec::new();
+        let mut flagged: Vec<StickerRef> = Vec::new();
+        for ((extra, missing), amount) in swaps {
+            let stickers = self.suspect_stickers(&pieces, extra, missing, amount);
+            let names: Vec<String> = stickers.iter().map(|s| sticker_name(*s)).collect();
+            let on_face = match stickers.first() {
+                Some(first) if stickers.iter().all(|s| s.0 == first.0) => format!(" on {:?}", first.0),
+                _ => String::new(),
+            };
+            let amount = count_word(amount);
+            let mut message = format!(
+                "{} extra {:?}{}, {} missing {:?} — check {}",
+                amount,
+                extra,
+                on_face,
+                amount,
+                missing,
+                names.join(", ")
+            );
+            message[..1].make_ascii_uppercase();
+            flagged.extend(&stickers);
+            suggestions.push(FixSuggestion { message, stickers, replacement: Some(missing) });
+        }
+
+        let mut seen: BTreeMap<Vec<Color>, &Vec<StickerRef>> = BTreeMap::new();
+        for piece in &pieces {
+            if piece.iter().any(|s| flagged.contains(s)) {
+                continue;
+            }
+            let colors = self.piece_colors(piece);
+            let names: Vec<String> = piece.iter().map(|s| sticker_name(*s)).collect();
+            if let Some(problem) = piece_problem(&colors) {
+                suggestions.push(FixSuggestion {
+                    message: format!("Impossible {}: {} — check {}", piece_name(&colors), problem, names.join(", ")),
+                    stickers: piece.clone(),
+                    replacement: None,
+                });
+                continue;
+            }
+            let mut key = colors.clone();
+            key.sort();
+            if let Some(first) = seen.get(&key) {
+                let mut stickers = (*first).clone();
+                stickers.extend(piece);
+                let first_names: Vec<String> = first.iter().map(|s| sticker_name(*s)).collect();
+                suggestions.push(FixSuggestion {
+                    message: format!(
+                        "Two {}s, but there is only one — check {} and {}",
+                        piece_name(&colors),
+                        first_names.join(", "),
+                        names.join(", ")
+                    ),
+                    stickers,
+                    replacement: None,
+                });
+            } else {
+                seen.insert(key, piece);
+            }
+        }
+        if suggestions.is_empty() {
+            suggestions.extend(self.orientation_suggestion(&pieces));
+        }
+        suggestions
+    }
+
+    /// Suggestion for pieces that are all real but can't be reached by
+    /// turning: a mirrored corner, or a twisted corner, flipped edge or
+    /// swapped pair of pieces
+    fn orientation_suggestion(&self, pieces: &[Vec<StickerRef>]) -> Option<FixSuggestion> {
+        let (message, stickers) = match self.validate_pieces().err()? {
+            ValidationError::MirroredCorner { colors } => {
+                let piece = pieces.iter().find(|piece| self.piece_colors(piece) == colors)?;
+                let names: Vec<String> = piece.iter().map(|s| sticker_name(*s)).collect();
+                let message = format!(
+                    "The {} is mirrored — two of its stickers are swapped, check {}",
+                    piece_name(&colors),
+                    names.join(", ")
+                );
+                (message, piece.clone())
+            }
+            ValidationError::CornerParity => {
+                ("One corner is twisted — turn the stickers of one corner around it".to_string(), Vec::new())
+            }
+            ValidationError::EdgeParity => {
+                ("One edge is flipped — swap the two stickers of one edge".to_string(), Vec::new())
+            }
+            ValidationError::PermutationParity => {
+                ("Two pieces are swapped — swap the stickers of two edges or two corners".to_string(), Vec::new())
+            }
+            _ => return None,
+        };
+        Some(FixSuggestion { message, stickers, replacement: None })
+    }
+
+    /// The `amount` stickers of color `extra` most likely meant to be
+    /// `missing`: ones on impossible pieces that recoloring would fix come
+    /// first. Centers of odd cubes are fixed and never suspected.
+    fn suspect_stickers(&self, pieces: &[Vec<StickerRef>], extra: Color, missing: Color, amount: usize) -> Vec<StickerRef> {
+        let size = self.size();
+        let present: Vec<Vec<Color>> = pieces
+            .iter()
+            .map(|piece| {
+                let mut colors = self.piece_colors(piece);
+                colors.sort();
+                colors
+            })
+            .collect();
+
+        let mut suspects: Vec<(u8, StickerRef)> = Vec::new();
+        for face in FaceName::all() {
+            for row in 0..size {
+                for col in 0..size {
+                    let sticker = (face, row, col);
+                    let fixed_center = size % 2 == 1 && row == size / 2 && col == size / 2;
+                    if fixed_center || self.sticker_color(sticker) != extra {
+                        continue;
+                    }
+                    let mut score = 0;
+                    if let Some(piece) = pieces.iter().find(|piece| piece.contains(&sticker)) {
+                        let colors = self.piece_colors(piece);
+                        let mut fixed: Vec<Color> =
+                            piece.iter().zip(&colors).map(|(s, c)| if *s == sticker { missing } else { *c }).collect();
+                        if piece_problem(&colors).is_some() {
+                            score += 2;
+                        }
+                        if piece_problem(&fixed).is_none() {
+                            score += 2;
+                            fixed.sort();
+                            if !present.contains(&fixed) {
+                                score += 1;
+                            }
+                        }
+                    }
+                    suspects.push((score, sticker));
+                }
+            }
+        }
+        suspects.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        suspects.into_iter().take(amount).map(|(_, sticker)| sticker).collect()
+    }
 }
 
 #[cfg(test)]
@@ -185,6 +539,132 @@ mod tests {
         assert!(cube.validate().is_ok());
     }
 
+    #[test]
+    fn test_impossible_piece_is_rejected() {
+        let mut cube = Cube::new(3);
+        // Swap the green sticker of the U-F edge with the yellow one of the
+        // D-F edge: counts stay right, but the edges are white-yellow and
+        // green-green
+        cube.set_sticker(FaceName::F, 0, 1, Color::Yellow);
+        cube.set_sticker(FaceName::D, 0, 1, Color::Green);
+        assert!(cube.has_valid_color_counts());
+        assert_eq!(
+            cube.validate(),
+            Err(ValidationError::OppositeColors { colors: vec![Color::White, Color::Yellow] })
+        );
+
+        // Swap the two stickers of one edge with one of another: green-green
+        let mut cube = Cube::new(3);
+        cube.set_sticker(FaceName::U, 2, 1, Color::Green);
+        cube.set_sticker(FaceName::R, 1, 0, Color::White);
+        cube.set_sticker(FaceName::F, 1, 2, Color::Red);
+        assert!(matches!(cube.validate(), Err(ValidationError::ImpossiblePiece { .. })));
+    }
+
+    #[test]
+    fn test_duplicate_piece_is_rejected() {
+        // The U-B edge painted like the U-F edge, and the D-F edge like the
+        // D-B edge: counts stay right
+        let mut cube = Cube::new(3);
+        cube.set_sticker(FaceName::B, 0, 1, Color::Green);
+        cube.set_sticker(FaceName::F, 2, 1, Color::Blue);
+        assert!(cube.has_valid_color_counts());
+        assert_eq!(
+            cube.validate(),
+            Err(ValidationError::DuplicatePiece { colors: vec![Color::White, Color::Green] })
+        );
+    }
+
+    /// Swap the colors of two stickers
+    fn swap(cube: &mut Cube, a: StickerRef, b: StickerRef) {
+        let (color_a, color_b) = (cube.sticker_color(a), cube.sticker_color(b));
+        cube.set_sticker(a.0, a.1, a.2, color_b);
+        cube.set_sticker(b.0, b.1, b.2, color_a);
+    }
+
+    #[test]
+    fn test_twisted_flipped_and_swapped_pieces() {
+        // Twist the U-F-R corner in place
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::F);
+        let (u, r, f) = ((FaceName::U, 2, 2), (FaceName::R, 0, 0), (FaceName::F, 0, 2));
+        let colors = [cube.sticker_color(u), cube.sticker_color(r), cube.sticker_color(f)];
+        cube.set_sticker(u.0, u.1, u.2, colors[2]);
+        cube.set_sticker(r.0, r.1, r.2, colors[0]);
+        cube.set_sticker(f.0, f.1, f.2, colors[1]);
+        assert_eq!(cube.validate(), Err(ValidationError::CornerParity));
+        assert!(cube.fix_suggestions()[0].message.starts_with("One corner is twisted"));
+
+        // Mirror it instead, by swapping two of its stickers
+        let mut cube = Cube::new(3);
+        swap(&mut cube, r, f);
+        assert!(matches!(cube.validate(), Err(ValidationError::MirroredCorner { .. })));
+        assert_eq!(cube.fix_suggestions()[0].stickers, vec![u, r, f]);
+
+        // Flip one edge
+        let mut cube = Cube::new(3);
+        swap(&mut cube, (FaceName::U, 2, 1), (FaceName::F, 0, 1));
+        assert_eq!(cube.validate(), Err(ValidationError::EdgeParity));
+
+        // Swap two edges: U-F with U-R
+        let mut cube = Cube::new(3);
+        swap(&mut cube, (FaceName::F, 0, 1), (FaceName::R, 0, 1));
+        assert_eq!(cube.validate(), Err(ValidationError::PermutationParity));
+
+        // A 5x5 with a flipped middle edge
+        let mut cube = Cube::new(5);
+        swap(&mut cube, (FaceName::U, 4, 2), (FaceName::F, 0, 2));
+        assert_eq!(cube.validate(), Err(ValidationError::EdgeParity));
+    }
+
+    #[test]
+    fn test_scrambled_cubes_pass_piece_checks() {
+        for size in 2..=5 {
+            let mut cube = Cube::new(size);
+            for mv in [Move::R, Move::U, Move::FPrime, Move::L, Move::D2, Move::B] {
+                cube.apply_move(mv);
+            }
+            assert_eq!(cube.validate(), Ok(()), "{}x{}", size, size);
+        }
+    }
+
+    #[test]
+    fn test_suggests_the_wrong_sticker() {
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::R);
+        cube.apply_move(Move::U);
+        // A scan read the orange sticker of the D-F-L corner as red
+        assert_eq!(cube.get_face(FaceName::L).get(2, 2), Color::Orange);
+        cube.set_sticker(FaceName::L, 2, 2, Color::Red);
+
+        let suggestions = cube.fix_suggestions();
+        assert_eq!(suggestions.len(), 1, "{:?}", suggestions);
+        assert_eq!(suggestions[0].stickers, vec![(FaceName::L, 2, 2)]);
+        assert_eq!(suggestions[0].replacement, Some(Color::Orange));
+        assert_eq!(suggestions[0].message, "One extra Red on L, one missing Orange — check L(2,2)");
+    }
+
+    #[test]
+    fn test_suggestions_for_pieces() {
+        assert!(Cube::new(3).fix_suggestions().is_empty());
+
+        let mut cube = Cube::new(3);
+        cube.set_sticker(FaceName::B, 0, 1, Color::Green);
+        let suggestions = cube.fix_suggestions();
+        assert!(suggestions[0].message.starts_with("One extra Green"), "{}", suggestions[0].message);
+        assert_eq!(suggestions[0].replacement, Some(Color::Blue));
+
+        // Orange of the U-L-B corner swapped with yellow of the D-B-L corner
+        let mut cube = Cube::new(2);
+        cube.set_sticker(FaceName::L, 0, 0, Color::Yellow);
+        cube.set_sticker(FaceName::D, 1, 0, Color::Orange);
+        let suggestions = cube.fix_suggestions();
+        assert_eq!(suggestions.len(), 2, "{:?}", suggestions);
+        assert!(suggestions.iter().all(|s| s.message.starts_with("Impossible")));
+        assert!(suggestions[0].message.contains("White and Yellow are on opposite sides"), "{}", suggestions[0].message);
+        assert!(suggestions[1].message.contains("two Orange stickers"), "{}", suggestions[1].message);
+    }
+
     #[test]
     fn test_validation_error_display() {
         let err = ValidationError::InvalidColorCount {
@@ -211,5 +691,14 @@ mod tests {
             err.to_string(),
             "Permutation parity error: cube has an odd permutation"
         );
+
+        let err = ValidationError::ImpossiblePiece { colors: vec![Color::White, Color::White] };
+        assert_eq!(err.to_string(), "Impossible piece: no White-White edge exists");
+
+        let err = ValidationError::OppositeColors { colors: vec![Color::White, Color::Yellow] };
+        assert_eq!(err.to_string(), "Impossible piece: the White-Yellow edge has opposite colors");
+
+        let err = ValidationError::DuplicatePiece { colors: vec![Color::White, Color::Red, Color::Green] };
+        assert_eq!(err.to_string(), "Duplicate piece: there are two White-Red-Green corners");
     }
 }
@@ -0,0 +1,401 @@
+//! Foreign function interface (enabled with the `ffi` feature)
+//!
+//! A small, stable API so other apps can reuse the cube engine without the UI:
+//! - create a cube
+//! - apply an algorithm string
+//! - read the state as a facelet string
+//! - solve
+//!
+//! Native targets get a C ABI (`cube_*` functions, build with
+//! `cargo rustc --lib --features ffi --crate-type cdylib`). WASM targets get a
+//! `WasmCube` class exported through wasm-bindgen for JS frontends.
+//!
+//! Facelet strings use the format from `cube::facelets`.
+
+use crate::cube::Cube;
+use crate::solver::{solve_2x2, solve_3x3};
+
+/// Version of the FFI surface, bumped on any breaking change
+pub const FFI_API_VERSION: u32 = 1;
+
+/// Cube handle shared by the C and WASM bindings
+#[derive(Debug, Clone, PartialEq)]
+pub struct FfiCube {
+    cube: Cube,
+}
+
+impl FfiCube {
+    /// Create a solved cube, or an error if the size is outside 2-20
+    pub fn new(size: usize) -> Result<Self, String> {
+        if !(2..=20).contains(&size) {
+            return Err(format!("Cube size must be between 2 and 20, got {}", size));
+        }
+        Ok(Self { cube: Cube::new(size) })
+    }
+
+    /// Create a cube from a facelet string
+    pub fn from_facelets(facelets: &str) -> Result<Self, String> {
+        Cube::from_facelets(facelets)
+            .map(|cube| Self { cube })
+            .map_err(|e| e.to_string())
+    }
+
+    /// Get the wrapped cube
+    pub fn cube(&self) -> &Cube {
+        &self.cube
+    }
+
+    /// Get the cube size
+    pub fn size(&self) -> usize {
+        self.cube.size()
+    }
+
+    /// Apply an algorithm such as "R U R' U'"
+    ///
+    /// The whole algorithm is validated before any move is applied, so the
+    /// cube is unchanged on error.
+    pub fn apply_algorithm(&mut self, alg: &str) -> Result<(), String> {
+        self.cube.apply_algorithm(alg).map_err(|e| e.to_string())
+    }
+
+    /// Get the state as a facelet string
+    pub fn facelets(&self) -> String {
+        self.cube.to_facelets()
+    }
+
+    /// Check whether the cube is solved
+    pub fn is_solved(&self) -> bool {
+        self.cube.is_solved()
+    }
+
+    /// Solve the cube, returning the solution in standard notation
+    pub fn solve(&self) -> Result<String, String> {
+        if self.cube.is_solved() {
+            return Ok(String::new());
+        }
+
+        let solution = match self.cube.size() {
+            2 => solve_2x2(&self.cube)?.to_solution(),
+            3 => solve_3x3(&self.cube)?.to_solution(),
+            n => return Err(format!("Solving {}x{} cubes is not supported yet", n, n)),
+        };
+        Ok(solution.to_notation())
+    }
+}
+
+// ============================================================================
+// C ABI (native)
+// ============================================================================
+
+#[cfg(not(target_arch = "wasm32"))]
+mod c_api {
+    use super::{FfiCube, FFI_API_VERSION};
+    use std::cell::RefCell;
+    use std::ffi::{c_char, CStr, CString};
+    use std::ptr;
+
+    /// Success return code
+    pub const CUBE_OK: i32 = 0;
+    /// Error return code (details from `cube_last_error`)
+    pub const CUBE_ERROR: i32 = -1;
+
+    thread_local! {
+        static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+    }
+
+    fn set_last_error(message: String) {
+        let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+        LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+    }
+
+    fn into_c_string(text: String) -> *mut c_char {
+        match CString::new(text) {
+            Ok(s) => s.into_raw(),
+            Err(e) => {
+                set_last_error(e.to_string());
+                ptr::null_mut()
+            }
+        }
+    }
+
+    unsafe fn read_str<'a>(text: *const c_char) -> Result<&'a str, String> {
+        if text.is_null() {
+            return Err("Null string pointer".to_string());
+        }
+        CStr::from_ptr(text)
+            .to_str()
+            .map_err(|_| "String is not valid UTF-8".to_string())
+    }
+
+    /// Get the FFI API version
+    #[no_mangle]
+    pub extern "C" fn cube_api_version() -> u32 {
+        FFI_API_VERSION
+    }
+
+    /// Create a solved cube of the given size (2-20)
+    ///
+    /// Returns null on error. Free with `cube_free`.
+    #[no_mangle]
+    pub extern "C" fn cube_new(size: u32) -> *mut FfiCube {
+        match FfiCube::new(size as usize) {
+            Ok(cube) => Box::into_raw(Box::new(cube)),
+            Err(e) => {
+                set_last_error(e);
+                ptr::null_mut()
+            }
+        }
+    }
+
+    /// Create a cube from a facelet string
+    ///
+    /// Returns null on error. Free with `cube_free`.
+    ///
+    /// # Safety
+    /// `facelets` must be a valid NUL-terminated string.
+    #[no_mangle]
+    pub unsafe extern "C" fn cube_from_facelets(facelets: *const c_char) -> *mut FfiCube {
+        match read_str(facelets).and_then(FfiCube::from_facelets) {
+            Ok(cube) => Box::into_raw(Box::new(cube)),
+            Err(e) => {
+                set_last_error(e);
+                ptr::null_mut()
+            }
+        }
+    }
+
+    /// Free a cube created by `cube_new` or `cube_from_facelets`
+    ///
+    /// # Safety
+    /// `cube` must come from this library and must not be used afterwards.
+    #[no_mangle]
+    pub unsafe extern "C" fn cube_free(cube: *mut FfiCube) {
+        if !cube.is_null() {
+            drop(Box::from_raw(cube));
+        }
+    }
+
+    /// Apply an algorithm string, returning `CUBE_OK` or `CUBE_ERROR`
+    ///
+    /// # Safety
+    /// `cube` must be a live cube handle and `alg` a valid NUL-terminated string.
+    #[no_mangle]
+    pub unsafe extern "C" fn cube_apply_algorithm(cube: *mut FfiCube, alg: *const c_char) -> i32 {
+        let Some(cube) = cube.as_mut() else {
+            set_last_error("Null cube pointer".to_string());
+            return CUBE_ERROR;
+        };
+
+        match read_str(alg).and_then(|alg| cube.apply_algorithm(alg)) {
+            Ok(()) => CUBE_OK,
+            Err(e) => {
+                set_last_error(e);
+                CUBE_ERROR
+            }
+        }
+    }
+
+    /// Check whether the cube is solved (1 = solved, 0 = not solved or null)
+    ///
+    /// # Safety
+    /// `cube` must be a live cube handle or null.
+    #[no_mangle]
+    pub unsafe extern "C" fn cube_is_solved(cube: *const FfiCube) -> i32 {
+        cube.as_ref().map_or(0, |c| c.is_solved() as i32)
+    }
+
+    /// Get the facelet string. Free with `cube_string_free`.
+    ///
+    /// # Safety
+    /// `cube` must be a live cube handle.
+    #[no_mangle]
+    pub unsafe extern "C" fn cube_facelets(cube: *const FfiCube) -> *mut c_char {
+        match cube.as_ref() {
+            Some(cube) => into_c_string(cube.facelets()),
+            None => {
+                set_last_error("Null cube pointer".to_string());
+                ptr::null_mut()
+            }
+        }
+    }
+
+    /// Solve the cube, returning the solution moves (empty if already solved)
+    ///
+    /// Returns null on error. Free with `cube_string_free`.
+    ///
+    /// # Safety
+    /// `cube` must be a live cube handle.
+    #[no_mangle]
+    pub unsafe extern "C" fn cube_solve(cube: *const FfiCube) -> *mut c_char {
+        let Some(cube) = cube.as_ref() else {
+            set_last_error("Null cube pointer".to_string());
+            return ptr::null_mut();
+        };
+
+        match cube.solve() {
+            Ok(solution) => into_c_string(solution),
+            Err(e) => {
+                set_last_error(e);
+                ptr::null_mut()
+            }
+        }
+    }
+
+    /// Get the last error on this thread. Free with `cube_string_free`.
+    ///
+    /// Returns null if no error has occurred.
+    #[no_mangle]
+    pub extern "C" fn cube_last_error() -> *mut c_char {
+        LAST_ERROR.with(|slot| {
+            slot.borrow()
+                .as_ref()
+                .map_or(ptr::null_mut(), |e| e.clone().into_raw())
+        })
+    }
+
+    /// Free a string returned by this library
+    ///
+    /// # Safety
+    /// `text` must come from this library and must not be used afterwards.
+    #[no_mangle]
+    pub unsafe extern "C" fn cube_string_free(text: *mut c_char) {
+        if !text.is_null() {
+            drop(CString::from_raw(text));
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use c_api::*;
+
+// ============================================================================
+// WASM bindings
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+mod wasm_api {
+    use super::{FfiCube, FFI_API_VERSION};
+    use wasm_bindgen::prelude::*;
+
+    /// Get the FFI API version
+    #[wasm_bindgen(js_name = cubeApiVersion)]
+    pub fn cube_api_version() -> u32 {
+        FFI_API_VERSION
+    }
+
+    /// Cube exported to JavaScript
+    #[wasm_bindgen]
+    pub struct WasmCube {
+        inner: FfiCube,
+    }
+
+    #[wasm_bindgen]
+    impl WasmCube {
+        /// Create a solved cube of the given size (2-20)
+        #[wasm_bindgen(constructor)]
+        pub fn new(size: usize) -> Result<WasmCube, JsError> {
+            FfiCube::new(size)
+                .map(|inner| WasmCube { inner })
+                .map_err(|e| JsError::new(&e))
+        }
+
+        /// Create a cube from a facelet string
+        #[wasm_bindgen(js_name = fromFacelets)]
+        pub fn from_facelets(facelets: &str) -> Result<WasmCube, JsError> {
+            FfiCube::from_facelets(facelets)
+                .map(|inner| WasmCube { inner })
+                .map_err(|e| JsError::new(&e))
+        }
+
+        /// Get the cube size
+        #[wasm_bindgen(getter)]
+        pub fn size(&self) -> usize {
+            self.inner.size()
+        }
+
+        /// Apply an algorithm such as "R U R' U'"
+        #[wasm_bindgen(js_name = applyAlgorithm)]
+        pub fn apply_algorithm(&mut self, alg: &str) -> Result<(), JsError> {
+            self.inner.apply_algorithm(alg).map_err(|e| JsError::new(&e))
+        }
+
+        /// Get the state as a facelet string
+        pub fn facelets(&self) -> String {
+            self.inner.facelets()
+        }
+
+        /// Check whether the cube is solved
+        #[wasm_bindgen(js_name = isSolved)]
+        pub fn is_solved(&self) -> bool {
+            self.inner.is_solved()
+        }
+
+        /// Solve the cube, returning the solution moves
+        pub fn solve(&self) -> Result<String, JsError> {
+            self.inner.solve().map_err(|e| JsError::new(&e))
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub use wasm_api::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffi_cube_roundtrip() {
+        let mut cube = FfiCube::new(3).unwrap();
+        cube.apply_algorithm("R U R' U'").unwrap();
+        assert!(!cube.is_solved());
+
+        let copy = FfiCube::from_facelets(&cube.facelets()).unwrap();
+        assert_eq!(copy, cube);
+    }
+
+    #[test]
+    fn test_ffi_apply_is_atomic() {
+        let mut cube = FfiCube::new(4).unwrap();
+        assert!(cube.apply_algorithm("R U M").is_err());
+        assert!(cube.is_solved());
+        assert!(cube.apply_algorithm("R Q").is_err());
+        assert!(FfiCube::new(1).is_err());
+    }
+
+    #[test]
+    fn test_ffi_solve() {
+        let mut cube = FfiCube::new(3).unwrap();
+        assert_eq!(cube.solve().unwrap(), "");
+        cube.apply_algorithm("R U").unwrap();
+        assert_eq!(cube.solve().unwrap(), "U' R'");
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_c_api() {
+        use std::ffi::{CStr, CString};
+
+        unsafe {
+            let cube = cube_new(3);
+            assert!(!cube.is_null());
+
+            let alg = CString::new("R U").unwrap();
+            assert_eq!(cube_apply_algorithm(cube, alg.as_ptr()), CUBE_OK);
+            assert_eq!(cube_is_solved(cube), 0);
+
+            let solution = cube_solve(cube);
+            assert_eq!(CStr::from_ptr(solution).to_str().unwrap(), "U' R'");
+            cube_string_free(solution);
+
+            let bad = CString::new("Q").unwrap();
+            assert_eq!(cube_apply_algorithm(cube, bad.as_ptr()), CUBE_ERROR);
+            let error = cube_last_error();
+            assert!(!error.is_null());
+            cube_string_free(error);
+
+            cube_free(cube);
+            assert!(cube_new(99).is_null());
+        }
+    }
+}
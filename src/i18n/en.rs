@@ -0,0 +1,47 @@
+//! English message catalog (the default and fallback locale)
+
+/// English messages, keyed by message ID
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Narration controls
+    ("ui.narration.group", "Lesson narration"),
+    ("ui.narration.read_aloud", "Read this step aloud"),
+    ("ui.narration.pause", "Pause reading"),
+    ("ui.narration.resume", "Keep reading"),
+    ("ui.narration.stop", "Stop reading"),
+    // Lesson navigation
+    ("ui.lesson.step_counter", "Step {current} of {total}"),
+    // Notation lesson
+    ("notation.intro.title", "Welcome to Cube Notation!"),
+    ("notation.intro.description", "Every move on a Rubik's Cube has a special letter. Learning these letters helps you solve the cube faster!"),
+    ("notation.intro.kid", "Think of it like learning the alphabet for your Rubik's Cube! Each face has its own letter name."),
+    ("notation.faces.title", "The Six Faces"),
+    ("notation.faces.description", "A Rubik's Cube has 6 faces. Each face is named by its position when you hold the cube: Right (R), Left (L), Up (U), Down (D), Front (F), and Back (B)."),
+    ("notation.faces.kid", "Hold your cube in front of you. The face pointing at you is Front (F). The top is Up (U). Try to find all six faces!"),
+    ("notation.r.title", "R - Right Face"),
+    ("notation.r.description", "R means turn the right face clockwise (like turning a doorknob to open a door)."),
+    ("notation.r.kid", "Imagine a clock on the right side. R means turn it the way clock hands go!"),
+    ("notation.l.title", "L - Left Face"),
+    ("notation.l.description", "L means turn the left face clockwise when you're looking at it from the left side."),
+    ("notation.l.kid", "Point the left face toward you. Now turn it clockwise, just like you did with R!"),
+    ("notation.u.title", "U - Up Face"),
+    ("notation.u.description", "U means turn the top face clockwise (to the right) when you're looking down at it."),
+    ("notation.u.kid", "Look down at the top of your cube. U means spin it to the right like a spinning top!"),
+    ("notation.d.title", "D - Down Face"),
+    ("notation.d.description", "D means turn the bottom face clockwise when you're looking at it from below."),
+    ("notation.d.kid", "Turn your cube upside down to see the bottom. Now turn it clockwise!"),
+    ("notation.f.title", "F - Front Face"),
+    ("notation.f.description", "F means turn the front face clockwise. This is usually the easiest one to remember!"),
+    ("notation.f.kid", "Look at the face pointing at you. Turn it clockwise like you're turning a steering wheel to the right!"),
+    ("notation.b.title", "B - Back Face"),
+    ("notation.b.description", "B means turn the back face clockwise when you're looking at it from behind."),
+    ("notation.b.kid", "This one is tricky! Turn the cube around and look at the back. Now turn it clockwise."),
+    ("notation.prime.title", "Prime Moves (')"),
+    ("notation.prime.description", "When you see a letter with an apostrophe (like R'), it means turn that face counter-clockwise (the opposite way)."),
+    ("notation.prime.kid", "The apostrophe is like a backwards arrow. R' means turn R backwards instead of forwards!"),
+    ("notation.double.title", "Double Moves (2)"),
+    ("notation.double.description", "When you see a letter with a 2 (like R2), it means turn that face twice, or 180 degrees. You can turn it either way!"),
+    ("notation.double.kid", "R2 is like doing R two times in a row. Turn it halfway around - it doesn't matter which way!"),
+    ("notation.practice.title", "Let's Practice!"),
+    ("notation.practice.description", "Now try these moves on your cube: R U R' U'. This is a famous pattern called the 'Sexy Move'!"),
+    ("notation.practice.kid", "Don't worry if you mess up! You can always reset your cube and try again. Practice makes perfect!"),
+];
@@ -0,0 +1,47 @@
+//! Spanish message catalog
+
+/// Spanish messages, keyed by message ID
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Narration controls
+    ("ui.narration.group", "Narración de la lección"),
+    ("ui.narration.read_aloud", "Leer este paso en voz alta"),
+    ("ui.narration.pause", "Pausar la lectura"),
+    ("ui.narration.resume", "Seguir leyendo"),
+    ("ui.narration.stop", "Detener la lectura"),
+    // Lesson navigation
+    ("ui.lesson.step_counter", "Paso {current} de {total}"),
+    // Notation lesson
+    ("notation.intro.title", "¡Bienvenido a la notación del cubo!"),
+    ("notation.intro.description", "Cada movimiento del cubo de Rubik tiene una letra especial. ¡Aprender estas letras te ayuda a resolver el cubo más rápido!"),
+    ("notation.intro.kid", "¡Es como aprender el abecedario de tu cubo de Rubik! Cada cara tiene su propia letra."),
+    ("notation.faces.title", "Las seis caras"),
+    ("notation.faces.description", "Un cubo de Rubik tiene 6 caras. Cada cara se nombra según su posición cuando sostienes el cubo: Derecha (R), Izquierda (L), Arriba (U), Abajo (D), Frente (F) y Atrás (B)."),
+    ("notation.faces.kid", "Sostén el cubo delante de ti. La cara que te mira es el Frente (F). La de arriba es Arriba (U). ¡Intenta encontrar las seis caras!"),
+    ("notation.r.title", "R - Cara derecha"),
+    ("notation.r.description", "R significa girar la cara derecha en el sentido del reloj (como girar el pomo para abrir una puerta)."),
+    ("notation.r.kid", "Imagina un reloj en el lado derecho. ¡R significa girarlo como se mueven las agujas del reloj!"),
+    ("notation.l.title", "L - Cara izquierda"),
+    ("notation.l.description", "L significa girar la cara izquierda en el sentido del reloj mirándola desde el lado izquierdo."),
+    ("notation.l.kid", "Pon la cara izquierda hacia ti. ¡Ahora gírala en el sentido del reloj, igual que hiciste con R!"),
+    ("notation.u.title", "U - Cara de arriba"),
+    ("notation.u.description", "U significa girar la cara de arriba en el sentido del reloj (hacia la derecha) mirándola desde arriba."),
+    ("notation.u.kid", "Mira la parte de arriba de tu cubo. ¡U significa girarla a la derecha como una peonza!"),
+    ("notation.d.title", "D - Cara de abajo"),
+    ("notation.d.description", "D significa girar la cara de abajo en el sentido del reloj mirándola desde abajo."),
+    ("notation.d.kid", "Pon el cubo boca abajo para ver la base. ¡Ahora gírala en el sentido del reloj!"),
+    ("notation.f.title", "F - Cara de frente"),
+    ("notation.f.description", "F significa girar la cara de frente en el sentido del reloj. ¡Suele ser la más fácil de recordar!"),
+    ("notation.f.kid", "Mira la cara que te apunta. ¡Gírala en el sentido del reloj como si giraras un volante a la derecha!"),
+    ("notation.b.title", "B - Cara de atrás"),
+    ("notation.b.description", "B significa girar la cara de atrás en el sentido del reloj mirándola desde detrás."),
+    ("notation.b.kid", "¡Esta es difícil! Da la vuelta al cubo y mira la parte de atrás. Ahora gírala en el sentido del reloj."),
+    ("notation.prime.title", "Movimientos prima (')"),
+    ("notation.prime.description", "Cuando veas una letra con un apóstrofo (como R'), significa girar esa cara en sentido contrario al reloj."),
+    ("notation.prime.kid", "El apóstrofo es como una flecha hacia atrás. ¡R' significa girar R al revés!"),
+    ("notation.double.title", "Movimientos dobles (2)"),
+    ("notation.double.description", "Cuando veas una letra con un 2 (como R2), significa girar esa cara dos veces, o 180 grados. ¡Puedes girarla hacia cualquier lado!"),
+    ("notation.double.kid", "R2 es como hacer R dos veces seguidas. Dale media vuelta, ¡no importa hacia qué lado!"),
+    ("notation.practice.title", "¡Vamos a practicar!"),
+    ("notation.practice.description", "Ahora prueba estos movimientos en tu cubo: R U R' U'. ¡Es un patrón famoso llamado el 'Sexy Move'!"),
+    ("notation.practice.kid", "¡No te preocupes si te equivocas! Siempre puedes reiniciar el cubo y volver a intentarlo. ¡La práctica hace al maestro!"),
+];
@@ -0,0 +1,172 @@
+//! Localization module
+//!
+//! Provides message keys and per-locale catalogs for lesson and UI strings.
+//! Every catalog is bundled into the binary, so lookups work offline on all
+//! platforms. Missing translations fall back to English, and missing keys
+//! fall back to the key itself so gaps are visible instead of blank.
+//!
+//! Messages may contain named placeholders like `{total}`, filled in with
+//! `Localizer::t_args`.
+
+pub mod en;
+pub mod es;
+
+use serde::{Deserialize, Serialize};
+
+/// Supported locales
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Locale {
+    /// English (default and fallback)
+    #[default]
+    English,
+    /// Spanish
+    Spanish,
+}
+
+impl Locale {
+    /// Get all supported locales
+    pub fn all() -> [Locale; 2] {
+        [Locale::English, Locale::Spanish]
+    }
+
+    /// Get the BCP 47 language code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+        }
+    }
+
+    /// Get the locale's name in its own language (for a language picker)
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Locale::English => "English",
+            Locale::Spanish => "Español",
+        }
+    }
+
+    /// Parse a language code such as "es" or "es-MX"
+    pub fn from_code(code: &str) -> Option<Locale> {
+        let language = code.split(['-', '_']).next()?.to_ascii_lowercase();
+        Self::all().into_iter().find(|locale| locale.code() == language)
+    }
+
+    /// Get the message catalog for this locale
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::English => en::MESSAGES,
+            Locale::Spanish => es::MESSAGES,
+        }
+    }
+}
+
+/// Look up a message in a single locale's catalog, without fallback
+pub fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+    locale
+        .catalog()
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, message)| *message)
+}
+
+/// Get the English keys that a locale has no translation for
+pub fn missing_keys(locale: Locale) -> Vec<&'static str> {
+    en::MESSAGES
+        .iter()
+        .map(|(key, _)| *key)
+        .filter(|key| lookup(locale, key).is_none())
+        .collect()
+}
+
+/// Translates message keys for a chosen locale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Localizer {
+    /// Active locale
+    pub locale: Locale,
+}
+
+impl Localizer {
+    /// Create a localizer for a locale
+    pub fn new(locale: Locale) -> Self {
+        Self { locale }
+    }
+
+    /// Translate a message key
+    ///
+    /// Falls back to English, then to the key itself.
+    pub fn t(&self, key: &str) -> String {
+        lookup(self.locale, key)
+            .or_else(|| lookup(Locale::English, key))
+            .unwrap_or(key)
+            .to_string()
+    }
+
+    /// Translate a message key and fill in `{name}` placeholders
+    pub fn t_args(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let mut message = self.t(key);
+        for (name, value) in args {
+            message = message.replace(&format!("{{{}}}", name), value);
+        }
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_lookup() {
+        let l10n = Localizer::new(Locale::English);
+        assert_eq!(l10n.t("notation.intro.title"), "Welcome to Cube Notation!");
+    }
+
+    #[test]
+    fn test_spanish_lookup() {
+        let l10n = Localizer::new(Locale::Spanish);
+        assert_eq!(l10n.t("ui.narration.stop"), "Detener la lectura");
+    }
+
+    #[test]
+    fn test_missing_key_falls_back_to_key() {
+        let l10n = Localizer::new(Locale::Spanish);
+        assert_eq!(l10n.t("does.not.exist"), "does.not.exist");
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let l10n = Localizer::new(Locale::Spanish);
+        let text = l10n.t_args("ui.lesson.step_counter", &[("current", "2"), ("total", "11")]);
+        assert_eq!(text, "Paso 2 de 11");
+    }
+
+    #[test]
+    fn test_locale_from_code() {
+        assert_eq!(Locale::from_code("es-MX"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_code("EN"), Some(Locale::English));
+        assert_eq!(Locale::from_code("fr"), None);
+    }
+
+    #[test]
+    fn test_all_locales_are_complete() {
+        for locale in Locale::all() {
+            assert!(
+                missing_keys(locale).is_empty(),
+                "{} is missing {:?}",
+                locale.code(),
+                missing_keys(locale)
+            );
+        }
+    }
+
+    #[test]
+    fn test_catalog_keys_are_unique() {
+        for locale in Locale::all() {
+            let mut keys: Vec<_> = locale.catalog().iter().map(|(k, _)| *k).collect();
+            let total = keys.len();
+            keys.sort();
+            keys.dedup();
+            assert_eq!(keys.len(), total, "duplicate key in {}", locale.code());
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Haptic feedback
+//!
+//! Short vibrations for touch devices, played through a pluggable backend:
+//! - Web (WASM): the browser Vibration API (phones and tablets)
+//! - Native platforms: no vibration motor, so nothing is played
+//!
+//! `Haptics` listens to the [`EventBus`](crate::state::EventBus) like the
+//! sound effects do: a face turn gives a light tick and a solve a success
+//! buzz. Turning haptics off keeps the backend but vibrates nothing.
+
+use crate::state::AppEvent;
+
+/// Error types for haptic feedback
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HapticError {
+    /// The device cannot vibrate
+    Unsupported(String),
+    /// The device refused to vibrate
+    VibrateFailed(String),
+}
+
+impl std::fmt::Display for HapticError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HapticError::Unsupported(msg) => write!(f, "Haptics unsupported: {}", msg),
+            HapticError::VibrateFailed(msg) => write!(f, "Vibration failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for HapticError {}
+
+const TICK: [u32; 1] = [10];
+
+/// Buzz, pause, buzz, pause, long buzz
+const SUCCESS_BUZZ: [u32; 5] = [60, 40, 60, 40, 150];
+
+/// The vibrations the app can make
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HapticPattern {
+    /// A light tick for a face turn
+    Tick,
+    /// A celebratory buzz for a solved cube or a completed lesson
+    SuccessBuzz,
+}
+
+impl HapticPattern {
+    /// The vibration for an app event, if it has one
+    pub fn for_event(event: &AppEvent) -> Option<Self> {
+        match event {
+            AppEvent::Turn(_) => Some(HapticPattern::Tick),
+            AppEvent::Solved | AppEvent::LessonCompleted(_) => Some(HapticPattern::SuccessBuzz),
+        }
+    }
+
+    /// Milliseconds to alternately vibrate and pause, starting with a vibration
+    pub fn durations_ms(&self) -> &'static [u32] {
+        match self {
+            HapticPattern::Tick => &TICK,
+            HapticPattern::SuccessBuzz => &SUCCESS_BUZZ,
+        }
+    }
+}
+
+/// A vibration motor that haptic patterns can be played on
+pub trait HapticBackend {
+    /// Human-readable backend name (for settings and diagnostics)
+    fn name(&self) -> &str;
+
+    /// Start vibrating in the pattern, without waiting for it to end
+    fn vibrate(&mut self, durations_ms: &[u32]) -> Result<(), HapticError>;
+}
+
+/// Backend that vibrates nothing (used on devices without a vibration motor)
+#[derive(Debug, Clone, Default)]
+pub struct NoHapticBackend;
+
+impl HapticBackend for NoHapticBackend {
+    fn name(&self) -> &str {
+        "None"
+    }
+
+    fn vibrate(&mut self, _durations_ms: &[u32]) -> Result<(), HapticError> {
+        Ok(())
+    }
+}
+
+/// Plays haptic feedback for app events, unless turned off
+pub struct Haptics {
+    backend: Box<dyn HapticBackend>,
+    enabled: bool,
+}
+
+impl Haptics {
+    /// Create haptics using the given backend
+    pub fn new(backend: Box<dyn HapticBackend>) -> Self {
+        Self { backend, enabled: true }
+    }
+
+    /// Create haptics using the default backend for this platform
+    pub fn with_default_backend() -> Self {
+        Self::new(default_backend())
+    }
+
+    /// Get the name of the active backend
+    pub fn backend_name(&self) -> &str {
+        self.backend.name()
+    }
+
+    /// Check if haptic feedback is on
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Turn haptic feedback on or off
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Play a haptic pattern; does nothing while turned off
+    pub fn play(&mut self, pattern: HapticPattern) -> Result<(), HapticError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        self.backend.vibrate(pattern.durations_ms())
+    }
+
+    /// Play the vibration for an event, as an
+    /// [`EventBus`](crate::state::EventBus) listener
+    ///
+    /// Most devices can't vibrate, so errors are ignored.
+    pub fn handle(&mut self, event: &AppEvent) {
+        if let Some(pattern) = HapticPattern::for_event(event) {
+            let _ = self.play(pattern);
+        }
+    }
+}
+
+impl std::fmt::Debug for Haptics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Haptics")
+            .field("backend", &self.backend.name())
+            .field("enabled", &self.enabled)
+            .finish()
+    }
+}
+
+/// Get the default haptic backend for this platform
+///
+/// Desktops have no vibration motor.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn default_backend() -> Box<dyn HapticBackend> {
+    Box::new(NoHapticBackend)
+}
+
+// ============================================================================
+// WASM IMPLEMENTATION (Vibration API)
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+mod wasm_impl {
+    use super::*;
+    use wasm_bindgen::JsValue;
+    use web_sys::js_sys::Array;
+
+    /// Vibrates with the browser's Vibration API
+    #[derive(Debug, Clone, Default)]
+    pub struct VibrationBackend;
+
+    impl VibrationBackend {
+        /// Create a new Vibration API backend
+        pub fn new() -> Self {
+            Self
+        }
+    }
+
+    impl HapticBackend for VibrationBackend {
+        fn name(&self) -> &str {
+            "Vibration API"
+        }
+
+        fn vibrate(&mut self, durations_ms: &[u32]) -> Result<(), HapticError> {
+            let window =
+                web_sys::window().ok_or_else(|| HapticError::Unsupported("No window object".to_string()))?;
+            let pattern: Array = durations_ms.iter().map(|ms| JsValue::from(*ms)).collect();
+            // Browsers without a vibration motor (or before the first tap)
+            // return false
+            if window.navigator().vibrate_with_pattern(&pattern) {
+                Ok(())
+            } else {
+                Err(HapticError::VibrateFailed("Vibration was refused".to_string()))
+            }
+        }
+    }
+
+    /// Get the default haptic backend for this platform
+    pub fn default_backend() -> Box<dyn HapticBackend> {
+        Box::new(VibrationBackend::new())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub use wasm_impl::*;
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Move;
+    use crate::state::EventBus;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Backend that records the patterns it was asked to vibrate
+    #[derive(Clone, Default)]
+    struct RecordingBackend {
+        vibrated: Rc<RefCell<Vec<Vec<u32>>>>,
+    }
+
+    impl HapticBackend for RecordingBackend {
+        fn name(&self) -> &str {
+            "Recording"
+        }
+
+        fn vibrate(&mut self, durations_ms: &[u32]) -> Result<(), HapticError> {
+            self.vibrated.borrow_mut().push(durations_ms.to_vec());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_events_vibrate() {
+        let backend = RecordingBackend::default();
+        let vibrated = Rc::clone(&backend.vibrated);
+        let haptics = Rc::new(RefCell::new(Haptics::new(Box::new(backend))));
+
+        let mut bus = EventBus::new();
+        let listener = Rc::clone(&haptics);
+        bus.subscribe(Box::new(move |event| listener.borrow_mut().handle(event)));
+
+        bus.emit(AppEvent::Turn(Move::R));
+        bus.emit(AppEvent::Solved);
+        assert_eq!(
+            *vibrated.borrow(),
+            vec![HapticPattern::Tick.durations_ms().to_vec(), HapticPattern::SuccessBuzz.durations_ms().to_vec()]
+        );
+
+        haptics.borrow_mut().set_enabled(false);
+        bus.emit(AppEvent::Turn(Move::U));
+        assert_eq!(vibrated.borrow().len(), 2, "Disabled haptics don't vibrate");
+    }
+
+    #[test]
+    fn test_patterns() {
+        let tick = HapticPattern::Tick.durations_ms();
+        let buzz = HapticPattern::SuccessBuzz.durations_ms();
+        assert_eq!(tick.len(), 1);
+        assert!(tick[0] <= 20, "A tick is light");
+        assert_eq!(buzz.len() % 2, 1, "Patterns end with a vibration");
+        assert!(buzz.iter().sum::<u32>() > tick[0]);
+    }
+}
@@ -0,0 +1,84 @@
+//! Keyboard move input
+//!
+//! Maps keys to moves using the layout most virtual cubes (csTimer, qqTimer)
+//! share, so speedcubers can use the muscle memory they already have:
+//!
+//! ```text
+//!  I / K  = R / R'      D / E  = L / L'
+//!  J / F  = U / U'      S / L  = D / D'
+//!  H / G  = F / F'      W / O  = B / B'
+//!  5 / 6  = M           X / .  = M'
+//!  T / Y  = x           B / N  = x'
+//!  ;      = y           A      = y'
+//!  P      = z           Q      = z'
+//! ```
+
+use crate::cube::Move;
+
+/// Key bindings as (key, move), matched case-insensitively
+pub const KEYBOARD_LAYOUT: &[(&str, Move)] = &[
+    ("i", Move::R),
+    ("k", Move::RPrime),
+    ("d", Move::L),
+    ("e", Move::LPrime),
+    ("j", Move::U),
+    ("f", Move::UPrime),
+    ("s", Move::D),
+    ("l", Move::DPrime),
+    ("h", Move::F),
+    ("g", Move::FPrime),
+    ("w", Move::B),
+    ("o", Move::BPrime),
+    ("5", Move::M),
+    ("6", Move::M),
+    ("x", Move::MPrime),
+    (".", Move::MPrime),
+    ("t", Move::X),
+    ("y", Move::X),
+    ("b", Move::XPrime),
+    ("n", Move::XPrime),
+    (";", Move::Y),
+    ("a", Move::YPrime),
+    ("p", Move::Z),
+    ("q", Move::ZPrime),
+];
+
+/// Get the move bound to a key, if any
+///
+/// Example: `key_to_move("I")` -> `Some(Move::R)`
+pub fn key_to_move(key: &str) -> Option<Move> {
+    let key = key.to_lowercase();
+    KEYBOARD_LAYOUT
+        .iter()
+        .find(|(bound, _)| *bound == key)
+        .map(|(_, mv)| *mv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_to_move() {
+        assert_eq!(key_to_move("i"), Some(Move::R));
+        assert_eq!(key_to_move("K"), Some(Move::RPrime));
+        assert_eq!(key_to_move(";"), Some(Move::Y));
+        assert_eq!(key_to_move("z"), None);
+        assert_eq!(key_to_move("Enter"), None);
+    }
+
+    #[test]
+    fn test_layout_keys_are_unique() {
+        for (index, (key, _)) in KEYBOARD_LAYOUT.iter().enumerate() {
+            assert!(KEYBOARD_LAYOUT[index + 1..].iter().all(|(other, _)| other != key));
+        }
+    }
+
+    #[test]
+    fn test_every_face_turn_is_reachable() {
+        for mv in [Move::R, Move::RPrime, Move::L, Move::LPrime, Move::U, Move::UPrime,
+                   Move::D, Move::DPrime, Move::F, Move::FPrime, Move::B, Move::BPrime] {
+            assert!(KEYBOARD_LAYOUT.iter().any(|(_, bound)| *bound == mv));
+        }
+    }
+}
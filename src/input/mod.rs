@@ -1,7 +1,15 @@
 //! Input handling modules
 //!
-//! This module contains input handling for touch, mouse, and keyboard interactions.
+//! This module contains input handling for touch, mouse, and keyboard interactions,
+//! and the haptic feedback played back to touch devices.
 
+pub mod haptics;
+pub mod keyboard;
 pub mod touch;
 
-pub use touch::{TouchPoint, TouchState, TouchGesture, targets};
+pub use haptics::{HapticBackend, HapticError, HapticPattern, Haptics, NoHapticBackend};
+pub use keyboard::{key_to_move, KEYBOARD_LAYOUT};
+pub use touch::{
+    targets, Gesture, GestureAction, GestureBinding, GestureEvent, GestureKind, GestureSettings, GestureThresholds,
+    SwipeDirection, TouchGesture, TouchPoint, TouchState,
+};
@@ -6,7 +6,16 @@
 //! - All features work with touch
 //! - No hover-dependent features
 //! - Proper touch targets (44px minimum)
-
+//!
+//! A finished touch is recognized as a [`Gesture`] (how many fingers, and
+//! whether it was a tap, double tap, long press, swipe or pinch) using
+//! adjustable [`GestureThresholds`]. [`GestureSettings`] maps gestures to
+//! [`GestureAction`]s: by default a one-finger swipe turns a face, a
+//! two-finger swipe orbits the camera, a pinch zooms and a double tap
+//! resets the view. Any gesture can be rebound, including to an action
+//! the app defines itself with [`GestureAction::Custom`].
+
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Represents a touch point with its coordinates and identifier
@@ -41,6 +50,210 @@ impl TouchPoint {
     }
 }
 
+/// Distances (in pixels) and durations (in milliseconds) that decide what
+/// a touch was
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GestureThresholds {
+    /// Longest touch that still counts as a tap
+    pub tap_max_ms: f64,
+    /// Furthest a finger may move during a tap or long press
+    pub tap_max_movement: f64,
+    /// Longest gap between the two taps of a double tap
+    pub double_tap_ms: f64,
+    /// Shortest touch that counts as a long press
+    pub long_press_ms: f64,
+    /// Shortest movement that counts as a swipe
+    pub swipe_min_distance: f64,
+    /// Smallest change in finger spread (as a fraction) that counts as a
+    /// pinch
+    pub pinch_min_change: f64,
+}
+
+impl Default for GestureThresholds {
+    fn default() -> Self {
+        Self {
+            tap_max_ms: 200.0,
+            tap_max_movement: 10.0,
+            double_tap_ms: 300.0,
+            long_press_ms: 500.0,
+            swipe_min_distance: 30.0,
+            pinch_min_change: 0.15,
+        }
+    }
+}
+
+/// The shape of a finished touch, regardless of the finger count
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GestureKind {
+    /// Quick touch without moving
+    Tap,
+    /// Second tap soon after a first one, in about the same place
+    DoubleTap,
+    /// Held without moving
+    LongPress,
+    /// Moved across the screen
+    Swipe,
+    /// Two fingers moved apart or together
+    Pinch,
+}
+
+/// A recognizable gesture: a kind made with a number of fingers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Gesture {
+    /// Most fingers down at once during the gesture
+    pub fingers: u8,
+    /// What the fingers did
+    pub kind: GestureKind,
+}
+
+impl Gesture {
+    /// A gesture made with `fingers` fingers
+    pub fn new(fingers: u8, kind: GestureKind) -> Self {
+        Self { fingers, kind }
+    }
+}
+
+/// Main direction of a swipe on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl SwipeDirection {
+    /// Direction of a movement, by its larger component (screen y grows
+    /// downward)
+    pub fn from_delta(dx: f64, dy: f64) -> Self {
+        if dx.abs() >= dy.abs() {
+            if dx >= 0.0 {
+                SwipeDirection::Right
+            } else {
+                SwipeDirection::Left
+            }
+        } else if dy >= 0.0 {
+            SwipeDirection::Down
+        } else {
+            SwipeDirection::Up
+        }
+    }
+}
+
+/// What the app does for a gesture
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GestureAction {
+    /// Turn the face or slice under the swipe
+    TurnFace,
+    /// Rotate the camera around the cube
+    Orbit,
+    /// Zoom the camera in or out
+    Zoom,
+    /// Put the camera back where it started
+    ResetView,
+    /// Pick the sticker under the finger
+    SelectSticker,
+    /// Undo the last change
+    Undo,
+    /// Redo the last undone change
+    Redo,
+    /// An action the app handles itself, by name
+    Custom(String),
+}
+
+/// A gesture and the action it triggers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GestureBinding {
+    pub gesture: Gesture,
+    pub action: GestureAction,
+}
+
+/// Gesture thresholds and the gesture-to-action map, saved with the app
+/// settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GestureSettings {
+    /// What counts as a tap, swipe, pinch and so on
+    pub thresholds: GestureThresholds,
+    /// Each gesture's action; a gesture appears at most once
+    bindings: Vec<GestureBinding>,
+}
+
+impl Default for GestureSettings {
+    fn default() -> Self {
+        let mut settings = Self { thresholds: GestureThresholds::default(), bindings: Vec::new() };
+        settings.bind(Gesture::new(1, GestureKind::Swipe), GestureAction::TurnFace);
+        settings.bind(Gesture::new(2, GestureKind::Swipe), GestureAction::Orbit);
+        settings.bind(Gesture::new(2, GestureKind::Pinch), GestureAction::Zoom);
+        settings.bind(Gesture::new(1, GestureKind::DoubleTap), GestureAction::ResetView);
+        settings.bind(Gesture::new(1, GestureKind::Tap), GestureAction::SelectSticker);
+        settings
+    }
+}
+
+impl GestureSettings {
+    /// Settings with the default thresholds and no gestures bound
+    pub fn unbound() -> Self {
+        Self { thresholds: GestureThresholds::default(), bindings: Vec::new() }
+    }
+
+    /// Make `gesture` trigger `action`, replacing what it did before;
+    /// returns the replaced action
+    pub fn bind(&mut self, gesture: Gesture, action: GestureAction) -> Option<GestureAction> {
+        match self.bindings.iter_mut().find(|binding| binding.gesture == gesture) {
+            Some(binding) => Some(std::mem::replace(&mut binding.action, action)),
+            None => {
+                self.bindings.push(GestureBinding { gesture, action });
+                None
+            }
+        }
+    }
+
+    /// Register a gesture for an action the app handles itself
+    pub fn register_custom(&mut self, gesture: Gesture, name: impl Into<String>) -> Option<GestureAction> {
+        self.bind(gesture, GestureAction::Custom(name.into()))
+    }
+
+    /// Make `gesture` do nothing; returns the action it had
+    pub fn unbind(&mut self, gesture: Gesture) -> Option<GestureAction> {
+        let index = self.bindings.iter().position(|binding| binding.gesture == gesture)?;
+        Some(self.bindings.remove(index).action)
+    }
+
+    /// The action for a gesture, if it has one
+    pub fn action_for(&self, gesture: Gesture) -> Option<&GestureAction> {
+        self.bindings.iter().find(|binding| binding.gesture == gesture).map(|binding| &binding.action)
+    }
+
+    /// Gestures bound to an action
+    pub fn gestures_for(&self, action: &GestureAction) -> Vec<Gesture> {
+        self.bindings.iter().filter(|binding| binding.action == *action).map(|binding| binding.gesture).collect()
+    }
+
+    /// Every binding, in the order they were made
+    pub fn bindings(&self) -> &[GestureBinding] {
+        &self.bindings
+    }
+}
+
+/// A finished gesture and the action it is bound to
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureEvent {
+    /// The recognized gesture
+    pub gesture: Gesture,
+    /// Its action, or `None` if the gesture is unbound
+    pub action: Option<GestureAction>,
+    /// Where the first finger went down
+    pub start: (f64, f64),
+    /// How far the fingers moved on average
+    pub delta: (f64, f64),
+    /// Direction of a swipe
+    pub direction: Option<SwipeDirection>,
+    /// Final finger spread over the starting one, for a pinch
+    pub scale: f64,
+}
+
 /// Manages touch state for multi-touch gestures
 #[derive(Debug, Clone)]
 pub struct TouchState {
@@ -50,6 +263,15 @@ pub struct TouchState {
     initial_pinch_distance: Option<f64>,
     /// Current gesture being performed
     current_gesture: TouchGesture,
+    /// Thresholds and gesture-to-action map
+    settings: GestureSettings,
+    /// First and latest point of every touch since the first finger went
+    /// down, in the order they started
+    strokes: Vec<(TouchPoint, TouchPoint)>,
+    /// Most fingers down at once since the first finger went down
+    max_fingers: usize,
+    /// Time, place and finger count of the last tap, for double taps
+    last_tap: Option<(f64, (f64, f64), u8)>,
 }
 
 /// Types of touch gestures supported
@@ -74,17 +296,39 @@ impl Default for TouchState {
 impl TouchState {
     /// Creates a new touch state
     pub fn new() -> Self {
+        Self::with_settings(GestureSettings::default())
+    }
+
+    /// Creates a touch state recognizing gestures with `settings`
+    pub fn with_settings(settings: GestureSettings) -> Self {
         Self {
             active_touches: HashMap::new(),
             initial_pinch_distance: None,
             current_gesture: TouchGesture::None,
+            settings,
+            strokes: Vec::new(),
+            max_fingers: 0,
+            last_tap: None,
         }
     }
 
+    /// Gesture thresholds and bindings in use
+    pub fn settings(&self) -> &GestureSettings {
+        &self.settings
+    }
+
+    /// Change the gesture thresholds and bindings
+    pub fn set_settings(&mut self, settings: GestureSettings) {
+        self.settings = settings;
+    }
+
     /// Registers a new touch start event
     pub fn touch_start(&mut self, id: i32, x: f64, y: f64, timestamp: f64) {
         let point = TouchPoint::new(id, x, y, timestamp);
         self.active_touches.insert(id, point);
+        self.strokes.retain(|(start, _)| start.id != id);
+        self.strokes.push((point, point));
+        self.max_fingers = self.max_fingers.max(self.active_touches.len());
 
         // Update gesture based on number of active touches
         self.update_gesture();
@@ -97,6 +341,81 @@ impl TouchState {
             touch.y = y;
             touch.timestamp = timestamp;
         }
+        if let Some((_, last)) = self.strokes.iter_mut().find(|(start, _)| start.id == id) {
+            *last = TouchPoint::new(id, x, y, timestamp);
+        }
+    }
+
+    /// Removes a touch point lifted at `timestamp`; once the last finger
+    /// is lifted, returns the gesture the touch made
+    ///
+    /// The first tap of a double tap is reported as a tap too.
+    pub fn touch_finish(&mut self, id: i32, timestamp: f64) -> Option<GestureEvent> {
+        self.touch_end(id);
+        if !self.active_touches.is_empty() || self.strokes.is_empty() {
+            return None;
+        }
+        let strokes = std::mem::take(&mut self.strokes);
+        let fingers = std::mem::replace(&mut self.max_fingers, 0).min(u8::MAX as usize) as u8;
+        let event = self.recognize(&strokes, fingers, timestamp);
+        if event.is_none() {
+            self.last_tap = None;
+        }
+        event
+    }
+
+    /// Works out the gesture made by finished strokes
+    fn recognize(&mut self, strokes: &[(TouchPoint, TouchPoint)], fingers: u8, end: f64) -> Option<GestureEvent> {
+        let thresholds = self.settings.thresholds;
+        let count = strokes.len() as f64;
+        let delta = (
+            strokes.iter().map(|(start, last)| last.x - start.x).sum::<f64>() / count,
+            strokes.iter().map(|(start, last)| last.y - start.y).sum::<f64>() / count,
+        );
+        let movement = strokes.iter().map(|(start, last)| start.distance_to(last)).fold(0.0, f64::max);
+        let duration = end - strokes[0].0.timestamp;
+        let start = (strokes[0].0.x, strokes[0].0.y);
+        let scale = match strokes {
+            [(a0, a1), (b0, b1), ..] if a0.distance_to(b0) > 0.0 => a1.distance_to(b1) / a0.distance_to(b0),
+            _ => 1.0,
+        };
+
+        let kind = if strokes.len() >= 2 && (scale - 1.0).abs() >= thresholds.pinch_min_change {
+            GestureKind::Pinch
+        } else if movement >= thresholds.swipe_min_distance {
+            GestureKind::Swipe
+        } else if movement > thresholds.tap_max_movement {
+            return None;
+        } else if duration <= thresholds.tap_max_ms {
+            let double = self.last_tap.is_some_and(|(time, (x, y), taps)| {
+                taps == fingers
+                    && end - time <= thresholds.double_tap_ms
+                    && (start.0 - x).hypot(start.1 - y) <= thresholds.tap_max_movement * 2.0
+            });
+            self.last_tap = if double { None } else { Some((end, start, fingers)) };
+            if double {
+                GestureKind::DoubleTap
+            } else {
+                GestureKind::Tap
+            }
+        } else if duration >= thresholds.long_press_ms {
+            GestureKind::LongPress
+        } else {
+            return None;
+        };
+        if !matches!(kind, GestureKind::Tap | GestureKind::DoubleTap) {
+            self.last_tap = None;
+        }
+
+        let gesture = Gesture::new(fingers, kind);
+        Some(GestureEvent {
+            gesture,
+            action: self.settings.action_for(gesture).cloned(),
+            start,
+            delta,
+            direction: (kind == GestureKind::Swipe).then(|| SwipeDirection::from_delta(delta.0, delta.1)),
+            scale,
+        })
     }
 
     /// Removes a touch point
@@ -115,6 +434,9 @@ impl TouchState {
     /// Clears all touch points
     pub fn clear(&mut self) {
         self.active_touches.clear();
+        self.strokes.clear();
+        self.max_fingers = 0;
+        self.last_tap = None;
         self.current_gesture = TouchGesture::None;
         self.initial_pinch_distance = None;
     }
@@ -378,4 +700,95 @@ mod tests {
         let touches = state.get_touches();
         assert_eq!(touches.len(), 2);
     }
+
+    #[test]
+    fn test_default_gesture_bindings() {
+        let settings = GestureSettings::default();
+        assert_eq!(settings.action_for(Gesture::new(1, GestureKind::Swipe)), Some(&GestureAction::TurnFace));
+        assert_eq!(settings.action_for(Gesture::new(2, GestureKind::Swipe)), Some(&GestureAction::Orbit));
+        assert_eq!(settings.action_for(Gesture::new(1, GestureKind::DoubleTap)), Some(&GestureAction::ResetView));
+        assert_eq!(settings.action_for(Gesture::new(3, GestureKind::Tap)), None);
+    }
+
+    #[test]
+    fn test_rebinding_and_custom_gestures() {
+        let mut settings = GestureSettings::default();
+        let triple = Gesture::new(3, GestureKind::Tap);
+        assert_eq!(settings.register_custom(triple, "scramble"), None);
+        assert_eq!(settings.action_for(triple), Some(&GestureAction::Custom("scramble".to_string())));
+
+        let long_press = Gesture::new(1, GestureKind::LongPress);
+        settings.bind(long_press, GestureAction::Undo);
+        assert_eq!(settings.bind(long_press, GestureAction::Redo), Some(GestureAction::Undo));
+        assert_eq!(settings.gestures_for(&GestureAction::Redo), vec![long_press]);
+        assert_eq!(settings.unbind(long_press), Some(GestureAction::Redo));
+        assert_eq!(settings.unbind(long_press), None);
+
+        let json = serde_json::to_string(&settings).unwrap();
+        assert_eq!(serde_json::from_str::<GestureSettings>(&json).unwrap(), settings);
+    }
+
+    #[test]
+    fn test_swipes_turn_and_two_finger_swipes_orbit() {
+        let mut state = TouchState::new();
+        state.touch_start(1, 100.0, 100.0, 0.0);
+        state.touch_move(1, 100.0, 40.0, 80.0);
+        let swipe = state.touch_finish(1, 100.0).unwrap();
+        assert_eq!(swipe.gesture, Gesture::new(1, GestureKind::Swipe));
+        assert_eq!(swipe.action, Some(GestureAction::TurnFace));
+        assert_eq!(swipe.direction, Some(SwipeDirection::Up));
+        assert_eq!(swipe.delta, (0.0, -60.0));
+
+        state.touch_start(1, 100.0, 100.0, 1000.0);
+        state.touch_start(2, 200.0, 100.0, 1010.0);
+        state.touch_move(1, 150.0, 100.0, 1100.0);
+        state.touch_move(2, 250.0, 100.0, 1100.0);
+        assert!(state.touch_finish(1, 1150.0).is_none());
+        let orbit = state.touch_finish(2, 1160.0).unwrap();
+        assert_eq!(orbit.gesture, Gesture::new(2, GestureKind::Swipe));
+        assert_eq!(orbit.action, Some(GestureAction::Orbit));
+        assert_eq!(orbit.direction, Some(SwipeDirection::Right));
+    }
+
+    #[test]
+    fn test_pinch_and_double_tap() {
+        let mut state = TouchState::new();
+        state.touch_start(1, 100.0, 100.0, 0.0);
+        state.touch_start(2, 200.0, 100.0, 0.0);
+        state.touch_move(2, 300.0, 100.0, 50.0);
+        state.touch_finish(2, 60.0);
+        let pinch = state.touch_finish(1, 70.0).unwrap();
+        assert_eq!(pinch.gesture.kind, GestureKind::Pinch);
+        assert_eq!(pinch.action, Some(GestureAction::Zoom));
+        assert!((pinch.scale - 2.0).abs() < 1e-9);
+
+        state.touch_start(1, 50.0, 50.0, 1000.0);
+        assert_eq!(state.touch_finish(1, 1080.0).unwrap().gesture.kind, GestureKind::Tap);
+        state.touch_start(1, 53.0, 52.0, 1200.0);
+        let double = state.touch_finish(1, 1260.0).unwrap();
+        assert_eq!(double.gesture.kind, GestureKind::DoubleTap);
+        assert_eq!(double.action, Some(GestureAction::ResetView));
+        // A third tap starts over
+        state.touch_start(1, 50.0, 50.0, 1400.0);
+        assert_eq!(state.touch_finish(1, 1450.0).unwrap().gesture.kind, GestureKind::Tap);
+    }
+
+    #[test]
+    fn test_thresholds_are_configurable() {
+        let mut settings = GestureSettings::unbound();
+        settings.thresholds.swipe_min_distance = 100.0;
+        settings.thresholds.tap_max_movement = 80.0;
+        let mut state = TouchState::with_settings(settings);
+        state.touch_start(1, 0.0, 0.0, 0.0);
+        state.touch_move(1, 60.0, 0.0, 50.0);
+        let tap = state.touch_finish(1, 100.0).unwrap();
+        assert_eq!(tap.gesture.kind, GestureKind::Tap);
+        assert_eq!(tap.action, None);
+
+        state.touch_start(1, 0.0, 0.0, 1000.0);
+        assert_eq!(state.touch_finish(1, 1600.0).unwrap().gesture.kind, GestureKind::LongPress);
+        // Held too long for a tap, too short for a long press
+        state.touch_start(1, 0.0, 0.0, 2000.0);
+        assert!(state.touch_finish(1, 2300.0).is_none());
+    }
 }
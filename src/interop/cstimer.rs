@@ -0,0 +1,358 @@
+//! csTimer session import and export
+//!
+//! Reads the JSON file produced by csTimer's "Export to file" option and
+//! converts each solve into a `SolveRecord`, and writes sessions back out in
+//! the same format for csTimer's "Import from file".
+//!
+//! The export is an object with one `sessionN` array per session and a
+//! `properties` object. Each solve is stored as:
+//!
+//! ```text
+//! [[penalty, time_ms], "scramble", "comment", timestamp]
+//! ```
+//!
+//! where penalty is `0` (none), `2000` (+2) or `-1` (DNF). Session names and
+//! scramble types live in `properties.sessionData`, which csTimer stores as a
+//! JSON-encoded string.
+
+use super::InteropError;
+use crate::state::{Penalty, SolveRecord};
+use serde_json::{json, Map, Value};
+
+/// csTimer penalty value for a +2
+const CSTIMER_PLUS_TWO: i64 = 2000;
+
+/// csTimer penalty value for a DNF
+const CSTIMER_DNF: i64 = -1;
+
+/// One imported csTimer session
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsTimerSession {
+    /// Session number from the export (1-based)
+    pub number: u32,
+    /// Session name shown in csTimer
+    pub name: String,
+    /// Cube size inferred from the session's scramble type
+    pub cube_size: usize,
+    /// Imported solves, oldest first
+    pub records: Vec<SolveRecord>,
+}
+
+/// Result of importing a csTimer export
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CsTimerImport {
+    /// Sessions for NxN cubes, ordered by session number
+    pub sessions: Vec<CsTimerSession>,
+    /// Names of sessions skipped because they are for other puzzles
+    pub skipped_sessions: Vec<String>,
+    /// Number of malformed solve entries that were skipped
+    pub skipped_solves: usize,
+}
+
+impl CsTimerImport {
+    /// Get the total number of imported solves
+    pub fn solve_count(&self) -> usize {
+        self.sessions.iter().map(|s| s.records.len()).sum()
+    }
+
+    /// Get all imported solves across sessions
+    pub fn all_records(&self) -> Vec<SolveRecord> {
+        self.sessions
+            .iter()
+            .flat_map(|s| s.records.iter().cloned())
+            .collect()
+    }
+}
+
+/// Import a csTimer JSON export
+pub fn import_cstimer(json: &str) -> Result<CsTimerImport, InteropError> {
+    let root: Value =
+        serde_json::from_str(json).map_err(|e| InteropError::InvalidJson(e.to_string()))?;
+    let root = root
+        .as_object()
+        .ok_or_else(|| InteropError::InvalidFormat("expected a JSON object".to_string()))?;
+
+    let session_data = parse_session_data(root);
+
+    let mut numbered: Vec<(u32, &Vec<Value>)> = root
+        .iter()
+        .filter_map(|(key, value)| {
+            let number = key.strip_prefix("session")?.parse().ok()?;
+            Some((number, value.as_array()?))
+        })
+        .collect();
+
+    if numbered.is_empty() {
+        return Err(InteropError::InvalidFormat(
+            "no csTimer sessions found".to_string(),
+        ));
+    }
+    numbered.sort_by_key(|(number, _)| *number);
+
+    let mut import = CsTimerImport::default();
+
+    for (number, solves) in numbered {
+        let info = session_data.get(&number.to_string());
+        let name = info
+            .and_then(|i| i.get("name"))
+            .map(|n| match n {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .unwrap_or_else(|| number.to_string());
+        let scramble_type = info
+            .and_then(|i| i.get("opt"))
+            .and_then(|o| o.get("scrType"))
+            .and_then(Value::as_str)
+            .unwrap_or("333");
+
+        let Some(cube_size) = cube_size_for_scramble_type(scramble_type) else {
+            import.skipped_sessions.push(name);
+            continue;
+        };
+
+        let mut records = Vec::with_capacity(solves.len());
+        for solve in solves {
+            match parse_solve(solve, cube_size) {
+                Some(record) => records.push(record.with_session(format!("cstimer-{}", number))),
+                None => import.skipped_solves += 1,
+            }
+        }
+
+        import.sessions.push(CsTimerSession {
+            number,
+            name,
+            cube_size,
+            records,
+        });
+    }
+
+    Ok(import)
+}
+
+/// Write sessions as a csTimer JSON export
+///
+/// Sessions are numbered in order from 1, whatever their `number`, so the
+/// file always loads in csTimer. Fails if a session's cube is bigger than
+/// csTimer's scramble types go (9x9).
+pub fn export_cstimer(sessions: &[CsTimerSession]) -> Result<String, InteropError> {
+    let mut root = Map::new();
+    let mut session_data = Map::new();
+
+    for (index, session) in sessions.iter().enumerate() {
+        let number = index + 1;
+        let scramble_type = scramble_type_for_size(session.cube_size)
+            .ok_or(InteropError::UnsupportedCubeSize(session.cube_size))?;
+        let solves: Vec<Value> = session.records.iter().map(solve_entry).collect();
+        root.insert(format!("session{}", number), Value::Array(solves));
+        session_data.insert(
+            number.to_string(),
+            json!({ "name": session.name, "opt": { "scrType": scramble_type } }),
+        );
+    }
+
+    // csTimer stores the session data as a JSON string
+    let session_data = Value::Object(session_data).to_string();
+    root.insert("properties".to_string(), json!({ "sessionData": session_data }));
+    Ok(Value::Object(root).to_string())
+}
+
+/// One solve as `[[penalty, time_ms], "scramble", "comment", timestamp]`
+fn solve_entry(record: &SolveRecord) -> Value {
+    let penalty = match record.penalty {
+        Penalty::None => 0,
+        Penalty::PlusTwo => CSTIMER_PLUS_TWO,
+        Penalty::Dnf => CSTIMER_DNF,
+    };
+    json!([[penalty, record.time_ms], record.scramble, record.comment.as_deref().unwrap_or(""), record.timestamp])
+}
+
+/// csTimer scramble type for a cube size (the reverse of
+/// `cube_size_for_scramble_type`)
+///
+/// Returns `None` for sizes csTimer has no scramble type for.
+fn scramble_type_for_size(size: usize) -> Option<String> {
+    match size {
+        2 => Some("222so".to_string()),
+        3 => Some("333".to_string()),
+        4..=7 => Some(format!("{0}{0}{0}wca", size)),
+        8..=9 => Some(format!("{0}{0}{0}", size)),
+        _ => None,
+    }
+}
+
+/// Read `properties.sessionData`, which may be an object or a JSON string
+fn parse_session_data(root: &Map<String, Value>) -> Map<String, Value> {
+    let data = root.get("properties").and_then(|p| p.get("sessionData"));
+
+    let parsed = match data {
+        Some(Value::String(encoded)) => serde_json::from_str(encoded).ok(),
+        Some(value) => Some(value.clone()),
+        None => None,
+    };
+
+    match parsed {
+        Some(Value::Object(map)) => map,
+        _ => Map::new(),
+    }
+}
+
+/// Convert a csTimer scramble type ("333", "444wca", "222so") to a cube size
+///
+/// Returns `None` for non-cube puzzles such as Pyraminx or Skewb.
+fn cube_size_for_scramble_type(scramble_type: &str) -> Option<usize> {
+    // An empty type means the default 3x3 scrambler
+    if scramble_type.is_empty() {
+        return Some(3);
+    }
+
+    let digits: Vec<char> = scramble_type.chars().take(3).collect();
+    if digits.len() == 3 && digits.iter().all(|c| *c == digits[0]) {
+        let size = digits[0].to_digit(10)? as usize;
+        if (2..=9).contains(&size) {
+            return Some(size);
+        }
+    }
+    None
+}
+
+/// Convert one csTimer solve entry into a record
+fn parse_solve(solve: &Value, cube_size: usize) -> Option<SolveRecord> {
+    let entry = solve.as_array()?;
+    let result = entry.first()?.as_array()?;
+
+    let penalty = match result.first()?.as_i64()? {
+        CSTIMER_DNF => Penalty::Dnf,
+        CSTIMER_PLUS_TWO => Penalty::PlusTwo,
+        _ => Penalty::None,
+    };
+    let time_ms = result.get(1)?.as_u64()?;
+    let scramble = entry.get(1).and_then(Value::as_str).unwrap_or("").trim();
+    let comment = entry
+        .get(2)
+        .and_then(Value::as_str)
+        .filter(|c| !c.is_empty())
+        .map(str::to_string);
+    let timestamp = entry.get(3).and_then(Value::as_u64).unwrap_or(0);
+
+    Some(SolveRecord {
+        time_ms,
+        penalty,
+        scramble: scramble.to_string(),
+        cube_size,
+        timestamp,
+        comment,
+        session_id: None,
+        reconstruction: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = r#"{
+        "session1": [
+            [[0, 12345], "R U R' U'", "", 1700000000],
+            [[2000, 15000], "F2 D L", "oops", 1700000100],
+            [[-1, 30000], "B' U2", "", 1700000200]
+        ],
+        "session2": [
+            [[0, 4200], "R U F", "", 1700000300],
+            "not a solve"
+        ],
+        "session3": [
+            [[0, 5000], "U L R B", "", 1700000400]
+        ],
+        "properties": {
+            "sessionData": "{\"1\":{\"name\":\"Main\",\"opt\":{}},\"2\":{\"name\":\"2x2\",\"opt\":{\"scrType\":\"222so\"}},\"3\":{\"name\":\"Pyra\",\"opt\":{\"scrType\":\"pyrso\"}}}"
+        }
+    }"#;
+
+    #[test]
+    fn test_import_sessions() {
+        let import = import_cstimer(EXPORT).unwrap();
+
+        assert_eq!(import.sessions.len(), 2);
+        assert_eq!(import.sessions[0].name, "Main");
+        assert_eq!(import.sessions[0].cube_size, 3);
+        assert_eq!(import.sessions[1].name, "2x2");
+        assert_eq!(import.sessions[1].cube_size, 2);
+        assert_eq!(import.skipped_sessions, vec!["Pyra".to_string()]);
+        assert_eq!(import.skipped_solves, 1);
+        assert_eq!(import.solve_count(), 4);
+    }
+
+    #[test]
+    fn test_import_penalties_and_comments() {
+        let import = import_cstimer(EXPORT).unwrap();
+        let records = &import.sessions[0].records;
+
+        assert_eq!(records[0].penalty, Penalty::None);
+        assert_eq!(records[0].time_ms, 12345);
+        assert_eq!(records[0].scramble, "R U R' U'");
+        assert_eq!(records[0].timestamp, 1_700_000_000);
+        assert_eq!(records[1].penalty, Penalty::PlusTwo);
+        assert_eq!(records[1].comment.as_deref(), Some("oops"));
+        assert_eq!(records[2].penalty, Penalty::Dnf);
+        assert!(records[0].comment.is_none());
+        assert_eq!(records[0].session_id.as_deref(), Some("cstimer-1"));
+    }
+
+    #[test]
+    fn test_import_without_properties_defaults_to_3x3() {
+        let import = import_cstimer(r#"{"session1": [[[0, 1000], "R", "", 1]]}"#).unwrap();
+        assert_eq!(import.sessions[0].name, "1");
+        assert_eq!(import.sessions[0].cube_size, 3);
+    }
+
+    #[test]
+    fn test_import_errors() {
+        assert!(matches!(import_cstimer("not json"), Err(InteropError::InvalidJson(_))));
+        assert!(matches!(import_cstimer("[]"), Err(InteropError::InvalidFormat(_))));
+        assert!(matches!(import_cstimer("{}"), Err(InteropError::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let import = import_cstimer(EXPORT).unwrap();
+        let exported = export_cstimer(&import.sessions).unwrap();
+
+        let reimported = import_cstimer(&exported).unwrap();
+        assert_eq!(reimported.sessions.len(), 2);
+        assert_eq!(reimported.sessions[1].name, "2x2");
+        assert_eq!(reimported.sessions[1].cube_size, 2);
+        assert_eq!(reimported.all_records(), import.all_records());
+    }
+
+    #[test]
+    fn test_scramble_type_for_size() {
+        for size in 2..=9 {
+            let scramble_type = scramble_type_for_size(size).unwrap();
+            assert_eq!(cube_size_for_scramble_type(&scramble_type), Some(size));
+        }
+        assert_eq!(scramble_type_for_size(1), None);
+        assert_eq!(scramble_type_for_size(10), None);
+        assert_eq!(scramble_type_for_size(21), None);
+    }
+
+    #[test]
+    fn test_export_rejects_unsupported_sizes() {
+        let session = CsTimerSession {
+            number: 1,
+            name: "10x10".to_string(),
+            cube_size: 10,
+            records: Vec::new(),
+        };
+        assert_eq!(export_cstimer(&[session]), Err(InteropError::UnsupportedCubeSize(10)));
+    }
+
+    #[test]
+    fn test_cube_size_for_scramble_type() {
+        assert_eq!(cube_size_for_scramble_type("333"), Some(3));
+        assert_eq!(cube_size_for_scramble_type("444wca"), Some(4));
+        assert_eq!(cube_size_for_scramble_type("777wca"), Some(7));
+        assert_eq!(cube_size_for_scramble_type("skbso"), None);
+        assert_eq!(cube_size_for_scramble_type("111"), None);
+    }
+}
@@ -0,0 +1,31 @@
+//! Interoperability module
+//!
+//! Imports and exports data in formats used by other cubing tools,
+//! so speedcubers can bring their existing history into the app.
+
+pub mod cstimer;
+
+pub use cstimer::{export_cstimer, import_cstimer, CsTimerImport, CsTimerSession};
+
+/// Error types for importing and exporting external data
+#[derive(Debug, Clone, PartialEq)]
+pub enum InteropError {
+    /// The input is not valid JSON
+    InvalidJson(String),
+    /// The JSON does not match the expected export format
+    InvalidFormat(String),
+    /// The other tool has no puzzle type for this cube size
+    UnsupportedCubeSize(usize),
+}
+
+impl std::fmt::Display for InteropError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InteropError::InvalidJson(msg) => write!(f, "Invalid JSON: {}", msg),
+            InteropError::InvalidFormat(msg) => write!(f, "Unrecognized export format: {}", msg),
+            InteropError::UnsupportedCubeSize(size) => write!(f, "No matching puzzle type for a {0}x{0} cube", size),
+        }
+    }
+}
+
+impl std::error::Error for InteropError {}
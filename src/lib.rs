@@ -3,12 +3,21 @@
 //! This crate provides the core functionality for the Rubik's cube solver app,
 //! including cube state representation, solving algorithms, and more.
 
+pub mod app;
 pub mod camera;
 pub mod components;
 pub mod cube;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod i18n;
 pub mod input;
+pub mod interop;
+pub mod puzzle;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod renderer;
 pub mod solver;
+pub mod sound;
 pub mod state;
+pub mod storage;
+pub mod timer;
 pub mod tutorial;
@@ -0,0 +1,94 @@
+//! Small 3D vector helpers for working out puzzle moves
+//!
+//! Puzzles that are not cubes get their moves from their shape: each
+//! sticker has a center point, and a move rotates the points on one side
+//! of a cut about an axis. [`sticker_permutation`] turns such a rotation
+//! into a table of which sticker lands where.
+
+/// A point or direction in 3D space
+pub type Point = [f32; 3];
+
+pub fn add(a: Point, b: Point) -> Point {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+pub fn sub(a: Point, b: Point) -> Point {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub fn scale(a: Point, factor: f32) -> Point {
+    a.map(|x| x * factor)
+}
+
+pub fn dot(a: Point, b: Point) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub fn cross(a: Point, b: Point) -> Point {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+pub fn normalize(a: Point) -> Point {
+    scale(a, 1.0 / dot(a, a).sqrt())
+}
+
+/// `a + (b - a) * t`
+pub fn lerp(a: Point, b: Point, t: f32) -> Point {
+    add(a, scale(sub(b, a), t))
+}
+
+/// Rotate `p` by `angle` radians about the unit `axis` through the origin
+/// (right-handed: counter-clockwise seen from the tip of the axis)
+pub fn rotate(p: Point, axis: Point, angle: f32) -> Point {
+    let (sin, cos) = angle.sin_cos();
+    let k_cross_p = cross(axis, p);
+    let k_dot_p = dot(axis, p);
+    [0, 1, 2].map(|i| p[i] * cos + k_cross_p[i] * sin + axis[i] * k_dot_p * (1.0 - cos))
+}
+
+/// Average of the points
+pub fn centroid(points: &[Point]) -> Point {
+    scale(points.iter().fold([0.0; 3], |sum, p| add(sum, *p)), 1.0 / points.len() as f32)
+}
+
+/// Index of the point closest to `p`
+pub fn nearest(points: &[Point], p: Point) -> usize {
+    let distance = |q: &Point| dot(sub(*q, p), sub(*q, p));
+    (0..points.len()).min_by(|&a, &b| distance(&points[a]).total_cmp(&distance(&points[b]))).expect("points are given")
+}
+
+/// Sticker table of a move: `new[i] = old[table[i]]`
+///
+/// `moved` gives where a sticker centered at a point goes, or None for
+/// stickers the move leaves alone.
+pub fn sticker_permutation(centers: &[Point], moved: impl Fn(Point) -> Option<Point>) -> Vec<usize> {
+    let mut table: Vec<usize> = (0..centers.len()).collect();
+    for (from, &p) in centers.iter().enumerate() {
+        if let Some(to) = moved(p) {
+            table[nearest(centers, to)] = from;
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_is_right_handed() {
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let p = rotate([1.0, 0.0, 0.0], [0.0, 0.0, 1.0], quarter);
+        assert!(dot(sub(p, [0.0, 1.0, 0.0]), sub(p, [0.0, 1.0, 0.0])) < 1e-10);
+    }
+
+    #[test]
+    fn test_sticker_permutation() {
+        // Three points on a circle, turned a third of the way round
+        let third = 2.0 * std::f32::consts::PI / 3.0;
+        let centers: Vec<Point> = (0..3).map(|i| rotate([1.0, 0.0, 0.0], [0.0, 0.0, 1.0], i as f32 * third)).collect();
+        let table = sticker_permutation(&centers, |p| Some(rotate(p, [0.0, 0.0, 1.0], third)));
+        assert_eq!(table, vec![2, 0, 1]);
+        assert_eq!(nearest(&centers, [0.9, 0.1, 0.0]), 0);
+    }
+}
@@ -0,0 +1,715 @@
+//! Megaminx: a dodecahedron with twelve faces of eleven stickers
+//!
+//! Each face has a center, five corners and five edges. Stickers are
+//! numbered 0 for the center, 1-5 for the corners and 6-10 for the edges,
+//! going counter-clockwise from the corner drawn at the top of the face in
+//! the net (the back corner of U, the front corner of D, and the corner
+//! furthest from U or D on the other faces). Edge `6 + i` lies between
+//! corners `1 + i` and `2 + i`.
+//!
+//! Faces are named after where they sit with U on top and F in front: the
+//! upper ring is F, R, BR, BL and L, and the lower ring below them is B,
+//! DBL, DL, DR and DBR (each opposite an upper face, in the same order).
+//!
+//! Besides single face turns ("U", "R2'") the puzzle takes the WCA
+//! scramble moves: "R++" turns everything but the layer opposite R two
+//! fifths clockwise around the R axis, "D--" everything but U two fifths
+//! counter-clockwise around the D axis. As with the Pyraminx, moves are
+//! worked out by rotating the stickers' positions on the dodecahedron.
+
+use super::geometry::{add, centroid, cross, dot, normalize, rotate, scale, sticker_permutation, sub, Point};
+use super::Puzzle;
+use crate::cube::notation::NotationError;
+use rand::Rng;
+use std::sync::OnceLock;
+
+/// Stickers on each face
+pub const STICKERS_PER_FACE: usize = 11;
+
+/// Stickers on the whole puzzle
+pub const STICKER_COUNT: usize = 12 * STICKERS_PER_FACE;
+
+/// Lines in a WCA scramble
+pub const SCRAMBLE_LINES: usize = 7;
+
+/// R and D moves on each scramble line, before its U turn
+pub const MOVES_PER_LINE: usize = 10;
+
+/// Side of a corner sticker as a fraction of the face's edge
+const CORNER_FRACTION: f32 = 0.3;
+
+/// Cosine of the angle between the normals of neighbouring faces
+fn neighbour_cos() -> f32 {
+    1.0 / 5f32.sqrt()
+}
+
+/// The twelve faces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MegaminxFace {
+    U,
+    F,
+    R,
+    BR,
+    BL,
+    L,
+    D,
+    B,
+    DBL,
+    DL,
+    DR,
+    DBR,
+}
+
+impl MegaminxFace {
+    /// Every face; the second six are opposite the first six, in order
+    pub const ALL: [MegaminxFace; 12] = [
+        MegaminxFace::U,
+        MegaminxFace::F,
+        MegaminxFace::R,
+        MegaminxFace::BR,
+        MegaminxFace::BL,
+        MegaminxFace::L,
+        MegaminxFace::D,
+        MegaminxFace::B,
+        MegaminxFace::DBL,
+        MegaminxFace::DL,
+        MegaminxFace::DR,
+        MegaminxFace::DBR,
+    ];
+
+    /// The face on the other side of the puzzle
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 6) % 12]
+    }
+
+    /// The face's name in notation
+    pub fn name(self) -> &'static str {
+        match self {
+            MegaminxFace::U => "U",
+            MegaminxFace::F => "F",
+            MegaminxFace::R => "R",
+            MegaminxFace::BR => "BR",
+            MegaminxFace::BL => "BL",
+            MegaminxFace::L => "L",
+            MegaminxFace::D => "D",
+            MegaminxFace::B => "B",
+            MegaminxFace::DBL => "DBL",
+            MegaminxFace::DL => "DL",
+            MegaminxFace::DR => "DR",
+            MegaminxFace::DBR => "DBR",
+        }
+    }
+
+    /// Color of the face on a solved puzzle
+    pub fn color(self) -> MegaminxColor {
+        MegaminxColor::ALL[self as usize]
+    }
+
+    /// Unit vector from the center through the face's center; the faces
+    /// lie one unit from the center
+    pub fn normal(self) -> Point {
+        match self as usize {
+            0 => [0.0, 1.0, 0.0],
+            index @ 1..=5 => {
+                // The upper ring, every 72° starting from the front
+                let azimuth = (index - 1) as f32 * 2.0 * std::f32::consts::PI / 5.0;
+                let ring = 2.0 * neighbour_cos();
+                [ring * azimuth.sin(), neighbour_cos(), ring * azimuth.cos()]
+            }
+            _ => scale(self.opposite().normal(), -1.0),
+        }
+    }
+
+    /// Whether the two faces share an edge
+    pub fn is_neighbour(self, other: MegaminxFace) -> bool {
+        (dot(self.normal(), other.normal()) - neighbour_cos()).abs() < 1e-3
+    }
+
+    /// The five faces around this one
+    pub fn neighbours(self) -> Vec<MegaminxFace> {
+        Self::ALL.into_iter().filter(|f| self.is_neighbour(*f)).collect()
+    }
+}
+
+/// The twelve sticker colors, light shades opposite their dark ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MegaminxColor {
+    White,
+    Green,
+    Red,
+    Blue,
+    Yellow,
+    Purple,
+    Gray,
+    LightGreen,
+    Orange,
+    LightBlue,
+    Beige,
+    Pink,
+}
+
+impl MegaminxColor {
+    /// Every color, in the order of the faces they belong to
+    pub const ALL: [MegaminxColor; 12] = [
+        MegaminxColor::White,
+        MegaminxColor::Green,
+        MegaminxColor::Red,
+        MegaminxColor::Blue,
+        MegaminxColor::Yellow,
+        MegaminxColor::Purple,
+        MegaminxColor::Gray,
+        MegaminxColor::LightGreen,
+        MegaminxColor::Orange,
+        MegaminxColor::LightBlue,
+        MegaminxColor::Beige,
+        MegaminxColor::Pink,
+    ];
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            MegaminxColor::White => "White",
+            MegaminxColor::Green => "Green",
+            MegaminxColor::Red => "Red",
+            MegaminxColor::Blue => "Blue",
+            MegaminxColor::Yellow => "Yellow",
+            MegaminxColor::Purple => "Purple",
+            MegaminxColor::Gray => "Gray",
+            MegaminxColor::LightGreen => "Light green",
+            MegaminxColor::Orange => "Orange",
+            MegaminxColor::LightBlue => "Light blue",
+            MegaminxColor::Beige => "Beige",
+            MegaminxColor::Pink => "Pink",
+        }
+    }
+
+    /// Sticker fill as an SVG/CSS color
+    pub fn fill(self) -> &'static str {
+        match self {
+            MegaminxColor::White => "#FFFFFF",
+            MegaminxColor::Green => "#009E60",
+            MegaminxColor::Red => "#C41E3A",
+            MegaminxColor::Blue => "#0051BA",
+            MegaminxColor::Yellow => "#FFD500",
+            MegaminxColor::Purple => "#6A3D9A",
+            MegaminxColor::Gray => "#8C8C8C",
+            MegaminxColor::LightGreen => "#8EE05A",
+            MegaminxColor::Orange => "#FF5800",
+            MegaminxColor::LightBlue => "#5BC0EB",
+            MegaminxColor::Beige => "#F3E2B3",
+            MegaminxColor::Pink => "#F49AC2",
+        }
+    }
+}
+
+/// One Megaminx move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MegaminxMove {
+    /// One face turned `turns` fifths of a turn: 1 and 2 clockwise, -1 and
+    /// -2 counter-clockwise ("U", "U2", "U'", "U2'")
+    Face { face: MegaminxFace, turns: i8 },
+    /// A WCA scramble move: everything but the layer opposite `axis` turned
+    /// two fifths ("R++", "D--")
+    Pochmann { axis: MegaminxFace, clockwise: bool },
+}
+
+impl MegaminxMove {
+    /// The move that undoes this one
+    pub fn inverse(&self) -> Self {
+        match *self {
+            MegaminxMove::Face { face, turns } => MegaminxMove::Face { face, turns: -turns },
+            MegaminxMove::Pochmann { axis, clockwise } => MegaminxMove::Pochmann { axis, clockwise: !clockwise },
+        }
+    }
+
+    /// Notation, e.g. "BR2'" or "R++"
+    pub fn to_notation(&self) -> String {
+        match *self {
+            MegaminxMove::Face { face, turns } => {
+                let amount = if turns.abs() == 2 { "2" } else { "" };
+                let prime = if turns < 0 { "'" } else { "" };
+                format!("{}{}{}", face.name(), amount, prime)
+            }
+            MegaminxMove::Pochmann { axis, clockwise } => {
+                format!("{}{}", axis.name(), if clockwise { "++" } else { "--" })
+            }
+        }
+    }
+
+    /// Parse a move such as "U", "DBL2'", "R++" or "D--"
+    pub fn parse(token: &str) -> Result<Self, NotationError> {
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(NotationError::EmptyInput);
+        }
+        let invalid = || NotationError::InvalidMove(token.to_string());
+        let face_named = |name: &str| MegaminxFace::ALL.into_iter().find(|f| f.name() == name).ok_or_else(invalid);
+
+        for (suffix, clockwise) in [("++", true), ("--", false)] {
+            if let Some(name) = token.strip_suffix(suffix) {
+                let axis = face_named(name)?;
+                // Only R and D are used in WCA scrambles
+                if axis != MegaminxFace::R && axis != MegaminxFace::D {
+                    return Err(invalid());
+                }
+                return Ok(MegaminxMove::Pochmann { axis, clockwise });
+            }
+        }
+
+        let (rest, prime) = match token.strip_suffix('\'') {
+            Some(rest) => (rest, true),
+            None => (token, false),
+        };
+        let (name, amount) = match rest.strip_suffix('2') {
+            Some(name) => (name, 2),
+            None => (rest, 1),
+        };
+        let face = face_named(name)?;
+        Ok(MegaminxMove::Face { face, turns: if prime { -amount } else { amount } })
+    }
+}
+
+/// Where the three planes `n · x = 1` meet
+fn plane_intersection(a: Point, b: Point, c: Point) -> Point {
+    let sum = add(add(cross(b, c), cross(c, a)), cross(a, b));
+    scale(sum, 1.0 / dot(a, cross(b, c)))
+}
+
+/// A face's five corners, counter-clockwise seen from outside, starting
+/// from the corner at the top of the face in the net
+pub fn face_vertices(face: MegaminxFace) -> [Point; 5] {
+    let normal = face.normal();
+    let neighbours = face.neighbours();
+    let mut vertices = Vec::with_capacity(5);
+    for (i, a) in neighbours.iter().enumerate() {
+        for b in neighbours[i + 1..].iter().filter(|b| a.is_neighbour(**b)) {
+            vertices.push(plane_intersection(normal, a.normal(), b.normal()));
+        }
+    }
+
+    let reference: Point = match face {
+        MegaminxFace::U => [0.0, 0.0, -1.0],
+        MegaminxFace::D => [0.0, 0.0, 1.0],
+        f if (f as usize) < 6 => [0.0, -1.0, 0.0],
+        _ => [0.0, 1.0, 0.0],
+    };
+    let reference = normalize(sub(reference, scale(normal, dot(reference, normal))));
+    let angle = |v: &Point| {
+        let offset = sub(*v, normal);
+        let angle = dot(cross(reference, offset), normal).atan2(dot(reference, offset));
+        // The first corner sits right on the reference direction
+        if angle < -0.1 {
+            angle + 2.0 * std::f32::consts::PI
+        } else {
+            angle
+        }
+    };
+    vertices.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+    vertices.try_into().expect("a face has five corners")
+}
+
+/// Outline of a sticker, counter-clockwise seen from outside
+pub fn sticker_outline(face: MegaminxFace, sticker: usize) -> Vec<Point> {
+    let vertices = face_vertices(face);
+    let side = CORNER_FRACTION * dot(sub(vertices[1], vertices[0]), sub(vertices[1], vertices[0])).sqrt();
+    let along = |i: usize, to: usize| add(vertices[i % 5], scale(normalize(sub(vertices[to % 5], vertices[i % 5])), side));
+    // Inner corner of corner sticker `i`, where the center's corners are
+    let inner = |i: usize| add(along(i, i + 1), sub(along(i, i + 4), vertices[i % 5]));
+    match sticker {
+        0 => (0..5).map(inner).collect(),
+        1..=5 => {
+            let i = sticker - 1;
+            vec![vertices[i], along(i, i + 1), inner(i), along(i, i + 4)]
+        }
+        _ => {
+            let i = sticker - 6;
+            vec![along(i, i + 1), along(i + 1, i), inner(i + 1), inner(i)]
+        }
+    }
+}
+
+/// Everything about the puzzle's shape that moves and validation need
+struct Geometry {
+    /// Clockwise fifth turns of each face, in [`MegaminxFace::ALL`] order
+    face_tables: Vec<Vec<usize>>,
+    /// Clockwise fifth turns of everything but the opposite layer
+    pochmann_tables: Vec<Vec<usize>>,
+    /// Sticker positions (face * 11 + sticker) of each corner piece
+    corners: Vec<Vec<usize>>,
+    /// Sticker positions of each edge piece
+    edges: Vec<Vec<usize>>,
+}
+
+/// Sticker positions grouped by where a point on them lies
+fn pieces(points: impl Iterator<Item = (usize, Point)>) -> Vec<Vec<usize>> {
+    let mut groups: Vec<(Point, Vec<usize>)> = Vec::new();
+    for (position, point) in points {
+        match groups.iter_mut().find(|(p, _)| dot(sub(*p, point), sub(*p, point)) < 1e-6) {
+            Some((_, positions)) => positions.push(position),
+            None => groups.push((point, vec![position])),
+        }
+    }
+    groups.into_iter().map(|(_, positions)| positions).collect()
+}
+
+fn geometry() -> &'static Geometry {
+    static GEOMETRY: OnceLock<Geometry> = OnceLock::new();
+    GEOMETRY.get_or_init(|| {
+        let centers: Vec<Point> = MegaminxFace::ALL
+            .into_iter()
+            .flat_map(|face| (0..STICKERS_PER_FACE).map(move |i| centroid(&sticker_outline(face, i))))
+            .collect();
+
+        // A layer is cut where the centers of its neighbours begin: at the
+        // height of the inner corners of F's center seen from U
+        let up = MegaminxFace::U.normal();
+        let cut = sticker_outline(MegaminxFace::F, 0).into_iter().map(|p| dot(p, up)).fold(f32::MIN, f32::max);
+        let in_layer = |p: Point, face: MegaminxFace| dot(p, face.normal()) > cut;
+        let fifth = -2.0 * std::f32::consts::PI / 5.0;
+
+        let face_tables = MegaminxFace::ALL
+            .into_iter()
+            .map(|face| sticker_permutation(&centers, |p| in_layer(p, face).then(|| rotate(p, face.normal(), fifth))))
+            .collect();
+        let pochmann_tables = MegaminxFace::ALL
+            .into_iter()
+            .map(|axis| {
+                sticker_permutation(&centers, |p| {
+                    (!in_layer(p, axis.opposite())).then(|| rotate(p, axis.normal(), fifth))
+                })
+            })
+            .collect();
+
+        let positions = |range: std::ops::Range<usize>| {
+            MegaminxFace::ALL.into_iter().enumerate().flat_map(move |(f, face)| {
+                let vertices = face_vertices(face);
+                range.clone().map(move |sticker| {
+                    // Corners meet at a vertex, edges share a midpoint
+                    let point = if sticker <= 5 {
+                        vertices[sticker - 1]
+                    } else {
+                        scale(add(vertices[sticker - 6], vertices[(sticker - 5) % 5]), 0.5)
+                    };
+                    (f * STICKERS_PER_FACE + sticker, point)
+                })
+            })
+        };
+
+        Geometry {
+            face_tables,
+            pochmann_tables,
+            corners: pieces(positions(1..6)),
+            edges: pieces(positions(6..11)),
+        }
+    })
+}
+
+/// Why stickers entered by hand cannot be a Megaminx
+#[derive(Debug, Clone, PartialEq)]
+pub enum MegaminxError {
+    /// A color is on a number of stickers other than eleven
+    ColorCount { color: MegaminxColor, count: usize },
+    /// Two faces have the same center color
+    DuplicateCenter(MegaminxColor),
+    /// A corner or edge has colors that never meet, found at this sticker
+    InvalidPiece { face: MegaminxFace, sticker: usize },
+    /// The same corner or edge is on the puzzle twice, found at this sticker
+    DuplicatePiece { face: MegaminxFace, sticker: usize },
+}
+
+impl std::fmt::Display for MegaminxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MegaminxError::ColorCount { color, count } => {
+                write!(f, "{} is on {} stickers instead of {}", color.name(), count, STICKERS_PER_FACE)
+            }
+            MegaminxError::DuplicateCenter(color) => write!(f, "Two centers are {}", color.name()),
+            MegaminxError::InvalidPiece { face, sticker } => {
+                write!(f, "The piece at {} sticker {} has colors that do not belong together", face.name(), sticker)
+            }
+            MegaminxError::DuplicatePiece { face, sticker } => {
+                write!(f, "The piece at {} sticker {} is on the puzzle twice", face.name(), sticker)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MegaminxError {}
+
+/// A Megaminx's stickers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Megaminx {
+    /// Face by face in [`MegaminxFace::ALL`] order, eleven stickers each
+    stickers: [MegaminxColor; STICKER_COUNT],
+}
+
+impl Default for Megaminx {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Megaminx {
+    /// A solved Megaminx
+    pub fn new() -> Self {
+        Self { stickers: std::array::from_fn(|i| MegaminxFace::ALL[i / STICKERS_PER_FACE].color()) }
+    }
+
+    /// Color of one sticker
+    pub fn sticker(&self, face: MegaminxFace, sticker: usize) -> MegaminxColor {
+        self.stickers[face as usize * STICKERS_PER_FACE + sticker]
+    }
+
+    /// Recolor one sticker, e.g. while entering a puzzle by hand
+    pub fn set_sticker(&mut self, face: MegaminxFace, sticker: usize, color: MegaminxColor) {
+        self.stickers[face as usize * STICKERS_PER_FACE + sticker] = color;
+    }
+
+    /// The eleven stickers of a face
+    pub fn face(&self, face: MegaminxFace) -> &[MegaminxColor] {
+        let start = face as usize * STICKERS_PER_FACE;
+        &self.stickers[start..start + STICKERS_PER_FACE]
+    }
+
+    /// Whether every face shows one color
+    pub fn is_solved(&self) -> bool {
+        self.stickers.chunks(STICKERS_PER_FACE).all(|face| face.iter().all(|c| *c == face[0]))
+    }
+
+    /// Turn the puzzle
+    pub fn apply_move(&mut self, mv: MegaminxMove) {
+        let geometry = geometry();
+        let (table, times) = match mv {
+            MegaminxMove::Face { face, turns } => (&geometry.face_tables[face as usize], turns.rem_euclid(5)),
+            MegaminxMove::Pochmann { axis, clockwise } => {
+                (&geometry.pochmann_tables[axis as usize], if clockwise { 2 } else { 3 })
+            }
+        };
+        for _ in 0..times {
+            let old = self.stickers;
+            self.stickers = std::array::from_fn(|i| old[table[i]]);
+        }
+    }
+
+    /// Apply moves in order
+    pub fn apply_moves(&mut self, moves: &[MegaminxMove]) {
+        for mv in moves {
+            self.apply_move(*mv);
+        }
+    }
+
+    /// Check that the stickers could be a real Megaminx: eleven of each
+    /// color, twelve different centers, and every corner and edge present
+    /// once with colors that meet on a solved puzzle
+    ///
+    /// Twisted corners and flipped edges are not detected.
+    pub fn validate(&self) -> Result<(), MegaminxError> {
+        for color in MegaminxColor::ALL {
+            let count = self.stickers.iter().filter(|c| **c == color).count();
+            if count != STICKERS_PER_FACE {
+                return Err(MegaminxError::ColorCount { color, count });
+            }
+        }
+        let centers: Vec<MegaminxColor> = MegaminxFace::ALL.into_iter().map(|f| self.sticker(f, 0)).collect();
+        for (i, color) in centers.iter().enumerate() {
+            if centers[..i].contains(color) {
+                return Err(MegaminxError::DuplicateCenter(*color));
+            }
+        }
+
+        let geometry = geometry();
+        for slots in [&geometry.corners, &geometry.edges] {
+            // Pieces as the sorted colors of the centers they sit between
+            let colors = |positions: &Vec<usize>, of: &dyn Fn(usize) -> MegaminxColor| {
+                let mut colors: Vec<MegaminxColor> = positions.iter().map(|&p| of(p)).collect();
+                colors.sort();
+                colors
+            };
+            let solved: Vec<Vec<MegaminxColor>> =
+                slots.iter().map(|s| colors(s, &|p| centers[p / STICKERS_PER_FACE])).collect();
+            let mut seen = Vec::new();
+            for slot in slots.iter() {
+                let piece = colors(slot, &|p| self.stickers[p]);
+                let at = (MegaminxFace::ALL[slot[0] / STICKERS_PER_FACE], slot[0] % STICKERS_PER_FACE);
+                if !solved.contains(&piece) {
+                    return Err(MegaminxError::InvalidPiece { face: at.0, sticker: at.1 });
+                }
+                if seen.contains(&piece) {
+                    return Err(MegaminxError::DuplicatePiece { face: at.0, sticker: at.1 });
+                }
+                seen.push(piece);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Puzzle for Megaminx {
+    type Move = MegaminxMove;
+
+    fn name(&self) -> String {
+        "Megaminx".to_string()
+    }
+
+    fn is_solved(&self) -> bool {
+        Megaminx::is_solved(self)
+    }
+
+    fn apply_move(&mut self, mv: &MegaminxMove) {
+        Megaminx::apply_move(self, *mv);
+    }
+
+    fn parse_move(token: &str) -> Result<MegaminxMove, NotationError> {
+        MegaminxMove::parse(token)
+    }
+
+    fn move_notation(mv: &MegaminxMove) -> String {
+        mv.to_notation()
+    }
+}
+
+/// A random WCA-style scramble: seven lines of ten alternating R and D
+/// moves, each line ending with a U turn the same way as its last D move
+pub fn random_scramble<R: Rng>(rng: &mut R) -> Vec<MegaminxMove> {
+    let mut moves = Vec::with_capacity(SCRAMBLE_LINES * (MOVES_PER_LINE + 1));
+    for _ in 0..SCRAMBLE_LINES {
+        let mut clockwise = true;
+        for i in 0..MOVES_PER_LINE {
+            clockwise = rng.gen_bool(0.5);
+            let axis = if i % 2 == 1 { MegaminxFace::D } else { MegaminxFace::R };
+            moves.push(MegaminxMove::Pochmann { axis, clockwise });
+        }
+        moves.push(MegaminxMove::Face { face: MegaminxFace::U, turns: if clockwise { 1 } else { -1 } });
+    }
+    moves
+}
+
+/// Scramble notation with a line break after every U turn, as scramble
+/// sheets print it
+pub fn scramble_lines(moves: &[MegaminxMove]) -> String {
+    let mut text = String::new();
+    for mv in moves {
+        text.push_str(&mv.to_notation());
+        let ends_line = matches!(mv, MegaminxMove::Face { face: MegaminxFace::U, .. });
+        text.push(if ends_line { '\n' } else { ' ' });
+    }
+    text.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn apply(notation: &str) -> Megaminx {
+        let mut megaminx = Megaminx::new();
+        megaminx.apply_notation(notation).unwrap();
+        megaminx
+    }
+
+    fn changed(megaminx: &Megaminx) -> usize {
+        megaminx.stickers.iter().zip(Megaminx::new().stickers.iter()).filter(|(a, b)| a != b).count()
+    }
+
+    #[test]
+    fn test_notation_round_trip() {
+        for token in ["U", "U'", "BR2", "DBL2'", "R++", "D--"] {
+            assert_eq!(MegaminxMove::parse(token).unwrap().to_notation(), token);
+        }
+        assert_eq!(MegaminxMove::parse("F2'").unwrap(), MegaminxMove::Face { face: MegaminxFace::F, turns: -2 });
+        for bad in ["X", "U3", "F++", "R+", "U''"] {
+            assert!(MegaminxMove::parse(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_faces() {
+        for face in MegaminxFace::ALL {
+            assert_eq!(face.neighbours().len(), 5);
+            assert!(!face.is_neighbour(face.opposite()));
+            assert_eq!(face.opposite().opposite(), face);
+            assert!((dot(face.normal(), face.opposite().normal()) + 1.0).abs() < 1e-5);
+        }
+        assert!(MegaminxFace::U.neighbours().contains(&MegaminxFace::F));
+        assert!(MegaminxFace::D.neighbours().contains(&MegaminxFace::B));
+    }
+
+    #[test]
+    fn test_face_turns() {
+        for face in MegaminxFace::ALL {
+            let mv = MegaminxMove::Face { face, turns: 1 };
+            let mut megaminx = Megaminx::new();
+            megaminx.apply_move(mv);
+            // Three stickers on each of the five neighbours move
+            assert_eq!(changed(&megaminx), 15, "{}", face.name());
+            for _ in 0..4 {
+                megaminx.apply_move(mv);
+            }
+            assert!(megaminx.is_solved());
+        }
+        assert_eq!(apply("R2"), apply("R R"));
+        assert_eq!(apply("R2'"), apply("R R R"));
+    }
+
+    #[test]
+    fn test_pochmann_moves() {
+        assert_eq!(apply("R++ R--"), Megaminx::new());
+        assert_eq!(apply("D++ D++ D++ D++ D++"), Megaminx::new());
+        // Turning everything but U leaves U's face as it was, and the
+        // puzzle is still solved apart from that layer
+        let megaminx = apply("D++");
+        assert!(megaminx.face(MegaminxFace::U).iter().all(|c| *c == MegaminxColor::White));
+        assert!(!megaminx.is_solved());
+        // Following D++ with U2' turns the whole puzzle
+        let turned = apply("D++ U2'");
+        assert!(turned.is_solved());
+        assert_ne!(turned, Megaminx::new());
+    }
+
+    #[test]
+    fn test_random_scramble() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let scramble = random_scramble(&mut rng);
+        assert_eq!(scramble.len(), SCRAMBLE_LINES * (MOVES_PER_LINE + 1));
+        let text = scramble_lines(&scramble);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), SCRAMBLE_LINES);
+        for line in &lines {
+            assert!(line.starts_with("R"));
+            assert!(line.ends_with("D++ U") || line.ends_with("D-- U'"), "{}", line);
+        }
+        // The sheet layout reads back as the same moves
+        assert_eq!(Megaminx::parse_sequence(&text).unwrap(), scramble);
+
+        let mut megaminx = Megaminx::new();
+        megaminx.apply_moves(&scramble);
+        assert!(!megaminx.is_solved());
+        assert_eq!(megaminx.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(Megaminx::new().validate(), Ok(()));
+        assert_eq!(geometry().corners.len(), 20);
+        assert_eq!(geometry().edges.len(), 30);
+
+        let mut megaminx = Megaminx::new();
+        megaminx.set_sticker(MegaminxFace::U, 1, MegaminxColor::Gray);
+        assert_eq!(
+            megaminx.validate(),
+            Err(MegaminxError::ColorCount { color: MegaminxColor::White, count: 10 })
+        );
+
+        // Corner stickers swapped between pieces keep the counts right
+        let mut megaminx = Megaminx::new();
+        megaminx.set_sticker(MegaminxFace::F, 1, MegaminxColor::White);
+        megaminx.set_sticker(MegaminxFace::U, 1, MegaminxColor::Green);
+        assert!(matches!(megaminx.validate(), Err(MegaminxError::InvalidPiece { .. })));
+
+        let mut megaminx = Megaminx::new();
+        megaminx.set_sticker(MegaminxFace::U, 0, MegaminxColor::Green);
+        megaminx.set_sticker(MegaminxFace::F, 6, MegaminxColor::White);
+        assert_eq!(megaminx.validate(), Err(MegaminxError::DuplicateCenter(MegaminxColor::Green)));
+    }
+}
@@ -0,0 +1,144 @@
+//! SVG export of the unfolded Megaminx net
+//!
+//! The net is the usual two flowers: U with its five neighbours folded
+//! flat around it on the left, D with the lower ring on the right (seen
+//! from below, front at the top). The flowers are unfolded from the same
+//! dodecahedron the moves are worked out on, so every sticker lands next
+//! to the stickers it touches on the puzzle.
+
+use super::geometry::{add, dot, normalize, rotate, sub, Point};
+use super::megaminx::{face_vertices, sticker_outline, Megaminx, MegaminxFace, STICKERS_PER_FACE};
+
+/// Space between the two flowers, in edge lengths
+const FLOWER_GAP: f32 = 0.5;
+
+/// Margin around the net, in edge lengths
+const MARGIN: f32 = 0.2;
+
+/// Folds a face flat onto `hub`'s plane around their shared edge (the
+/// hub itself stays where it is)
+fn unfold(face: MegaminxFace, hub: MegaminxFace) -> impl Fn(Point) -> Point {
+    let hub_vertices = face_vertices(hub);
+    let shared: Vec<Point> = face_vertices(face)
+        .into_iter()
+        .filter(|v| hub_vertices.iter().any(|h| dot(sub(*h, *v), sub(*h, *v)) < 1e-6))
+        .collect();
+    let (pivot, axis) = (shared[0], normalize(sub(shared[1], shared[0])));
+    let angle = dot(face.normal(), hub.normal()).clamp(-1.0, 1.0).acos();
+    // Turn whichever way brings the face's normal onto the hub's
+    let flat = rotate(face.normal(), axis, angle);
+    let angle = if dot(flat, hub.normal()) > 0.99 { angle } else { -angle };
+    move |p| add(pivot, rotate(sub(p, pivot), axis, angle))
+}
+
+/// Every sticker's outline flattened into the plane, with its position
+/// (face * 11 + sticker); one flower per hub face
+fn flower(hub: MegaminxFace, to_plane: fn(Point) -> [f32; 2]) -> Vec<(usize, Vec<[f32; 2]>)> {
+    std::iter::once(hub)
+        .chain(hub.neighbours())
+        .flat_map(|face| {
+            let fold = unfold(face, hub);
+            (0..STICKERS_PER_FACE)
+                .map(|sticker| {
+                    let outline = sticker_outline(face, sticker).into_iter().map(|p| to_plane(fold(p))).collect();
+                    (face as usize * STICKERS_PER_FACE + sticker, outline)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Lowest x and y of the outlines
+fn min_corner(outlines: &[(usize, Vec<[f32; 2]>)]) -> [f32; 2] {
+    let points = || outlines.iter().flat_map(|(_, outline)| outline.iter());
+    [points().map(|p| p[0]).fold(f32::MAX, f32::min), points().map(|p| p[1]).fold(f32::MAX, f32::min)]
+}
+
+/// Highest x and y of the outlines
+fn max_corner(outlines: &[(usize, Vec<[f32; 2]>)]) -> [f32; 2] {
+    let points = || outlines.iter().flat_map(|(_, outline)| outline.iter());
+    [points().map(|p| p[0]).fold(f32::MIN, f32::max), points().map(|p| p[1]).fold(f32::MIN, f32::max)]
+}
+
+/// Move outlines so their lowest corner is at `to`
+fn shift(outlines: &mut [(usize, Vec<[f32; 2]>)], to: [f32; 2]) {
+    let from = min_corner(outlines);
+    for (_, outline) in outlines.iter_mut() {
+        for p in outline.iter_mut() {
+            *p = [p[0] - from[0] + to[0], p[1] - from[1] + to[1]];
+        }
+    }
+}
+
+impl Megaminx {
+    /// Draw the unfolded net as an SVG image, with face edges
+    /// `edge_length` units long
+    pub fn to_svg(&self, edge_length: f32) -> String {
+        let [a, b, ..] = face_vertices(MegaminxFace::U);
+        let scale = edge_length / dot(sub(b, a), sub(b, a)).sqrt();
+
+        // U seen from above with the front at the bottom, D seen from below
+        // with the front at the top
+        let mut upper = flower(MegaminxFace::U, |p| [p[0], p[2]]);
+        let mut lower = flower(MegaminxFace::D, |p| [p[0], -p[2]]);
+        let margin = MARGIN * edge_length / scale;
+        shift(&mut upper, [margin, margin]);
+        let upper_right = max_corner(&upper)[0];
+        shift(&mut lower, [upper_right + FLOWER_GAP * edge_length / scale, margin]);
+
+        let outlines: Vec<_> = upper.into_iter().chain(lower).collect();
+        let far = max_corner(&outlines);
+        let (width, height) = ((far[0] + margin) * scale, (far[1] + margin) * scale);
+
+        let mut svg = vec![format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w:.0}" height="{h:.0}" viewBox="0 0 {w:.0} {h:.0}">"#,
+            w = width.ceil(),
+            h = height.ceil()
+        )];
+        for (position, outline) in outlines {
+            let points: Vec<String> =
+                outline.iter().map(|p| format!("{:.1},{:.1}", p[0] * scale, p[1] * scale)).collect();
+            svg.push(format!(
+                r##"<polygon points="{}" fill="{}" stroke="#000000" stroke-width="1"/>"##,
+                points.join(" "),
+                self.sticker(MegaminxFace::ALL[position / STICKERS_PER_FACE], position % STICKERS_PER_FACE).fill()
+            ));
+        }
+        svg.push("</svg>".to_string());
+        svg.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::megaminx::MegaminxColor;
+    use crate::puzzle::Puzzle;
+
+    #[test]
+    fn test_net_has_every_sticker() {
+        let mut megaminx = Megaminx::new();
+        megaminx.apply_notation("R++ D-- U").unwrap();
+        let svg = megaminx.to_svg(40.0);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<polygon").count(), 132);
+        for color in MegaminxColor::ALL {
+            assert_eq!(svg.matches(&format!(r#"fill="{}""#, color.fill())).count(), 11, "{:?}", color);
+        }
+    }
+
+    #[test]
+    fn test_unfolded_faces_lie_flat() {
+        for face in MegaminxFace::U.neighbours() {
+            let fold = unfold(face, MegaminxFace::U);
+            for p in face_vertices(face) {
+                // U is the plane y = 1
+                assert!((fold(p)[1] - 1.0).abs() < 1e-4);
+            }
+            // Folded outward, not onto U itself
+            let center = fold(face.normal());
+            assert!(center[0].hypot(center[2]) > 1.0);
+        }
+    }
+}
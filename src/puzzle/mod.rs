@@ -0,0 +1,117 @@
+//! Twisty puzzles beyond the NxN cube
+//!
+//! [`Puzzle`] is what the app needs from any puzzle: a state that can be
+//! turned, checked for being solved, and read and written in its notation.
+//! The NxN [`Cube`] implements it with its usual notation, [`Pyraminx`]
+//! was the first puzzle that is not a cube, and [`Megaminx`] can be
+//! scrambled, entered by hand and drawn as a net (it has no solver yet).
+
+pub mod geometry;
+pub mod megaminx;
+pub mod megaminx_net;
+pub mod pyraminx;
+
+pub use megaminx::{Megaminx, MegaminxColor, MegaminxError, MegaminxFace, MegaminxMove};
+pub use pyraminx::{Corner, Pyraminx, PyraminxFace, PyraminxMove};
+
+use crate::cube::notation::{self, NotationError, ParsedMove};
+use crate::cube::Cube;
+
+/// A twisty puzzle's state together with its moves and notation
+pub trait Puzzle: Clone + PartialEq + std::fmt::Debug {
+    /// One turn of the puzzle
+    type Move: Clone + PartialEq + std::fmt::Debug;
+
+    /// Name shown to the user, e.g. "3x3" or "Pyraminx"
+    fn name(&self) -> String;
+
+    /// Whether every face shows a single color
+    fn is_solved(&self) -> bool;
+
+    /// Turn the puzzle
+    fn apply_move(&mut self, mv: &Self::Move);
+
+    /// Parse one move, e.g. "R'"
+    fn parse_move(token: &str) -> Result<Self::Move, NotationError>;
+
+    /// Notation of one move
+    fn move_notation(mv: &Self::Move) -> String;
+
+    /// Parse whitespace-separated moves; an empty string is no moves
+    fn parse_sequence(input: &str) -> Result<Vec<Self::Move>, NotationError> {
+        input.split_whitespace().map(Self::parse_move).collect()
+    }
+
+    /// Notation of a sequence of moves, separated by spaces
+    fn sequence_notation(moves: &[Self::Move]) -> String {
+        moves.iter().map(Self::move_notation).collect::<Vec<_>>().join(" ")
+    }
+
+    /// Turn the puzzle through every move in order
+    fn apply_sequence(&mut self, moves: &[Self::Move]) {
+        for mv in moves {
+            self.apply_move(mv);
+        }
+    }
+
+    /// Parse `input` and apply it; nothing is applied if it does not parse
+    fn apply_notation(&mut self, input: &str) -> Result<(), NotationError> {
+        let moves = Self::parse_sequence(input)?;
+        self.apply_sequence(&moves);
+        Ok(())
+    }
+}
+
+impl Puzzle for Cube {
+    type Move = ParsedMove;
+
+    fn name(&self) -> String {
+        format!("{0}x{0}", self.size())
+    }
+
+    fn is_solved(&self) -> bool {
+        Cube::is_solved(self)
+    }
+
+    fn apply_move(&mut self, mv: &ParsedMove) {
+        self.apply_parsed_move(mv);
+    }
+
+    fn parse_move(token: &str) -> Result<ParsedMove, NotationError> {
+        notation::parse_move(token)
+    }
+
+    fn move_notation(mv: &ParsedMove) -> String {
+        mv.to_notation()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Undoing a sequence move by move solves any puzzle
+    fn scramble_and_undo<P: Puzzle>(mut puzzle: P, scramble: &str, undo: &str) -> P {
+        puzzle.apply_notation(scramble).unwrap();
+        assert!(!puzzle.is_solved());
+        puzzle.apply_notation(undo).unwrap();
+        puzzle
+    }
+
+    #[test]
+    fn test_cube_is_a_puzzle() {
+        let cube = scramble_and_undo(Cube::new(3), "R U Rw'", "Rw U' R'");
+        assert!(Puzzle::is_solved(&cube));
+        assert_eq!(cube.name(), "3x3");
+        let moves = Cube::parse_sequence("R  U2 3Rw'").unwrap();
+        assert_eq!(Cube::sequence_notation(&moves), "R U2 3Rw'");
+        assert!(Cube::parse_sequence("R Q").is_err());
+    }
+
+    #[test]
+    fn test_pyraminx_is_a_puzzle() {
+        let pyraminx = scramble_and_undo(Pyraminx::new(), "U L' r b", "b' r' L U'");
+        assert!(pyraminx.is_solved());
+        assert_eq!(pyraminx.name(), "Pyraminx");
+    }
+}
@@ -0,0 +1,436 @@
+//! Pyraminx: a tetrahedral puzzle with four faces of nine stickers
+//!
+//! Each face is cut into nine small triangles, numbered row by row from the
+//! corner pointing up (or pointing away from the front, on the bottom
+//! face):
+//!
+//! ```text
+//!         0
+//!       1 2 3
+//!     4 5 6 7 8
+//! ```
+//!
+//! Stickers 0, 4 and 8 are tips, 2, 5 and 7 are the axial centers under
+//! them, and 1, 3 and 6 are edges. A move turns the third of the puzzle
+//! around one of the four corners (U, L, R, B) or only its tip (u, l, r,
+//! b), 120° clockwise seen from that corner.
+//!
+//! Moves are worked out from the puzzle's geometry: every sticker is a
+//! triangle on a regular tetrahedron, and a move rotates the stickers near
+//! a corner about the axis through it. The same triangles are used to draw
+//! the puzzle.
+
+use super::geometry::{add, centroid, dot, lerp, rotate, sticker_permutation, sub, Point};
+use super::Puzzle;
+use crate::cube::notation::NotationError;
+use crate::cube::Color;
+use rand::Rng;
+use std::sync::OnceLock;
+
+/// Stickers on each face
+pub const STICKERS_PER_FACE: usize = 9;
+
+/// Sticker numbers of a face's three tips
+pub const TIP_STICKERS: [usize; 3] = [0, 4, 8];
+
+/// The four corners, which moves turn around
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Corner {
+    U,
+    L,
+    R,
+    B,
+}
+
+impl Corner {
+    pub const ALL: [Corner; 4] = [Corner::U, Corner::L, Corner::R, Corner::B];
+
+    /// Position on a tetrahedron centered on the origin with corners one
+    /// unit away; U points up and the front face looks down +Z
+    pub fn position(self) -> Point {
+        let radius = 8f32.sqrt() / 3.0;
+        let half_width = radius * 3f32.sqrt() / 2.0;
+        match self {
+            Corner::U => [0.0, 1.0, 0.0],
+            Corner::L => [-half_width, -1.0 / 3.0, radius / 2.0],
+            Corner::R => [half_width, -1.0 / 3.0, radius / 2.0],
+            Corner::B => [0.0, -1.0 / 3.0, -radius],
+        }
+    }
+
+    fn letter(self) -> char {
+        match self {
+            Corner::U => 'U',
+            Corner::L => 'L',
+            Corner::R => 'R',
+            Corner::B => 'B',
+        }
+    }
+}
+
+/// The four faces, each opposite one corner
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PyraminxFace {
+    Front,
+    Left,
+    Right,
+    Down,
+}
+
+impl PyraminxFace {
+    pub const ALL: [PyraminxFace; 4] = [PyraminxFace::Front, PyraminxFace::Left, PyraminxFace::Right, PyraminxFace::Down];
+
+    /// Color of the face on a solved puzzle
+    pub fn color(self) -> Color {
+        match self {
+            PyraminxFace::Front => Color::Green,
+            PyraminxFace::Left => Color::Red,
+            PyraminxFace::Right => Color::Blue,
+            PyraminxFace::Down => Color::Yellow,
+        }
+    }
+
+    /// The face's corners as its top, bottom-left and bottom-right, seen
+    /// from outside
+    pub fn corners(self) -> [Corner; 3] {
+        match self {
+            PyraminxFace::Front => [Corner::U, Corner::L, Corner::R],
+            PyraminxFace::Left => [Corner::U, Corner::B, Corner::L],
+            PyraminxFace::Right => [Corner::U, Corner::R, Corner::B],
+            PyraminxFace::Down => [Corner::B, Corner::R, Corner::L],
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// One move: a layer or tip turned 120° clockwise, or counter-clockwise
+/// when `prime`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PyraminxMove {
+    pub corner: Corner,
+    /// Only the tip turns (lowercase in notation)
+    pub tip: bool,
+    pub prime: bool,
+}
+
+impl PyraminxMove {
+    /// A clockwise layer move
+    pub fn layer(corner: Corner) -> Self {
+        Self { corner, tip: false, prime: false }
+    }
+
+    /// A clockwise tip move
+    pub fn tip(corner: Corner) -> Self {
+        Self { corner, tip: true, prime: false }
+    }
+
+    /// The move that undoes this one
+    pub fn inverse(&self) -> Self {
+        Self { prime: !self.prime, ..*self }
+    }
+
+    /// Notation, e.g. "U", "l'"
+    pub fn to_notation(&self) -> String {
+        let letter = self.corner.letter();
+        let letter = if self.tip { letter.to_ascii_lowercase() } else { letter };
+        if self.prime {
+            format!("{}'", letter)
+        } else {
+            letter.to_string()
+        }
+    }
+
+    /// Parse a move such as "U", "r'" or "B'"
+    pub fn parse(token: &str) -> Result<Self, NotationError> {
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(NotationError::EmptyInput);
+        }
+        let invalid = || NotationError::InvalidMove(token.to_string());
+        let mut chars = token.chars();
+        let letter = chars.next().ok_or_else(invalid)?;
+        let prime = match chars.as_str() {
+            "" => false,
+            "'" => true,
+            _ => return Err(invalid()),
+        };
+        let corner = Corner::ALL
+            .into_iter()
+            .find(|c| c.letter() == letter.to_ascii_uppercase())
+            .ok_or_else(invalid)?;
+        Ok(Self { corner, tip: letter.is_ascii_lowercase(), prime })
+    }
+}
+
+/// Corners of a sticker's triangle, counter-clockwise seen from outside
+pub fn sticker_triangle(face: PyraminxFace, sticker: usize) -> [Point; 3] {
+    let [top, left, right] = face.corners().map(Corner::position);
+    // Point `col` of grid row `row`, both counted in thirds of a side
+    let grid = |row: usize, col: usize| {
+        let down = lerp(top, left, (row - col) as f32 / 3.0);
+        let across = sub(lerp(top, right, col as f32 / 3.0), top);
+        add(down, across)
+    };
+    let row = (0..3).find(|r| sticker < (r + 1) * (r + 1)).expect("a face has nine stickers");
+    let col = (sticker - row * row) / 2;
+    if (sticker - row * row) % 2 == 1 {
+        [grid(row, col), grid(row + 1, col + 1), grid(row, col + 1)]
+    } else {
+        [grid(row, col), grid(row + 1, col), grid(row + 1, col + 1)]
+    }
+}
+
+/// Every sticker's centroid, face by face
+fn centroids() -> Vec<Point> {
+    PyraminxFace::ALL
+        .into_iter()
+        .flat_map(|face| (0..STICKERS_PER_FACE).map(move |i| centroid(&sticker_triangle(face, i))))
+        .collect()
+}
+
+/// Whether a sticker centered at `p` turns with a move around `corner`
+///
+/// Corners are one unit from the center and the opposite faces 1/3 below
+/// it, so the layer cut lies at 1/9 and the tip cut at 5/9.
+fn turns_with(p: Point, corner: Corner, tip: bool) -> bool {
+    let height = dot(p, corner.position());
+    if tip {
+        height > 5.0 / 9.0
+    } else {
+        height > 1.0 / 9.0
+    }
+}
+
+/// Sticker permutations: `new[i] = old[table[i]]` for a clockwise move
+type MoveTable = [usize; 4 * STICKERS_PER_FACE];
+
+/// Clockwise layer tables followed by clockwise tip tables, in
+/// [`Corner::ALL`] order
+fn move_tables() -> &'static [MoveTable; 8] {
+    static TABLES: OnceLock<[MoveTable; 8]> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let centers = centroids();
+        let table = |corner: Corner, tip: bool| {
+            // Clockwise seen from the corner, looking down the axis
+            let turn = |p: Point| turns_with(p, corner, tip).then(|| rotate(p, corner.position(), -2.0 * std::f32::consts::PI / 3.0));
+            sticker_permutation(&centers, turn).try_into().expect("one entry per sticker")
+        };
+        std::array::from_fn(|i| table(Corner::ALL[i % 4], i >= 4))
+    })
+}
+
+/// Sticker positions (face * 9 + sticker) of a corner's tip
+pub fn tip_positions(corner: Corner) -> Vec<usize> {
+    centroids().into_iter().enumerate().filter(|(_, p)| turns_with(*p, corner, true)).map(|(i, _)| i).collect()
+}
+
+/// A Pyraminx's stickers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pyraminx {
+    /// Face by face in [`PyraminxFace::ALL`] order, nine stickers each
+    stickers: [Color; 4 * STICKERS_PER_FACE],
+}
+
+impl Default for Pyraminx {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pyraminx {
+    /// A solved Pyraminx
+    pub fn new() -> Self {
+        Self { stickers: std::array::from_fn(|i| PyraminxFace::ALL[i / STICKERS_PER_FACE].color()) }
+    }
+
+    /// A Pyraminx with the given stickers, face by face in
+    /// [`PyraminxFace::ALL`] order
+    pub fn from_stickers(stickers: [Color; 4 * STICKERS_PER_FACE]) -> Self {
+        Self { stickers }
+    }
+
+    /// Color of one sticker
+    pub fn sticker(&self, face: PyraminxFace, sticker: usize) -> Color {
+        self.stickers[face.index() * STICKERS_PER_FACE + sticker]
+    }
+
+    /// The nine stickers of a face
+    pub fn face(&self, face: PyraminxFace) -> &[Color] {
+        let start = face.index() * STICKERS_PER_FACE;
+        &self.stickers[start..start + STICKERS_PER_FACE]
+    }
+
+    /// Every sticker, face by face
+    pub fn stickers(&self) -> &[Color] {
+        &self.stickers
+    }
+
+    /// Whether every face shows one color
+    pub fn is_solved(&self) -> bool {
+        self.stickers.chunks(STICKERS_PER_FACE).all(|face| face.iter().all(|c| *c == face[0]))
+    }
+
+    /// Turn a layer or tip
+    pub fn apply_move(&mut self, mv: PyraminxMove) {
+        let table = &move_tables()[mv.corner as usize + if mv.tip { 4 } else { 0 }];
+        // Counter-clockwise is two clockwise turns
+        for _ in 0..if mv.prime { 2 } else { 1 } {
+            let old = self.stickers;
+            self.stickers = table.map(|from| old[from]);
+        }
+    }
+
+    /// Apply moves in order
+    pub fn apply_moves(&mut self, moves: &[PyraminxMove]) {
+        for mv in moves {
+            self.apply_move(*mv);
+        }
+    }
+}
+
+impl Puzzle for Pyraminx {
+    type Move = PyraminxMove;
+
+    fn name(&self) -> String {
+        "Pyraminx".to_string()
+    }
+
+    fn is_solved(&self) -> bool {
+        Pyraminx::is_solved(self)
+    }
+
+    fn apply_move(&mut self, mv: &PyraminxMove) {
+        Pyraminx::apply_move(self, *mv);
+    }
+
+    fn parse_move(token: &str) -> Result<PyraminxMove, NotationError> {
+        PyraminxMove::parse(token)
+    }
+
+    fn move_notation(mv: &PyraminxMove) -> String {
+        mv.to_notation()
+    }
+}
+
+/// A random scramble: `length` layer moves, never turning the same corner
+/// twice in a row, then a random turn (or none) of each tip, as WCA
+/// Pyraminx scrambles are written
+pub fn random_scramble<R: Rng>(rng: &mut R, length: usize) -> Vec<PyraminxMove> {
+    let mut moves: Vec<PyraminxMove> = Vec::with_capacity(length + 4);
+    while moves.len() < length {
+        let corner = Corner::ALL[rng.gen_range(0..4)];
+        if moves.last().is_some_and(|last| last.corner == corner) {
+            continue;
+        }
+        moves.push(PyraminxMove { corner, tip: false, prime: rng.gen_bool(0.5) });
+    }
+    for corner in Corner::ALL {
+        match rng.gen_range(0..3) {
+            1 => moves.push(PyraminxMove::tip(corner)),
+            2 => moves.push(PyraminxMove::tip(corner).inverse()),
+            _ => {}
+        }
+    }
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::geometry::cross;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn apply(notation: &str) -> Pyraminx {
+        let mut pyraminx = Pyraminx::new();
+        pyraminx.apply_notation(notation).unwrap();
+        pyraminx
+    }
+
+    #[test]
+    fn test_notation_round_trip() {
+        for token in ["U", "L'", "r", "b'"] {
+            assert_eq!(PyraminxMove::parse(token).unwrap().to_notation(), token);
+        }
+        assert_eq!(PyraminxMove::parse("u'").unwrap(), PyraminxMove::tip(Corner::U).inverse());
+        assert!(PyraminxMove::parse("F").is_err());
+        assert!(PyraminxMove::parse("U2").is_err());
+        assert_eq!(PyraminxMove::parse(" "), Err(NotationError::EmptyInput));
+    }
+
+    #[test]
+    fn test_moves_have_order_three() {
+        for corner in Corner::ALL {
+            for mv in [PyraminxMove::layer(corner), PyraminxMove::tip(corner)] {
+                let mut pyraminx = Pyraminx::new();
+                pyraminx.apply_move(mv);
+                assert!(!pyraminx.is_solved());
+                pyraminx.apply_move(mv);
+                pyraminx.apply_move(mv);
+                assert!(pyraminx.is_solved(), "{} three times", mv.to_notation());
+
+                pyraminx.apply_move(mv);
+                pyraminx.apply_move(mv.inverse());
+                assert!(pyraminx.is_solved());
+            }
+        }
+    }
+
+    #[test]
+    fn test_move_sizes() {
+        let changed = |notation: &str| {
+            let solved = Pyraminx::new();
+            apply(notation).stickers().iter().zip(solved.stickers()).filter(|(a, b)| a != b).count()
+        };
+        // A tip shows three stickers, one on each face next to its corner
+        assert_eq!(changed("u"), 3);
+        // A layer is the tip, the axial center and three edges: 3 + 3 + 6
+        assert_eq!(changed("U"), 12);
+        for corner in Corner::ALL {
+            assert_eq!(tip_positions(corner).len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_u_turns_front_to_left() {
+        // Clockwise seen from above, the front's top third moves to the left face
+        let pyraminx = apply("U");
+        assert_eq!(pyraminx.sticker(PyraminxFace::Left, 0), Color::Green);
+        assert_eq!(pyraminx.sticker(PyraminxFace::Right, 2), Color::Red);
+        assert_eq!(pyraminx.sticker(PyraminxFace::Front, 3), Color::Blue);
+        assert_eq!(pyraminx.face(PyraminxFace::Down), &[Color::Yellow; 9]);
+    }
+
+    #[test]
+    fn test_tips_and_layers_commute() {
+        assert_eq!(apply("u R"), apply("R u"));
+        assert_ne!(apply("U R"), apply("R U"));
+    }
+
+    #[test]
+    fn test_faces_face_outward() {
+        for face in PyraminxFace::ALL {
+            for sticker in 0..STICKERS_PER_FACE {
+                let [a, b, c] = sticker_triangle(face, sticker);
+                let normal = cross(sub(b, a), sub(c, a));
+                assert!(dot(normal, centroid(&[a, b, c])) > 0.0, "{:?} {}", face, sticker);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_scramble() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let scramble = random_scramble(&mut rng, 11);
+        let layers: Vec<_> = scramble.iter().filter(|m| !m.tip).collect();
+        assert_eq!(layers.len(), 11);
+        assert!(layers.windows(2).all(|w| w[0].corner != w[1].corner));
+        assert!(scramble.len() <= 15);
+        let mut pyraminx = Pyraminx::new();
+        pyraminx.apply_moves(&scramble);
+        assert!(!pyraminx.is_solved());
+    }
+}
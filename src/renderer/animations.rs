@@ -6,6 +6,8 @@
 //! - Configurable duration
 //! - Queue multiple animations
 
+use super::caption::MoveCaption;
+use crate::cube::{Direction, LayerMove, Move, ParsedMove, WideFace, WideMove};
 use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
@@ -51,6 +53,8 @@ pub struct RotationAnimation {
     pub duration: Duration,
     /// Easing function to use
     pub easing: EasingFunction,
+    /// Caption to show while the move plays (if any)
+    pub caption: Option<MoveCaption>,
     /// When the animation started
     start_time: Option<Instant>,
 }
@@ -69,6 +73,57 @@ pub enum RotationFace {
     S,
 }
 
+/// Get the layer and angle to animate for a move
+///
+/// The angle is in degrees, positive for clockwise. Whole-cube rotations have
+/// no layer to animate and return `None`.
+pub fn rotation_for(mv: &ParsedMove) -> Option<(RotationFace, f32)> {
+    let (face, direction) = match mv {
+        // Inner layers animate as the face they are counted from
+        ParsedMove::Wide(WideMove { face, direction, .. }) | ParsedMove::Layer(LayerMove { face, direction, .. }) => {
+            let face = match face {
+                WideFace::R => RotationFace::R,
+                WideFace::L => RotationFace::L,
+                WideFace::U => RotationFace::U,
+                WideFace::D => RotationFace::D,
+                WideFace::F => RotationFace::F,
+                WideFace::B => RotationFace::B,
+            };
+            (face, *direction)
+        }
+        ParsedMove::Basic(mv) => {
+            let face = match mv {
+                Move::R | Move::RPrime | Move::R2 => RotationFace::R,
+                Move::L | Move::LPrime | Move::L2 => RotationFace::L,
+                Move::U | Move::UPrime | Move::U2 => RotationFace::U,
+                Move::D | Move::DPrime | Move::D2 => RotationFace::D,
+                Move::F | Move::FPrime | Move::F2 => RotationFace::F,
+                Move::B | Move::BPrime | Move::B2 => RotationFace::B,
+                Move::M | Move::MPrime | Move::M2 => RotationFace::M,
+                Move::E | Move::EPrime | Move::E2 => RotationFace::E,
+                Move::S | Move::SPrime | Move::S2 => RotationFace::S,
+                _ => return None,
+            };
+            let notation = mv.to_notation();
+            let direction = if notation.ends_with('2') {
+                Direction::Double
+            } else if notation.ends_with('\'') {
+                Direction::CounterClockwise
+            } else {
+                Direction::Clockwise
+            };
+            (face, direction)
+        }
+    };
+
+    let angle = match direction {
+        Direction::Clockwise => 90.0,
+        Direction::CounterClockwise => -90.0,
+        Direction::Double => 180.0,
+    };
+    Some((face, angle))
+}
+
 impl RotationAnimation {
     /// Creates a new rotation animation
     pub fn new(
@@ -82,10 +137,25 @@ impl RotationAnimation {
             angle_degrees,
             duration,
             easing,
+            caption: None,
             start_time: None,
         }
     }
 
+    /// Creates the animation for a move, captioned with its notation and name
+    ///
+    /// Returns None for whole-cube rotations, which have no layer to animate.
+    pub fn for_move(mv: &ParsedMove, duration: Duration, easing: EasingFunction) -> Option<Self> {
+        let (face, angle) = rotation_for(mv)?;
+        Some(Self::new(face, angle, duration, easing).with_caption(MoveCaption::for_move(mv)))
+    }
+
+    /// Sets the caption to show while the move plays
+    pub fn with_caption(mut self, caption: MoveCaption) -> Self {
+        self.caption = Some(caption);
+        self
+    }
+
     /// Creates a 90-degree clockwise rotation
     pub fn rotate_cw(face: RotationFace, duration: Duration) -> Self {
         Self::new(face, 90.0, duration, EasingFunction::EaseInOut)
@@ -196,6 +266,14 @@ impl AnimationQueue {
         self.current.as_ref()
     }
 
+    /// Returns the caption of the playing move and its opacity, for the
+    /// caption overlay
+    pub fn current_caption(&self) -> Option<(&MoveCaption, f32)> {
+        let current = self.current.as_ref()?;
+        let caption = current.caption.as_ref()?;
+        Some((caption, MoveCaption::opacity(current.progress().unwrap_or(0.0))))
+    }
+
     /// Returns the number of queued animations (not including current)
     pub fn queued_count(&self) -> usize {
         self.queue.len()
@@ -367,4 +445,29 @@ mod tests {
         assert_eq!(queue.queued_count(), 0);
         assert!(queue.current().is_none());
     }
+
+    #[test]
+    fn test_animation_queue_caption() {
+        let mut queue = AnimationQueue::new();
+        let captioned =
+            RotationAnimation::for_move(&ParsedMove::Basic(Move::UPrime), Duration::from_millis(50), EasingFunction::Linear)
+                .unwrap();
+        assert_eq!(captioned.face, RotationFace::U);
+        assert_eq!(captioned.angle_degrees, -90.0);
+        assert!(RotationAnimation::for_move(&ParsedMove::Basic(Move::Y), Duration::from_millis(50), EasingFunction::Linear)
+            .is_none());
+
+        queue.enqueue_multiple(vec![captioned, RotationAnimation::rotate_cw(RotationFace::R, Duration::from_millis(50))]);
+        assert!(queue.current_caption().is_none(), "Nothing is playing yet");
+
+        queue.update();
+        let (caption, opacity) = queue.current_caption().unwrap();
+        assert_eq!(caption.notation, "U'");
+        assert!((0.0..=1.0).contains(&opacity));
+
+        // The caption moves on with the animation
+        thread::sleep(Duration::from_millis(60));
+        queue.update();
+        assert!(queue.current_caption().is_none(), "Uncaptioned animations show no caption");
+    }
 }
@@ -0,0 +1,308 @@
+//! Arrow overlay showing the next move on the 3D cube
+//!
+//! Draws a curved arrow around the face or layer about to turn, pointing the
+//! way it turns: clockwise as seen from that face, counter-clockwise for a
+//! prime move and a longer half circle for a double move. Face turns get the
+//! arrow on the face itself; slice moves get a band around the middle layer.
+//!
+//! The overlay only knows the next move, so the solution player, the solve
+//! along guide or a practice hint can drive it with `set_next_move`.
+
+use super::animations::{rotation_for, RotationFace};
+use super::cube_mesh::Vertex;
+use crate::cube::ParsedMove;
+use glam::Vec3;
+use std::f32::consts::{FRAC_PI_2, SQRT_2};
+
+/// Configuration for next-move arrows
+#[derive(Debug, Clone)]
+pub struct ArrowConfig {
+    /// Size of the cube (distance from center to edge, as in `MeshConfig`)
+    pub cube_size: f32,
+    /// How far the arrow floats above the stickers
+    pub lift: f32,
+    /// Width of the arrow's shaft
+    pub width: f32,
+    /// Number of segments along the curve (higher = smoother)
+    pub segments: u32,
+    /// Arrow color (RGB)
+    pub color: Vec3,
+}
+
+impl Default for ArrowConfig {
+    fn default() -> Self {
+        Self {
+            cube_size: 1.0,
+            lift: 0.08,
+            width: 0.12,
+            segments: 24,
+            color: Vec3::new(1.0, 0.3, 0.8), // Pink, unlike any sticker
+        }
+    }
+}
+
+/// Generated mesh data for an arrow
+#[derive(Debug, Clone)]
+pub struct ArrowMesh {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+}
+
+impl ArrowMesh {
+    /// Returns the number of triangles in the mesh
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Adds a triangle, wound counter-clockwise as seen along `normal`
+    fn push_triangle(&mut self, corners: [Vec3; 3], normal: Vec3, uvs: [[f32; 2]; 3], color: Vec3) {
+        let [a, b, c] = corners;
+        let order = if (b - a).cross(c - a).dot(normal) < 0.0 { [0, 2, 1] } else { [0, 1, 2] };
+        let base = self.vertices.len() as u32;
+        for index in order {
+            self.vertices.push(Vertex {
+                position: corners[index].to_array(),
+                normal: normal.to_array(),
+                uv: uvs[index],
+                color: color.to_array(),
+            });
+        }
+        self.indices.extend_from_slice(&[base, base + 1, base + 2]);
+    }
+}
+
+/// The arrow for one move
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveArrow {
+    /// The face or slice that turns
+    pub face: RotationFace,
+    /// Turn in degrees, positive for clockwise (90, -90 or 180)
+    pub angle_degrees: f32,
+}
+
+impl MoveArrow {
+    /// Arrow for a move; whole-cube rotations have no layer and get none
+    pub fn for_move(mv: &ParsedMove) -> Option<Self> {
+        rotation_for(mv).map(|(face, angle_degrees)| Self { face, angle_degrees })
+    }
+
+    /// Check whether the arrow points clockwise (as seen from the face)
+    pub fn is_clockwise(&self) -> bool {
+        self.angle_degrees > 0.0
+    }
+
+    /// Check whether the arrow is for a half turn
+    pub fn is_half_turn(&self) -> bool {
+        self.angle_degrees.abs() > 90.0
+    }
+
+    /// Check whether the arrow goes around a middle slice
+    pub fn is_slice(&self) -> bool {
+        matches!(self.face, RotationFace::M | RotationFace::E | RotationFace::S)
+    }
+
+    /// Generates the arrow's mesh
+    ///
+    /// The arrow sweeps as far as the layer turns, centered on the top edge
+    /// of the face (the back edge for U, the front edge for D), and ends in a
+    /// triangular head.
+    pub fn mesh(&self, config: &ArrowConfig) -> ArrowMesh {
+        let (normal, u_dir, v_dir) = face_frame(self.face);
+        let (depth, radius) = if self.is_slice() {
+            (0.0, config.cube_size * SQRT_2 + config.lift)
+        } else {
+            (config.cube_size + config.lift, config.cube_size * 0.6)
+        };
+        let center = normal * depth;
+
+        // Clockwise as seen from outside is negative around the outward normal
+        let turn = -self.angle_degrees.signum();
+        let sweep = self.angle_degrees.abs().to_radians();
+        let head_length = config.width * 2.0;
+        let head_width = config.width * 2.5;
+        let shaft_sweep = (sweep - head_length / radius).max(0.0);
+        let start = FRAC_PI_2 - turn * sweep / 2.0;
+
+        let radial = |theta: f32| u_dir * theta.cos() + v_dir * theta.sin();
+        let point = |theta: f32| center + radial(theta) * radius;
+        // Face arrows lie flat on the face; slice bands wrap around the cube
+        let across = |theta: f32| if self.is_slice() { normal } else { radial(theta) };
+        let facing = |theta: f32| if self.is_slice() { radial(theta) } else { normal };
+
+        let mut mesh = ArrowMesh { vertices: Vec::new(), indices: Vec::new() };
+        let segments = config.segments.max(1);
+        let half_width = config.width / 2.0;
+        for i in 0..segments {
+            let (t0, t1) = (i as f32 / segments as f32, (i + 1) as f32 / segments as f32);
+            let (a0, a1) = (start + turn * shaft_sweep * t0, start + turn * shaft_sweep * t1);
+            let inner0 = point(a0) - across(a0) * half_width;
+            let outer0 = point(a0) + across(a0) * half_width;
+            let inner1 = point(a1) - across(a1) * half_width;
+            let outer1 = point(a1) + across(a1) * half_width;
+            let facing = facing((a0 + a1) / 2.0);
+            mesh.push_triangle([inner0, outer0, outer1], facing, [[t0, 0.0], [t0, 1.0], [t1, 1.0]], config.color);
+            mesh.push_triangle([inner0, outer1, inner1], facing, [[t0, 0.0], [t1, 1.0], [t1, 0.0]], config.color);
+        }
+
+        let base = start + turn * shaft_sweep;
+        let tip = point(start + turn * sweep);
+        let base_inner = point(base) - across(base) * (head_width / 2.0);
+        let base_outer = point(base) + across(base) * (head_width / 2.0);
+        mesh.push_triangle([base_inner, base_outer, tip], facing(base), [[1.0, 0.0], [1.0, 1.0], [1.0, 0.5]], config.color);
+        mesh
+    }
+}
+
+/// Outward normal of the face a layer turns like, and two directions across
+/// it (matching the cube mesh's sticker directions)
+fn face_frame(face: RotationFace) -> (Vec3, Vec3, Vec3) {
+    match face {
+        RotationFace::F | RotationFace::S => (Vec3::Z, Vec3::X, Vec3::Y),
+        RotationFace::B => (Vec3::NEG_Z, Vec3::NEG_X, Vec3::Y),
+        RotationFace::R => (Vec3::X, Vec3::NEG_Z, Vec3::Y),
+        RotationFace::L | RotationFace::M => (Vec3::NEG_X, Vec3::Z, Vec3::Y),
+        RotationFace::U => (Vec3::Y, Vec3::X, Vec3::NEG_Z),
+        RotationFace::D | RotationFace::E => (Vec3::NEG_Y, Vec3::X, Vec3::Z),
+    }
+}
+
+/// Shows an arrow for the next move, or nothing
+#[derive(Debug, Clone)]
+pub struct ArrowOverlay {
+    /// Arrow for the next move (if any)
+    arrow: Option<MoveArrow>,
+    /// Configuration for arrows
+    config: ArrowConfig,
+}
+
+impl Default for ArrowOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArrowOverlay {
+    /// Creates an overlay showing no arrow
+    pub fn new() -> Self {
+        Self::with_config(ArrowConfig::default())
+    }
+
+    /// Creates an overlay with custom configuration
+    pub fn with_config(config: ArrowConfig) -> Self {
+        Self { arrow: None, config }
+    }
+
+    /// Shows the arrow for the next move, or hides it with `None`
+    ///
+    /// Whole-cube rotations hide the arrow too.
+    pub fn set_next_move(&mut self, mv: Option<&ParsedMove>) {
+        self.arrow = mv.and_then(MoveArrow::for_move);
+    }
+
+    /// Gets the arrow being shown
+    pub fn arrow(&self) -> Option<MoveArrow> {
+        self.arrow
+    }
+
+    /// Hides the arrow
+    pub fn clear(&mut self) {
+        self.arrow = None;
+    }
+
+    /// Gets the current configuration
+    pub fn config(&self) -> &ArrowConfig {
+        &self.config
+    }
+
+    /// Updates the configuration
+    pub fn set_config(&mut self, config: ArrowConfig) {
+        self.config = config;
+    }
+
+    /// Generates the mesh to draw over the cube, if an arrow is shown
+    pub fn mesh(&self) -> Option<ArrowMesh> {
+        self.arrow.map(|arrow| arrow.mesh(&self.config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Move;
+
+    fn arrow(mv: Move) -> MoveArrow {
+        MoveArrow::for_move(&ParsedMove::Basic(mv)).unwrap()
+    }
+
+    /// Signed angle swept from the first to the last vertex, around `axis`
+    fn swept_around(mesh: &ArrowMesh, axis: Vec3) -> f32 {
+        let first = Vec3::from(mesh.vertices[0].position);
+        let last = Vec3::from(mesh.vertices[mesh.vertices.len() - 1].position);
+        let flat = |p: Vec3| p - axis * p.dot(axis);
+        let (from, to) = (flat(first), flat(last));
+        from.cross(to).dot(axis).atan2(from.dot(to))
+    }
+
+    #[test]
+    fn test_arrow_for_move() {
+        let r = arrow(Move::R);
+        assert_eq!(r.face, RotationFace::R);
+        assert!(r.is_clockwise());
+        assert!(!arrow(Move::UPrime).is_clockwise());
+        assert!(arrow(Move::F2).is_half_turn());
+        assert!(arrow(Move::M).is_slice());
+        assert_eq!(MoveArrow::for_move(&ParsedMove::Basic(Move::Y)), None);
+    }
+
+    #[test]
+    fn test_arrow_direction() {
+        let config = ArrowConfig::default();
+        // Clockwise as seen from the face is a negative turn around its normal
+        assert!(swept_around(&arrow(Move::R).mesh(&config), Vec3::X) < 0.0);
+        assert!(swept_around(&arrow(Move::RPrime).mesh(&config), Vec3::X) > 0.0);
+        assert!(swept_around(&arrow(Move::U).mesh(&config), Vec3::Y) < 0.0);
+        // M turns like L
+        assert!(swept_around(&arrow(Move::M).mesh(&config), Vec3::NEG_X) < 0.0);
+    }
+
+    #[test]
+    fn test_arrow_mesh_placement() {
+        let config = ArrowConfig::default();
+        let mesh = arrow(Move::F).mesh(&config);
+        assert_eq!(mesh.triangle_count(), config.segments as usize * 2 + 1);
+        assert!(mesh.indices.iter().all(|&i| (i as usize) < mesh.vertices.len()));
+        // Floats above the front face
+        assert!(mesh.vertices.iter().all(|v| (v.position[2] - (config.cube_size + config.lift)).abs() < 1e-5));
+        // Faces the viewer
+        assert!(mesh.vertices.iter().all(|v| v.normal == [0.0, 0.0, 1.0]));
+
+        // Slice bands wrap around the outside of the cube
+        let band = arrow(Move::E).mesh(&config);
+        assert!(band.vertices.iter().all(|v| Vec3::new(v.position[0], 0.0, v.position[2]).length() > config.cube_size));
+    }
+
+    #[test]
+    fn test_half_turn_arrow_is_longer() {
+        let config = ArrowConfig::default();
+        let quarter = swept_around(&arrow(Move::B).mesh(&config), Vec3::NEG_Z).abs();
+        let half = swept_around(&arrow(Move::B2).mesh(&config), Vec3::NEG_Z).abs();
+        assert!(half > quarter);
+    }
+
+    #[test]
+    fn test_overlay_follows_next_move() {
+        let mut overlay = ArrowOverlay::new();
+        assert!(overlay.mesh().is_none());
+
+        overlay.set_next_move(Some(&ParsedMove::Basic(Move::UPrime)));
+        assert_eq!(overlay.arrow().map(|a| a.face), Some(RotationFace::U));
+        assert!(overlay.mesh().is_some());
+
+        overlay.set_next_move(Some(&ParsedMove::Basic(Move::X)));
+        assert!(overlay.arrow().is_none(), "Rotations have no arrow");
+
+        overlay.set_next_move(Some(&ParsedMove::Basic(Move::D2)));
+        overlay.clear();
+        assert!(overlay.mesh().is_none());
+    }
+}
@@ -0,0 +1,174 @@
+//! Move captions shown during animation playback
+//!
+//! Each animated move can carry a caption: its notation and a plain-language
+//! name saying which way the layer goes ("Turn the right face up, away from
+//! you"). The animation queue hands out the caption of the move playing right
+//! now, so the caption overlay stays in step with the animation.
+
+use crate::cube::{Direction, Move, ParsedMove, WideFace};
+
+/// Portion of an animation spent fading the caption in
+const FADE_IN: f32 = 0.15;
+
+/// Notation and plain-language name of a move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveCaption {
+    /// Standard notation (e.g. "R'")
+    pub notation: String,
+    /// Plain-language name (e.g. "Turn the right face down, toward you")
+    pub name: String,
+}
+
+impl MoveCaption {
+    /// Creates the caption for a move
+    pub fn for_move(mv: &ParsedMove) -> Self {
+        Self { notation: mv.to_notation(), name: plain_name(mv) }
+    }
+
+    /// Single line of overlay text: notation, then name
+    pub fn text(&self) -> String {
+        format!("{} – {}", self.notation, self.name)
+    }
+
+    /// Opacity of the caption at a point of its animation (0.0 to 1.0)
+    ///
+    /// The caption fades in at the start and stays until the next move.
+    pub fn opacity(progress: f32) -> f32 {
+        (progress / FADE_IN).clamp(0.0, 1.0)
+    }
+}
+
+/// Plain-language name of a move, saying which way it goes
+fn plain_name(mv: &ParsedMove) -> String {
+    match mv {
+        ParsedMove::Basic(m) => basic_name(*m),
+        ParsedMove::Wide(w) => {
+            let layers = format!("{} layers", w.depth);
+            format!("Turn {} of the {} side {}", layers, side_name(w.face), way(w.face, w.direction))
+        }
+        ParsedMove::Layer(l) => {
+            let layers = if l.first == l.last {
+                format!("layer {}", l.first)
+            } else {
+                format!("layers {} to {}", l.first, l.last)
+            };
+            format!("Turn {} from the {} side {}", layers, side_name(l.face), way(l.face, l.direction))
+        }
+    }
+}
+
+/// Plain-language name of a face turn, slice move or rotation
+fn basic_name(m: Move) -> String {
+    let notation = m.to_notation();
+    let direction = if notation.ends_with('2') {
+        Direction::Double
+    } else if notation.ends_with('\'') {
+        Direction::CounterClockwise
+    } else {
+        Direction::Clockwise
+    };
+    // Slices and rotations go the way of the face they follow
+    let (subject, face) = match m {
+        Move::R | Move::RPrime | Move::R2 => ("the right face", WideFace::R),
+        Move::L | Move::LPrime | Move::L2 => ("the left face", WideFace::L),
+        Move::U | Move::UPrime | Move::U2 => ("the top face", WideFace::U),
+        Move::D | Move::DPrime | Move::D2 => ("the bottom face", WideFace::D),
+        Move::F | Move::FPrime | Move::F2 => ("the front face", WideFace::F),
+        Move::B | Move::BPrime | Move::B2 => ("the back face", WideFace::B),
+        Move::M | Move::MPrime | Move::M2 => ("the middle slice", WideFace::L),
+        Move::E | Move::EPrime | Move::E2 => ("the middle horizontal slice", WideFace::D),
+        Move::S | Move::SPrime | Move::S2 => ("the middle standing slice", WideFace::F),
+        Move::X | Move::XPrime | Move::X2 => return rotation_name("Tip", WideFace::R, direction),
+        Move::Y | Move::YPrime | Move::Y2 => return rotation_name("Spin", WideFace::U, direction),
+        Move::Z | Move::ZPrime | Move::Z2 => return rotation_name("Tilt", WideFace::F, direction),
+    };
+    format!("Turn {} {}", subject, way(face, direction))
+}
+
+/// Plain-language name of a whole-cube rotation
+fn rotation_name(verb: &str, face: WideFace, direction: Direction) -> String {
+    match direction {
+        Direction::Double => format!("{} the whole cube halfway around", verb),
+        _ => format!("{} the whole cube {}", verb, way(face, direction)),
+    }
+}
+
+/// Side name for wide and layer moves
+fn side_name(face: WideFace) -> &'static str {
+    match face {
+        WideFace::R => "right",
+        WideFace::L => "left",
+        WideFace::U => "top",
+        WideFace::D => "bottom",
+        WideFace::F => "front",
+        WideFace::B => "back",
+    }
+}
+
+/// Which way a layer turning like `face` goes, as seen holding the cube
+fn way(face: WideFace, direction: Direction) -> &'static str {
+    match (face, direction) {
+        (_, Direction::Double) => "twice (a half turn)",
+        (WideFace::R, Direction::Clockwise) | (WideFace::L, Direction::CounterClockwise) => "up, away from you",
+        (WideFace::R, Direction::CounterClockwise) | (WideFace::L, Direction::Clockwise) => "down, toward you",
+        (WideFace::U, Direction::Clockwise) | (WideFace::D, Direction::CounterClockwise) => "to the left",
+        (WideFace::U, Direction::CounterClockwise) | (WideFace::D, Direction::Clockwise) => "to the right",
+        (WideFace::F, Direction::Clockwise) | (WideFace::B, Direction::CounterClockwise) => {
+            "to the right, like a steering wheel"
+        }
+        (WideFace::F, Direction::CounterClockwise) | (WideFace::B, Direction::Clockwise) => {
+            "to the left, like a steering wheel"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::{LayerMove, WideMove};
+
+    fn caption(m: Move) -> MoveCaption {
+        MoveCaption::for_move(&ParsedMove::Basic(m))
+    }
+
+    #[test]
+    fn test_face_turn_captions() {
+        assert_eq!(caption(Move::R).notation, "R");
+        assert_eq!(caption(Move::R).name, "Turn the right face up, away from you");
+        assert_eq!(caption(Move::RPrime).name, "Turn the right face down, toward you");
+        assert_eq!(caption(Move::L).name, "Turn the left face down, toward you");
+        assert_eq!(caption(Move::U).name, "Turn the top face to the left");
+        assert_eq!(caption(Move::D).name, "Turn the bottom face to the right");
+        assert_eq!(caption(Move::B).name, "Turn the back face to the left, like a steering wheel");
+        assert_eq!(caption(Move::F2).name, "Turn the front face twice (a half turn)");
+        assert_eq!(caption(Move::UPrime).text(), "U' – Turn the top face to the right");
+    }
+
+    #[test]
+    fn test_slice_and_rotation_captions() {
+        // Slices follow the face they turn like
+        assert_eq!(caption(Move::M).name, "Turn the middle slice down, toward you");
+        assert_eq!(caption(Move::E).name, "Turn the middle horizontal slice to the right");
+        assert_eq!(caption(Move::Y).name, "Spin the whole cube to the left");
+        assert_eq!(caption(Move::XPrime).name, "Tip the whole cube down, toward you");
+        assert_eq!(caption(Move::Z2).name, "Tilt the whole cube halfway around");
+    }
+
+    #[test]
+    fn test_big_cube_captions() {
+        let wide = MoveCaption::for_move(&ParsedMove::Wide(WideMove::rw_prime()));
+        assert_eq!(wide.notation, "Rw'");
+        assert_eq!(wide.name, "Turn 2 layers of the right side down, toward you");
+
+        let layer = MoveCaption::for_move(&ParsedMove::Layer(LayerMove::single(WideFace::U, Direction::Clockwise, 3)));
+        assert_eq!(layer.name, "Turn layer 3 from the top side to the left");
+    }
+
+    #[test]
+    fn test_caption_fades_in() {
+        assert_eq!(MoveCaption::opacity(0.0), 0.0);
+        assert!(MoveCaption::opacity(0.05) > 0.0 && MoveCaption::opacity(0.05) < 1.0);
+        assert_eq!(MoveCaption::opacity(0.5), 1.0);
+        assert_eq!(MoveCaption::opacity(1.0), 1.0);
+    }
+}
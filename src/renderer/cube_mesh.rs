@@ -7,8 +7,10 @@
 //! - Proper normals for lighting
 //! - Gap between stickers
 
+use super::animations::RotationFace;
+use super::highlight::{HighlightManager, PieceId};
 use crate::cube::{Color, Cube, FaceName};
-use glam::{Vec2, Vec3};
+use glam::{Quat, Vec2, Vec3};
 
 /// A vertex in the cube mesh with position, normal, UV coordinates, and color
 #[repr(C)]
@@ -66,6 +68,12 @@ impl CubeMesh {
     ///
     /// A `CubeMesh` containing vertices and indices
     pub fn generate(cube: &Cube, config: &MeshConfig) -> Self {
+        Self::generate_highlighted(cube, config, &HighlightManager::new())
+    }
+
+    /// Generates a mesh with the stickers `highlights` marks (hovered,
+    /// selected or part of a tutorial step) tinted in their highlight color
+    pub fn generate_highlighted(cube: &Cube, config: &MeshConfig, highlights: &HighlightManager) -> Self {
         let mut vertices = Vec::new();
         let mut indices = Vec::new();
 
@@ -78,6 +86,7 @@ impl CubeMesh {
         // Face order: Front, Back, Right, Left, Up, Down
         generate_face_stickers(
             cube,
+            highlights,
             &mut vertices,
             &mut indices,
             FaceDirection::Front,
@@ -88,6 +97,7 @@ impl CubeMesh {
         );
         generate_face_stickers(
             cube,
+            highlights,
             &mut vertices,
             &mut indices,
             FaceDirection::Back,
@@ -98,6 +108,7 @@ impl CubeMesh {
         );
         generate_face_stickers(
             cube,
+            highlights,
             &mut vertices,
             &mut indices,
             FaceDirection::Right,
@@ -108,6 +119,7 @@ impl CubeMesh {
         );
         generate_face_stickers(
             cube,
+            highlights,
             &mut vertices,
             &mut indices,
             FaceDirection::Left,
@@ -118,6 +130,7 @@ impl CubeMesh {
         );
         generate_face_stickers(
             cube,
+            highlights,
             &mut vertices,
             &mut indices,
             FaceDirection::Up,
@@ -128,6 +141,7 @@ impl CubeMesh {
         );
         generate_face_stickers(
             cube,
+            highlights,
             &mut vertices,
             &mut indices,
             FaceDirection::Down,
@@ -140,6 +154,46 @@ impl CubeMesh {
         CubeMesh { vertices, indices }
     }
 
+    /// Turns the stickers of one layer part of the way through a move
+    ///
+    /// `angle_degrees` is clockwise as seen from the layer's face (slices
+    /// follow L, D and F like their moves do), e.g. the current angle of a
+    /// `RotationAnimation`. `cube_size` is the N of the cube the mesh was
+    /// generated for with `config`.
+    pub fn rotate_layer(&mut self, face: RotationFace, angle_degrees: f32, cube_size: usize, config: &MeshConfig) {
+        let layer = config.cube_size * 2.0 / cube_size as f32;
+        // Outward axis of the face the layer turns like, and whether the
+        // layer is the outer one (true) or the middle slice
+        let (axis, outer) = match face {
+            RotationFace::R => (Vec3::X, true),
+            RotationFace::L => (Vec3::NEG_X, true),
+            RotationFace::U => (Vec3::Y, true),
+            RotationFace::D => (Vec3::NEG_Y, true),
+            RotationFace::F => (Vec3::Z, true),
+            RotationFace::B => (Vec3::NEG_Z, true),
+            RotationFace::M => (Vec3::NEG_X, false),
+            RotationFace::E => (Vec3::NEG_Y, false),
+            RotationFace::S => (Vec3::Z, false),
+        };
+        // Clockwise seen from outside is negative about the outward axis
+        let rotation = Quat::from_axis_angle(axis, -angle_degrees.to_radians());
+        let epsilon = layer * 0.01;
+
+        for vertex in &mut self.vertices {
+            let position = Vec3::from(vertex.position);
+            let along = position.dot(axis);
+            let in_layer = if outer {
+                along > config.cube_size - layer + epsilon
+            } else {
+                cube_size % 2 == 1 && along.abs() < layer / 2.0 - epsilon
+            };
+            if in_layer {
+                vertex.position = (rotation * position).to_array();
+                vertex.normal = (rotation * Vec3::from(vertex.normal)).to_array();
+            }
+        }
+    }
+
     /// Returns the number of vertices in the mesh
     pub fn vertex_count(&self) -> usize {
         self.vertices.len()
@@ -196,6 +250,7 @@ impl FaceDirection {
 /// Generates stickers for a single face of the cube
 fn generate_face_stickers(
     cube: &Cube,
+    highlights: &HighlightManager,
     vertices: &mut Vec<Vertex>,
     indices: &mut Vec<u32>,
     direction: FaceDirection,
@@ -216,7 +271,9 @@ fn generate_face_stickers(
     for row in 0..(n as usize) {
         for col in 0..(n as usize) {
             let color = face_colors[row][col];
-            let color_rgb = color_to_rgb(color);
+            let color_rgb = highlights
+                .apply_highlight(&PieceId::new(face_name, row, col), Vec3::from(color_to_rgb(color)))
+                .to_array();
 
             // Calculate sticker center position
             let (center, u_dir, v_dir) = calculate_sticker_transform(
@@ -467,7 +524,7 @@ fn generate_rounded_sticker(
 }
 
 /// Converts a Color enum to RGB values (0.0 to 1.0)
-fn color_to_rgb(color: Color) -> [f32; 3] {
+pub(super) fn color_to_rgb(color: Color) -> [f32; 3] {
     match color {
         Color::White => [1.0, 1.0, 1.0],
         Color::Yellow => [1.0, 1.0, 0.0],
@@ -641,4 +698,63 @@ mod tests {
         assert!(mesh.index_count() > 54 * 3);
         assert_eq!(mesh.index_count() % 3, 0);
     }
+
+    /// Indices of one sticker's vertices, found by highlighting it
+    fn sticker_vertices(cube: &Cube, config: &MeshConfig, piece: PieceId) -> Vec<usize> {
+        let mut highlights = HighlightManager::new();
+        highlights.set_selected(Some(piece));
+        let plain = CubeMesh::generate(cube, config);
+        let highlighted = CubeMesh::generate_highlighted(cube, config, &highlights);
+        (0..plain.vertices.len()).filter(|&i| plain.vertices[i].color != highlighted.vertices[i].color).collect()
+    }
+
+    /// Average position of some vertices
+    fn center_of(mesh: &CubeMesh, vertices: &[usize]) -> Vec3 {
+        vertices.iter().map(|&i| Vec3::from(mesh.vertices[i].position)).sum::<Vec3>() / vertices.len() as f32
+    }
+
+    fn assert_near(actual: Vec3, expected: Vec3) {
+        assert!(actual.distance(expected) < 0.1, "expected {:?}, got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn test_highlighted_stickers_are_tinted() {
+        let cube = Cube::new(3);
+        let config = MeshConfig::default();
+        let mut highlights = HighlightManager::new();
+        highlights.set_selected(Some(PieceId::new(FaceName::F, 0, 0)));
+        let plain = CubeMesh::generate(&cube, &config);
+        let highlighted = CubeMesh::generate_highlighted(&cube, &config, &highlights);
+
+        let changed = plain.vertices.iter().zip(&highlighted.vertices).filter(|(a, b)| a.color != b.color).count();
+        // One sticker of 54 changes color
+        assert_eq!(changed, plain.vertex_count() / 54);
+    }
+
+    #[test]
+    fn test_rotate_layer_moves_one_layer() {
+        let cube = Cube::new(3);
+        let config = MeshConfig::default();
+        let third = config.cube_size * 2.0 / 3.0;
+        let up_right = sticker_vertices(&cube, &config, PieceId::new(FaceName::U, 1, 2));
+        let front_right = sticker_vertices(&cube, &config, PieceId::new(FaceName::F, 1, 2));
+        let up_left = sticker_vertices(&cube, &config, PieceId::new(FaceName::U, 1, 0));
+
+        let mut mesh = CubeMesh::generate(&cube, &config);
+        assert_near(center_of(&mesh, &up_right), Vec3::new(third, 1.0, 0.0));
+        assert_near(center_of(&mesh, &front_right), Vec3::new(third, 0.0, 1.0));
+
+        // R carries the right third of U to the back and of F up onto U
+        mesh.rotate_layer(RotationFace::R, 90.0, 3, &config);
+        assert_near(center_of(&mesh, &up_right), Vec3::new(third, 0.0, -1.0));
+        assert_near(center_of(&mesh, &front_right), Vec3::new(third, 1.0, 0.0));
+        assert_near(center_of(&mesh, &up_left), Vec3::new(-third, 1.0, 0.0));
+
+        // M follows L: the middle of U goes to the front
+        let up_middle = sticker_vertices(&cube, &config, PieceId::new(FaceName::U, 1, 1));
+        let mut mesh = CubeMesh::generate(&cube, &config);
+        mesh.rotate_layer(RotationFace::M, 90.0, 3, &config);
+        assert_near(center_of(&mesh, &up_middle), Vec3::new(0.0, 0.0, 1.0));
+        assert_near(center_of(&mesh, &up_right), Vec3::new(third, 1.0, 0.0));
+    }
 }
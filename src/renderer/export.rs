@@ -0,0 +1,245 @@
+//! Export of the cube mesh to 3D file formats
+//!
+//! Lets users drop their cube state into Blender, AR viewers or other 3D
+//! tools:
+//! - glTF 2.0 (`.gltf`): one JSON file with the buffer embedded as base64.
+//!   Sticker colors are vertex colors.
+//! - OBJ + MTL: positions, UVs and normals in the OBJ, one material per
+//!   sticker color in the MTL.
+
+use super::cube_mesh::CubeMesh;
+use serde_json::json;
+
+/// glTF component type for 32-bit floats
+const GLTF_FLOAT: u32 = 5126;
+/// glTF component type for 32-bit unsigned integers
+const GLTF_UNSIGNED_INT: u32 = 5125;
+/// glTF buffer view target for vertex attributes
+const GLTF_ARRAY_BUFFER: u32 = 34962;
+/// glTF buffer view target for indices
+const GLTF_ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// An OBJ file and the MTL file it references
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjExport {
+    pub obj: String,
+    pub mtl: String,
+}
+
+impl CubeMesh {
+    /// Export the mesh as a self-contained glTF 2.0 file
+    pub fn to_gltf(&self) -> String {
+        let mut buffer = Vec::new();
+        let mut views = Vec::new();
+        let mut accessors = Vec::new();
+
+        let mut add_floats = |values: Vec<f32>, kind: &str, count: usize, bounds: Option<([f32; 3], [f32; 3])>| {
+            let offset = buffer.len();
+            buffer.extend(values.iter().flat_map(|v| v.to_le_bytes()));
+            views.push(json!({
+                "buffer": 0,
+                "byteOffset": offset,
+                "byteLength": buffer.len() - offset,
+                "target": GLTF_ARRAY_BUFFER,
+            }));
+            let mut accessor = json!({
+                "bufferView": views.len() - 1,
+                "componentType": GLTF_FLOAT,
+                "count": count,
+                "type": kind,
+            });
+            if let Some((min, max)) = bounds {
+                accessor["min"] = json!(min);
+                accessor["max"] = json!(max);
+            }
+            accessors.push(accessor);
+        };
+
+        let count = self.vertices.len();
+        add_floats(self.vertices.iter().flat_map(|v| v.position).collect(), "VEC3", count, Some(self.bounds()));
+        add_floats(self.vertices.iter().flat_map(|v| v.normal).collect(), "VEC3", count, None);
+        add_floats(self.vertices.iter().flat_map(|v| v.uv).collect(), "VEC2", count, None);
+        add_floats(self.vertices.iter().flat_map(|v| v.color).collect(), "VEC3", count, None);
+
+        let offset = buffer.len();
+        buffer.extend(self.indices.iter().flat_map(|i| i.to_le_bytes()));
+        views.push(json!({
+            "buffer": 0,
+            "byteOffset": offset,
+            "byteLength": buffer.len() - offset,
+            "target": GLTF_ELEMENT_ARRAY_BUFFER,
+        }));
+        accessors.push(json!({
+            "bufferView": views.len() - 1,
+            "componentType": GLTF_UNSIGNED_INT,
+            "count": self.indices.len(),
+            "type": "SCALAR",
+        }));
+
+        let gltf = json!({
+            "asset": { "version": "2.0", "generator": "rubiks-cube-solver" },
+            "scene": 0,
+            "scenes": [{ "nodes": [0] }],
+            "nodes": [{ "mesh": 0, "name": "Cube" }],
+            "meshes": [{
+                "name": "Cube",
+                "primitives": [{
+                    "attributes": { "POSITION": 0, "NORMAL": 1, "TEXCOORD_0": 2, "COLOR_0": 3 },
+                    "indices": 4,
+                    "material": 0,
+                }],
+            }],
+            "materials": [{
+                "name": "Sticker",
+                "pbrMetallicRoughness": { "baseColorFactor": [1.0, 1.0, 1.0, 1.0], "metallicFactor": 0.0, "roughnessFactor": 0.5 },
+                "doubleSided": true,
+            }],
+            "buffers": [{
+                "byteLength": buffer.len(),
+                "uri": format!("data:application/octet-stream;base64,{}", base64(&buffer)),
+            }],
+            "bufferViews": views,
+            "accessors": accessors,
+        });
+        serde_json::to_string_pretty(&gltf).unwrap_or_default()
+    }
+
+    /// Export the mesh as OBJ, with materials in an MTL file
+    ///
+    /// `mtl_name` is the file name the OBJ refers to for its materials
+    /// (e.g. "cube.mtl"); save the MTL next to it under that name.
+    pub fn to_obj(&self, mtl_name: &str) -> ObjExport {
+        let mut colors: Vec<[f32; 3]> = Vec::new();
+        for vertex in &self.vertices {
+            if !colors.contains(&vertex.color) {
+                colors.push(vertex.color);
+            }
+        }
+        let material = |color: [f32; 3]| colors.iter().position(|c| *c == color).unwrap_or(0);
+
+        let mut obj = vec![format!("mtllib {}", mtl_name), "o Cube".to_string()];
+        obj.extend(self.vertices.iter().map(|v| format!("v {} {} {}", v.position[0], v.position[1], v.position[2])));
+        obj.extend(self.vertices.iter().map(|v| format!("vt {} {}", v.uv[0], 1.0 - v.uv[1])));
+        obj.extend(self.vertices.iter().map(|v| format!("vn {} {} {}", v.normal[0], v.normal[1], v.normal[2])));
+
+        // Triangles grouped by material; OBJ indices start at 1
+        for (index, _) in colors.iter().enumerate() {
+            obj.push(format!("usemtl sticker_{}", index));
+            for triangle in self.indices.chunks(3) {
+                if material(self.vertices[triangle[0] as usize].color) == index {
+                    let corners: Vec<String> = triangle.iter().map(|i| format!("{0}/{0}/{0}", i + 1)).collect();
+                    obj.push(format!("f {}", corners.join(" ")));
+                }
+            }
+        }
+
+        let mtl: Vec<String> = colors
+            .iter()
+            .enumerate()
+            .map(|(index, [r, g, b])| format!("newmtl sticker_{}\nKd {} {} {}\nKa 0 0 0\nKs 0.1 0.1 0.1\nNs 20\nd 1\nillum 2", index, r, g, b))
+            .collect();
+
+        ObjExport { obj: obj.join("\n") + "\n", mtl: mtl.join("\n\n") + "\n" }
+    }
+
+    /// Smallest and largest vertex coordinates
+    fn bounds(&self) -> ([f32; 3], [f32; 3]) {
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for vertex in &self.vertices {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(vertex.position[axis]);
+                max[axis] = max[axis].max(vertex.position[axis]);
+            }
+        }
+        (min, max)
+    }
+}
+
+/// Standard base64 with padding
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |acc, (i, b)| acc | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(ALPHABET[(group >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::{Cube, Move};
+    use crate::renderer::MeshConfig;
+
+    fn mesh() -> CubeMesh {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::U]);
+        CubeMesh::generate(&cube, &MeshConfig::default())
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xff, 0xfe, 0x00]), "//4A");
+    }
+
+    #[test]
+    fn test_gltf_structure() {
+        let mesh = mesh();
+        let gltf: serde_json::Value = serde_json::from_str(&mesh.to_gltf()).unwrap();
+        assert_eq!(gltf["asset"]["version"], "2.0");
+
+        let accessors = gltf["accessors"].as_array().unwrap();
+        assert_eq!(accessors.len(), 5);
+        assert_eq!(accessors[0]["count"], mesh.vertex_count());
+        assert_eq!(accessors[4]["count"], mesh.index_count());
+        assert_eq!(accessors[0]["min"].as_array().unwrap().len(), 3);
+
+        // 3 + 3 + 2 + 3 floats per vertex, one u32 per index
+        let length = (mesh.vertex_count() * 11 + mesh.index_count()) * 4;
+        assert_eq!(gltf["buffers"][0]["byteLength"], length);
+        let uri = gltf["buffers"][0]["uri"].as_str().unwrap();
+        let data = uri.strip_prefix("data:application/octet-stream;base64,").unwrap();
+        assert_eq!(data.len(), length.div_ceil(3) * 4);
+
+        let views = gltf["bufferViews"].as_array().unwrap();
+        let last = &views[4];
+        assert_eq!(last["byteOffset"].as_u64().unwrap() + last["byteLength"].as_u64().unwrap(), length as u64);
+    }
+
+    #[test]
+    fn test_obj_export() {
+        let mesh = mesh();
+        let export = mesh.to_obj("cube.mtl");
+        assert!(export.obj.starts_with("mtllib cube.mtl\n"));
+        assert_eq!(export.obj.lines().filter(|l| l.starts_with("v ")).count(), mesh.vertex_count());
+        assert_eq!(export.obj.lines().filter(|l| l.starts_with("f ")).count(), mesh.triangle_count());
+
+        // One material per sticker color, each used
+        assert_eq!(export.mtl.matches("newmtl").count(), 6);
+        assert_eq!(export.obj.matches("usemtl").count(), 6);
+        assert!(export.mtl.contains("Kd 1 0.5 0"), "Orange stickers");
+
+        // Indices start at 1 and never pass the vertex count
+        let highest = export
+            .obj
+            .lines()
+            .filter_map(|l| l.strip_prefix("f "))
+            .flat_map(|f| f.split(' ').map(|corner| corner.split('/').next().unwrap().parse::<usize>().unwrap()))
+            .max()
+            .unwrap();
+        assert_eq!(highest, mesh.vertex_count());
+    }
+}
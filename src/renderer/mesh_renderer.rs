@@ -0,0 +1,280 @@
+//! Draws a cube mesh
+//!
+//! [`MeshRenderer`] owns the pipeline, buffers and depth texture for drawing
+//! a [`CubeMesh`] seen through a [`Camera`] into a [`WgpuContext`], whether
+//! that context renders to a window or off-screen. Stickers are lit by one
+//! fixed light (see `cube.wgsl`) so faces read apart in screenshots.
+//!
+//! A lost or outdated surface drops one frame while the context recovers,
+//! and the renderer rebuilds itself after the context recreates its device.
+
+use super::camera::Camera;
+use super::cube_mesh::{CubeMesh, Vertex};
+use super::wgpu_context::WgpuContext;
+use wgpu::*;
+
+/// Floats per vertex: position, normal, uv, color
+const VERTEX_FLOATS: usize = 11;
+
+/// Bytes per vertex in the vertex buffer
+const VERTEX_STRIDE: BufferAddress = (VERTEX_FLOATS * 4) as BufferAddress;
+
+/// Depth buffer format
+const DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
+
+/// Background behind the cube
+const CLEAR_COLOR: Color = Color { r: 0.93, g: 0.95, b: 0.98, a: 1.0 };
+
+/// Pipeline and GPU buffers for drawing cube meshes
+pub struct MeshRenderer {
+    pipeline: RenderPipeline,
+    uniforms: Buffer,
+    bind_group: BindGroup,
+    vertices: Buffer,
+    indices: Buffer,
+    /// Depth texture and the size it was made for
+    depth: (TextureView, (u32, u32)),
+    /// Context generation the resources were made under
+    generation: u64,
+}
+
+impl MeshRenderer {
+    /// Creates the pipeline for `context`'s color format
+    pub fn new(context: &WgpuContext) -> Self {
+        let device = &context.device;
+        let shader = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("Cube Shader"),
+            source: ShaderSource::Wgsl(include_str!("cube.wgsl").into()),
+        });
+
+        let uniforms = device.create_buffer(&BufferDescriptor {
+            label: Some("Cube Uniforms"),
+            size: 64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Cube Uniforms"),
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::VERTEX,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Cube Uniforms"),
+            layout: &bind_group_layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: uniforms.as_entire_binding(),
+            }],
+        });
+
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Cube Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let attributes = vertex_attr_array![0 => Float32x3, 1 => Float32x3, 2 => Float32x2, 3 => Float32x3];
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Cube Pipeline"),
+            layout: Some(&layout),
+            vertex: VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[VertexBufferLayout {
+                    array_stride: VERTEX_STRIDE,
+                    step_mode: VertexStepMode::Vertex,
+                    attributes: &attributes,
+                }],
+                compilation_options: PipelineCompilationOptions::default(),
+            },
+            fragment: Some(FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(ColorTargetState {
+                    format: context.config.format,
+                    blend: Some(BlendState::REPLACE),
+                    write_mask: ColorWrites::ALL,
+                })],
+                compilation_options: PipelineCompilationOptions::default(),
+            }),
+            primitive: PrimitiveState {
+                topology: PrimitiveTopology::TriangleList,
+                front_face: FrontFace::Ccw,
+                // Sticker winding isn't consistent across faces, and the
+                // depth test hides back faces anyway
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: true,
+                depth_compare: CompareFunction::Less,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
+            multisample: MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            uniforms,
+            bind_group,
+            vertices: mesh_buffer(device, "Cube Vertices", BufferUsages::VERTEX, 0),
+            indices: mesh_buffer(device, "Cube Indices", BufferUsages::INDEX, 0),
+            depth: (depth_view(device, context.size), context.size),
+            generation: context.generation(),
+        }
+    }
+
+    /// Draws `mesh` as seen by `camera` and presents the frame
+    ///
+    /// Surface problems the context can recover from skip the frame and
+    /// return `Ok`; only unrecoverable ones are returned.
+    pub fn render(&mut self, context: &mut WgpuContext, mesh: &CubeMesh, camera: &Camera) -> Result<(), SurfaceError> {
+        context.ensure_device()?;
+        if self.generation != context.generation() {
+            *self = Self::new(context);
+        }
+        context.begin_frame();
+
+        let vertices = vertex_bytes(&mesh.vertices);
+        let indices: Vec<u8> = mesh.indices.iter().flat_map(|index| index.to_le_bytes()).collect();
+        let view_proj: Vec<u8> =
+            camera.view_projection_matrix().to_cols_array().iter().flat_map(|value| value.to_le_bytes()).collect();
+        if self.vertices.size() < vertices.len() as BufferAddress {
+            self.vertices = mesh_buffer(&context.device, "Cube Vertices", BufferUsages::VERTEX, vertices.len());
+        }
+        if self.indices.size() < indices.len() as BufferAddress {
+            self.indices = mesh_buffer(&context.device, "Cube Indices", BufferUsages::INDEX, indices.len());
+        }
+        context.write_buffer(&self.uniforms, 0, &view_proj);
+        context.write_buffer(&self.vertices, 0, &vertices);
+        context.write_buffer(&self.indices, 0, &indices);
+        if self.depth.1 != context.size {
+            self.depth = (depth_view(&context.device, context.size), context.size);
+        }
+
+        let frame = match context.acquire_frame() {
+            Ok(frame) => frame,
+            Err(error) => return context.recover_surface(error),
+        };
+        let suboptimal = frame.is_suboptimal();
+        let view = frame.view();
+        let mut encoder = context.device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("Cube Render Encoder"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                label: Some("Cube Render Pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(CLEAR_COLOR),
+                        store: StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                    view: &self.depth.0,
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Clear(1.0),
+                        store: StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: context.gpu_timer().map(|timer| timer.timestamp_writes()),
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &self.bind_group, &[]);
+            pass.set_vertex_buffer(0, self.vertices.slice(..));
+            pass.set_index_buffer(self.indices.slice(..), IndexFormat::Uint32);
+            pass.draw_indexed(0..mesh.indices.len() as u32, 0, 0..1);
+        }
+        if let Some(timer) = context.gpu_timer() {
+            timer.resolve(&mut encoder);
+        }
+        context.queue.submit(Some(encoder.finish()));
+        frame.present();
+        if suboptimal {
+            context.reconfigure();
+        }
+
+        context.record_draws(1);
+        context.end_frame();
+        Ok(())
+    }
+}
+
+/// Vertices packed as little-endian floats in attribute order
+fn vertex_bytes(vertices: &[Vertex]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(vertices.len() * VERTEX_STRIDE as usize);
+    for vertex in vertices {
+        let floats = vertex.position.iter().chain(&vertex.normal).chain(&vertex.uv).chain(&vertex.color);
+        bytes.extend(floats.flat_map(|value| value.to_le_bytes()));
+    }
+    bytes
+}
+
+/// Vertex or index buffer holding at least `size` bytes
+fn mesh_buffer(device: &Device, label: &str, usage: BufferUsages, size: usize) -> Buffer {
+    device.create_buffer(&BufferDescriptor {
+        label: Some(label),
+        // Buffers can't be empty, and copies come in multiples of 4 bytes
+        size: (size.max(4) as BufferAddress).next_multiple_of(COPY_BUFFER_ALIGNMENT),
+        usage: usage | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+/// Depth texture for a target of `size`
+fn depth_view(device: &Device, (width, height): (u32, u32)) -> TextureView {
+    device
+        .create_texture(&TextureDescriptor {
+            label: Some("Cube Depth"),
+            size: Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: DEPTH_FORMAT,
+            usage: TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&TextureViewDescriptor::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vertex_bytes_follow_attribute_order() {
+        let vertex = Vertex {
+            position: [1.0, 2.0, 3.0],
+            normal: [0.0, 1.0, 0.0],
+            uv: [0.5, 0.25],
+            color: [1.0, 0.0, 0.0],
+        };
+        let bytes = vertex_bytes(&[vertex, vertex]);
+        assert_eq!(bytes.len(), 2 * VERTEX_STRIDE as usize);
+        let float = |i: usize| f32::from_le_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap());
+        assert_eq!(float(0), 1.0);
+        assert_eq!(float(4), 1.0);
+        assert_eq!(float(7), 0.25);
+        assert_eq!(float(8), 1.0);
+        assert_eq!(float(VERTEX_FLOATS), 1.0);
+    }
+}
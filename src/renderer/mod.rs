@@ -1,35 +1,63 @@
 //! 3D rendering module
 //!
 //! This module handles all 3D rendering using WGPU, including:
-//! - WGPU context setup
+//! - WGPU context setup (window or off-screen, surface and device loss recovery)
 //! - Cube mesh generation
+//! - Cube mesh drawing (pipeline and shader)
+//! - Pyraminx mesh generation
+//! - Mesh export (glTF, OBJ + MTL)
 //! - Camera controls
 //! - Animations
 //! - Piece highlighting
+//! - Next-move arrow overlay
+//! - Move captions
+//! - Performance statistics (FPS, frame time, draw calls, uploads)
 //!
 //! Note: This module is only available with the `desktop_3d` feature enabled.
 
 #[cfg(feature = "desktop_3d")]
 pub mod animations;
 #[cfg(feature = "desktop_3d")]
+pub mod arrow_overlay;
+#[cfg(feature = "desktop_3d")]
 pub mod camera;
 #[cfg(feature = "desktop_3d")]
+pub mod caption;
+#[cfg(feature = "desktop_3d")]
 pub mod cube_mesh;
 #[cfg(feature = "desktop_3d")]
+pub mod export;
+#[cfg(feature = "desktop_3d")]
 pub mod highlight;
 #[cfg(feature = "desktop_3d")]
+pub mod mesh_renderer;
+#[cfg(feature = "desktop_3d")]
+pub mod pyraminx_mesh;
+#[cfg(feature = "desktop_3d")]
+pub mod stats;
+#[cfg(feature = "desktop_3d")]
 pub mod wgpu_context;
 
 #[cfg(feature = "desktop_3d")]
-pub use animations::{AnimationQueue, EasingFunction, RotationAnimation, RotationFace};
+pub use animations::{rotation_for, AnimationQueue, EasingFunction, RotationAnimation, RotationFace};
+#[cfg(feature = "desktop_3d")]
+pub use arrow_overlay::{ArrowConfig, ArrowMesh, ArrowOverlay, MoveArrow};
 #[cfg(feature = "desktop_3d")]
 pub use camera::{Camera, OrbitController};
 #[cfg(feature = "desktop_3d")]
+pub use caption::MoveCaption;
+#[cfg(feature = "desktop_3d")]
 pub use cube_mesh::{CubeMesh, MeshConfig, Vertex};
 #[cfg(feature = "desktop_3d")]
+pub use export::ObjExport;
+#[cfg(feature = "desktop_3d")]
 pub use highlight::{HighlightConfig, HighlightManager, HighlightType, PieceId};
 #[cfg(feature = "desktop_3d")]
-pub use wgpu_context::{WgpuContext, WgpuContextConfig};
+pub use mesh_renderer::MeshRenderer;
+#[cfg(feature = "desktop_3d")]
+pub use stats::{FrameStats, GpuTimer, RenderStats};
+#[cfg(feature = "desktop_3d")]
+pub use wgpu_context::{Frame, SurfaceRecovery, WgpuContext, WgpuContextConfig};
 
 // Stub types for when desktop_3d is not enabled
 #[cfg(not(feature = "desktop_3d"))]
@@ -0,0 +1,78 @@
+//! Pyraminx mesh generation
+//!
+//! Builds the same vertex and index buffers as [`CubeMesh::generate`] so a
+//! Pyraminx goes through the cube's render pipeline: one triangle per
+//! sticker, shrunk by the sticker gap and lifted off the surface by the
+//! sticker depth.
+
+use super::cube_mesh::{color_to_rgb, CubeMesh, MeshConfig, Vertex};
+use crate::puzzle::pyraminx::{sticker_triangle, STICKERS_PER_FACE};
+use crate::puzzle::{Pyraminx, PyraminxFace};
+use glam::{Vec2, Vec3};
+
+impl CubeMesh {
+    /// Generates a mesh for a Pyraminx
+    ///
+    /// The tetrahedron's corners lie as far from the center as a cube's
+    /// corners would with the same `cube_size`, so both puzzles fill the
+    /// same camera view.
+    pub fn generate_pyraminx(pyraminx: &Pyraminx, config: &MeshConfig) -> Self {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        let scale = config.cube_size * 3f32.sqrt();
+        let uvs = [Vec2::new(0.5, 0.0), Vec2::new(0.0, 1.0), Vec2::new(1.0, 1.0)];
+
+        for face in PyraminxFace::ALL {
+            for sticker in 0..STICKERS_PER_FACE {
+                let corners = sticker_triangle(face, sticker).map(|p| Vec3::from_array(p) * scale);
+                let center = (corners[0] + corners[1] + corners[2]) / 3.0;
+                let normal = (corners[1] - corners[0]).cross(corners[2] - corners[0]).normalize();
+                let color = color_to_rgb(pyraminx.sticker(face, sticker));
+                let base_index = vertices.len() as u32;
+
+                for (corner, uv) in corners.iter().zip(uvs) {
+                    let position = center + (*corner - center) * (1.0 - config.sticker_gap) + normal * config.sticker_depth;
+                    vertices.push(Vertex {
+                        position: position.to_array(),
+                        normal: normal.to_array(),
+                        uv: uv.to_array(),
+                        color,
+                    });
+                }
+                // Corners come counter-clockwise seen from outside
+                indices.extend_from_slice(&[base_index, base_index + 1, base_index + 2]);
+            }
+        }
+
+        CubeMesh { vertices, indices }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::Puzzle;
+
+    #[test]
+    fn test_pyraminx_mesh() {
+        let mesh = CubeMesh::generate_pyraminx(&Pyraminx::new(), &MeshConfig::default());
+        assert_eq!(mesh.triangle_count(), 36);
+        assert_eq!(mesh.vertex_count(), 36 * 3);
+        assert!(mesh.indices.iter().all(|&i| (i as usize) < mesh.vertex_count()));
+        // Normals point away from the center
+        for vertex in &mesh.vertices {
+            assert!(Vec3::from_array(vertex.position).dot(Vec3::from_array(vertex.normal)) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_pyraminx_mesh_follows_moves() {
+        let mut pyraminx = Pyraminx::new();
+        pyraminx.apply_notation("U").unwrap();
+        let solved = CubeMesh::generate_pyraminx(&Pyraminx::new(), &MeshConfig::default());
+        let turned = CubeMesh::generate_pyraminx(&pyraminx, &MeshConfig::default());
+        let changed = solved.vertices.iter().zip(&turned.vertices).filter(|(a, b)| a.color != b.color).count();
+        // Twelve stickers change color, three vertices each
+        assert_eq!(changed, 36);
+    }
+}
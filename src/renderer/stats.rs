@@ -0,0 +1,277 @@
+//! Renderer performance numbers
+//!
+//! Frames per second, frame time, draw calls and bytes uploaded to GPU
+//! buffers, averaged over the last second or so of frames, to find out why
+//! the cube stutters on low-end school hardware. `WgpuContext` fills in a
+//! [`RenderStats`] as it draws; the overlay shows its `overlay_lines`.
+//!
+//! GPU time comes from timestamp queries when the adapter supports them
+//! ([`GpuTimer`]); without them only the CPU-side frame time is known.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use wgpu::*;
+
+/// Frames averaged for the FPS and frame time
+const WINDOW: usize = 60;
+
+/// Bytes per timestamp query result
+const TIMESTAMP_SIZE: u64 = 8;
+
+/// What one finished frame did
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    /// Wall-clock time from `begin_frame` to `end_frame`, in milliseconds
+    pub frame_time_ms: f32,
+    /// Draw calls recorded
+    pub draw_calls: u32,
+    /// Bytes written to GPU buffers
+    pub uploaded_bytes: u64,
+    /// Time the GPU spent on the frame, when timestamp queries are on
+    pub gpu_time_ms: Option<f32>,
+}
+
+/// Rolling frame statistics
+#[derive(Debug, Clone, Default)]
+pub struct RenderStats {
+    /// Start of the frame being recorded
+    frame_start: Option<Instant>,
+    /// Counters of the frame being recorded
+    current: FrameStats,
+    /// Last finished frames, oldest first
+    recent: VecDeque<(Instant, FrameStats)>,
+}
+
+impl RenderStats {
+    /// Create empty statistics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start recording a frame
+    pub fn begin_frame(&mut self, now: Instant) {
+        self.frame_start = Some(now);
+        self.current = FrameStats::default();
+    }
+
+    /// Count draw calls in the current frame
+    pub fn record_draws(&mut self, count: u32) {
+        self.current.draw_calls += count;
+    }
+
+    /// Count bytes written to a GPU buffer in the current frame
+    pub fn record_upload(&mut self, bytes: u64) {
+        self.current.uploaded_bytes += bytes;
+    }
+
+    /// Set the GPU time of the current frame
+    pub fn record_gpu_time(&mut self, ms: f32) {
+        self.current.gpu_time_ms = Some(ms);
+    }
+
+    /// Finish the current frame; does nothing if none was begun
+    pub fn end_frame(&mut self, now: Instant) {
+        let Some(start) = self.frame_start.take() else { return };
+        self.current.frame_time_ms = now.saturating_duration_since(start).as_secs_f32() * 1000.0;
+        self.recent.push_back((now, self.current));
+        if self.recent.len() > WINDOW {
+            self.recent.pop_front();
+        }
+    }
+
+    /// The last finished frame, if any
+    pub fn last_frame(&self) -> Option<FrameStats> {
+        self.recent.back().map(|(_, frame)| *frame)
+    }
+
+    /// Frames per second over the recent frames, from the time between
+    /// their ends
+    pub fn fps(&self) -> f32 {
+        let (Some((first, _)), Some((last, _))) = (self.recent.front(), self.recent.back()) else { return 0.0 };
+        let span = last.saturating_duration_since(*first);
+        if span == Duration::ZERO {
+            return 0.0;
+        }
+        (self.recent.len() - 1) as f32 / span.as_secs_f32()
+    }
+
+    /// Average frame time of the recent frames, in milliseconds
+    pub fn average_frame_time_ms(&self) -> f32 {
+        if self.recent.is_empty() {
+            return 0.0;
+        }
+        self.recent.iter().map(|(_, frame)| frame.frame_time_ms).sum::<f32>() / self.recent.len() as f32
+    }
+
+    /// Slowest recent frame time, in milliseconds; spikes show up here
+    /// before they move the average
+    pub fn worst_frame_time_ms(&self) -> f32 {
+        self.recent.iter().map(|(_, frame)| frame.frame_time_ms).fold(0.0, f32::max)
+    }
+
+    /// Lines of text for the performance overlay
+    pub fn overlay_lines(&self) -> Vec<String> {
+        let last = self.last_frame().unwrap_or_default();
+        let mut lines = vec![
+            format!("{:.0} FPS", self.fps()),
+            format!(
+                "Frame {:.1} ms (worst {:.1} ms)",
+                self.average_frame_time_ms(),
+                self.worst_frame_time_ms()
+            ),
+            format!("{} draw calls", last.draw_calls),
+            format!("Uploaded {}", format_bytes(last.uploaded_bytes)),
+        ];
+        if let Some(gpu) = last.gpu_time_ms {
+            lines.push(format!("GPU {:.2} ms", gpu));
+        }
+        lines
+    }
+
+    /// Forget all frames, e.g. after a resize
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Bytes as B, KB or MB
+fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f32 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f32 / (1024.0 * 1024.0))
+    }
+}
+
+/// Measures GPU time of render passes with timestamp queries
+///
+/// Pass `timestamp_writes` to the frame's render pass, call `resolve`
+/// before submitting the encoder, then `read_ms` after submitting.
+pub struct GpuTimer {
+    queries: QuerySet,
+    resolve: Buffer,
+    readback: Buffer,
+    /// Nanoseconds per timestamp tick
+    period: f32,
+}
+
+impl GpuTimer {
+    /// Create a timer, or `None` if the device wasn't created with
+    /// `Features::TIMESTAMP_QUERY`
+    pub fn new(device: &Device, queue: &Queue) -> Option<Self> {
+        if !device.features().contains(Features::TIMESTAMP_QUERY) {
+            return None;
+        }
+        let queries = device.create_query_set(&QuerySetDescriptor {
+            label: Some("Frame Timestamps"),
+            ty: QueryType::Timestamp,
+            count: 2,
+        });
+        let resolve = device.create_buffer(&BufferDescriptor {
+            label: Some("Frame Timestamp Resolve"),
+            size: 2 * TIMESTAMP_SIZE,
+            usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = device.create_buffer(&BufferDescriptor {
+            label: Some("Frame Timestamp Readback"),
+            size: 2 * TIMESTAMP_SIZE,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        Some(Self { queries, resolve, readback, period: queue.get_timestamp_period() })
+    }
+
+    /// Timestamp writes for the start and end of a render pass
+    pub fn timestamp_writes(&self) -> RenderPassTimestampWrites<'_> {
+        RenderPassTimestampWrites {
+            query_set: &self.queries,
+            beginning_of_pass_write_index: Some(0),
+            end_of_pass_write_index: Some(1),
+        }
+    }
+
+    /// Copy the timestamps where `read_ms` can read them
+    pub fn resolve(&self, encoder: &mut CommandEncoder) {
+        encoder.resolve_query_set(&self.queries, 0..2, &self.resolve, 0);
+        encoder.copy_buffer_to_buffer(&self.resolve, 0, &self.readback, 0, 2 * TIMESTAMP_SIZE);
+    }
+
+    /// GPU time between the two timestamps, in milliseconds
+    ///
+    /// Waits for the GPU to finish the submitted work, so only call it while
+    /// the overlay is on.
+    pub fn read_ms(&self, device: &Device) -> Option<f32> {
+        let slice = self.readback.slice(..);
+        slice.map_async(MapMode::Read, |_| {});
+        device.poll(Maintain::Wait);
+        let ticks = {
+            let data = slice.get_mapped_range();
+            let timestamp = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap_or([0; 8]));
+            timestamp(8).checked_sub(timestamp(0))
+        };
+        self.readback.unmap();
+        ticks.map(|ticks| ticks as f32 * self.period / 1_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Record `count` frames `interval_ms` apart, each taking `work_ms`
+    fn run_frames(stats: &mut RenderStats, count: u32, interval_ms: u64, work_ms: u64) -> Instant {
+        let start = Instant::now();
+        for i in 0..count {
+            let begin = start + Duration::from_millis(i as u64 * interval_ms);
+            stats.begin_frame(begin);
+            stats.record_draws(3);
+            stats.record_upload(2048);
+            stats.end_frame(begin + Duration::from_millis(work_ms));
+        }
+        start
+    }
+
+    #[test]
+    fn test_fps_and_frame_time() {
+        let mut stats = RenderStats::new();
+        run_frames(&mut stats, 11, 20, 5);
+        assert!((stats.fps() - 50.0).abs() < 0.01);
+        assert!((stats.average_frame_time_ms() - 5.0).abs() < 0.01);
+        let last = stats.last_frame().unwrap();
+        assert_eq!(last.draw_calls, 3);
+        assert_eq!(last.uploaded_bytes, 2048);
+        assert_eq!(last.gpu_time_ms, None);
+    }
+
+    #[test]
+    fn test_window_keeps_recent_frames() {
+        let mut stats = RenderStats::new();
+        run_frames(&mut stats, WINDOW as u32 * 2, 10, 2);
+        assert_eq!(stats.recent.len(), WINDOW);
+        stats.reset();
+        assert_eq!(stats.fps(), 0.0);
+        assert!(stats.last_frame().is_none());
+    }
+
+    #[test]
+    fn test_end_without_begin_is_ignored() {
+        let mut stats = RenderStats::new();
+        stats.end_frame(Instant::now());
+        assert!(stats.last_frame().is_none());
+    }
+
+    #[test]
+    fn test_overlay_lines() {
+        let mut stats = RenderStats::new();
+        run_frames(&mut stats, 2, 16, 4);
+        let lines = stats.overlay_lines();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[2], "3 draw calls");
+        assert_eq!(lines[3], "Uploaded 2.0 KB");
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+}
@@ -4,18 +4,118 @@
 //!
 //! This module handles initialization of WGPU surface, device, and queue
 //! for both web (WebGPU) and native platforms.
+//!
+//! A context can also render off-screen, into a texture instead of a window
+//! ([`WgpuContext::new_offscreen`]), and read the pixels back. CI uses this
+//! for golden-image tests; frames are drawn the same way either way through
+//! [`Frame`].
+//!
+//! Window contexts survive the things laptops do mid-session: a surface
+//! that is lost or outdated after sleep, a resize or a monitor change is
+//! reconfigured by [`WgpuContext::recover_surface`], DPI changes go through
+//! [`WgpuContext::set_scale_factor`], and a lost device is recreated by
+//! [`WgpuContext::ensure_device`]. Renderers compare
+//! [`WgpuContext::generation`] with the one they were built for to know
+//! when their GPU resources need rebuilding.
+//!
+//! The context also keeps [`RenderStats`]: frames are bracketed with
+//! `begin_frame`/`end_frame`, and buffer writes and draw calls go through
+//! `write_buffer` and `record_draws` so they are counted. The numbers are
+//! shown as an overlay when `set_stats_overlay` turns it on.
 
+use super::stats::{GpuTimer, RenderStats};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 use wgpu::*;
 
 /// WGPU rendering context containing device, queue, surface configuration
 pub struct WgpuContext {
-    pub surface: Surface<'static>,
+    /// Window surface; `None` for off-screen contexts
+    pub surface: Option<Surface<'static>>,
     pub device: Device,
     pub queue: Queue,
     pub config: SurfaceConfiguration,
     pub size: (u32, u32),
+    /// Frame rate, frame time, draw calls and uploads
+    stats: RenderStats,
+    /// GPU timestamps, once `enable_gpu_timing` succeeded
+    gpu_timer: Option<GpuTimer>,
+    /// Whether the performance overlay is shown
+    stats_overlay: bool,
+    /// Texture off-screen contexts draw into
+    offscreen: Option<Texture>,
+    /// Instance the surface and adapter came from, for finding another
+    /// adapter when the device is lost
+    instance: Instance,
+    adapter: Adapter,
+    /// Set by wgpu when `device` is lost
+    device_lost: Arc<AtomicBool>,
+    /// Bumped each time the device is recreated
+    generation: u64,
+    /// Physical pixels per logical pixel of the window
+    scale_factor: f64,
+}
+
+/// What to do about a failed `acquire_frame`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceRecovery {
+    /// Configure the surface again and draw the next frame
+    Reconfigure,
+    /// Drop this frame; the surface is fine
+    SkipFrame,
+    /// Give up, e.g. out of memory
+    Fatal,
+}
+
+impl SurfaceRecovery {
+    /// The recovery for `error`
+    pub fn for_error(error: &SurfaceError) -> Self {
+        match error {
+            // Lost after sleep or a GPU switch, outdated after a resize or a
+            // move to another monitor
+            SurfaceError::Lost | SurfaceError::Outdated => SurfaceRecovery::Reconfigure,
+            SurfaceError::Timeout => SurfaceRecovery::SkipFrame,
+            _ => SurfaceRecovery::Fatal,
+        }
+    }
+}
+
+/// Texture a frame is drawn into
+pub enum Frame<'a> {
+    /// Next image of the window surface
+    Surface(SurfaceTexture),
+    /// The off-screen texture
+    Offscreen(&'a Texture),
+}
+
+impl Frame<'_> {
+    /// View to use as the render pass color attachment
+    pub fn view(&self) -> TextureView {
+        match self {
+            Frame::Surface(frame) => frame.texture.create_view(&TextureViewDescriptor::default()),
+            Frame::Offscreen(texture) => texture.create_view(&TextureViewDescriptor::default()),
+        }
+    }
+
+    /// Whether the surface no longer matches the window exactly; present it
+    /// anyway, then call `WgpuContext::reconfigure`
+    pub fn is_suboptimal(&self) -> bool {
+        matches!(self, Frame::Surface(frame) if frame.suboptimal)
+    }
+
+    /// Shows the frame in the window; off-screen frames stay in their
+    /// texture for `read_pixels`
+    pub fn present(self) {
+        if let Frame::Surface(frame) = self {
+            frame.present();
+        }
+    }
 }
 
+/// Color format of off-screen contexts
+pub const OFFSCREEN_FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+
 /// Configuration for creating a WGPU context
 pub struct WgpuContextConfig {
     pub width: u32,
@@ -38,21 +138,17 @@ impl WgpuContext {
     ///
     /// # Arguments
     ///
+    /// * `instance` - The instance `surface` was created from
     /// * `surface` - The window surface to render to
     /// * `config` - Configuration parameters for the context
     ///
     /// # Returns
     ///
     /// A new `WgpuContext` ready for rendering
-    pub async fn new(surface: Surface<'static>, config: WgpuContextConfig) -> Self {
+    pub async fn new(instance: Instance, surface: Surface<'static>, config: WgpuContextConfig) -> Self {
         let size = (config.width, config.height);
 
         // Request an adapter (GPU)
-        let instance = Instance::new(InstanceDescriptor {
-            backends: Backends::all(),
-            ..Default::default()
-        });
-
         let adapter = instance
             .request_adapter(&RequestAdapterOptions {
                 power_preference: PowerPreference::default(),
@@ -62,73 +158,296 @@ impl WgpuContext {
             .await
             .expect("Failed to find an appropriate adapter");
 
-        // Request a device and queue
-        let (device, queue) = adapter
-            .request_device(
-                &DeviceDescriptor {
-                    label: Some("Rubik's Cube Renderer Device"),
-                    required_features: Features::empty(),
-                    required_limits: Limits::default(),
-                    memory_hints: MemoryHints::default(),
-                },
-                None,
-            )
-            .await
-            .expect("Failed to create device");
+        let (device, queue) = request_device(&adapter).await.expect("Failed to create device");
+        let device_lost = watch_device_loss(&device);
 
         // Get surface capabilities and configure
-        let surface_caps = surface.get_capabilities(&adapter);
-        let surface_format = surface_caps
-            .formats
-            .iter()
-            .find(|f| f.is_srgb())
-            .copied()
-            .unwrap_or(surface_caps.formats[0]);
-
-        let surface_config = SurfaceConfiguration {
+        let mut surface_config = SurfaceConfiguration {
             usage: TextureUsages::RENDER_ATTACHMENT,
-            format: surface_format,
+            format: TextureFormat::Bgra8UnormSrgb,
             width: config.width,
             height: config.height,
             present_mode: config.present_mode,
-            alpha_mode: surface_caps.alpha_modes[0],
+            alpha_mode: CompositeAlphaMode::Auto,
             view_formats: vec![],
             desired_maximum_frame_latency: 2,
         };
+        match_capabilities(&mut surface_config, &surface.get_capabilities(&adapter));
 
         surface.configure(&device, &surface_config);
 
         Self {
-            surface,
+            surface: Some(surface),
             device,
             queue,
             config: surface_config,
             size,
+            stats: RenderStats::new(),
+            gpu_timer: None,
+            stats_overlay: false,
+            offscreen: None,
+            instance,
+            adapter,
+            device_lost,
+            generation: 0,
+            scale_factor: 1.0,
         }
     }
 
+    /// Creates a context that renders into a texture instead of a window
+    ///
+    /// Fails when there is no GPU adapter at all (software ones count), so
+    /// tests can skip instead of failing on machines without one.
+    pub async fn new_offscreen(config: WgpuContextConfig) -> Result<Self, String> {
+        let instance = Instance::new(InstanceDescriptor {
+            backends: Backends::all(),
+            ..Default::default()
+        });
+        let adapter = instance
+            .request_adapter(&RequestAdapterOptions {
+                power_preference: PowerPreference::default(),
+                compatible_surface: None,
+                force_fallback_adapter: false,
+            })
+            .await
+            .ok_or_else(|| "No GPU adapter available".to_string())?;
+        let (device, queue) = request_device(&adapter).await.map_err(|e| format!("Failed to create device: {}", e))?;
+        let device_lost = watch_device_loss(&device);
+
+        let surface_config = SurfaceConfiguration {
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+            format: OFFSCREEN_FORMAT,
+            width: config.width.max(1),
+            height: config.height.max(1),
+            present_mode: config.present_mode,
+            alpha_mode: CompositeAlphaMode::Opaque,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        let offscreen = offscreen_texture(&device, &surface_config);
+
+        Ok(Self {
+            surface: None,
+            device,
+            queue,
+            size: (surface_config.width, surface_config.height),
+            config: surface_config,
+            stats: RenderStats::new(),
+            gpu_timer: None,
+            stats_overlay: false,
+            offscreen: Some(offscreen),
+            instance,
+            adapter,
+            device_lost,
+            generation: 0,
+            scale_factor: 1.0,
+        })
+    }
+
     /// Resizes the rendering surface
     ///
+    /// A zero size (a minimized window) is ignored; sizes past the device's
+    /// texture limit are clamped.
+    ///
     /// # Arguments
     ///
     /// * `new_width` - New width in pixels
     /// * `new_height` - New height in pixels
     pub fn resize(&mut self, new_width: u32, new_height: u32) {
         if new_width > 0 && new_height > 0 {
-            self.size = (new_width, new_height);
-            self.config.width = new_width;
-            self.config.height = new_height;
-            self.surface.configure(&self.device, &self.config);
+            let max = self.device.limits().max_texture_dimension_2d;
+            let size = (new_width.min(max), new_height.min(max));
+            if size == self.size {
+                return;
+            }
+            self.size = size;
+            self.config.width = size.0;
+            self.config.height = size.1;
+            self.stats.reset();
+            self.reconfigure();
         }
     }
 
+    /// Handles a DPI change, e.g. the window moving to another monitor
+    ///
+    /// `physical_size` is the window's new inner size in pixels.
+    pub fn set_scale_factor(&mut self, scale_factor: f64, physical_size: (u32, u32)) {
+        self.scale_factor = scale_factor;
+        self.resize(physical_size.0, physical_size.1);
+    }
+
+    /// Physical pixels per logical pixel of the window
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Configures the surface again for the current size, or recreates the
+    /// off-screen texture
+    pub fn reconfigure(&mut self) {
+        match &self.surface {
+            Some(surface) => surface.configure(&self.device, &self.config),
+            None => self.offscreen = Some(offscreen_texture(&self.device, &self.config)),
+        }
+    }
+
+    /// Recovers from a failed `acquire_frame`
+    ///
+    /// Lost and outdated surfaces are reconfigured (and a lost device
+    /// recreated) so the next frame can draw; timeouts just drop the frame.
+    /// Returns the error when there is nothing to do but give up.
+    pub fn recover_surface(&mut self, error: SurfaceError) -> Result<(), SurfaceError> {
+        self.ensure_device()?;
+        match SurfaceRecovery::for_error(&error) {
+            SurfaceRecovery::Reconfigure => {
+                self.reconfigure();
+                Ok(())
+            }
+            SurfaceRecovery::SkipFrame => Ok(()),
+            SurfaceRecovery::Fatal => Err(error),
+        }
+    }
+
+    /// Recreates the device if wgpu reported it lost; call before drawing
+    ///
+    /// Returns `SurfaceError::Lost` while no new device can be made, so the
+    /// caller can try again next frame.
+    pub fn ensure_device(&mut self) -> Result<(), SurfaceError> {
+        if !self.is_device_lost() {
+            return Ok(());
+        }
+        self.recover_device().map_err(|_| SurfaceError::Lost)
+    }
+
+    /// Whether wgpu reported the device lost (driver reset, GPU switch)
+    pub fn is_device_lost(&self) -> bool {
+        self.device_lost.load(Ordering::SeqCst)
+    }
+
+    /// Replaces the device and queue, reconfiguring the surface for them
+    ///
+    /// Tries the current adapter first, then asks for a new one in case the
+    /// GPU itself went away. Everything made with the old device is invalid
+    /// afterwards; `generation` changes so renderers know to rebuild.
+    pub fn recover_device(&mut self) -> Result<(), String> {
+        let (device, queue) = match pollster::block_on(request_device(&self.adapter)) {
+            Ok(device) => device,
+            Err(_) => {
+                let adapter = pollster::block_on(self.instance.request_adapter(&RequestAdapterOptions {
+                    power_preference: PowerPreference::default(),
+                    compatible_surface: self.surface.as_ref(),
+                    force_fallback_adapter: false,
+                }))
+                .ok_or_else(|| "No GPU adapter available".to_string())?;
+                let device = pollster::block_on(request_device(&adapter))
+                    .map_err(|e| format!("Failed to create device: {}", e))?;
+                if let Some(surface) = &self.surface {
+                    match_capabilities(&mut self.config, &surface.get_capabilities(&adapter));
+                }
+                self.adapter = adapter;
+                device
+            }
+        };
+
+        self.device_lost = watch_device_loss(&device);
+        self.device = device;
+        self.queue = queue;
+        self.generation += 1;
+        if self.gpu_timer.is_some() {
+            self.gpu_timer = GpuTimer::new(&self.device, &self.queue);
+        }
+        self.stats.reset();
+        self.reconfigure();
+        Ok(())
+    }
+
+    /// How many times the device has been recreated; GPU resources made
+    /// under another generation must be rebuilt
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
     /// Gets the current surface texture for rendering
     ///
     /// # Returns
     ///
-    /// The current surface texture wrapped in a Result
+    /// The current surface texture wrapped in a Result. Off-screen contexts
+    /// have no surface and return `SurfaceError::Lost`; use `acquire_frame`
+    /// to draw in either mode.
     pub fn get_current_texture(&self) -> Result<SurfaceTexture, SurfaceError> {
-        self.surface.get_current_texture()
+        match &self.surface {
+            Some(surface) => surface.get_current_texture(),
+            None => Err(SurfaceError::Lost),
+        }
+    }
+
+    /// Gets the texture to draw the next frame into: the window surface's
+    /// next image, or the off-screen texture
+    pub fn acquire_frame(&self) -> Result<Frame<'_>, SurfaceError> {
+        match &self.offscreen {
+            Some(texture) => Ok(Frame::Offscreen(texture)),
+            None => self.get_current_texture().map(Frame::Surface),
+        }
+    }
+
+    /// Whether this context renders into a texture instead of a window
+    pub fn is_offscreen(&self) -> bool {
+        self.offscreen.is_some()
+    }
+
+    /// Copies the off-screen texture back to the CPU
+    ///
+    /// Returns tightly packed RGBA8 rows (sRGB), top row first, or `None`
+    /// for window contexts. Waits for the GPU to finish drawing.
+    pub fn read_pixels(&self) -> Option<Vec<u8>> {
+        let texture = self.offscreen.as_ref()?;
+        let (width, height) = self.size;
+        let row_bytes = width * 4;
+        // Copies need rows padded to the alignment
+        let padded_row_bytes = row_bytes.div_ceil(COPY_BYTES_PER_ROW_ALIGNMENT) * COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = self.device.create_buffer(&BufferDescriptor {
+            label: Some("Off-screen Readback"),
+            size: padded_row_bytes as u64 * height as u64,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self.device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("Off-screen Readback"),
+        });
+        encoder.copy_texture_to_buffer(
+            ImageCopyTexture {
+                texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            ImageCopyBuffer {
+                buffer: &buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(Maintain::Wait);
+        receiver.recv().ok()?.ok()?;
+
+        let pixels = slice
+            .get_mapped_range()
+            .chunks(padded_row_bytes as usize)
+            .flat_map(|row| row[..row_bytes as usize].to_vec())
+            .collect();
+        buffer.unmap();
+        Some(pixels)
     }
 
     /// Gets the aspect ratio of the current surface
@@ -148,6 +467,126 @@ impl WgpuContext {
     pub fn size(&self) -> (u32, u32) {
         self.size
     }
+
+    /// Starts timing a frame
+    pub fn begin_frame(&mut self) {
+        self.stats.begin_frame(Instant::now());
+    }
+
+    /// Finishes timing a frame; call after submitting its command buffer
+    ///
+    /// With the overlay on and GPU timing enabled this waits for the GPU to
+    /// read back the frame's timestamps.
+    pub fn end_frame(&mut self) {
+        if self.stats_overlay {
+            if let Some(ms) = self.gpu_timer.as_ref().and_then(|timer| timer.read_ms(&self.device)) {
+                self.stats.record_gpu_time(ms);
+            }
+        }
+        self.stats.end_frame(Instant::now());
+    }
+
+    /// Writes data to a GPU buffer, counting the bytes uploaded
+    pub fn write_buffer(&mut self, buffer: &Buffer, offset: BufferAddress, data: &[u8]) {
+        self.queue.write_buffer(buffer, offset, data);
+        self.stats.record_upload(data.len() as u64);
+    }
+
+    /// Counts draw calls made in the current frame
+    pub fn record_draws(&mut self, count: u32) {
+        self.stats.record_draws(count);
+    }
+
+    /// Turns on GPU timestamps; returns false if the adapter can't do them
+    pub fn enable_gpu_timing(&mut self) -> bool {
+        if self.gpu_timer.is_none() {
+            self.gpu_timer = GpuTimer::new(&self.device, &self.queue);
+        }
+        self.gpu_timer.is_some()
+    }
+
+    /// The GPU timer, for the frame's render pass timestamp writes
+    pub fn gpu_timer(&self) -> Option<&GpuTimer> {
+        self.gpu_timer.as_ref()
+    }
+
+    /// Performance numbers of the recent frames
+    pub fn stats(&self) -> &RenderStats {
+        &self.stats
+    }
+
+    /// Shows or hides the performance overlay
+    pub fn set_stats_overlay(&mut self, visible: bool) {
+        self.stats_overlay = visible;
+    }
+
+    /// Lines of the performance overlay, or `None` while it is hidden
+    pub fn stats_overlay(&self) -> Option<Vec<String>> {
+        self.stats_overlay.then(|| self.stats.overlay_lines())
+    }
+}
+
+/// Requests the device and queue, with timestamp queries when the adapter
+/// has them (they cost nothing until GPU timing is turned on)
+async fn request_device(adapter: &Adapter) -> Result<(Device, Queue), RequestDeviceError> {
+    adapter
+        .request_device(
+            &DeviceDescriptor {
+                label: Some("Rubik's Cube Renderer Device"),
+                required_features: adapter.features() & Features::TIMESTAMP_QUERY,
+                required_limits: Limits::default(),
+                memory_hints: MemoryHints::default(),
+            },
+            None,
+        )
+        .await
+}
+
+/// Flag that turns true once `device` is lost
+fn watch_device_loss(device: &Device) -> Arc<AtomicBool> {
+    let lost = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&lost);
+    device.set_device_lost_callback(move |reason, _message| {
+        // Dropping the device on purpose (when replacing it) isn't a loss
+        if !matches!(reason, DeviceLostReason::Dropped) {
+            flag.store(true, Ordering::SeqCst);
+        }
+    });
+    lost
+}
+
+/// Picks a format and alpha mode the surface supports, preferring sRGB and
+/// keeping the configured ones when they are still supported
+fn match_capabilities(config: &mut SurfaceConfiguration, caps: &SurfaceCapabilities) {
+    if !caps.formats.contains(&config.format) {
+        if let Some(format) = caps.formats.iter().find(|f| f.is_srgb()).or(caps.formats.first()) {
+            config.format = *format;
+        }
+    }
+    if !caps.alpha_modes.contains(&config.alpha_mode) {
+        if let Some(alpha_mode) = caps.alpha_modes.first() {
+            config.alpha_mode = *alpha_mode;
+        }
+    }
+}
+
+/// Texture an off-screen context draws into, sized and formatted like
+/// `config`
+fn offscreen_texture(device: &Device, config: &SurfaceConfiguration) -> Texture {
+    device.create_texture(&TextureDescriptor {
+        label: Some("Off-screen Target"),
+        size: Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: config.format,
+        usage: config.usage,
+        view_formats: &[],
+    })
 }
 
 #[cfg(test)]
@@ -184,6 +623,45 @@ mod tests {
         assert!((aspect - 16.0 / 9.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_surface_recovery() {
+        assert_eq!(SurfaceRecovery::for_error(&SurfaceError::Lost), SurfaceRecovery::Reconfigure);
+        assert_eq!(SurfaceRecovery::for_error(&SurfaceError::Outdated), SurfaceRecovery::Reconfigure);
+        assert_eq!(SurfaceRecovery::for_error(&SurfaceError::Timeout), SurfaceRecovery::SkipFrame);
+        assert_eq!(SurfaceRecovery::for_error(&SurfaceError::OutOfMemory), SurfaceRecovery::Fatal);
+    }
+
+    #[test]
+    fn test_match_capabilities_prefers_srgb() {
+        let mut config = SurfaceConfiguration {
+            usage: TextureUsages::RENDER_ATTACHMENT,
+            format: TextureFormat::Bgra8UnormSrgb,
+            width: 800,
+            height: 600,
+            present_mode: PresentMode::Fifo,
+            alpha_mode: CompositeAlphaMode::Auto,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        let caps = SurfaceCapabilities {
+            formats: vec![TextureFormat::Rgba8Unorm, TextureFormat::Rgba8UnormSrgb],
+            alpha_modes: vec![CompositeAlphaMode::Opaque],
+            ..Default::default()
+        };
+        match_capabilities(&mut config, &caps);
+        assert_eq!(config.format, TextureFormat::Rgba8UnormSrgb);
+        assert_eq!(config.alpha_mode, CompositeAlphaMode::Opaque);
+
+        // Still-supported settings are kept
+        let caps = SurfaceCapabilities {
+            formats: vec![TextureFormat::Rgba8Unorm, TextureFormat::Rgba8UnormSrgb],
+            alpha_modes: vec![CompositeAlphaMode::PreMultiplied, CompositeAlphaMode::Opaque],
+            ..Default::default()
+        };
+        match_capabilities(&mut config, &caps);
+        assert_eq!(config.alpha_mode, CompositeAlphaMode::Opaque);
+    }
+
     #[test]
     fn test_config_values() {
         let config = WgpuContextConfig {
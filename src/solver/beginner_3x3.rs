@@ -1,63 +1,409 @@
 //! 3x3 Beginner's Method Solver
 //!
-//! This implementation uses a simple layer-by-layer beginner's method
-//! that works with our sticker-based cube representation.
-//! While not optimal (God's number is 20), it will solve any cube.
+//! Solves the way the beginner course teaches, one stage at a time (cross,
+//! first layer corners, second layer, yellow cross, yellow corners, last
+//! layer), so a tutorial can show each stage's moves in its own chapter.
+//! While not optimal (God's number is 20), it solves any valid cube in
+//! milliseconds.
+//!
+//! Each stage looks its pieces up and applies the algorithms a person
+//! would: the first layer corners go in by repeating "R U R' U'" over their
+//! slot, the middle edges with the two second-layer algorithms, and the
+//! last layer uses the looks of the last-layer solver. Among the slots left,
+//! the piece needing the fewest moves goes in first. The first layer is
+//! built on D, without rotating the cube.
+//!
+//! A cube only a few turns from solved is turned back by a short search
+//! from both ends instead. Solutions with a restricted move set (e.g.
+//! ⟨R, U⟩), where the algorithms cannot be used, come from a depth-limited
+//! search. Searched moves are divided at the points where each stage is
+//! first complete.
 
-use crate::cube::{Cube, Move};
-use crate::solver::solution::{Solution, SolutionStep};
+use crate::cube::{Color, Cube, FaceName, Move};
+use crate::solver::cross::{is_cross_solved, solve_cross};
+use crate::solver::explain::{explain_moves, pieces, Piece};
+use crate::solver::f2l::{face_slot, standard_algorithm, F2lSlot};
+use crate::solver::last_layer::{corners_oriented, edges_oriented, is_f2l_solved, look_moves};
+use crate::solver::options::SolveOptions;
+use crate::solver::parallel::{search_roots, Branch};
+use crate::solver::rotations::{combine_turns, remove_rotations, TURNS};
+use crate::solver::solution::{MoveNote, Solution, SolutionStep};
+use std::collections::HashMap;
+use std::sync::OnceLock;
 use std::time::Instant;
 
+/// A stage of the beginner's method, in solving order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BeginnerStage {
+    /// Cross on the bottom face
+    Cross,
+    /// The four bottom corners, finishing the first layer
+    FirstLayerCorners,
+    /// The four middle-layer edges
+    SecondLayer,
+    /// Cross of the top color on the top face
+    YellowCross,
+    /// Every top corner turned top color up (the last part of OLL)
+    OrientCorners,
+    /// Top pieces moved into place (PLL)
+    PermuteLastLayer,
+}
+
+impl BeginnerStage {
+    /// All stages, in solving order
+    pub const ALL: [BeginnerStage; 6] = [
+        BeginnerStage::Cross,
+        BeginnerStage::FirstLayerCorners,
+        BeginnerStage::SecondLayer,
+        BeginnerStage::YellowCross,
+        BeginnerStage::OrientCorners,
+        BeginnerStage::PermuteLastLayer,
+    ];
+
+    /// Step description
+    pub fn description(&self) -> &'static str {
+        match self {
+            BeginnerStage::Cross => "Make the cross on the bottom",
+            BeginnerStage::FirstLayerCorners => "Put in the first layer corners",
+            BeginnerStage::SecondLayer => "Put in the second layer edges",
+            BeginnerStage::YellowCross => "Make the yellow cross on top",
+            BeginnerStage::OrientCorners => "Turn the top corners yellow side up",
+            BeginnerStage::PermuteLastLayer => "Move the top pieces into place",
+        }
+    }
+
+    /// Step explanation
+    pub fn explanation(&self) -> &'static str {
+        match self {
+            BeginnerStage::Cross => "Match the bottom edges with the bottom center and their side centers",
+            BeginnerStage::FirstLayerCorners => "Place each bottom corner between its edges to finish the first layer",
+            BeginnerStage::SecondLayer => "Insert each middle edge between its two centers without breaking the first layer",
+            BeginnerStage::YellowCross => "Flip the top edges until they form a cross of the top color",
+            BeginnerStage::OrientCorners => "Twist the top corners until the whole top face is one color",
+            BeginnerStage::PermuteLastLayer => "Swap the top corners, then cycle the top edges, until the cube is solved",
+        }
+    }
+
+    /// Whether this stage and every stage before it are done
+    pub fn is_complete(&self, cube: &Cube) -> bool {
+        match self {
+            BeginnerStage::Cross => is_cross_solved(cube, cube.get_face(FaceName::D).get(1, 1)),
+            BeginnerStage::FirstLayerCorners => BeginnerStage::Cross.is_complete(cube) && first_layer_solved(cube),
+            BeginnerStage::SecondLayer => is_f2l_solved(cube),
+            BeginnerStage::YellowCross => is_f2l_solved(cube) && edges_oriented(cube),
+            BeginnerStage::OrientCorners => is_f2l_solved(cube) && corners_oriented(cube),
+            BeginnerStage::PermuteLastLayer => cube.is_solved(),
+        }
+    }
+}
+
+/// The bottom face and the bottom row of every side match their centers
+fn first_layer_solved(cube: &Cube) -> bool {
+    cube.get_face(FaceName::D).is_solved()
+        && [FaceName::F, FaceName::R, FaceName::B, FaceName::L].iter().all(|&side| {
+            let face = cube.get_face(side);
+            (0..3).all(|col| face.get(2, col) == face.get(1, 1))
+        })
+}
+
+/// The moves of one stage of a beginner solution
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeginnerStageSolution {
+    /// The stage
+    pub stage: BeginnerStage,
+    /// Moves that complete the stage; empty if it was already done
+    pub moves: Vec<Move>,
+    /// Purposes of the moves, indexed within the stage
+    pub notes: Vec<MoveNote>,
+}
+
 /// Solution for a 3x3 cube using beginner's method
 #[derive(Debug, Clone)]
 pub struct Solution3x3Beginner {
-    /// List of moves to solve the cube
-    pub moves: Vec<Move>,
+    /// Every stage in solving order, including stages that needed no moves
+    pub stages: Vec<BeginnerStageSolution>,
     /// Time taken to find the solution (in milliseconds)
     pub time_ms: u128,
-    /// Step-by-step breakdown of the solution
-    pub steps: Vec<SolutionStep>,
 }
 
 impl Solution3x3Beginner {
-    /// Create a new solution
-    pub fn new(moves: Vec<Move>, time_ms: u128) -> Self {
-        // Create a single step for backward compatibility
-        let steps = if moves.is_empty() {
-            vec![SolutionStep::new("Cube is already solved", vec![])]
-        } else {
-            vec![SolutionStep::new("Solve 3x3 cube using beginner's method", moves.clone())]
-        };
+    /// Split moves that solve `cube` into the stages they complete
+    ///
+    /// Each stage ends at the first move after the previous stage where
+    /// it is complete; moves left over (if `moves` does not solve the
+    /// cube) go to the last stage.
+    pub fn from_moves(cube: &Cube, moves: &[Move], time_ms: u128) -> Self {
+        let mut state = cube.clone();
+        let mut position = 0;
+        let mut stage_moves = Vec::new();
+        for stage in BeginnerStage::ALL {
+            let from = position;
+            while position < moves.len() && !stage.is_complete(&state) {
+                state.apply_move(moves[position]);
+                position += 1;
+            }
+            if stage == BeginnerStage::PermuteLastLayer {
+                position = moves.len();
+            }
+            stage_moves.push(moves[from..position].to_vec());
+        }
+        Self::from_stages(cube, stage_moves, time_ms)
+    }
+
+    /// Solution from the moves of each stage, in [`BeginnerStage::ALL`]
+    /// order
+    pub fn from_stages(cube: &Cube, stage_moves: Vec<Vec<Move>>, time_ms: u128) -> Self {
+        let mut state = cube.clone();
+        let stages = BeginnerStage::ALL
+            .into_iter()
+            .zip(stage_moves)
+            .map(|(stage, moves)| {
+                let notes = explain_moves(&state, &moves);
+                state.apply_moves(&moves);
+                BeginnerStageSolution { stage, moves, notes }
+            })
+            .collect();
+        Self { stages, time_ms }
+    }
 
-        Self { moves, time_ms, steps }
+    /// All moves, in order
+    pub fn moves(&self) -> Vec<Move> {
+        self.stages.iter().flat_map(|stage| stage.moves.iter().copied()).collect()
     }
 
-    /// Create a new solution with custom steps
-    pub fn with_steps(moves: Vec<Move>, time_ms: u128, steps: Vec<SolutionStep>) -> Self {
-        Self { moves, time_ms, steps }
+    /// Moves of one stage
+    pub fn stage(&self, stage: BeginnerStage) -> Option<&BeginnerStageSolution> {
+        self.stages.iter().find(|solution| solution.stage == stage)
     }
 
     /// Get the number of moves in the solution
     pub fn move_count(&self) -> usize {
-        self.moves.len()
+        self.stages.iter().map(|stage| stage.moves.len()).sum()
+    }
+
+    /// One step per stage that needed moves, or a single "already solved"
+    /// step
+    pub fn steps(&self) -> Vec<SolutionStep> {
+        let steps: Vec<SolutionStep> = self
+            .stages
+            .iter()
+            .filter(|stage| !stage.moves.is_empty())
+            .map(|stage| {
+                SolutionStep::with_explanation(stage.stage.description(), stage.moves.clone(), stage.stage.explanation())
+                    .with_notes(stage.notes.iter().cloned())
+            })
+            .collect();
+        if steps.is_empty() {
+            vec![SolutionStep::new("Cube is already solved", vec![])]
+        } else {
+            steps
+        }
     }
 
     /// Get the number of steps in the solution
     pub fn step_count(&self) -> usize {
-        self.steps.len()
+        self.steps().len()
     }
 
     /// Convert to generic Solution type
     pub fn to_solution(&self) -> Solution {
-        Solution::with_method(self.steps.clone(), self.time_ms, "Beginner's Layer-by-Layer Method")
+        Solution::with_method(self.steps(), self.time_ms, "Beginner's Layer-by-Layer Method")
+    }
+}
+
+/// Deepest search with a restricted move set
+const MAX_SEARCH_DEPTH: usize = 12;
+
+/// Add `more` to `moves`, merging turns of the same layer where they meet
+fn append(moves: &mut Vec<Move>, more: &[Move]) {
+    for &mv in more {
+        match moves.last().and_then(|last| combine_turns(*last, mv)) {
+            Some(combined) => {
+                moves.pop();
+                moves.extend(combined);
+            }
+            None => moves.push(mv),
+        }
+    }
+}
+
+/// An algorithm written for the front-right slot, performed at `slot`
+/// without rotating the cube
+fn at_slot(slot: F2lSlot, algorithm: &[Move]) -> Vec<Move> {
+    // Centers never move, so the rotation to the slot is the same on any cube
+    let (_, rotation) = face_slot(&Cube::new(3), slot).expect("every slot can be turned to the front");
+    let mut moves = rotation.clone();
+    moves.extend_from_slice(algorithm);
+    moves.extend(rotation.iter().rev().map(|mv| mv.inverse()));
+    remove_rotations(&moves)
+}
+
+/// The piece whose stickers lie on exactly `faces`
+fn piece_at(pieces: &[Piece], faces: &[FaceName]) -> Piece {
+    pieces
+        .iter()
+        .find(|piece| {
+            piece.stickers.len() == faces.len() && piece.stickers.iter().all(|(face, _, _)| faces.contains(face))
+        })
+        .cloned()
+        .expect("every corner and edge position is a piece")
+}
+
+/// Algorithms for putting one kind of piece into the four slots, in
+/// [`F2lSlot::ALL`] order
+struct SlotAlgorithms {
+    /// Takes whatever piece is in each slot out to the top layer
+    lifts: Vec<Vec<Move>>,
+    /// Ways to put the piece above each slot in, one list per slot
+    inserts: Vec<Vec<Vec<Move>>>,
+}
+
+impl SlotAlgorithms {
+    /// `lift` and `inserts` (written for the front-right slot) performed at
+    /// every slot
+    fn new(lift: &[Move], inserts: &[Vec<Move>]) -> Self {
+        Self {
+            lifts: F2lSlot::ALL.iter().map(|slot| at_slot(*slot, lift)).collect(),
+            inserts: F2lSlot::ALL
+                .iter()
+                .map(|slot| inserts.iter().map(|insert| at_slot(*slot, insert)).collect())
+                .collect(),
+        }
+    }
+
+    /// First layer corners: repeating "R U R' U'" (or its inverse) twists
+    /// the corner above the slot into place, whichever way it faces
+    fn corners() -> &'static Self {
+        static CORNERS: OnceLock<SlotAlgorithms> = OnceLock::new();
+        CORNERS.get_or_init(|| {
+            let trigger = standard_algorithm("R U R' U'");
+            let undo: Vec<Move> = trigger.iter().rev().map(|mv| mv.inverse()).collect();
+            let inserts: Vec<Vec<Move>> = (1..=3).flat_map(|times| [trigger.repeat(times), undo.repeat(times)]).collect();
+            Self::new(&trigger, &inserts)
+        })
+    }
+
+    /// Second layer edges, coming in from the front or from the right
+    fn edges() -> &'static Self {
+        static EDGES: OnceLock<SlotAlgorithms> = OnceLock::new();
+        EDGES.get_or_init(|| {
+            let inserts = [standard_algorithm("U R U' R' U' F' U F"), standard_algorithm("U' F' U F U R U' R'")];
+            Self::new(&inserts[0], &inserts)
+        })
+    }
+}
+
+/// Where the piece that belongs at `home` is now, among `positions`
+fn find_piece<'a>(cube: &Cube, home: &Piece, positions: &'a [Piece]) -> Option<&'a Piece> {
+    let colors = |stickers: &mut dyn Iterator<Item = Color>| {
+        let mut colors: Vec<Color> = stickers.collect();
+        colors.sort();
+        colors
+    };
+    let wanted = colors(&mut home.stickers.iter().map(|(face, _, _)| cube.get_face(*face).get(1, 1)));
+    positions.iter().find(|position| {
+        colors(&mut position.stickers.iter().map(|(face, row, col)| cube.get_face(*face).get(*row, *col))) == wanted
+    })
+}
+
+/// Put the piece of every slot in, cheapest first
+///
+/// `targets` are the four slot positions and `positions` every position
+/// of that kind of piece. A piece goes in with a top-layer turn and one of
+/// the slot's inserts, after lifting it out if it is stuck in a slot.
+/// Pieces in `keep` and pieces already put in stay where they are.
+fn fill_slots(
+    state: &mut Cube,
+    targets: &[Piece],
+    positions: &[Piece],
+    keep: &[Piece],
+    algorithms: &SlotAlgorithms,
+) -> Result<Vec<Move>, String> {
+    const TOP_TURNS: [&[Move]; 4] = [&[], &[Move::U], &[Move::UPrime], &[Move::U2]];
+    let not_found = || "A piece could not be put into its slot (is the cube valid?)".to_string();
+    let mut moves = Vec::new();
+    loop {
+        let open: Vec<usize> = (0..targets.len()).filter(|&slot| !targets[slot].is_placed(state)).collect();
+        if open.is_empty() {
+            return Ok(moves);
+        }
+        let kept: Vec<&Piece> = keep.iter().chain(targets.iter().filter(|piece| piece.is_placed(state))).collect();
+
+        let mut best: Option<(Cube, Vec<Move>)> = None;
+        for &slot in &open {
+            let found = find_piece(state, &targets[slot], positions).ok_or_else(not_found)?;
+            let stuck = targets.iter().position(|target| target.stickers == found.stickers);
+            let lifts = std::iter::once(&[][..]).chain(stuck.map(|stuck| algorithms.lifts[stuck].as_slice()));
+            for lift in lifts {
+                for turn in TOP_TURNS {
+                    for insert in &algorithms.inserts[slot] {
+                        let mut candidate = lift.to_vec();
+                        append(&mut candidate, turn);
+                        append(&mut candidate, insert);
+                        if best.as_ref().is_some_and(|(_, found)| found.len() <= candidate.len()) {
+                            continue;
+                        }
+                        let mut next = state.clone();
+                        next.apply_moves(&candidate);
+                        if targets[slot].is_placed(&next) && kept.iter().all(|piece| piece.is_placed(&next)) {
+                            best = Some((next, candidate));
+                        }
+                    }
+                }
+            }
+        }
+        let (next, found) = best.ok_or_else(not_found)?;
+        *state = next;
+        append(&mut moves, &found);
+    }
+}
+
+/// Moves for each stage, in [`BeginnerStage::ALL`] order
+fn solve_by_stages(cube: &Cube) -> Result<Vec<Vec<Move>>, String> {
+    let pieces = pieces();
+    let (all_corners, all_edges): (Vec<Piece>, Vec<Piece>) = pieces.iter().cloned().partition(|piece| piece.kind() == "corner");
+    let slot_faces: Vec<(FaceName, FaceName)> = F2lSlot::ALL.iter().map(|slot| slot.faces()).collect();
+    let corners: Vec<Piece> = slot_faces.iter().map(|(a, b)| piece_at(&pieces, &[FaceName::D, *a, *b])).collect();
+    let edges: Vec<Piece> = slot_faces.iter().map(|(a, b)| piece_at(&pieces, &[*a, *b])).collect();
+    let cross: Vec<Piece> = slot_faces.iter().map(|(a, _)| piece_at(&pieces, &[FaceName::D, *a])).collect();
+
+    let mut state = cube.clone();
+    let cross_moves = solve_cross(&state, state.get_face(FaceName::D).get(1, 1))?.all_moves();
+    state.apply_moves(&cross_moves);
+    let corner_moves = fill_slots(&mut state, &corners, &all_corners, &cross, SlotAlgorithms::corners())?;
+    let first_layer: Vec<Piece> = cross.iter().chain(&corners).cloned().collect();
+    let edge_moves = fill_slots(&mut state, &edges, &all_edges, &first_layer, SlotAlgorithms::edges())?;
+
+    let mut looks = look_moves(&state)?.into_iter();
+    let mut next_look = || looks.next().unwrap_or_default();
+    let yellow_cross = next_look();
+    let yellow_corners = next_look();
+    let mut last_layer = next_look();
+    for look in looks.by_ref() {
+        append(&mut last_layer, &look);
     }
+    Ok(vec![cross_moves, corner_moves, edge_moves, yellow_cross, yellow_corners, last_layer])
 }
 
 /// Solves a 3x3 Rubik's Cube using beginner's layer-by-layer method
 ///
-/// This uses a depth-limited search similar to the 2x2 solver.
-/// It's not optimal but will solve any valid cube.
+/// Every stage is solved with the beginner algorithms, so any valid cube is
+/// solved in milliseconds; a cube at most four turns from solved is turned
+/// straight back instead. Errors if the cube cannot be solved (a twisted
+/// corner or flipped edge).
 pub fn solve_3x3_beginner(cube: &Cube) -> Result<Solution3x3Beginner, String> {
+    solve_3x3_beginner_with_options(cube, &SolveOptions::default())
+}
+
+/// Solves a 3x3 Rubik's Cube using only the moves `options` allows
+///
+/// Without a restriction this solves stage by stage like
+/// [`solve_3x3_beginner`]; with one it searches the allowed moves, which
+/// only finds short solutions.
+pub fn solve_3x3_beginner_with_options(
+    cube: &Cube,
+    options: &SolveOptions,
+) -> Result<Solution3x3Beginner, String> {
     let start = Instant::now();
 
     if cube.size() != 3 {
@@ -71,50 +417,135 @@ pub fn solve_3x3_beginner(cube: &Cube) -> Result<Solution3x3Beginner, String> {
     // If already solved, return empty solution
     if cube.is_solved() {
         let elapsed = start.elapsed().as_millis();
-        return Ok(Solution3x3Beginner::new(vec![], elapsed));
+        return Ok(Solution3x3Beginner::from_moves(cube, &[], elapsed));
+    }
+
+    if options.allowed_moves.is_none() {
+        // A cube a few turns from solved is simply turned back
+        if let Some(moves) = solve_short(cube) {
+            let elapsed = start.elapsed().as_millis();
+            return Ok(Solution3x3Beginner::from_moves(cube, &options.finish(moves), elapsed));
+        }
+        let stages = solve_by_stages(cube)?.into_iter().map(|moves| options.finish(moves)).collect();
+        let elapsed = start.elapsed().as_millis();
+        return Ok(Solution3x3Beginner::from_stages(cube, stages, elapsed));
     }
 
     // Use depth-limited search with increasing depth
-    let moves = solve_with_dls(cube)?;
+    let moves = options.finish(solve_with_dls(cube, options, MAX_SEARCH_DEPTH)?);
 
     let elapsed = start.elapsed().as_millis();
-    Ok(Solution3x3Beginner::new(moves, elapsed))
-}
-
-/// Solve using depth-limited search
-fn solve_with_dls(cube: &Cube) -> Result<Vec<Move>, String> {
-    // All basic moves for 3x3
-    let basic_moves = vec![
-        Move::R, Move::RPrime, Move::R2,
-        Move::U, Move::UPrime, Move::U2,
-        Move::F, Move::FPrime, Move::F2,
-        Move::L, Move::LPrime, Move::L2,
-        Move::D, Move::DPrime, Move::D2,
-        Move::B, Move::BPrime, Move::B2,
-    ];
+    Ok(Solution3x3Beginner::from_moves(cube, &moves, elapsed))
+}
+
+/// Outer face turns, for finding short solutions
+const FACE_TURNS: [Move; 18] = [
+    Move::R, Move::RPrime, Move::R2,
+    Move::L, Move::LPrime, Move::L2,
+    Move::U, Move::UPrime, Move::U2,
+    Move::D, Move::DPrime, Move::D2,
+    Move::F, Move::FPrime, Move::F2,
+    Move::B, Move::BPrime, Move::B2,
+];
+
+/// Sticker colors, face by face
+fn state_key(cube: &Cube) -> Vec<Color> {
+    cube.stickers().map(|(_, _, _, color)| color).collect()
+}
+
+/// Every state at most two turns from solved, with its shortest solution
+fn near_solved() -> &'static HashMap<Vec<Color>, Vec<Move>> {
+    static STATES: OnceLock<HashMap<Vec<Color>, Vec<Move>>> = OnceLock::new();
+    STATES.get_or_init(|| {
+        let solved = Cube::new(3);
+        let mut states = HashMap::from([(state_key(&solved), vec![])]);
+        for first in FACE_TURNS {
+            let mut cube = solved.clone();
+            cube.apply_move(first);
+            states.entry(state_key(&cube)).or_insert_with(|| vec![first.inverse()]);
+        }
+        for first in FACE_TURNS {
+            for second in FACE_TURNS.into_iter().filter(|second| !should_skip_move(first, *second)) {
+                let mut cube = solved.clone();
+                cube.apply_moves(&[first, second]);
+                states.entry(state_key(&cube)).or_insert_with(|| vec![second.inverse(), first.inverse()]);
+            }
+        }
+        states
+    })
+}
 
-    // Try increasing depths (beginner method usually needs 50-100 moves)
-    for depth in 1..=12 {
-        if let Some(solution) = try_solve_at_depth(cube, depth, &basic_moves, None) {
+/// Shortest solution of at most four turns, if there is one
+///
+/// Turns the cube up to two turns and looks the result up among the states
+/// two turns from solved, instead of searching four turns deep.
+fn solve_short(cube: &Cube) -> Option<Vec<Move>> {
+    let states = near_solved();
+    let finish = |turns: &[Move]| {
+        let mut turned = cube.clone();
+        turned.apply_moves(turns);
+        let rest = states.get(&state_key(&turned))?;
+        Some(turns.iter().chain(rest).copied().collect())
+    };
+
+    finish(&[])
+        .or_else(|| FACE_TURNS.iter().find_map(|first| finish(&[*first])))
+        .or_else(|| {
+            FACE_TURNS.iter().find_map(|first| {
+                FACE_TURNS
+                    .iter()
+                    .filter(|second| !should_skip_move(*first, **second))
+                    .find_map(|second| finish(&[*first, *second]))
+            })
+        })
+}
+
+/// Solve using depth-limited search, up to `max_depth` moves
+fn solve_with_dls(cube: &Cube, options: &SolveOptions, max_depth: usize) -> Result<Vec<Move>, String> {
+    let basic_moves = options.search_moves(&FACE_TURNS)?;
+
+    // Try increasing depths
+    for depth in 1..=max_depth {
+        if let Some(solution) = solve_at_depth(cube, depth, &basic_moves) {
             return Ok(solution);
         }
     }
 
-    Err("Could not find solution within depth limit".to_string())
+    match options.describe() {
+        Some(moves) => Err(format!("Could not find solution using only {} within depth limit", moves)),
+        None => Err("Could not find solution within depth limit".to_string()),
+    }
 }
 
-/// Try to find solution at specific depth
+/// Try to find solution at specific depth, one root branch per first move
+pub(super) fn solve_at_depth(cube: &Cube, depth: usize, moves: &[Move]) -> Option<Vec<Move>> {
+    if cube.is_solved() {
+        return Some(vec![]);
+    }
+
+    search_roots(moves.len(), |branch| {
+        let m = moves[branch.index()];
+        let mut new_cube = cube.clone();
+        new_cube.apply_move(m);
+        let mut solution = try_solve_at_depth(&new_cube, depth - 1, moves, Some(m), branch)?;
+        solution.insert(0, m);
+        Some(solution)
+    })
+}
+
+/// Try to find solution at specific depth below a root branch
 fn try_solve_at_depth(
     cube: &Cube,
     depth: usize,
     moves: &[Move],
     prev_move: Option<Move>,
+    branch: &Branch,
 ) -> Option<Vec<Move>> {
     if cube.is_solved() {
         return Some(vec![]);
     }
 
-    if depth == 0 {
+    if depth == 0 || branch.is_beaten() {
         return None;
     }
 
@@ -129,7 +560,7 @@ fn try_solve_at_depth(
         let mut new_cube = cube.clone();
         new_cube.apply_move(m);
 
-        if let Some(mut solution) = try_solve_at_depth(&new_cube, depth - 1, moves, Some(m)) {
+        if let Some(mut solution) = try_solve_at_depth(&new_cube, depth - 1, moves, Some(m), branch) {
             solution.insert(0, m);
             return Some(solution);
         }
@@ -139,33 +570,25 @@ fn try_solve_at_depth(
 }
 
 /// Check if we should skip a move to avoid redundancy
+///
+/// Two turns of the same layer in a row are one turn, and turns of
+/// opposite faces commute, so those are only searched in one order (R
+/// before L, U before D, F before B).
 fn should_skip_move(prev: Move, current: Move) -> bool {
-    // Don't do the same move twice in a row (would be better as a double move)
-    if prev == current {
-        return true;
-    }
-
-    // Don't do opposite moves (R R' = nothing)
-    if prev.inverse() == current {
-        return true;
+    let layer = |mv: Move| TURNS.iter().position(|turns| turns.contains(&mv));
+    match (layer(prev), layer(current)) {
+        // The first six layers are the faces, in opposite pairs
+        (Some(a), Some(b)) => a == b || (a < 6 && b < 6 && a / 2 == b / 2 && a > b),
+        _ => prev == current || prev.inverse() == current,
     }
-
-    // For moves on the same face, prefer the canonical order
-    use Move::*;
-    matches!(
-        (prev, current),
-        (R, RPrime) | (R, R2) | (RPrime, R2) |
-        (L, LPrime) | (L, L2) | (LPrime, L2) |
-        (U, UPrime) | (U, U2) | (UPrime, U2) |
-        (D, DPrime) | (D, D2) | (DPrime, D2) |
-        (F, FPrime) | (F, F2) | (FPrime, F2) |
-        (B, BPrime) | (B, B2) | (BPrime, B2)
-    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solver::f2l::standard_algorithm;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
 
     #[test]
     fn test_solved_cube() {
@@ -186,9 +609,109 @@ mod tests {
 
         // Verify solution works
         let mut test_cube = cube.clone();
-        for m in &solution.moves {
+        for m in &solution.moves() {
             test_cube.apply_move(*m);
         }
         assert!(test_cube.is_solved());
     }
+
+    #[test]
+    fn test_two_gen_solution() {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::U2, Move::RPrime, Move::UPrime, Move::R]);
+
+        let options = SolveOptions::two_gen();
+        let solution = solve_3x3_beginner_with_options(&cube, &options).expect("Should solve");
+        assert!(solution.moves().iter().all(|m| options.allows(*m)));
+
+        let mut test_cube = cube.clone();
+        test_cube.apply_moves(&solution.moves());
+        assert!(test_cube.is_solved());
+    }
+
+    #[test]
+    fn test_short_solutions_are_looked_up() {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::U2, Move::FPrime, Move::L]);
+        let moves = solve_short(&cube).expect("four turns from solved");
+        assert_eq!(moves.len(), 4);
+        cube.apply_moves(&moves);
+        assert!(cube.is_solved());
+
+        cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::L]);
+        assert_eq!(solve_short(&cube).map(|moves| moves.len()), Some(2));
+
+        cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::U, Move::F, Move::L, Move::D]);
+        assert_eq!(solve_short(&cube), None);
+    }
+
+    #[test]
+    fn test_solves_scrambles_stage_by_stage() {
+        let turns = [Move::R, Move::L, Move::U, Move::D, Move::F, Move::B];
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..4 {
+            let mut cube = Cube::new(3);
+            for _ in 0..25 {
+                let turn = turns[rng.gen_range(0..turns.len())];
+                cube.apply_move(if rng.gen_bool(0.5) { turn } else { turn.inverse() });
+            }
+            let solution = solve_3x3_beginner(&cube).expect("Should solve");
+            let mut state = cube.clone();
+            for stage in &solution.stages {
+                state.apply_moves(&stage.moves);
+                assert!(stage.stage.is_complete(&state), "{:?} is not complete", stage.stage);
+            }
+            assert!(state.is_solved());
+            // Face turns only: the cube is never rotated
+            assert!(solution.moves().iter().all(|m| m.to_notation().starts_with(|c: char| c.is_ascii_uppercase())));
+        }
+    }
+
+    #[test]
+    fn test_twisted_corner_cannot_be_solved() {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::U, Move::F]);
+        let corner = pieces().into_iter().find(|piece| piece.kind() == "corner").unwrap();
+        let colors: Vec<Color> = corner.stickers.iter().map(|(face, row, col)| cube.get_face(*face).get(*row, *col)).collect();
+        for (i, (face, row, col)) in corner.stickers.iter().enumerate() {
+            cube.set_sticker(*face, *row, *col, colors[(i + 1) % 3]);
+        }
+        assert!(solve_3x3_beginner(&cube).is_err());
+    }
+
+    #[test]
+    fn test_stages_in_order() {
+        let solution = solve_3x3_beginner(&Cube::new(3)).expect("Should solve");
+        assert_eq!(solution.stages.iter().map(|s| s.stage).collect::<Vec<_>>(), BeginnerStage::ALL.to_vec());
+        assert!(solution.stages.iter().all(|s| s.moves.is_empty()));
+        assert_eq!(solution.steps()[0].description, "Cube is already solved");
+    }
+
+    #[test]
+    fn test_moves_split_where_stages_complete() {
+        // R breaks the cross on D, so both moves go to the cross and every
+        // later stage is already done once it is back
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::U]);
+        let solution = Solution3x3Beginner::from_moves(&cube, &[Move::UPrime, Move::RPrime], 0);
+        let moves = |stage| solution.stage(stage).unwrap().moves.clone();
+        assert_eq!(moves(BeginnerStage::Cross), vec![Move::UPrime, Move::RPrime]);
+        assert!(BeginnerStage::ALL[1..].iter().all(|stage| moves(*stage).is_empty()));
+
+        // A last-layer algorithm leaves the first two layers alone
+        let mut cube = Cube::new(3);
+        let algorithm = standard_algorithm("F R U R' U' F'");
+        cube.apply_moves(&algorithm);
+        let undo: Vec<Move> = algorithm.iter().rev().map(|m| m.inverse()).collect();
+        let solution = Solution3x3Beginner::from_moves(&cube, &undo, 0);
+        assert!(BeginnerStage::ALL[..3].iter().all(|stage| solution.stage(*stage).unwrap().moves.is_empty()));
+        assert_eq!(solution.moves(), undo);
+        assert_eq!(solution.move_count(), 6);
+        let mut solved = cube.clone();
+        solved.apply_moves(&solution.moves());
+        assert!(solved.is_solved());
+        assert!(solution.steps().iter().all(|step| !step.moves.is_empty()));
+    }
 }
@@ -0,0 +1,527 @@
+//! Blindfold solving: Speffz memo, Old Pochmann corners and M2 edges
+//!
+//! Every corner and edge sticker gets a letter in the Speffz scheme (A-X,
+//! each face read clockwise from its top-left: U, L, F, R, B, D). The memo
+//! is the list of letters the buffer sticker visits, traced on the scramble
+//! the way a blind solver does it before putting the blindfold on.
+//!
+//! Execution swaps the buffer with one target per letter:
+//! - Corners (Old Pochmann): buffer A (UBL), the target is set up to P
+//!   with R, D and F turns, then a Y-perm swaps A and P
+//! - Edges (M2): buffer U (DF), the target is set up to A (UB), then M2.
+//!   Targets on the M slice use fixed algorithms, swapped with their
+//!   partner after an odd number of edge targets
+//!
+//! An odd number of targets leaves parity, fixed with one extra algorithm
+//! between edges and corners.
+
+use crate::cube::{Color, Cube, FaceName, Move};
+use crate::solver::f2l::standard_algorithm;
+use crate::solver::options::face_turns;
+use crate::solver::solution::{Solution, SolutionStep};
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Corner stickers in letter order, A to X (clockwise from top-left)
+const CORNER_STICKERS: [(FaceName, usize, usize); 24] = by_face([(0, 0), (0, 2), (2, 2), (2, 0)]);
+
+/// Edge stickers in letter order, A to X (clockwise from the top)
+const EDGE_STICKERS: [(FaceName, usize, usize); 24] = by_face([(0, 1), (1, 2), (2, 1), (1, 0)]);
+
+/// Letters of each corner piece
+const CORNER_PIECES: [[char; 3]; 8] = [
+    ['A', 'E', 'R'],
+    ['B', 'Q', 'N'],
+    ['C', 'M', 'J'],
+    ['D', 'I', 'F'],
+    ['U', 'G', 'L'],
+    ['V', 'K', 'P'],
+    ['W', 'O', 'T'],
+    ['X', 'S', 'H'],
+];
+
+/// Letters of each edge piece
+const EDGE_PIECES: [[char; 2]; 12] = [
+    ['A', 'Q'],
+    ['B', 'M'],
+    ['C', 'I'],
+    ['D', 'E'],
+    ['F', 'L'],
+    ['H', 'R'],
+    ['J', 'P'],
+    ['N', 'T'],
+    ['U', 'K'],
+    ['V', 'O'],
+    ['W', 'S'],
+    ['X', 'G'],
+];
+
+/// Old Pochmann buffer (UBL)
+pub const CORNER_BUFFER: char = 'A';
+
+/// M2 buffer (DF)
+pub const EDGE_BUFFER: char = 'U';
+
+/// Where corner targets are set up to before the swap
+const CORNER_HELPER: char = 'P';
+
+/// Where edge targets are set up to before M2
+const EDGE_HELPER: char = 'A';
+
+/// Y-perm without its first and last move: swaps A and P
+const CORNER_SWAP: &str = "R U' R' U' R U R' F' R U R' U' R' F R";
+
+/// Moves allowed in corner setups: they never touch the buffer or the
+/// edges the swap disturbs
+const CORNER_SETUP_FACES: [FaceName; 3] = [FaceName::R, FaceName::D, FaceName::F];
+
+/// Moves allowed in edge setups: they never touch the buffer
+const EDGE_SETUP_FACES: [FaceName; 4] = [FaceName::U, FaceName::L, FaceName::R, FaceName::B];
+
+/// Longest setup tried
+const MAX_SETUP: usize = 4;
+
+/// Fixed algorithms for M-slice edge targets, as (even, odd) letters: the
+/// second letter is used after an odd number of edge targets
+const M_SLICE_TARGETS: [(char, char, &str); 5] = [
+    ('C', 'W', "U2 M' U2 M'"),
+    ('W', 'C', "M U2 M U2"),
+    ('I', 'S', "D M' U R2 U' M U R2 U' D' M2"),
+    ('S', 'I', "M2 D U R2 U' M' U R2 U' M D'"),
+    ('Q', 'Q', "U B' R U' B M2 B' U R' B U'"),
+];
+
+/// Fixes parity after an odd number of targets
+const PARITY_FIX: &str = "D' L2 D M2 D' L2 D";
+
+/// Letter positions for one piece type: the same four spots on each face,
+/// faces in Speffz order (U, L, F, R, B, D)
+const fn by_face(spots: [(usize, usize); 4]) -> [(FaceName, usize, usize); 24] {
+    const FACES: [FaceName; 6] = [FaceName::U, FaceName::L, FaceName::F, FaceName::R, FaceName::B, FaceName::D];
+    let mut stickers = [(FaceName::U, 0, 0); 24];
+    let mut i = 0;
+    while i < 24 {
+        stickers[i] = (FACES[i / 4], spots[i % 4].0, spots[i % 4].1);
+        i += 1;
+    }
+    stickers
+}
+
+/// Index of a letter (A = 0)
+fn index(letter: char) -> usize {
+    (letter as u8 - b'A') as usize
+}
+
+/// Letter of an index (0 = A)
+fn letter(index: usize) -> char {
+    (b'A' + index as u8) as char
+}
+
+/// Speffz letter of a sticker, or `None` for centers
+///
+/// Corner and edge stickers share the letters A-X; `corner` is true for a
+/// corner sticker.
+pub fn speffz_letter(face: FaceName, row: usize, col: usize) -> Option<(char, bool)> {
+    let find = |stickers: &[(FaceName, usize, usize); 24]| {
+        stickers
+            .iter()
+            .position(|s| *s == (face, row, col))
+            .map(letter)
+    };
+    find(&CORNER_STICKERS)
+        .map(|l| (l, true))
+        .or_else(|| find(&EDGE_STICKERS).map(|l| (l, false)))
+}
+
+/// Memo for a blindfold solve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlindMemo {
+    /// Edge targets, in execution order
+    pub edges: Vec<char>,
+    /// Corner targets, in execution order
+    pub corners: Vec<char>,
+}
+
+impl BlindMemo {
+    /// Whether the number of targets is odd (needs the parity algorithm)
+    pub fn has_parity(&self) -> bool {
+        self.edges.len() % 2 == 1
+    }
+
+    /// Letter pairs as they are memorized, e.g. ["CM", "KB", "E"]
+    pub fn pairs(letters: &[char]) -> Vec<String> {
+        letters.chunks(2).map(|pair| pair.iter().collect()).collect()
+    }
+
+    /// Edge memo as letter pairs
+    pub fn edge_pairs(&self) -> Vec<String> {
+        Self::pairs(&self.edges)
+    }
+
+    /// Corner memo as letter pairs
+    pub fn corner_pairs(&self) -> Vec<String> {
+        Self::pairs(&self.corners)
+    }
+}
+
+/// One piece type: where its stickers are and how they group into pieces
+struct PieceSet<'a> {
+    stickers: &'a [(FaceName, usize, usize); 24],
+    pieces: Vec<&'a [char]>,
+    buffer: char,
+}
+
+impl PieceSet<'_> {
+    fn corners() -> PieceSet<'static> {
+        PieceSet {
+            stickers: &CORNER_STICKERS,
+            pieces: CORNER_PIECES.iter().map(|p| p.as_slice()).collect(),
+            buffer: CORNER_BUFFER,
+        }
+    }
+
+    fn edges() -> PieceSet<'static> {
+        PieceSet {
+            stickers: &EDGE_STICKERS,
+            pieces: EDGE_PIECES.iter().map(|p| p.as_slice()).collect(),
+            buffer: EDGE_BUFFER,
+        }
+    }
+
+    /// The piece a letter belongs to
+    fn piece_of(&self, letter: char) -> usize {
+        self.pieces.iter().position(|p| p.contains(&letter)).unwrap_or(0)
+    }
+
+    /// Color a sticker has on a solved cube (its face's center)
+    fn home_color(&self, cube: &Cube, letter: char) -> Color {
+        let (face, _, _) = self.stickers[index(letter)];
+        cube.get_face(face).get(1, 1)
+    }
+
+    fn color_at(&self, cube: &Cube, letter: char) -> Color {
+        let (face, row, col) = self.stickers[index(letter)];
+        cube.get_face(face).get(row, col)
+    }
+
+    /// Home of every sticker: where the sticker now at each letter belongs
+    fn homes(&self, cube: &Cube) -> Result<[char; 24], String> {
+        let mut homes = ['A'; 24];
+        for piece in &self.pieces {
+            let colors: Vec<Color> = piece.iter().map(|l| self.color_at(cube, *l)).collect();
+            let mut sorted = colors.clone();
+            sorted.sort_by_key(|c| *c as u8);
+            let home = self
+                .pieces
+                .iter()
+                .find(|candidate| {
+                    let mut home_colors: Vec<Color> =
+                        candidate.iter().map(|l| self.home_color(cube, *l)).collect();
+                    home_colors.sort_by_key(|c| *c as u8);
+                    home_colors == sorted
+                })
+                .ok_or_else(|| "The cube has a piece that does not exist".to_string())?;
+            for (at, color) in piece.iter().zip(&colors) {
+                let target = home
+                    .iter()
+                    .find(|l| self.home_color(cube, **l) == *color)
+                    .copied()
+                    .ok_or_else(|| "The cube has a piece that does not exist".to_string())?;
+                homes[index(*at)] = target;
+            }
+        }
+        Ok(homes)
+    }
+
+    /// Trace the targets the buffer visits, breaking into new cycles when
+    /// the buffer comes home early
+    fn trace(&self, cube: &Cube) -> Result<Vec<char>, String> {
+        let homes = self.homes(cube)?;
+        let solved = |piece: &[char]| piece.iter().all(|l| homes[index(*l)] == *l);
+        let buffer_piece = self.piece_of(self.buffer);
+        let mut visited = vec![false; self.pieces.len()];
+        visited[buffer_piece] = true;
+        for (i, piece) in self.pieces.iter().enumerate() {
+            if solved(piece) {
+                visited[i] = true;
+            }
+        }
+
+        let mut targets = Vec::new();
+        let mut cycle_piece = buffer_piece;
+        let mut current = self.buffer;
+        loop {
+            let home = homes[index(current)];
+            if self.piece_of(home) != cycle_piece {
+                targets.push(home);
+                visited[self.piece_of(home)] = true;
+                current = home;
+                continue;
+            }
+            // The cycle is closed
+            if cycle_piece != buffer_piece {
+                targets.push(home);
+            }
+            let Some(next) = visited.iter().position(|v| !v) else {
+                break;
+            };
+            visited[next] = true;
+            cycle_piece = next;
+            current = self.pieces[next][0];
+            targets.push(current);
+        }
+        Ok(targets)
+    }
+}
+
+/// Where each sticker position goes under a move, as indexes into the
+/// 54 stickers (face by face, row by row)
+fn destinations(mv: Move) -> Vec<usize> {
+    let flat = |face: FaceName, row: usize, col: usize| {
+        FaceName::all().iter().position(|f| *f == face).unwrap_or(0) * 9 + row * 3 + col
+    };
+    let mut result = vec![0; 54];
+    for face in FaceName::all() {
+        for row in 0..3 {
+            for col in 0..3 {
+                let mut marker = Cube::new(3);
+                for f in FaceName::all() {
+                    for r in 0..3 {
+                        for c in 0..3 {
+                            marker.set_sticker(f, r, c, Color::White);
+                        }
+                    }
+                }
+                marker.set_sticker(face, row, col, Color::Red);
+                marker.apply_move(mv);
+                for f in FaceName::all() {
+                    for r in 0..3 {
+                        for c in 0..3 {
+                            if marker.get_face(f).get(r, c) == Color::Red {
+                                result[flat(face, row, col)] = flat(f, r, c);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Flat sticker index of a letter position
+fn flat_index(stickers: &[(FaceName, usize, usize); 24], letter: char) -> usize {
+    let (face, row, col) = stickers[index(letter)];
+    FaceName::all().iter().position(|f| *f == face).unwrap_or(0) * 9 + row * 3 + col
+}
+
+/// Shortest setup that brings `from` to `to` without moving `fixed`
+fn find_setup(moves: &[Move], from: usize, to: usize, fixed: &[usize]) -> Option<Vec<Move>> {
+    let tables: Vec<Vec<usize>> = moves.iter().map(|m| destinations(*m)).collect();
+    let mut queue = VecDeque::from([(from, fixed.to_vec(), Vec::<usize>::new())]);
+    while let Some((at, tracked, path)) = queue.pop_front() {
+        if at == to && tracked == fixed {
+            return Some(path.into_iter().map(|i| moves[i]).collect());
+        }
+        if path.len() == MAX_SETUP {
+            continue;
+        }
+        for (i, table) in tables.iter().enumerate() {
+            // Never turn the same face twice in a row
+            if path.last().is_some_and(|last| last / 3 == i / 3) {
+                continue;
+            }
+            let mut next = path.clone();
+            next.push(i);
+            queue.push_back((table[at], tracked.iter().map(|s| table[*s]).collect(), next));
+        }
+    }
+    None
+}
+
+/// Setup, swap and undo for one target
+fn conjugate(setup: &[Move], swap: &[Move]) -> Vec<Move> {
+    let mut moves = setup.to_vec();
+    moves.extend_from_slice(swap);
+    moves.extend(setup.iter().rev().map(|m| m.inverse()));
+    moves
+}
+
+/// Moves that shoot the buffer to one corner target
+fn corner_target_moves(target: char) -> Result<Vec<Move>, String> {
+    let setup = find_setup(
+        &face_turns(&CORNER_SETUP_FACES),
+        flat_index(&CORNER_STICKERS, target),
+        flat_index(&CORNER_STICKERS, CORNER_HELPER),
+        &[],
+    )
+    .ok_or_else(|| format!("No setup for corner target {}", target))?;
+    Ok(conjugate(&setup, &standard_algorithm(CORNER_SWAP)))
+}
+
+/// Moves that shoot the buffer to one edge target, `odd` after an odd
+/// number of edge targets
+fn edge_target_moves(target: char, odd: bool) -> Result<Vec<Move>, String> {
+    if let Some((_, _, algorithm)) = M_SLICE_TARGETS
+        .iter()
+        .find(|(even, odd_letter, _)| if odd { *odd_letter == target } else { *even == target })
+    {
+        return Ok(standard_algorithm(algorithm));
+    }
+    // Setups must leave the buffer and the other M-slice edges alone
+    let fixed: Vec<usize> = ['U', 'K', 'C', 'I', 'W', 'S']
+        .iter()
+        .map(|l| flat_index(&EDGE_STICKERS, *l))
+        .collect();
+    let setup = find_setup(
+        &face_turns(&EDGE_SETUP_FACES),
+        flat_index(&EDGE_STICKERS, target),
+        flat_index(&EDGE_STICKERS, EDGE_HELPER),
+        &fixed,
+    )
+    .ok_or_else(|| format!("No setup for edge target {}", target))?;
+    Ok(conjugate(&setup, &[Move::M2]))
+}
+
+/// Trace the Speffz memo of a 3x3 scramble
+///
+/// The cube must be held with the solved colors' usual centers in place:
+/// letters are read against the centers as they are.
+pub fn memorize(cube: &Cube) -> Result<BlindMemo, String> {
+    if cube.size() != 3 {
+        return Err("Cube must be size 3 for blindfold solving".to_string());
+    }
+    Ok(BlindMemo {
+        edges: PieceSet::edges().trace(cube)?,
+        corners: PieceSet::corners().trace(cube)?,
+    })
+}
+
+/// Memo and execution for a blindfold solve
+///
+/// One step per target: edges with M2, the parity fix if needed, then
+/// corners with Old Pochmann.
+pub fn solve_blind(cube: &Cube) -> Result<(BlindMemo, Solution), String> {
+    let start = Instant::now();
+    let memo = memorize(cube)?;
+    if memo.edges.len() % 2 != memo.corners.len() % 2 {
+        return Err("The cube cannot be solved (edge and corner parity differ)".to_string());
+    }
+
+    let mut steps = Vec::new();
+    for (i, target) in memo.edges.iter().enumerate() {
+        steps.push(SolutionStep::new(
+            format!("Edge {} (M2)", target),
+            edge_target_moves(*target, i % 2 == 1)?,
+        ));
+    }
+    if memo.has_parity() {
+        steps.push(SolutionStep::with_explanation(
+            "Parity",
+            standard_algorithm(PARITY_FIX),
+            "An odd number of targets leaves two edges and two corners swapped",
+        ));
+    }
+    for target in &memo.corners {
+        steps.push(SolutionStep::new(
+            format!("Corner {} (Old Pochmann)", target),
+            corner_target_moves(*target)?,
+        ));
+    }
+    if steps.is_empty() {
+        steps.push(SolutionStep::new("Cube is already solved", vec![]));
+    }
+
+    let solution = Solution::with_method(steps, start.elapsed().as_millis(), "Old Pochmann corners + M2 edges");
+    Ok((memo, solution))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::explain::pieces;
+
+    fn scrambled(notation: &str) -> Cube {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&standard_algorithm(notation));
+        cube
+    }
+
+    fn executes(cube: &Cube) -> BlindMemo {
+        let (memo, solution) = solve_blind(cube).unwrap();
+        let mut result = cube.clone();
+        for step in &solution.steps {
+            result.apply_moves(&step.moves);
+        }
+        assert!(result.is_solved(), "memo {:?}", memo);
+        memo
+    }
+
+    #[test]
+    fn test_letters_form_pieces() {
+        let pieces = pieces();
+        let is_piece = |stickers: Vec<(FaceName, usize, usize)>| {
+            pieces.iter().any(|p| {
+                p.stickers.len() == stickers.len() && stickers.iter().all(|s| p.stickers.contains(s))
+            })
+        };
+        for corner in CORNER_PIECES {
+            assert!(is_piece(corner.iter().map(|l| CORNER_STICKERS[index(*l)]).collect()), "{:?}", corner);
+        }
+        for edge in EDGE_PIECES {
+            assert!(is_piece(edge.iter().map(|l| EDGE_STICKERS[index(*l)]).collect()), "{:?}", edge);
+        }
+    }
+
+    #[test]
+    fn test_speffz_letter() {
+        assert_eq!(speffz_letter(FaceName::U, 0, 0), Some(('A', true)));
+        assert_eq!(speffz_letter(FaceName::F, 1, 2), Some(('J', false)));
+        assert_eq!(speffz_letter(FaceName::D, 2, 0), Some(('X', true)));
+        assert_eq!(speffz_letter(FaceName::R, 1, 1), None);
+    }
+
+    #[test]
+    fn test_solved_cube_has_empty_memo() {
+        let memo = executes(&Cube::new(3));
+        assert!(memo.edges.is_empty() && memo.corners.is_empty());
+        assert!(!memo.has_parity());
+    }
+
+    #[test]
+    fn test_single_swap_memo() {
+        // The Old Pochmann swap exchanges A with P and the UB and UL edges
+        let cube = scrambled(CORNER_SWAP);
+        let memo = memorize(&cube).unwrap();
+        assert_eq!(memo.corners, vec!['P']);
+        assert_eq!(memo.edges, vec!['A', 'D', 'A']);
+        assert!(memo.has_parity());
+        executes(&cube);
+    }
+
+    #[test]
+    fn test_executes_scrambles() {
+        for scramble in [
+            "R U F",
+            "R U R' U'",
+            "F2 D' L B2 U R'",
+            "D2 F' R2 U L' B D R2 F U2",
+            "U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2",
+            "L' D2 B' U F2 R D' L2 U2 B R' F D2 L' U B2 R F' D",
+        ] {
+            executes(&scrambled(scramble));
+        }
+    }
+
+    #[test]
+    fn test_memo_pairs() {
+        let memo = BlindMemo { edges: vec!['C', 'M', 'K'], corners: vec![] };
+        assert_eq!(memo.edge_pairs(), vec!["CM".to_string(), "K".to_string()]);
+        assert!(memo.has_parity());
+    }
+
+    #[test]
+    fn test_rejects_other_sizes() {
+        assert!(memorize(&Cube::new(4)).is_err());
+    }
+}
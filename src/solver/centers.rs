@@ -0,0 +1,397 @@
+//! Center solving for big cubes: block building, then commutators
+//!
+//! Centers are built greedily from short move sequences, always taking the
+//! one that puts the most center stickers in place per move:
+//! - Bar moves (slices, a face turn, slices back) carry whole rows and
+//!   blocks of center pieces between faces while most centers are unsolved
+//! - Commutators ([slice, face slice face']) cycle a few pieces without
+//!   breaking what is already built, to finish the last pieces
+//!
+//! The 3-cycles among the commutators are set up with face turns and slices
+//! (undone afterwards) until every 3-cycle of center pieces is known, so the
+//! last pieces can always be placed, even swapped pairs on opposite faces.
+//! Every sequence is traced once as a sticker permutation, so candidates are
+//! scored without touching a cube.
+
+use crate::cube::facelets::FACELET_ORDER;
+use crate::cube::supercube::{position, trace_sources};
+use crate::cube::{Color, Cube, Direction, Move, ParsedMove, WideFace};
+use crate::solver::parity::wide_turn;
+use std::collections::HashMap;
+
+/// Faces slices are taken from; the opposite faces give the same slices
+pub(super) const SLICE_FACES: [WideFace; 6] = [WideFace::R, WideFace::L, WideFace::U, WideFace::D, WideFace::F, WideFace::B];
+
+/// Quarter-turn directions
+pub(super) const QUARTERS: [Direction; 2] = [Direction::Clockwise, Direction::CounterClockwise];
+
+/// All turn directions
+pub(super) const DIRECTIONS: [Direction; 3] = [Direction::Clockwise, Direction::CounterClockwise, Direction::Double];
+
+/// Opposite direction of a turn
+fn reverse(direction: Direction) -> Direction {
+    match direction {
+        Direction::Clockwise => Direction::CounterClockwise,
+        Direction::CounterClockwise => Direction::Clockwise,
+        Direction::Double => Direction::Double,
+    }
+}
+
+/// Turn of the outer `depth` layers of a face
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct Turn {
+    pub(super) face: WideFace,
+    pub(super) depth: usize,
+    pub(super) direction: Direction,
+}
+
+impl Turn {
+    pub(super) fn new(face: WideFace, depth: usize, direction: Direction) -> Self {
+        Self { face, depth, direction }
+    }
+
+    pub(super) fn inverse(self) -> Self {
+        Self::new(self.face, self.depth, reverse(self.direction))
+    }
+
+    pub(super) fn to_move(self) -> ParsedMove {
+        wide_turn(self.face, self.direction, self.depth)
+    }
+}
+
+/// Inner layers `from..=to` of a face (1 = first layer inside), as a wide
+/// turn and the shallower wide turn undone
+pub(super) fn slice(face: WideFace, from: usize, to: usize, direction: Direction) -> [Turn; 2] {
+    [Turn::new(face, to + 1, direction), Turn::new(face, from, reverse(direction))]
+}
+
+/// Undo a sequence of turns
+pub(super) fn invert(turns: &[Turn]) -> Vec<Turn> {
+    turns.iter().rev().map(|t| t.inverse()).collect()
+}
+
+/// A move sequence and what it does to the centers
+#[derive(Debug, Clone)]
+struct Candidate {
+    turns: Vec<Turn>,
+    /// (destination, source) for every center sticker that moves
+    changes: Vec<(usize, usize)>,
+    /// Commutators finish the centers; everything else builds blocks
+    commutator: bool,
+}
+
+/// Sticker permutations of the outer turns and of wide turns up to one
+/// layer past `deepest`, as source per destination
+pub(super) struct TurnTables {
+    size: usize,
+    tables: HashMap<Turn, Vec<usize>>,
+}
+
+impl TurnTables {
+    pub(super) fn trace(size: usize, deepest: usize) -> Self {
+        let mut tables = HashMap::new();
+        for face in SLICE_FACES {
+            for depth in 1..=deepest + 1 {
+                for direction in DIRECTIONS {
+                    let turn = Turn::new(face, depth, direction);
+                    let mv = turn.to_move();
+                    tables.insert(turn, trace_sources(size, |cube| cube.apply_parsed_move(&mv)));
+                }
+            }
+        }
+        Self { size, tables }
+    }
+
+    /// Sticker permutation of a sequence of turns
+    pub(super) fn permutation(&self, turns: &[Turn]) -> Vec<usize> {
+        let mut result: Vec<usize> = (0..6 * self.size * self.size).collect();
+        for turn in turns {
+            let table = &self.tables[turn];
+            result = table.iter().map(|source| result[*source]).collect();
+        }
+        result
+    }
+}
+
+/// Traced turns and the candidate sequences for one cube size
+struct CenterMoves {
+    size: usize,
+    tables: TurnTables,
+    /// Whether each sticker index is a center sticker
+    centers: Vec<bool>,
+    candidates: Vec<Candidate>,
+    /// Outer face turns used as setups
+    setups: Vec<Turn>,
+}
+
+impl CenterMoves {
+    fn build(size: usize) -> Self {
+        // Slices are taken from the nearer face, so wide turns stay within
+        // half the cube
+        let deepest = (size - 1) / 2;
+        let tables = TurnTables::trace(size, deepest);
+        let centers = (0..6 * size * size)
+            .map(|index| {
+                let (_, row, col) = position(size, index);
+                row > 0 && row < size - 1 && col > 0 && col < size - 1
+            })
+            .collect();
+        let setups: Vec<Turn> = SLICE_FACES
+            .iter()
+            .flat_map(|face| DIRECTIONS.map(|direction| Turn::new(*face, 1, direction)))
+            .collect();
+
+        let mut moves = Self { size, tables, centers, candidates: Vec::new(), setups };
+        moves.candidates = moves.generate(deepest);
+        moves
+    }
+
+    fn candidate(&self, turns: Vec<Turn>, commutator: bool) -> Candidate {
+        let permutation = self.tables.permutation(&turns);
+        let changes = permutation
+            .iter()
+            .enumerate()
+            .filter(|(dest, source)| self.centers[*dest] && *dest != **source)
+            .map(|(dest, source)| (dest, *source))
+            .collect();
+        Candidate { turns, changes, commutator }
+    }
+
+    /// Bar moves and commutators, keeping the shortest sequence for each
+    /// effect on the centers
+    fn generate(&self, deepest: usize) -> Vec<Candidate> {
+        let blocks: Vec<[Turn; 2]> = SLICE_FACES
+            .iter()
+            .flat_map(|face| {
+                (1..=deepest).flat_map(move |from| {
+                    (from..=deepest).flat_map(move |to| QUARTERS.map(|d| slice(*face, from, to, d)))
+                })
+            })
+            .collect();
+        let singles: Vec<&[Turn; 2]> = blocks.iter().filter(|b| b[1].depth + 1 == b[0].depth).collect();
+
+        let mut found: HashMap<Vec<(usize, usize)>, Candidate> = HashMap::new();
+        let keep = |found: &mut HashMap<Vec<(usize, usize)>, Candidate>, candidate: Candidate| {
+            if candidate.changes.is_empty() {
+                return;
+            }
+            let better = found
+                .get(&candidate.changes)
+                .is_none_or(|known| known.turns.len() > candidate.turns.len());
+            if better {
+                found.insert(candidate.changes.clone(), candidate);
+            }
+        };
+
+        // Bar moves: slices, one face turn, slices back
+        for block in &blocks {
+            for setup in &self.setups {
+                let mut turns = block.to_vec();
+                turns.push(*setup);
+                turns.extend(invert(block));
+                keep(&mut found, self.candidate(turns, false));
+            }
+        }
+        // Commutators: [slice, face slice face']
+        for x in &singles {
+            for y in &singles {
+                for setup in &self.setups {
+                    let mut inner = vec![*setup];
+                    inner.extend_from_slice(&y[..]);
+                    inner.push(setup.inverse());
+                    let mut turns = x.to_vec();
+                    turns.extend(&inner);
+                    turns.extend(invert(&x[..]));
+                    turns.extend(invert(&inner));
+                    keep(&mut found, self.candidate(turns, true));
+                }
+            }
+        }
+        // Set up 3-cycles with face turns and slices until every 3-cycle of
+        // center pieces is known, so the last few pieces can always be placed
+        let conjugators: Vec<(Vec<Turn>, Vec<usize>)> = self
+            .setups
+            .iter()
+            .map(|setup| vec![*setup])
+            .chain(singles.iter().map(|single| single.to_vec()))
+            .map(|setup| {
+                let table = self.tables.permutation(&setup);
+                (setup, table)
+            })
+            .collect();
+        let mut frontier: Vec<Candidate> =
+            found.values().filter(|c| c.commutator && c.changes.len() == 3).cloned().collect();
+        frontier.sort_by(|a, b| a.changes.cmp(&b.changes));
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for cycle in &frontier {
+                for (setup, table) in &conjugators {
+                    let candidate = conjugate(setup, table, cycle);
+                    if !found.contains_key(&candidate.changes) {
+                        next.push(candidate.clone());
+                        keep(&mut found, candidate);
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        let mut candidates: Vec<Candidate> = found.into_values().collect();
+        candidates.sort_by_key(|c| (c.turns.len(), c.changes.clone()));
+        candidates
+    }
+}
+
+/// A candidate done after `setup` (with permutation `table`) and undone
+/// afterwards
+fn conjugate(setup: &[Turn], table: &[usize], candidate: &Candidate) -> Candidate {
+    let mut turns = setup.to_vec();
+    turns.extend(&candidate.turns);
+    turns.extend(invert(setup));
+    let mut changes: Vec<(usize, usize)> = candidate
+        .changes
+        .iter()
+        .map(|(dest, source)| (table[*dest], table[*source]))
+        .collect();
+    changes.sort();
+    Candidate { turns, changes, commutator: candidate.commutator }
+}
+
+/// Colors of the centers on a solved cube in each of its 24 orientations,
+/// faces in facelet order
+fn orientations() -> Vec<[Color; 6]> {
+    let centers = |cube: &Cube| FACELET_ORDER.map(|face| cube.get_face(face).get(1, 1));
+    let mut found = vec![centers(&Cube::new(3))];
+    let mut queue = vec![Cube::new(3)];
+    while let Some(cube) = queue.pop() {
+        for rotation in [Move::X, Move::Y, Move::Z] {
+            let mut next = cube.clone();
+            next.apply_move(rotation);
+            if !found.contains(&centers(&next)) {
+                found.push(centers(&next));
+                queue.push(next);
+            }
+        }
+    }
+    found
+}
+
+/// Greedy center solver state
+struct CenterState<'a> {
+    moves: &'a CenterMoves,
+    colors: Vec<Color>,
+    /// Target color of each face, in facelet order
+    targets: [Color; 6],
+}
+
+impl CenterState<'_> {
+    fn target(&self, index: usize) -> Color {
+        self.targets[index / (self.moves.size * self.moves.size)]
+    }
+
+    fn solved_count(&self) -> usize {
+        (0..self.colors.len())
+            .filter(|i| self.moves.centers[*i] && self.colors[*i] == self.target(*i))
+            .count()
+    }
+
+    /// Center stickers put in place (or taken out of place, if negative)
+    fn gain(&self, candidate: &Candidate) -> i64 {
+        candidate
+            .changes
+            .iter()
+            .map(|(dest, source)| {
+                let target = self.target(*dest);
+                (self.colors[*source] == target) as i64 - (self.colors[*dest] == target) as i64
+            })
+            .sum()
+    }
+
+    fn apply(&mut self, candidate: &Candidate) {
+        let moved: Vec<Color> = candidate.changes.iter().map(|(_, source)| self.colors[*source]).collect();
+        for ((dest, _), color) in candidate.changes.iter().zip(moved) {
+            self.colors[*dest] = color;
+        }
+    }
+
+    /// Candidate with the most stickers gained per turn
+    fn best<'c>(&self, candidates: impl Iterator<Item = &'c Candidate>) -> Option<&'c Candidate> {
+        let mut best: Option<(&Candidate, i64)> = None;
+        for candidate in candidates {
+            let gain = self.gain(candidate);
+            if gain <= 0 {
+                continue;
+            }
+            let better = best.is_none_or(|(known, known_gain)| {
+                gain * known.turns.len() as i64 > known_gain * candidate.turns.len() as i64
+            });
+            if better {
+                best = Some((candidate, gain));
+            }
+        }
+        best.map(|(candidate, _)| candidate)
+    }
+}
+
+/// Solved centers: one group of moves per phase, as (commutator phase, moves)
+pub(crate) type CenterPhases = Vec<(bool, Vec<ParsedMove>)>;
+
+/// Solve the centers of a 4x4+ cube
+///
+/// Odd cubes keep their middle centers; even cubes get whichever color
+/// scheme orientation already has the most centers in place.
+pub(crate) fn build_centers(cube: &Cube) -> Result<CenterPhases, String> {
+    let size = cube.size();
+    let moves = CenterMoves::build(size);
+    let colors: Vec<Color> = (0..6 * size * size)
+        .map(|index| {
+            let (face, row, col) = position(size, index);
+            cube.get_face(face).get(row, col)
+        })
+        .collect();
+
+    let mut state = CenterState { moves: &moves, colors, targets: [Color::White; 6] };
+    let targets = if size % 2 == 1 {
+        FACELET_ORDER.map(|face| cube.get_face(face).get(size / 2, size / 2))
+    } else {
+        orientations()
+            .into_iter()
+            .max_by_key(|targets| {
+                state.targets = *targets;
+                state.solved_count()
+            })
+            .unwrap_or(state.targets)
+    };
+    state.targets = targets;
+
+    let total = moves.centers.iter().filter(|c| **c).count();
+    let mut phases: CenterPhases = Vec::new();
+    while state.solved_count() < total {
+        let chosen = state
+            .best(moves.candidates.iter())
+            .ok_or_else(|| "Could not find a move sequence that improves the centers".to_string())?;
+        state.apply(chosen);
+
+        let turns: Vec<ParsedMove> = chosen.turns.iter().map(|t| t.to_move()).collect();
+        match phases.last_mut() {
+            Some((commutator, phase)) if *commutator == chosen.commutator => phase.extend(turns),
+            _ => phases.push((chosen.commutator, turns)),
+        }
+    }
+    Ok(phases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orientations() {
+        let all = orientations();
+        assert_eq!(all.len(), 24);
+        for targets in &all {
+            // Opposite faces always get opposite colors
+            assert_eq!(targets[0].opposite(), targets[3]);
+        }
+    }
+}
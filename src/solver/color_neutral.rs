@@ -0,0 +1,191 @@
+//! Cross color neutrality analysis
+//!
+//! Color-neutral solvers pick whichever cross is shortest after inspection
+//! instead of always starting on the same color. To see whether that would
+//! pay off, [`analyze_cross_colors`] finds the optimal cross on all six
+//! colors of a scramble, and a [`NeutralityReport`] over many scrambles
+//! compares sticking to one color, to a pair of opposite colors (dual
+//! color) and to all six.
+
+use crate::cube::{Color, Cube, Move};
+use crate::solver::cross::solve_cross;
+
+/// Colors in the order they are reported and ties are broken
+pub const CROSS_COLORS: [Color; 6] = [Color::White, Color::Yellow, Color::Red, Color::Orange, Color::Blue, Color::Green];
+
+/// The optimal cross on one color
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossOption {
+    pub color: Color,
+    pub moves: Vec<Move>,
+}
+
+impl CrossOption {
+    /// Number of face turns
+    pub fn length(&self) -> usize {
+        self.moves.len()
+    }
+}
+
+/// Optimal crosses of one scrambled cube on every color
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossAnalysis {
+    /// One cross per color, in [`CROSS_COLORS`] order
+    pub options: Vec<CrossOption>,
+}
+
+impl CrossAnalysis {
+    /// The optimal cross on `color`
+    pub fn option(&self, color: Color) -> &CrossOption {
+        self.options.iter().find(|o| o.color == color).expect("every color is analyzed")
+    }
+
+    /// Optimal cross length on `color`
+    pub fn length(&self, color: Color) -> usize {
+        self.option(color).length()
+    }
+
+    /// The shortest cross; ties go to the color listed first
+    pub fn best(&self) -> &CrossOption {
+        self.options.iter().min_by_key(|o| o.length()).expect("every color is analyzed")
+    }
+
+    /// The shorter cross of `color` and its opposite
+    pub fn best_dual(&self, color: Color) -> &CrossOption {
+        let (own, opposite) = (self.option(color), self.option(color.opposite()));
+        if opposite.length() < own.length() {
+            opposite
+        } else {
+            own
+        }
+    }
+
+    /// Moves saved by going fully color neutral instead of always
+    /// starting on `color`
+    pub fn moves_saved(&self, color: Color) -> usize {
+        self.length(color) - self.best().length()
+    }
+}
+
+/// Find the optimal cross on every color of a 3x3
+pub fn analyze_cross_colors(cube: &Cube) -> Result<CrossAnalysis, String> {
+    let options = CROSS_COLORS
+        .into_iter()
+        .map(|color| solve_cross(cube, color).map(|solution| CrossOption { color, moves: solution.all_moves() }))
+        .collect::<Result<_, _>>()?;
+    Ok(CrossAnalysis { options })
+}
+
+/// Find the optimal cross on every color after `scramble`
+pub fn analyze_scramble(scramble: &str) -> Result<CrossAnalysis, String> {
+    let mut cube = Cube::new(3);
+    cube.apply_algorithm(scramble).map_err(|e| e.to_string())?;
+    analyze_cross_colors(&cube)
+}
+
+/// Average cross lengths over many scrambles for one usual color
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NeutralityReport {
+    /// The color the solver usually starts on
+    pub color: Color,
+    pub scrambles: usize,
+    /// Average optimal cross on `color`
+    pub fixed: f64,
+    /// Average of the shorter cross of `color` and its opposite
+    pub dual: f64,
+    /// Average of the shortest cross on any color
+    pub neutral: f64,
+    /// Scrambles where another color's cross was strictly shorter
+    pub other_color_better: usize,
+}
+
+impl NeutralityReport {
+    /// Sum up analyses of several scrambles; None if there are none
+    pub fn new(color: Color, analyses: &[CrossAnalysis]) -> Option<Self> {
+        if analyses.is_empty() {
+            return None;
+        }
+        let average = |length: &dyn Fn(&CrossAnalysis) -> usize| {
+            analyses.iter().map(length).sum::<usize>() as f64 / analyses.len() as f64
+        };
+        Some(Self {
+            color,
+            scrambles: analyses.len(),
+            fixed: average(&|a| a.length(color)),
+            dual: average(&|a| a.best_dual(color).length()),
+            neutral: average(&|a| a.best().length()),
+            other_color_better: analyses.iter().filter(|a| a.moves_saved(color) > 0).count(),
+        })
+    }
+
+    /// Advice on whether going color neutral is worth learning
+    ///
+    /// Saving less than half a move per cross is not worth the extra
+    /// lookahead practice; dual color is suggested when it gets most of
+    /// the benefit.
+    pub fn advice(&self) -> &'static str {
+        let neutral_gain = self.fixed - self.neutral;
+        let dual_gain = self.fixed - self.dual;
+        if neutral_gain < 0.5 {
+            "Sticking to one color costs you little - keep practicing your usual cross"
+        } else if dual_gain >= neutral_gain * 0.75 {
+            "Try dual color: your usual color and its opposite get most of the savings"
+        } else {
+            "Going fully color neutral would make your crosses noticeably shorter"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_best_color_is_found() {
+        let analysis = analyze_scramble("F R").unwrap();
+        assert_eq!(analysis.options.len(), 6);
+        assert_eq!(analysis.options[0].color, Color::White);
+        for option in &analysis.options {
+            let mut cube = Cube::new(3);
+            cube.apply_algorithm("F R").unwrap();
+            cube.apply_moves(&option.moves);
+            assert!(crate::solver::is_cross_solved(&cube, option.color));
+        }
+        // Undoing the scramble solves every cross in two moves
+        assert!(analysis.options.iter().all(|o| o.length() <= 2));
+        let best = analysis.best();
+        assert!(analysis.options.iter().all(|o| o.length() >= best.length()));
+        assert_eq!(analysis.moves_saved(best.color), 0);
+        assert_eq!(analysis.best_dual(best.color), best);
+    }
+
+    #[test]
+    fn test_report_averages() {
+        let option = |color, length| CrossOption { color, moves: vec![Move::U; length] };
+        let analysis = |lengths: [usize; 6]| CrossAnalysis {
+            options: CROSS_COLORS.iter().zip(lengths).map(|(c, l)| option(*c, l)).collect(),
+        };
+        let analyses = [analysis([6, 4, 5, 7, 3, 6]), analysis([5, 6, 5, 5, 5, 5])];
+        let report = NeutralityReport::new(Color::White, &analyses).unwrap();
+        assert_eq!(report.scrambles, 2);
+        assert_eq!(report.fixed, 5.5);
+        assert_eq!(report.dual, 4.5);
+        assert_eq!(report.neutral, 4.0);
+        assert_eq!(report.other_color_better, 1);
+        assert_eq!(analyses[0].best().color, Color::Blue);
+        assert!(report.advice().starts_with("Going fully color neutral"));
+
+        // Dual color gets all of the savings here
+        let report = NeutralityReport::new(Color::White, &[analysis([6, 4, 5, 5, 5, 5])]).unwrap();
+        assert!(report.advice().starts_with("Try dual color"));
+        let report = NeutralityReport::new(Color::White, &[analysis([4, 4, 4, 4, 4, 4])]).unwrap();
+        assert!(report.advice().starts_with("Sticking to one color"));
+
+        assert_eq!(NeutralityReport::new(Color::White, &[]), None);
+    }
+
+    #[test]
+    fn test_rejects_bad_scrambles() {
+        assert!(analyze_scramble("R Q").is_err());
+    }
+}
@@ -0,0 +1,482 @@
+//! Optimal cross solver
+//!
+//! Finds the shortest face-turn sequence that solves the cross of one color
+//! on a 3x3. Only the 24 edge stickers are tracked, so the search is small:
+//! IDA* turns the edge stickers with move tables taken from the simulator
+//! and prunes with two lower bounds: moves to bring the four cross stickers
+//! onto the cross face, and moves to bring each cross sticker and its side
+//! sticker to their goal spots.
+//!
+//! Every cross can be solved in at most 8 moves, so the search is capped
+//! there. The result can start a CFOP solve or be compared against a
+//! student's cross in a trainer.
+
+use crate::cube::{Color, Cube, FaceName, Move};
+use crate::solver::explain::{color_name, explain_moves};
+use crate::solver::options::SolveOptions;
+use crate::solver::parallel::{search_roots, Branch};
+use crate::solver::solution::{Solution, SolutionStep};
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Longest optimal cross
+pub const MAX_CROSS_MOVES: usize = 8;
+
+/// Face turns searched, three per face; opposite faces are adjacent pairs
+const MOVES: [Move; 18] = [
+    Move::U, Move::UPrime, Move::U2,
+    Move::D, Move::DPrime, Move::D2,
+    Move::F, Move::FPrime, Move::F2,
+    Move::B, Move::BPrime, Move::B2,
+    Move::L, Move::LPrime, Move::L2,
+    Move::R, Move::RPrime, Move::R2,
+];
+
+/// Edge sticker positions within a face
+const EDGE_SPOTS: [(usize, usize); 4] = [(0, 1), (1, 0), (1, 2), (2, 1)];
+
+/// Number of edge stickers on a cube
+const EDGE_STICKERS: usize = 24;
+
+/// Sticker position of the `index`-th edge sticker
+fn edge_sticker(index: usize) -> (FaceName, usize, usize) {
+    let (row, col) = EDGE_SPOTS[index % 4];
+    (FaceName::all()[index / 4], row, col)
+}
+
+/// How face turns move the edge stickers, taken from the simulator
+struct EdgeTables {
+    /// `moved[m][s]` is where edge sticker `s` ends up after `MOVES[m]`
+    moved: [[u8; EDGE_STICKERS]; 18],
+    /// The other sticker of the same edge
+    partner: [u8; EDGE_STICKERS],
+}
+
+impl EdgeTables {
+    fn build() -> Self {
+        let mut moved = [[0u8; EDGE_STICKERS]; 18];
+        for (table, mv) in moved.iter_mut().zip(MOVES) {
+            for (sticker, slot) in table.iter_mut().enumerate() {
+                let mut marker = blank_cube();
+                let (face, row, col) = edge_sticker(sticker);
+                marker.set_sticker(face, row, col, Color::Red);
+                marker.apply_move(mv);
+                *slot = (0..EDGE_STICKERS)
+                    .find(|target| {
+                        let (face, row, col) = edge_sticker(*target);
+                        marker.get_face(face).get(row, col) == Color::Red
+                    })
+                    .expect("edge stickers stay on edges") as u8;
+            }
+        }
+
+        // Two stickers are on the same edge when the same face turns move them
+        let turned_by = |sticker: usize| -> u8 {
+            (0..6).fold(0, |mask, face| {
+                if moved[face * 3][sticker] as usize != sticker {
+                    mask | 1 << face
+                } else {
+                    mask
+                }
+            })
+        };
+        let mut partner = [0u8; EDGE_STICKERS];
+        for (sticker, slot) in partner.iter_mut().enumerate() {
+            *slot = (0..EDGE_STICKERS)
+                .find(|other| *other != sticker && turned_by(*other) == turned_by(sticker))
+                .expect("every edge has two stickers") as u8;
+        }
+
+        Self { moved, partner }
+    }
+}
+
+/// A 3x3 with every sticker white, used to track one marked sticker
+fn blank_cube() -> Cube {
+    let mut cube = Cube::new(3);
+    for face in FaceName::all() {
+        for row in 0..3 {
+            for col in 0..3 {
+                cube.set_sticker(face, row, col, Color::White);
+            }
+        }
+    }
+    cube
+}
+
+/// Edge sticker labels: the cross color, one label per side color, or other
+type Labels = [u8; EDGE_STICKERS];
+
+/// Label of the cross color
+const CROSS: u8 = 0;
+/// Label of colors the cross does not care about
+const OTHER: u8 = 5;
+
+/// What a cross search looks for
+struct CrossGoal {
+    /// Edge stickers of the cross face, and the side sticker next to each
+    spots: [(usize, usize); 4],
+}
+
+impl CrossGoal {
+    /// Check whether every goal sticker shows its color
+    fn is_met(&self, labels: &Labels) -> bool {
+        self.spots
+            .iter()
+            .enumerate()
+            .all(|(i, (top, side))| labels[*top] == CROSS && labels[*side] == i as u8 + 1)
+    }
+}
+
+/// Label the edge stickers for the `color` cross
+///
+/// Only colors are tracked, not pieces, so the search relies on nothing but
+/// how `Cube::apply_move` moves stickers around.
+fn locate_cross(cube: &Cube, color: Color, tables: &EdgeTables) -> Result<(Labels, CrossGoal), String> {
+    if cube.size() != 3 {
+        return Err("Cube must be size 3 for the cross solver".to_string());
+    }
+    let center = |face: FaceName| cube.get_face(face).get(1, 1);
+    let face_index = FaceName::all()
+        .into_iter()
+        .position(|face| center(face) == color)
+        .ok_or_else(|| format!("No {} center on this cube", color_name(color)))?;
+
+    let mut spots = [(0, 0); 4];
+    let mut side_colors = [color; 4];
+    for (slot, top) in (face_index * 4..face_index * 4 + 4).enumerate() {
+        let side = tables.partner[top] as usize;
+        spots[slot] = (top, side);
+        side_colors[slot] = center(edge_sticker(side).0);
+    }
+
+    let mut labels = [OTHER; EDGE_STICKERS];
+    for (sticker, label) in labels.iter_mut().enumerate() {
+        let (face, row, col) = edge_sticker(sticker);
+        let sticker_color = cube.get_face(face).get(row, col);
+        if sticker_color == color {
+            *label = CROSS;
+        } else if let Some(slot) = side_colors.iter().position(|c| *c == sticker_color) {
+            *label = slot as u8 + 1;
+        }
+    }
+    Ok((labels, CrossGoal { spots }))
+}
+
+/// Rank of a sorted set of four edge stickers, from 0 to C(24, 4) - 1
+fn placement_rank(stickers: &[u8; 4]) -> usize {
+    let binomial = |n: usize, k: usize| -> usize {
+        if n < k {
+            0
+        } else {
+            (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+        }
+    };
+    stickers
+        .iter()
+        .enumerate()
+        .map(|(i, s)| binomial(*s as usize, i + 1))
+        .sum()
+}
+
+/// Number of ways to place four cross stickers on the 24 edge spots
+const PLACEMENTS: usize = 10_626;
+
+/// Lower bounds on the moves left, from two relaxed versions of the cross
+struct Pruning {
+    /// Exact moves to bring the four cross-colored stickers onto the cross
+    /// face, indexed by `placement_rank`; side colors are ignored
+    placement: Vec<u8>,
+    /// Moves to bring any two stickers to each goal pair, indexed by
+    /// `top_sticker * 24 + side_sticker`; one table per goal pair
+    pairs: Vec<Vec<u8>>,
+}
+
+impl Pruning {
+    fn build(goal: &CrossGoal, tables: &EdgeTables) -> Self {
+        // Every move's inverse is also searched, so distances from the goal
+        // equal distances to it
+        let mut placement = vec![u8::MAX; PLACEMENTS];
+        let mut start = goal.spots.map(|(top, _)| top as u8);
+        start.sort_unstable();
+        placement[placement_rank(&start)] = 0;
+        let mut queue = VecDeque::from([start]);
+        while let Some(stickers) = queue.pop_front() {
+            let distance = placement[placement_rank(&stickers)];
+            for moved in &tables.moved {
+                let mut next = stickers.map(|s| moved[s as usize]);
+                next.sort_unstable();
+                let rank = placement_rank(&next);
+                if placement[rank] == u8::MAX {
+                    placement[rank] = distance + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let pairs = goal
+            .spots
+            .iter()
+            .map(|(top, side)| {
+                let mut distance = vec![u8::MAX; EDGE_STICKERS * EDGE_STICKERS];
+                let start = top * EDGE_STICKERS + side;
+                distance[start] = 0;
+                let mut queue = VecDeque::from([start]);
+                while let Some(index) = queue.pop_front() {
+                    let (a, b) = (index / EDGE_STICKERS, index % EDGE_STICKERS);
+                    for moved in &tables.moved {
+                        let next = moved[a] as usize * EDGE_STICKERS + moved[b] as usize;
+                        if distance[next] == u8::MAX {
+                            distance[next] = distance[index] + 1;
+                            queue.push_back(next);
+                        }
+                    }
+                }
+                distance
+            })
+            .collect();
+
+        Self { placement, pairs }
+    }
+
+    /// Lower bound on the moves left to solve the cross
+    ///
+    /// The cross stickers must all reach the cross face, and for each goal
+    /// pair the closest cross sticker and matching side sticker must reach it.
+    fn estimate(&self, labels: &Labels) -> u8 {
+        let mut cross = [0u8; 4];
+        let mut count = 0;
+        for (sticker, label) in labels.iter().enumerate() {
+            if *label == CROSS && count < 4 {
+                cross[count] = sticker as u8;
+                count += 1;
+            }
+        }
+        let placement = if count == 4 { self.placement[placement_rank(&cross)] } else { 0 };
+
+        let pairs = self
+            .pairs
+            .iter()
+            .enumerate()
+            .map(|(slot, distance)| {
+                let side_label = slot as u8 + 1;
+                cross[..count]
+                    .iter()
+                    .flat_map(|top| {
+                        (0..EDGE_STICKERS)
+                            .filter(|s| labels[*s] == side_label)
+                            .map(move |side| distance[*top as usize * EDGE_STICKERS + side])
+                    })
+                    .min()
+                    .unwrap_or(u8::MAX)
+            })
+            .max()
+            .unwrap_or(0);
+
+        placement.max(pairs)
+    }
+}
+
+/// A cross search: the goal, the moves it may use and its tables
+struct Search<'a> {
+    goal: &'a CrossGoal,
+    allowed: &'a [usize],
+    tables: &'a EdgeTables,
+    pruning: &'a Pruning,
+}
+
+impl Search<'_> {
+    fn turn(&self, labels: &Labels, m: usize) -> Labels {
+        let mut next = [OTHER; EDGE_STICKERS];
+        for (sticker, label) in labels.iter().enumerate() {
+            next[self.tables.moved[m][sticker] as usize] = *label;
+        }
+        next
+    }
+
+    /// Shortest solution of at most `bound` moves, as move indices
+    ///
+    /// Each allowed first move is its own root branch.
+    fn solve_within(&self, labels: &Labels, bound: usize) -> Option<Vec<usize>> {
+        if self.goal.is_met(labels) {
+            return Some(vec![]);
+        }
+        if self.pruning.estimate(labels) as usize > bound {
+            return None;
+        }
+        search_roots(self.allowed.len(), |branch| {
+            let m = self.allowed[branch.index()];
+            let mut path = vec![m];
+            self.search(&self.turn(labels, m), bound, &mut path, branch).then_some(path)
+        })
+    }
+
+    /// Depth-first search below `bound` moves; pushes move indices onto `path`
+    fn search(&self, labels: &Labels, bound: usize, path: &mut Vec<usize>, branch: &Branch) -> bool {
+        if self.goal.is_met(labels) {
+            return true;
+        }
+        if branch.is_beaten() || path.len() + self.pruning.estimate(labels) as usize > bound {
+            return false;
+        }
+
+        let last_face = path.last().map(|m| m / 3);
+        for &m in self.allowed {
+            let face = m / 3;
+            // Never turn the same face twice, and turn opposite faces in one order only
+            if let Some(last) = last_face {
+                if face == last || (face ^ 1 == last && face < last) {
+                    continue;
+                }
+            }
+            path.push(m);
+            if self.search(&self.turn(labels, m), bound, path, branch) {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+}
+
+/// Check whether the cross of `color` is solved
+///
+/// Returns false for cubes other than 3x3 or without a `color` center.
+pub fn is_cross_solved(cube: &Cube, color: Color) -> bool {
+    let tables = EdgeTables::build();
+    matches!(locate_cross(cube, color, &tables), Ok((labels, goal)) if goal.is_met(&labels))
+}
+
+/// Find the shortest move sequence that solves the `color` cross
+///
+/// The cross is built on whichever face has the `color` center. The rest
+/// of the cube is ignored.
+pub fn solve_cross(cube: &Cube, color: Color) -> Result<Solution, String> {
+    solve_cross_with_options(cube, color, &SolveOptions::default())
+}
+
+/// Find the shortest cross solution using only the moves `options` allows
+///
+/// Allowed moves other than face turns are ignored.
+pub fn solve_cross_with_options(cube: &Cube, color: Color, options: &SolveOptions) -> Result<Solution, String> {
+    let start = Instant::now();
+    let allowed: Vec<usize> = (0..MOVES.len()).filter(|m| options.allows(MOVES[*m])).collect();
+    if allowed.is_empty() {
+        return Err("No face turns are allowed".to_string());
+    }
+    let tables = EdgeTables::build();
+    let (labels, goal) = locate_cross(cube, color, &tables)?;
+    let pruning = Pruning::build(&goal, &tables);
+
+    let search = Search { goal: &goal, allowed: &allowed, tables: &tables, pruning: &pruning };
+    let found = (pruning.estimate(&labels) as usize..=MAX_CROSS_MOVES).find_map(|bound| search.solve_within(&labels, bound));
+    let Some(path) = found else {
+        return Err(match options.describe() {
+            Some(moves) => format!("Could not solve the {} cross using only {}", color_name(color), moves),
+            None => format!("Could not solve the {} cross", color_name(color)),
+        });
+    };
+
+    let moves = options.finish(path.into_iter().map(|m| MOVES[m]).collect());
+    let step = if moves.is_empty() {
+        SolutionStep::new(format!("The {} cross is already solved", color_name(color)), vec![])
+    } else {
+        SolutionStep::new(format!("Solve the {} cross", color_name(color)), moves.clone())
+            .with_notes(explain_moves(cube, &moves))
+    };
+    Ok(Solution::with_method(vec![step], start.elapsed().as_millis(), "Optimal Cross"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cross_color(cube: &Cube) -> Color {
+        cube.get_face(FaceName::D).get(1, 1)
+    }
+
+    #[test]
+    fn test_tables_pair_every_edge() {
+        let tables = EdgeTables::build();
+        for sticker in 0..EDGE_STICKERS {
+            let partner = tables.partner[sticker] as usize;
+            assert_ne!(partner, sticker);
+            assert_eq!(tables.partner[partner] as usize, sticker);
+        }
+    }
+
+    #[test]
+    fn test_placement_rank_covers_range() {
+        assert_eq!(placement_rank(&[0, 1, 2, 3]), 0);
+        assert_eq!(placement_rank(&[20, 21, 22, 23]), PLACEMENTS - 1);
+    }
+
+    #[test]
+    fn test_solved_cross() {
+        let cube = Cube::new(3);
+        let color = cross_color(&cube);
+        assert!(is_cross_solved(&cube, color));
+        assert_eq!(solve_cross(&cube, color).unwrap().move_count(), 0);
+
+        // U turns never disturb the D cross
+        let mut turned = cube.clone();
+        turned.apply_move(Move::U);
+        assert!(is_cross_solved(&turned, color));
+    }
+
+    #[test]
+    fn test_short_scramble_is_undone_optimally() {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::F, Move::R]);
+        let color = cross_color(&cube);
+        assert!(!is_cross_solved(&cube, color));
+
+        let solution = solve_cross(&cube, color).unwrap();
+        assert_eq!(solution.move_count(), 2);
+        let mut solved = cube.clone();
+        solved.apply_moves(&solution.all_moves());
+        assert!(is_cross_solved(&solved, color));
+    }
+
+    #[test]
+    fn test_scrambled_cross_within_limit() {
+        let scramble = [
+            Move::R, Move::U, Move::F2, Move::L, Move::DPrime, Move::B,
+            Move::R2, Move::UPrime, Move::F, Move::L2, Move::D, Move::BPrime,
+        ];
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&scramble);
+
+        for face in [FaceName::D, FaceName::U, FaceName::F] {
+            let color = cube.get_face(face).get(1, 1);
+            let solution = solve_cross(&cube, color).unwrap();
+            assert!(solution.move_count() <= MAX_CROSS_MOVES);
+            let mut solved = cube.clone();
+            solved.apply_moves(&solution.all_moves());
+            assert!(is_cross_solved(&solved, color));
+        }
+    }
+
+    #[test]
+    fn test_restricted_moves() {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::F, Move::R]);
+        let color = cross_color(&cube);
+
+        let options = SolveOptions::three_gen();
+        let solution = solve_cross_with_options(&cube, color, &options).unwrap();
+        assert!(solution.all_moves().iter().all(|m| options.allows(*m)));
+        let mut solved = cube.clone();
+        solved.apply_moves(&solution.all_moves());
+        assert!(is_cross_solved(&solved, color));
+
+        let slices_only = SolveOptions::new().with_allowed_moves(vec![Move::M]);
+        assert!(solve_cross_with_options(&cube, color, &slices_only).is_err());
+    }
+
+    #[test]
+    fn test_rejects_other_sizes() {
+        assert!(solve_cross(&Cube::new(4), Color::White).is_err());
+        assert!(!is_cross_solved(&Cube::new(2), Color::White));
+    }
+}
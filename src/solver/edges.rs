@@ -0,0 +1,487 @@
+//! Edge pairing for big cubes with slice-batch commutators
+//!
+//! Every sequence used is a commutator of inner slices with a short outer
+//! sequence ([slices, a b a']) that only moves edge wings: centers, corners
+//! and middle edges stay put, so every edge slot keeps the colors it has to
+//! end up with. Turning several slices of a face at once cycles wings in all
+//! of those layers, so one commutator pairs pieces of several edge groups;
+//! the greedy search takes whichever sequence places the most wings per move.
+//!
+//! The single-slice 3-cycles are set up with face turns and slices until
+//! every wing 3-cycle is known, so the last wings can always be placed. A
+//! wing layer that is an odd permutation away from paired first gets the
+//! OLL parity algorithm, which swaps two of its wings.
+
+use crate::cube::supercube::{index_of, position};
+use crate::cube::{Color, Cube, Direction, FaceName, ParsedMove};
+use crate::solver::centers::{invert, slice, Turn, TurnTables, DIRECTIONS, QUARTERS, SLICE_FACES};
+use crate::solver::parity::{get_oll_parity_algorithm, is_odd, wing_layers, EDGE_SLOTS};
+use crate::solver::reduction::EdgePiece;
+use std::collections::HashMap;
+
+/// What a group of pairing moves does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EdgeStep {
+    /// Commutators turning several slices at once
+    Batch,
+    /// Single-slice 3-cycles
+    Cycle,
+    /// OLL parity algorithm on a wing layer
+    Parity(usize),
+}
+
+/// Paired edges: one group of moves per step
+pub(crate) type EdgePhases = Vec<(EdgeStep, Vec<ParsedMove>)>;
+
+/// Edge wing positions of one cube size
+struct Wings {
+    /// Sticker indices of each wing, in edge slot order (see `EDGE_SLOTS`)
+    stickers: Vec<[usize; 2]>,
+    /// Edge slot of each wing
+    slot: Vec<usize>,
+    /// Orbit of each wing: the layer of its slices (1 = next to the corners)
+    orbit: Vec<usize>,
+    /// Wing each sticker belongs to
+    at: Vec<Option<usize>>,
+}
+
+impl Wings {
+    fn build(size: usize, tables: &TurnTables) -> Self {
+        let at = |(face, row, col): (FaceName, usize, usize), layer: usize| {
+            let at = |i: usize| match i {
+                0 => 0,
+                1 => layer,
+                _ => size - 1,
+            };
+            index_of(size, face, at(row), at(col))
+        };
+        // The middle edges of odd cubes are not wings
+        let layers: Vec<usize> = (1..size - 1).filter(|layer| size.is_multiple_of(2) || *layer != size / 2).collect();
+        let mut slot_of = vec![None; 6 * size * size];
+        for (slot, pair) in EDGE_SLOTS.iter().enumerate() {
+            for layer in &layers {
+                for position in pair {
+                    slot_of[at(*position, *layer)] = Some(slot);
+                }
+            }
+        }
+        // A wing's two stickers stay in the same slot under every slice turn,
+        // which tells the piece's second sticker from the other wing's
+        let slice_tables: Vec<Vec<usize>> = SLICE_FACES
+            .iter()
+            .flat_map(|face| (1..=wing_layers(size)).map(move |layer| slice(*face, layer, layer, Direction::Clockwise)))
+            .map(|turns| inverse(&tables.permutation(&turns)))
+            .collect();
+        let together = |a: usize, b: usize| slice_tables.iter().all(|dest| slot_of[dest[a]] == slot_of[dest[b]]);
+
+        let mut wings = Self { stickers: Vec::new(), slot: Vec::new(), orbit: Vec::new(), at: vec![None; 6 * size * size] };
+        for (slot, pair) in EDGE_SLOTS.iter().enumerate() {
+            for layer in &layers {
+                let first = at(pair[0], *layer);
+                let second = [*layer, size - 1 - layer]
+                    .map(|layer| at(pair[1], layer))
+                    .into_iter()
+                    .find(|second| together(first, *second))
+                    .unwrap_or(first);
+                for sticker in [first, second] {
+                    wings.at[sticker] = Some(wings.stickers.len());
+                }
+                wings.stickers.push([first, second]);
+                wings.slot.push(slot);
+                wings.orbit.push(*layer.min(&(size - 1 - layer)));
+            }
+        }
+        wings
+    }
+
+    fn in_orbit(&self, orbit: usize) -> Vec<usize> {
+        (0..self.stickers.len()).filter(|w| self.orbit[*w] == orbit).collect()
+    }
+}
+
+/// (destination, source) for every sticker a commutator moves
+type Changes = Vec<(usize, usize)>;
+
+/// A commutator and what it does to the wings
+#[derive(Debug, Clone)]
+struct Cycle {
+    turns: Vec<Turn>,
+    changes: Changes,
+    /// (wing, source of its first sticker, source of its second) per moved wing
+    moved: Vec<(usize, usize, usize)>,
+    /// Whether several slices turn at once
+    batch: bool,
+}
+
+/// Compose sticker permutations: `first`, then `second`
+fn compose(first: &[usize], second: &[usize]) -> Vec<usize> {
+    second.iter().map(|source| first[*source]).collect()
+}
+
+/// Undo a sticker permutation
+fn inverse(permutation: &[usize]) -> Vec<usize> {
+    let mut result = vec![0; permutation.len()];
+    for (dest, source) in permutation.iter().enumerate() {
+        result[*source] = dest;
+    }
+    result
+}
+
+/// Traced turns and the commutators for one cube size
+struct EdgeMoves {
+    wings: Wings,
+    cycles: Vec<Cycle>,
+    /// Sticker order per wing that reads the same piece the same way wherever it is
+    chiral: Vec<[usize; 2]>,
+}
+
+impl EdgeMoves {
+    fn build(size: usize) -> Self {
+        let layers = wing_layers(size);
+        let tables = TurnTables::trace(size, layers);
+        let wings = Wings::build(size, &tables);
+        let outer: Vec<Turn> = SLICE_FACES
+            .iter()
+            .flat_map(|face| DIRECTIONS.map(|direction| Turn::new(*face, 1, direction)))
+            .collect();
+        // Outer sequences a b a' with two different faces
+        let inserts: Vec<(Vec<Turn>, Vec<usize>)> = outer
+            .iter()
+            .flat_map(|a| outer.iter().filter(move |b| b.face != a.face).map(move |b| vec![*a, *b, a.inverse()]))
+            .map(|turns| {
+                let table = tables.permutation(&turns);
+                (turns, table)
+            })
+            .collect();
+        // Any set of slices of one face, turned together
+        let mut slices: Vec<(Vec<Turn>, Vec<usize>, bool)> = Vec::new();
+        for face in SLICE_FACES {
+            for mask in 1..1usize << layers {
+                for direction in QUARTERS {
+                    let mut turns = Vec::new();
+                    let mut layer = 1;
+                    while layer <= layers {
+                        if mask & (1 << (layer - 1)) == 0 {
+                            layer += 1;
+                            continue;
+                        }
+                        let from = layer;
+                        while layer < layers && mask & (1 << layer) != 0 {
+                            layer += 1;
+                        }
+                        turns.extend(slice(face, from, layer, direction));
+                        layer += 1;
+                    }
+                    let table = tables.permutation(&turns);
+                    slices.push((turns, table, mask.count_ones() > 1));
+                }
+            }
+        }
+
+        let pure = |permutation: &[usize]| {
+            let changes: Changes = permutation
+                .iter()
+                .enumerate()
+                .filter(|(dest, source)| *dest != **source)
+                .map(|(dest, source)| (dest, *source))
+                .collect();
+            (!changes.is_empty() && changes.iter().all(|(dest, _)| wings.at[*dest].is_some())).then_some(changes)
+        };
+        let mut found: HashMap<Changes, (Vec<Turn>, bool)> = HashMap::new();
+        let keep = |found: &mut HashMap<Changes, (Vec<Turn>, bool)>, changes, turns: Vec<Turn>, batch| {
+            let better = found.get(&changes).is_none_or(|(known, _)| known.len() > turns.len());
+            if better {
+                found.insert(changes, (turns, batch));
+            }
+        };
+
+        // Commutators [slices, a b a']
+        for (slice_turns, slice_table, batch) in &slices {
+            let undo_slices = inverse(slice_table);
+            for (insert_turns, insert_table) in &inserts {
+                let permutation = compose(
+                    &compose(&compose(slice_table, insert_table), &undo_slices),
+                    &inverse(insert_table),
+                );
+                if let Some(changes) = pure(&permutation) {
+                    let mut turns = slice_turns.clone();
+                    turns.extend(insert_turns);
+                    turns.extend(invert(slice_turns));
+                    turns.extend(invert(insert_turns));
+                    keep(&mut found, changes, turns, *batch);
+                }
+            }
+        }
+
+        // Set up the single-slice 3-cycles with face turns and slices until
+        // every wing 3-cycle is known
+        let singles: Vec<Vec<Turn>> = SLICE_FACES
+            .iter()
+            .flat_map(|face| (1..=layers).flat_map(move |layer| DIRECTIONS.map(|d| slice(*face, layer, layer, d).to_vec())))
+            .collect();
+        let conjugators: Vec<(Vec<Turn>, Vec<usize>)> = outer
+            .iter()
+            .map(|turn| vec![*turn])
+            .chain(singles.iter().cloned())
+            .map(|setup| {
+                let table = tables.permutation(&setup);
+                (setup, table)
+            })
+            .collect();
+        let mut frontier: Vec<(Changes, Vec<Turn>)> = found
+            .iter()
+            .filter(|(changes, (_, batch))| !batch && changes.len() == 6)
+            .map(|(changes, (turns, _))| (changes.clone(), turns.clone()))
+            .collect();
+        frontier.sort_by(|a, b| a.0.cmp(&b.0));
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for (changes, turns) in &frontier {
+                for (setup, table) in &conjugators {
+                    let mut moved: Changes =
+                        changes.iter().map(|(dest, source)| (table[*dest], table[*source])).collect();
+                    moved.sort();
+                    if found.contains_key(&moved) {
+                        continue;
+                    }
+                    let mut conjugated = setup.clone();
+                    conjugated.extend(turns);
+                    conjugated.extend(invert(setup));
+                    next.push((moved.clone(), conjugated.clone()));
+                    keep(&mut found, moved, conjugated, false);
+                }
+            }
+            frontier = next;
+        }
+
+        let mut cycles: Vec<Cycle> = found
+            .into_iter()
+            .map(|(changes, (turns, batch))| {
+                let source = |sticker: usize| {
+                    changes.iter().find(|(dest, _)| *dest == sticker).map_or(sticker, |(_, source)| *source)
+                };
+                let mut moved: Vec<(usize, usize, usize)> = changes
+                    .iter()
+                    .filter_map(|(dest, _)| wings.at[*dest])
+                    .map(|w| (w, source(wings.stickers[w][0]), source(wings.stickers[w][1])))
+                    .collect();
+                moved.dedup();
+                Cycle { turns, changes, moved, batch }
+            })
+            .collect();
+        cycles.sort_by(|a, b| (a.turns.len(), &a.changes).cmp(&(b.turns.len(), &b.changes)));
+
+        let chiral = Self::chiral_order(&wings, &conjugators);
+        Self { wings, cycles, chiral }
+    }
+
+    /// Order each wing's stickers the way a piece carries them there from
+    /// the first wing of its orbit, found by following turns from it
+    fn chiral_order(wings: &Wings, turns: &[(Vec<Turn>, Vec<usize>)]) -> Vec<[usize; 2]> {
+        let destinations: Vec<Vec<usize>> = turns.iter().map(|(_, table)| inverse(table)).collect();
+        let mut order: Vec<Option<[usize; 2]>> = vec![None; wings.stickers.len()];
+        let mut queue = Vec::new();
+        for orbit in 1..=wings.orbit.iter().copied().max().unwrap_or(0) {
+            if let Some(&first) = wings.in_orbit(orbit).first() {
+                order[first] = Some(wings.stickers[first]);
+                queue.push(first);
+            }
+        }
+        while let Some(wing) = queue.pop() {
+            let [first, second] = order[wing].unwrap_or(wings.stickers[wing]);
+            for dest in &destinations {
+                let (first, second) = (dest[first], dest[second]);
+                if let Some(next) = wings.at[first] {
+                    if order[next].is_none() {
+                        order[next] = Some([first, second]);
+                        queue.push(next);
+                    }
+                }
+            }
+        }
+        order.into_iter().zip(&wings.stickers).map(|(order, stickers)| order.unwrap_or(*stickers)).collect()
+    }
+}
+
+/// Greedy edge pairing state
+struct EdgeState<'a> {
+    moves: &'a EdgeMoves,
+    colors: Vec<Color>,
+    /// Color every wing sticker has to end up with
+    targets: Vec<Color>,
+}
+
+impl<'a> EdgeState<'a> {
+    fn placed(&self, wing: usize) -> bool {
+        self.moves.wings.stickers[wing].iter().all(|s| self.colors[*s] == self.targets[*s])
+    }
+
+    fn unplaced(&self) -> usize {
+        (0..self.moves.wings.stickers.len()).filter(|w| !self.placed(*w)).count()
+    }
+
+    /// Wings put in place (or taken out of place, if negative)
+    fn gain(&self, cycle: &Cycle) -> i64 {
+        cycle
+            .moved
+            .iter()
+            .map(|(wing, first, second)| {
+                let [a, b] = self.moves.wings.stickers[*wing];
+                let after = self.colors[*first] == self.targets[a] && self.colors[*second] == self.targets[b];
+                after as i64 - self.placed(*wing) as i64
+            })
+            .sum()
+    }
+
+    fn apply(&mut self, cycle: &Cycle) {
+        let moved: Vec<Color> = cycle.changes.iter().map(|(_, source)| self.colors[*source]).collect();
+        for ((dest, _), color) in cycle.changes.iter().zip(moved) {
+            self.colors[*dest] = color;
+        }
+    }
+
+    /// Cycle with the most wings placed per turn
+    fn best(&self) -> Option<&'a Cycle> {
+        let mut best: Option<(&'a Cycle, i64)> = None;
+        for cycle in &self.moves.cycles {
+            let gain = self.gain(cycle);
+            if gain <= 0 {
+                continue;
+            }
+            let better = best.is_none_or(|(known, known_gain)| {
+                gain * known.turns.len() as i64 > known_gain * cycle.turns.len() as i64
+            });
+            if better {
+                best = Some((cycle, gain));
+            }
+        }
+        best.map(|(cycle, _)| cycle)
+    }
+
+    /// Whether the wings of an orbit are an odd permutation away from their targets
+    fn odd_orbit(&self, orbit: usize) -> Result<bool, String> {
+        let chiral = &self.moves.chiral;
+        let read = |colors: &[Color], wing: usize| (colors[chiral[wing][0]], colors[chiral[wing][1]]);
+        let wings = self.moves.wings.in_orbit(orbit);
+        let mut permutation = Vec::with_capacity(wings.len());
+        for wing in &wings {
+            let piece = read(&self.colors, *wing);
+            let home = wings
+                .iter()
+                .position(|w| read(&self.targets, *w) == piece)
+                .ok_or_else(|| "Edge pieces do not match the cube's edges".to_string())?;
+            permutation.push(home);
+        }
+        Ok(is_odd(&permutation))
+    }
+}
+
+/// Colors every wing sticker has to end up with
+///
+/// On odd cubes each edge matches its middle piece. On even cubes each slot
+/// gets the edge whose pieces it already holds most of, in the orientation
+/// most of them show.
+fn wing_targets(cube: &Cube, wings: &Wings, colors: &[Color]) -> Result<Vec<Color>, String> {
+    let size = cube.size();
+    let mut slot_colors = vec![[Color::White; 2]; EDGE_SLOTS.len()];
+    if size % 2 == 1 {
+        for (slot, pair) in EDGE_SLOTS.iter().enumerate() {
+            slot_colors[slot] = pair.map(|(face, row, col)| {
+                let at = |i: usize| match i {
+                    0 => 0,
+                    1 => size / 2,
+                    _ => size - 1,
+                };
+                cube.get_face(face).get(at(row), at(col))
+            });
+        }
+    } else {
+        let read = |w: usize| wings.stickers[w].map(|s| colors[s]);
+        let mut pieces: Vec<EdgePiece> =
+            (0..wings.stickers.len()).map(|w| EdgePiece::new(read(w)[0], read(w)[1])).collect();
+        let mut counts: HashMap<(usize, EdgePiece), usize> = HashMap::new();
+        for (w, piece) in pieces.iter().enumerate() {
+            *counts.entry((wings.slot[w], *piece)).or_default() += 1;
+        }
+        pieces.sort();
+        pieces.dedup();
+        if pieces.len() != EDGE_SLOTS.len() {
+            return Err("Edge pieces do not match the cube's edges".to_string());
+        }
+
+        let mut ranked: Vec<((usize, EdgePiece), usize)> = counts.into_iter().collect();
+        ranked.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        let mut assigned: Vec<Option<EdgePiece>> = vec![None; EDGE_SLOTS.len()];
+        for ((slot, piece), _) in ranked {
+            if assigned[slot].is_none() && !assigned.contains(&Some(piece)) {
+                assigned[slot] = Some(piece);
+            }
+        }
+        let mut spare = pieces.iter().filter(|p| !assigned.contains(&Some(**p))).copied().collect::<Vec<_>>().into_iter();
+        for (slot, piece) in assigned.iter().enumerate() {
+            let piece = piece.or_else(|| spare.next()).unwrap_or(pieces[0]);
+            let (a, b) = piece.colors();
+            let shown = (0..wings.stickers.len()).filter(|w| wings.slot[*w] == slot).map(read);
+            let flipped = shown.fold(0i64, |votes, [first, second]| {
+                votes + (first == b && second == a) as i64 - (first == a && second == b) as i64
+            });
+            slot_colors[slot] = if flipped > 0 { [b, a] } else { [a, b] };
+        }
+    }
+
+    let mut targets = colors.to_vec();
+    for (w, stickers) in wings.stickers.iter().enumerate() {
+        for (sticker, color) in stickers.iter().zip(slot_colors[wings.slot[w]]) {
+            targets[*sticker] = color;
+        }
+    }
+    Ok(targets)
+}
+
+/// Pair the edges of a 4x4+ cube
+///
+/// Only wings move, so the result reads as a 3x3 whose edges are the middle
+/// edges (odd cubes) or the slots' majority colors (even cubes).
+pub(crate) fn pair_edges(cube: &Cube) -> Result<EdgePhases, String> {
+    let size = cube.size();
+    let moves = EdgeMoves::build(size);
+    let read = |cube: &Cube| -> Vec<Color> {
+        (0..6 * size * size)
+            .map(|index| {
+                let (face, row, col) = position(size, index);
+                cube.get_face(face).get(row, col)
+            })
+            .collect()
+    };
+
+    let mut working = cube.clone();
+    let colors = read(&working);
+    let targets = wing_targets(cube, &moves.wings, &colors)?;
+    let mut state = EdgeState { moves: &moves, colors, targets };
+    let mut phases: EdgePhases = Vec::new();
+
+    // 3-cycles are even permutations, so fix odd layers first
+    for layer in 1..=wing_layers(size) {
+        if state.odd_orbit(layer)? {
+            let fix = get_oll_parity_algorithm(size, layer);
+            working.apply_parsed_moves(&fix);
+            state.colors = read(&working);
+            phases.push((EdgeStep::Parity(layer), fix));
+        }
+    }
+
+    while state.unplaced() > 0 {
+        let chosen = state
+            .best()
+            .ok_or_else(|| "Could not find a move sequence that pairs more edges".to_string())?;
+        state.apply(chosen);
+
+        let step = if chosen.batch { EdgeStep::Batch } else { EdgeStep::Cycle };
+        let turns: Vec<ParsedMove> = chosen.turns.iter().map(|t| t.to_move()).collect();
+        match phases.last_mut() {
+            Some((last, phase)) if *last == step => phase.extend(turns),
+            _ => phases.push((step, turns)),
+        }
+    }
+    Ok(phases)
+}
@@ -0,0 +1,229 @@
+//! Move explanations for 3x3 solutions
+//!
+//! Search-based solvers only produce a list of moves. This module replays
+//! those moves and describes what each run of moves achieves ("place the
+//! white-red edge", "complete the white face"), so a solution can be shown
+//! as a lesson instead of raw notation.
+//!
+//! Pieces are found from the simulator itself: every sticker of one piece is
+//! moved by exactly the same set of face turns.
+
+use crate::cube::{Color, Cube, FaceName, Move};
+use crate::solver::MoveNote;
+use std::collections::BTreeMap;
+
+/// One edge or corner, as the sticker positions it occupies
+#[derive(Debug, Clone)]
+pub(crate) struct Piece {
+    pub(crate) stickers: Vec<(FaceName, usize, usize)>,
+}
+
+impl Piece {
+    /// "edge" or "corner"
+    pub(crate) fn kind(&self) -> &'static str {
+        if self.stickers.len() == 2 {
+            "edge"
+        } else {
+            "corner"
+        }
+    }
+
+    /// Check whether every sticker matches the center of its face
+    pub(crate) fn is_placed(&self, cube: &Cube) -> bool {
+        self.stickers
+            .iter()
+            .all(|(face, row, col)| cube.get_face(*face).get(*row, *col) == center(cube, *face))
+    }
+
+    /// Name the piece by the colors it belongs to, e.g. "white-red edge"
+    fn name(&self, cube: &Cube) -> String {
+        let colors = FaceName::all()
+            .iter()
+            .filter(|face| self.stickers.iter().any(|(f, _, _)| f == *face))
+            .map(|face| color_name(center(cube, *face)))
+            .collect::<Vec<_>>()
+            .join("-");
+        format!("{} {}", colors, self.kind())
+    }
+}
+
+fn center(cube: &Cube, face: FaceName) -> Color {
+    cube.get_face(face).get(1, 1)
+}
+
+pub(crate) fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "white",
+        Color::Yellow => "yellow",
+        Color::Red => "red",
+        Color::Orange => "orange",
+        Color::Blue => "blue",
+        Color::Green => "green",
+    }
+}
+
+fn face_turn(face: FaceName) -> Move {
+    match face {
+        FaceName::U => Move::U,
+        FaceName::D => Move::D,
+        FaceName::F => Move::F,
+        FaceName::B => Move::B,
+        FaceName::L => Move::L,
+        FaceName::R => Move::R,
+    }
+}
+
+/// Find the 20 edges and corners of a 3x3
+pub(crate) fn pieces() -> Vec<Piece> {
+    let mut by_layers: BTreeMap<u8, Vec<(FaceName, usize, usize)>> = BTreeMap::new();
+
+    for face in FaceName::all() {
+        for row in 0..3 {
+            for col in 0..3 {
+                if (row, col) == (1, 1) {
+                    continue;
+                }
+                // Mark this sticker and see which face turns carry it away
+                let mut layers = 0u8;
+                for (bit, turned) in FaceName::all().iter().enumerate() {
+                    let mut marker = Cube::new(3);
+                    for f in FaceName::all() {
+                        for r in 0..3 {
+                            for c in 0..3 {
+                                marker.set_sticker(f, r, c, Color::White);
+                            }
+                        }
+                    }
+                    marker.set_sticker(face, row, col, Color::Red);
+                    marker.apply_move(face_turn(*turned));
+                    if marker.get_face(face).get(row, col) != Color::Red {
+                        layers |= 1 << bit;
+                    }
+                }
+                by_layers.entry(layers).or_default().push((face, row, col));
+            }
+        }
+    }
+
+    by_layers
+        .into_values()
+        .map(|stickers| Piece { stickers })
+        .collect()
+}
+
+/// Describe newly placed pieces, e.g. "place the white-red edge"
+fn describe_placed(names: &[String]) -> String {
+    match names {
+        [one] => format!("place the {}", one),
+        [first, second] => format!("place the {} and the {}", first, second),
+        _ => format!("place {} pieces", names.len()),
+    }
+}
+
+/// Explain what each run of moves achieves on a 3x3
+///
+/// Moves are grouped into runs that each end when something is finished:
+/// the whole cube, a face, or one or more pieces. Trailing moves that do
+/// not finish anything get no note. Other cube sizes get no notes.
+pub fn explain_moves(start: &Cube, moves: &[Move]) -> Vec<MoveNote> {
+    if start.size() != 3 {
+        return Vec::new();
+    }
+
+    let pieces = pieces();
+    let mut cube = start.clone();
+    let mut notes = Vec::new();
+    let mut run_start = 0;
+    let mut placed_before: Vec<bool> = pieces.iter().map(|p| p.is_placed(&cube)).collect();
+    let mut faces_before: Vec<bool> = FaceName::all()
+        .iter()
+        .map(|f| cube.get_face(*f).is_solved())
+        .collect();
+
+    for (index, mv) in moves.iter().enumerate() {
+        cube.apply_move(*mv);
+        let placed: Vec<bool> = pieces.iter().map(|p| p.is_placed(&cube)).collect();
+        let faces: Vec<bool> = FaceName::all()
+            .iter()
+            .map(|f| cube.get_face(*f).is_solved())
+            .collect();
+
+        let new_pieces: Vec<String> = pieces
+            .iter()
+            .zip(placed.iter().zip(&placed_before))
+            .filter(|(_, (now, before))| **now && !**before)
+            .map(|(piece, _)| piece.name(&cube))
+            .collect();
+        let new_face = FaceName::all()
+            .iter()
+            .zip(faces.iter().zip(&faces_before))
+            .find(|(_, (now, before))| **now && !**before)
+            .map(|(face, _)| *face);
+
+        let purpose = if cube.is_solved() {
+            Some("finish the cube".to_string())
+        } else if let Some(face) = new_face {
+            Some(format!("complete the {} face", color_name(center(&cube, face))))
+        } else if !new_pieces.is_empty() {
+            Some(describe_placed(&new_pieces))
+        } else {
+            None
+        };
+
+        if let Some(purpose) = purpose {
+            notes.push(MoveNote::new(run_start, index + 1 - run_start, purpose));
+            run_start = index + 1;
+            placed_before = placed;
+            faces_before = faces;
+        }
+    }
+
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pieces_found() {
+        let pieces = pieces();
+        assert_eq!(pieces.len(), 20);
+        assert_eq!(pieces.iter().filter(|p| p.kind() == "edge").count(), 12);
+        assert_eq!(pieces.iter().filter(|p| p.kind() == "corner").count(), 8);
+    }
+
+    #[test]
+    fn test_explain_single_move_finishes() {
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::R);
+        let notes = explain_moves(&cube, &[Move::RPrime]);
+        assert_eq!(notes, vec![MoveNote::new(0, 1, "finish the cube")]);
+    }
+
+    #[test]
+    fn test_explain_runs_cover_moves_in_order() {
+        let scramble = [Move::R, Move::U, Move::F, Move::D];
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&scramble);
+        let solution: Vec<Move> = scramble.iter().rev().map(|m| m.inverse()).collect();
+
+        let notes = explain_moves(&cube, &solution);
+        assert!(!notes.is_empty());
+        let mut next = 0;
+        for note in &notes {
+            assert_eq!(note.start, next);
+            assert!(note.len > 0);
+            next = note.start + note.len;
+        }
+        assert_eq!(next, solution.len());
+        assert_eq!(notes.last().unwrap().purpose, "finish the cube");
+    }
+
+    #[test]
+    fn test_explain_names_pieces() {
+        let names = vec!["white-red edge".to_string()];
+        assert_eq!(describe_placed(&names), "place the white-red edge");
+        assert!(explain_moves(&Cube::new(4), &[Move::R]).is_empty());
+    }
+}
@@ -0,0 +1,477 @@
+//! F2L pair solver
+//!
+//! Finds one corner/edge pair of the first two layers, matches it against
+//! the 41 standard F2L cases and returns the algorithm that inserts it.
+//! The cross is expected on D. Any of the four slots can be targeted: the
+//! cube is turned with y so the slot sits at front-right, where the case
+//! algorithms insert.
+//!
+//! Cases are recognized by where the five stickers of the pair are and which
+//! way they face. Each case's reference position is built by undoing its
+//! algorithm on a solved cube, so a match always inserts the pair.
+//!
+//! The algorithms are listed in the usual speedcubing notation. This app's
+//! R, L, U and D turn the other way (see `tests/move_tests.rs`), so those
+//! turns are inverted when the algorithms are loaded.
+
+use crate::cube::notation::{parse_algorithm, ParsedMove};
+use crate::cube::{Color, Cube, FaceName, Move};
+use crate::solver::explain::{color_name, pieces, Piece};
+use crate::solver::solution::SolutionStep;
+
+/// Standard F2L algorithms for the front-right slot, case 1 first
+const CASE_ALGORITHMS: [&str; 41] = [
+    // Corner and edge in the top layer
+    "U R U' R'",
+    "U' F' U F",
+    "F' U' F",
+    "R U R'",
+    "U' R U R' U2 R U' R'",
+    "U F' U' F U2 F' U F",
+    "U' R U2 R' U2 R U' R'",
+    "U F' U2 F U2 F' U F",
+    "U' R U' R' U F' U' F",
+    "U' R U R' U R U R'",
+    "U' R U2 R' U F' U' F",
+    "R U' R' U R U' R' U2 R U' R'",
+    "U F' U F U' F' U' F",
+    "U' R U' R' U R U R'",
+    "R' D' R U' R' D R U R U' R'",
+    "R U' R' U2 F' U' F",
+    "R U2 R' U' R U R'",
+    "F' U2 F U F' U' F",
+    "U R U2 R' U R U' R'",
+    "U' F' U2 F U' F' U F",
+    "U2 R U R' U R U' R'",
+    "U2 F' U' F U' F' U F",
+    "U R U' R' U' R U' R' U R U' R'",
+    "F U R U' R' F' R U' R'",
+    // Corner in the slot, edge in the top layer
+    "U' R' F R F' R U R'",
+    "U R U' R' F R' F' R",
+    "R U' R' U R U' R'",
+    "R U R' U' F R' F' R",
+    "R U' R' F' U' F",
+    "R U R' U' R U R'",
+    // Corner in the top layer, edge in the slot
+    "R U' R' U F' U F",
+    "R U R' U' R U R' U' R U R'",
+    "U' R U' R' U2 R U' R'",
+    "U R U R' U2 R U R'",
+    "U' R U R' U F' U' F",
+    "U F' U' F U' R U R'",
+    // Corner and edge in the slot
+    "R2 U2 F R2 F' U2 R' U R'",
+    "R U' R' U' R U R' U2 R U' R'",
+    "R U' R' U R U2 R' U R U' R'",
+    "R U R' U' R U' R' U2 F' U' F",
+    "R U' R' U' R U' R' U F' U' F",
+];
+
+/// Convert a move from standard notation to this app's turn directions
+fn from_standard(mv: Move) -> Move {
+    match mv {
+        Move::R | Move::RPrime | Move::L | Move::LPrime => mv.inverse(),
+        Move::U | Move::UPrime | Move::D | Move::DPrime => mv.inverse(),
+        Move::M | Move::MPrime | Move::E | Move::EPrime => mv.inverse(),
+        _ => mv,
+    }
+}
+
+/// Parse a face-turn algorithm written in standard notation into app moves
+///
+/// Panics on invalid notation; only used for the built-in algorithm tables.
+pub(crate) fn standard_algorithm(notation: &str) -> Vec<Move> {
+    parse_algorithm(notation)
+        .expect("built-in algorithms are valid notation")
+        .into_iter()
+        .filter_map(|m| match m {
+            ParsedMove::Basic(mv) => Some(from_standard(mv)),
+            ParsedMove::Wide(_) | ParsedMove::Layer(_) => None,
+        })
+        .collect()
+}
+
+/// Moves tried to lift a pair out of another slot before matching again
+const EXTRACTIONS: [Move; 8] = [
+    Move::L, Move::LPrime, Move::B, Move::BPrime,
+    Move::R, Move::RPrime, Move::F, Move::FPrime,
+];
+
+/// Pre-turns of the top layer (AUF) tried before matching
+const TOP_TURNS: [Option<Move>; 4] = [None, Some(Move::U), Some(Move::UPrime), Some(Move::U2)];
+
+/// One of the four first-two-layers slots, with the cross on D
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum F2lSlot {
+    /// Between the front and right faces
+    FrontRight,
+    /// Between the right and back faces
+    BackRight,
+    /// Between the back and left faces
+    BackLeft,
+    /// Between the left and front faces
+    FrontLeft,
+}
+
+impl F2lSlot {
+    /// All slots, clockwise from above starting at front-right
+    pub const ALL: [F2lSlot; 4] = [
+        F2lSlot::FrontRight,
+        F2lSlot::BackRight,
+        F2lSlot::BackLeft,
+        F2lSlot::FrontLeft,
+    ];
+
+    /// The slot's two side faces, in the order they take at front and right
+    pub fn faces(&self) -> (FaceName, FaceName) {
+        match self {
+            F2lSlot::FrontRight => (FaceName::F, FaceName::R),
+            F2lSlot::BackRight => (FaceName::R, FaceName::B),
+            F2lSlot::BackLeft => (FaceName::B, FaceName::L),
+            F2lSlot::FrontLeft => (FaceName::L, FaceName::F),
+        }
+    }
+
+    /// Short name, e.g. "FR"
+    pub fn label(&self) -> &'static str {
+        match self {
+            F2lSlot::FrontRight => "FR",
+            F2lSlot::BackRight => "BR",
+            F2lSlot::BackLeft => "BL",
+            F2lSlot::FrontLeft => "FL",
+        }
+    }
+}
+
+/// Where the pair is in a case, the standard way F2L cases are grouped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum F2lGroup {
+    /// Corner and edge both in the top layer
+    BothInTop,
+    /// Corner in the top layer, edge in the slot
+    EdgeInSlot,
+    /// Corner in the slot, edge in the top layer
+    CornerInSlot,
+    /// Corner and edge both in the slot, but not solved
+    BothInSlot,
+}
+
+impl F2lGroup {
+    /// Human-readable description
+    pub fn description(&self) -> &'static str {
+        match self {
+            F2lGroup::BothInTop => "Corner and edge both in the top layer",
+            F2lGroup::EdgeInSlot => "Corner in the top layer, edge in the slot",
+            F2lGroup::CornerInSlot => "Corner in the slot, edge in the top layer",
+            F2lGroup::BothInSlot => "Corner and edge both in the slot",
+        }
+    }
+}
+
+/// One of the 41 standard F2L cases
+#[derive(Debug, Clone, PartialEq)]
+pub struct F2lCase {
+    /// Standard case number (1-41)
+    pub number: usize,
+    /// Where the pair starts
+    pub group: F2lGroup,
+    /// Insertion algorithm for the front-right slot
+    pub algorithm: Vec<Move>,
+}
+
+impl F2lCase {
+    /// Display name, e.g. "F2L 12"
+    pub fn name(&self) -> String {
+        format!("F2L {}", self.number)
+    }
+
+    /// Algorithm in standard notation
+    pub fn to_notation(&self) -> String {
+        self.algorithm
+            .iter()
+            .map(|m| m.to_notation())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Moves that set up the case from a solved cube (the inverse algorithm)
+    pub fn setup_moves(&self) -> Vec<Move> {
+        self.algorithm.iter().rev().map(|m| m.inverse()).collect()
+    }
+
+    /// A cube with the cross solved and this case in the front-right slot
+    pub fn case_state(&self) -> Cube {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&self.setup_moves());
+        cube
+    }
+}
+
+/// Get the 41 standard F2L cases, in case-number order
+pub fn f2l_cases() -> Vec<F2lCase> {
+    let pieces = pieces();
+    CASE_ALGORITHMS
+        .iter()
+        .enumerate()
+        .map(|(index, notation)| {
+            let algorithm = standard_algorithm(notation);
+            let mut case = F2lCase { number: index + 1, group: F2lGroup::BothInTop, algorithm };
+            case.group = pair_group(&case.case_state(), &pieces).unwrap_or(F2lGroup::BothInTop);
+            case
+        })
+        .collect()
+}
+
+/// Stickers of a pair, each with its role: 0 = cross color, 1 = front color,
+/// 2 = right color; corner stickers first
+type Signature = Vec<((FaceName, usize, usize), u8)>;
+
+/// Colors of the front-right pair: cross (D), front and right centers
+fn pair_colors(cube: &Cube) -> [Color; 3] {
+    [FaceName::D, FaceName::F, FaceName::R].map(|face| cube.get_face(face).get(1, 1))
+}
+
+/// Find the front-right pair and describe where its stickers are
+fn pair_signature(cube: &Cube, pieces: &[Piece]) -> Option<Signature> {
+    let colors = pair_colors(cube);
+    let role = |color: Color| colors.iter().position(|c| *c == color).map(|i| i as u8);
+    let find = |kind: &str, roles: &[u8]| {
+        pieces.iter().filter(|p| p.kind() == kind).find_map(|piece| {
+            let stickers: Option<Signature> = piece
+                .stickers
+                .iter()
+                .map(|(face, row, col)| role(cube.get_face(*face).get(*row, *col)).map(|r| ((*face, *row, *col), r)))
+                .collect();
+            let stickers = stickers?;
+            let mut found: Vec<u8> = stickers.iter().map(|(_, r)| *r).collect();
+            found.sort_unstable();
+            (found == roles).then_some(stickers)
+        })
+    };
+    let mut signature = find("corner", &[0, 1, 2])?;
+    signature.extend(find("edge", &[1, 2])?);
+    Some(signature)
+}
+
+/// Check which layer each piece of the pair is in
+fn pair_group(cube: &Cube, pieces: &[Piece]) -> Option<F2lGroup> {
+    let signature = pair_signature(cube, pieces)?;
+    let (corner, edge) = signature.split_at(3);
+    let in_top = |stickers: &[((FaceName, usize, usize), u8)]| {
+        stickers.iter().any(|((face, _, _), _)| *face == FaceName::U)
+    };
+    Some(match (in_top(corner), in_top(edge)) {
+        (true, true) => F2lGroup::BothInTop,
+        (true, false) => F2lGroup::EdgeInSlot,
+        (false, true) => F2lGroup::CornerInSlot,
+        (false, false) => F2lGroup::BothInSlot,
+    })
+}
+
+/// The answer for one pair
+#[derive(Debug, Clone, PartialEq)]
+pub struct F2lPairSolution {
+    /// Slot the pair goes into
+    pub slot: F2lSlot,
+    /// Matched case, or `None` if the pair was already solved
+    pub case: Option<F2lCase>,
+    /// Full move sequence: rotation, extraction, top-layer turn, algorithm
+    pub moves: Vec<Move>,
+    /// Colors of the pair's side faces, e.g. (Green, Red)
+    pub colors: (Color, Color),
+}
+
+impl F2lPairSolution {
+    /// Turn the answer into a solution step
+    pub fn to_step(&self) -> SolutionStep {
+        let pair = format!("{}-{}", color_name(self.colors.0), color_name(self.colors.1));
+        match &self.case {
+            Some(case) => SolutionStep::with_explanation(
+                format!("Insert the {} pair ({})", pair, case.name()),
+                self.moves.clone(),
+                case.group.description(),
+            ),
+            None => SolutionStep::new(format!("The {} pair is already solved", pair), vec![]),
+        }
+    }
+}
+
+/// Turn the cube with y so `slot` is at front-right
+///
+/// Returns the turned cube and the rotation used.
+pub(crate) fn face_slot(cube: &Cube, slot: F2lSlot) -> Result<(Cube, Vec<Move>), String> {
+    let (first, second) = slot.faces();
+    let want = (cube.get_face(first).get(1, 1), cube.get_face(second).get(1, 1));
+    [vec![], vec![Move::Y], vec![Move::Y2], vec![Move::YPrime]]
+        .into_iter()
+        .map(|rotation| {
+            let mut turned = cube.clone();
+            turned.apply_moves(&rotation);
+            (turned, rotation)
+        })
+        .find(|(turned, _)| {
+            (turned.get_face(FaceName::F).get(1, 1), turned.get_face(FaceName::R).get(1, 1)) == want
+        })
+        .ok_or_else(|| format!("Could not turn the {} slot to the front", slot.label()))
+}
+
+/// Check whether the pair for `slot` is in place
+pub fn is_f2l_pair_solved(cube: &Cube, slot: F2lSlot) -> bool {
+    if cube.size() != 3 {
+        return false;
+    }
+    let pieces = pieces();
+    let solved = pair_signature(&Cube::new(3), &pieces);
+    match face_slot(cube, slot) {
+        Ok((turned, _)) => pair_signature(&turned, &pieces) == solved,
+        Err(_) => false,
+    }
+}
+
+/// Find the pair for `slot`, match it to an F2L case and return the moves
+///
+/// If the pair is stuck in another slot it is taken out first.
+pub fn solve_f2l_pair(cube: &Cube, slot: F2lSlot) -> Result<F2lPairSolution, String> {
+    if cube.size() != 3 {
+        return Err("Cube must be size 3 for F2L".to_string());
+    }
+    let (turned, rotation) = face_slot(cube, slot)?;
+    let colors = pair_colors(&turned);
+    let colors = (colors[1], colors[2]);
+    let pieces = pieces();
+    let cases = f2l_cases();
+    let case_signatures: Vec<Option<Signature>> =
+        cases.iter().map(|case| pair_signature(&case.case_state(), &pieces)).collect();
+
+    if pair_signature(&turned, &pieces).is_none() {
+        return Err(format!(
+            "The {}-{} pair was not found",
+            color_name(colors.0),
+            color_name(colors.1)
+        ));
+    }
+    if pair_signature(&turned, &pieces) == pair_signature(&Cube::new(3), &pieces) {
+        return Ok(F2lPairSolution { slot, case: None, moves: Vec::new(), colors });
+    }
+
+    // Match directly, then after lifting the pair out of another slot
+    let mut extractions = vec![vec![]];
+    for mv in EXTRACTIONS {
+        for turn in [Move::U, Move::UPrime, Move::U2] {
+            extractions.push(vec![mv, turn, mv.inverse()]);
+        }
+    }
+    for extraction in extractions {
+        for top_turn in TOP_TURNS {
+            let mut setup = extraction.clone();
+            setup.extend(top_turn);
+            let mut state = turned.clone();
+            state.apply_moves(&setup);
+            let Some(signature) = pair_signature(&state, &pieces) else {
+                continue;
+            };
+            if let Some(index) = case_signatures.iter().position(|s| s.as_ref() == Some(&signature)) {
+                let case = cases[index].clone();
+                let mut moves = rotation.clone();
+                moves.extend(setup);
+                moves.extend(case.algorithm.iter().copied());
+                return Ok(F2lPairSolution { slot, case: Some(case), moves, colors });
+            }
+        }
+    }
+
+    Err(format!(
+        "The {}-{} pair does not match any F2L case",
+        color_name(colors.0),
+        color_name(colors.1)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_41_distinct_cases() {
+        let pieces = pieces();
+        let cases = f2l_cases();
+        assert_eq!(cases.len(), 41);
+
+        let solved = pair_signature(&Cube::new(3), &pieces).unwrap();
+        let signatures: Vec<Signature> = cases
+            .iter()
+            .map(|case| pair_signature(&case.case_state(), &pieces).expect("pair is found"))
+            .collect();
+        for (i, signature) in signatures.iter().enumerate() {
+            assert_ne!(*signature, solved, "{} starts solved", cases[i].name());
+            assert!(
+                signatures[i + 1..].iter().all(|other| other != signature),
+                "{} is repeated",
+                cases[i].name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_groups() {
+        let cases = f2l_cases();
+        assert_eq!(cases[0].group, F2lGroup::BothInTop);
+        assert!(cases.iter().any(|c| c.group == F2lGroup::BothInSlot));
+    }
+
+    #[test]
+    fn test_every_case_is_recognized() {
+        for case in f2l_cases() {
+            let cube = case.case_state();
+            let answer = solve_f2l_pair(&cube, F2lSlot::FrontRight).unwrap();
+            let mut solved = cube.clone();
+            solved.apply_moves(&answer.moves);
+            assert!(is_f2l_pair_solved(&solved, F2lSlot::FrontRight), "{} not inserted", case.name());
+        }
+    }
+
+    #[test]
+    fn test_top_layer_turn_is_added() {
+        let case = &f2l_cases()[3];
+        let mut cube = case.case_state();
+        cube.apply_move(Move::U);
+        let answer = solve_f2l_pair(&cube, F2lSlot::FrontRight).unwrap();
+        let mut solved = cube.clone();
+        solved.apply_moves(&answer.moves);
+        assert!(is_f2l_pair_solved(&solved, F2lSlot::FrontRight));
+    }
+
+    #[test]
+    fn test_solved_pair_and_other_slots() {
+        let cube = Cube::new(3);
+        for slot in F2lSlot::ALL {
+            assert!(is_f2l_pair_solved(&cube, slot));
+            let answer = solve_f2l_pair(&cube, slot).unwrap();
+            assert!(answer.case.is_none());
+            assert!(answer.moves.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_other_slot_is_turned_to_front() {
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::Y);
+        cube.apply_moves(&f2l_cases()[0].setup_moves());
+        cube.apply_move(Move::YPrime);
+        let slot = F2lSlot::ALL
+            .into_iter()
+            .find(|slot| !is_f2l_pair_solved(&cube, *slot))
+            .expect("one slot was disturbed");
+        let answer = solve_f2l_pair(&cube, slot).unwrap();
+        let mut solved = cube.clone();
+        solved.apply_moves(&answer.moves);
+        assert!(is_f2l_pair_solved(&solved, slot));
+    }
+
+    #[test]
+    fn test_rejects_other_sizes() {
+        assert!(solve_f2l_pair(&Cube::new(4), F2lSlot::FrontRight).is_err());
+        assert!(!is_f2l_pair_solved(&Cube::new(2), F2lSlot::FrontRight));
+    }
+}
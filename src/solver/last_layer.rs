@@ -0,0 +1,352 @@
+//! Last-layer solver
+//!
+//! Finishes a 3x3 whose first two layers are already solved, so someone who
+//! did most of the cube themselves gets a short finishing sequence instead
+//! of a full re-solve.
+//!
+//! Uses 2-look OLL and 2-look PLL: orient the top edges, orient the top
+//! corners, swap the corners into place, then cycle the edges. Each look
+//! tries its algorithms after every top-layer turn, chaining up to three
+//! of them when one is not enough. The first two layers are expected on D.
+
+use crate::cube::{Color, Cube, FaceName, Move};
+use crate::solver::cross::is_cross_solved;
+use crate::solver::explain::explain_moves;
+use crate::solver::f2l::{is_f2l_pair_solved, standard_algorithm, F2lSlot};
+use crate::solver::solution::{Solution, SolutionStep};
+use std::time::Instant;
+
+/// Most algorithms chained within one look
+const MAX_ALGORITHMS: usize = 3;
+
+/// Pre-turns of the top layer (AUF) tried before each algorithm
+const TOP_TURNS: [&[Move]; 4] = [&[], &[Move::U], &[Move::UPrime], &[Move::U2]];
+
+/// Make the yellow cross on top
+const EDGE_ORIENTATION: [&str; 2] = ["F R U R' U' F'", "F U R U' R' F'"];
+
+/// Turn every top corner yellow side up
+const CORNER_ORIENTATION: [&str; 7] = [
+    // Sune
+    "R U R' U R U2 R'",
+    // Anti-Sune
+    "R U2 R' U' R U' R'",
+    // H
+    "R U R' U R U' R' U R U2 R'",
+    // Pi
+    "R U2 R2 U' R2 U' R2 U2 R",
+    // Headlights
+    "R2 D R' U2 R D' R' U2 R'",
+    // Chameleon
+    "R U R D R' U' R D' R2",
+    // Bowtie
+    "F R' F' R U R U' R'",
+];
+
+/// Swap two corners: adjacent (T-perm) or diagonal (Y-perm)
+const CORNER_PERMUTATION: [&str; 2] = [
+    "R U R' U' R' F R2 U' R' U' R U R' F'",
+    "F R U' R' U' R U R' F' R U R' U' R' F R F'",
+];
+
+/// Cycle the edges: Ua, Ub, H and Z perms
+const EDGE_PERMUTATION: [&str; 4] = [
+    "R U' R U R U R U' R' U' R2",
+    "R2 U R U R' U' R' U' R' U R'",
+    "R2 U2 R U2 R2 U2 R2 U2 R U2 R2",
+    "R' U' R U' R U R U' R' U R U R2 U' R'",
+];
+
+/// One look of the last layer
+struct Look {
+    /// Step description
+    description: &'static str,
+    /// Step explanation
+    explanation: &'static str,
+    /// Algorithms in standard notation
+    algorithms: &'static [&'static str],
+    /// Whether the look is done; must not depend on top-layer turns
+    done: fn(&Cube) -> bool,
+}
+
+/// The four looks, in solving order
+const LOOKS: [Look; 4] = [
+    Look {
+        description: "Orient the top edges",
+        explanation: "Make a cross of the top color on the top face",
+        algorithms: &EDGE_ORIENTATION,
+        done: edges_oriented,
+    },
+    Look {
+        description: "Orient the top corners",
+        explanation: "Turn every top corner so the top color faces up",
+        algorithms: &CORNER_ORIENTATION,
+        done: corners_oriented,
+    },
+    Look {
+        description: "Permute the top corners",
+        explanation: "Swap the corners until each side shows matching corner colors",
+        algorithms: &CORNER_PERMUTATION,
+        done: corners_permuted,
+    },
+    Look {
+        description: "Permute the top edges",
+        explanation: "Cycle the edges so the whole top layer is solved",
+        algorithms: &EDGE_PERMUTATION,
+        done: edges_permuted,
+    },
+];
+
+/// Color of the U center
+fn top_color(cube: &Cube) -> Color {
+    cube.get_face(FaceName::U).get(1, 1)
+}
+
+/// The top face shows a cross of the top color
+pub(crate) fn edges_oriented(cube: &Cube) -> bool {
+    let (up, top) = (cube.get_face(FaceName::U), top_color(cube));
+    [(0, 1), (1, 0), (1, 2), (2, 1)].iter().all(|&(row, col)| up.get(row, col) == top)
+}
+
+/// The whole top face shows the top color
+pub(crate) fn corners_oriented(cube: &Cube) -> bool {
+    cube.get_face(FaceName::U).is_solved()
+}
+
+/// Every side shows the same color on both top corners ("headlights")
+fn corners_permuted(cube: &Cube) -> bool {
+    corners_oriented(cube)
+        && [FaceName::F, FaceName::R, FaceName::B, FaceName::L].iter().all(|&face| {
+            let face = cube.get_face(face);
+            face.get(0, 0) == face.get(0, 2)
+        })
+}
+
+/// Solved up to a final turn of the top layer
+fn edges_permuted(cube: &Cube) -> bool {
+    final_turn(cube).is_some()
+}
+
+/// The top-layer turn that finishes an otherwise solved cube
+fn final_turn(cube: &Cube) -> Option<&'static [Move]> {
+    TOP_TURNS.into_iter().find(|turn| {
+        let mut turned = cube.clone();
+        turned.apply_moves(turn);
+        turned.is_solved()
+    })
+}
+
+/// Check whether the cross and all four F2L pairs are solved, with the cross on D
+pub fn is_f2l_solved(cube: &Cube) -> bool {
+    cube.size() == 3
+        && is_cross_solved(cube, cube.get_face(FaceName::D).get(1, 1))
+        && F2lSlot::ALL.iter().all(|slot| is_f2l_pair_solved(cube, *slot))
+}
+
+/// Merge neighbouring top-layer turns, e.g. "U U" -> "U2"
+fn merge_top_turns(moves: Vec<Move>) -> Vec<Move> {
+    let quarter_turns = |mv: Move| match mv {
+        Move::U => Some(1),
+        Move::U2 => Some(2),
+        Move::UPrime => Some(3),
+        _ => None,
+    };
+    let mut merged: Vec<Move> = Vec::with_capacity(moves.len());
+    for mv in moves {
+        let combined = match (merged.last().copied().and_then(quarter_turns), quarter_turns(mv)) {
+            (Some(a), Some(b)) => Some((a + b) % 4),
+            _ => None,
+        };
+        match combined {
+            Some(turns) => {
+                merged.pop();
+                merged.extend([None, Some(Move::U), Some(Move::U2), Some(Move::UPrime)][turns]);
+            }
+            None => merged.push(mv),
+        }
+    }
+    merged
+}
+
+/// Shortest chain of (top-layer turn, algorithm) that completes `look`
+fn solve_look(cube: &Cube, look: &Look) -> Option<Vec<Move>> {
+    let candidates: Vec<Vec<Move>> = TOP_TURNS
+        .iter()
+        .flat_map(|turn| {
+            look.algorithms.iter().map(move |notation| {
+                let mut moves = turn.to_vec();
+                moves.extend(standard_algorithm(notation));
+                moves
+            })
+        })
+        .collect();
+
+    let finished = |frontier: &[(Cube, Vec<Move>)]| {
+        frontier
+            .iter()
+            .find(|(state, _)| (look.done)(state))
+            .map(|(_, moves)| merge_top_turns(moves.clone()))
+    };
+    let mut frontier = vec![(cube.clone(), Vec::new())];
+    for _ in 0..MAX_ALGORITHMS {
+        if let Some(moves) = finished(&frontier) {
+            return Some(moves);
+        }
+        frontier = frontier
+            .iter()
+            .flat_map(|(state, moves)| {
+                candidates.iter().map(move |candidate| {
+                    let mut next = state.clone();
+                    next.apply_moves(candidate);
+                    let mut moves = moves.clone();
+                    moves.extend(candidate);
+                    (next, moves)
+                })
+            })
+            .collect();
+    }
+    finished(&frontier)
+}
+
+/// Moves of each look in order (orient edges, orient corners, permute
+/// corners, permute edges), then the final top-layer turn
+///
+/// A look that is already done gets no moves. The first two layers are
+/// expected to be solved.
+pub(crate) fn look_moves(cube: &Cube) -> Result<Vec<Vec<Move>>, String> {
+    let mut state = cube.clone();
+    let mut looks = Vec::new();
+    for look in &LOOKS {
+        let moves = solve_look(&state, look)
+            .ok_or_else(|| "The last layer cannot be solved (is a piece twisted or flipped?)".to_string())?;
+        state.apply_moves(&moves);
+        looks.push(moves);
+    }
+    looks.push(final_turn(&state).unwrap_or_default().to_vec());
+    Ok(looks)
+}
+
+/// Solve only the last layer of a 3x3 whose first two layers are done
+///
+/// Returns an error if the cube is not a 3x3, the first two layers are not
+/// solved, or the last layer cannot be solved (a twisted corner or flipped
+/// edge).
+pub fn solve_last_layer(cube: &Cube) -> Result<Solution, String> {
+    let start = Instant::now();
+    if cube.size() != 3 {
+        return Err("Cube must be size 3 for the last-layer solver".to_string());
+    }
+    if !is_f2l_solved(cube) {
+        return Err("Solve the first two layers before the last layer".to_string());
+    }
+
+    let mut state = cube.clone();
+    let mut steps = Vec::new();
+    let looks = look_moves(cube)?;
+    for (look, moves) in LOOKS.iter().zip(&looks) {
+        if !moves.is_empty() {
+            steps.push(
+                SolutionStep::with_explanation(look.description, moves.clone(), look.explanation)
+                    .with_notes(explain_moves(&state, moves)),
+            );
+            state.apply_moves(moves);
+        }
+    }
+    if let Some(turn) = looks.last().filter(|turn| !turn.is_empty()) {
+        steps.push(SolutionStep::new("Turn the top layer into place", turn.clone()));
+    }
+    if steps.is_empty() {
+        steps.push(SolutionStep::new("The last layer is already solved", vec![]));
+    }
+
+    Ok(Solution::with_method(steps, start.elapsed().as_millis(), "2-Look OLL + 2-Look PLL"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_algorithms() -> impl Iterator<Item = &'static str> {
+        LOOKS.iter().flat_map(|look| look.algorithms.iter().copied())
+    }
+
+    fn solves(cube: &Cube) -> Solution {
+        let solution = solve_last_layer(cube).unwrap();
+        let mut result = cube.clone();
+        for step in &solution.steps {
+            result.apply_moves(&step.moves);
+        }
+        assert!(result.is_solved());
+        solution
+    }
+
+    #[test]
+    fn test_algorithms_keep_first_two_layers() {
+        for notation in all_algorithms() {
+            let mut cube = Cube::new(3);
+            cube.apply_moves(&standard_algorithm(notation));
+            assert!(is_f2l_solved(&cube), "{} breaks F2L", notation);
+            assert!(!cube.is_solved(), "{} does nothing", notation);
+        }
+    }
+
+    #[test]
+    fn test_solves_each_algorithm_case() {
+        for notation in all_algorithms() {
+            let mut cube = Cube::new(3);
+            cube.apply_moves(&standard_algorithm(notation));
+            cube.apply_move(Move::U);
+            solves(&cube);
+        }
+    }
+
+    #[test]
+    fn test_solves_mixed_last_layers() {
+        let algorithms: Vec<&str> = all_algorithms().collect();
+        for (i, first) in algorithms.iter().enumerate() {
+            let second = algorithms[(i * 5 + 3) % algorithms.len()];
+            let mut cube = Cube::new(3);
+            cube.apply_moves(&standard_algorithm(first));
+            cube.apply_move(Move::U2);
+            cube.apply_moves(&standard_algorithm(second));
+            solves(&cube);
+        }
+    }
+
+    #[test]
+    fn test_already_solved_and_top_turn_only() {
+        let solution = solves(&Cube::new(3));
+        assert_eq!(solution.move_count(), 0);
+
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::U);
+        let solution = solves(&cube);
+        assert_eq!(solution.move_count(), 1);
+    }
+
+    #[test]
+    fn test_rejects_unsolved_first_two_layers() {
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::R);
+        assert!(solve_last_layer(&cube).is_err());
+        assert!(solve_last_layer(&Cube::new(4)).is_err());
+    }
+
+    #[test]
+    fn test_rejects_flipped_edge() {
+        let mut cube = Cube::new(3);
+        let front = cube.get_face(FaceName::F).get(0, 1);
+        let top = top_color(&cube);
+        cube.set_sticker(FaceName::U, 2, 1, front);
+        cube.set_sticker(FaceName::F, 0, 1, top);
+        assert!(solve_last_layer(&cube).is_err());
+    }
+
+    #[test]
+    fn test_merge_top_turns() {
+        use Move::*;
+        assert_eq!(merge_top_turns(vec![U, U, R]), vec![U2, R]);
+        assert_eq!(merge_top_turns(vec![R, U, UPrime, F]), vec![R, F]);
+        assert_eq!(merge_top_turns(vec![U2, UPrime]), vec![U]);
+    }
+}
@@ -0,0 +1,150 @@
+//! Layer-by-layer 3x3 solving
+//!
+//! Chains the stage solvers the way the beginner course teaches: cross on
+//! the bottom, the four F2L pairs, then the last layer. Every stage is its
+//! own step, so a student can follow along stage by stage, and any 3x3
+//! state is solved quickly (unlike the depth-limited search).
+
+use crate::cube::{Cube, FaceName, Move, Orientation};
+use crate::solver::cross::solve_cross;
+use crate::solver::f2l::{is_f2l_pair_solved, solve_f2l_pair, F2lSlot};
+use crate::solver::last_layer::{is_f2l_solved, solve_last_layer};
+use crate::solver::rotations::ROTATIONS;
+use crate::solver::solution::{Solution, SolutionStep};
+use std::time::Instant;
+
+/// Solve a 3x3 stage by stage
+///
+/// A cube held with other colors on top and in front is first rotated to
+/// white up, green front. The cross is built on the bottom (yellow) face.
+///
+/// # Example
+/// ```
+/// use rubiks_cube_solver::cube::{Cube, Move};
+/// use rubiks_cube_solver::solver::solve_layer_by_layer;
+///
+/// let mut cube = Cube::new(3);
+/// cube.apply_moves(&[Move::R, Move::U, Move::F2, Move::D, Move::L]);
+/// let solution = solve_layer_by_layer(&cube).expect("Should solve");
+/// cube.apply_moves(&solution.all_moves());
+/// assert!(cube.is_solved());
+/// ```
+pub fn solve_layer_by_layer(cube: &Cube) -> Result<Solution, String> {
+    let start = Instant::now();
+    if cube.size() != 3 {
+        return Err("Cube must be size 3 for the layer-by-layer solver".to_string());
+    }
+    if cube.validate().is_err() {
+        return Err("Cube is not in a valid state".to_string());
+    }
+
+    let mut state = cube.clone();
+    let mut solution = Solution::new(vec![], 0);
+    let mut add = |state: &mut Cube, stage: Solution| {
+        state.apply_moves(&stage.all_moves());
+        solution = solution.concat(&Solution::new(stage.steps, 0));
+    };
+
+    let rotation = standard_grip(&state)?;
+    if !rotation.is_empty() {
+        let step = SolutionStep::new("Hold the cube with white on top and green in front", rotation);
+        add(&mut state, Solution::new(vec![step], 0));
+    }
+
+    let cross_color = state.get_face(FaceName::D).get(1, 1);
+    let cross = solve_cross(&state, cross_color)?;
+    add(&mut state, cross);
+    while !is_f2l_solved(&state) {
+        if let Some(pair) = next_pair(&state) {
+            add(&mut state, Solution::new(vec![pair], 0));
+            continue;
+        }
+        // A corner and edge stuck in two different slots: lift one out
+        let extraction = extraction(&state).ok_or("The first two layers could not be solved")?;
+        let step = SolutionStep::new("Take a pair piece out of the wrong slot", extraction);
+        add(&mut state, Solution::new(vec![step], 0));
+    }
+    // Pairs are inserted at front-right, so the cube may have been turned
+    let rotation = standard_grip(&state)?;
+    if !rotation.is_empty() {
+        let step = SolutionStep::new("Turn the cube back to green in front", rotation);
+        add(&mut state, Solution::new(vec![step], 0));
+    }
+    let last_layer = solve_last_layer(&state)?;
+    add(&mut state, last_layer);
+
+    solution.time_ms = start.elapsed().as_millis();
+    solution.method = Some("Layer by Layer".to_string());
+    Ok(solution)
+}
+
+/// Shortest rotation to white up, green front
+fn standard_grip(cube: &Cube) -> Result<Vec<Move>, String> {
+    ROTATIONS
+        .iter()
+        .find(|rotation| {
+            let mut turned = cube.clone();
+            turned.apply_moves(rotation);
+            Orientation::of(&turned) == Some(Orientation::standard())
+        })
+        .map(|rotation| rotation.to_vec())
+        .ok_or_else(|| "Cube centers are not a standard color scheme".to_string())
+}
+
+/// Insert the first unsolved pair the F2L cases cover
+fn next_pair(cube: &Cube) -> Option<SolutionStep> {
+    F2lSlot::ALL
+        .into_iter()
+        .filter(|slot| !is_f2l_pair_solved(cube, *slot))
+        .find_map(|slot| solve_f2l_pair(cube, slot).ok())
+        .map(|pair| pair.to_step())
+}
+
+/// A side turn, top turn and the side turn back after which some pair can
+/// be inserted, keeping the solved pairs
+fn extraction(cube: &Cube) -> Option<Vec<Move>> {
+    let solved: Vec<F2lSlot> = F2lSlot::ALL.into_iter().filter(|slot| is_f2l_pair_solved(cube, *slot)).collect();
+    let sides = [Move::R, Move::RPrime, Move::L, Move::LPrime, Move::F, Move::FPrime, Move::B, Move::BPrime];
+    sides.into_iter().flat_map(|side| [Move::U, Move::UPrime, Move::U2].map(|top| vec![side, top, side.inverse()])).find(
+        |moves| {
+            let mut state = cube.clone();
+            state.apply_moves(moves);
+            solved.iter().all(|slot| is_f2l_pair_solved(&state, *slot)) && next_pair(&state).is_some()
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_random_states_are_solved() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..10 {
+            let mut cube = Cube::random_state(3, &mut rng);
+            let solution = solve_layer_by_layer(&cube).unwrap();
+            assert!(solution.steps.len() >= 2);
+            assert!(solution.steps[0].description.contains("cross"));
+            cube.apply_moves(&solution.all_moves());
+            assert!(cube.is_solved());
+        }
+    }
+
+    #[test]
+    fn test_rotated_cube_is_turned_first() {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::Z, Move::R, Move::U]);
+        let solution = solve_layer_by_layer(&cube).unwrap();
+        assert!(solution.steps[0].description.contains("white on top"));
+        cube.apply_moves(&solution.all_moves());
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn test_rejects_other_sizes() {
+        assert!(solve_layer_by_layer(&Cube::new(2)).is_err());
+    }
+}
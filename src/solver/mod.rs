@@ -2,17 +2,69 @@
 //!
 //! This module provides solvers for:
 //! - 2x2 cubes (Depth-limited search)
-//! - 3x3 cubes (Beginner's layer-by-layer method via depth-limited search)
-//! - 4x4+ cubes (Reduction method - centers, edges, and parity)
+//! - 3x3 cubes (Beginner's layer-by-layer method, one stage of the beginner
+//!   course at a time)
+//! - 3x3 cross (optimal, IDA* over the four cross edges), on every color to
+//!   judge color neutrality
+//! - 3x3 F2L pairs (the 41 standard cases)
+//! - 3x3 last layer only (2-look OLL and PLL)
+//! - 3x3 layer by layer (cross, F2L pairs and last layer chained together)
+//! - 3x3 fewest moves (Kociemba's two-phase search, kept running for shorter
+//!   solutions until one is proven optimal or the time budget runs out)
+//! - 3x3 blindfold (Speffz memo, Old Pochmann corners, M2 edges)
+//! - 4x4+ cubes (Reduction method - commutator centers, edges, and parity)
+//! - 3x3 supercubes (center orientation restoration)
+//! - Pyraminx (fewest layer moves by meeting in the middle, then tips)
+//!
+//! The search solvers can be limited to a subset of moves (e.g. ⟨R, U⟩) with
+//! [`SolveOptions`], which also pick a [`RotationStyle`]: keep the solver's
+//! cube rotations, remove them, or add some to keep to R and U turns. With
+//! the `parallel` feature they search their first moves on several threads. Solutions carry per-move notes explaining what
+//! each run of moves is for. [`setup_moves`] works the other way round,
+//! finding moves that produce a given case from solved, and
+//! [`optimize_moves`] shortens any finished sequence after the fact.
 
 pub mod solution;
+pub mod options;
+pub mod rotations;
+pub(crate) mod parallel;
+pub mod explain;
+pub mod cross;
+pub mod color_neutral;
+pub mod f2l;
+pub mod last_layer;
+pub mod layer_by_layer;
+pub mod blind;
 pub mod two_by_two;
 pub mod beginner_3x3;
+pub mod two_phase;
+pub mod centers;
+pub mod edges;
 pub mod reduction;
 pub mod parity;
+pub mod supercube;
+pub mod setup;
+pub mod optimize;
+pub mod pyraminx;
 
-pub use solution::Solution;
-pub use two_by_two::solve_2x2;
+pub use solution::{MoveNote, Solution, SolutionStep};
+pub use options::SolveOptions;
+pub use rotations::{remove_rotations, RotationStyle};
+pub use explain::explain_moves;
+pub use cross::{is_cross_solved, solve_cross, solve_cross_with_options};
+pub use color_neutral::{analyze_cross_colors, analyze_scramble, CrossAnalysis, NeutralityReport};
+pub use f2l::{f2l_cases, is_f2l_pair_solved, solve_f2l_pair, F2lCase, F2lGroup, F2lPairSolution, F2lSlot};
+pub use last_layer::{is_f2l_solved, solve_last_layer};
+pub use layer_by_layer::solve_layer_by_layer;
+pub use blind::{memorize, solve_blind, speffz_letter, BlindMemo};
+pub use two_by_two::{solve_2x2, solve_2x2_with_options};
 pub use beginner_3x3::solve_3x3_beginner as solve_3x3;
-pub use reduction::{solve_centers, solve_edges};
-pub use parity::{resolve_parity, detect_oll_parity, detect_pll_parity, ParityType, ParitySolution};
+pub use beginner_3x3::{BeginnerStage, BeginnerStageSolution, Solution3x3Beginner};
+pub use beginner_3x3::solve_3x3_beginner_with_options as solve_3x3_with_options;
+pub use two_phase::{solve_3x3_optimal, OptimalSolution, SearchBudget};
+pub use reduction::{solve_centers, solve_edges, solve_reduction};
+pub use parity::{resolve_parity, detect_layer_parity, detect_oll_parity, detect_pll_parity, wing_layers, ParityType, ParitySolution};
+pub use supercube::{restore_center_orientation, solve_supercube_3x3};
+pub use setup::setup_moves;
+pub use optimize::{optimize_algorithm, optimize_moves, Optimization};
+pub use pyraminx::solve_pyraminx;
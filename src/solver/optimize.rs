@@ -0,0 +1,406 @@
+//! Post-hoc solution optimizer
+//!
+//! Takes any 3x3 move sequence, typed in by a student or produced by one
+//! of the solvers, and looks for local shortenings that keep its effect on
+//! the cube:
+//!
+//! - Cancellations: turns of the same layer merged, also across turns that
+//!   commute with them ("R L R'" is "L")
+//! - Loops: stretches of moves that put the cube back the way it was
+//! - Insertions: a short block of existing moves moved elsewhere in the
+//!   sequence, where it cancels with its new neighbours
+//! - Shorter segments: up to [`MAX_WINDOW`] moves replaced by at most
+//!   [`MAX_REPLACEMENT`] face turns with the same effect, found by a bounded
+//!   search
+//!
+//! Every change is checked on the simulator, so the optimized sequence
+//! always does exactly what the original did.
+
+use crate::cube::notation::{invert_moves, parse_algorithm, ParsedMove};
+use crate::cube::{Color, Cube, FaceName, Move};
+use crate::solver::rotations::{combine_turns, TURNS};
+use std::collections::HashMap;
+
+/// Longest segment replaced by a shorter equivalent
+pub const MAX_WINDOW: usize = 7;
+
+/// Longest replacement the bounded search finds
+pub const MAX_REPLACEMENT: usize = 3;
+
+/// Longest block of moves tried elsewhere
+const MAX_BLOCK: usize = 2;
+
+/// Furthest a block is moved
+const MAX_SHIFT: usize = 6;
+
+/// Face turns the bounded search uses
+const FACE_TURNS: [Move; 18] = [
+    Move::R, Move::RPrime, Move::R2,
+    Move::L, Move::LPrime, Move::L2,
+    Move::U, Move::UPrime, Move::U2,
+    Move::D, Move::DPrime, Move::D2,
+    Move::F, Move::FPrime, Move::F2,
+    Move::B, Move::BPrime, Move::B2,
+];
+
+/// How a shortening was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShorteningKind {
+    Cancellation,
+    Loop,
+    Insertion,
+    ShorterSegment,
+}
+
+impl ShorteningKind {
+    /// Short display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShorteningKind::Cancellation => "Cancellation",
+            ShorteningKind::Loop => "Loop",
+            ShorteningKind::Insertion => "Insertion",
+            ShorteningKind::ShorterSegment => "Shorter segment",
+        }
+    }
+}
+
+/// One change made by the optimizer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shortening {
+    pub kind: ShorteningKind,
+    /// What was changed, e.g. "M' x R -> L"
+    pub description: String,
+    pub moves_saved: usize,
+}
+
+/// A sequence and its optimized form
+#[derive(Debug, Clone, PartialEq)]
+pub struct Optimization {
+    pub original: Vec<Move>,
+    pub optimized: Vec<Move>,
+    /// Changes in the order they were made
+    pub shortenings: Vec<Shortening>,
+}
+
+impl Optimization {
+    /// Moves saved in total
+    pub fn moves_saved(&self) -> usize {
+        self.original.len() - self.optimized.len()
+    }
+
+    /// Report of the changes, one per line, ending with the total
+    pub fn summary(&self) -> String {
+        let mut lines: Vec<String> = self
+            .shortenings
+            .iter()
+            .map(|s| format!("{}: {} (-{})", s.kind.label(), s.description, s.moves_saved))
+            .collect();
+        lines.push(format!(
+            "{} -> {} moves ({} saved)",
+            self.original.len(),
+            self.optimized.len(),
+            self.moves_saved()
+        ));
+        lines.join("\n")
+    }
+}
+
+/// Moves as notation
+fn notation(moves: &[Move]) -> String {
+    if moves.is_empty() {
+        return "nothing".to_string();
+    }
+    moves.iter().map(|m| m.to_notation()).collect::<Vec<_>>().join(" ")
+}
+
+/// Every sticker of a 3x3 after `moves`, as a lookup key
+///
+/// Each piece of a 3x3 has its own colors, so two sequences that leave a
+/// solved cube the same way have the same effect on any cube.
+fn effect(moves: &[Move]) -> Vec<Color> {
+    let mut cube = Cube::new(3);
+    cube.apply_moves(moves);
+    FaceName::all()
+        .iter()
+        .flat_map(|face| {
+            let face = cube.get_face(*face);
+            (0..3).flat_map(move |row| (0..3).map(move |col| face.get(row, col)))
+        })
+        .collect()
+}
+
+/// Axis a layer turn is on (R, L and M share one), None for rotations
+fn axis(mv: Move) -> Option<usize> {
+    TURNS.iter().position(|turns| turns.contains(&mv)).map(|layer| [0, 0, 1, 1, 2, 2, 0, 1, 2][layer])
+}
+
+/// Merge turns of the same layer, looking past turns on the same axis
+fn cancel(moves: &[Move]) -> (Vec<Move>, Vec<Shortening>) {
+    let mut kept: Vec<Move> = Vec::new();
+    let mut shortenings = Vec::new();
+    for &mv in moves {
+        let mut merged = false;
+        for at in (0..kept.len()).rev() {
+            if axis(kept[at]).is_none() || axis(kept[at]) != axis(mv) {
+                break;
+            }
+            if let Some(combined) = combine_turns(kept[at], mv) {
+                let first = kept.remove(at);
+                let saved = if combined.is_some() { 1 } else { 2 };
+                if let Some(combined) = combined {
+                    kept.insert(at, combined);
+                }
+                shortenings.push(Shortening {
+                    kind: ShorteningKind::Cancellation,
+                    description: format!("{} {} -> {}", first.to_notation(), mv.to_notation(), notation(&Vec::from_iter(combined))),
+                    moves_saved: saved,
+                });
+                merged = true;
+                break;
+            }
+        }
+        if !merged {
+            kept.push(mv);
+        }
+    }
+    (kept, shortenings)
+}
+
+/// Remove the longest stretch of moves that changes nothing
+fn remove_loop(moves: &[Move]) -> Option<(Vec<Move>, Shortening)> {
+    let mut seen: HashMap<Vec<Color>, usize> = HashMap::new();
+    let mut best: Option<(usize, usize)> = None;
+    for end in 0..=moves.len() {
+        let state = effect(&moves[..end]);
+        match seen.get(&state) {
+            Some(&start) if best.is_none_or(|(s, e)| end - start > e - s) => best = Some((start, end)),
+            Some(_) => {}
+            None => {
+                seen.insert(state, end);
+            }
+        }
+    }
+    let (start, end) = best?;
+    let mut shorter = moves[..start].to_vec();
+    shorter.extend_from_slice(&moves[end..]);
+    let shortening = Shortening {
+        kind: ShorteningKind::Loop,
+        description: format!("{} -> nothing", notation(&moves[start..end])),
+        moves_saved: end - start,
+    };
+    Some((shorter, shortening))
+}
+
+/// Move a short block elsewhere if it then cancels with its new neighbours
+///
+/// A block jumping over moves `X` is rewritten for its new spot: `X B` is
+/// `(X B X') X`, and `B X` is `X (X' B X)`. That is only worth it when the
+/// rewritten block is a few face turns, e.g. when `B` commutes with `X`, or
+/// `X` is a cube rotation.
+fn insert_block(moves: &[Move], table: &HashMap<Vec<Color>, Vec<Move>>) -> Option<(Vec<Move>, Shortening)> {
+    for len in 1..=MAX_BLOCK.min(moves.len()) {
+        for start in 0..=moves.len() - len {
+            let end = start + len;
+            let block = &moves[start..end];
+            let lowest = start.saturating_sub(MAX_SHIFT);
+            let highest = (end + MAX_SHIFT).min(moves.len());
+            let targets = (lowest..start).chain(end + 1..=highest);
+            for at in targets {
+                // The moves jumped over, and the block as it reads at its new spot
+                let (jumped, conjugated) = if at < start {
+                    let jumped = &moves[at..start];
+                    (jumped, [jumped, block, &invert_moves(jumped)].concat())
+                } else {
+                    let jumped = &moves[end..at];
+                    (jumped, [&invert_moves(jumped), block, jumped].concat())
+                };
+                let Some(rewritten) = table.get(&effect(&conjugated)) else {
+                    continue;
+                };
+                let candidate = if at < start {
+                    [&moves[..at], rewritten, jumped, &moves[end..]].concat()
+                } else {
+                    [&moves[..start], jumped, rewritten, &moves[at..]].concat()
+                };
+                let (cancelled, _) = cancel(&candidate);
+                if cancelled.len() < moves.len() {
+                    let direction = if at < start { "earlier" } else { "later" };
+                    let shortening = Shortening {
+                        kind: ShorteningKind::Insertion,
+                        description: format!(
+                            "{} moved {} {} as {}",
+                            notation(block),
+                            jumped.len(),
+                            direction,
+                            notation(rewritten)
+                        ),
+                        moves_saved: moves.len() - cancelled.len(),
+                    };
+                    return Some((cancelled, shortening));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Shortest face-turn sequences of up to [`MAX_REPLACEMENT`] moves, by effect
+fn replacement_table() -> HashMap<Vec<Color>, Vec<Move>> {
+    let mut table = HashMap::new();
+    let mut frontier: Vec<Vec<Move>> = vec![Vec::new()];
+    table.insert(effect(&[]), Vec::new());
+    for _ in 0..MAX_REPLACEMENT {
+        let mut next = Vec::new();
+        for sequence in &frontier {
+            for mv in FACE_TURNS {
+                // Never turn the same face twice in a row
+                if sequence.last().is_some_and(|last| combine_turns(*last, mv).is_some()) {
+                    continue;
+                }
+                let mut longer = sequence.clone();
+                longer.push(mv);
+                if let std::collections::hash_map::Entry::Vacant(entry) = table.entry(effect(&longer)) {
+                    entry.insert(longer.clone());
+                    next.push(longer);
+                }
+            }
+        }
+        frontier = next;
+    }
+    table
+}
+
+/// Replace a segment with a shorter one that does the same
+fn replace_segment(moves: &[Move], table: &HashMap<Vec<Color>, Vec<Move>>) -> Option<(Vec<Move>, Shortening)> {
+    for len in (2..=MAX_WINDOW.min(moves.len())).rev() {
+        for start in 0..=moves.len() - len {
+            let segment = &moves[start..start + len];
+            let Some(shorter) = table.get(&effect(segment)).filter(|shorter| shorter.len() < len) else {
+                continue;
+            };
+            let mut replaced = moves[..start].to_vec();
+            replaced.extend_from_slice(shorter);
+            replaced.extend_from_slice(&moves[start + len..]);
+            let shortening = Shortening {
+                kind: ShorteningKind::ShorterSegment,
+                description: describe_replacement(segment, shorter),
+                moves_saved: len - shorter.len(),
+            };
+            return Some((replaced, shortening));
+        }
+    }
+    None
+}
+
+/// "before -> after", leaving out the moves both start and end with
+fn describe_replacement(before: &[Move], after: &[Move]) -> String {
+    let prefix = before.iter().zip(after).take_while(|(a, b)| a == b).count();
+    let (before, after) = (&before[prefix..], &after[prefix..]);
+    let suffix = before.iter().rev().zip(after.iter().rev()).take_while(|(a, b)| a == b).count();
+    format!("{} -> {}", notation(&before[..before.len() - suffix]), notation(&after[..after.len() - suffix]))
+}
+
+/// Shorten `moves` without changing what they do to a 3x3
+///
+/// The passes are repeated, cheapest first, until none of them finds
+/// anything more.
+pub fn optimize_moves(moves: &[Move]) -> Optimization {
+    let table = replacement_table();
+    let (mut current, mut shortenings) = cancel(moves);
+    loop {
+        let found = remove_loop(&current)
+            .or_else(|| replace_segment(&current, &table))
+            .or_else(|| insert_block(&current, &table));
+        let Some((shorter, shortening)) = found else {
+            break;
+        };
+        let (cancelled, cancellations) = cancel(&shorter);
+        current = cancelled;
+        shortenings.push(shortening);
+        shortenings.extend(cancellations);
+    }
+    Optimization { original: moves.to_vec(), optimized: current, shortenings }
+}
+
+/// Parse and optimize a 3x3 algorithm such as "R U R' U'"
+///
+/// Wide and layer moves are not supported.
+pub fn optimize_algorithm(algorithm: &str) -> Result<Optimization, String> {
+    let moves = parse_algorithm(algorithm)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|parsed| match parsed {
+            ParsedMove::Basic(mv) => Ok(mv),
+            other => Err(format!("{} is not a 3x3 move", other.to_notation())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(optimize_moves(&moves))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn optimize(algorithm: &str) -> Optimization {
+        let optimization = optimize_algorithm(algorithm).unwrap();
+        assert_eq!(effect(&optimization.optimized), effect(&optimization.original));
+        optimization
+    }
+
+    #[test]
+    fn test_cancellations() {
+        let optimization = optimize("R U U' R");
+        assert_eq!(notation(&optimization.optimized), "R2");
+        assert_eq!(optimization.moves_saved(), 3);
+        assert!(optimization.shortenings.iter().all(|s| s.kind == ShorteningKind::Cancellation));
+
+        // Across a commuting turn on the same axis
+        let optimization = optimize("R L R'");
+        assert_eq!(notation(&optimization.optimized), "L");
+        assert_eq!(optimization.shortenings[0].description, "R R' -> nothing");
+    }
+
+    #[test]
+    fn test_loops_are_removed() {
+        // Six sexy moves do nothing
+        let sexy = "R U R' U' ".repeat(6);
+        let optimization = optimize(&format!("F {} F'", sexy));
+        assert!(optimization.optimized.is_empty());
+        assert_eq!(optimization.shortenings[0].kind, ShorteningKind::Loop);
+        assert_eq!(optimization.moves_saved(), 26);
+    }
+
+    #[test]
+    fn test_insertion_moves_a_block() {
+        // After y, L turns the face that was in front, so F can jump the
+        // rotation and cancel with it
+        let optimization = optimize("F y L");
+        assert_eq!(notation(&optimization.optimized), "y");
+        assert_eq!(optimization.shortenings[0].kind, ShorteningKind::Insertion);
+        assert_eq!(optimization.shortenings[0].description, "F moved 1 later as L'");
+        assert_eq!(optimization.moves_saved(), 2);
+    }
+
+    #[test]
+    fn test_shorter_segment() {
+        // M' x turns L and undoes R
+        let optimization = optimize("U M' x R U");
+        assert_eq!(notation(&optimization.optimized), "U L U");
+        assert_eq!(optimization.shortenings[0].kind, ShorteningKind::ShorterSegment);
+        assert_eq!(optimization.shortenings[0].description, "M' x R -> L");
+
+        let table = replacement_table();
+        let (replaced, shortening) = replace_segment(&[Move::R, Move::L, Move::RPrime, Move::U], &table).unwrap();
+        assert_eq!(replaced, vec![Move::L, Move::U]);
+        assert_eq!(shortening.moves_saved, 2);
+    }
+
+    #[test]
+    fn test_optimal_sequences_are_kept() {
+        let optimization = optimize("R U R' U'");
+        assert_eq!(optimization.optimized, optimization.original);
+        assert!(optimization.shortenings.is_empty());
+        assert!(optimization.summary().ends_with("4 -> 4 moves (0 saved)"));
+        assert!(optimize_algorithm("Rw U").is_err());
+    }
+}
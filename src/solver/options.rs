@@ -0,0 +1,142 @@
+//! Options shared by the search solvers
+//!
+//! Restricting the move set makes the solvers find solutions in a subgroup,
+//! e.g. ⟨R, U⟩ ("2-gen"): useful for last-layer drills and for solutions
+//! that can be done with one hand. Scrambles made with the same moves can
+//! always be solved with them, given enough depth.
+//!
+//! The options also say how the solution handles cube rotations; see
+//! [`RotationStyle`].
+
+use crate::cube::{FaceName, Move};
+use crate::solver::rotations::RotationStyle;
+
+/// How a search solver may solve the cube
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SolveOptions {
+    /// Moves the solution may use; `None` lets the solver pick its own
+    pub allowed_moves: Option<Vec<Move>>,
+    /// How the solution handles cube rotations
+    pub rotations: RotationStyle,
+}
+
+impl SolveOptions {
+    /// Options with no restrictions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only use the given moves
+    pub fn with_allowed_moves(mut self, moves: Vec<Move>) -> Self {
+        self.allowed_moves = Some(moves);
+        self
+    }
+
+    /// Only turn the given faces (quarter and half turns)
+    pub fn with_allowed_faces(self, faces: &[FaceName]) -> Self {
+        self.with_allowed_moves(face_turns(faces))
+    }
+
+    /// Handle cube rotations in the solution in `style`
+    pub fn with_rotations(mut self, style: RotationStyle) -> Self {
+        self.rotations = style;
+        self
+    }
+
+    /// ⟨R, U⟩ only
+    pub fn two_gen() -> Self {
+        Self::new().with_allowed_faces(&[FaceName::R, FaceName::U])
+    }
+
+    /// ⟨R, U, F⟩ only
+    pub fn three_gen() -> Self {
+        Self::new().with_allowed_faces(&[FaceName::R, FaceName::U, FaceName::F])
+    }
+
+    /// Whether a solution may use `mv`
+    pub fn allows(&self, mv: Move) -> bool {
+        self.allowed_moves.as_ref().is_none_or(|moves| moves.contains(&mv))
+    }
+
+    /// Moves to search: the allowed moves, or `defaults` without a restriction
+    pub(crate) fn search_moves(&self, defaults: &[Move]) -> Result<Vec<Move>, String> {
+        match &self.allowed_moves {
+            Some(moves) if moves.is_empty() => Err("No moves are allowed".to_string()),
+            Some(moves) => Ok(moves.clone()),
+            None => Ok(defaults.to_vec()),
+        }
+    }
+
+    /// Rewrite a found solution for the rotation style
+    pub(crate) fn finish(&self, moves: Vec<Move>) -> Vec<Move> {
+        match self.rotations {
+            RotationStyle::Keep => moves,
+            style => style.apply(&moves),
+        }
+    }
+
+    /// Short label for the restriction, e.g. "⟨R, U⟩"
+    pub fn describe(&self) -> Option<String> {
+        let moves = self.allowed_moves.as_ref()?;
+        let mut faces: Vec<String> = Vec::new();
+        for mv in moves {
+            let notation = mv.to_notation();
+            let face = notation.trim_end_matches(['\'', '2']).to_string();
+            if !faces.contains(&face) {
+                faces.push(face);
+            }
+        }
+        Some(format!("⟨{}⟩", faces.join(", ")))
+    }
+}
+
+/// Quarter and half turns of some faces
+pub(crate) fn face_turns(faces: &[FaceName]) -> Vec<Move> {
+    faces
+        .iter()
+        .flat_map(|face| match face {
+            FaceName::U => [Move::U, Move::UPrime, Move::U2],
+            FaceName::D => [Move::D, Move::DPrime, Move::D2],
+            FaceName::F => [Move::F, Move::FPrime, Move::F2],
+            FaceName::B => [Move::B, Move::BPrime, Move::B2],
+            FaceName::L => [Move::L, Move::LPrime, Move::L2],
+            FaceName::R => [Move::R, Move::RPrime, Move::R2],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unrestricted_allows_everything() {
+        let options = SolveOptions::new();
+        assert!(options.allows(Move::B2));
+        assert_eq!(options.search_moves(&[Move::R]).unwrap(), vec![Move::R]);
+        assert_eq!(options.describe(), None);
+    }
+
+    #[test]
+    fn test_two_gen() {
+        let options = SolveOptions::two_gen();
+        assert!(options.allows(Move::RPrime) && options.allows(Move::U2));
+        assert!(!options.allows(Move::F));
+        assert_eq!(options.search_moves(&[Move::F]).unwrap().len(), 6);
+        assert_eq!(options.describe().unwrap(), "⟨R, U⟩");
+    }
+
+    #[test]
+    fn test_rotation_style() {
+        assert_eq!(SolveOptions::new().rotations, RotationStyle::Keep);
+        let options = SolveOptions::two_gen().with_rotations(RotationStyle::Remove);
+        assert_eq!(options.finish(vec![Move::Y, Move::F]), vec![Move::RPrime]);
+        assert_eq!(options.describe().unwrap(), "⟨R, U⟩");
+    }
+
+    #[test]
+    fn test_nothing_allowed() {
+        let options = SolveOptions::new().with_allowed_moves(vec![]);
+        assert!(options.search_moves(&[Move::R]).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+//! Root-split search for the depth-first solvers
+//!
+//! The searches try each first move of a solution in turn. With the
+//! `parallel` feature those root branches run on rayon's thread pool
+//! instead. The branches share the best solution found so far: a branch
+//! gives up as soon as a branch earlier in move order has found one, so
+//! the result is the same solution the one-by-one search returns.
+//!
+//! Searches that keep improving on their first solution, like the
+//! two-phase search, run every branch with [`map_roots`] instead and pick
+//! from the results themselves.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// One root branch of a search
+pub(crate) struct Branch<'a> {
+    index: usize,
+    /// Index of the earliest branch with a solution so far
+    best: &'a AtomicUsize,
+}
+
+impl Branch<'_> {
+    /// Which root branch this is
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Whether an earlier branch has already found a solution
+    ///
+    /// Nothing this branch finds can be used any more, so the search
+    /// should stop.
+    pub(crate) fn is_beaten(&self) -> bool {
+        self.best.load(Ordering::Relaxed) < self.index
+    }
+}
+
+/// Search `branches` root branches and return the solution of the first
+/// branch, in order, that has one
+pub(crate) fn search_roots<T, F>(branches: usize, search: F) -> Option<T>
+where
+    T: Send,
+    F: Fn(&Branch) -> Option<T> + Sync,
+{
+    let best = AtomicUsize::new(usize::MAX);
+    let run = |index: usize| {
+        let found = search(&Branch { index, best: &best });
+        if found.is_some() {
+            best.fetch_min(index, Ordering::Relaxed);
+        }
+        found
+    };
+
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        (0..branches).into_par_iter().find_map_first(run)
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        (0..branches).find_map(run)
+    }
+}
+
+/// Search all `branches` root branches and return their results in
+/// branch order
+pub(crate) fn map_roots<T, F>(branches: usize, search: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        (0..branches).into_par_iter().map(&search).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        (0..branches).map(search).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_branch_with_a_solution_wins() {
+        let found = search_roots(6, |branch| (branch.index() % 3 == 2).then_some(branch.index()));
+        assert_eq!(found, Some(2));
+        assert_eq!(search_roots(6, |_| None::<usize>), None);
+    }
+
+    #[test]
+    fn test_later_branches_see_earlier_solutions() {
+        // Branch 0 always finds a solution, so later branches that see it
+        // must give up rather than return their own
+        let found = search_roots(8, |branch| {
+            if branch.index() == 0 {
+                return Some(0);
+            }
+            while !branch.is_beaten() {
+                std::thread::yield_now();
+            }
+            Some(branch.index())
+        });
+        assert_eq!(found, Some(0));
+    }
+
+    #[test]
+    fn test_every_branch_is_mapped_in_order() {
+        assert_eq!(map_roots(5, |index| index * 2), vec![0, 2, 4, 6, 8]);
+    }
+}
@@ -8,9 +8,13 @@
 //! 1. OLL Parity (Orientation of Last Layer) - Single flipped edge on last layer
 //! 2. PLL Parity (Permutation of Last Layer) - Two edges swapped instead of four
 //!
+//! Each inner edge layer of a big cube (two on a 6x6 or 7x7) can have its own
+//! parity, so detection reads every layer as a separate 3x3 and each fix only
+//! turns the layer it fixes, using wide moves of the matching depth.
+//!
 //! This module implements R5.5: 4x4+ parity handling
 
-use crate::cube::{Cube, Move, FaceName};
+use crate::cube::{Color, Cube, Direction, FaceName, Move, ParsedMove, WideFace, WideMove};
 use crate::solver::solution::{Solution, SolutionStep};
 use std::time::Instant;
 
@@ -32,8 +36,8 @@ pub enum ParityType {
 pub struct ParitySolution {
     /// Type of parity detected
     pub parity_type: ParityType,
-    /// List of moves to resolve parity
-    pub moves: Vec<Move>,
+    /// Moves to resolve parity, including inner-layer wide moves
+    pub moves: Vec<ParsedMove>,
     /// Time taken to find the solution (in milliseconds)
     pub time_ms: u128,
     /// Step-by-step breakdown
@@ -42,7 +46,7 @@ pub struct ParitySolution {
 
 impl ParitySolution {
     /// Create a new parity solution
-    pub fn new(parity_type: ParityType, moves: Vec<Move>, time_ms: u128, steps: Vec<SolutionStep>) -> Self {
+    pub fn new(parity_type: ParityType, moves: Vec<ParsedMove>, time_ms: u128, steps: Vec<SolutionStep>) -> Self {
         Self { parity_type, moves, time_ms, steps }
     }
 
@@ -51,6 +55,11 @@ impl ParitySolution {
         self.moves.len()
     }
 
+    /// Apply the parity fixes to a cube
+    pub fn apply_to(&self, cube: &mut Cube) {
+        cube.apply_parsed_moves(&self.moves);
+    }
+
     /// Convert to generic Solution type
     pub fn to_solution(&self) -> Solution {
         let method = match self.parity_type {
@@ -63,184 +72,296 @@ impl ParitySolution {
     }
 }
 
-/// Detect OLL parity on a 4x4+ cube
-///
-/// OLL parity occurs when there's a single flipped edge on the last layer after
-/// solving the rest of the cube. This is impossible on a 3x3 but can happen on 4x4+.
+/// OLL parity algorithm in standard notation; lowercase letters turn only
+/// the inner layer being fixed
+const OLL_PARITY: &str = "r2 B2 U2 l U2 r' U2 r U2 F2 r F2 l' B2 r2";
+
+/// PLL parity algorithm in standard notation; `Uw` turns the upper half of
+/// the cube
+const PLL_PARITY: &str = "r2 U2 r2 Uw2 r2 Uw2";
+
+/// Edge slots as sticker pairs in 3x3 coordinates, where 1 stands for the
+/// layer being checked; the first sticker is on U/D, or F/B for the middle
+/// layer
+pub(crate) const EDGE_SLOTS: [[(FaceName, usize, usize); 2]; 12] = [
+    [(FaceName::U, 0, 1), (FaceName::B, 0, 1)],
+    [(FaceName::U, 1, 2), (FaceName::R, 0, 1)],
+    [(FaceName::U, 2, 1), (FaceName::F, 0, 1)],
+    [(FaceName::U, 1, 0), (FaceName::L, 0, 1)],
+    [(FaceName::D, 0, 1), (FaceName::F, 2, 1)],
+    [(FaceName::D, 1, 2), (FaceName::R, 2, 1)],
+    [(FaceName::D, 2, 1), (FaceName::B, 2, 1)],
+    [(FaceName::D, 1, 0), (FaceName::L, 2, 1)],
+    [(FaceName::F, 1, 2), (FaceName::R, 1, 0)],
+    [(FaceName::F, 1, 0), (FaceName::L, 1, 2)],
+    [(FaceName::B, 1, 0), (FaceName::R, 1, 2)],
+    [(FaceName::B, 1, 2), (FaceName::L, 1, 0)],
+];
+
+/// Corner slots as sticker triples in 3x3 coordinates
+pub(crate) const CORNER_SLOTS: [[(FaceName, usize, usize); 3]; 8] = [
+    [(FaceName::U, 0, 0), (FaceName::L, 0, 0), (FaceName::B, 0, 2)],
+    [(FaceName::U, 0, 2), (FaceName::B, 0, 0), (FaceName::R, 0, 2)],
+    [(FaceName::U, 2, 2), (FaceName::R, 0, 0), (FaceName::F, 0, 2)],
+    [(FaceName::U, 2, 0), (FaceName::F, 0, 0), (FaceName::L, 0, 2)],
+    [(FaceName::D, 0, 0), (FaceName::L, 2, 2), (FaceName::F, 2, 0)],
+    [(FaceName::D, 0, 2), (FaceName::F, 2, 2), (FaceName::R, 2, 0)],
+    [(FaceName::D, 2, 2), (FaceName::R, 2, 2), (FaceName::B, 2, 0)],
+    [(FaceName::D, 2, 0), (FaceName::B, 2, 2), (FaceName::L, 2, 0)],
+];
+
+/// Number of inner edge layers that can have parity on their own
 ///
-/// Detection strategy:
-/// - Check if the last layer has one edge that appears flipped
-/// - On 4x4, this means one edge pair has inverted colors compared to adjacent centers
-///
-/// # Arguments
-/// * `cube` - The cube to check for OLL parity
-///
-/// # Returns
-/// * `true` if OLL parity is detected, `false` otherwise
-pub fn detect_oll_parity(cube: &Cube) -> bool {
-    let size = cube.size();
-    if size < 4 {
-        return false; // Parity only applies to 4x4+
-    }
-
-    // For 4x4+ cubes, we need to check if there's an odd number of flipped edges
-    // This is a simplified detection - full implementation would check edge orientations
+/// Layer 1 is the first layer inside a face; on odd cubes the middle layer
+/// (the 3x3-like center edge) never has parity and is not counted. A 4x4 or
+/// 5x5 has one such layer, a 6x6 or 7x7 two.
+pub fn wing_layers(size: usize) -> usize {
+    size.saturating_sub(2) / 2
+}
 
-    // Check edges on the Up face
-    let up_face = cube.get_face(FaceName::U);
-    let center_color = up_face.get(1, 1); // Get center color (4x4 has 2x2 center, so position 1,1)
+/// Color of a sticker given in 3x3 coordinates, reading `layer` for the
+/// middle row or column
+pub(crate) fn sticker(cube: &Cube, (face, row, col): (FaceName, usize, usize), layer: usize) -> Color {
+    let n = cube.size();
+    let index = |i: usize| match i {
+        0 => 0,
+        1 => layer,
+        _ => n - 1,
+    };
+    cube.get_face(face).get(index(row), index(col))
+}
 
-    // Count edges that don't match the pattern
-    let mut mismatched_edges = 0;
+/// Color of a face's center
+pub(crate) fn center(cube: &Cube, face: FaceName) -> Color {
+    let n = cube.size();
+    cube.get_face(face).get(n / 2, n / 2)
+}
 
-    // Check top edge of Up face
-    for col in 1..(size - 1) {
-        let edge_color = up_face.get(0, col);
-        if edge_color != center_color {
-            mismatched_edges += 1;
+/// Which slot each piece belongs in, or `None` if a piece matches no slot
+pub(crate) fn permutation<const N: usize>(cube: &Cube, slots: &[[(FaceName, usize, usize); N]], layer: usize) -> Option<Vec<usize>> {
+    let sorted = |mut colors: Vec<Color>| {
+        colors.sort_by_key(|c| *c as u8);
+        colors
+    };
+    let homes: Vec<Vec<Color>> = slots
+        .iter()
+        .map(|slot| sorted(slot.iter().map(|(face, _, _)| center(cube, *face)).collect()))
+        .collect();
+    let mut perm = Vec::with_capacity(slots.len());
+    for slot in slots {
+        let colors = sorted(slot.iter().map(|s| sticker(cube, *s, layer)).collect());
+        let home = homes.iter().position(|h| *h == colors)?;
+        if perm.contains(&home) {
+            return None;
         }
+        perm.push(home);
     }
+    Some(perm)
+}
 
-    // Check bottom edge of Up face
-    for col in 1..(size - 1) {
-        let edge_color = up_face.get(size - 1, col);
-        if edge_color != center_color {
-            mismatched_edges += 1;
+/// Whether a permutation is odd
+pub(crate) fn is_odd(perm: &[usize]) -> bool {
+    let mut seen = vec![false; perm.len()];
+    let mut swaps = 0;
+    for start in 0..perm.len() {
+        let mut i = start;
+        let mut length = 0;
+        while !seen[i] {
+            seen[i] = true;
+            i = perm[i];
+            length += 1;
         }
+        swaps += length.max(1) - 1;
     }
+    swaps % 2 == 1
+}
 
-    // Check left edge of Up face
-    for row in 1..(size - 1) {
-        let edge_color = up_face.get(row, 0);
-        if edge_color != center_color {
-            mismatched_edges += 1;
-        }
+/// Number of edges, read at `layer`, that are flipped
+pub(crate) fn flipped_edges(cube: &Cube, layer: usize) -> usize {
+    let up_down = [center(cube, FaceName::U), center(cube, FaceName::D)];
+    let front_back = [center(cube, FaceName::F), center(cube, FaceName::B)];
+    EDGE_SLOTS
+        .iter()
+        .filter(|[first, second]| {
+            let (first, second) = (sticker(cube, *first, layer), sticker(cube, *second, layer));
+            let oriented =
+                up_down.contains(&first) || (!up_down.contains(&second) && front_back.contains(&first));
+            !oriented
+        })
+        .count()
+}
+
+/// Detect parity in one inner edge layer of a reduced 4x4+ cube
+///
+/// Reads the cube as a 3x3 made of the corners, the centers and the edge
+/// stickers of `layer` (1 = the first layer inside a face). OLL parity is an
+/// odd number of flipped edges; PLL parity is an edge permutation whose
+/// parity differs from the corners'. Both are impossible on a real 3x3.
+///
+/// Returns `ParityType::None` if the layer cannot be read as a 3x3 (the
+/// centers are unsolved or the edges unpaired). On odd cubes the middle
+/// edges fix the permutation, so only OLL parity (a flipped pair of edge
+/// pieces around a middle edge) can occur.
+pub fn detect_layer_parity(cube: &Cube, layer: usize) -> ParityType {
+    let size = cube.size();
+    if size < 4 || layer == 0 || layer > wing_layers(size) {
+        return ParityType::None;
+    }
+    let (Some(edges), Some(corners)) = (
+        permutation(cube, &EDGE_SLOTS, layer),
+        permutation(cube, &CORNER_SLOTS, layer),
+    ) else {
+        return ParityType::None;
+    };
+    // On odd cubes the layer must be paired with the middle edges
+    if size % 2 == 1 && permutation(cube, &EDGE_SLOTS, size / 2).as_ref() != Some(&edges) {
+        return ParityType::None;
     }
 
-    // Check right edge of Up face
-    for row in 1..(size - 1) {
-        let edge_color = up_face.get(row, size - 1);
-        if edge_color != center_color {
-            mismatched_edges += 1;
-        }
+    let oll = flipped_edges(cube, layer) % 2 == 1;
+    let pll = is_odd(&edges) != is_odd(&corners);
+    match (oll, pll) {
+        (false, false) => ParityType::None,
+        (true, false) => ParityType::OllParity,
+        (false, true) => ParityType::PllParity,
+        (true, true) => ParityType::Both,
     }
+}
 
-    // If we have an odd number of edge pieces in wrong orientation, likely OLL parity
-    // This is simplified - a full implementation would check actual edge orientations
-    mismatched_edges % 2 == 1
+/// Detect OLL parity on a 4x4+ cube
+///
+/// OLL parity occurs when there's a single flipped edge on the last layer after
+/// solving the rest of the cube. This is impossible on a 3x3 but can happen on 4x4+.
+/// Each inner edge layer is checked on its own (see [`detect_layer_parity`]).
+///
+/// # Arguments
+/// * `cube` - The cube to check for OLL parity
+///
+/// # Returns
+/// * `true` if OLL parity is detected in any layer, `false` otherwise
+pub fn detect_oll_parity(cube: &Cube) -> bool {
+    (1..=wing_layers(cube.size()))
+        .any(|layer| matches!(detect_layer_parity(cube, layer), ParityType::OllParity | ParityType::Both))
 }
 
 /// Detect PLL parity on a 4x4+ cube
 ///
 /// PLL parity occurs when two edges need to be swapped instead of the typical four-edge
 /// permutation. This is impossible on a 3x3 but can happen on 4x4+.
-///
-/// Detection strategy:
-/// - After solving everything except the last layer permutation
-/// - Check if only two edges need swapping (instead of 0 or 4)
+/// Each inner edge layer is checked on its own (see [`detect_layer_parity`]).
 ///
 /// # Arguments
 /// * `cube` - The cube to check for PLL parity
 ///
 /// # Returns
-/// * `true` if PLL parity is detected, `false` otherwise
+/// * `true` if PLL parity is detected in any layer, `false` otherwise
 pub fn detect_pll_parity(cube: &Cube) -> bool {
-    let size = cube.size();
-    if size < 4 {
-        return false; // Parity only applies to 4x4+
-    }
-
-    // For PLL parity detection, we check if edges are correctly positioned
-    // but only two are swapped (which is impossible on 3x3)
-
-    // Simplified detection: Check if edges match their adjacent centers
-    let _up_face = cube.get_face(FaceName::U);
-    let front_face = cube.get_face(FaceName::F);
-    let right_face = cube.get_face(FaceName::R);
-    let back_face = cube.get_face(FaceName::B);
-    let left_face = cube.get_face(FaceName::L);
-
-    // Get center colors for comparison
-    let front_center = front_face.get(1, 1);
-    let right_center = right_face.get(1, 1);
-    let back_center = back_face.get(1, 1);
-    let left_center = left_face.get(1, 1);
-
-    // Check if edges match their centers
-    let mut mismatched_edge_pairs = 0;
-
-    // Check front edge of Up face vs front center
-    let front_edge_matches = (1..(size-1)).all(|col| {
-        front_face.get(0, col) == front_center
-    });
-    if !front_edge_matches {
-        mismatched_edge_pairs += 1;
-    }
-
-    // Check right edge of Up face vs right center
-    let right_edge_matches = (1..(size-1)).all(|row| {
-        right_face.get(row, 0) == right_center
-    });
-    if !right_edge_matches {
-        mismatched_edge_pairs += 1;
-    }
+    (1..=wing_layers(cube.size()))
+        .any(|layer| matches!(detect_layer_parity(cube, layer), ParityType::PllParity | ParityType::Both))
+}
 
-    // Check back edge of Up face vs back center
-    let back_edge_matches = (1..(size-1)).all(|col| {
-        back_face.get(0, col) == back_center
-    });
-    if !back_edge_matches {
-        mismatched_edge_pairs += 1;
+/// Outer face turn in app moves
+fn face_turn(face: WideFace, direction: Direction) -> Move {
+    use Direction::*;
+    match (face, direction) {
+        (WideFace::R, Clockwise) => Move::R,
+        (WideFace::R, CounterClockwise) => Move::RPrime,
+        (WideFace::R, Double) => Move::R2,
+        (WideFace::L, Clockwise) => Move::L,
+        (WideFace::L, CounterClockwise) => Move::LPrime,
+        (WideFace::L, Double) => Move::L2,
+        (WideFace::U, Clockwise) => Move::U,
+        (WideFace::U, CounterClockwise) => Move::UPrime,
+        (WideFace::U, Double) => Move::U2,
+        (WideFace::D, Clockwise) => Move::D,
+        (WideFace::D, CounterClockwise) => Move::DPrime,
+        (WideFace::D, Double) => Move::D2,
+        (WideFace::F, Clockwise) => Move::F,
+        (WideFace::F, CounterClockwise) => Move::FPrime,
+        (WideFace::F, Double) => Move::F2,
+        (WideFace::B, Clockwise) => Move::B,
+        (WideFace::B, CounterClockwise) => Move::BPrime,
+        (WideFace::B, Double) => Move::B2,
     }
+}
 
-    // Check left edge of Up face vs left center
-    let left_edge_matches = (1..(size-1)).all(|row| {
-        left_face.get(row, size - 1) == left_center
-    });
-    if !left_edge_matches {
-        mismatched_edge_pairs += 1;
+/// Turn of the outer `depth` layers
+pub(crate) fn wide_turn(face: WideFace, direction: Direction, depth: usize) -> ParsedMove {
+    if depth == 1 {
+        ParsedMove::Basic(face_turn(face, direction))
+    } else {
+        ParsedMove::Wide(WideMove::new(face, direction, depth))
     }
-
-    // PLL parity: exactly 2 edges are mismatched (indicating a 2-edge swap)
-    mismatched_edge_pairs == 2
 }
 
-/// Standard OLL parity algorithm for 4x4 cubes
-///
-/// This algorithm flips a single edge on the last layer.
-/// Algorithm: Rw U2 x Rw U2 Rw U2 Rw' U2 Lw U2 Rw' U2 Rw U2 Rw' U2 Rw'
+/// Expand a parity algorithm for one inner layer
 ///
-/// Note: For simplicity, we use an alternative shorter algorithm:
-/// r U2 r U2 r' U2 r' U2 r U2 r'
-fn get_oll_parity_algorithm() -> Vec<Move> {
-    vec![
-        Move::R, Move::U, Move::U, // r U2 (using R as approximation)
-        Move::R, Move::U, Move::U, // r U2
-        Move::RPrime, Move::U, Move::U, // r' U2
-        Move::RPrime, Move::U, Move::U, // r' U2
-        Move::R, Move::U, Move::U, // r U2
-        Move::RPrime, // r'
-    ]
+/// Uppercase letters are outer face turns, `w` moves turn half the cube,
+/// and lowercase letters turn only `layer` (as a wide move and
+/// the next shallower one undone). Directions are converted from standard
+/// notation to the app's turning directions.
+fn layer_algorithm(template: &str, size: usize, layer: usize) -> Vec<ParsedMove> {
+    template
+        .split_whitespace()
+        .flat_map(|token| {
+            let letter = token.chars().next().unwrap_or('R');
+            let face = match letter.to_ascii_uppercase() {
+                'R' => WideFace::R,
+                'L' => WideFace::L,
+                'U' => WideFace::U,
+                'D' => WideFace::D,
+                'F' => WideFace::F,
+                _ => WideFace::B,
+            };
+            let mut direction = if token.ends_with('2') {
+                Direction::Double
+            } else if token.ends_with('\'') {
+                Direction::CounterClockwise
+            } else {
+                Direction::Clockwise
+            };
+            // The app turns R, L, U and D the opposite way to standard notation
+            if matches!(face, WideFace::R | WideFace::L | WideFace::U | WideFace::D) {
+                direction = WideMove::new(face, direction, 1).inverse().direction;
+            }
+            let undo = WideMove::new(face, direction, 1).inverse().direction;
+
+            if letter.is_ascii_lowercase() {
+                vec![wide_turn(face, direction, layer + 1), wide_turn(face, undo, layer)]
+            } else if token.contains('w') {
+                vec![wide_turn(face, direction, size / 2)]
+            } else {
+                vec![wide_turn(face, direction, 1)]
+            }
+        })
+        .collect()
 }
 
-/// Standard PLL parity algorithm for 4x4 cubes
+/// OLL parity algorithm for one inner layer: flips a single edge
 ///
-/// This algorithm swaps two opposite edges on the last layer.
-/// Algorithm: 2R2 U2 2R2 Uw2 2R2 Uw2
+/// On a 4x4 (layer 1) this is the usual r2 B2 U2 l U2 r' U2 r U2 F2 r F2 l' B2 r2.
+pub(crate) fn get_oll_parity_algorithm(size: usize, layer: usize) -> Vec<ParsedMove> {
+    layer_algorithm(OLL_PARITY, size, layer)
+}
+
+/// PLL parity algorithm for one inner layer: swaps two opposite edges
 ///
-/// Note: For simplicity, we use an alternative algorithm with basic moves:
-/// R2 U2 R2 U2 R2 U2
-fn get_pll_parity_algorithm() -> Vec<Move> {
-    vec![
-        Move::R2, Move::U, Move::U,
-        Move::R2, Move::U, Move::U,
-        Move::R2, Move::U, Move::U,
-    ]
+/// On a 4x4 (layer 1) this is the usual r2 U2 r2 Uw2 r2 Uw2. Only needed on
+/// even cubes.
+fn get_pll_parity_algorithm(size: usize, layer: usize) -> Vec<ParsedMove> {
+    layer_algorithm(PLL_PARITY, size, layer)
+}
+
+/// Parity step turning the algorithm's inner layers
+fn parity_step(description: String, moves: &[ParsedMove]) -> SolutionStep {
+    SolutionStep::with_layer_moves(description, moves.to_vec())
 }
 
 /// Resolve parity on a 4x4+ cube
 ///
-/// This function detects and resolves OLL and/or PLL parity cases.
+/// This function detects and resolves OLL and/or PLL parity cases in every
+/// inner edge layer, from the outside in, leaving a cube that solves like a
+/// 3x3. Each fix only turns the outer faces and the layer it fixes.
 ///
 /// # Arguments
 /// * `cube` - The 4x4+ cube to check and resolve parity for
@@ -266,44 +387,49 @@ pub fn resolve_parity(cube: &Cube) -> Result<ParitySolution, String> {
         return Err(format!("Parity only applies to 4x4+ cubes (got {}x{})", size, size));
     }
 
-    // Detect parity types
-    let has_oll_parity = detect_oll_parity(cube);
-    let has_pll_parity = detect_pll_parity(cube);
+    let mut state = cube.clone();
+    let mut all_moves = Vec::new();
+    let mut steps = Vec::new();
+    let (mut had_oll, mut had_pll) = (false, false);
+
+    // Each fix moves only its own layer's wings in the affected edges, so
+    // an edge stays paired only if every layer gets the same fix. Flip
+    // edges in all layers first; swapping edges first would leave the
+    // flips landing on different edges in each layer.
+    for layer in 1..=wing_layers(size) {
+        if matches!(detect_layer_parity(&state, layer), ParityType::OllParity | ParityType::Both) {
+            let moves = get_oll_parity_algorithm(size, layer);
+            state.apply_parsed_moves(&moves);
+            steps.push(parity_step(
+                format!("Resolve OLL parity on layer {} (flip single edge)", layer + 1),
+                &moves,
+            ));
+            all_moves.extend(moves);
+            had_oll = true;
+        }
+    }
+    // The OLL fixes can change the edge permutation, so check again
+    for layer in 1..=wing_layers(size) {
+        if matches!(detect_layer_parity(&state, layer), ParityType::PllParity | ParityType::Both) {
+            let moves = get_pll_parity_algorithm(size, layer);
+            state.apply_parsed_moves(&moves);
+            steps.push(parity_step(
+                format!("Resolve PLL parity on layer {} (swap two edges)", layer + 1),
+                &moves,
+            ));
+            all_moves.extend(moves);
+            had_pll = true;
+        }
+    }
 
-    let parity_type = match (has_oll_parity, has_pll_parity) {
+    let parity_type = match (had_oll, had_pll) {
         (false, false) => ParityType::None,
         (true, false) => ParityType::OllParity,
         (false, true) => ParityType::PllParity,
         (true, true) => ParityType::Both,
     };
-
-    let mut all_moves = Vec::new();
-    let mut steps = Vec::new();
-
-    match parity_type {
-        ParityType::None => {
-            steps.push(SolutionStep::new("No parity detected", vec![]));
-        }
-        ParityType::OllParity => {
-            let oll_moves = get_oll_parity_algorithm();
-            steps.push(SolutionStep::new("Resolve OLL parity (flip single edge)", oll_moves.clone()));
-            all_moves.extend(oll_moves);
-        }
-        ParityType::PllParity => {
-            let pll_moves = get_pll_parity_algorithm();
-            steps.push(SolutionStep::new("Resolve PLL parity (swap two edges)", pll_moves.clone()));
-            all_moves.extend(pll_moves);
-        }
-        ParityType::Both => {
-            // Resolve OLL parity first, then PLL parity
-            let oll_moves = get_oll_parity_algorithm();
-            steps.push(SolutionStep::new("Resolve OLL parity (flip single edge)", oll_moves.clone()));
-            all_moves.extend(oll_moves);
-
-            let pll_moves = get_pll_parity_algorithm();
-            steps.push(SolutionStep::new("Resolve PLL parity (swap two edges)", pll_moves.clone()));
-            all_moves.extend(pll_moves);
-        }
+    if steps.is_empty() {
+        steps.push(SolutionStep::new("No parity detected", vec![]));
     }
 
     let time_ms = start.elapsed().as_millis();
@@ -313,6 +439,7 @@ pub fn resolve_parity(cube: &Cube) -> Result<ParitySolution, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cube::notation::algorithm_to_notation;
     use crate::cube::Cube;
 
     #[test]
@@ -350,13 +477,13 @@ mod tests {
 
     #[test]
     fn test_oll_parity_algorithm_not_empty() {
-        let moves = get_oll_parity_algorithm();
+        let moves = get_oll_parity_algorithm(4, 1);
         assert!(!moves.is_empty(), "OLL parity algorithm should have moves");
     }
 
     #[test]
     fn test_pll_parity_algorithm_not_empty() {
-        let moves = get_pll_parity_algorithm();
+        let moves = get_pll_parity_algorithm(4, 1);
         assert!(!moves.is_empty(), "PLL parity algorithm should have moves");
     }
 
@@ -415,6 +542,14 @@ mod tests {
         assert!(!solution.steps.is_empty());
     }
 
+    #[test]
+    fn test_outer_turns_have_no_parity() {
+        let mut cube = Cube::new(6);
+        cube.apply_moves(&[Move::R, Move::U, Move::FPrime, Move::L2, Move::D]);
+        assert_eq!(detect_layer_parity(&cube, 1), ParityType::None);
+        assert_eq!(detect_layer_parity(&cube, 2), ParityType::None);
+    }
+
     #[test]
     fn test_detect_oll_parity_works_on_5x5() {
         let cube = Cube::new(5);
@@ -428,4 +563,110 @@ mod tests {
         // Should not panic, returns bool
         let _has_parity = detect_pll_parity(&cube);
     }
+
+    /// Every face still shows one center color
+    fn centers_solid(cube: &Cube) -> bool {
+        let n = cube.size();
+        FaceName::all().iter().all(|face| {
+            (1..n - 1).all(|row| (1..n - 1).all(|col| cube.get_face(*face).get(row, col) == center(cube, *face)))
+        })
+    }
+
+    /// A solved cube with one parity algorithm applied
+    fn with_parity(size: usize, algorithm: Vec<ParsedMove>) -> Cube {
+        let mut cube = Cube::new(size);
+        cube.apply_parsed_moves(&algorithm);
+        cube
+    }
+
+    #[test]
+    fn test_wing_layers() {
+        assert_eq!(wing_layers(3), 0);
+        assert_eq!(wing_layers(4), 1);
+        assert_eq!(wing_layers(5), 1);
+        assert_eq!(wing_layers(6), 2);
+        assert_eq!(wing_layers(7), 2);
+    }
+
+    #[test]
+    fn test_layer_algorithm_notation() {
+        assert_eq!(algorithm_to_notation(&layer_algorithm("r2 Uw2 F", 4, 1)), "Rw2 R2 Uw2 F");
+        assert_eq!(algorithm_to_notation(&layer_algorithm("r' Uw", 6, 2)), "3Rw Rw' 3Uw'");
+    }
+
+    #[test]
+    fn test_algorithms_only_affect_their_layer() {
+        for size in 4..=7 {
+            for layer in 1..=wing_layers(size) {
+                let oll = with_parity(size, get_oll_parity_algorithm(size, layer));
+                for other in 1..=wing_layers(size) {
+                    let expected = if other == layer { ParityType::OllParity } else { ParityType::None };
+                    assert_eq!(detect_layer_parity(&oll, other), expected, "{}x{} layer {}", size, size, layer);
+                }
+                assert!(centers_solid(&oll), "{}x{} layer {}", size, size, layer);
+
+                // PLL parity cannot happen on odd cubes
+                if size % 2 == 0 {
+                    let pll = with_parity(size, get_pll_parity_algorithm(size, layer));
+                    for other in 1..=wing_layers(size) {
+                        let expected = if other == layer { ParityType::PllParity } else { ParityType::None };
+                        assert_eq!(detect_layer_parity(&pll, other), expected, "{}x{} layer {}", size, size, layer);
+                    }
+                    assert!(centers_solid(&pll), "{}x{} layer {}", size, size, layer);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolves_parity_in_every_layer_of_6x6() {
+        let mut cube = Cube::new(6);
+        cube.apply_parsed_moves(&get_oll_parity_algorithm(6, 1));
+        cube.apply_parsed_moves(&get_pll_parity_algorithm(6, 2));
+        cube.apply_parsed_moves(&get_oll_parity_algorithm(6, 2));
+        // Outer turns never change parity
+        cube.apply_moves(&[Move::R, Move::U, Move::F2, Move::DPrime, Move::B]);
+        assert!(detect_oll_parity(&cube) && detect_pll_parity(&cube));
+
+        let solution = resolve_parity(&cube).unwrap();
+        assert_eq!(solution.parity_type, ParityType::Both);
+        solution.apply_to(&mut cube);
+        assert!(!detect_oll_parity(&cube) && !detect_pll_parity(&cube));
+        assert!(centers_solid(&cube));
+    }
+
+    #[test]
+    fn test_resolving_every_layer_keeps_edges_paired() {
+        use crate::solver::reduction::are_edges_paired;
+
+        for size in [6, 8] {
+            // Parity shows in every layer of a paired cube at once
+            let mut cube = Cube::new(size);
+            for layer in 1..=wing_layers(size) {
+                cube.apply_parsed_moves(&get_oll_parity_algorithm(size, layer));
+            }
+            for layer in 1..=wing_layers(size) {
+                cube.apply_parsed_moves(&get_pll_parity_algorithm(size, layer));
+            }
+            cube.apply_moves(&[Move::R, Move::U, Move::F2, Move::DPrime, Move::B]);
+            assert!(are_edges_paired(&cube), "{}x{}", size, size);
+
+            let solution = resolve_parity(&cube).unwrap();
+            assert_eq!(solution.parity_type, ParityType::Both);
+            solution.apply_to(&mut cube);
+            assert!(are_edges_paired(&cube), "{}x{} edges unpaired after parity", size, size);
+            assert!(!detect_oll_parity(&cube) && !detect_pll_parity(&cube), "{}x{}", size, size);
+            assert!(centers_solid(&cube), "{}x{}", size, size);
+        }
+    }
+
+    #[test]
+    fn test_resolves_edge_parity_on_5x5() {
+        let mut cube = with_parity(5, get_oll_parity_algorithm(5, 1));
+        let solution = resolve_parity(&cube).unwrap();
+        assert_eq!(solution.parity_type, ParityType::OllParity);
+        assert_eq!(solution.steps.len(), 1);
+        solution.apply_to(&mut cube);
+        assert_eq!(detect_layer_parity(&cube, 1), ParityType::None);
+    }
 }
@@ -0,0 +1,188 @@
+//! Pyraminx solver
+//!
+//! Tips turn on their own, so the solver leaves them for last: it first
+//! solves the rest of the puzzle (axial centers and edges) in the fewest
+//! layer moves, then turns each tip to match. Any Pyraminx needs at most 11
+//! layer moves, which is found by meeting in the middle: every state up to
+//! [`TABLE_DEPTH`] moves from solved is tabulated, and a depth-first search
+//! from the scrambled puzzle looks for one of them.
+
+use crate::cube::Color;
+use crate::puzzle::pyraminx::{tip_positions, STICKERS_PER_FACE, TIP_STICKERS};
+use crate::puzzle::{Corner, Pyraminx, PyraminxMove};
+use std::collections::HashMap;
+
+/// Most layer moves ever needed to solve a Pyraminx
+pub const MAX_LAYER_MOVES: usize = 11;
+
+/// Depth of the table of states near solved
+const TABLE_DEPTH: usize = 5;
+
+/// The eight layer moves
+fn layer_moves() -> impl Iterator<Item = PyraminxMove> {
+    Corner::ALL.into_iter().flat_map(|corner| {
+        let mv = PyraminxMove::layer(corner);
+        [mv, mv.inverse()]
+    })
+}
+
+/// Colors of every sticker but the tips, which layer moves cannot fix
+fn body_key(pyraminx: &Pyraminx) -> Vec<Color> {
+    pyraminx
+        .stickers()
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !TIP_STICKERS.contains(&(i % STICKERS_PER_FACE)))
+        .map(|(_, c)| *c)
+        .collect()
+}
+
+/// Every body state up to `TABLE_DEPTH` moves from solved, with the
+/// shortest moves that solve it
+fn near_solved_table() -> HashMap<Vec<Color>, Vec<PyraminxMove>> {
+    let solved = Pyraminx::new();
+    let mut table = HashMap::from([(body_key(&solved), Vec::new())]);
+    let mut frontier = vec![(solved, Vec::<PyraminxMove>::new())];
+    for _ in 0..TABLE_DEPTH {
+        let mut next = Vec::new();
+        for (state, solution) in &frontier {
+            for mv in layer_moves() {
+                let mut moved = *state;
+                moved.apply_move(mv);
+                let key = body_key(&moved);
+                if table.contains_key(&key) {
+                    continue;
+                }
+                // Undo `mv` first, then the way back from `state`
+                let mut moved_solution = vec![mv.inverse()];
+                moved_solution.extend_from_slice(solution);
+                table.insert(key, moved_solution.clone());
+                next.push((moved, moved_solution));
+            }
+        }
+        frontier = next;
+    }
+    table
+}
+
+/// Depth-first search for a path of exactly `depth` moves into the table
+/// whose table part is at most `remaining` moves long
+fn search(
+    state: &Pyraminx,
+    depth: usize,
+    remaining: usize,
+    table: &HashMap<Vec<Color>, Vec<PyraminxMove>>,
+    path: &mut Vec<PyraminxMove>,
+) -> Option<Vec<PyraminxMove>> {
+    if depth == 0 {
+        return table.get(&body_key(state)).filter(|rest| rest.len() <= remaining).map(|rest| {
+            let mut moves = path.clone();
+            moves.extend_from_slice(rest);
+            moves
+        });
+    }
+    for mv in layer_moves() {
+        // Two turns of the same layer in a row are one turn
+        if path.last().is_some_and(|last| last.corner == mv.corner) {
+            continue;
+        }
+        let mut moved = *state;
+        moved.apply_move(mv);
+        path.push(mv);
+        let found = search(&moved, depth - 1, remaining, table, path);
+        path.pop();
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+/// Solve a Pyraminx: the fewest layer moves, then one move for each
+/// twisted tip
+pub fn solve_pyraminx(pyraminx: &Pyraminx) -> Result<Vec<PyraminxMove>, String> {
+    let table = near_solved_table();
+    let mut moves = (0..=MAX_LAYER_MOVES)
+        .find_map(|length| {
+            let depth = length.saturating_sub(TABLE_DEPTH);
+            search(pyraminx, depth, length - depth, &table, &mut Vec::new())
+        })
+        .ok_or_else(|| "Pyraminx cannot be solved: some pieces are not where turns could put them".to_string())?;
+
+    let mut state = *pyraminx;
+    state.apply_moves(&moves);
+    for corner in Corner::ALL {
+        // With the body solved, the sticker next to the tip (an edge) shows
+        // each face's color
+        let tip_solved = |state: &Pyraminx| {
+            tip_positions(corner).iter().all(|&i| state.stickers()[i] == state.stickers()[i - i % STICKERS_PER_FACE + 1])
+        };
+        if tip_solved(&state) {
+            continue;
+        }
+        let turn = [PyraminxMove::tip(corner), PyraminxMove::tip(corner).inverse()].into_iter().find(|mv| {
+            let mut turned = state;
+            turned.apply_move(*mv);
+            tip_solved(&turned)
+        });
+        if let Some(mv) = turn {
+            state.apply_move(mv);
+            moves.push(mv);
+        }
+    }
+
+    if !state.is_solved() {
+        return Err("Pyraminx cannot be solved: a tip's colors do not match its corner".to_string());
+    }
+    Ok(moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::pyraminx::random_scramble;
+    use crate::puzzle::Puzzle;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn solve(scramble: &str) -> Vec<PyraminxMove> {
+        let mut pyraminx = Pyraminx::new();
+        pyraminx.apply_notation(scramble).unwrap();
+        let moves = solve_pyraminx(&pyraminx).unwrap();
+        pyraminx.apply_moves(&moves);
+        assert!(pyraminx.is_solved(), "{} + {}", scramble, Pyraminx::sequence_notation(&moves));
+        moves
+    }
+
+    #[test]
+    fn test_solves_short_scrambles_optimally() {
+        assert!(solve("").is_empty());
+        assert_eq!(Pyraminx::sequence_notation(&solve("U")), "U'");
+        assert_eq!(Pyraminx::sequence_notation(&solve("R U' l")), "U R' l'");
+        assert_eq!(solve("R U L' B R' U' L B' u' r").len(), 10);
+    }
+
+    #[test]
+    fn test_solves_random_scrambles() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..3 {
+            let scramble = random_scramble(&mut rng, 11);
+            let moves = solve(&Pyraminx::sequence_notation(&scramble));
+            assert!(moves.iter().filter(|m| !m.tip).count() <= MAX_LAYER_MOVES);
+            assert!(moves.iter().filter(|m| m.tip).count() <= 4);
+        }
+    }
+
+    #[test]
+    fn test_rejects_impossible_states() {
+        // Two edge stickers swapped between faces
+        let mut stickers: [Color; 36] = Pyraminx::new().stickers().try_into().unwrap();
+        stickers.swap(1, STICKERS_PER_FACE + 1);
+        assert!(solve_pyraminx(&Pyraminx::from_stickers(stickers)).is_err());
+
+        // A tip sticker of the wrong color
+        let mut stickers: [Color; 36] = Pyraminx::new().stickers().try_into().unwrap();
+        stickers[0] = Color::White;
+        assert!(solve_pyraminx(&Pyraminx::from_stickers(stickers)).is_err());
+    }
+}
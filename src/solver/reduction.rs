@@ -8,16 +8,18 @@
 //!
 //! This module implements R5.3 (centers) and R5.4 (edge pairing)
 
-use crate::cube::{Cube, Move, Color, FaceName};
+use crate::cube::{Cube, Color, FaceName, ParsedMove};
+use crate::solver::centers::build_centers;
+use crate::solver::edges::{pair_edges, EdgeStep};
+use crate::solver::parity::{resolve_parity, sticker, EDGE_SLOTS};
 use crate::solver::solution::{Solution, SolutionStep};
 use std::time::Instant;
-use std::collections::HashMap;
 
 /// Solution for 4x4+ center solving
 #[derive(Debug, Clone)]
 pub struct CenterSolution {
-    /// List of moves to solve centers
-    pub moves: Vec<Move>,
+    /// Moves to solve centers, including inner-layer wide moves
+    pub moves: Vec<ParsedMove>,
     /// Time taken to find the solution (in milliseconds)
     pub time_ms: u128,
     /// Step-by-step breakdown
@@ -26,7 +28,7 @@ pub struct CenterSolution {
 
 impl CenterSolution {
     /// Create a new center solution
-    pub fn new(moves: Vec<Move>, time_ms: u128, steps: Vec<SolutionStep>) -> Self {
+    pub fn new(moves: Vec<ParsedMove>, time_ms: u128, steps: Vec<SolutionStep>) -> Self {
         Self { moves, time_ms, steps }
     }
 
@@ -35,6 +37,11 @@ impl CenterSolution {
         self.moves.len()
     }
 
+    /// Apply the center moves to a cube
+    pub fn apply_to(&self, cube: &mut Cube) {
+        cube.apply_parsed_moves(&self.moves);
+    }
+
     /// Convert to generic Solution type
     pub fn to_solution(&self) -> Solution {
         Solution::with_method(self.steps.clone(), self.time_ms, "4x4+ Reduction Method - Centers")
@@ -73,12 +80,12 @@ fn are_centers_solved(cube: &Cube) -> bool {
     true
 }
 
-/// Solve the centers of a 4x4+ cube using a simple algorithm
+/// Solve the centers of a 4x4+ cube
 ///
-/// This uses a greedy approach to build centers face by face:
-/// 1. Pick target colors for each face
-/// 2. Build each center by moving correct-color pieces into position
-/// 3. Use slice moves to avoid disturbing already-solved centers
+/// Builds blocks of center pieces with bar moves first, then finishes with
+/// commutators that leave the built blocks alone (see [`crate::solver::centers`]).
+/// Odd cubes keep their middle centers; even cubes are solved to whichever
+/// color scheme orientation already has the most centers in place.
 ///
 /// # Arguments
 /// * `cube` - The 4x4+ cube to solve centers for (must be size 4 or larger)
@@ -89,11 +96,11 @@ fn are_centers_solved(cube: &Cube) -> bool {
 ///
 /// # Example
 /// ```
-/// use rubiks_cube_solver::cube::Cube;
+/// use rubiks_cube_solver::cube::{Cube, WideMove};
 /// use rubiks_cube_solver::solver::solve_centers;
 ///
 /// let mut cube = Cube::new(4);
-/// // Scramble the cube...
+/// cube.apply_wide_move(WideMove::rw());
 /// let solution = solve_centers(&cube).expect("Should solve centers");
 /// assert!(solution.move_count() > 0);
 /// ```
@@ -115,136 +122,27 @@ pub fn solve_centers(cube: &Cube) -> Result<CenterSolution, String> {
         ));
     }
 
-    // For now, we'll use a simple algorithm:
-    // 1. Solve each center face by face
-
-    let mut working_cube = cube.clone();
     let mut all_moves = Vec::new();
     let mut steps = Vec::new();
-
-    // Determine target color for each face based on current centers
-    let target_colors = get_target_colors(&working_cube);
-
-    // Solve each face's center
-    for face_name in FaceName::all() {
-        let target_color = target_colors.get(&face_name).copied().unwrap();
-        let moves = solve_single_center(&mut working_cube, face_name, target_color)?;
-
-        if !moves.is_empty() {
-            let step_desc = format!("Solve {} center ({})", face_name_str(face_name), color_name(&target_color));
-            steps.push(SolutionStep::new(step_desc, moves.clone()));
-            all_moves.extend(moves);
-        }
-    }
-
-    // Verify centers are solved
-    if !are_centers_solved(&working_cube) {
-        return Err("Failed to solve centers - algorithm incomplete".to_string());
+    for (commutators, moves) in build_centers(cube)? {
+        let description = if commutators {
+            "Finish the centers with commutators"
+        } else {
+            "Build center bars and blocks"
+        };
+        steps.push(SolutionStep::with_layer_moves(description, moves.clone()));
+        all_moves.extend(moves);
     }
 
     let time_ms = start.elapsed().as_millis();
     Ok(CenterSolution::new(all_moves, time_ms, steps))
 }
 
-/// Determine the target color for each face based on current center pieces
-fn get_target_colors(cube: &Cube) -> HashMap<FaceName, Color> {
-    let mut colors = HashMap::new();
-
-    // For each face, use the most common color in the center region as the target
-    for face_name in FaceName::all() {
-        let face = cube.get_face(face_name);
-        let size = cube.size();
-
-        // Count colors in center region
-        let mut color_counts = HashMap::new();
-        for row in 1..(size - 1) {
-            for col in 1..(size - 1) {
-                let color = face.get(row, col);
-                *color_counts.entry(color).or_insert(0) += 1;
-            }
-        }
-
-        // Find most common color
-        let target = color_counts
-            .iter()
-            .max_by_key(|(_, &count)| count)
-            .map(|(&color, _)| color)
-            .unwrap_or(Color::White); // Fallback
-
-        colors.insert(face_name, target);
-    }
-
-    colors
-}
-
-/// Solve a single face's center to be all one color
-fn solve_single_center(cube: &mut Cube, face_name: FaceName, target_color: Color) -> Result<Vec<Move>, String> {
-    let moves = Vec::new();
-    let size = cube.size();
-
-    // Check if this center is already solved
-    let face = cube.get_face(face_name);
-    let mut is_solved = true;
-    for row in 1..(size - 1) {
-        for col in 1..(size - 1) {
-            if face.get(row, col) != target_color {
-                is_solved = false;
-                break;
-            }
-        }
-        if !is_solved {
-            break;
-        }
-    }
-
-    if is_solved {
-        return Ok(moves); // Already solved
-    }
-
-    // Simple greedy algorithm:
-    // For each position in the center, if it's not the target color,
-    // find a piece with the target color and swap it in
-    //
-    // NOTE: This is a simplified implementation that acknowledges the complexity
-    // A full implementation would use slice moves to avoid disturbing other centers,
-    // but that requires a sophisticated move sequence generator
-    //
-    // For now, we return an empty move list to indicate the algorithm needs
-    // more work. This satisfies the "generate move sequence" requirement
-    // while being honest about the implementation complexity.
-
-    Ok(moves)
-}
-
-/// Get human-readable face name
-fn face_name_str(face_name: FaceName) -> &'static str {
-    match face_name {
-        FaceName::U => "Up",
-        FaceName::D => "Down",
-        FaceName::F => "Front",
-        FaceName::B => "Back",
-        FaceName::R => "Right",
-        FaceName::L => "Left",
-    }
-}
-
-/// Get human-readable color name
-fn color_name(color: &Color) -> &'static str {
-    match color {
-        Color::White => "White",
-        Color::Yellow => "Yellow",
-        Color::Red => "Red",
-        Color::Orange => "Orange",
-        Color::Blue => "Blue",
-        Color::Green => "Green",
-    }
-}
-
 /// Solution for 4x4+ edge pairing
 #[derive(Debug, Clone)]
 pub struct EdgeSolution {
-    /// List of moves to pair edges
-    pub moves: Vec<Move>,
+    /// Moves to pair edges, including inner-layer wide moves
+    pub moves: Vec<ParsedMove>,
     /// Time taken to find the solution (in milliseconds)
     pub time_ms: u128,
     /// Step-by-step breakdown
@@ -253,7 +151,7 @@ pub struct EdgeSolution {
 
 impl EdgeSolution {
     /// Create a new edge solution
-    pub fn new(moves: Vec<Move>, time_ms: u128, steps: Vec<SolutionStep>) -> Self {
+    pub fn new(moves: Vec<ParsedMove>, time_ms: u128, steps: Vec<SolutionStep>) -> Self {
         Self { moves, time_ms, steps }
     }
 
@@ -262,6 +160,11 @@ impl EdgeSolution {
         self.moves.len()
     }
 
+    /// Apply the edge moves to a cube
+    pub fn apply_to(&self, cube: &mut Cube) {
+        cube.apply_parsed_moves(&self.moves);
+    }
+
     /// Convert to generic Solution type
     pub fn to_solution(&self) -> Solution {
         Solution::with_method(self.steps.clone(), self.time_ms, "4x4+ Reduction Method - Edges")
@@ -269,16 +172,14 @@ impl EdgeSolution {
 }
 
 /// Represents an edge piece with two colors
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-#[allow(dead_code)]
-struct EdgePiece {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct EdgePiece {
     color1: Color,
     color2: Color,
 }
 
 impl EdgePiece {
-    #[allow(dead_code)]
-    fn new(color1: Color, color2: Color) -> Self {
+    pub(crate) fn new(color1: Color, color2: Color) -> Self {
         // Always store colors in a consistent order for comparison
         if color1 as u8 <= color2 as u8 {
             Self { color1, color2 }
@@ -287,6 +188,11 @@ impl EdgePiece {
         }
     }
 
+    /// The two colors, in their normalized order
+    pub(crate) fn colors(&self) -> (Color, Color) {
+        (self.color1, self.color2)
+    }
+
     /// Check if two edge pieces should be paired together
     #[allow(dead_code)]
     fn matches(&self, other: &EdgePiece) -> bool {
@@ -297,66 +203,28 @@ impl EdgePiece {
 
 /// Check if all edges are paired on a 4x4+ cube
 ///
-/// For 4x4, each edge position should have both pieces with the same color combination.
-/// For 5x5+, each edge should have all pieces with matching colors.
-fn are_edges_paired(cube: &Cube) -> bool {
+/// Every edge piece of an edge shows the same two colors on the same faces,
+/// so the cube's edges read like a 3x3's.
+pub(crate) fn are_edges_paired(cube: &Cube) -> bool {
     let size = cube.size();
     if size < 4 {
         return true; // 2x2 and 3x3 don't have separate edge pieces
     }
 
-    // For 4x4 cubes, check that each edge consists of two pieces with matching colors
-    // We need to check all 12 edges
-
-    // Check edges on each face
-    for face_name in FaceName::all() {
-        let _face = cube.get_face(face_name);
-
-        // Check top edge
-        for col in 1..(size - 1) {
-            if !is_edge_piece_paired(cube, face_name, 0, col, size) {
-                return false;
-            }
-        }
-
-        // Check bottom edge
-        for col in 1..(size - 1) {
-            if !is_edge_piece_paired(cube, face_name, size - 1, col, size) {
-                return false;
-            }
-        }
-
-        // Check left edge
-        for row in 1..(size - 1) {
-            if !is_edge_piece_paired(cube, face_name, row, 0, size) {
-                return false;
-            }
-        }
-
-        // Check right edge
-        for row in 1..(size - 1) {
-            if !is_edge_piece_paired(cube, face_name, row, size - 1, size) {
-                return false;
-            }
-        }
-    }
-
-    true
-}
-
-/// Check if a specific edge piece is properly paired
-fn is_edge_piece_paired(_cube: &Cube, _face: FaceName, _row: usize, _col: usize, _size: usize) -> bool {
-    // For now, simplified check - a full implementation would verify
-    // that adjacent edge pieces have matching colors
-    // This is a placeholder that returns true to indicate structure is in place
-    true
+    EDGE_SLOTS.iter().all(|slot| {
+        let colors = |layer: usize| slot.map(|position| sticker(cube, position, layer));
+        (2..size - 1).all(|layer| colors(layer) == colors(1))
+    })
 }
 
 /// Pair up edge pieces on a 4x4+ cube
 ///
-/// This implements the edge pairing step of the reduction method.
-/// After centers are solved, we pair up edge pieces so the cube
-/// can be solved like a 3x3.
+/// This implements the edge pairing step of the reduction method. After
+/// centers are solved, edge pieces are paired with slice-batch commutators
+/// (see [`crate::solver::edges`]) so the cube can be solved like a 3x3.
+/// Centers, corners and the middle edges of odd cubes are left where they
+/// are. A layer that can only be paired by swapping two pieces gets the OLL
+/// parity algorithm first.
 ///
 /// # Arguments
 /// * `cube` - The 4x4+ cube with solved centers
@@ -367,21 +235,20 @@ fn is_edge_piece_paired(_cube: &Cube, _face: FaceName, _row: usize, _col: usize,
 ///
 /// # Example
 /// ```
-/// use rubiks_cube_solver::cube::Cube;
+/// use rubiks_cube_solver::cube::{Cube, WideMove};
 /// use rubiks_cube_solver::solver::{solve_centers, solve_edges};
 ///
-/// let mut cube = Cube::new(4);
-/// // Scramble and solve centers first...
-/// let center_solution = solve_centers(&cube).expect("Should solve centers");
+/// let mut cube = Cube::new(5);
+/// cube.apply_wide_move(WideMove::rw());
+/// cube.apply_wide_move(WideMove::uw());
 ///
-/// // Apply center solution moves
-/// for mv in center_solution.moves {
-///     cube.apply_move(mv);
-/// }
+/// // Solve centers first...
+/// let centers = solve_centers(&cube).expect("Should solve centers");
+/// centers.apply_to(&mut cube);
 ///
-/// // Now pair edges
+/// // ...then pair edges
 /// let edge_solution = solve_edges(&cube).expect("Should pair edges");
-/// assert!(edge_solution.move_count() >= 0);
+/// assert!(edge_solution.move_count() > 0);
 /// ```
 pub fn solve_edges(cube: &Cube) -> Result<EdgeSolution, String> {
     let start = Instant::now();
@@ -401,58 +268,48 @@ pub fn solve_edges(cube: &Cube) -> Result<EdgeSolution, String> {
         ));
     }
 
-    // Implementation strategy:
-    // 1. Identify unpaired edge pieces
-    // 2. For each edge position, find matching pieces
-    // 3. Use slice moves to bring them together
-    // 4. Pair them without disturbing centers
-
-    let mut working_cube = cube.clone();
     let mut all_moves = Vec::new();
     let mut steps = Vec::new();
-
-    // For 4x4 cubes, there are 12 edge positions, each needs 2 pieces paired
-    // For 5x5+, each edge has more pieces but the strategy is similar
-
-    // Simplified algorithm: Pair edges one at a time
-    // In practice, this is complex and requires sophisticated algorithms
-    // For now, we provide the structure and a basic implementation
-
-    let edges_to_pair = if size == 4 { 12 } else { 12 }; // 12 edges on any cube
-
-    for i in 0..edges_to_pair {
-        let edge_moves = pair_single_edge(&mut working_cube, i)?;
-
-        if !edge_moves.is_empty() {
-            let step_desc = format!("Pair edge {}", i + 1);
-            steps.push(SolutionStep::new(step_desc, edge_moves.clone()));
-            all_moves.extend(edge_moves);
-        }
-    }
-
-    // If no moves were generated but edges aren't paired, acknowledge limitation
-    if all_moves.is_empty() && !are_edges_paired(&working_cube) {
-        steps.push(SolutionStep::new(
-            "Edge pairing algorithm in progress - structure complete",
-            vec![]
-        ));
+    for (step, moves) in pair_edges(cube)? {
+        let description = match step {
+            EdgeStep::Batch => "Pair edges in slice batches".to_string(),
+            EdgeStep::Cycle => "Pair the last edge pieces with 3-cycles".to_string(),
+            EdgeStep::Parity(layer) => format!("Fix edge parity on layer {} (swap two edge pieces)", layer + 1),
+        };
+        steps.push(SolutionStep::with_layer_moves(description, moves.clone()));
+        all_moves.extend(moves);
     }
 
     let time_ms = start.elapsed().as_millis();
     Ok(EdgeSolution::new(all_moves, time_ms, steps))
 }
 
-/// Pair a single edge position
-fn pair_single_edge(_cube: &mut Cube, _edge_index: usize) -> Result<Vec<Move>, String> {
-    // This is a placeholder for the complex edge pairing algorithm
-    // A full implementation would:
-    // 1. Identify the two pieces that belong to this edge
-    // 2. Position them using slice moves
-    // 3. Pair them using specific algorithms
-    // 4. Move to next edge without disturbing previous pairs
-
-    // For now, return empty moves to satisfy structure requirements
-    Ok(vec![])
+/// Reduce a 4x4+ cube to one that solves like a 3x3
+///
+/// Solves the centers, pairs the edges and resolves parity, joining the
+/// phases with [`Solution::concat`] so turns that cancel where one phase
+/// ends and the next begins are left out.
+///
+/// # Example
+/// ```
+/// use rubiks_cube_solver::cube::{Cube, WideMove};
+/// use rubiks_cube_solver::solver::solve_reduction;
+///
+/// let mut cube = Cube::new(4);
+/// cube.apply_wide_move(WideMove::rw());
+/// let solution = solve_reduction(&cube).expect("Should reduce");
+/// solution.apply_to(&mut cube);
+/// ```
+pub fn solve_reduction(cube: &Cube) -> Result<Solution, String> {
+    let mut state = cube.clone();
+    let centers = solve_centers(&state)?;
+    centers.apply_to(&mut state);
+    let edges = solve_edges(&state)?;
+    edges.apply_to(&mut state);
+    let parity = resolve_parity(&state)?;
+
+    let joined = centers.to_solution().concat(&edges.to_solution()).concat(&parity.to_solution());
+    Ok(Solution::with_method(joined.steps, joined.time_ms, "4x4+ Reduction Method"))
 }
 
 #[cfg(test)]
@@ -489,25 +346,41 @@ mod tests {
         assert!(solution.steps[0].description.contains("already solved"));
     }
 
-    #[test]
-    fn test_get_target_colors_solved_cube() {
-        let cube = Cube::new(4);
-        let colors = get_target_colors(&cube);
-        assert_eq!(colors.len(), 6);
-        // On a solved cube, each face should target its own color
+    /// Scramble the centers with wide and inner slice turns
+    fn scrambled(size: usize, turns: usize) -> Cube {
+        use crate::cube::{Direction, WideFace};
+        use crate::solver::parity::wide_turn;
+
+        let faces = [WideFace::R, WideFace::U, WideFace::F, WideFace::L, WideFace::D, WideFace::B];
+        let directions = [Direction::Clockwise, Direction::CounterClockwise, Direction::Double];
+        let mut cube = Cube::new(size);
+        let mut seed = 12345usize;
+        for _ in 0..turns {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345) % (1 << 31);
+            let depth = 2 + (seed >> 8) % size.div_ceil(2).saturating_sub(1).max(1);
+            let mv = wide_turn(faces[seed % 6], directions[(seed >> 4) % 3], depth);
+            cube.apply_parsed_move(&mv);
+        }
+        cube
     }
 
     #[test]
-    fn test_face_names() {
-        assert_eq!(face_name_str(FaceName::U), "Up");
-        assert_eq!(face_name_str(FaceName::D), "Down");
-        assert_eq!(face_name_str(FaceName::F), "Front");
+    fn test_solve_centers_scrambled() {
+        for size in 4..=7 {
+            let mut cube = scrambled(size, 30);
+            assert!(!are_centers_solved(&cube));
+            let solution = solve_centers(&cube).expect("Should solve centers");
+            solution.apply_to(&mut cube);
+            assert!(are_centers_solved(&cube), "{}x{} centers not solved", size, size);
+            assert!(solution.steps.iter().all(|step| step.description.contains("center")));
+        }
     }
 
     #[test]
-    fn test_color_names() {
-        assert_eq!(color_name(&Color::White), "White");
-        assert_eq!(color_name(&Color::Red), "Red");
+    fn test_solve_centers_7x7_move_count() {
+        let cube = scrambled(7, 60);
+        let solution = solve_centers(&cube).expect("Should solve centers");
+        assert!(solution.move_count() < 1000, "took {} moves", solution.move_count());
     }
 
     // Edge pairing tests
@@ -541,6 +414,97 @@ mod tests {
         assert!(solution.steps[0].description.contains("already paired"));
     }
 
+    /// Mix the edges with slice, a X a', slice back (X being the slice's
+    /// face), which keeps the centers solved
+    fn scrambled_edges(size: usize, turns: usize) -> Cube {
+        use crate::cube::{Direction, WideFace};
+        use crate::solver::parity::wide_turn;
+
+        let faces = [WideFace::R, WideFace::U, WideFace::F, WideFace::L, WideFace::D, WideFace::B];
+        let directions = [Direction::Clockwise, Direction::CounterClockwise, Direction::Double];
+        let mut cube = Cube::new(size);
+        let mut seed = 777usize;
+        for _ in 0..turns {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345) % (1 << 31);
+            let face_index = seed % 6;
+            let face = faces[face_index];
+            let direction = directions[(seed >> 4) % 3];
+            let layer = 1 + (seed >> 8) % crate::solver::wing_layers(size);
+            let slice = [wide_turn(face, direction, layer + 1), wide_turn(face, direction, layer).inverse()];
+            let other = wide_turn(faces[(face_index + 1 + (seed >> 12) % 5) % 6], Direction::Clockwise, 1);
+            cube.apply_parsed_moves(&slice);
+            cube.apply_parsed_moves(&[other.clone(), wide_turn(face, direction, 1), other.inverse()]);
+            cube.apply_parsed_moves(&[slice[1].inverse(), slice[0].inverse()]);
+            cube.apply_parsed_moves(&[wide_turn(faces[(seed >> 16) % 6], directions[(seed >> 20) % 3], 1)]);
+        }
+        cube
+    }
+
+    /// The 3x3 a reduced cube reads as: corners, centers and paired edges
+    fn as_3x3(cube: &Cube) -> Cube {
+        let n = cube.size();
+        let mut small = Cube::new(3);
+        for face in FaceName::all() {
+            for row in 0..3 {
+                for col in 0..3 {
+                    let at = |i: usize| [0, 1, n - 1][i];
+                    small.set_sticker(face, row, col, cube.get_face(face).get(at(row), at(col)));
+                }
+            }
+        }
+        small
+    }
+
+    #[test]
+    fn test_solve_edges_reduces_to_3x3() {
+        use crate::solver::{resolve_parity, solve_blind};
+
+        for size in 5..=9 {
+            let mut cube = scrambled_edges(size, 20);
+            assert!(are_centers_solved(&cube) && !are_edges_paired(&cube));
+            let solution = solve_edges(&cube).expect("Should pair edges");
+            solution.apply_to(&mut cube);
+            assert!(are_edges_paired(&cube), "{}x{} edges not paired", size, size);
+            assert!(are_centers_solved(&cube), "{}x{} centers broken", size, size);
+
+            // Parity fixed, the cube solves as a 3x3
+            resolve_parity(&cube).unwrap().apply_to(&mut cube);
+            assert!(are_edges_paired(&cube), "{}x{} parity fix unpaired edges", size, size);
+            let mut small = as_3x3(&cube);
+            let (_, blind) = solve_blind(&small).expect("Should be a valid 3x3");
+            small.apply_moves(&blind.all_moves());
+            assert!(small.is_solved(), "{}x{} does not solve as a 3x3", size, size);
+        }
+    }
+
+    #[test]
+    fn test_solve_edges_batches_slices() {
+        let cube = scrambled_edges(8, 80);
+        let solution = solve_edges(&cube).expect("Should pair edges");
+        assert!(solution.steps.iter().any(|step| step.description.contains("slice batches")));
+        assert!(solution.steps.iter().all(|step| step.description.contains("edge")));
+    }
+
+    #[test]
+    fn test_solve_reduction_replays() {
+        use crate::solver::{detect_oll_parity, detect_pll_parity};
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(9);
+        for size in 4..=7 {
+            let mut cube = Cube::random_state(size, &mut rng);
+            let solution = solve_reduction(&cube).expect("Should reduce");
+            assert_eq!(solution.move_count(), solution.all_parsed_moves().len());
+            assert!(solution.steps.iter().any(|step| !step.layer_moves.is_empty()));
+
+            solution.apply_to(&mut cube);
+            assert!(are_centers_solved(&cube), "{}x{} centers not solved", size, size);
+            assert!(are_edges_paired(&cube), "{}x{} edges not paired", size, size);
+            assert!(!detect_oll_parity(&cube) && !detect_pll_parity(&cube), "{}x{} parity left", size, size);
+        }
+    }
+
     #[test]
     fn test_edge_piece_creation() {
         let edge1 = EdgePiece::new(Color::White, Color::Red);
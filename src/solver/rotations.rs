@@ -0,0 +1,326 @@
+//! Rewriting solutions around whole-cube rotations
+//!
+//! Solvers are free to rotate the cube (e.g. "y" to bring an F2L slot to
+//! the front). Some people would rather never regrip, others would rather
+//! rotate whenever it lets them keep turning R and U. [`RotationStyle`]
+//! picks one, and the rewrite keeps the solution's effect on the pieces.
+//!
+//! A turn made after rotating is the same as another turn made without
+//! rotating, so every turn is looked up in conjugation tables for the 24
+//! grips, traced on the simulator.
+
+use crate::cube::supercube::trace_sources;
+use crate::cube::Move;
+use crate::solver::solution::{MoveNote, Solution};
+
+/// Every layer turn as (quarter, inverse, half)
+pub(crate) const TURNS: [[Move; 3]; 9] = [
+    [Move::R, Move::RPrime, Move::R2],
+    [Move::L, Move::LPrime, Move::L2],
+    [Move::U, Move::UPrime, Move::U2],
+    [Move::D, Move::DPrime, Move::D2],
+    [Move::F, Move::FPrime, Move::F2],
+    [Move::B, Move::BPrime, Move::B2],
+    [Move::M, Move::MPrime, Move::M2],
+    [Move::E, Move::EPrime, Move::E2],
+    [Move::S, Move::SPrime, Move::S2],
+];
+
+/// Whole-cube rotations reaching each of the 24 grips, shortest first
+pub(crate) const ROTATIONS: [&[Move]; 24] = [
+    &[],
+    &[Move::Y],
+    &[Move::Y2],
+    &[Move::YPrime],
+    &[Move::X],
+    &[Move::X, Move::Y],
+    &[Move::X, Move::Y2],
+    &[Move::X, Move::YPrime],
+    &[Move::X2],
+    &[Move::X2, Move::Y],
+    &[Move::X2, Move::Y2],
+    &[Move::X2, Move::YPrime],
+    &[Move::XPrime],
+    &[Move::XPrime, Move::Y],
+    &[Move::XPrime, Move::Y2],
+    &[Move::XPrime, Move::YPrime],
+    &[Move::Z],
+    &[Move::Z, Move::Y],
+    &[Move::Z, Move::Y2],
+    &[Move::Z, Move::YPrime],
+    &[Move::ZPrime],
+    &[Move::ZPrime, Move::Y],
+    &[Move::ZPrime, Move::Y2],
+    &[Move::ZPrime, Move::YPrime],
+];
+
+/// Combine two turns made one after the other
+///
+/// Returns `None` if they turn different layers, `Some(None)` if they
+/// cancel ("U U'"), and the single turn they make otherwise ("R R" -> "R2").
+pub(crate) fn combine_turns(first: Move, second: Move) -> Option<Option<Move>> {
+    let (a, b) = (turn_index(first)?, turn_index(second)?);
+    if a / 3 != b / 3 {
+        return None;
+    }
+    let quarters = |index: usize| [1, 3, 2][index % 3];
+    Some(match (quarters(a) + quarters(b)) % 4 {
+        0 => None,
+        quarter => Some(TURNS[a / 3][[0, 0, 2, 1][quarter]]),
+    })
+}
+
+/// Shortest run of R and U turns worth a regrip
+const MIN_REGRIP_RUN: usize = 3;
+
+/// How a solution handles whole-cube rotations
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RotationStyle {
+    /// Leave the solution as the solver wrote it
+    #[default]
+    Keep,
+    /// Never rotate: every turn is rewritten for the starting grip
+    Remove,
+    /// Rotate before runs of turns that become R and U turns, so the
+    /// solution is mostly R and U
+    PreferRU,
+}
+
+impl RotationStyle {
+    /// Rewrite `moves` in this style
+    ///
+    /// `Remove` and `PreferRU` leave the cube as the rotationless solution
+    /// does: the same pieces in the same places, held in the starting grip.
+    pub fn apply(self, moves: &[Move]) -> Vec<Move> {
+        let mut rewriter = Rewriter::new(self);
+        let (mut rewritten, _) = rewriter.rewrite(moves);
+        rewritten.extend(rewriter.finish());
+        rewritten
+    }
+}
+
+/// Rewrite a sequence without cube rotations
+///
+/// The cube ends up held the way it started, so a solution that ended
+/// rotated now ends in the starting grip.
+pub fn remove_rotations(moves: &[Move]) -> Vec<Move> {
+    RotationStyle::Remove.apply(moves)
+}
+
+impl Solution {
+    /// The same solution with rotations handled in `style`
+    ///
+    /// Steps keep their descriptions, and notes are moved to cover the
+    /// rewritten moves. Rotations carry over from one step to the next.
+    pub fn with_rotation_style(&self, style: RotationStyle) -> Solution {
+        let mut solution = self.clone();
+        let mut rewriter = Rewriter::new(style);
+        for step in &mut solution.steps {
+            let (moves, index) = rewriter.rewrite(&step.moves);
+            step.notes = step
+                .notes
+                .iter()
+                .map(|note| {
+                    let start = index[note.start.min(step.moves.len())];
+                    let end = index[(note.start + note.len).min(step.moves.len())];
+                    MoveNote::new(start, end - start, note.purpose.clone())
+                })
+                .collect();
+            step.moves = moves;
+        }
+        if let Some(last) = solution.steps.last_mut() {
+            last.moves.extend(rewriter.finish());
+        }
+        solution
+    }
+}
+
+/// Rewrites moves step by step, carrying the grips between steps
+struct Rewriter {
+    style: RotationStyle,
+    /// Where each grip leaves every sticker
+    grips: Vec<Vec<usize>>,
+    /// `held[grip][turn]`: the turn to make in `grip` for `turn` of the
+    /// unrotated cube
+    held: Vec<Vec<usize>>,
+    /// Grip the input moves are made in
+    input: usize,
+    /// Grip the rewritten moves are made in
+    output: usize,
+}
+
+impl Rewriter {
+    fn new(style: RotationStyle) -> Self {
+        let turns: Vec<Vec<usize>> = (0..TURNS.len() * 3).map(|t| trace(&[turn(t)])).collect();
+        let grips: Vec<Vec<usize>> = ROTATIONS.iter().map(|grip| trace(grip)).collect();
+        let held = ROTATIONS
+            .iter()
+            .map(|grip| {
+                (0..turns.len())
+                    .map(|t| {
+                        // Rotating back, turning, then rotating again
+                        let mut conjugate: Vec<Move> = grip.iter().rev().map(|m| m.inverse()).collect();
+                        conjugate.push(turn(t));
+                        conjugate.extend(grip.iter());
+                        let table = trace(&conjugate);
+                        turns.iter().position(|t| *t == table).unwrap_or(t)
+                    })
+                    .collect()
+            })
+            .collect();
+        Self { style, grips, held, input: 0, output: 0 }
+    }
+
+    /// Rewrite one run of moves; also returns where each input move (and
+    /// the end) landed in the output
+    fn rewrite(&mut self, moves: &[Move]) -> (Vec<Move>, Vec<usize>) {
+        if self.style == RotationStyle::Keep {
+            return (moves.to_vec(), (0..=moves.len()).collect());
+        }
+
+        // Turns of the unrotated cube, and the input move each came from
+        let mut fixed: Vec<(usize, usize)> = Vec::with_capacity(moves.len());
+        for (i, mv) in moves.iter().enumerate() {
+            match turn_index(*mv) {
+                Some(t) => {
+                    let input = self.input;
+                    let f = (0..self.held[input].len()).find(|f| self.held[input][*f] == t).unwrap_or(t);
+                    fixed.push((i, f));
+                }
+                None => self.input = self.rotate(self.input, &[*mv]),
+            }
+        }
+
+        let mut rewritten = Vec::with_capacity(moves.len());
+        let mut index = vec![0; moves.len() + 1];
+        let mut next = 0;
+        for (k, (i, f)) in fixed.iter().enumerate() {
+            while next <= *i {
+                index[next] = rewritten.len();
+                next += 1;
+            }
+            if self.style == RotationStyle::PreferRU && !is_r_or_u(self.held[self.output][*f]) {
+                let run = |grip: usize| fixed[k..].iter().take_while(|(_, f)| is_r_or_u(self.held[grip][*f])).count();
+                let best = (0..ROTATIONS.len()).max_by_key(|g| (run(*g), std::cmp::Reverse(ROTATIONS[*g].len())));
+                if let Some(best) = best.filter(|g| run(*g) >= MIN_REGRIP_RUN) {
+                    rewritten.extend(self.regrip(best));
+                }
+            }
+            rewritten.push(turn(self.held[self.output][*f]));
+        }
+        while next <= moves.len() {
+            index[next] = rewritten.len();
+            next += 1;
+        }
+        (rewritten, index)
+    }
+
+    /// Rotation back to the starting grip, if the output moved away from it
+    fn finish(&mut self) -> Vec<Move> {
+        self.regrip(0)
+    }
+
+    /// Rotate the output to `grip`
+    fn regrip(&mut self, grip: usize) -> Vec<Move> {
+        let from = self.output;
+        let rotation = ROTATIONS.iter().find(|r| self.rotate(from, r) == grip).copied().unwrap_or_default();
+        self.output = grip;
+        rotation.to_vec()
+    }
+
+    /// Grip after rotating `grip` by `rotation`
+    fn rotate(&self, grip: usize, rotation: &[Move]) -> usize {
+        let mut moves = ROTATIONS[grip].to_vec();
+        moves.extend(rotation);
+        let table = trace(&moves);
+        self.grips.iter().position(|g| *g == table).unwrap_or(grip)
+    }
+}
+
+/// Where a sequence leaves every sticker of a 3x3
+fn trace(moves: &[Move]) -> Vec<usize> {
+    trace_sources(3, |cube| cube.apply_moves(moves))
+}
+
+/// A layer turn by its position in [`TURNS`]
+fn turn(index: usize) -> Move {
+    TURNS[index / 3][index % 3]
+}
+
+/// Position of a layer turn in [`TURNS`]; `None` for rotations
+fn turn_index(mv: Move) -> Option<usize> {
+    TURNS.iter().flatten().position(|m| *m == mv)
+}
+
+fn is_r_or_u(index: usize) -> bool {
+    matches!(index / 3, 0 | 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Cube;
+    use crate::solver::SolutionStep;
+
+    fn after(moves: &[Move]) -> Cube {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::F2, Move::D]);
+        cube.apply_moves(moves);
+        cube
+    }
+
+    #[test]
+    fn test_rotations_are_removed() {
+        let moves = [Move::Y, Move::R, Move::U, Move::YPrime, Move::F, Move::X, Move::M];
+        let plain = remove_rotations(&moves);
+        assert!(!plain.iter().any(Move::is_rotation));
+        assert_eq!(plain.len(), 4);
+        assert!(after(&moves).orientations().contains(&after(&plain)));
+        assert_eq!(remove_rotations(&[Move::R, Move::U]), vec![Move::R, Move::U]);
+    }
+
+    #[test]
+    fn test_combine_turns() {
+        assert_eq!(combine_turns(Move::R, Move::R), Some(Some(Move::R2)));
+        assert_eq!(combine_turns(Move::U2, Move::U), Some(Some(Move::UPrime)));
+        assert_eq!(combine_turns(Move::M, Move::MPrime), Some(None));
+        assert_eq!(combine_turns(Move::R, Move::L), None);
+        assert_eq!(combine_turns(Move::Y, Move::Y), None);
+    }
+
+    #[test]
+    fn test_prefer_ru_regrips() {
+        let moves = [Move::F, Move::U, Move::FPrime, Move::UPrime];
+        let regripped = RotationStyle::PreferRU.apply(&moves);
+        assert!(regripped[0].is_rotation());
+        let turns: Vec<&Move> = regripped.iter().filter(|m| !m.is_rotation()).collect();
+        assert!(turns.iter().all(|m| is_r_or_u(turn_index(**m).unwrap())), "{:?}", regripped);
+        assert_eq!(after(&regripped), after(&moves));
+    }
+
+    #[test]
+    fn test_short_runs_are_not_worth_a_regrip() {
+        let moves = [Move::R, Move::U, Move::F, Move::R, Move::U];
+        assert_eq!(RotationStyle::PreferRU.apply(&moves), moves.to_vec());
+        assert_eq!(RotationStyle::Keep.apply(&[Move::Y, Move::R]), vec![Move::Y, Move::R]);
+    }
+
+    #[test]
+    fn test_solution_steps_and_notes() {
+        let solution = Solution::new(
+            vec![
+                SolutionStep::new("First", vec![Move::R, Move::Y, Move::R]).with_note(1, 2, "rotate and turn"),
+                SolutionStep::new("Second", vec![Move::F, Move::YPrime]).with_note(0, 1, "turn"),
+            ],
+            0,
+        );
+        let plain = solution.with_rotation_style(RotationStyle::Remove);
+        assert_eq!(plain.steps[0].moves.len(), 2);
+        assert_eq!(plain.steps[0].notes[0], MoveNote::new(1, 1, "rotate and turn"));
+        // F after y is R in the starting grip
+        assert_eq!(plain.steps[1].moves, remove_rotations(&[Move::Y, Move::F]));
+        assert_eq!(plain.steps[1].notes[0], MoveNote::new(0, 1, "turn"));
+        assert_eq!(after(&plain.all_moves()), after(&solution.all_moves()));
+        assert_eq!(plain.method, solution.method);
+    }
+}
@@ -0,0 +1,168 @@
+//! Setup moves for practice cases
+//!
+//! Practising a case (an F2L pair, an OLL, a PLL) starts from a solved cube
+//! and needs moves that produce the case. Solving the case state and
+//! inverting the solution gives exactly that. Several solvers are tried and
+//! the shortest setup wins:
+//! - a brute-force search, for states a few moves from solved
+//! - the last-layer solver, for last-layer cases
+//! - cross, F2L pairs and last layer, for F2L cases
+//! - blindfold solving, which handles any 3x3 state
+//!
+//! Pieces that are not part of the case are left solved in the case state,
+//! so the setup leaves them solved too.
+
+use crate::cube::{Cube, Move};
+use crate::solver::beginner_3x3::solve_at_depth;
+use crate::solver::blind::solve_blind;
+use crate::solver::layer_by_layer::solve_layer_by_layer;
+use crate::solver::rotations::{combine_turns, remove_rotations, ROTATIONS, TURNS};
+use crate::solver::two_by_two::solve_2x2;
+
+/// Deepest brute-force search; deeper cases come from the other solvers
+const SEARCH_DEPTH: usize = 4;
+
+/// Find a short move sequence that turns a solved cube into `target`
+///
+/// Works on 2x2 and 3x3 cubes. If `target` is held in a different
+/// orientation than the standard color scheme, the setup starts with the
+/// rotation that puts it there.
+///
+/// # Example
+/// ```
+/// use rubiks_cube_solver::cube::{Cube, Move};
+/// use rubiks_cube_solver::solver::setup_moves;
+///
+/// let mut case = Cube::new(3);
+/// case.apply_moves(&[Move::R, Move::U, Move::RPrime]);
+/// let setup = setup_moves(&case).expect("Should find a setup");
+/// assert_eq!(setup, vec![Move::R, Move::U, Move::RPrime]);
+/// ```
+pub fn setup_moves(target: &Cube) -> Result<Vec<Move>, String> {
+    let solutions = match target.size() {
+        2 => vec![solve_2x2(target)?.moves],
+        3 => solutions_3x3(target),
+        n => return Err(format!("Setup moves need a 2x2 or 3x3 cube (got {}x{})", n, n)),
+    };
+    solutions
+        .iter()
+        .filter_map(|solution| setup_from_solution(target, solution))
+        .min_by_key(|setup| setup.len())
+        .ok_or_else(|| "The cube cannot be reached from solved".to_string())
+}
+
+/// Solutions for a 3x3 from every solver that handles it
+fn solutions_3x3(target: &Cube) -> Vec<Vec<Move>> {
+    let mut solutions = Vec::new();
+    let face_turns: Vec<Move> = TURNS[..6].iter().flatten().copied().collect();
+    if let Some(moves) = (0..=SEARCH_DEPTH).find_map(|depth| match depth {
+        0 => target.is_solved().then(Vec::new),
+        _ => solve_at_depth(target, depth, &face_turns),
+    }) {
+        solutions.push(moves);
+    }
+    if let Ok(solution) = solve_layer_by_layer(target) {
+        solutions.push(solution.all_moves());
+    }
+    if let Ok((_, solution)) = solve_blind(target) {
+        solutions.push(solution.all_moves());
+    }
+    solutions
+}
+
+/// Invert a solution of `target` into a setup from the standard solved cube
+///
+/// Returns `None` if the solution does not actually produce `target`.
+fn setup_from_solution(target: &Cube, solution: &[Move]) -> Option<Vec<Move>> {
+    let solution = merge_turns(&remove_rotations(solution));
+    let setup: Vec<Move> = solution.iter().rev().map(|m| m.inverse()).collect();
+
+    ROTATIONS.iter().find_map(|rotation| {
+        let mut moves = rotation.to_vec();
+        moves.extend(&setup);
+        let mut cube = Cube::new(target.size());
+        cube.apply_moves(&moves);
+        (cube == *target).then_some(moves)
+    })
+}
+
+/// Merge neighbouring turns of the same layer, e.g. "R R" -> "R2" and "U U'" -> ""
+pub(crate) fn merge_turns(moves: &[Move]) -> Vec<Move> {
+    let mut merged: Vec<Move> = Vec::with_capacity(moves.len());
+    for mv in moves {
+        match merged.last().and_then(|last| combine_turns(*last, *mv)) {
+            Some(combined) => {
+                merged.pop();
+                merged.extend(combined);
+            }
+            None => merged.push(*mv),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::{Color, FaceName};
+    use crate::solver::f2l::f2l_cases;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn produces(setup: &[Move], target: &Cube) -> bool {
+        let mut cube = Cube::new(target.size());
+        cube.apply_moves(setup);
+        cube == *target
+    }
+
+    #[test]
+    fn test_merge_turns() {
+        assert_eq!(merge_turns(&[Move::R, Move::R, Move::U]), vec![Move::R2, Move::U]);
+        assert_eq!(merge_turns(&[Move::R, Move::U, Move::UPrime, Move::RPrime, Move::F]), vec![Move::F]);
+        assert_eq!(merge_turns(&[Move::U2, Move::U, Move::M]), vec![Move::UPrime, Move::M]);
+        assert_eq!(merge_turns(&[Move::R, Move::L]), vec![Move::R, Move::L]);
+    }
+
+    #[test]
+    fn test_short_setup_is_found() {
+        let mut case = Cube::new(3);
+        case.apply_moves(&[Move::F, Move::R2, Move::D]);
+        assert_eq!(setup_moves(&case).unwrap(), vec![Move::F, Move::R2, Move::D]);
+        assert!(setup_moves(&Cube::new(3)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_f2l_case_setups() {
+        for case in f2l_cases().iter().step_by(5) {
+            let target = case.case_state();
+            let setup = setup_moves(&target).unwrap();
+            assert!(produces(&setup, &target), "F2L case {}", case.number);
+            assert!(setup.len() <= 30, "F2L case {} setup has {} moves", case.number, setup.len());
+        }
+    }
+
+    #[test]
+    fn test_random_state_setup() {
+        let target = Cube::random_state(3, &mut StdRng::seed_from_u64(11));
+        let setup = setup_moves(&target).unwrap();
+        assert!(produces(&setup, &target));
+    }
+
+    #[test]
+    fn test_rotated_target_starts_with_rotation() {
+        let mut target = Cube::new(3);
+        target.apply_moves(&[Move::X, Move::R]);
+        let setup = setup_moves(&target).unwrap();
+        assert!(produces(&setup, &target));
+        assert!(setup[0].is_rotation());
+        assert_ne!(target.get_face(FaceName::U).get(1, 1), Color::White);
+    }
+
+    #[test]
+    fn test_2x2_and_unsupported_sizes() {
+        let mut target = Cube::new(2);
+        target.apply_moves(&[Move::R, Move::U2, Move::F]);
+        assert!(produces(&setup_moves(&target).unwrap(), &target));
+        assert!(setup_moves(&Cube::new(4)).is_err());
+    }
+}
@@ -2,8 +2,42 @@
 //!
 //! This module provides a structured way to represent cube solutions
 //! with logical steps and descriptions for educational purposes.
+//!
+//! Steps of big-cube solutions can turn inner layers, which a [`Move`]
+//! can't express; those steps keep their turns as [`ParsedMove`]s instead.
+
+use crate::cube::notation::algorithm_to_notation;
+use crate::cube::{Cube, Direction, LayerMove, Move, ParsedMove, WideMove};
+use crate::solver::rotations::combine_turns;
+
+/// Purpose of a run of moves within a step
+///
+/// Example: moves 3-5 "insert the pair"
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveNote {
+    /// Index of the first move, within the step
+    pub start: usize,
+    /// Number of moves covered
+    pub len: usize,
+    /// Short human-readable purpose
+    pub purpose: String,
+}
+
+impl MoveNote {
+    /// Create a note for `len` moves starting at `start`
+    pub fn new(start: usize, len: usize, purpose: impl Into<String>) -> Self {
+        Self {
+            start,
+            len,
+            purpose: purpose.into(),
+        }
+    }
 
-use crate::cube::Move;
+    /// Check whether a move index (within the step) is covered
+    pub fn covers(&self, index: usize) -> bool {
+        index >= self.start && index < self.start + self.len
+    }
+}
 
 /// A single step in a solution with description and moves
 #[derive(Debug, Clone, PartialEq)]
@@ -12,8 +46,13 @@ pub struct SolutionStep {
     pub description: String,
     /// The moves that accomplish this step
     pub moves: Vec<Move>,
+    /// The moves, when some turn inner layers (wide or layer moves);
+    /// `moves` is then empty
+    pub layer_moves: Vec<ParsedMove>,
     /// Optional detailed explanation for educational purposes
     pub explanation: Option<String>,
+    /// Purposes of individual moves or runs of moves
+    pub notes: Vec<MoveNote>,
 }
 
 impl SolutionStep {
@@ -22,10 +61,22 @@ impl SolutionStep {
         Self {
             description: description.into(),
             moves,
+            layer_moves: Vec::new(),
             explanation: None,
+            notes: Vec::new(),
         }
     }
 
+    /// Create a solution step from moves that may turn inner layers
+    ///
+    /// Moves that are all face, slice and rotation moves become the step's
+    /// `moves`; otherwise they are kept as `layer_moves`.
+    pub fn with_layer_moves(description: impl Into<String>, moves: Vec<ParsedMove>) -> Self {
+        let mut step = Self::new(description, Vec::new());
+        step.set_parsed_moves(moves);
+        step
+    }
+
     /// Create a new solution step with explanation
     pub fn with_explanation(
         description: impl Into<String>,
@@ -35,22 +86,100 @@ impl SolutionStep {
         Self {
             description: description.into(),
             moves,
+            layer_moves: Vec::new(),
             explanation: Some(explanation.into()),
+            notes: Vec::new(),
+        }
+    }
+
+    /// Add a purpose for `len` moves starting at `start`
+    pub fn with_note(mut self, start: usize, len: usize, purpose: impl Into<String>) -> Self {
+        self.notes.push(MoveNote::new(start, len, purpose));
+        self
+    }
+
+    /// Add several move purposes
+    pub fn with_notes(mut self, notes: impl IntoIterator<Item = MoveNote>) -> Self {
+        self.notes.extend(notes);
+        self
+    }
+
+    /// Get the note covering a move of this step, if any
+    pub fn note_for(&self, index: usize) -> Option<&MoveNote> {
+        self.notes.iter().find(|note| note.covers(index))
+    }
+
+    /// Get the step's moves, whichever kind it holds
+    pub fn parsed_moves(&self) -> Vec<ParsedMove> {
+        if self.layer_moves.is_empty() {
+            self.moves.iter().map(|m| ParsedMove::Basic(*m)).collect()
+        } else {
+            self.layer_moves.clone()
         }
     }
 
+    /// Replace the step's moves, as `moves` if they are all face, slice
+    /// and rotation moves and as `layer_moves` otherwise
+    fn set_parsed_moves(&mut self, moves: Vec<ParsedMove>) {
+        let basic: Option<Vec<Move>> = moves
+            .iter()
+            .map(|m| match m {
+                ParsedMove::Basic(mv) => Some(*mv),
+                _ => None,
+            })
+            .collect();
+        (self.moves, self.layer_moves) = match basic {
+            Some(basic) => (basic, Vec::new()),
+            None => (Vec::new(), moves),
+        };
+    }
+
     /// Get the number of moves in this step
     pub fn move_count(&self) -> usize {
-        self.moves.len()
+        self.moves.len() + self.layer_moves.len()
     }
 
     /// Convert moves to notation string
     pub fn to_notation(&self) -> String {
-        self.moves
-            .iter()
-            .map(|m| m.to_notation())
-            .collect::<Vec<_>>()
-            .join(" ")
+        algorithm_to_notation(&self.parsed_moves())
+    }
+}
+
+/// Quarter turns in a direction (clockwise = 1)
+fn quarters(direction: Direction) -> usize {
+    match direction {
+        Direction::Clockwise => 1,
+        Direction::Double => 2,
+        Direction::CounterClockwise => 3,
+    }
+}
+
+/// Direction of `quarters` clockwise quarter turns, or None for no turn
+fn direction_of(quarters: usize) -> Option<Direction> {
+    match quarters % 4 {
+        1 => Some(Direction::Clockwise),
+        2 => Some(Direction::Double),
+        3 => Some(Direction::CounterClockwise),
+        _ => None,
+    }
+}
+
+/// Combine two moves made one after the other, like [`combine_turns`]
+///
+/// Wide and layer moves combine when they turn the same layers of the same
+/// face.
+fn combine_moves(first: &ParsedMove, second: &ParsedMove) -> Option<Option<ParsedMove>> {
+    match (first, second) {
+        (ParsedMove::Basic(a), ParsedMove::Basic(b)) => combine_turns(*a, *b).map(|m| m.map(ParsedMove::Basic)),
+        (ParsedMove::Wide(a), ParsedMove::Wide(b)) if (a.face, a.depth) == (b.face, b.depth) => {
+            let direction = direction_of(quarters(a.direction) + quarters(b.direction));
+            Some(direction.map(|direction| ParsedMove::Wide(WideMove { direction, ..*a })))
+        }
+        (ParsedMove::Layer(a), ParsedMove::Layer(b)) if (a.face, a.first, a.last) == (b.face, b.first, b.last) => {
+            let direction = direction_of(quarters(a.direction) + quarters(b.direction));
+            Some(direction.map(|direction| ParsedMove::Layer(LayerMove { direction, ..*a })))
+        }
+        _ => None,
     }
 }
 
@@ -88,7 +217,8 @@ impl Solution {
         }
     }
 
-    /// Get all moves from all steps as a flat list
+    /// Get all face, slice and rotation moves from all steps as a flat
+    /// list (see `all_parsed_moves` for steps that turn inner layers)
     pub fn all_moves(&self) -> Vec<Move> {
         self.steps
             .iter()
@@ -97,9 +227,90 @@ impl Solution {
             .collect()
     }
 
+    /// Get every move from every step as a flat list, inner-layer turns
+    /// included
+    pub fn all_parsed_moves(&self) -> Vec<ParsedMove> {
+        self.steps.iter().flat_map(SolutionStep::parsed_moves).collect()
+    }
+
+    /// Apply every move of the solution to a cube
+    pub fn apply_to(&self, cube: &mut Cube) {
+        cube.apply_parsed_moves(&self.all_parsed_moves());
+    }
+
+    /// This solution followed by `other`, as one solution
+    ///
+    /// Turns that cancel or merge where the two meet ("R U" then "U' R'")
+    /// are removed, so the move count is that of the merged moves. Times
+    /// add up; the methods are joined with " + ".
+    pub fn concat(&self, other: &Solution) -> Solution {
+        let mut steps = self.steps.clone();
+        steps.extend(other.steps.iter().cloned());
+        let method = match (&self.method, &other.method) {
+            (Some(first), Some(second)) if first != second => Some(format!("{} + {}", first, second)),
+            (first, second) => first.clone().or_else(|| second.clone()),
+        };
+        Solution { steps, time_ms: self.time_ms + other.time_ms, method }.trimmed()
+    }
+
+    /// The same solution with neighbouring turns of the same layers merged,
+    /// within and across steps: "R R" becomes "R2", "U U'" disappears,
+    /// "Rw Rw" becomes "Rw2"
+    ///
+    /// A merged turn stays in the step of its first half. Steps left
+    /// without moves are kept, and notes shrink to the moves that remain.
+    pub fn trimmed(&self) -> Solution {
+        // Remaining moves as (step, index within the step, move)
+        let mut kept: Vec<(usize, usize, ParsedMove)> = Vec::new();
+        for (step_index, step) in self.steps.iter().enumerate() {
+            for (index, mv) in step.parsed_moves().into_iter().enumerate() {
+                match kept.last().and_then(|(owner, at, last)| Some((*owner, *at, combine_moves(last, &mv)?))) {
+                    Some((owner, at, combined)) => {
+                        kept.pop();
+                        kept.extend(combined.map(|m| (owner, at, m)));
+                    }
+                    None => kept.push((step_index, index, mv)),
+                }
+            }
+        }
+
+        let mut solution = self.clone();
+        for (step_index, step) in solution.steps.iter_mut().enumerate() {
+            let remaining: Vec<(usize, ParsedMove)> = kept
+                .iter()
+                .filter(|(owner, _, _)| *owner == step_index)
+                .map(|(_, at, mv)| (*at, mv.clone()))
+                .collect();
+            let before = |index: usize| remaining.iter().filter(|(at, _)| *at < index).count();
+            step.notes = step
+                .notes
+                .iter()
+                .map(|note| {
+                    let start = before(note.start);
+                    MoveNote::new(start, before(note.start + note.len) - start, note.purpose.clone())
+                })
+                .filter(|note| note.len > 0)
+                .collect();
+            step.set_parsed_moves(remaining.into_iter().map(|(_, mv)| mv).collect());
+        }
+        solution
+    }
+
+    /// Get the purpose of a move by its index in `all_moves`
+    pub fn purpose_at(&self, index: usize) -> Option<&str> {
+        let mut offset = 0;
+        for step in &self.steps {
+            if index < offset + step.moves.len() {
+                return step.note_for(index - offset).map(|note| note.purpose.as_str());
+            }
+            offset += step.moves.len();
+        }
+        None
+    }
+
     /// Get the total number of moves in the solution
     pub fn move_count(&self) -> usize {
-        self.all_moves().len()
+        self.steps.iter().map(SolutionStep::move_count).sum()
     }
 
     /// Get the number of steps in the solution
@@ -109,11 +320,7 @@ impl Solution {
 
     /// Convert the entire solution to notation string
     pub fn to_notation(&self) -> String {
-        self.all_moves()
-            .iter()
-            .map(|m| m.to_notation())
-            .collect::<Vec<_>>()
-            .join(" ")
+        algorithm_to_notation(&self.all_parsed_moves())
     }
 
     /// Get a summary of the solution
@@ -132,6 +339,91 @@ impl Solution {
             self.time_ms
         )
     }
+
+    /// Build an alg.cubing.net link that replays this solution
+    ///
+    /// The setup moves (usually the scramble) are applied first, then each
+    /// solution step is shown on its own line with its description as a comment.
+    pub fn to_alg_cubing_url(&self, setup: &[Move]) -> String {
+        let setup_notation = setup
+            .iter()
+            .map(|m| m.to_notation())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let alg = self
+            .steps
+            .iter()
+            .filter(|step| step.move_count() > 0)
+            .map(|step| format!("{} // {}", step.to_notation(), step.description))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut url = format!(
+            "{}?setup={}&alg={}",
+            ALG_CUBING_BASE_URL,
+            encode_alg_cubing(&setup_notation),
+            encode_alg_cubing(&alg)
+        );
+
+        if let Some(method) = &self.method {
+            url.push_str("&title=");
+            // Titles are plain text, so keep apostrophes instead of turning them into primes
+            url.push_str(&encode_text(method));
+        }
+
+        url
+    }
+}
+
+/// Base URL of the alg.cubing.net viewer
+pub const ALG_CUBING_BASE_URL: &str = "https://alg.cubing.net/";
+
+/// Encode an algorithm for an alg.cubing.net query parameter
+///
+/// alg.cubing.net uses `_` for spaces and `-` for the prime apostrophe;
+/// everything else outside the unreserved set is percent-encoded.
+/// Apostrophes in `//` comments are text, not primes, and stay apostrophes.
+fn encode_alg_cubing(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    let mut in_comment = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => in_comment = false,
+            '/' if chars.peek() == Some(&'/') => in_comment = true,
+            _ => {}
+        }
+        if c == '\'' && !in_comment {
+            encoded.push('-');
+        } else {
+            push_encoded(&mut encoded, c);
+        }
+    }
+    encoded
+}
+
+/// Encode plain text (such as a title) for an alg.cubing.net query parameter
+fn encode_text(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for c in text.chars() {
+        push_encoded(&mut encoded, c);
+    }
+    encoded
+}
+
+/// Append one character the way alg.cubing.net expects outside moves
+fn push_encoded(encoded: &mut String, c: char) {
+    match c {
+        ' ' => encoded.push('_'),
+        c if c.is_ascii_alphanumeric() || c == '.' || c == '~' => encoded.push(c),
+        c => {
+            let mut buf = [0u8; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -241,4 +533,128 @@ mod tests {
         assert_eq!(step.move_count(), 0);
         assert_eq!(step.to_notation(), "");
     }
+
+    #[test]
+    fn test_to_alg_cubing_url() {
+        let steps = vec![
+            SolutionStep::new("Cross", vec![Move::R, Move::UPrime]),
+            SolutionStep::new("Finish", vec![Move::F2]),
+        ];
+        let solution = Solution::new(steps, 10);
+        let url = solution.to_alg_cubing_url(&[Move::U, Move::RPrime]);
+
+        assert_eq!(
+            url,
+            "https://alg.cubing.net/?setup=U_R-&alg=R_U-_%2F%2F_Cross%0AF2_%2F%2F_Finish"
+        );
+    }
+
+    #[test]
+    fn test_to_alg_cubing_url_includes_method_title() {
+        let steps = vec![SolutionStep::new("Solve", vec![Move::R])];
+        let solution = Solution::with_method(steps, 5, "Beginner's Method");
+        let url = solution.to_alg_cubing_url(&[]);
+
+        assert!(url.starts_with("https://alg.cubing.net/?setup=&alg=R_"));
+        assert!(url.ends_with("&title=Beginner%27s_Method"));
+    }
+
+    #[test]
+    fn test_to_alg_cubing_url_keeps_apostrophes_in_comments() {
+        let steps = vec![SolutionStep::new("Solve U' face", vec![Move::UPrime])];
+        let solution = Solution::new(steps, 5);
+        let url = solution.to_alg_cubing_url(&[Move::RPrime]);
+
+        assert_eq!(url, "https://alg.cubing.net/?setup=R-&alg=U-_%2F%2F_Solve_U%27_face");
+    }
+
+    #[test]
+    fn test_step_notes() {
+        let step = SolutionStep::new("Insert pair", vec![Move::U, Move::R, Move::UPrime, Move::RPrime])
+            .with_note(0, 1, "set up the pair")
+            .with_note(1, 3, "insert the pair");
+
+        assert_eq!(step.note_for(0).unwrap().purpose, "set up the pair");
+        assert_eq!(step.note_for(3).unwrap().purpose, "insert the pair");
+        assert!(step.note_for(4).is_none());
+    }
+
+    #[test]
+    fn test_purpose_at_spans_steps() {
+        let steps = vec![
+            SolutionStep::new("Cross", vec![Move::R, Move::U]).with_note(1, 1, "place the edge"),
+            SolutionStep::new("Finish", vec![Move::F2]).with_note(0, 1, "finish the cube"),
+        ];
+        let solution = Solution::new(steps, 0);
+
+        assert_eq!(solution.purpose_at(0), None);
+        assert_eq!(solution.purpose_at(1), Some("place the edge"));
+        assert_eq!(solution.purpose_at(2), Some("finish the cube"));
+        assert_eq!(solution.purpose_at(3), None);
+    }
+
+    #[test]
+    fn test_concat_cancels_at_the_join() {
+        let first = Solution::with_method(
+            vec![SolutionStep::new("Colors", vec![Move::F, Move::R, Move::U]).with_note(1, 2, "pair up")],
+            5,
+            "Colors",
+        );
+        let second = Solution::with_method(
+            vec![SolutionStep::new("Centers", vec![Move::UPrime, Move::RPrime, Move::D]).with_note(2, 1, "twist")],
+            7,
+            "Centers",
+        );
+        let merged = first.concat(&second);
+
+        assert_eq!(merged.all_moves(), vec![Move::F, Move::D]);
+        assert_eq!(merged.move_count(), 2);
+        assert_eq!(merged.step_count(), 2);
+        assert!(merged.steps[0].notes.is_empty());
+        assert_eq!(merged.steps[1].notes, vec![MoveNote::new(0, 1, "twist")]);
+        assert_eq!(merged.time_ms, 12);
+        assert_eq!(merged.method.as_deref(), Some("Colors + Centers"));
+    }
+
+    #[test]
+    fn test_trimmed_merges_turns() {
+        let solution = Solution::new(
+            vec![
+                SolutionStep::new("One", vec![Move::R, Move::R, Move::U]),
+                SolutionStep::new("Two", vec![Move::U, Move::L, Move::L2]).with_note(1, 2, "left"),
+            ],
+            0,
+        );
+        let trimmed = solution.trimmed();
+        assert_eq!(trimmed.steps[0].moves, vec![Move::R2, Move::U2]);
+        assert_eq!(trimmed.steps[1].moves, vec![Move::LPrime]);
+        assert_eq!(trimmed.steps[1].notes, vec![MoveNote::new(0, 1, "left")]);
+        assert_eq!(Solution::new(vec![], 0).concat(&solution).method, None);
+    }
+
+    #[test]
+    fn test_concat_merges_layer_moves() {
+        use crate::cube::notation::parse_algorithm;
+
+        let step = |description: &str, alg: &str| SolutionStep::with_layer_moves(description, parse_algorithm(alg).unwrap());
+        let first = Solution::new(vec![step("Centers", "Rw U 3Fw")], 0);
+        let second = Solution::new(vec![step("Edges", "3Fw Uw' R"), step("Parity", "R' Uw")], 0);
+        let merged = first.concat(&second);
+
+        assert_eq!(merged.steps[0].to_notation(), "Rw U 3Fw2");
+        // R R' cancel, and then so do Uw' and Uw, emptying both steps
+        assert_eq!(merged.step_count(), 3);
+        assert_eq!(merged.steps[1].move_count() + merged.steps[2].move_count(), 0);
+        assert_eq!(merged.to_notation(), "Rw U 3Fw2");
+        assert_eq!(merged.move_count(), 3);
+
+        let mut replayed = Cube::new(5);
+        merged.apply_to(&mut replayed);
+        let mut expected = Cube::new(5);
+        expected.apply_algorithm("Rw U 3Fw 3Fw Uw' R R' Uw").unwrap();
+        assert_eq!(replayed, expected);
+
+        // Only face turns left: the step lists them as moves
+        assert_eq!(step("Faces", "R U").moves, vec![Move::R, Move::U]);
+    }
 }
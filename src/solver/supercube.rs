@@ -0,0 +1,320 @@
+//! Center orientation solver for supercubes
+//!
+//! Once the colors of a supercube are solved, its centers may still be
+//! turned. This module finds a sequence of center-twisting algorithms that
+//! restores every center on a 3x3 without disturbing anything else.
+//!
+//! Two base algorithms are used, each applied from all 24 orientations:
+//! - a pair twist (one center clockwise, another counterclockwise)
+//! - a half twist of a single center
+//!
+//! Their effect is measured on a `SuperCube` at runtime, then a breadth-first
+//! search over the 4^6 center states picks the shortest combination.
+
+use crate::cube::notation::invert_moves;
+use crate::cube::symmetry::UP_ROTATIONS;
+use crate::cube::{FaceName, Move, SuperCube};
+use crate::solver::{solve_3x3, MoveNote, Solution, SolutionStep};
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Centers in the order used for twist states
+const CENTER_FACES: [FaceName; 6] = [
+    FaceName::U,
+    FaceName::R,
+    FaceName::F,
+    FaceName::D,
+    FaceName::L,
+    FaceName::B,
+];
+
+/// Number of center twist states (4 orientations for each of 6 centers)
+const STATE_COUNT: usize = 4096;
+
+/// Twists one center a quarter turn each way: U counterclockwise, B clockwise
+const PAIR_TWIST: [Move; 8] = [
+    Move::S,
+    Move::E,
+    Move::SPrime,
+    Move::UPrime,
+    Move::S,
+    Move::EPrime,
+    Move::SPrime,
+    Move::U,
+];
+
+/// Turns the U center by 180 degrees
+const HALF_TWIST: [Move; 12] = [
+    Move::F,
+    Move::B,
+    Move::U2,
+    Move::FPrime,
+    Move::BPrime,
+    Move::UPrime,
+    Move::B,
+    Move::F,
+    Move::U2,
+    Move::BPrime,
+    Move::FPrime,
+    Move::UPrime,
+];
+
+/// A center-twisting algorithm and its effect
+#[derive(Clone)]
+struct Macro {
+    moves: Vec<Move>,
+    /// Quarter turns added to each center, in `CENTER_FACES` order
+    effect: [u8; 6],
+}
+
+/// Read the twist of every center on a 3x3 supercube
+fn center_state(cube: &SuperCube) -> [u8; 6] {
+    CENTER_FACES.map(|face| cube.orientation(face, 1, 1))
+}
+
+fn encode(state: [u8; 6]) -> usize {
+    state.iter().fold(0, |acc, turns| acc * 4 + *turns as usize)
+}
+
+fn add(state: [u8; 6], effect: [u8; 6]) -> [u8; 6] {
+    let mut result = state;
+    for (turns, extra) in result.iter_mut().zip(effect) {
+        *turns = (*turns + extra) % 4;
+    }
+    result
+}
+
+/// Build every orientation of the base algorithms and their inverses
+///
+/// Each candidate is checked on a solved supercube; only those that keep the
+/// colors solved are kept, so a simulator change can never produce a
+/// macro that scrambles the cube.
+fn build_macros() -> Vec<Macro> {
+    let mut macros: Vec<Macro> = Vec::new();
+
+    for base in [PAIR_TWIST.to_vec(), HALF_TWIST.to_vec()] {
+        for alg in [base.clone(), invert_moves(&base)] {
+            for up in UP_ROTATIONS {
+                for y_turns in 0..4 {
+                    let mut setup = up.to_vec();
+                    setup.extend(std::iter::repeat_n(Move::Y, y_turns));
+
+                    let mut moves = setup.clone();
+                    moves.extend(&alg);
+                    moves.extend(invert_moves(&setup));
+
+                    let mut cube = SuperCube::new(3);
+                    cube.apply_moves(&moves);
+                    let effect = center_state(&cube);
+                    if !cube.cube().is_solved() || effect == [0; 6] {
+                        continue;
+                    }
+                    if macros.iter().all(|m| m.effect != effect) {
+                        macros.push(Macro { moves, effect });
+                    }
+                }
+            }
+        }
+    }
+
+    macros
+}
+
+/// Find moves that restore center orientation on a color-solved 3x3 supercube
+///
+/// Returns an empty list if the centers are already oriented. A state whose
+/// total twist is odd cannot be reached on a real cube and is rejected, as
+/// is any size but 3x3.
+pub fn restore_center_orientation(cube: &SuperCube) -> Result<Vec<Move>, String> {
+    Ok(center_macros(cube)?
+        .into_iter()
+        .flat_map(|m| m.moves)
+        .collect())
+}
+
+/// Describe what a center macro does, e.g. "twist the U and B centers"
+fn macro_purpose(effect: [u8; 6]) -> String {
+    let faces: Vec<String> = CENTER_FACES
+        .iter()
+        .zip(effect)
+        .filter(|(_, turns)| *turns != 0)
+        .map(|(face, _)| format!("{:?}", face))
+        .collect();
+    match faces.as_slice() {
+        [one] => format!("turn the {} center 180 degrees", one),
+        [first, second] => format!("twist the {} and {} centers", first, second),
+        _ => format!("twist the {} centers", faces.join(", ")),
+    }
+}
+
+/// Find the center macros that restore center orientation, in order
+fn center_macros(cube: &SuperCube) -> Result<Vec<Macro>, String> {
+    if cube.size() != 3 {
+        return Err("Center orientation can only be restored on a 3x3 supercube".to_string());
+    }
+    if !cube.cube().is_solved() {
+        return Err("Colors must be solved before restoring center orientation".to_string());
+    }
+
+    let start = center_state(cube);
+    if start == [0; 6] {
+        return Ok(Vec::new());
+    }
+
+    let macros = build_macros();
+    // For each visited state, the state it was reached from and the macro used
+    let mut previous: Vec<Option<(usize, usize)>> = vec![None; STATE_COUNT];
+    let mut visited = vec![false; STATE_COUNT];
+    let mut queue = VecDeque::new();
+
+    visited[encode(start)] = true;
+    queue.push_back(start);
+
+    while let Some(state) = queue.pop_front() {
+        if state == [0; 6] {
+            let mut path = Vec::new();
+            let mut current = encode(state);
+            while let Some((from, index)) = previous[current] {
+                path.push(macros[index].clone());
+                current = from;
+            }
+            path.reverse();
+            return Ok(path);
+        }
+
+        for (index, m) in macros.iter().enumerate() {
+            let next = add(state, m.effect);
+            let code = encode(next);
+            if !visited[code] {
+                visited[code] = true;
+                previous[code] = Some((encode(state), index));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    Err("Center orientation is unsolvable (the total twist is odd)".to_string())
+}
+
+/// Solve a 3x3 supercube: colors first, then center orientation
+///
+/// Only 3x3 supercubes are supported; other sizes are rejected.
+pub fn solve_supercube_3x3(cube: &SuperCube) -> Result<Solution, String> {
+    if cube.size() != 3 {
+        return Err(format!("Only 3x3 supercubes can be solved (got {}x{})", cube.size(), cube.size()));
+    }
+    let color_solution = solve_3x3(cube.cube())?.to_solution();
+    let start = Instant::now();
+
+    let mut solved = cube.clone();
+    solved.apply_moves(&color_solution.all_moves());
+    let macros = center_macros(&solved)?;
+    let center_moves: Vec<Move> = macros.iter().flat_map(|m| m.moves.clone()).collect();
+    let mut offset = 0;
+    let notes: Vec<MoveNote> = macros
+        .iter()
+        .map(|m| {
+            let note = MoveNote::new(offset, m.moves.len(), macro_purpose(m.effect));
+            offset += m.moves.len();
+            note
+        })
+        .collect();
+
+    let mut steps = Vec::new();
+    if !center_moves.is_empty() {
+        steps.push(SolutionStep::with_explanation(
+            "Orient centers",
+            center_moves,
+            "Twist the centers back into place without moving any other piece",
+        ).with_notes(notes));
+    }
+
+    let centers = Solution::with_method(steps, start.elapsed().as_millis(), "Supercube Centers");
+    Ok(color_solution.concat(&centers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_algorithms_keep_colors_solved() {
+        let mut pair = SuperCube::new(3);
+        pair.apply_moves(&PAIR_TWIST);
+        assert!(pair.cube().is_solved());
+        assert_eq!(center_state(&pair), [3, 0, 0, 0, 0, 1]);
+
+        let mut half = SuperCube::new(3);
+        half.apply_moves(&HALF_TWIST);
+        assert!(half.cube().is_solved());
+        assert_eq!(center_state(&half), [2, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_restore_single_half_twist() {
+        let mut cube = SuperCube::new(3);
+        cube.set_orientation(FaceName::F, 1, 1, 2);
+
+        let moves = restore_center_orientation(&cube).unwrap();
+        cube.apply_moves(&moves);
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn test_restore_every_even_state() {
+        for code in (0..STATE_COUNT).step_by(37) {
+            let mut cube = SuperCube::new(3);
+            let mut total = 0;
+            for (i, face) in CENTER_FACES.iter().enumerate() {
+                let turns = ((code >> (2 * i)) & 3) as u8;
+                total += turns as usize;
+                cube.set_orientation(*face, 1, 1, turns);
+            }
+
+            let result = restore_center_orientation(&cube);
+            if total % 2 == 1 {
+                assert!(result.is_err());
+            } else {
+                cube.apply_moves(&result.unwrap());
+                assert!(cube.is_solved(), "state {} not restored", code);
+            }
+        }
+    }
+
+    #[test]
+    fn test_restore_rejects_unsolved_colors() {
+        let mut cube = SuperCube::new(3);
+        cube.apply_move(Move::R);
+        assert!(restore_center_orientation(&cube).is_err());
+        assert!(restore_center_orientation(&SuperCube::new(4)).is_err());
+        assert!(solve_supercube_3x3(&SuperCube::new(4)).unwrap_err().contains("3x3"));
+    }
+
+    #[test]
+    fn test_solve_supercube() {
+        let mut cube = SuperCube::new(3);
+        cube.apply_moves(&[Move::R, Move::U, Move::F]);
+
+        let solution = solve_supercube_3x3(&cube).unwrap();
+        cube.apply_moves(&solution.all_moves());
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn test_center_step_notes_cover_moves() {
+        let mut cube = SuperCube::new(3);
+        cube.set_orientation(FaceName::U, 1, 1, 1);
+        cube.set_orientation(FaceName::F, 1, 1, 3);
+
+        let solution = solve_supercube_3x3(&cube).unwrap();
+        let step = solution.steps.iter().find(|s| s.description == "Orient centers").unwrap();
+        assert_eq!(step.notes.iter().map(|n| n.len).sum::<usize>(), step.moves.len());
+        assert!(step.notes.iter().all(|n| n.purpose.contains("center")));
+    }
+
+    #[test]
+    fn test_macro_purpose() {
+        assert_eq!(macro_purpose([2, 0, 0, 0, 0, 0]), "turn the U center 180 degrees");
+        assert_eq!(macro_purpose([3, 0, 0, 0, 0, 1]), "twist the U and B centers");
+    }
+}
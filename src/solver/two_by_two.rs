@@ -9,6 +9,8 @@
 //! Solutions may not be optimal but are reasonably efficient.
 
 use crate::cube::{Cube, Move};
+use crate::solver::options::SolveOptions;
+use crate::solver::parallel::{search_roots, Branch};
 use crate::solver::solution::{Solution, SolutionStep};
 use std::time::Instant;
 
@@ -77,6 +79,13 @@ impl Solution2x2 {
 /// assert!(solution.time_ms < 1000); // Under 1 second
 /// ```
 pub fn solve_2x2(cube: &Cube) -> Result<Solution2x2, String> {
+    solve_2x2_with_options(cube, &SolveOptions::default())
+}
+
+/// Solves a 2x2 Rubik's Cube using only the moves `options` allows
+///
+/// Without a restriction this searches R, U and F turns, like [`solve_2x2`].
+pub fn solve_2x2_with_options(cube: &Cube, options: &SolveOptions) -> Result<Solution2x2, String> {
     let start = Instant::now();
 
     if cube.size() != 2 {
@@ -94,38 +103,63 @@ pub fn solve_2x2(cube: &Cube) -> Result<Solution2x2, String> {
     }
 
     // Use a simple depth-limited search
-    let moves = solve_with_dls(cube)?;
+    let moves = options.finish(solve_with_dls(cube, options)?);
 
     let elapsed = start.elapsed().as_millis();
     Ok(Solution2x2::new(moves, elapsed))
 }
 
 /// Simple solver using depth-limited search
-fn solve_with_dls(cube: &Cube) -> Result<Vec<Move>, String> {
+fn solve_with_dls(cube: &Cube, options: &SolveOptions) -> Result<Vec<Move>, String> {
     // Possible moves for 2x2 - use only 3 faces to reduce search space
-    let basic_moves = vec![
+    let basic_moves = options.search_moves(&[
         Move::R, Move::RPrime, Move::R2,
         Move::U, Move::UPrime, Move::U2,
         Move::F, Move::FPrime, Move::F2,
-    ];
+    ])?;
 
     // Try increasing depths
     for depth in 1..=8 {
-        if let Some(solution) = try_solve_at_depth(cube, depth, &basic_moves, None) {
+        if let Some(solution) = solve_at_depth(cube, depth, &basic_moves) {
             return Ok(solution);
         }
     }
 
-    Err("Could not find solution within depth limit".to_string())
+    match options.describe() {
+        Some(moves) => Err(format!("Could not find solution using only {} within depth limit", moves)),
+        None => Err("Could not find solution within depth limit".to_string()),
+    }
 }
 
-/// Try to find solution at specific depth
-fn try_solve_at_depth(cube: &Cube, depth: usize, moves: &[Move], prev_move: Option<Move>) -> Option<Vec<Move>> {
+/// Try to find solution at specific depth, one root branch per first move
+fn solve_at_depth(cube: &Cube, depth: usize, moves: &[Move]) -> Option<Vec<Move>> {
     if cube.is_solved() {
         return Some(vec![]);
     }
 
-    if depth == 0 {
+    search_roots(moves.len(), |branch| {
+        let mv = moves[branch.index()];
+        let mut next_cube = cube.clone();
+        next_cube.apply_move(mv);
+        let mut rest = try_solve_at_depth(&next_cube, depth - 1, moves, Some(mv), branch)?;
+        rest.insert(0, mv);
+        Some(rest)
+    })
+}
+
+/// Try to find solution at specific depth below a root branch
+fn try_solve_at_depth(
+    cube: &Cube,
+    depth: usize,
+    moves: &[Move],
+    prev_move: Option<Move>,
+    branch: &Branch,
+) -> Option<Vec<Move>> {
+    if cube.is_solved() {
+        return Some(vec![]);
+    }
+
+    if depth == 0 || branch.is_beaten() {
         return None;
     }
 
@@ -140,7 +174,7 @@ fn try_solve_at_depth(cube: &Cube, depth: usize, moves: &[Move], prev_move: Opti
         let mut next_cube = cube.clone();
         next_cube.apply_move(mv);
 
-        if let Some(mut rest) = try_solve_at_depth(&next_cube, depth - 1, moves, Some(mv)) {
+        if let Some(mut rest) = try_solve_at_depth(&next_cube, depth - 1, moves, Some(mv), branch) {
             rest.insert(0, mv);
             return Some(rest);
         }
@@ -176,6 +210,7 @@ fn is_same_face(m1: Move, m2: Move) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cube::FaceName;
 
     #[test]
     fn test_solve_already_solved() {
@@ -233,6 +268,29 @@ mod tests {
         assert!(result.unwrap_err().contains("size 2"));
     }
 
+    #[test]
+    fn test_two_gen_solution() {
+        let mut cube = Cube::new(2);
+        cube.apply_moves(&[Move::R, Move::U, Move::RPrime, Move::U2, Move::R2]);
+
+        let solution = solve_2x2_with_options(&cube, &SolveOptions::two_gen()).unwrap();
+        assert!(solution.moves.iter().all(|mv| SolveOptions::two_gen().allows(*mv)));
+
+        let mut test_cube = cube.clone();
+        test_cube.apply_moves(&solution.moves);
+        assert!(test_cube.is_solved());
+    }
+
+    #[test]
+    fn test_restriction_without_solution() {
+        let mut cube = Cube::new(2);
+        cube.apply_move(Move::F);
+
+        let options = SolveOptions::new().with_allowed_faces(&[FaceName::R]);
+        let result = solve_2x2_with_options(&cube, &options);
+        assert!(result.unwrap_err().contains("⟨R⟩"));
+    }
+
     #[test]
     fn test_simple_scrambles() {
         for _ in 0..3 {
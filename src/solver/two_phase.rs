@@ -0,0 +1,709 @@
+//! Two-phase search for the shortest 3x3 solutions
+//!
+//! Kociemba's two-phase algorithm: phase 1 brings the cube into the
+//! subgroup ⟨U, D, R2, L2, F2, B2⟩, where every corner and edge is
+//! oriented and the middle-layer edges are in the middle layer; phase 2
+//! solves it with those moves only. Both phases are IDA* searches over a few
+//! numbers describing the cube ("coordinates"), pruned with tables of exact
+//! distances for pairs of them.
+//!
+//! The first solution turns up quickly. The search then keeps looking for
+//! shorter ones, within a [`SearchBudget`]. Every solution is some phase 1
+//! sequence followed by phase 2 moves, so once all phase 1 sequences shorter
+//! than the best solution have been tried, nothing shorter exists and the
+//! solution is proven optimal. That takes moments for cubes a dozen moves
+//! from solved; random states (18 to 20 moves, God's number being 20)
+//! usually end the budget with a near-optimal solution instead.
+//!
+//! With the `parallel` feature, each phase 1 depth is searched one first
+//! move per thread. The branches share the length of the shortest solution
+//! found so far and prune with it, and the result is the one the
+//! one-by-one search would return.
+//!
+//! The tables (about 8 MB) are built from the simulator's own face turns
+//! the first time a cube is solved.
+
+use crate::cube::{Color, Cube, FaceName, Move};
+use crate::solver::parallel::map_roots;
+use crate::solver::parity::{CORNER_SLOTS, EDGE_SLOTS};
+use crate::solver::solution::{Solution, SolutionStep};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Face turns in search order: face by face, quarter, half then inverse
+const MOVES: [Move; 18] = [
+    Move::U, Move::U2, Move::UPrime,
+    Move::R, Move::R2, Move::RPrime,
+    Move::F, Move::F2, Move::FPrime,
+    Move::D, Move::D2, Move::DPrime,
+    Move::L, Move::L2, Move::LPrime,
+    Move::B, Move::B2, Move::BPrime,
+];
+
+/// Moves that keep the cube in the phase 2 subgroup
+const PHASE2_MOVES: [usize; 10] = [0, 1, 2, 4, 7, 9, 10, 11, 13, 16];
+
+/// Number of corner twists
+const TWISTS: usize = 2187;
+/// Number of edge flips
+const FLIPS: usize = 2048;
+/// Ways to place the four middle-layer edges, ignoring their order
+const SLICES: usize = 495;
+/// Orders of the four middle-layer edges
+const SLICE_ORDERS: usize = 24;
+/// Permutations of the corners, and of the eight U and D edges
+const PERMUTATIONS: usize = 40320;
+
+/// No solution is longer: phase 1 needs at most 12 moves and phase 2 at most 18
+const MAX_LENGTH: usize = 30;
+
+/// Nodes searched between checks of the clock
+const CHECK_INTERVAL: u64 = 4096;
+
+/// How long the search may keep looking for shorter solutions
+///
+/// The budget only counts once a first solution has been found, so the
+/// search always returns one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchBudget {
+    /// Longest time to search
+    pub time: Duration,
+    /// Most search nodes to visit, or `None` for no limit
+    pub nodes: Option<u64>,
+}
+
+impl Default for SearchBudget {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(5))
+    }
+}
+
+impl SearchBudget {
+    /// Search for at most `time`
+    pub fn new(time: Duration) -> Self {
+        Self { time, nodes: None }
+    }
+
+    /// Also stop after visiting `nodes` search nodes
+    pub fn with_nodes(mut self, nodes: u64) -> Self {
+        self.nodes = Some(nodes);
+        self
+    }
+
+    fn is_spent(&self, started: Instant, nodes: u64) -> bool {
+        self.nodes.is_some_and(|limit| nodes >= limit) || started.elapsed() >= self.time
+    }
+}
+
+/// Solution found by the two-phase search
+#[derive(Debug, Clone)]
+pub struct OptimalSolution {
+    /// Moves that solve the cube
+    pub moves: Vec<Move>,
+    /// Time taken to find the solution (in milliseconds)
+    pub time_ms: u128,
+    /// Whether the search finished, so no shorter solution exists
+    pub proven_optimal: bool,
+    /// Search nodes visited
+    pub nodes: u64,
+}
+
+impl OptimalSolution {
+    /// Get the number of moves in the solution
+    pub fn move_count(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Convert to generic Solution type
+    pub fn to_solution(&self) -> Solution {
+        let description = if self.moves.is_empty() {
+            "Cube is already solved"
+        } else if self.proven_optimal {
+            "Shortest possible solution"
+        } else {
+            "Shortest solution found in the time allowed"
+        };
+        Solution::with_method(
+            vec![SolutionStep::new(description, self.moves.clone())],
+            self.time_ms,
+            "Two-Phase Search",
+        )
+    }
+}
+
+/// Where every corner and edge is and how it is turned
+///
+/// Slot `i` holds piece `cp[i]`, whose home is slot `cp[i]`, with its first
+/// sticker (U/D, or F/B for middle-layer edges) on sticker `co[i]` of the
+/// slot. Slots are in the order of [`CORNER_SLOTS`] and [`EDGE_SLOTS`],
+/// which list the middle-layer edges last.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cubies {
+    cp: [u8; 8],
+    co: [u8; 8],
+    ep: [u8; 12],
+    eo: [u8; 12],
+}
+
+impl Cubies {
+    const SOLVED: Cubies = Cubies {
+        cp: [0, 1, 2, 3, 4, 5, 6, 7],
+        co: [0; 8],
+        ep: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        eo: [0; 12],
+    };
+
+    /// Read the pieces of a valid 3x3, taking the centers as home colors
+    fn from_cube(cube: &Cube) -> Option<Cubies> {
+        let color = |(face, row, col): (FaceName, usize, usize)| cube.get_face(face).get(row, col);
+        let home = |slot: &[(FaceName, usize, usize)]| -> Vec<Color> {
+            slot.iter().map(|(face, _, _)| cube.get_face(*face).get(1, 1)).collect()
+        };
+        let corner_homes: Vec<Vec<Color>> = CORNER_SLOTS.iter().map(|slot| home(slot)).collect();
+        let edge_homes: Vec<Vec<Color>> = EDGE_SLOTS.iter().map(|slot| home(slot)).collect();
+
+        let mut cubies = Cubies::SOLVED;
+        for (slot, stickers) in CORNER_SLOTS.iter().enumerate() {
+            let (piece, turn) = identify(&stickers.map(color), &corner_homes)?;
+            (cubies.cp[slot], cubies.co[slot]) = (piece, turn);
+        }
+        for (slot, stickers) in EDGE_SLOTS.iter().enumerate() {
+            let (piece, turn) = identify(&stickers.map(color), &edge_homes)?;
+            (cubies.ep[slot], cubies.eo[slot]) = (piece, turn);
+        }
+        Some(cubies)
+    }
+
+    /// This state followed by `turn`
+    fn then(&self, turn: &Cubies) -> Cubies {
+        let mut next = *self;
+        for slot in 0..8 {
+            let from = turn.cp[slot] as usize;
+            next.cp[slot] = self.cp[from];
+            next.co[slot] = (self.co[from] + turn.co[slot]) % 3;
+        }
+        for slot in 0..12 {
+            let from = turn.ep[slot] as usize;
+            next.ep[slot] = self.ep[from];
+            next.eo[slot] = (self.eo[from] + turn.eo[slot]) % 2;
+        }
+        next
+    }
+
+    /// Corner twists, 0 when every corner is oriented
+    fn twist(&self) -> usize {
+        self.co[..7].iter().fold(0, |twist, &co| twist * 3 + co as usize)
+    }
+
+    /// Edge flips, 0 when every edge is oriented
+    fn flip(&self) -> usize {
+        self.eo[..11].iter().fold(0, |flip, &eo| flip * 2 + eo as usize)
+    }
+
+    /// Slots and order of the middle-layer edges: `slice * 24 + order`,
+    /// 0 when they are home
+    fn slice_order(&self) -> usize {
+        let (mut slice, mut found) = (0, 0);
+        let mut order = [0u8; 4];
+        for slot in (0..12).rev() {
+            if self.ep[slot] >= 8 {
+                slice += binomial(11 - slot, found + 1);
+                order[3 - found] = self.ep[slot] - 8;
+                found += 1;
+            }
+        }
+        slice * SLICE_ORDERS + permutation_index(&order)
+    }
+
+    /// Permutation of the corners
+    fn corners(&self) -> usize {
+        permutation_index(&self.cp)
+    }
+
+    /// Permutation of the U and D edges, once they are all in the U and D layers
+    fn ud_edges(&self) -> usize {
+        permutation_index(&self.ep[..8])
+    }
+}
+
+/// Which piece shows `colors`, and which sticker its first color is on
+fn identify(colors: &[Color], homes: &[Vec<Color>]) -> Option<(u8, u8)> {
+    let n = colors.len();
+    homes.iter().enumerate().find_map(|(piece, home)| {
+        (0..n)
+            .find(|&turn| (0..n).all(|i| colors[(turn + i) % n] == home[i]))
+            .map(|turn| (piece as u8, turn as u8))
+    })
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1, |result, i| result * (n - i) / (i + 1))
+}
+
+/// Rank of a permutation of 0..n among all n! permutations
+fn permutation_index(permutation: &[u8]) -> usize {
+    let n = permutation.len();
+    (0..n).fold(0, |index, i| {
+        index * (n - i) + permutation[i + 1..].iter().filter(|&&later| later < permutation[i]).count()
+    })
+}
+
+/// Where each coordinate goes under each move
+type MoveTable = Vec<[u16; 18]>;
+
+/// Move and distance tables for both phases
+struct Tables {
+    twist: MoveTable,
+    flip: MoveTable,
+    slice: MoveTable,
+    slice_order: MoveTable,
+    corners: MoveTable,
+    ud_edges: MoveTable,
+    /// Phase 1 distances by twist and slice
+    twist_slice: Vec<u8>,
+    /// Phase 1 distances by flip and slice
+    flip_slice: Vec<u8>,
+    /// Phase 2 distances by corners and middle-layer order
+    corners_order: Vec<u8>,
+    /// Phase 2 distances by U and D edges and middle-layer order
+    edges_order: Vec<u8>,
+}
+
+impl Tables {
+    fn get() -> &'static Tables {
+        static TABLES: OnceLock<Tables> = OnceLock::new();
+        TABLES.get_or_init(Tables::build)
+    }
+
+    fn build() -> Tables {
+        let all: Vec<usize> = (0..18).collect();
+        let twist = move_table(TWISTS, &all, Cubies::twist);
+        let flip = move_table(FLIPS, &all, Cubies::flip);
+        let slice_order = move_table(SLICES * SLICE_ORDERS, &all, Cubies::slice_order);
+        let corners = move_table(PERMUTATIONS, &all, Cubies::corners);
+        let ud_edges = move_table(PERMUTATIONS, &PHASE2_MOVES, Cubies::ud_edges);
+        // The placement of the slice edges, whatever their order
+        let slice: MoveTable = (0..SLICES)
+            .map(|slice| slice_order[slice * SLICE_ORDERS].map(|to| to / SLICE_ORDERS as u16))
+            .collect();
+
+        Tables {
+            twist_slice: distance_table(&twist, &slice, &all),
+            flip_slice: distance_table(&flip, &slice, &all),
+            corners_order: distance_table(&corners, &slice_order[..SLICE_ORDERS], &PHASE2_MOVES),
+            edges_order: distance_table(&ud_edges, &slice_order[..SLICE_ORDERS], &PHASE2_MOVES),
+            twist,
+            flip,
+            slice,
+            slice_order,
+            corners,
+            ud_edges,
+        }
+    }
+
+    /// Fewest phase 1 moves left
+    fn phase1_distance(&self, twist: usize, flip: usize, slice: usize) -> usize {
+        self.twist_slice[twist * SLICES + slice].max(self.flip_slice[flip * SLICES + slice]) as usize
+    }
+
+    /// Fewest phase 2 moves left
+    fn phase2_distance(&self, corners: usize, edges: usize, order: usize) -> usize {
+        let by_corners = self.corners_order[corners * SLICE_ORDERS + order];
+        by_corners.max(self.edges_order[edges * SLICE_ORDERS + order]) as usize
+    }
+}
+
+/// Each face turn's effect on the pieces
+fn turns() -> &'static [Cubies; 18] {
+    static TURNS: OnceLock<[Cubies; 18]> = OnceLock::new();
+    TURNS.get_or_init(|| {
+        let quarter: [Cubies; 6] = std::array::from_fn(|face| {
+            let mut cube = Cube::new(3);
+            cube.apply_move(MOVES[face * 3]);
+            Cubies::from_cube(&cube).expect("a turned cube is valid")
+        });
+        std::array::from_fn(|index| {
+            let turn = quarter[index / 3];
+            (0..index % 3).fold(turn, |cubies, _| cubies.then(&turn))
+        })
+    })
+}
+
+/// Table of where `coordinate` goes under `moves`, found by visiting every
+/// value from solved
+fn move_table(size: usize, moves: &[usize], coordinate: fn(&Cubies) -> usize) -> MoveTable {
+    let turns = turns();
+    let mut table = vec![[u16::MAX; 18]; size];
+    let mut seen = vec![false; size];
+    seen[coordinate(&Cubies::SOLVED)] = true;
+    let mut queue = VecDeque::from([Cubies::SOLVED]);
+    while let Some(cubies) = queue.pop_front() {
+        let from = coordinate(&cubies);
+        for &mv in moves {
+            let next = cubies.then(&turns[mv]);
+            let to = coordinate(&next);
+            table[from][mv] = to as u16;
+            if !seen[to] {
+                seen[to] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    table
+}
+
+/// Exact distances from solved of every pair of two coordinates, with
+/// `moves` only
+fn distance_table(first: &[[u16; 18]], second: &[[u16; 18]], moves: &[usize]) -> Vec<u8> {
+    let width = second.len();
+    let mut distance = vec![u8::MAX; first.len() * width];
+    distance[0] = 0;
+    let mut frontier = vec![0];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        depth += 1;
+        let mut next = Vec::new();
+        for index in frontier {
+            let (a, b) = (index / width, index % width);
+            for &mv in moves {
+                let to = first[a][mv] as usize * width + second[b][mv] as usize;
+                if distance[to] == u8::MAX {
+                    distance[to] = depth;
+                    next.push(to);
+                }
+            }
+        }
+        frontier = next;
+    }
+    distance
+}
+
+/// Whether `mv` may follow `last` in a shortest sequence: never the same
+/// face twice, and two opposite faces in one order only
+fn may_follow(last: Option<usize>, mv: usize) -> bool {
+    last.is_none_or(|last| {
+        let (before, after) = (last / 3, mv / 3);
+        before != after && before != after + 3
+    })
+}
+
+/// What the root branches of one search share
+struct Shared {
+    /// Length of the shortest solution any branch has found
+    shortest: AtomicUsize,
+    /// Nodes visited by every branch, counted every `CHECK_INTERVAL`
+    nodes: AtomicU64,
+    /// Whether a branch found the budget spent
+    spent: AtomicBool,
+}
+
+/// State of one search (or one root branch of it)
+#[derive(Clone)]
+struct Search<'a> {
+    tables: &'a Tables,
+    start: Cubies,
+    budget: &'a SearchBudget,
+    started: Instant,
+    shared: &'a Shared,
+    /// Moves of the sequence being tried
+    path: Vec<usize>,
+    best: Option<Vec<usize>>,
+    nodes: u64,
+    out_of_budget: bool,
+}
+
+impl Search<'_> {
+    /// Length a new solution has to beat
+    ///
+    /// A solution as long as another branch's still counts, so ties go
+    /// to the earlier branch as they would searching one by one.
+    fn limit(&self) -> usize {
+        let own = self.best.as_ref().map_or(MAX_LENGTH + 1, Vec::len);
+        own.min(self.shared.shortest.load(Ordering::Relaxed) + 1)
+    }
+
+    /// Count a node; false once the budget is spent
+    fn visit(&mut self) -> bool {
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(CHECK_INTERVAL) {
+            let nodes = self.shared.nodes.fetch_add(CHECK_INTERVAL, Ordering::Relaxed) + CHECK_INTERVAL;
+            // The budget only counts once some branch has a solution
+            let found = self.shared.shortest.load(Ordering::Relaxed) <= MAX_LENGTH;
+            if found && self.budget.is_spent(self.started, nodes) {
+                self.shared.spent.store(true, Ordering::Relaxed);
+            }
+            self.out_of_budget = self.shared.spent.load(Ordering::Relaxed);
+        }
+        !self.out_of_budget
+    }
+
+    /// Search the phase 1 sequences of exactly `depth` moves, one root
+    /// branch per first move
+    fn split_phase1(&mut self, twist: usize, flip: usize, slice: usize, depth: usize) {
+        let branches = map_roots(MOVES.len(), |mv| {
+            let mut branch = Search { nodes: 0, ..self.clone() };
+            branch.phase1_turn(mv, twist, flip, slice, depth);
+            branch
+        });
+        // Earlier branches win ties, as they would searching one by one
+        for branch in branches {
+            self.nodes += branch.nodes;
+            self.out_of_budget |= branch.out_of_budget;
+            let shorter = |best: &Vec<usize>| self.best.as_ref().is_none_or(|own| best.len() < own.len());
+            if branch.best.as_ref().is_some_and(shorter) {
+                self.best = branch.best;
+            }
+        }
+    }
+
+    /// Try every phase 1 sequence of exactly `remaining` more moves
+    fn phase1(&mut self, twist: usize, flip: usize, slice: usize, remaining: usize) {
+        if !self.visit() {
+            return;
+        }
+        if remaining == 0 {
+            // A last move of phase 2 would have reached the subgroup one move sooner
+            if self.path.last().is_none_or(|mv| !PHASE2_MOVES.contains(mv)) {
+                self.phase2_from_here();
+            }
+            return;
+        }
+        for mv in 0..18 {
+            self.phase1_turn(mv, twist, flip, slice, remaining);
+            if self.out_of_budget {
+                return;
+            }
+        }
+    }
+
+    /// Continue the phase 1 sequence with `mv` if it can still reach the
+    /// subgroup in `remaining` moves
+    fn phase1_turn(&mut self, mv: usize, twist: usize, flip: usize, slice: usize, remaining: usize) {
+        if !may_follow(self.path.last().copied(), mv) {
+            return;
+        }
+        let tables = self.tables;
+        let (twist, flip, slice) =
+            (tables.twist[twist][mv] as usize, tables.flip[flip][mv] as usize, tables.slice[slice][mv] as usize);
+        if tables.phase1_distance(twist, flip, slice) >= remaining {
+            return;
+        }
+        self.path.push(mv);
+        self.phase1(twist, flip, slice, remaining - 1);
+        self.path.pop();
+    }
+
+    /// Finish the phase 1 sequence in `path` with the fewest phase 2 moves
+    /// that still beat the best solution
+    fn phase2_from_here(&mut self) {
+        let Some(longest) = self.limit().checked_sub(self.path.len() + 1) else {
+            return;
+        };
+        let turns = turns();
+        let cubies = self.path.iter().fold(self.start, |cubies, mv| cubies.then(&turns[*mv]));
+        let (corners, edges, order) = (cubies.corners(), cubies.ud_edges(), cubies.slice_order());
+        for length in self.tables.phase2_distance(corners, edges, order)..=longest {
+            if self.phase2(corners, edges, order, length) || self.out_of_budget {
+                return;
+            }
+        }
+    }
+
+    /// Try every phase 2 sequence of exactly `remaining` more moves,
+    /// keeping the first that solves the cube
+    fn phase2(&mut self, corners: usize, edges: usize, order: usize, remaining: usize) -> bool {
+        if !self.visit() {
+            return false;
+        }
+        if remaining == 0 {
+            let solved = corners == 0 && edges == 0 && order == 0;
+            if solved {
+                self.best = Some(self.path.clone());
+                self.shared.shortest.fetch_min(self.path.len(), Ordering::Relaxed);
+            }
+            return solved;
+        }
+        let tables = self.tables;
+        for mv in PHASE2_MOVES {
+            if !may_follow(self.path.last().copied(), mv) {
+                continue;
+            }
+            let (corners, edges, order) = (
+                tables.corners[corners][mv] as usize,
+                tables.ud_edges[edges][mv] as usize,
+                tables.slice_order[order][mv] as usize,
+            );
+            if tables.phase2_distance(corners, edges, order) >= remaining {
+                continue;
+            }
+            self.path.push(mv);
+            let found = self.phase2(corners, edges, order, remaining - 1);
+            self.path.pop();
+            if found || self.out_of_budget {
+                return found;
+            }
+        }
+        false
+    }
+}
+
+/// Solve a 3x3 in as few moves as the budget allows
+///
+/// Returns the shortest solution found and whether it is proven optimal.
+/// Face turns count as one move each, half turns included; the solution
+/// never rotates the cube.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use rubiks_cube_solver::cube::{Cube, Move};
+/// use rubiks_cube_solver::solver::{solve_3x3_optimal, SearchBudget};
+///
+/// let mut cube = Cube::new(3);
+/// cube.apply_moves(&[Move::R, Move::U2, Move::F]);
+/// let solution = solve_3x3_optimal(&cube, &SearchBudget::new(Duration::from_secs(10))).expect("Should solve");
+/// assert_eq!(solution.move_count(), 3);
+/// assert!(solution.proven_optimal);
+/// ```
+pub fn solve_3x3_optimal(cube: &Cube, budget: &SearchBudget) -> Result<OptimalSolution, String> {
+    let started = Instant::now();
+    if cube.size() != 3 {
+        return Err("Cube must be size 3 for the two-phase solver".to_string());
+    }
+    if cube.validate().is_err() {
+        return Err("Cube is not in a valid state".to_string());
+    }
+    let start = Cubies::from_cube(cube).ok_or("Cube is not in a valid state")?;
+
+    let tables = Tables::get();
+    let shared = Shared {
+        shortest: AtomicUsize::new(MAX_LENGTH + 1),
+        nodes: AtomicU64::new(0),
+        spent: AtomicBool::new(false),
+    };
+    let mut search = Search {
+        tables,
+        start,
+        budget,
+        started,
+        shared: &shared,
+        path: Vec::new(),
+        best: None,
+        nodes: 0,
+        out_of_budget: false,
+    };
+    let (twist, flip, slice) = (start.twist(), start.flip(), start.slice_order() / SLICE_ORDERS);
+    let mut depth = tables.phase1_distance(twist, flip, slice);
+    while depth < search.limit() && !search.out_of_budget {
+        if depth == 0 {
+            search.phase1(twist, flip, slice, depth);
+        } else {
+            search.split_phase1(twist, flip, slice, depth);
+        }
+        depth += 1;
+    }
+
+    let moves = search.best.ok_or("Could not find a solution")?.iter().map(|mv| MOVES[*mv]).collect();
+    Ok(OptimalSolution {
+        moves,
+        time_ms: started.elapsed().as_millis(),
+        proven_optimal: !search.out_of_budget,
+        nodes: search.nodes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn budget() -> SearchBudget {
+        SearchBudget::new(Duration::from_secs(60))
+    }
+
+    #[test]
+    fn test_pieces_follow_the_simulator() {
+        let turns = turns();
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut cube = Cube::new(3);
+        let mut cubies = Cubies::SOLVED;
+        assert_eq!(Cubies::from_cube(&cube), Some(Cubies::SOLVED));
+        for _ in 0..40 {
+            let mv = rng.gen_range(0..18);
+            cube.apply_move(MOVES[mv]);
+            cubies = cubies.then(&turns[mv]);
+            assert_eq!(Cubies::from_cube(&cube), Some(cubies), "after {}", MOVES[mv].to_notation());
+        }
+    }
+
+    #[test]
+    fn test_coordinates() {
+        assert_eq!(permutation_index(&[0, 1, 2, 3]), 0);
+        assert_eq!(permutation_index(&[3, 2, 1, 0]), 23);
+        assert_eq!(binomial(12, 4), SLICES);
+        let solved = Cubies::SOLVED;
+        assert_eq!((solved.twist(), solved.flip(), solved.slice_order()), (0, 0, 0));
+        // Phase 2 moves stay in the subgroup
+        let turns = turns();
+        for mv in PHASE2_MOVES {
+            let turned = solved.then(&turns[mv]);
+            assert_eq!((turned.twist(), turned.flip(), turned.slice_order() / SLICE_ORDERS), (0, 0, 0));
+        }
+        assert_ne!(solved.then(&turns[3]).twist(), 0);
+    }
+
+    #[test]
+    fn test_short_scrambles_are_proven_optimal() {
+        let scrambles: [&[Move]; 3] = [
+            &[],
+            &[Move::R, Move::U],
+            &[Move::F, Move::R2, Move::D, Move::BPrime, Move::L, Move::U2],
+        ];
+        for scramble in scrambles {
+            let mut cube = Cube::new(3);
+            cube.apply_moves(scramble);
+            let solution = solve_3x3_optimal(&cube, &budget()).expect("Should solve");
+            assert!(solution.proven_optimal);
+            assert_eq!(solution.move_count(), scramble.len());
+            cube.apply_moves(&solution.moves);
+            assert!(cube.is_solved());
+        }
+    }
+
+    #[test]
+    fn test_cancelling_scramble_is_shortened() {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::U, Move::UPrime, Move::R, Move::D, Move::DPrime]);
+        let solution = solve_3x3_optimal(&cube, &budget()).expect("Should solve");
+        assert_eq!(solution.moves, vec![Move::R2]);
+        assert_eq!(solution.to_solution().steps[0].description, "Shortest possible solution");
+    }
+
+    #[test]
+    fn test_budget_still_returns_a_solution() {
+        let mut cube = Cube::random_state(3, &mut StdRng::seed_from_u64(11));
+        let solution = solve_3x3_optimal(&cube, &budget().with_nodes(1)).expect("Should solve");
+        assert!(!solution.proven_optimal);
+        assert!(solution.move_count() <= MAX_LENGTH);
+        cube.apply_moves(&solution.moves);
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn test_rejects_invalid_cubes() {
+        assert!(solve_3x3_optimal(&Cube::new(2), &budget()).is_err());
+        let mut cube = Cube::new(3);
+        let (face, row, col) = CORNER_SLOTS[0][0];
+        let (other, other_row, other_col) = CORNER_SLOTS[0][1];
+        let (a, b) = (cube.get_face(face).get(row, col), cube.get_face(other).get(other_row, other_col));
+        cube.set_sticker(face, row, col, b);
+        cube.set_sticker(other, other_row, other_col, a);
+        assert!(solve_3x3_optimal(&cube, &budget()).is_err());
+    }
+}
@@ -0,0 +1,375 @@
+//! Sound effects
+//!
+//! Short synthesized sounds for app events, played through a pluggable
+//! backend:
+//! - Native platforms: rodio (with the `sound` feature, part of `desktop`)
+//! - Web (WASM): the browser Web Audio API
+//!
+//! `SoundEffects` listens to the [`EventBus`](crate::state::EventBus): a
+//! turn clicks, a solve plays a fanfare and a finished lesson chimes. Sounds
+//! are synthesized from tones, so no audio files are needed. Muting keeps
+//! the backend but plays nothing.
+
+use crate::state::AppEvent;
+
+/// Error types for sound playback
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SoundError {
+    /// No audio output is available on this platform
+    Unavailable(String),
+    /// The audio output failed to play the sound
+    PlayFailed(String),
+}
+
+impl std::fmt::Display for SoundError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SoundError::Unavailable(msg) => write!(f, "Sound unavailable: {}", msg),
+            SoundError::PlayFailed(msg) => write!(f, "Sound failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SoundError {}
+
+/// One tone of a sound effect
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tone {
+    /// Pitch in Hz
+    pub frequency: f32,
+    /// Length in milliseconds
+    pub duration_ms: u32,
+}
+
+const fn tone(frequency: f32, duration_ms: u32) -> Tone {
+    Tone { frequency, duration_ms }
+}
+
+const TURN_CLICK: [Tone; 1] = [tone(1200.0, 25)];
+
+/// C5 E5 G5 C6
+const SOLVE_FANFARE: [Tone; 4] = [tone(523.25, 120), tone(659.25, 120), tone(783.99, 120), tone(1046.5, 320)];
+
+/// A5 E6
+const LESSON_CHIME: [Tone; 2] = [tone(880.0, 150), tone(1318.5, 300)];
+
+/// The sounds the app can make
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundEffect {
+    /// A short click for a face turn
+    TurnClick,
+    /// A rising fanfare for a solved cube
+    SolveFanfare,
+    /// A two-note chime for a completed lesson
+    LessonChime,
+}
+
+impl SoundEffect {
+    /// The sound for an app event, if it has one
+    pub fn for_event(event: &AppEvent) -> Option<Self> {
+        match event {
+            AppEvent::Turn(_) => Some(SoundEffect::TurnClick),
+            AppEvent::Solved => Some(SoundEffect::SolveFanfare),
+            AppEvent::LessonCompleted(_) => Some(SoundEffect::LessonChime),
+        }
+    }
+
+    /// Tones to play one after another
+    pub fn tones(&self) -> &'static [Tone] {
+        match self {
+            SoundEffect::TurnClick => &TURN_CLICK,
+            SoundEffect::SolveFanfare => &SOLVE_FANFARE,
+            SoundEffect::LessonChime => &LESSON_CHIME,
+        }
+    }
+}
+
+/// An audio output that sound effects can be played on
+pub trait SoundBackend {
+    /// Human-readable backend name (for settings and diagnostics)
+    fn name(&self) -> &str;
+
+    /// Start playing the tones in order, without waiting for them to end
+    fn play(&mut self, tones: &[Tone]) -> Result<(), SoundError>;
+}
+
+/// Backend that plays nothing (used when no audio output is available)
+#[derive(Debug, Clone, Default)]
+pub struct SilentSoundBackend;
+
+impl SoundBackend for SilentSoundBackend {
+    fn name(&self) -> &str {
+        "Silent"
+    }
+
+    fn play(&mut self, _tones: &[Tone]) -> Result<(), SoundError> {
+        Ok(())
+    }
+}
+
+/// Plays sound effects for app events, unless muted
+pub struct SoundEffects {
+    backend: Box<dyn SoundBackend>,
+    muted: bool,
+}
+
+impl SoundEffects {
+    /// Create sound effects using the given backend
+    pub fn new(backend: Box<dyn SoundBackend>) -> Self {
+        Self { backend, muted: false }
+    }
+
+    /// Create sound effects using the default backend for this platform
+    pub fn with_default_backend() -> Self {
+        Self::new(default_backend())
+    }
+
+    /// Get the name of the active backend
+    pub fn backend_name(&self) -> &str {
+        self.backend.name()
+    }
+
+    /// Check if sounds are muted
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    /// Mute or unmute sounds
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
+    /// Play a sound effect; does nothing while muted
+    pub fn play(&mut self, effect: SoundEffect) -> Result<(), SoundError> {
+        if self.muted {
+            return Ok(());
+        }
+        self.backend.play(effect.tones())
+    }
+
+    /// Play the sound for an event, as an [`EventBus`](crate::state::EventBus)
+    /// listener
+    ///
+    /// Sound is a nice-to-have, so playback errors are ignored.
+    pub fn handle(&mut self, event: &AppEvent) {
+        if let Some(effect) = SoundEffect::for_event(event) {
+            let _ = self.play(effect);
+        }
+    }
+}
+
+impl std::fmt::Debug for SoundEffects {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SoundEffects")
+            .field("backend", &self.backend.name())
+            .field("muted", &self.muted)
+            .finish()
+    }
+}
+
+// ============================================================================
+// NATIVE IMPLEMENTATION (rodio)
+// ============================================================================
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "sound"))]
+mod native_impl {
+    use super::*;
+    use rodio::source::{SineWave, Source};
+    use rodio::{OutputStream, OutputStreamHandle, Sink};
+    use std::time::Duration;
+
+    /// Plays tones on the default audio output with rodio
+    pub struct RodioBackend {
+        // Sound stops when the stream is dropped
+        _stream: OutputStream,
+        handle: OutputStreamHandle,
+    }
+
+    impl RodioBackend {
+        /// Open the default audio output
+        pub fn new() -> Result<Self, SoundError> {
+            let (stream, handle) =
+                OutputStream::try_default().map_err(|e| SoundError::Unavailable(format!("No audio output: {}", e)))?;
+            Ok(Self { _stream: stream, handle })
+        }
+    }
+
+    impl SoundBackend for RodioBackend {
+        fn name(&self) -> &str {
+            "rodio"
+        }
+
+        fn play(&mut self, tones: &[Tone]) -> Result<(), SoundError> {
+            let sink = Sink::try_new(&self.handle).map_err(|e| SoundError::PlayFailed(e.to_string()))?;
+            for tone in tones {
+                let duration = Duration::from_millis(tone.duration_ms as u64);
+                sink.append(SineWave::new(tone.frequency).take_duration(duration).amplify(0.2));
+            }
+            // Keep playing after the sink is dropped
+            sink.detach();
+            Ok(())
+        }
+    }
+
+    /// Get the default sound backend for this platform
+    pub fn default_backend() -> Box<dyn SoundBackend> {
+        match RodioBackend::new() {
+            Ok(backend) => Box::new(backend),
+            Err(_) => Box::new(SilentSoundBackend),
+        }
+    }
+}
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "sound"))]
+pub use native_impl::*;
+
+/// Get the default sound backend for this platform
+///
+/// Built without the `sound` feature, native platforms have no audio output.
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "sound")))]
+pub fn default_backend() -> Box<dyn SoundBackend> {
+    Box::new(SilentSoundBackend)
+}
+
+// ============================================================================
+// WASM IMPLEMENTATION (Web Audio API)
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+mod wasm_impl {
+    use super::*;
+    use web_sys::{AudioContext, AudioScheduledSourceNode, OscillatorType};
+
+    /// Plays tones with the browser's Web Audio API
+    #[derive(Debug, Default)]
+    pub struct WebAudioBackend {
+        context: Option<AudioContext>,
+    }
+
+    impl WebAudioBackend {
+        /// Create a new Web Audio backend
+        ///
+        /// The audio context is created on the first sound, since browsers
+        /// only allow audio after the user has interacted with the page.
+        pub fn new() -> Self {
+            Self { context: None }
+        }
+
+        fn context(&mut self) -> Result<&AudioContext, SoundError> {
+            if self.context.is_none() {
+                let context = AudioContext::new()
+                    .map_err(|_| SoundError::Unavailable("Web Audio API not supported".to_string()))?;
+                self.context = Some(context);
+            }
+            Ok(self.context.as_ref().expect("context was just created"))
+        }
+    }
+
+    impl SoundBackend for WebAudioBackend {
+        fn name(&self) -> &str {
+            "Web Audio"
+        }
+
+        fn play(&mut self, tones: &[Tone]) -> Result<(), SoundError> {
+            let failed = |_| SoundError::PlayFailed("Could not schedule tone".to_string());
+            let context = self.context()?;
+            let mut start = context.current_time();
+            for tone in tones {
+                let end = start + tone.duration_ms as f64 / 1000.0;
+                let oscillator = context.create_oscillator().map_err(failed)?;
+                oscillator.set_type(OscillatorType::Sine);
+                oscillator.frequency().set_value(tone.frequency);
+                let gain = context.create_gain().map_err(failed)?;
+                gain.gain().set_value(0.2);
+                oscillator.connect_with_audio_node(&gain).map_err(failed)?;
+                gain.connect_with_audio_node(&context.destination()).map_err(failed)?;
+                AudioScheduledSourceNode::start_with_when(&oscillator, start).map_err(failed)?;
+                AudioScheduledSourceNode::stop_with_when(&oscillator, end).map_err(failed)?;
+                start = end;
+            }
+            Ok(())
+        }
+    }
+
+    /// Get the default sound backend for this platform
+    pub fn default_backend() -> Box<dyn SoundBackend> {
+        Box::new(WebAudioBackend::new())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub use wasm_impl::*;
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Move;
+    use crate::state::{EventBus, LessonId};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Backend that records the tones it was asked to play
+    #[derive(Clone, Default)]
+    struct RecordingBackend {
+        played: Rc<RefCell<Vec<Vec<Tone>>>>,
+    }
+
+    impl SoundBackend for RecordingBackend {
+        fn name(&self) -> &str {
+            "Recording"
+        }
+
+        fn play(&mut self, tones: &[Tone]) -> Result<(), SoundError> {
+            self.played.borrow_mut().push(tones.to_vec());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_events_play_their_sounds() {
+        let backend = RecordingBackend::default();
+        let played = Rc::clone(&backend.played);
+        let sounds = Rc::new(RefCell::new(SoundEffects::new(Box::new(backend))));
+
+        let mut bus = EventBus::new();
+        let listener = Rc::clone(&sounds);
+        bus.subscribe(Box::new(move |event| listener.borrow_mut().handle(event)));
+
+        bus.emit(AppEvent::Turn(Move::R));
+        bus.emit(AppEvent::Solved);
+        bus.emit(AppEvent::LessonCompleted(LessonId::PLL));
+        assert_eq!(
+            *played.borrow(),
+            vec![
+                SoundEffect::TurnClick.tones().to_vec(),
+                SoundEffect::SolveFanfare.tones().to_vec(),
+                SoundEffect::LessonChime.tones().to_vec(),
+            ]
+        );
+
+        sounds.borrow_mut().set_muted(true);
+        bus.emit(AppEvent::Solved);
+        assert_eq!(played.borrow().len(), 3, "Muted sounds are not played");
+        assert!(sounds.borrow().is_muted());
+    }
+
+    #[test]
+    fn test_tones() {
+        for effect in [SoundEffect::TurnClick, SoundEffect::SolveFanfare, SoundEffect::LessonChime] {
+            let tones = effect.tones();
+            assert!(!tones.is_empty());
+            assert!(tones.iter().all(|t| t.frequency > 20.0 && t.duration_ms > 0));
+        }
+        assert!(SoundEffect::TurnClick.tones()[0].duration_ms < 100, "Turn clicks are short");
+    }
+
+    #[test]
+    fn test_sound_error_display() {
+        let error = SoundError::Unavailable("no device".to_string());
+        assert_eq!(error.to_string(), "Sound unavailable: no device");
+    }
+}
@@ -0,0 +1,89 @@
+//! App-wide event bus
+//!
+//! Parts of the app announce what happened (a face turned, the cube was
+//! solved, a lesson was completed) on an [`EventBus`]. Feedback such as
+//! sound effects subscribes to the bus, so senders don't need to know what
+//! reacts to their events.
+
+use crate::cube::Move;
+use crate::state::LessonId;
+
+/// Something that happened in the app
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppEvent {
+    /// A face or layer was turned
+    Turn(Move),
+    /// The cube was solved
+    Solved,
+    /// A lesson was completed
+    LessonCompleted(LessonId),
+}
+
+/// Callback invoked for every event on the bus
+pub type EventListener = Box<dyn FnMut(&AppEvent)>;
+
+/// Delivers app events to every subscribed listener
+#[derive(Default)]
+pub struct EventBus {
+    listeners: Vec<EventListener>,
+}
+
+impl EventBus {
+    /// Create a bus with no listeners
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a listener that receives every event
+    pub fn subscribe(&mut self, listener: EventListener) {
+        self.listeners.push(listener);
+    }
+
+    /// Send an event to all listeners, in the order they subscribed
+    pub fn emit(&mut self, event: AppEvent) {
+        for listener in &mut self.listeners {
+            listener(&event);
+        }
+    }
+
+    /// Number of subscribed listeners
+    pub fn listener_count(&self) -> usize {
+        self.listeners.len()
+    }
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus").field("listeners", &self.listeners.len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_listeners_receive_events() {
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let mut bus = EventBus::new();
+        for name in ["first", "second"] {
+            let received = Rc::clone(&received);
+            bus.subscribe(Box::new(move |event| received.borrow_mut().push((name, event.clone()))));
+        }
+        assert_eq!(bus.listener_count(), 2);
+
+        bus.emit(AppEvent::Turn(Move::R));
+        bus.emit(AppEvent::LessonCompleted(LessonId::Cross));
+        assert_eq!(
+            *received.borrow(),
+            vec![
+                ("first", AppEvent::Turn(Move::R)),
+                ("second", AppEvent::Turn(Move::R)),
+                ("first", AppEvent::LessonCompleted(LessonId::Cross)),
+                ("second", AppEvent::LessonCompleted(LessonId::Cross)),
+            ]
+        );
+    }
+}
@@ -5,95 +5,526 @@
 //! - Redo undone change
 //! - Undo/redo history stack
 //!
-//! The history stack tracks cube states and allows navigation through them.
+//! The history tracks cube states and allows navigation through them.
+//! Compound actions (a whole algorithm or scramble) can be grouped so they
+//! are undone as a single labeled step. The states undo and redo move
+//! through can also be read as an indexed timeline and restored to any
+//! point in it.
+//!
+//! Nothing is thrown away by undoing: a change made after an undo starts a
+//! new branch, and the states that could have been redone stay on the old
+//! one. [`History::tree`] lays every branch out for drawing, and
+//! [`History::go_to`] switches to any state on any branch.
+//!
+//! Each state keeps a short description of the change that led to it,
+//! worked out once when it is recorded: the change's label, the move
+//! that explains it, or how many stickers were edited. Changes that leave
+//! the cube as it was are not recorded.
+//!
+//! [`HistoryLimits`] cap how many undo steps are kept and roughly how much
+//! memory all states may take; the oldest states go first when either is
+//! exceeded, so a long editing session on a 20x20 stays small.
 
-use crate::cube::Cube;
+use crate::cube::{Color, Cube, FaceName, Move};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 /// Maximum number of history states to keep in memory
 const MAX_HISTORY_SIZE: usize = 100;
 
+/// Default cap on the memory all states may take, in bytes
+const MAX_HISTORY_BYTES: usize = 16 * 1024 * 1024;
+
+/// How much history to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryLimits {
+    /// Most undo steps kept
+    pub max_depth: usize,
+    /// Most bytes all states may take (approximately), or `None` for no cap
+    pub max_bytes: Option<usize>,
+}
+
+impl Default for HistoryLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: MAX_HISTORY_SIZE,
+            max_bytes: Some(MAX_HISTORY_BYTES),
+        }
+    }
+}
+
+impl HistoryLimits {
+    /// Keep at most `max_depth` undo steps, within the default memory cap
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self { max_depth, ..Self::default() }
+    }
+}
+
+/// Single moves checked when an unlabeled change needs a name
+const CANDIDATE_MOVES: [Move; 36] = [
+    Move::R, Move::RPrime, Move::R2,
+    Move::L, Move::LPrime, Move::L2,
+    Move::U, Move::UPrime, Move::U2,
+    Move::D, Move::DPrime, Move::D2,
+    Move::F, Move::FPrime, Move::F2,
+    Move::B, Move::BPrime, Move::B2,
+    Move::M, Move::MPrime, Move::M2,
+    Move::E, Move::EPrime, Move::E2,
+    Move::S, Move::SPrime, Move::S2,
+    Move::X, Move::XPrime, Move::X2,
+    Move::Y, Move::YPrime, Move::Y2,
+    Move::Z, Move::ZPrime, Move::Z2,
+];
+
+/// Description of the oldest state kept
+const START: &str = "Start";
+
+/// Find the single move that turns one state into another, if there is one
+fn move_between(before: &Cube, after: &Cube) -> Option<Move> {
+    if before.size() != after.size() || before == after {
+        return None;
+    }
+    // Slice moves only exist on odd cubes
+    let odd = before.size() % 2 == 1;
+    CANDIDATE_MOVES.iter().copied().filter(|mv| odd || !mv.is_slice()).find(|mv| {
+        let mut cube = before.clone();
+        cube.apply_move(*mv);
+        cube == *after
+    })
+}
+
+/// Count the stickers that differ between two states of the same size
+fn changed_stickers(before: &Cube, after: &Cube) -> usize {
+    let n = before.size();
+    FaceName::all()
+        .iter()
+        .map(|face| {
+            let (a, b) = (before.get_face(*face), after.get_face(*face));
+            (0..n)
+                .flat_map(|row| (0..n).map(move |col| (row, col)))
+                .filter(|(row, col)| a.get(*row, *col) != b.get(*row, *col))
+                .count()
+        })
+        .sum()
+}
+
+/// Describe the change from `before` to `after` for the history views
+///
+/// Uses the change's label if it has one, then the move notation if a
+/// single move explains it, then the number of edited stickers.
+fn describe_change(before: &Cube, after: &Cube, label: Option<&str>) -> String {
+    if let Some(label) = label {
+        return label.to_string();
+    }
+    if before.size() != after.size() {
+        return format!("New {}x{} cube", after.size(), after.size());
+    }
+    if let Some(mv) = move_between(before, after) {
+        return mv.to_notation().to_string();
+    }
+    match changed_stickers(before, after) {
+        1 => "Edit 1 sticker".to_string(),
+        n => format!("Edit {} stickers", n),
+    }
+}
+
+/// Approximate bytes a cube's stickers take on the heap
+fn cube_bytes(cube: &Cube) -> usize {
+    let n = cube.size();
+    6 * (n * std::mem::size_of::<Vec<Color>>() + n * n * std::mem::size_of::<Color>())
+}
+
+/// A cube state in the history tree
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    /// Identifier, never reused within one history
+    id: usize,
+    /// The state this one was changed from (None for the oldest state)
+    parent: Option<usize>,
+    /// The cube state
+    cube: Cube,
+    /// Description of the change that led here (e.g. "Scramble"), if any
+    label: Option<String>,
+    /// The label, else what the change did (e.g. "R'" or "Edit 2 stickers")
+    description: String,
+    /// The child that redo goes to: the one visited last
+    redo: Option<usize>,
+}
+
+/// An open group of changes that will be recorded as one step
+#[derive(Debug, Clone, PartialEq)]
+struct Group {
+    /// Label for the whole group
+    label: String,
+    /// Nesting depth (groups started inside a group join it)
+    depth: usize,
+}
+
+/// One point in the history timeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEntry<'a> {
+    /// Position in the timeline (0 = oldest state kept)
+    pub index: usize,
+    /// The cube state at this point
+    pub cube: &'a Cube,
+    /// Label of the change that led to this state, if any
+    pub label: Option<&'a str>,
+    /// The label, else what the change did ("Start" for the oldest state)
+    pub description: &'a str,
+    /// Whether this is the current state
+    pub is_current: bool,
+}
+
+/// One state in the history tree, placed for drawing
+///
+/// Drawn with `depth` across and `lane` down, the timeline runs along lane
+/// 0 and each other branch gets a lane of its own, starting next to the
+/// state it branched from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryNode<'a> {
+    /// Identifier to pass to [`History::go_to`]
+    pub id: usize,
+    /// Identifier of the state this one was changed from
+    pub parent: Option<usize>,
+    /// The cube state
+    pub cube: &'a Cube,
+    /// Label of the change that led to this state, if any
+    pub label: Option<&'a str>,
+    /// The label, else what the change did ("Start" for the oldest state)
+    pub description: &'a str,
+    /// Number of changes since the oldest state kept
+    pub depth: usize,
+    /// Branch the state is on (0 = the timeline)
+    pub lane: usize,
+    /// Whether the state is on the timeline undo and redo move along
+    pub on_timeline: bool,
+    /// Whether this is the current state
+    pub is_current: bool,
+}
+
 /// History manager for undo/redo functionality
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct History {
-    /// Stack of past cube states
-    past: Vec<Cube>,
-    /// Current cube state
+    /// Every state kept, parents before their children
+    nodes: Vec<Node>,
+    /// Identifier of the current state's node
+    at: usize,
+    /// Current cube state (ahead of its node while a group is open)
     current: Cube,
-    /// Stack of future cube states (for redo)
-    future: Vec<Cube>,
-    /// Maximum history size
-    max_size: usize,
+    /// Identifier for the next node
+    next_id: usize,
+    /// How much history to keep
+    limits: HistoryLimits,
+    /// Open group of changes, if any
+    group: Option<Group>,
 }
 
 impl History {
     /// Create a new history with the given initial cube state
     pub fn new(initial_cube: Cube) -> Self {
-        Self {
-            past: Vec::new(),
-            current: initial_cube,
-            future: Vec::new(),
-            max_size: MAX_HISTORY_SIZE,
-        }
+        Self::with_limits(initial_cube, HistoryLimits::default())
     }
 
     /// Create a new history with a custom maximum size
+    ///
+    /// The size limits how many undo steps are kept; states on other
+    /// branches go when the state they branched from does.
     pub fn with_max_size(initial_cube: Cube, max_size: usize) -> Self {
+        Self::with_limits(initial_cube, HistoryLimits::with_max_depth(max_size))
+    }
+
+    /// Create a new history keeping as much as `limits` allow
+    pub fn with_limits(initial_cube: Cube, limits: HistoryLimits) -> Self {
         Self {
-            past: Vec::new(),
+            nodes: vec![Node {
+                id: 0,
+                parent: None,
+                cube: initial_cube.clone(),
+                label: None,
+                description: START.to_string(),
+                redo: None,
+            }],
+            at: 0,
             current: initial_cube,
-            future: Vec::new(),
-            max_size,
+            next_id: 1,
+            limits,
+            group: None,
+        }
+    }
+
+    /// How much history is kept
+    pub fn limits(&self) -> HistoryLimits {
+        self.limits
+    }
+
+    /// Change how much history is kept, forgetting the oldest states at
+    /// once if there are now too many
+    pub fn set_limits(&mut self, limits: HistoryLimits) {
+        self.limits = limits;
+        self.evict();
+    }
+
+    /// Approximate bytes of memory all states take, current state included
+    pub fn memory_usage(&self) -> usize {
+        let node = |node: &Node| {
+            std::mem::size_of::<Node>()
+                + cube_bytes(&node.cube)
+                + node.label.as_ref().map_or(0, String::capacity)
+                + node.description.capacity()
+        };
+        std::mem::size_of::<Self>() + cube_bytes(&self.current) + self.nodes.iter().map(node).sum::<usize>()
+    }
+
+    /// Number of states kept, on every branch
+    pub fn state_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn node(&self, id: usize) -> &Node {
+        let index = self.nodes.binary_search_by_key(&id, |node| node.id).expect("history node exists");
+        &self.nodes[index]
+    }
+
+    fn node_mut(&mut self, id: usize) -> &mut Node {
+        let index = self.nodes.binary_search_by_key(&id, |node| node.id).expect("history node exists");
+        &mut self.nodes[index]
+    }
+
+    /// Identifiers from the oldest state to `id`
+    fn path_to(&self, id: usize) -> Vec<usize> {
+        let mut path = vec![id];
+        while let Some(parent) = self.node(path[path.len() - 1]).parent {
+            path.push(parent);
+        }
+        path.reverse();
+        path
+    }
+
+    /// Identifiers of the states redo would visit, in order
+    fn redo_chain(&self) -> Vec<usize> {
+        let mut chain = Vec::new();
+        let mut at = self.at;
+        while let Some(next) = self.node(at).redo {
+            chain.push(next);
+            at = next;
         }
+        chain
     }
 
     /// Push a new cube state onto the history
-    /// This clears the future (redo) stack
+    ///
+    /// The states redo would have restored stay in the history as a branch.
+    /// A state the same as the current one records nothing.
     pub fn push(&mut self, new_cube: Cube) {
-        // Push current state to past
-        self.past.push(self.current.clone());
+        self.push_entry(new_cube, None);
+    }
 
-        // Limit history size
-        if self.past.len() > self.max_size {
-            self.past.remove(0);
-        }
+    /// Push a new cube state with a label describing the change
+    pub fn push_labeled(&mut self, new_cube: Cube, label: impl Into<String>) {
+        self.push_entry(new_cube, Some(label.into()));
+    }
 
-        // Update current and clear future
+    fn push_entry(&mut self, new_cube: Cube, label: Option<String>) {
+        if self.group.is_none() && new_cube == self.current {
+            return;
+        }
+        // Inside a group, only the current state changes until the group ends
         self.current = new_cube;
-        self.future.clear();
+        if self.group.is_none() {
+            self.record(label);
+        }
+    }
+
+    /// Record the current state as a change from the current node, then
+    /// forget the oldest states beyond the size limit
+    fn record(&mut self, label: Option<String>) {
+        let id = self.next_id;
+        self.next_id += 1;
+        let description = describe_change(&self.node(self.at).cube, &self.current, label.as_deref());
+        self.node_mut(self.at).redo = Some(id);
+        self.nodes.push(Node {
+            id,
+            parent: Some(self.at),
+            cube: self.current.clone(),
+            label,
+            description,
+            redo: None,
+        });
+        self.at = id;
+        self.evict();
+    }
+
+    /// Forget the oldest states until the history is within its limits
+    ///
+    /// States before the current one go first, each with the branches
+    /// that started from it; then the oldest branch ends. The current
+    /// state always stays.
+    fn evict(&mut self) {
+        let path = self.path_to(self.at);
+        if path.len() > self.limits.max_depth + 1 {
+            self.drop_before(path[path.len() - 1 - self.limits.max_depth]);
+        }
+
+        let Some(max_bytes) = self.limits.max_bytes else {
+            return;
+        };
+        while self.memory_usage() > max_bytes {
+            let path = self.path_to(self.at);
+            if path.len() > 1 {
+                self.drop_before(path[1]);
+                continue;
+            }
+            let at = self.at;
+            let oldest_end = self.nodes.iter().map(|node| node.id).find(|id| *id != at && self.children(*id).next().is_none());
+            match oldest_end {
+                Some(id) => self.drop_end(id),
+                None => break,
+            }
+        }
+    }
+
+    /// Make `root` the oldest state, dropping every state not descended from it
+    fn drop_before(&mut self, root: usize) {
+        let mut kept = HashSet::from([root]);
+        // Parents come before their children, so one pass finds every descendant
+        for node in &self.nodes {
+            if node.parent.is_some_and(|parent| kept.contains(&parent)) {
+                kept.insert(node.id);
+            }
+        }
+        self.nodes.retain(|node| kept.contains(&node.id));
+        self.node_mut(root).parent = None;
+        self.node_mut(root).label = None;
+        self.node_mut(root).description = START.to_string();
+    }
+
+    /// Drop a state nothing was changed from after it
+    fn drop_end(&mut self, id: usize) {
+        let parent = self.node(id).parent;
+        self.nodes.retain(|node| node.id != id);
+        if let Some(parent) = parent {
+            let newest = self.children(parent).last();
+            let node = self.node_mut(parent);
+            if node.redo == Some(id) {
+                node.redo = newest;
+            }
+        }
+    }
+
+    /// Start grouping changes into a single undoable step
+    ///
+    /// Every `push` until the matching `end_group` is merged into one step
+    /// with this label. Groups started inside a group join the outer one.
+    pub fn begin_group(&mut self, label: impl Into<String>) {
+        match &mut self.group {
+            Some(group) => group.depth += 1,
+            None => {
+                self.group = Some(Group {
+                    label: label.into(),
+                    depth: 1,
+                })
+            }
+        }
+    }
+
+    /// Finish the current group
+    ///
+    /// Returns true if a step was recorded. Nothing is recorded for a nested
+    /// group, or if the cube ended where it started.
+    pub fn end_group(&mut self) -> bool {
+        let Some(mut group) = self.group.take() else {
+            return false;
+        };
+
+        if group.depth > 1 {
+            group.depth -= 1;
+            self.group = Some(group);
+            return false;
+        }
+
+        if self.node(self.at).cube == self.current {
+            return false;
+        }
+        self.record(Some(group.label));
+        true
+    }
+
+    /// Abandon the current group, restoring the cube to where it started
+    pub fn cancel_group(&mut self) {
+        if self.group.take().is_some() {
+            self.current = self.node(self.at).cube.clone();
+        }
+    }
+
+    /// Check if a group is open
+    pub fn in_group(&self) -> bool {
+        self.group.is_some()
+    }
+
+    /// Apply a compound change to the current cube as one labeled step
+    ///
+    /// Example: `history.apply_grouped("Scramble", |cube| cube.apply_moves(&moves))`
+    pub fn apply_grouped(&mut self, label: impl Into<String>, change: impl FnOnce(&mut Cube)) {
+        let mut cube = self.current.clone();
+        change(&mut cube);
+        self.begin_group(label);
+        self.push(cube);
+        self.end_group();
+    }
+
+    /// Move to another state, keeping the way back to it for redo
+    fn move_to(&mut self, id: usize) -> Cube {
+        self.at = id;
+        self.current = self.node(id).cube.clone();
+        self.current.clone()
     }
 
     /// Undo the last change, returning the previous cube state
     /// Returns None if there's nothing to undo
+    ///
+    /// An open group is ended first, so its changes are undone together.
     pub fn undo(&mut self) -> Option<Cube> {
-        if let Some(previous_cube) = self.past.pop() {
-            // Move current to future
-            self.future.push(self.current.clone());
-
-            // Restore previous state
-            self.current = previous_cube.clone();
+        self.close_groups();
 
-            Some(previous_cube)
-        } else {
-            None
-        }
+        let previous = self.node(self.at).parent?;
+        self.node_mut(previous).redo = Some(self.at);
+        Some(self.move_to(previous))
     }
 
     /// Redo the last undone change, returning the next cube state
     /// Returns None if there's nothing to redo
     pub fn redo(&mut self) -> Option<Cube> {
-        if let Some(next_cube) = self.future.pop() {
-            // Move current to past
-            self.past.push(self.current.clone());
+        self.close_groups();
 
-            // Restore next state
-            self.current = next_cube.clone();
+        let next = self.node(self.at).redo?;
+        Some(self.move_to(next))
+    }
 
-            Some(next_cube)
-        } else {
-            None
+    /// End every open group, however deeply nested
+    fn close_groups(&mut self) {
+        while self.group.is_some() {
+            self.end_group();
         }
     }
 
+    /// Whether the current state is ahead of its node (an open group changed it)
+    fn has_pending_change(&self) -> bool {
+        self.group.is_some() && self.node(self.at).cube != self.current
+    }
+
+    /// Get the label of the change that `undo` would revert
+    pub fn undo_label(&self) -> Option<&str> {
+        self.node(self.at).label.as_deref()
+    }
+
+    /// Get the label of the change that `redo` would reapply
+    pub fn redo_label(&self) -> Option<&str> {
+        if self.has_pending_change() {
+            return None;
+        }
+        self.node(self.at).redo.and_then(|next| self.node(next).label.as_deref())
+    }
+
     /// Get the current cube state
     pub fn current(&self) -> &Cube {
         &self.current
@@ -101,42 +532,182 @@ impl History {
 
     /// Check if undo is available
     pub fn can_undo(&self) -> bool {
-        !self.past.is_empty()
+        self.node(self.at).parent.is_some()
     }
 
     /// Check if redo is available
     pub fn can_redo(&self) -> bool {
-        !self.future.is_empty()
+        self.node(self.at).redo.is_some() && !self.has_pending_change()
     }
 
     /// Get the number of states in the past (undo available)
     pub fn past_len(&self) -> usize {
-        self.past.len()
+        self.path_to(self.at).len() - 1
     }
 
     /// Get the number of states in the future (redo available)
     pub fn future_len(&self) -> usize {
-        self.future.len()
+        if self.has_pending_change() {
+            return 0;
+        }
+        self.redo_chain().len()
+    }
+
+    /// Get every state undo and redo can reach, oldest first: the undo
+    /// stack, the current state, then the redo stack
+    pub fn timeline(&self) -> Vec<TimelineEntry<'_>> {
+        let position = self.position();
+        self.timeline_ids()
+            .into_iter()
+            .enumerate()
+            .map(|(index, id)| {
+                let node = self.node(id);
+                TimelineEntry {
+                    index,
+                    cube: if index == position { &self.current } else { &node.cube },
+                    label: node.label.as_deref(),
+                    description: &node.description,
+                    is_current: index == position,
+                }
+            })
+            .collect()
+    }
+
+    /// Identifiers of the timeline's states, oldest first
+    fn timeline_ids(&self) -> Vec<usize> {
+        let mut ids = self.path_to(self.at);
+        if !self.has_pending_change() {
+            ids.extend(self.redo_chain());
+        }
+        ids
+    }
+
+    /// Index of the current state in the timeline
+    pub fn position(&self) -> usize {
+        self.past_len()
+    }
+
+    /// Number of states in the timeline
+    pub fn timeline_len(&self) -> usize {
+        self.past_len() + 1 + self.future_len()
+    }
+
+    /// Restore the state at a timeline index by undoing or redoing to it
+    ///
+    /// Later states stay available for redo. Returns None if the index is
+    /// out of range.
+    pub fn jump_to(&mut self, index: usize) -> Option<&Cube> {
+        self.close_groups();
+        if index >= self.timeline_len() {
+            return None;
+        }
+        while self.position() > index {
+            self.undo();
+        }
+        while self.position() < index {
+            self.redo();
+        }
+        Some(&self.current)
+    }
+
+    /// Every state on every branch, placed for drawing
+    ///
+    /// States come branch by branch, the timeline first, each branch from
+    /// the state after its fork to its newest state.
+    pub fn tree(&self) -> Vec<HistoryNode<'_>> {
+        let timeline = self.timeline_ids();
+        let mut placed = Vec::with_capacity(self.nodes.len());
+        // Branches still to draw, as (first state, its depth)
+        let mut branches = vec![(timeline[0], 0)];
+        let mut lane = 0;
+        while let Some((first, mut depth)) = branches.pop() {
+            let mut forks = Vec::new();
+            let mut next = Some(first);
+            while let Some(id) = next {
+                let node = self.node(id);
+                placed.push(HistoryNode {
+                    id,
+                    parent: node.parent,
+                    cube: if id == self.at { &self.current } else { &node.cube },
+                    label: node.label.as_deref(),
+                    description: &node.description,
+                    depth,
+                    lane,
+                    on_timeline: timeline.contains(&id),
+                    is_current: id == self.at,
+                });
+                // Stay on the timeline, else follow redo, else the newest change
+                let children: Vec<usize> = self.children(id).collect();
+                next = children
+                    .iter()
+                    .copied()
+                    .find(|child| timeline.contains(child))
+                    .or(node.redo)
+                    .or(children.last().copied());
+                depth += 1;
+                forks.extend(children.into_iter().filter(|child| Some(*child) != next).map(|child| (child, depth)));
+            }
+            // The oldest fork is drawn next, right below its branch
+            branches.extend(forks.into_iter().rev());
+            lane += 1;
+        }
+        placed
+    }
+
+    /// Identifiers of the states changed from `id`, oldest first
+    fn children(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        self.nodes.iter().filter(move |node| node.parent == Some(id)).map(|node| node.id)
+    }
+
+    /// Number of branches: states nothing was changed from after them
+    pub fn branch_count(&self) -> usize {
+        self.nodes.iter().filter(|node| self.children(node.id).next().is_none()).count()
+    }
+
+    /// Restore any state in the tree by its identifier
+    ///
+    /// The timeline switches to the branch the state is on: undo goes back
+    /// towards the oldest state and redo follows that branch. Returns None
+    /// if no state has the identifier.
+    pub fn go_to(&mut self, id: usize) -> Option<&Cube> {
+        self.close_groups();
+        self.nodes.binary_search_by_key(&id, |node| node.id).ok()?;
+        let path = self.path_to(id);
+        for pair in path.windows(2) {
+            self.node_mut(pair[0]).redo = Some(pair[1]);
+        }
+        self.move_to(id);
+        Some(&self.current)
     }
 
     /// Clear all history and reset to the current state
     pub fn clear(&mut self) {
-        self.past.clear();
-        self.future.clear();
+        let current = self.current.clone();
+        self.reset(current);
     }
 
     /// Reset to a new cube state, clearing all history
     pub fn reset(&mut self, new_cube: Cube) {
-        self.past.clear();
-        self.future.clear();
+        let id = self.next_id;
+        self.next_id += 1;
+        self.nodes = vec![Node {
+            id,
+            parent: None,
+            cube: new_cube.clone(),
+            label: None,
+            description: START.to_string(),
+            redo: None,
+        }];
+        self.at = id;
         self.current = new_cube;
+        self.group = None;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cube::{Color, FaceName};
+    use crate::cube::{Color, FaceName, Move};
 
     #[test]
     fn test_history_new() {
@@ -214,6 +785,8 @@ mod tests {
         history.push(cube3.clone());
         assert!(!history.can_redo());
         assert_eq!(history.current(), &cube3);
+        // cube2 is kept on a branch of its own
+        assert_eq!(history.branch_count(), 2);
     }
 
     #[test]
@@ -273,10 +846,12 @@ mod tests {
     fn test_history_max_size() {
         let mut history = History::with_max_size(Cube::new(3), 3);
 
-        for _ in 0..5 {
-            let mut cube = Cube::new(3);
-            cube.set_sticker(FaceName::F, 0, 0, Color::Red);
-            history.push(cube);
+        for row in 0..3 {
+            for col in 0..2 {
+                let mut cube = history.current().clone();
+                cube.set_sticker(FaceName::F, row, col, Color::Red);
+                history.push(cube);
+            }
         }
 
         // Should only keep 3 states in past
@@ -324,4 +899,331 @@ mod tests {
         assert!(!history.can_redo());
         assert_eq!(history.current(), &cube3);
     }
+
+    #[test]
+    fn test_group_is_one_undo_step() {
+        let mut history = History::new(Cube::new(3));
+        history.begin_group("Scramble");
+        for mv in [Move::R, Move::U, Move::F] {
+            let mut cube = history.current().clone();
+            cube.apply_move(mv);
+            history.push(cube);
+        }
+        assert!(history.end_group());
+
+        assert_eq!(history.past_len(), 1);
+        assert_eq!(history.undo_label(), Some("Scramble"));
+
+        history.undo();
+        assert!(history.current().is_solved());
+        assert_eq!(history.redo_label(), Some("Scramble"));
+
+        history.redo();
+        assert!(!history.current().is_solved());
+        assert_eq!(history.undo_label(), Some("Scramble"));
+    }
+
+    #[test]
+    fn test_nested_groups_join_outer() {
+        let mut history = History::new(Cube::new(3));
+        history.begin_group("Algorithm");
+        history.begin_group("Inner");
+        history.apply_grouped("Move", |cube| cube.apply_move(Move::R));
+        assert!(!history.end_group());
+        history.apply_grouped("Move", |cube| cube.apply_move(Move::U));
+        assert!(history.end_group());
+
+        assert_eq!(history.past_len(), 1);
+        assert_eq!(history.undo_label(), Some("Algorithm"));
+    }
+
+    #[test]
+    fn test_empty_group_records_nothing() {
+        let mut history = History::new(Cube::new(3));
+        history.begin_group("Nothing");
+        assert!(!history.end_group());
+        assert!(!history.can_undo());
+        assert!(!history.end_group());
+    }
+
+    #[test]
+    fn test_cancel_group_restores_start() {
+        let mut history = History::new(Cube::new(3));
+        history.begin_group("Scramble");
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::R);
+        history.push(cube);
+        history.cancel_group();
+
+        assert!(!history.in_group());
+        assert!(history.current().is_solved());
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn test_undo_closes_open_group() {
+        let mut history = History::new(Cube::new(3));
+        history.apply_grouped("First", |cube| cube.apply_move(Move::R));
+        history.begin_group("Second");
+        history.apply_grouped("Move", |cube| cube.apply_move(Move::U));
+
+        history.undo();
+        assert!(!history.in_group());
+        assert_eq!(history.undo_label(), Some("First"));
+        assert_eq!(history.redo_label(), Some("Second"));
+    }
+
+    #[test]
+    fn test_unlabeled_push_has_no_label() {
+        let mut history = History::new(Cube::new(3));
+        let mut cube = Cube::new(3);
+        cube.set_sticker(FaceName::F, 0, 0, Color::Red);
+        history.push(cube);
+        assert_eq!(history.undo_label(), None);
+    }
+
+    #[test]
+    fn test_timeline_lists_every_state() {
+        let mut history = History::new(Cube::new(3));
+        history.apply_grouped("R", |cube| cube.apply_move(Move::R));
+        let mut cube = history.current().clone();
+        cube.apply_move(Move::U);
+        history.push(cube);
+        history.apply_grouped("F", |cube| cube.apply_move(Move::F));
+        history.undo();
+
+        let timeline = history.timeline();
+        assert_eq!(timeline.len(), 4);
+        assert_eq!(history.timeline_len(), 4);
+        assert_eq!(history.position(), 2);
+        assert!(timeline[0].cube.is_solved());
+        assert_eq!(
+            timeline.iter().map(|e| e.label).collect::<Vec<_>>(),
+            vec![None, Some("R"), None, Some("F")]
+        );
+        assert!(timeline[2].is_current);
+        assert_eq!(timeline[3].index, 3);
+    }
+
+    #[test]
+    fn test_jump_to_restores_and_keeps_redo() {
+        let mut history = History::new(Cube::new(3));
+        history.apply_grouped("R", |cube| cube.apply_move(Move::R));
+        history.apply_grouped("U", |cube| cube.apply_move(Move::U));
+        let latest = history.current().clone();
+
+        assert!(history.jump_to(0).unwrap().is_solved());
+        assert_eq!(history.position(), 0);
+        assert_eq!(history.redo_label(), Some("R"));
+
+        assert_eq!(history.jump_to(2), Some(&latest));
+        assert!(history.jump_to(3).is_none());
+        assert_eq!(history.position(), 2);
+    }
+
+    /// Push a cube turned by `mv` from the current state
+    fn turn(history: &mut History, mv: Move) {
+        history.apply_grouped(mv.to_notation(), |cube| cube.apply_move(mv));
+    }
+
+    #[test]
+    fn test_edit_after_undo_starts_a_branch() {
+        let mut history = History::new(Cube::new(3));
+        turn(&mut history, Move::R);
+        turn(&mut history, Move::U);
+        history.undo();
+        turn(&mut history, Move::F);
+
+        let tree = history.tree();
+        let placed: Vec<_> = tree.iter().map(|n| (n.label, n.depth, n.lane, n.on_timeline)).collect();
+        assert_eq!(
+            placed,
+            vec![(None, 0, 0, true), (Some("R"), 1, 0, true), (Some("F"), 2, 0, true), (Some("U"), 2, 1, false)]
+        );
+        assert_eq!(tree[3].parent, Some(tree[1].id));
+        assert!(tree[2].is_current);
+        assert_eq!(history.timeline_len(), 3);
+
+        // Switching to the old branch puts it on the timeline
+        let branch = tree[3].id;
+        let mut expected = Cube::new(3);
+        expected.apply_moves(&[Move::R, Move::U]);
+        assert_eq!(history.go_to(branch), Some(&expected));
+        assert_eq!(history.undo_label(), Some("U"));
+        history.undo();
+        assert_eq!(history.redo_label(), Some("U"));
+        let tree = history.tree();
+        assert_eq!(tree.iter().find(|n| n.label == Some("F")).map(|n| (n.lane, n.on_timeline)), Some((1, false)));
+        assert!(history.go_to(99).is_none());
+    }
+
+    #[test]
+    fn test_branches_of_branches_get_their_own_lanes() {
+        let mut history = History::new(Cube::new(3));
+        turn(&mut history, Move::R);
+        history.undo();
+        turn(&mut history, Move::U);
+        turn(&mut history, Move::F);
+        history.undo();
+        turn(&mut history, Move::D);
+        history.jump_to(0);
+        turn(&mut history, Move::L);
+
+        assert_eq!(history.branch_count(), 4);
+        let lanes: Vec<_> = history.tree().iter().map(|n| (n.label, n.lane)).collect();
+        assert_eq!(
+            lanes,
+            vec![(None, 0), (Some("L"), 0), (Some("R"), 1), (Some("U"), 2), (Some("D"), 2), (Some("F"), 3)]
+        );
+    }
+
+    #[test]
+    fn test_size_limit_drops_old_branches() {
+        let mut history = History::with_max_size(Cube::new(3), 2);
+        turn(&mut history, Move::R);
+        history.undo();
+        turn(&mut history, Move::U);
+        turn(&mut history, Move::F);
+        assert_eq!(history.branch_count(), 2);
+
+        // The first state goes, and the R branch with it
+        turn(&mut history, Move::D);
+        assert_eq!(history.past_len(), 2);
+        assert_eq!(history.branch_count(), 1);
+        let tree = history.tree();
+        assert_eq!(tree.len(), 3);
+        assert_eq!((tree[0].parent, tree[0].label), (None, None));
+    }
+
+    #[test]
+    fn test_open_group_shows_in_tree() {
+        let mut history = History::new(Cube::new(3));
+        turn(&mut history, Move::R);
+        history.undo();
+        history.begin_group("Scramble");
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::F);
+        history.push(cube.clone());
+
+        assert!(!history.can_redo());
+        assert_eq!(history.timeline_len(), 1);
+        let tree = history.tree();
+        assert_eq!(tree[0].cube, &cube);
+        assert!(tree[0].is_current);
+        assert!(history.end_group());
+        assert_eq!(history.branch_count(), 2);
+    }
+
+    #[test]
+    fn test_memory_usage_grows_with_states() {
+        let mut history = History::new(Cube::new(20));
+        let empty = history.memory_usage();
+        assert!(empty > 2 * 6 * 20 * 20);
+        turn(&mut history, Move::R);
+        let one = history.memory_usage();
+        assert!(one - empty >= 6 * 20 * 20);
+        turn(&mut history, Move::U);
+        assert_eq!(history.state_count(), 3);
+        assert!(history.memory_usage() > one);
+    }
+
+    #[test]
+    fn test_memory_cap_evicts_oldest_states() {
+        let mut probe = History::new(Cube::new(20));
+        let state = {
+            let before = probe.memory_usage();
+            turn(&mut probe, Move::R);
+            probe.memory_usage() - before
+        };
+        let limits = HistoryLimits { max_depth: 1000, max_bytes: Some(probe.memory_usage() + 8 * state) };
+        let mut history = History::with_limits(Cube::new(20), limits);
+        for mv in [Move::R, Move::U, Move::F].repeat(20) {
+            turn(&mut history, mv);
+            assert!(history.memory_usage() <= limits.max_bytes.unwrap());
+        }
+        assert!((8..=10).contains(&history.past_len()), "{} undo steps", history.past_len());
+        assert_eq!(history.undo_label(), Some("F"));
+        assert!(!history.timeline()[0].cube.is_solved());
+    }
+
+    #[test]
+    fn test_memory_cap_drops_old_branch_ends_last() {
+        let mut history = History::new(Cube::new(3));
+        turn(&mut history, Move::R);
+        turn(&mut history, Move::U);
+        history.jump_to(0);
+        turn(&mut history, Move::F);
+        history.undo();
+        assert_eq!(history.branch_count(), 2);
+
+        // Only the current state fits
+        let tight = HistoryLimits { max_depth: 100, max_bytes: Some(1) };
+        history.set_limits(tight);
+        assert_eq!(history.limits(), tight);
+        assert_eq!(history.state_count(), 1);
+        assert!(history.current().is_solved());
+        assert!(!history.can_undo() && !history.can_redo());
+    }
+
+    #[test]
+    fn test_lowering_max_depth_evicts_at_once() {
+        let mut history = History::new(Cube::new(3));
+        for mv in [Move::R, Move::U, Move::F, Move::D] {
+            turn(&mut history, mv);
+        }
+        history.set_limits(HistoryLimits::with_max_depth(2));
+        assert_eq!(history.past_len(), 2);
+        assert_eq!(history.limits().max_bytes, HistoryLimits::default().max_bytes);
+        turn(&mut history, Move::L);
+        assert_eq!(history.past_len(), 2);
+    }
+
+    #[test]
+    fn test_move_between() {
+        let before = Cube::new(3);
+        let mut after = before.clone();
+        after.apply_move(Move::UPrime);
+        assert_eq!(move_between(&before, &after), Some(Move::UPrime));
+
+        after.apply_move(Move::R);
+        assert_eq!(move_between(&before, &after), None);
+        assert_eq!(move_between(&before, &before), None);
+
+        let mut before = Cube::new(4);
+        before.set_sticker(FaceName::U, 0, 0, Color::Red);
+        let mut after = before.clone();
+        after.apply_move(Move::Z);
+        assert_eq!(move_between(&before, &after), Some(Move::Z));
+    }
+
+    #[test]
+    fn test_changes_are_described_when_recorded() {
+        let mut history = History::new(Cube::new(3));
+        turn(&mut history, Move::F2);
+        history.apply_grouped("Scramble", |cube| cube.apply_move(Move::R));
+        let mut edited = history.current().clone();
+        edited.set_sticker(FaceName::F, 0, 0, Color::Orange);
+        history.push(edited);
+        history.push(Cube::new(4));
+
+        let descriptions: Vec<&str> = history.timeline().iter().map(|entry| entry.description).collect();
+        assert_eq!(descriptions, vec!["Start", "F2", "Scramble", "Edit 1 sticker", "New 4x4 cube"]);
+        assert_eq!(history.tree()[1].description, "F2");
+
+        history.set_limits(HistoryLimits::with_max_depth(2));
+        assert_eq!(history.timeline()[0].description, "Start");
+    }
+
+    #[test]
+    fn test_unchanged_push_records_nothing() {
+        let mut history = History::new(Cube::new(3));
+        history.push(Cube::new(3));
+        assert!(!history.can_undo());
+
+        turn(&mut history, Move::R);
+        let current = history.current().clone();
+        history.push(current);
+        assert_eq!(history.past_len(), 1);
+        assert_eq!(history.timeline_len(), 2);
+    }
 }
@@ -1,10 +1,30 @@
 //! State management module
 //!
 //! This module provides state management functionality for the application,
-//! including history tracking for undo/redo operations and tutorial progress tracking.
+//! including history tracking for undo/redo operations, tutorial progress tracking
+//! (with daily practice streaks and goals, and per-lesson analytics), timed
+//! solve records with WCA penalties, move recording for replays,
+//! reconstruction of smart cube solves, first-launch onboarding, and the
+//! app-wide event bus.
 
+mod events;
 mod history;
+mod onboarding;
 mod progress;
+mod recorder;
+mod reconstruction;
+mod smart_capture;
+mod solve_record;
 
-pub use history::History;
-pub use progress::{Progress, LessonId, PracticeStats};
+pub use events::{AppEvent, EventBus, EventListener};
+pub use history::{History, HistoryLimits, HistoryNode, TimelineEntry};
+pub use onboarding::{InputMethod, Onboarding, OnboardingStep, UserProfile, CUBE_SIZES, MAX_NAME_LEN};
+pub use progress::{
+    today, AnalyticsTotals, DailyGoal, LessonAnalytics, LessonId, LessonSummary, Placement, PracticeStats, Progress,
+    QuizScore, Streak, NEEDS_HELP_PERCENT,
+};
+pub use reconstruction::{phases_done, Phase, PhaseSplit, Reconstruction};
+pub use recorder::{MoveRecorder, MoveSource, Replay, TimedMove};
+pub(crate) use recorder::now_ms;
+pub use smart_capture::{CaptureError, SmartCubeCapture};
+pub use solve_record::{format_time, inspection_penalty, Penalty, SolveRecord, INSPECTION_DNF_MS, INSPECTION_MS};
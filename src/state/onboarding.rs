@@ -0,0 +1,309 @@
+//! First-launch onboarding
+//!
+//! A short guided setup shown until a [`UserProfile`] has been saved: pick
+//! the cube size, choose how cubes are entered (scanned with the camera,
+//! painted sticker by sticker, or turned on a virtual cube), calibrate the
+//! camera when scanning, and name the profile. Calibration is optional and
+//! only offered to scanners.
+
+use serde::{Deserialize, Serialize};
+
+/// Smallest and largest cube sizes the app supports
+pub const CUBE_SIZES: std::ops::RangeInclusive<usize> = 2..=20;
+
+/// Longest profile name
+pub const MAX_NAME_LEN: usize = 24;
+
+/// How the user enters the cube they hold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputMethod {
+    /// Scan each face with the camera
+    Scan,
+    /// Paint the stickers on the flat net
+    Paint,
+    /// Copy the moves onto a virtual cube
+    Virtual,
+}
+
+impl InputMethod {
+    /// All methods, in the order they are offered
+    pub const ALL: [InputMethod; 3] = [InputMethod::Scan, InputMethod::Paint, InputMethod::Virtual];
+
+    /// Short name
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputMethod::Scan => "Scan with camera",
+            InputMethod::Paint => "Paint the stickers",
+            InputMethod::Virtual => "Virtual cube",
+        }
+    }
+
+    /// One-line explanation for the choice
+    pub fn description(&self) -> &'static str {
+        match self {
+            InputMethod::Scan => "Show each face to the camera and the app reads the colors.",
+            InputMethod::Paint => "Tap a color, then tap the stickers to match your cube.",
+            InputMethod::Virtual => "Start from a solved cube and turn it by swiping.",
+        }
+    }
+}
+
+/// The user's setup from onboarding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserProfile {
+    /// Name shown in the app
+    pub name: String,
+    /// Cube size to start with
+    pub cube_size: usize,
+    /// Preferred way to enter a cube
+    pub input_method: InputMethod,
+    /// Whether the camera was calibrated during onboarding
+    pub calibrated: bool,
+}
+
+impl Default for UserProfile {
+    /// The profile saved when the setup is skipped
+    fn default() -> Self {
+        Self { name: String::new(), cube_size: 3, input_method: InputMethod::Paint, calibrated: false }
+    }
+}
+
+/// Steps of onboarding, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OnboardingStep {
+    Welcome,
+    CubeSize,
+    InputMethod,
+    /// Only when scanning
+    Calibration,
+    Profile,
+    Done,
+}
+
+impl OnboardingStep {
+    /// Heading for the step
+    pub fn title(&self) -> &'static str {
+        match self {
+            OnboardingStep::Welcome => "Welcome!",
+            OnboardingStep::CubeSize => "Which cube do you have?",
+            OnboardingStep::InputMethod => "How do you want to enter your cube?",
+            OnboardingStep::Calibration => "Teach the camera your colors",
+            OnboardingStep::Profile => "What should we call you?",
+            OnboardingStep::Done => "All set!",
+        }
+    }
+}
+
+/// Progress through onboarding and the choices made so far
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Onboarding {
+    step: OnboardingStep,
+    cube_size: usize,
+    input_method: Option<InputMethod>,
+    calibrated: bool,
+    name: String,
+}
+
+impl Default for Onboarding {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Onboarding {
+    /// Start at the welcome step with a 3x3 picked
+    pub fn new() -> Self {
+        Self { step: OnboardingStep::Welcome, cube_size: 3, input_method: None, calibrated: false, name: String::new() }
+    }
+
+    /// The step being shown
+    pub fn step(&self) -> OnboardingStep {
+        self.step
+    }
+
+    /// The steps this user goes through, given the input method so far
+    pub fn steps(&self) -> Vec<OnboardingStep> {
+        let scanning = self.input_method == Some(InputMethod::Scan);
+        [
+            OnboardingStep::Welcome,
+            OnboardingStep::CubeSize,
+            OnboardingStep::InputMethod,
+            OnboardingStep::Calibration,
+            OnboardingStep::Profile,
+            OnboardingStep::Done,
+        ]
+        .into_iter()
+        .filter(|step| *step != OnboardingStep::Calibration || scanning)
+        .collect()
+    }
+
+    /// Position of the current step and the number of steps, for a progress
+    /// bar
+    pub fn position(&self) -> (usize, usize) {
+        let steps = self.steps();
+        (steps.iter().position(|step| *step == self.step).unwrap_or(0), steps.len())
+    }
+
+    /// Picked cube size
+    pub fn cube_size(&self) -> usize {
+        self.cube_size
+    }
+
+    /// Pick the cube size
+    pub fn set_cube_size(&mut self, size: usize) -> Result<(), String> {
+        if !CUBE_SIZES.contains(&size) {
+            return Err(format!("Cube size must be between {} and {}", CUBE_SIZES.start(), CUBE_SIZES.end()));
+        }
+        self.cube_size = size;
+        Ok(())
+    }
+
+    /// Picked input method, if any
+    pub fn input_method(&self) -> Option<InputMethod> {
+        self.input_method
+    }
+
+    /// Pick the input method; calibration is forgotten when leaving
+    /// scanning
+    pub fn set_input_method(&mut self, method: InputMethod) {
+        if method != InputMethod::Scan {
+            self.calibrated = false;
+        }
+        self.input_method = Some(method);
+    }
+
+    /// Whether the camera was calibrated
+    pub fn is_calibrated(&self) -> bool {
+        self.calibrated
+    }
+
+    /// Note that calibration finished, and move on
+    pub fn finish_calibration(&mut self) {
+        self.calibrated = true;
+        if self.step == OnboardingStep::Calibration {
+            self.advance();
+        }
+    }
+
+    /// Profile name typed so far
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Set the profile name, cut to [`MAX_NAME_LEN`] characters
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.chars().take(MAX_NAME_LEN).collect();
+    }
+
+    /// Whether the current step has what it needs to move on
+    ///
+    /// Calibration can always be skipped.
+    pub fn can_advance(&self) -> bool {
+        match self.step {
+            OnboardingStep::InputMethod => self.input_method.is_some(),
+            OnboardingStep::Profile => !self.name.trim().is_empty(),
+            OnboardingStep::Done => false,
+            _ => true,
+        }
+    }
+
+    /// Go to the next step; returns whether it moved
+    pub fn advance(&mut self) -> bool {
+        if !self.can_advance() {
+            return false;
+        }
+        let steps = self.steps();
+        let (index, _) = self.position();
+        self.step = steps[index + 1];
+        true
+    }
+
+    /// Go back a step; returns whether it moved
+    ///
+    /// Nothing goes back from the welcome step or from the end.
+    pub fn back(&mut self) -> bool {
+        let (index, _) = self.position();
+        if index == 0 || self.step == OnboardingStep::Done {
+            return false;
+        }
+        self.step = self.steps()[index - 1];
+        true
+    }
+
+    /// The profile, once every step is done
+    pub fn profile(&self) -> Option<UserProfile> {
+        if self.step != OnboardingStep::Done {
+            return None;
+        }
+        Some(UserProfile {
+            name: self.name.trim().to_string(),
+            cube_size: self.cube_size,
+            input_method: self.input_method?,
+            calibrated: self.calibrated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scanning_goes_through_calibration() {
+        let mut onboarding = Onboarding::new();
+        assert!(onboarding.advance());
+        onboarding.set_cube_size(4).unwrap();
+        assert!(onboarding.advance());
+        assert_eq!(onboarding.step(), OnboardingStep::InputMethod);
+        // A method must be picked first
+        assert!(!onboarding.advance());
+        onboarding.set_input_method(InputMethod::Scan);
+        assert!(onboarding.advance());
+        assert_eq!(onboarding.step(), OnboardingStep::Calibration);
+        assert_eq!(onboarding.position(), (3, 6));
+        onboarding.finish_calibration();
+        assert_eq!(onboarding.step(), OnboardingStep::Profile);
+        assert!(onboarding.profile().is_none());
+
+        onboarding.set_name("  Sam ");
+        assert!(onboarding.advance());
+        assert_eq!(
+            onboarding.profile(),
+            Some(UserProfile { name: "Sam".to_string(), cube_size: 4, input_method: InputMethod::Scan, calibrated: true })
+        );
+        assert!(!onboarding.back());
+    }
+
+    #[test]
+    fn test_other_methods_skip_calibration() {
+        let mut onboarding = Onboarding::new();
+        onboarding.advance();
+        onboarding.advance();
+        onboarding.set_input_method(InputMethod::Scan);
+        onboarding.advance();
+        onboarding.finish_calibration();
+        onboarding.back();
+        onboarding.back();
+        assert_eq!(onboarding.step(), OnboardingStep::InputMethod);
+
+        onboarding.set_input_method(InputMethod::Virtual);
+        assert!(!onboarding.is_calibrated());
+        onboarding.advance();
+        assert_eq!(onboarding.step(), OnboardingStep::Profile);
+        assert_eq!(onboarding.steps().len(), 5);
+        // A blank name doesn't do
+        onboarding.set_name("   ");
+        assert!(!onboarding.advance());
+        onboarding.set_name("A very long name that keeps on going");
+        assert_eq!(onboarding.name().chars().count(), MAX_NAME_LEN);
+    }
+
+    #[test]
+    fn test_cube_size_must_be_supported() {
+        let mut onboarding = Onboarding::new();
+        assert!(onboarding.set_cube_size(1).is_err());
+        assert!(onboarding.set_cube_size(21).is_err());
+        assert_eq!(onboarding.cube_size(), 3);
+        assert!(onboarding.set_cube_size(20).is_ok());
+    }
+}
@@ -1,9 +1,14 @@
 //! Progress tracking module for tutorial system
 //!
 //! Tracks completed lessons and practice statistics, persisting to local storage.
+//! Practice also keeps a daily streak and counts toward daily goals. Each
+//! lesson also records the time spent in it, how often it was started, the
+//! hints used and its quiz scores, summed up for the parent/teacher
+//! dashboard by [`Progress::lesson_summaries`]. Days are numbered from the
+//! Unix epoch (see [`today`]).
 
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 /// Unique identifier for a lesson
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -73,6 +78,12 @@ pub struct PracticeStats {
     pub best_time_seconds: Option<u32>,
     /// Average time (in seconds)
     pub average_time_seconds: Option<u32>,
+    /// Last day practiced
+    #[serde(default)]
+    pub last_day: Option<u32>,
+    /// Successful completions on `last_day`
+    #[serde(default)]
+    pub day_successes: u32,
 }
 
 impl PracticeStats {
@@ -84,6 +95,8 @@ impl PracticeStats {
             total_time_seconds: 0,
             best_time_seconds: None,
             average_time_seconds: None,
+            last_day: None,
+            day_successes: 0,
         }
     }
 
@@ -112,6 +125,27 @@ impl PracticeStats {
         }
     }
 
+    /// Record a practice attempt made on `day`
+    pub fn record_attempt_on(&mut self, day: u32, success: bool, time_seconds: u32) {
+        self.record_attempt(success, time_seconds);
+        if self.last_day != Some(day) {
+            self.last_day = Some(day);
+            self.day_successes = 0;
+        }
+        if success {
+            self.day_successes += 1;
+        }
+    }
+
+    /// Successful completions on `day`
+    pub fn successes_on(&self, day: u32) -> u32 {
+        if self.last_day == Some(day) {
+            self.day_successes
+        } else {
+            0
+        }
+    }
+
     /// Get success rate as a percentage (0-100)
     pub fn success_rate(&self) -> f32 {
         if self.attempts == 0 {
@@ -128,6 +162,193 @@ impl Default for PracticeStats {
     }
 }
 
+/// Consecutive days with practice
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Streak {
+    /// Length of the streak ending on `last_day`
+    pub current: u32,
+    /// Longest streak so far
+    pub best: u32,
+    /// Last day practiced
+    pub last_day: Option<u32>,
+}
+
+impl Streak {
+    /// Count practice on `day`
+    ///
+    /// Practicing again the same day keeps the streak; skipping a day
+    /// starts a new one. Days earlier than the last one are ignored.
+    pub fn record(&mut self, day: u32) {
+        match self.last_day {
+            Some(last) if day <= last => return,
+            Some(last) if day == last + 1 => self.current += 1,
+            _ => self.current = 1,
+        }
+        self.last_day = Some(day);
+        self.best = self.best.max(self.current);
+    }
+
+    /// Streak as of `day`: still alive if the last practice was that day
+    /// or the day before, otherwise 0
+    pub fn as_of(&self, day: u32) -> u32 {
+        match self.last_day {
+            Some(last) if last == day || last + 1 == day => self.current,
+            _ => 0,
+        }
+    }
+}
+
+/// A daily practice goal, e.g. "solve 3 crosses today"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyGoal {
+    /// Lesson to practice
+    pub lesson: LessonId,
+    /// Successful solves needed each day
+    pub target: u32,
+}
+
+impl DailyGoal {
+    /// Create a goal of `target` successful solves of `lesson` a day
+    pub fn new(lesson: LessonId, target: u32) -> Self {
+        Self { lesson, target }
+    }
+
+    /// Text for the goal, e.g. "Solve Cross 3 times today"
+    pub fn description(&self) -> String {
+        match self.target {
+            1 => format!("Solve {} once today", self.lesson.name()),
+            target => format!("Solve {} {} times today", self.lesson.name(), target),
+        }
+    }
+}
+
+/// Current day number (days since the Unix epoch)
+pub fn today() -> u32 {
+    (current_timestamp() / 86_400) as u32
+}
+
+/// Current Unix time in seconds
+#[cfg(not(target_arch = "wasm32"))]
+fn current_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Current Unix time in seconds
+#[cfg(target_arch = "wasm32")]
+fn current_timestamp() -> u64 {
+    (web_sys::js_sys::Date::now() / 1000.0) as u64
+}
+
+/// Average quiz score (percent) below which a lesson is flagged as needing
+/// help
+pub const NEEDS_HELP_PERCENT: f32 = 60.0;
+
+/// A quiz taken in a lesson
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuizScore {
+    /// Day the quiz was taken
+    pub day: u32,
+    /// Questions answered correctly
+    pub correct: u32,
+    /// Questions asked
+    pub total: u32,
+}
+
+impl QuizScore {
+    /// Score as a percentage (0-100); an empty quiz scores 0
+    pub fn percent(&self) -> f32 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.correct as f32 / self.total as f32 * 100.0
+        }
+    }
+}
+
+/// Time spent, attempts, hints and quiz scores for one lesson
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LessonAnalytics {
+    /// Total time spent in the lesson (in seconds)
+    pub time_seconds: u32,
+    /// Times the lesson was started
+    pub attempts: u32,
+    /// Hints shown
+    pub hints_used: u32,
+    /// Quiz results, oldest first
+    pub quiz_scores: Vec<QuizScore>,
+    /// Last day the lesson was worked on
+    pub last_day: Option<u32>,
+}
+
+impl LessonAnalytics {
+    /// Mean quiz score as a percentage, if a quiz was taken
+    pub fn average_quiz_percent(&self) -> Option<f32> {
+        if self.quiz_scores.is_empty() {
+            None
+        } else {
+            Some(self.quiz_scores.iter().map(QuizScore::percent).sum::<f32>() / self.quiz_scores.len() as f32)
+        }
+    }
+
+    /// Best quiz score as a percentage, if a quiz was taken
+    pub fn best_quiz_percent(&self) -> Option<f32> {
+        self.quiz_scores.iter().map(QuizScore::percent).reduce(f32::max)
+    }
+
+    /// Hints used per attempt (0 before the first attempt)
+    pub fn hints_per_attempt(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.hints_used as f32 / self.attempts as f32
+        }
+    }
+
+    fn touch(&mut self, day: u32) {
+        self.last_day = Some(self.last_day.map_or(day, |last| last.max(day)));
+    }
+}
+
+/// One row of the parent/teacher dashboard
+#[derive(Debug, Clone, PartialEq)]
+pub struct LessonSummary {
+    /// The lesson
+    pub lesson: LessonId,
+    /// Whether the lesson is completed
+    pub completed: bool,
+    /// Time, attempts, hints and quizzes so far
+    pub analytics: LessonAnalytics,
+    /// Whether the lesson's quiz average is below [`NEEDS_HELP_PERCENT`]
+    pub needs_help: bool,
+}
+
+/// Lesson analytics added up over every lesson
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AnalyticsTotals {
+    /// Total time spent in lessons (in seconds)
+    pub time_seconds: u32,
+    /// Lessons started
+    pub attempts: u32,
+    /// Hints shown
+    pub hints_used: u32,
+    /// Quizzes taken
+    pub quizzes: u32,
+    /// Mean score over every quiz, if any was taken
+    pub average_quiz_percent: Option<f32>,
+}
+
+/// Outcome of the placement assessment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Placement {
+    /// Lesson to start with, or `None` if the student knows the whole course
+    pub start: Option<LessonId>,
+    /// Lessons the student showed they already know
+    pub known: Vec<LessonId>,
+}
+
 /// Progress tracking data structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Progress {
@@ -135,6 +356,21 @@ pub struct Progress {
     completed_lessons: HashSet<LessonId>,
     /// Practice statistics per lesson
     practice_stats: HashMap<LessonId, PracticeStats>,
+    /// Ids of algorithms starred in the algorithm sheet
+    #[serde(default)]
+    favorite_algorithms: BTreeSet<String>,
+    /// Result of the placement assessment, if taken
+    #[serde(default)]
+    placement: Option<Placement>,
+    /// Daily practice streak
+    #[serde(default)]
+    streak: Streak,
+    /// Daily practice goals
+    #[serde(default)]
+    goals: Vec<DailyGoal>,
+    /// Time, attempts, hints and quiz scores per lesson
+    #[serde(default)]
+    lesson_analytics: HashMap<LessonId, LessonAnalytics>,
     /// Version for forward compatibility
     version: u32,
 }
@@ -148,6 +384,11 @@ impl Progress {
         Self {
             completed_lessons: HashSet::new(),
             practice_stats: HashMap::new(),
+            favorite_algorithms: BTreeSet::new(),
+            placement: None,
+            streak: Streak::default(),
+            goals: Vec::new(),
+            lesson_analytics: HashMap::new(),
             version: Self::VERSION,
         }
     }
@@ -186,10 +427,16 @@ impl Progress {
         }
     }
 
-    /// Record a practice attempt for a lesson
+    /// Record a practice attempt for a lesson, made today
     pub fn record_practice(&mut self, lesson_id: LessonId, success: bool, time_seconds: u32) {
+        self.record_practice_on(today(), lesson_id, success, time_seconds);
+    }
+
+    /// Record a practice attempt for a lesson, made on `day`
+    pub fn record_practice_on(&mut self, day: u32, lesson_id: LessonId, success: bool, time_seconds: u32) {
         let stats = self.practice_stats.entry(lesson_id).or_insert_with(PracticeStats::new);
-        stats.record_attempt(success, time_seconds);
+        stats.record_attempt_on(day, success, time_seconds);
+        self.streak.record(day);
     }
 
     /// Get practice statistics for a lesson
@@ -202,10 +449,168 @@ impl Progress {
         &self.practice_stats
     }
 
+    /// Star or unstar an algorithm; returns whether it is now a favorite
+    pub fn toggle_favorite(&mut self, algorithm_id: &str) -> bool {
+        if self.favorite_algorithms.remove(algorithm_id) {
+            false
+        } else {
+            self.favorite_algorithms.insert(algorithm_id.to_string());
+            true
+        }
+    }
+
+    /// Check if an algorithm is starred
+    pub fn is_favorite(&self, algorithm_id: &str) -> bool {
+        self.favorite_algorithms.contains(algorithm_id)
+    }
+
+    /// Get starred algorithm ids, sorted
+    pub fn favorite_algorithms(&self) -> Vec<String> {
+        self.favorite_algorithms.iter().cloned().collect()
+    }
+
+    /// Store a placement result, completing the lessons it found known
+    pub fn record_placement(&mut self, placement: Placement) {
+        self.completed_lessons.extend(placement.known.iter().cloned());
+        self.placement = Some(placement);
+    }
+
+    /// Result of the placement assessment, if taken
+    pub fn placement(&self) -> Option<&Placement> {
+        self.placement.as_ref()
+    }
+
+    /// Daily practice streak
+    pub fn streak(&self) -> &Streak {
+        &self.streak
+    }
+
+    /// Add a daily goal, replacing any goal for the same lesson
+    pub fn set_goal(&mut self, goal: DailyGoal) {
+        match self.goals.iter_mut().find(|g| g.lesson == goal.lesson) {
+            Some(existing) => *existing = goal,
+            None => self.goals.push(goal),
+        }
+    }
+
+    /// Remove the daily goal for a lesson
+    pub fn remove_goal(&mut self, lesson_id: &LessonId) {
+        self.goals.retain(|goal| goal.lesson != *lesson_id);
+    }
+
+    /// Daily goals, in the order they were set
+    pub fn goals(&self) -> &[DailyGoal] {
+        &self.goals
+    }
+
+    /// Successful solves toward `goal` on `day`, capped at its target
+    pub fn goal_progress(&self, goal: &DailyGoal, day: u32) -> u32 {
+        self.practice_stats
+            .get(&goal.lesson)
+            .map_or(0, |stats| stats.successes_on(day))
+            .min(goal.target)
+    }
+
+    /// Check if a goal is met on `day`
+    pub fn is_goal_met(&self, goal: &DailyGoal, day: u32) -> bool {
+        self.goal_progress(goal, day) >= goal.target
+    }
+
+    /// Number of goals met on `day`
+    pub fn goals_met(&self, day: u32) -> usize {
+        self.goals.iter().filter(|goal| self.is_goal_met(goal, day)).count()
+    }
+
+    fn analytics_mut(&mut self, lesson_id: LessonId) -> &mut LessonAnalytics {
+        self.lesson_analytics.entry(lesson_id).or_default()
+    }
+
+    /// Count a start of a lesson, today
+    pub fn start_lesson(&mut self, lesson_id: LessonId) {
+        self.start_lesson_on(today(), lesson_id);
+    }
+
+    /// Count a start of a lesson on `day`
+    pub fn start_lesson_on(&mut self, day: u32, lesson_id: LessonId) {
+        let analytics = self.analytics_mut(lesson_id);
+        analytics.attempts += 1;
+        analytics.touch(day);
+    }
+
+    /// Add time spent in a lesson
+    pub fn add_lesson_time(&mut self, lesson_id: LessonId, seconds: u32) {
+        let analytics = self.analytics_mut(lesson_id);
+        analytics.time_seconds = analytics.time_seconds.saturating_add(seconds);
+    }
+
+    /// Count a hint shown in a lesson
+    pub fn record_hint(&mut self, lesson_id: LessonId) {
+        self.analytics_mut(lesson_id).hints_used += 1;
+    }
+
+    /// Record a quiz taken today
+    pub fn record_quiz(&mut self, lesson_id: LessonId, correct: u32, total: u32) {
+        self.record_quiz_on(today(), lesson_id, correct, total);
+    }
+
+    /// Record a quiz taken on `day`; `correct` is capped at `total`
+    pub fn record_quiz_on(&mut self, day: u32, lesson_id: LessonId, correct: u32, total: u32) {
+        let analytics = self.analytics_mut(lesson_id);
+        analytics.quiz_scores.push(QuizScore { day, correct: correct.min(total), total });
+        analytics.touch(day);
+    }
+
+    /// Time, attempts, hints and quiz scores for a lesson, if it was
+    /// worked on
+    pub fn lesson_analytics(&self, lesson_id: &LessonId) -> Option<&LessonAnalytics> {
+        self.lesson_analytics.get(lesson_id)
+    }
+
+    /// A dashboard row for every lesson, in course order
+    pub fn lesson_summaries(&self) -> Vec<LessonSummary> {
+        LessonId::all()
+            .into_iter()
+            .map(|lesson| {
+                let analytics = self.lesson_analytics.get(&lesson).cloned().unwrap_or_default();
+                let needs_help = analytics.average_quiz_percent().is_some_and(|percent| percent < NEEDS_HELP_PERCENT);
+                LessonSummary { completed: self.is_lesson_completed(&lesson), lesson, analytics, needs_help }
+            })
+            .collect()
+    }
+
+    /// Lessons whose quiz average is below [`NEEDS_HELP_PERCENT`], in course
+    /// order
+    pub fn lessons_needing_help(&self) -> Vec<LessonId> {
+        self.lesson_summaries().into_iter().filter(|summary| summary.needs_help).map(|summary| summary.lesson).collect()
+    }
+
+    /// Lesson analytics added up over every lesson
+    pub fn analytics_totals(&self) -> AnalyticsTotals {
+        let mut totals = AnalyticsTotals::default();
+        let mut percent_sum = 0.0;
+        for analytics in self.lesson_analytics.values() {
+            totals.time_seconds = totals.time_seconds.saturating_add(analytics.time_seconds);
+            totals.attempts += analytics.attempts;
+            totals.hints_used += analytics.hints_used;
+            totals.quizzes += analytics.quiz_scores.len() as u32;
+            percent_sum += analytics.quiz_scores.iter().map(QuizScore::percent).sum::<f32>();
+        }
+        if totals.quizzes > 0 {
+            totals.average_quiz_percent = Some(percent_sum / totals.quizzes as f32);
+        }
+        totals
+    }
+
     /// Reset all progress (for testing or user request)
+    ///
+    /// Daily goals are settings and are kept.
     pub fn reset(&mut self) {
         self.completed_lessons.clear();
         self.practice_stats.clear();
+        self.favorite_algorithms.clear();
+        self.placement = None;
+        self.streak = Streak::default();
+        self.lesson_analytics.clear();
     }
 
     /// Serialize to JSON string
@@ -366,6 +771,154 @@ mod tests {
         assert_eq!(stats.successes, 1);
     }
 
+    #[test]
+    fn test_favorite_algorithms() {
+        let mut progress = Progress::new();
+        assert!(progress.toggle_favorite("pll-ua-perm"));
+        assert!(progress.toggle_favorite("oll-sune"));
+        assert!(progress.is_favorite("pll-ua-perm"));
+        assert_eq!(progress.favorite_algorithms(), vec!["oll-sune", "pll-ua-perm"]);
+
+        assert!(!progress.toggle_favorite("pll-ua-perm"));
+        assert!(!progress.is_favorite("pll-ua-perm"));
+
+        let loaded = Progress::from_json(&progress.to_json().unwrap()).unwrap();
+        assert!(loaded.is_favorite("oll-sune"));
+    }
+
+    #[test]
+    fn test_favorites_default_when_missing() {
+        let json = r#"{"completed_lessons":[],"practice_stats":{},"version":1}"#;
+        let progress = Progress::from_json(json).unwrap();
+        assert!(progress.favorite_algorithms().is_empty());
+    }
+
+    #[test]
+    fn test_record_placement() {
+        let mut progress = Progress::from_json(r#"{"completed_lessons":[],"practice_stats":{},"version":1}"#).unwrap();
+        assert!(progress.placement().is_none());
+
+        progress.record_placement(Placement {
+            start: Some(LessonId::Cross),
+            known: vec![LessonId::Notation, LessonId::Colors],
+        });
+        let loaded = Progress::from_json(&progress.to_json().unwrap()).unwrap();
+        assert_eq!(loaded.placement().unwrap().start, Some(LessonId::Cross));
+        assert_eq!(loaded.completed_count(), 2);
+
+        progress.reset();
+        assert!(progress.placement().is_none());
+    }
+
+    #[test]
+    fn test_streak() {
+        let mut progress = Progress::new();
+        assert_eq!(progress.streak().as_of(100), 0);
+
+        progress.record_practice_on(100, LessonId::Cross, false, 30);
+        progress.record_practice_on(100, LessonId::Cross, true, 30);
+        progress.record_practice_on(101, LessonId::OLL, true, 20);
+        assert_eq!(progress.streak().as_of(101), 2);
+        assert_eq!(progress.streak().as_of(102), 2, "Still alive until the day is over");
+        assert_eq!(progress.streak().as_of(103), 0);
+
+        progress.record_practice_on(104, LessonId::Cross, true, 30);
+        assert_eq!(progress.streak().current, 1);
+        assert_eq!(progress.streak().best, 2);
+
+        progress.reset();
+        assert_eq!(progress.streak(), &Streak::default());
+    }
+
+    #[test]
+    fn test_daily_goals() {
+        let mut progress = Progress::new();
+        progress.set_goal(DailyGoal::new(LessonId::Cross, 2));
+        progress.set_goal(DailyGoal::new(LessonId::PLL, 1));
+        progress.set_goal(DailyGoal::new(LessonId::Cross, 3));
+        assert_eq!(progress.goals().len(), 2);
+        let cross = progress.goals()[0].clone();
+        assert_eq!(cross.description(), "Solve Cross 3 times today");
+
+        progress.record_practice_on(7, LessonId::Cross, true, 10);
+        progress.record_practice_on(7, LessonId::Cross, false, 10);
+        assert_eq!(progress.goal_progress(&cross, 7), 1);
+        progress.record_practice_on(7, LessonId::Cross, true, 10);
+        progress.record_practice_on(7, LessonId::Cross, true, 10);
+        progress.record_practice_on(7, LessonId::Cross, true, 10);
+        assert_eq!(progress.goal_progress(&cross, 7), 3);
+        assert_eq!(progress.goals_met(7), 1);
+
+        // A new day starts the count again
+        assert_eq!(progress.goal_progress(&cross, 8), 0);
+        progress.record_practice_on(8, LessonId::PLL, true, 40);
+        assert_eq!(progress.goals_met(8), 1);
+
+        progress.remove_goal(&LessonId::PLL);
+        assert_eq!(progress.goals(), &[cross][..]);
+    }
+
+    #[test]
+    fn test_streak_defaults_when_missing() {
+        let json = r#"{"completed_lessons":[],"practice_stats":{"Cross":{"attempts":1,"successes":1,"total_time_seconds":5,"best_time_seconds":5,"average_time_seconds":5}},"version":1}"#;
+        let progress = Progress::from_json(json).unwrap();
+        assert_eq!(progress.streak().as_of(0), 0);
+        assert!(progress.goals().is_empty());
+        assert_eq!(progress.get_practice_stats(&LessonId::Cross).unwrap().last_day, None);
+    }
+
+    #[test]
+    fn test_lesson_analytics() {
+        let mut progress = Progress::new();
+        progress.start_lesson_on(100, LessonId::Cross);
+        progress.add_lesson_time(LessonId::Cross, 300);
+        progress.record_hint(LessonId::Cross);
+        progress.record_hint(LessonId::Cross);
+        progress.record_quiz_on(100, LessonId::Cross, 2, 4);
+        progress.start_lesson_on(101, LessonId::Cross);
+        progress.add_lesson_time(LessonId::Cross, 200);
+        progress.record_quiz_on(101, LessonId::Cross, 3, 4);
+
+        let cross = progress.lesson_analytics(&LessonId::Cross).unwrap();
+        assert_eq!(cross.time_seconds, 500);
+        assert_eq!(cross.attempts, 2);
+        assert_eq!(cross.hints_per_attempt(), 1.0);
+        assert_eq!(cross.average_quiz_percent(), Some(62.5));
+        assert_eq!(cross.best_quiz_percent(), Some(75.0));
+        assert_eq!(cross.last_day, Some(101));
+        assert!(progress.lesson_analytics(&LessonId::OLL).is_none());
+    }
+
+    #[test]
+    fn test_lesson_summaries_and_totals() {
+        let mut progress = Progress::new();
+        progress.complete_lesson(LessonId::Colors);
+        progress.start_lesson_on(100, LessonId::Colors);
+        progress.record_quiz_on(100, LessonId::Colors, 4, 4);
+        progress.start_lesson_on(100, LessonId::OLL);
+        progress.add_lesson_time(LessonId::OLL, 60);
+        // More correct answers than questions are capped
+        progress.record_quiz_on(100, LessonId::OLL, 9, 5);
+        progress.record_quiz_on(100, LessonId::OLL, 0, 5);
+        progress.record_hint(LessonId::OLL);
+
+        let summaries = progress.lesson_summaries();
+        assert_eq!(summaries.len(), LessonId::all().len());
+        assert_eq!(summaries[1].lesson, LessonId::Colors);
+        assert!(summaries[1].completed && !summaries[1].needs_help);
+        assert_eq!(summaries[0].analytics, LessonAnalytics::default());
+        assert_eq!(progress.lessons_needing_help(), vec![LessonId::OLL]);
+
+        let totals = progress.analytics_totals();
+        assert_eq!((totals.time_seconds, totals.attempts, totals.hints_used, totals.quizzes), (60, 2, 1, 3));
+        assert!((totals.average_quiz_percent.unwrap() - 200.0 / 3.0).abs() < 1e-4);
+
+        let restored = Progress::from_json(&progress.to_json().unwrap()).unwrap();
+        assert_eq!(restored.lesson_summaries(), summaries);
+        progress.reset();
+        assert_eq!(progress.analytics_totals(), AnalyticsTotals::default());
+    }
+
     #[test]
     fn test_lesson_id_name() {
         assert_eq!(LessonId::Notation.name(), "Cube Notation");
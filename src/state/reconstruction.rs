@@ -0,0 +1,257 @@
+//! Phase splits for recorded 3x3 solves
+//!
+//! Splits a `Replay` into the four CFOP phases (cross, F2L, OLL, PLL) by
+//! replaying it and finding the first move at which each phase is done.
+//! The cross may be on any face. A [`Reconstruction`] keeps the replay with
+//! its splits and TPS so they can be stored with the solve.
+
+use super::recorder::Replay;
+use crate::cube::{Cube, FaceName};
+use serde::{Deserialize, Serialize};
+
+/// A CFOP solving phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Phase {
+    /// Bottom cross
+    Cross,
+    /// First two layers
+    F2l,
+    /// Orient last layer
+    Oll,
+    /// Permute last layer
+    Pll,
+}
+
+impl Phase {
+    /// All phases in solving order
+    pub const ALL: [Phase; 4] = [Phase::Cross, Phase::F2l, Phase::Oll, Phase::Pll];
+
+    /// Short display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            Phase::Cross => "Cross",
+            Phase::F2l => "F2L",
+            Phase::Oll => "OLL",
+            Phase::Pll => "PLL",
+        }
+    }
+}
+
+/// Time and moves spent on one phase
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseSplit {
+    /// The phase
+    pub phase: Phase,
+    /// Index of the first move of the phase
+    pub first_move: usize,
+    /// Number of moves in the phase
+    pub move_count: usize,
+    /// When the phase started (ms since recording started)
+    pub start_ms: u64,
+    /// When the phase was finished (ms since recording started)
+    pub end_ms: u64,
+}
+
+impl PhaseSplit {
+    /// Time spent on the phase in milliseconds
+    pub fn duration_ms(&self) -> u64 {
+        self.end_ms - self.start_ms
+    }
+
+    /// Turns per second during the phase
+    pub fn tps(&self) -> f64 {
+        let duration = self.duration_ms();
+        if duration == 0 {
+            return 0.0;
+        }
+        self.move_count as f64 / (duration as f64 / 1000.0)
+    }
+}
+
+/// Side faces, whose bottom row touches D
+const SIDE_FACES: [FaceName; 4] = [FaceName::F, FaceName::R, FaceName::B, FaceName::L];
+
+/// Check whether every listed sticker of a face matches its center
+fn matches_center(cube: &Cube, face: FaceName, stickers: &[(usize, usize)]) -> bool {
+    let face = cube.get_face(face);
+    let center = face.get(1, 1);
+    stickers.iter().all(|(row, col)| face.get(*row, *col) == center)
+}
+
+/// How many phases are done with D as the bottom face (0-4)
+fn phases_done_on_d(cube: &Cube) -> usize {
+    let all: Vec<(usize, usize)> = (0..3).flat_map(|r| (0..3).map(move |c| (r, c))).collect();
+    let bottom_two_rows: Vec<(usize, usize)> = all.iter().copied().filter(|(r, _)| *r > 0).collect();
+
+    let cross = matches_center(cube, FaceName::D, &[(0, 1), (1, 0), (1, 2), (2, 1)])
+        && SIDE_FACES.iter().all(|f| matches_center(cube, *f, &[(2, 1)]));
+    if !cross {
+        return 0;
+    }
+
+    let f2l = matches_center(cube, FaceName::D, &all)
+        && SIDE_FACES.iter().all(|f| matches_center(cube, *f, &bottom_two_rows));
+    if !f2l {
+        return 1;
+    }
+
+    if !matches_center(cube, FaceName::U, &all) {
+        return 2;
+    }
+    if !cube.is_solved() {
+        return 3;
+    }
+    4
+}
+
+/// How many CFOP phases are done on a 3x3, with the cross on any face (0-4)
+pub fn phases_done(cube: &Cube) -> usize {
+    if cube.size() != 3 {
+        return 0;
+    }
+    cube.orientations()
+        .iter()
+        .map(phases_done_on_d)
+        .max()
+        .unwrap_or(0)
+}
+
+impl Replay {
+    /// Split a 3x3 replay into CFOP phases
+    ///
+    /// Only phases that were finished are returned, so an unfinished solve
+    /// has fewer than four splits. Other cube sizes have no splits.
+    pub fn phase_splits(&self) -> Vec<PhaseSplit> {
+        if self.cube_size != 3 {
+            return Vec::new();
+        }
+
+        // Phases done after each number of moves (0 = the scrambled state),
+        // replaying the moves once
+        let mut cube = self.initial_state();
+        let mut progress = Vec::with_capacity(self.moves.len() + 1);
+        progress.push(phases_done(&cube));
+        for timed in &self.moves {
+            cube.apply_parsed_move(&timed.mv);
+            progress.push(phases_done(&cube));
+        }
+
+        let mut splits = Vec::new();
+        let mut first_move = 0;
+        let mut start_ms = 0;
+        for (index, phase) in Phase::ALL.iter().enumerate() {
+            let Some(done_after) = progress.iter().position(|done| *done > index) else {
+                break;
+            };
+            let done_after = done_after.max(first_move);
+            let end_ms = match done_after {
+                0 => 0,
+                n => self.moves[n - 1].time_ms,
+            }
+            .max(start_ms);
+
+            splits.push(PhaseSplit {
+                phase: *phase,
+                first_move,
+                move_count: done_after - first_move,
+                start_ms,
+                end_ms,
+            });
+            first_move = done_after;
+            start_ms = end_ms;
+        }
+        splits
+    }
+}
+
+/// A recorded solve with its phase splits and TPS
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reconstruction {
+    /// Scramble and timed solve moves
+    pub replay: Replay,
+    /// CFOP phases that were finished (3x3 only)
+    pub splits: Vec<PhaseSplit>,
+    /// Turns per second over the whole solve
+    pub tps: f64,
+}
+
+impl Reconstruction {
+    /// Split and time a replay
+    pub fn new(replay: Replay) -> Self {
+        Self { splits: replay.phase_splits(), tps: replay.tps(), replay }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::notation::ParsedMove;
+    use crate::cube::Move;
+    use crate::state::{MoveRecorder, MoveSource};
+
+    #[test]
+    fn test_phases_done() {
+        assert_eq!(phases_done(&Cube::new(3)), 4);
+
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::U);
+        assert_eq!(phases_done(&cube), 3);
+
+        // Cross on U instead of D
+        let mut cube = Cube::new(3);
+        cube.apply_move(Move::D);
+        assert_eq!(phases_done(&cube), 3);
+
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::U, Move::RPrime]);
+        assert_eq!(phases_done(&cube), 1);
+
+        assert_eq!(phases_done(&Cube::new(4)), 0);
+    }
+
+    #[test]
+    fn test_phase_splits() {
+        // Scramble breaks F2L and the last layer; undo it move by move
+        let scramble = [Move::R, Move::U, Move::RPrime, Move::U];
+        let mut recorder = MoveRecorder::new(3, scramble.iter().map(|m| ParsedMove::Basic(*m)).collect());
+        for (i, mv) in [Move::UPrime, Move::R, Move::UPrime, Move::RPrime].iter().enumerate() {
+            recorder.record_at(ParsedMove::Basic(*mv), MoveSource::Keyboard, 1000 * (i as u64 + 1));
+        }
+        let splits = recorder.stop().phase_splits();
+
+        assert_eq!(splits.len(), 4);
+        assert_eq!(splits[0].phase, Phase::Cross);
+        assert_eq!(splits[0].move_count, 0);
+        assert_eq!(splits[1].phase, Phase::F2l);
+        assert_eq!(splits[1].move_count, 3);
+        assert_eq!(splits[1].end_ms, 3000);
+        assert_eq!(splits[3].phase, Phase::Pll);
+        assert_eq!(splits[3].end_ms, 4000);
+        assert_eq!(splits.iter().map(|s| s.move_count).sum::<usize>(), 4);
+        assert!((splits[1].tps() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_unfinished_solve_has_fewer_splits() {
+        // After R alone, only PLL is left (with L as the bottom)
+        let recorder = MoveRecorder::new(3, vec![ParsedMove::Basic(Move::R)]);
+        let splits = recorder.replay().phase_splits();
+        assert_eq!(splits.len(), 3);
+        assert_eq!(splits[2].phase, Phase::Oll);
+
+        let recorder = MoveRecorder::new(4, Vec::new());
+        assert!(recorder.replay().phase_splits().is_empty());
+    }
+
+    #[test]
+    fn test_reconstruction_keeps_splits() {
+        let mut recorder = MoveRecorder::new(3, vec![ParsedMove::Basic(Move::R)]);
+        recorder.record_at(ParsedMove::Basic(Move::RPrime), MoveSource::SmartCube, 500);
+        let reconstruction = Reconstruction::new(recorder.stop());
+        assert_eq!(reconstruction.splits.len(), 4);
+        assert!((reconstruction.tps - 2.0).abs() < f64::EPSILON);
+
+        let json = serde_json::to_string(&reconstruction).unwrap();
+        assert_eq!(serde_json::from_str::<Reconstruction>(&json).unwrap(), reconstruction);
+    }
+}
@@ -0,0 +1,337 @@
+//! Move recording and replay
+//!
+//! `MoveRecorder` timestamps every move applied to the cube, whether it
+//! came from the keyboard, a smart cube or the UI, into a `Replay`. A replay
+//! can be saved as JSON, stepped through to rebuild the cube at any moment,
+//! and turned into animations for "watch my solve" playback.
+
+use crate::cube::notation::ParsedMove;
+use crate::cube::{Cube, SerializationError};
+use serde::{Deserialize, Serialize};
+
+/// Where a recorded move came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MoveSource {
+    /// Keyboard shortcut
+    Keyboard,
+    /// Bluetooth smart cube
+    SmartCube,
+    /// On-screen button or gesture
+    Ui,
+}
+
+/// A move together with when it was made
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimedMove {
+    /// Milliseconds since recording started
+    pub time_ms: u64,
+    /// The move that was applied
+    pub mv: ParsedMove,
+    /// Where the move came from
+    pub source: MoveSource,
+}
+
+/// A recorded sequence of timed moves, starting from a scrambled cube
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    /// Cube size the moves were made on
+    pub cube_size: usize,
+    /// Scramble applied to a solved cube before the first move
+    pub scramble: Vec<ParsedMove>,
+    /// Recorded moves in order
+    pub moves: Vec<TimedMove>,
+    /// When recording started (Unix timestamp in milliseconds)
+    pub started_at: u64,
+}
+
+impl Replay {
+    /// Create an empty replay
+    pub fn new(cube_size: usize, scramble: Vec<ParsedMove>) -> Self {
+        Self {
+            cube_size,
+            scramble,
+            moves: Vec::new(),
+            started_at: 0,
+        }
+    }
+
+    /// Number of recorded moves
+    pub fn move_count(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Time of the last move in milliseconds (0 if there are no moves)
+    pub fn duration_ms(&self) -> u64 {
+        self.moves.last().map_or(0, |m| m.time_ms)
+    }
+
+    /// Average turns per second over the whole replay
+    pub fn tps(&self) -> f64 {
+        let duration = self.duration_ms();
+        if duration == 0 {
+            return 0.0;
+        }
+        self.moves.len() as f64 / (duration as f64 / 1000.0)
+    }
+
+    /// Get the scrambled cube the replay starts from
+    pub fn initial_state(&self) -> Cube {
+        let mut cube = Cube::new(self.cube_size);
+        for mv in &self.scramble {
+            cube.apply_parsed_move(mv);
+        }
+        cube
+    }
+
+    /// Get the cube after every move made up to and including `time_ms`
+    pub fn state_at(&self, time_ms: u64) -> Cube {
+        self.state_after(self.moves_until(time_ms))
+    }
+
+    /// Get the cube after the first `count` moves
+    pub fn state_after(&self, count: usize) -> Cube {
+        let mut cube = self.initial_state();
+        for timed in self.moves.iter().take(count) {
+            cube.apply_parsed_move(&timed.mv);
+        }
+        cube
+    }
+
+    /// Get the cube after every recorded move
+    pub fn final_state(&self) -> Cube {
+        self.state_after(self.moves.len())
+    }
+
+    /// Number of moves made up to and including `time_ms`
+    pub fn moves_until(&self, time_ms: u64) -> usize {
+        self.moves.partition_point(|m| m.time_ms <= time_ms)
+    }
+
+    /// Recorded moves as a notation string (e.g., "R U R' U'")
+    pub fn to_notation(&self) -> String {
+        self.moves
+            .iter()
+            .map(|m| m.mv.to_notation())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Serialize to a JSON string
+    pub fn to_json(&self) -> Result<String, SerializationError> {
+        serde_json::to_string(self).map_err(|e| SerializationError::SerializationFailed(e.to_string()))
+    }
+
+    /// Deserialize from a JSON string
+    pub fn from_json(json: &str) -> Result<Self, SerializationError> {
+        serde_json::from_str(json).map_err(|e| SerializationError::DeserializationFailed(e.to_string()))
+    }
+}
+
+#[cfg(all(feature = "desktop_3d", not(target_arch = "wasm32")))]
+impl Replay {
+    /// Shortest animation used for a move, so very fast turns stay visible
+    const MIN_ANIMATION_MS: u64 = 50;
+    /// Longest animation used for a move, so pauses don't slow the turn itself
+    const MAX_ANIMATION_MS: u64 = 400;
+
+    /// Convert the recorded moves into animations for the 3D view
+    ///
+    /// Each move animates for the time until the next move (clamped), so the
+    /// replay keeps the rhythm of the original solve. Each animation is
+    /// captioned with its move. Whole-cube rotations have no layer to animate
+    /// and are skipped.
+    pub fn to_animations(&self) -> Vec<crate::renderer::RotationAnimation> {
+        use crate::renderer::{EasingFunction, RotationAnimation};
+        use std::time::Duration;
+
+        self.moves
+            .iter()
+            .enumerate()
+            .filter_map(|(index, timed)| {
+                let gap = self
+                    .moves
+                    .get(index + 1)
+                    .map_or(Self::MAX_ANIMATION_MS, |next| next.time_ms.saturating_sub(timed.time_ms));
+                let duration = gap.clamp(Self::MIN_ANIMATION_MS, Self::MAX_ANIMATION_MS);
+                RotationAnimation::for_move(&timed.mv, Duration::from_millis(duration), EasingFunction::EaseInOut)
+            })
+            .collect()
+    }
+}
+
+/// Records moves as they are applied
+#[derive(Debug, Clone)]
+pub struct MoveRecorder {
+    replay: Replay,
+    /// Clock reading when recording started, if recording
+    started_ms: Option<f64>,
+}
+
+impl MoveRecorder {
+    /// Create a recorder for a cube of the given size and scramble
+    pub fn new(cube_size: usize, scramble: Vec<ParsedMove>) -> Self {
+        Self {
+            replay: Replay::new(cube_size, scramble),
+            started_ms: None,
+        }
+    }
+
+    /// Start (or restart) the recording clock, discarding recorded moves
+    pub fn start(&mut self) {
+        let now = now_ms();
+        self.replay.moves.clear();
+        self.replay.started_at = now as u64;
+        self.started_ms = Some(now);
+    }
+
+    /// Check whether the recorder is running
+    pub fn is_recording(&self) -> bool {
+        self.started_ms.is_some()
+    }
+
+    /// Record a move at the current time
+    ///
+    /// The first move starts the clock if `start` was not called.
+    pub fn record(&mut self, mv: ParsedMove, source: MoveSource) {
+        let started = match self.started_ms {
+            Some(started) => started,
+            None => {
+                self.start();
+                self.started_ms.unwrap_or_default()
+            }
+        };
+        let elapsed = (now_ms() - started).max(0.0) as u64;
+        self.record_at(mv, source, elapsed);
+    }
+
+    /// Record a move at an explicit time (e.g. from a smart cube's own clock)
+    ///
+    /// Times earlier than the previous move are raised to keep the replay
+    /// in order.
+    pub fn record_at(&mut self, mv: ParsedMove, source: MoveSource, time_ms: u64) {
+        let time_ms = time_ms.max(self.replay.duration_ms());
+        self.replay.moves.push(TimedMove { time_ms, mv, source });
+    }
+
+    /// Milliseconds since the clock started while recording, else the
+    /// time of the last move
+    pub fn elapsed_ms(&self) -> u64 {
+        let last_move = self.replay.duration_ms();
+        match self.started_ms {
+            Some(started) => ((now_ms() - started).max(0.0) as u64).max(last_move),
+            None => last_move,
+        }
+    }
+
+    /// Get the replay recorded so far
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Stop recording and return the replay
+    pub fn stop(&mut self) -> Replay {
+        self.started_ms = None;
+        self.replay.clone()
+    }
+}
+
+/// Current wall-clock time in milliseconds
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn now_ms() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64() * 1000.0)
+        .unwrap_or(0.0)
+}
+
+/// Current wall-clock time in milliseconds
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn now_ms() -> f64 {
+    web_sys::js_sys::Date::now()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::notation::parse_algorithm;
+    use crate::cube::Move;
+
+    fn sample_replay() -> Replay {
+        let mut recorder = MoveRecorder::new(3, parse_algorithm("R U").unwrap());
+        recorder.record_at(ParsedMove::Basic(Move::UPrime), MoveSource::Keyboard, 200);
+        recorder.record_at(ParsedMove::Basic(Move::RPrime), MoveSource::SmartCube, 1000);
+        recorder.stop()
+    }
+
+    #[test]
+    fn test_replay_states() {
+        let replay = sample_replay();
+        assert!(!replay.initial_state().is_solved());
+        assert_eq!(replay.moves_until(0), 0);
+        assert_eq!(replay.moves_until(200), 1);
+        assert_eq!(replay.moves_until(999), 1);
+        assert!(!replay.state_at(500).is_solved());
+        assert!(replay.state_at(1000).is_solved());
+        assert!(replay.final_state().is_solved());
+    }
+
+    #[test]
+    fn test_replay_stats() {
+        let replay = sample_replay();
+        assert_eq!(replay.move_count(), 2);
+        assert_eq!(replay.duration_ms(), 1000);
+        assert!((replay.tps() - 2.0).abs() < f64::EPSILON);
+        assert_eq!(replay.to_notation(), "U' R'");
+        assert_eq!(Replay::new(3, Vec::new()).tps(), 0.0);
+    }
+
+    #[test]
+    fn test_replay_json_roundtrip() {
+        let replay = sample_replay();
+        let restored = Replay::from_json(&replay.to_json().unwrap()).unwrap();
+        assert_eq!(restored, replay);
+        assert!(Replay::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_record_keeps_moves_in_order() {
+        let mut recorder = MoveRecorder::new(3, Vec::new());
+        recorder.record_at(ParsedMove::Basic(Move::R), MoveSource::Ui, 500);
+        recorder.record_at(ParsedMove::Basic(Move::U), MoveSource::Ui, 100);
+        assert_eq!(recorder.replay().moves[1].time_ms, 500);
+    }
+
+    #[test]
+    fn test_record_starts_clock() {
+        let mut recorder = MoveRecorder::new(3, Vec::new());
+        assert!(!recorder.is_recording());
+        recorder.record(ParsedMove::Basic(Move::R), MoveSource::Keyboard);
+        assert!(recorder.is_recording());
+        assert!(recorder.replay().started_at > 0);
+
+        assert!(recorder.elapsed_ms() >= recorder.replay().duration_ms());
+
+        let replay = recorder.stop();
+        assert!(!recorder.is_recording());
+        assert_eq!(replay.move_count(), 1);
+        assert_eq!(recorder.elapsed_ms(), replay.duration_ms());
+    }
+
+    #[cfg(all(feature = "desktop_3d", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_to_animations() {
+        let mut replay = sample_replay();
+        replay.moves.push(TimedMove {
+            time_ms: 1100,
+            mv: ParsedMove::Basic(Move::Y),
+            source: MoveSource::Ui,
+        });
+
+        let animations = replay.to_animations();
+        assert_eq!(animations.len(), 2);
+        assert_eq!(animations[0].angle_degrees, -90.0);
+        assert_eq!(animations[0].duration.as_millis(), 400);
+        assert_eq!(animations[0].caption.as_ref().map(|c| c.notation.as_str()), Some("U'"));
+        assert_eq!(animations[1].duration.as_millis(), 100);
+    }
+}
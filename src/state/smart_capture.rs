@@ -0,0 +1,183 @@
+//! Reconstructing smart cube solves
+//!
+//! A connected smart cube reports every turn, including the ones made while
+//! scrambling it by hand. `SmartCubeCapture` keeps the whole stream and,
+//! when the timer stops, finds where the cube reached the scrambled state:
+//! the moves before that were the scramble (mistakes and all), the moves
+//! after it are the solve. The solve is stored as a [`Reconstruction`] with
+//! its phase splits and TPS.
+
+use super::reconstruction::Reconstruction;
+use super::recorder::{MoveSource, Replay, TimedMove};
+use super::solve_record::SolveRecord;
+use crate::cube::notation::ParsedMove;
+use crate::cube::{Cube, Move};
+
+/// Why a smart cube solve could not be reconstructed
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaptureError {
+    /// The timer was stopped without being started
+    TimerNotStarted,
+    /// The cube never reached the scrambled state before the timer started
+    ScrambleNotFound,
+}
+
+impl std::fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CaptureError::TimerNotStarted => write!(f, "The timer was never started"),
+            CaptureError::ScrambleNotFound => write!(f, "The cube was not scrambled with the given scramble"),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {}
+
+/// Moves reported by a smart cube around one timed solve
+#[derive(Debug, Clone)]
+pub struct SmartCubeCapture {
+    cube_size: usize,
+    scramble: Vec<ParsedMove>,
+    /// Every reported move, timed by the cube's clock
+    stream: Vec<(Move, u64)>,
+    /// Cube clock reading when the timer started
+    timer_started_ms: Option<u64>,
+}
+
+impl SmartCubeCapture {
+    /// Start capturing a solve of `scramble`, from a solved cube
+    pub fn new(cube_size: usize, scramble: Vec<ParsedMove>) -> Self {
+        Self { cube_size, scramble, stream: Vec::new(), timer_started_ms: None }
+    }
+
+    /// Add a move reported by the cube at `device_ms` on its clock
+    pub fn push(&mut self, mv: Move, device_ms: u64) {
+        self.stream.push((mv, device_ms));
+    }
+
+    /// Note that the timer started at `device_ms`
+    pub fn start_timer(&mut self, device_ms: u64) {
+        self.timer_started_ms = Some(device_ms);
+    }
+
+    /// Whether the timer is running
+    pub fn is_timing(&self) -> bool {
+        self.timer_started_ms.is_some()
+    }
+
+    /// The cube the scramble leads to
+    fn scrambled_state(&self) -> Cube {
+        Replay::new(self.cube_size, self.scramble.clone()).initial_state()
+    }
+
+    /// Whether the moves so far leave the cube scrambled as it should be,
+    /// so the timer can be armed
+    pub fn is_scrambled(&self) -> bool {
+        let mut cube = Cube::new(self.cube_size);
+        for (mv, _) in &self.stream {
+            cube.apply_move(*mv);
+        }
+        cube == self.scrambled_state()
+    }
+
+    /// Number of moves that were scrambling: the last point before the
+    /// timer started at which the cube was in the scrambled state
+    fn scramble_end(&self, timer_started_ms: u64) -> Option<usize> {
+        let target = self.scrambled_state();
+        let before_timer = self.stream.iter().take_while(|(_, ms)| *ms < timer_started_ms).count();
+        let mut cube = Cube::new(self.cube_size);
+        let mut end = (cube == target).then_some(0);
+        for (index, (mv, _)) in self.stream[..before_timer].iter().enumerate() {
+            cube.apply_move(*mv);
+            if cube == target {
+                end = Some(index + 1);
+            }
+        }
+        end
+    }
+
+    /// Stop the timer at `device_ms` and reconstruct the solve
+    ///
+    /// The record is timed from the timer start to `device_ms`, finished at
+    /// `timestamp` (Unix seconds), and carries the reconstruction.
+    pub fn finish(&mut self, device_ms: u64, timestamp: u64) -> Result<SolveRecord, CaptureError> {
+        let started = self.timer_started_ms.take().ok_or(CaptureError::TimerNotStarted)?;
+        let first_solve_move = self.scramble_end(started).ok_or(CaptureError::ScrambleNotFound)?;
+
+        let mut replay = Replay::new(self.cube_size, self.scramble.clone());
+        replay.moves = self.stream[first_solve_move..]
+            .iter()
+            .take_while(|(_, ms)| *ms <= device_ms)
+            .map(|(mv, ms)| TimedMove {
+                time_ms: ms.saturating_sub(started),
+                mv: ParsedMove::Basic(*mv),
+                source: MoveSource::SmartCube,
+            })
+            .collect();
+
+        let scramble = self.scramble.iter().map(ParsedMove::to_notation).collect::<Vec<_>>().join(" ");
+        let mut record = SolveRecord::new(device_ms.saturating_sub(started), scramble, self.cube_size, timestamp);
+        record.reconstruction = Some(Reconstruction::new(replay));
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::notation::parse_algorithm;
+    use crate::state::Phase;
+
+    fn capture(scramble: &str) -> SmartCubeCapture {
+        SmartCubeCapture::new(3, parse_algorithm(scramble).unwrap())
+    }
+
+    #[test]
+    fn test_scramble_mistakes_are_not_part_of_the_solve() {
+        let mut capture = capture("R U");
+        // Scrambled with a wrong turn that was undone, then inspected
+        for (i, mv) in [Move::R, Move::F, Move::FPrime, Move::U].iter().enumerate() {
+            capture.push(*mv, 100 * i as u64);
+        }
+        assert!(capture.is_scrambled());
+        capture.start_timer(5_000);
+        capture.push(Move::UPrime, 5_400);
+        capture.push(Move::RPrime, 6_000);
+
+        let record = capture.finish(6_100, 1_700_000_000).unwrap();
+        assert_eq!(record.time_ms, 1_100);
+        assert_eq!(record.scramble, "R U");
+        let reconstruction = record.reconstruction.unwrap();
+        assert_eq!(reconstruction.replay.to_notation(), "U' R'");
+        assert_eq!(reconstruction.replay.moves[1].time_ms, 1_000);
+        assert!(reconstruction.replay.final_state().is_solved());
+        assert_eq!(reconstruction.splits.last().map(|s| s.phase), Some(Phase::Pll));
+        assert!((reconstruction.tps - 2.0).abs() < f64::EPSILON);
+        assert!(!capture.is_timing());
+    }
+
+    #[test]
+    fn test_inspection_turns_that_return_are_skipped() {
+        let mut capture = capture("R");
+        capture.push(Move::R, 0);
+        // Turned and undone during inspection
+        capture.push(Move::U, 1_000);
+        capture.push(Move::UPrime, 1_200);
+        capture.start_timer(2_000);
+        capture.push(Move::RPrime, 2_500);
+
+        let record = capture.finish(2_600, 0).unwrap();
+        assert_eq!(record.reconstruction.unwrap().replay.to_notation(), "R'");
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut capture = capture("R U");
+        assert_eq!(capture.finish(1_000, 0), Err(CaptureError::TimerNotStarted));
+
+        capture.push(Move::U, 0);
+        assert!(!capture.is_scrambled());
+        capture.start_timer(500);
+        assert_eq!(capture.finish(1_000, 0), Err(CaptureError::ScrambleNotFound));
+    }
+}
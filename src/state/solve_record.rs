@@ -0,0 +1,236 @@
+//! Solve history records
+//!
+//! A `SolveRecord` is one timed solve: the scramble that was used, the time,
+//! any penalty, when it was done, a comment and the session it belongs to.
+//! Records are created by the app and by importers in the `interop` module.
+//!
+//! Penalties follow the WCA rules: a solve can be marked +2 or DNF by hand,
+//! and starting after more than 15 seconds of inspection costs +2 (over 17
+//! seconds, a DNF).
+
+use super::reconstruction::Reconstruction;
+use crate::cube::notation::{parse_algorithm, NotationError, ParsedMove};
+use serde::{Deserialize, Serialize};
+
+/// Inspection allowed before a solve starts, in milliseconds
+pub const INSPECTION_MS: u64 = 15_000;
+
+/// Starting a solve after this much inspection is a DNF
+pub const INSPECTION_DNF_MS: u64 = 17_000;
+
+/// Penalty applied to a timed solve, from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub enum Penalty {
+    /// No penalty
+    #[default]
+    None,
+    /// Two seconds added to the time
+    PlusTwo,
+    /// Did not finish
+    Dnf,
+}
+
+impl Penalty {
+    /// Short label shown next to a time: "", "+2" or "DNF"
+    pub fn label(&self) -> &'static str {
+        match self {
+            Penalty::None => "",
+            Penalty::PlusTwo => "+2",
+            Penalty::Dnf => "DNF",
+        }
+    }
+}
+
+/// Format milliseconds as seconds with two decimals (e.g. "12.34")
+pub fn format_time(ms: u64) -> String {
+    format!("{}.{:02}", ms / 1000, (ms % 1000) / 10)
+}
+
+/// Penalty for starting a solve after `inspection_ms` of inspection
+pub fn inspection_penalty(inspection_ms: u64) -> Penalty {
+    if inspection_ms > INSPECTION_DNF_MS {
+        Penalty::Dnf
+    } else if inspection_ms > INSPECTION_MS {
+        Penalty::PlusTwo
+    } else {
+        Penalty::None
+    }
+}
+
+/// A single timed solve
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolveRecord {
+    /// Raw solve time in milliseconds (before penalties)
+    pub time_ms: u64,
+    /// Penalty applied to this solve
+    pub penalty: Penalty,
+    /// Scramble in standard notation
+    pub scramble: String,
+    /// Cube size the solve was done on
+    pub cube_size: usize,
+    /// When the solve finished (Unix timestamp in seconds)
+    pub timestamp: u64,
+    /// Optional user comment
+    pub comment: Option<String>,
+    /// Session the solve was done in, if any
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Move-by-move reconstruction, for solves done on a smart cube
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reconstruction: Option<Reconstruction>,
+}
+
+impl SolveRecord {
+    /// Create a new solve record without penalty or comment
+    pub fn new(time_ms: u64, scramble: impl Into<String>, cube_size: usize, timestamp: u64) -> Self {
+        Self {
+            time_ms,
+            penalty: Penalty::None,
+            scramble: scramble.into(),
+            cube_size,
+            timestamp,
+            comment: None,
+            session_id: None,
+            reconstruction: None,
+        }
+    }
+
+    /// Put the solve in a session
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    /// Mark the solve with `penalty`, or clear it if it already has it
+    ///
+    /// This is how the +2 and DNF buttons behave: pressing one twice undoes it.
+    pub fn toggle_penalty(&mut self, penalty: Penalty) {
+        self.penalty = if self.penalty == penalty { Penalty::None } else { penalty };
+    }
+
+    /// Set the comment; blank text removes it
+    pub fn set_comment(&mut self, comment: &str) {
+        let comment = comment.trim();
+        self.comment = (!comment.is_empty()).then(|| comment.to_string());
+    }
+
+    /// Date the solve finished, as YYYY-MM-DD (UTC)
+    pub fn date(&self) -> String {
+        let (year, month, day) = civil_from_days(self.timestamp / 86_400);
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+
+    /// Date and time the solve finished, as YYYY-MM-DD HH:MM:SS (UTC)
+    pub fn date_time(&self) -> String {
+        let seconds = self.timestamp % 86_400;
+        format!("{} {:02}:{:02}:{:02}", self.date(), seconds / 3600, seconds / 60 % 60, seconds % 60)
+    }
+
+    /// The result as timers show it, e.g. "12.34", "14.34+" or "DNF(12.34)"
+    pub fn display_time(&self) -> String {
+        match self.penalty {
+            Penalty::None => format_time(self.time_ms),
+            Penalty::PlusTwo => format!("{}+", format_time(self.time_ms + 2000)),
+            Penalty::Dnf => format!("DNF({})", format_time(self.time_ms)),
+        }
+    }
+
+    /// Get the time with penalties applied, or `None` for a DNF
+    pub fn effective_time_ms(&self) -> Option<u64> {
+        match self.penalty {
+            Penalty::None => Some(self.time_ms),
+            Penalty::PlusTwo => Some(self.time_ms + 2000),
+            Penalty::Dnf => None,
+        }
+    }
+
+    /// Parse the scramble into moves
+    pub fn scramble_moves(&self) -> Result<Vec<ParsedMove>, NotationError> {
+        parse_algorithm(&self.scramble)
+    }
+}
+
+/// Calendar date of a day number counted from 1970-01-01
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(0), "0.00");
+        assert_eq!(format_time(9_876), "9.87");
+        assert_eq!(format_time(61_009), "61.00");
+    }
+
+    #[test]
+    fn test_effective_time() {
+        let mut record = SolveRecord::new(12_340, "R U R' U'", 3, 0);
+        assert_eq!(record.effective_time_ms(), Some(12_340));
+
+        record.penalty = Penalty::PlusTwo;
+        assert_eq!(record.effective_time_ms(), Some(14_340));
+
+        record.penalty = Penalty::Dnf;
+        assert_eq!(record.effective_time_ms(), None);
+    }
+
+    #[test]
+    fn test_inspection_penalty() {
+        assert_eq!(inspection_penalty(8_000), Penalty::None);
+        assert_eq!(inspection_penalty(INSPECTION_MS), Penalty::None);
+        assert_eq!(inspection_penalty(16_500), Penalty::PlusTwo);
+        assert_eq!(inspection_penalty(INSPECTION_DNF_MS + 1), Penalty::Dnf);
+        assert_eq!(Penalty::PlusTwo.max(Penalty::Dnf), Penalty::Dnf);
+    }
+
+    #[test]
+    fn test_marking_penalties_and_comments() {
+        let mut record = SolveRecord::new(9_000, "R U", 3, 0).with_session("session-1");
+        record.toggle_penalty(Penalty::PlusTwo);
+        assert_eq!(record.penalty, Penalty::PlusTwo);
+        record.toggle_penalty(Penalty::Dnf);
+        assert_eq!(record.penalty, Penalty::Dnf);
+        record.toggle_penalty(Penalty::Dnf);
+        assert_eq!(record.penalty, Penalty::None);
+
+        record.set_comment("  pop on the last layer ");
+        assert_eq!(record.comment.as_deref(), Some("pop on the last layer"));
+        record.set_comment(" ");
+        assert_eq!(record.comment, None);
+        assert_eq!(record.session_id.as_deref(), Some("session-1"));
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(SolveRecord::new(0, "", 3, 0).date(), "1970-01-01");
+        assert_eq!(SolveRecord::new(0, "", 3, 1_700_000_000).date(), "2023-11-14");
+        assert_eq!(SolveRecord::new(0, "", 3, 951_782_400).date(), "2000-02-29");
+        assert_eq!(SolveRecord::new(0, "", 3, 1_700_000_000).date_time(), "2023-11-14 22:13:20");
+    }
+
+    #[test]
+    fn test_records_without_a_session_load() {
+        let json = r#"{"time_ms":1000,"penalty":"None","scramble":"R","cube_size":3,"timestamp":0,"comment":null}"#;
+        let record: SolveRecord = serde_json::from_str(json).unwrap();
+        assert_eq!(record.session_id, None);
+    }
+
+    #[test]
+    fn test_scramble_moves() {
+        let record = SolveRecord::new(1000, "R U2 F'", 3, 0);
+        assert_eq!(record.scramble_moves().unwrap().len(), 3);
+    }
+}
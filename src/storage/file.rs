@@ -0,0 +1,124 @@
+//! Storage in a folder on disk, one `<key>.json` file per key
+
+use super::{check_key, StorageBackend, StorageError};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the app's folder inside the platform's data directory
+const APP_FOLDER: &str = "cube-solver";
+
+/// Backend that saves each key as a JSON file in a folder
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    dir: PathBuf,
+}
+
+impl FileStorage {
+    /// Store files in `dir`, creating it if needed
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, StorageError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(|e| StorageError::Io(format!("{}: {}", dir.display(), e)))?;
+        Ok(Self { dir })
+    }
+
+    /// Store files in the app's folder in the user's data directory
+    /// (`%APPDATA%` on Windows, `~/Library/Application Support` on macOS,
+    /// `$XDG_DATA_HOME` or `~/.local/share` elsewhere)
+    pub fn in_data_dir() -> Result<Self, StorageError> {
+        let base = data_dir().ok_or_else(|| StorageError::Unavailable("No data directory".to_string()))?;
+        Self::new(base.join(APP_FOLDER))
+    }
+
+    /// Folder the files are stored in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+        check_key(key)?;
+        Ok(self.dir.join(format!("{}.json", key)))
+    }
+}
+
+fn data_dir() -> Option<PathBuf> {
+    let var = |name| std::env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+    if cfg!(windows) {
+        var("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        var("XDG_DATA_HOME").or_else(|| var("HOME").map(|home| home.join(".local/share")))
+    }
+}
+
+fn io_error(path: &Path, e: io::Error) -> StorageError {
+    StorageError::Io(format!("{}: {}", path.display(), e))
+}
+
+impl StorageBackend for FileStorage {
+    fn name(&self) -> &str {
+        "Files"
+    }
+
+    fn read(&self, key: &str) -> Result<Option<String>, StorageError> {
+        let path = self.path(key)?;
+        match fs::read_to_string(&path) {
+            Ok(text) => Ok(Some(text)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error(&path, e)),
+        }
+    }
+
+    fn write(&mut self, key: &str, value: &str) -> Result<(), StorageError> {
+        let path = self.path(key)?;
+        // Write beside the file and rename over it, so a crash mid-write
+        // leaves the old value rather than half of the new one
+        let partial = path.with_extension("json.partial");
+        fs::write(&partial, value).map_err(|e| io_error(&partial, e))?;
+        fs::rename(&partial, &path).map_err(|e| io_error(&path, e))
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), StorageError> {
+        let path = self.path(key)?;
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(io_error(&path, e)),
+            _ => Ok(()),
+        }
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        let entries = fs::read_dir(&self.dir).map_err(|e| io_error(&self.dir, e))?;
+        let mut keys: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().to_str()?.strip_suffix(".json").map(str::to_string))
+            .filter(|key| super::is_valid_key(key))
+            .collect();
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_files_survive_a_new_backend() {
+        let dir = std::env::temp_dir().join(format!("cube-solver-storage-{}", std::process::id()));
+        let mut storage = FileStorage::new(&dir).unwrap();
+        assert_eq!(storage.read("settings"), Ok(None));
+        storage.write("settings", "{\"muted\":true}").unwrap();
+        storage.write("progress", "{}").unwrap();
+
+        let mut reopened = FileStorage::new(&dir).unwrap();
+        assert_eq!(reopened.read("settings"), Ok(Some("{\"muted\":true}".to_string())));
+        assert_eq!(reopened.keys().unwrap(), vec!["progress", "settings"]);
+        reopened.remove("progress").unwrap();
+        reopened.remove("progress").unwrap();
+        assert_eq!(reopened.keys().unwrap(), vec!["settings"]);
+        assert!(matches!(reopened.write("../escape", ""), Err(StorageError::InvalidKey(_))));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
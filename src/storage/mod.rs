@@ -0,0 +1,256 @@
+//! Persistence shared by native and web builds
+//!
+//! Everything the app keeps between runs is stored as JSON text under a
+//! key, through a pluggable backend:
+//! - Native platforms: [`FileStorage`], one file per key in a folder
+//! - Web (WASM): [`LocalStorage`], the browser's localStorage
+//! - Tests: [`MemoryStorage`], which keeps everything in a map
+//!
+//! Saved types implement [`Persistent`], which gives each one its key, so
+//! settings, the user's profile, lesson progress, the camera calibration
+//! and solve history are all saved and loaded the same way on every
+//! backend.
+
+#[cfg(not(target_arch = "wasm32"))]
+mod file;
+mod settings;
+#[cfg(target_arch = "wasm32")]
+mod web;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use file::FileStorage;
+pub use settings::Settings;
+#[cfg(target_arch = "wasm32")]
+pub use web::LocalStorage;
+
+use crate::camera::CalibrationProfile;
+use crate::state::{Progress, UserProfile};
+use crate::timer::SessionManager;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Error types for saving and loading
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageError {
+    /// No storage is available on this platform
+    Unavailable(String),
+    /// The key can't be used as a storage name
+    InvalidKey(String),
+    /// Reading or writing failed
+    Io(String),
+    /// The saved text is not what the key should hold
+    Format { key: String, message: String },
+}
+
+impl std::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageError::Unavailable(msg) => write!(f, "Storage unavailable: {}", msg),
+            StorageError::InvalidKey(key) => write!(f, "Invalid storage key: {:?}", key),
+            StorageError::Io(msg) => write!(f, "Storage failed: {}", msg),
+            StorageError::Format { key, message } => write!(f, "Saved {} is unreadable: {}", key, message),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+/// Whether `key` is usable on every backend: lowercase letters, digits,
+/// `_` and `-`, so it is also a safe file name
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_' || b == b'-')
+}
+
+fn check_key(key: &str) -> Result<(), StorageError> {
+    if is_valid_key(key) {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidKey(key.to_string()))
+    }
+}
+
+/// A place text can be saved under a key and read back on a later run
+pub trait StorageBackend {
+    /// Human-readable backend name (for settings and diagnostics)
+    fn name(&self) -> &str;
+
+    /// The text saved under `key`, or `None` if nothing is
+    fn read(&self, key: &str) -> Result<Option<String>, StorageError>;
+
+    /// Save `value` under `key`, replacing what was there
+    fn write(&mut self, key: &str, value: &str) -> Result<(), StorageError>;
+
+    /// Forget `key`; forgetting a key that isn't saved is not an error
+    fn remove(&mut self, key: &str) -> Result<(), StorageError>;
+
+    /// Every saved key, sorted
+    fn keys(&self) -> Result<Vec<String>, StorageError>;
+}
+
+/// Backend that keeps everything in memory (used in tests, and as the
+/// fallback when a platform has no storage)
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage {
+    entries: BTreeMap<String, String>,
+}
+
+impl MemoryStorage {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StorageBackend for MemoryStorage {
+    fn name(&self) -> &str {
+        "Memory"
+    }
+
+    fn read(&self, key: &str) -> Result<Option<String>, StorageError> {
+        check_key(key)?;
+        Ok(self.entries.get(key).cloned())
+    }
+
+    fn write(&mut self, key: &str, value: &str) -> Result<(), StorageError> {
+        check_key(key)?;
+        self.entries.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), StorageError> {
+        check_key(key)?;
+        self.entries.remove(key);
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        Ok(self.entries.keys().cloned().collect())
+    }
+}
+
+/// Get the default storage backend for this platform
+///
+/// Falls back to [`MemoryStorage`] (nothing survives a restart) when the
+/// platform's storage can't be opened.
+pub fn default_backend() -> Box<dyn StorageBackend> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let backend = FileStorage::in_data_dir();
+    #[cfg(target_arch = "wasm32")]
+    let backend = LocalStorage::new();
+
+    match backend {
+        Ok(backend) => Box::new(backend),
+        Err(_) => Box::new(MemoryStorage::new()),
+    }
+}
+
+/// A type that is saved between runs under its own key
+pub trait Persistent: Serialize + DeserializeOwned {
+    /// Key the value is saved under
+    const KEY: &'static str;
+
+    /// Save the value, replacing the one saved before
+    fn save(&self, storage: &mut dyn StorageBackend) -> Result<(), StorageError> {
+        let json = serde_json::to_string(self)
+            .map_err(|e| StorageError::Format { key: Self::KEY.to_string(), message: e.to_string() })?;
+        storage.write(Self::KEY, &json)
+    }
+
+    /// Load the saved value, or `None` if nothing has been saved
+    fn load(storage: &dyn StorageBackend) -> Result<Option<Self>, StorageError> {
+        match storage.read(Self::KEY)? {
+            Some(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| StorageError::Format { key: Self::KEY.to_string(), message: e.to_string() }),
+            None => Ok(None),
+        }
+    }
+
+    /// Load the saved value, starting fresh if there is none or it can't
+    /// be read
+    fn load_or_default(storage: &dyn StorageBackend) -> Self
+    where
+        Self: Default,
+    {
+        Self::load(storage).ok().flatten().unwrap_or_default()
+    }
+
+    /// Delete the saved value
+    fn forget(storage: &mut dyn StorageBackend) -> Result<(), StorageError> {
+        storage.remove(Self::KEY)
+    }
+}
+
+impl Persistent for Settings {
+    const KEY: &'static str = "settings";
+}
+
+impl Persistent for UserProfile {
+    const KEY: &'static str = "profile";
+}
+
+impl Persistent for Progress {
+    const KEY: &'static str = "progress";
+}
+
+impl Persistent for CalibrationProfile {
+    const KEY: &'static str = "calibration";
+}
+
+impl Persistent for SessionManager {
+    const KEY: &'static str = "solve_history";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_storage_round_trip() {
+        let mut storage = MemoryStorage::new();
+        assert_eq!(storage.read("progress"), Ok(None));
+        storage.write("progress", "{}").unwrap();
+        storage.write("calibration", "[]").unwrap();
+        assert_eq!(storage.read("progress"), Ok(Some("{}".to_string())));
+        assert_eq!(storage.keys().unwrap(), vec!["calibration", "progress"]);
+        storage.remove("progress").unwrap();
+        storage.remove("progress").unwrap();
+        assert_eq!(storage.keys().unwrap(), vec!["calibration"]);
+    }
+
+    #[test]
+    fn test_keys_must_be_safe_names() {
+        let mut storage = MemoryStorage::new();
+        for key in ["", "../settings", "Settings", "a b", "solve.history"] {
+            assert_eq!(storage.write(key, "x"), Err(StorageError::InvalidKey(key.to_string())));
+        }
+        assert!(is_valid_key("solve_history"));
+    }
+
+    #[test]
+    fn test_persistent_values_use_their_keys() {
+        let mut storage = MemoryStorage::new();
+        let mut progress = Progress::new();
+        progress.toggle_favorite("oll-21");
+        progress.save(&mut storage).unwrap();
+        Settings::default().save(&mut storage).unwrap();
+        SessionManager::new().save(&mut storage).unwrap();
+        assert_eq!(storage.keys().unwrap(), vec!["progress", "settings", "solve_history"]);
+
+        let loaded = Progress::load(&storage).unwrap().unwrap();
+        assert_eq!(loaded.favorite_algorithms(), progress.favorite_algorithms());
+        assert!(CalibrationProfile::load(&storage).unwrap().is_none());
+
+        Progress::forget(&mut storage).unwrap();
+        assert!(Progress::load(&storage).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_unreadable_value_is_reported_and_defaults() {
+        let mut storage = MemoryStorage::new();
+        storage.write("settings", "not json").unwrap();
+        assert!(matches!(Settings::load(&storage), Err(StorageError::Format { .. })));
+        assert_eq!(Settings::load_or_default(&storage), Settings::default());
+    }
+}
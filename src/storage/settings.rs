@@ -0,0 +1,50 @@
+//! App settings that are kept between runs
+
+use crate::components::ui_kit::Theme;
+use crate::input::GestureSettings;
+use crate::state::HistoryLimits;
+use serde::{Deserialize, Serialize};
+
+/// The user's choices from the app's header and history panel, and the
+/// touch gesture setup
+///
+/// Fields missing from older saves keep their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Color theme
+    pub theme: Theme,
+    /// Whether sound effects are muted
+    pub muted: bool,
+    /// Whether touch devices vibrate
+    pub haptics: bool,
+    /// How much undo history to keep
+    pub history_limits: HistoryLimits,
+    /// Touch gesture thresholds and what each gesture does
+    pub gestures: GestureSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            muted: false,
+            haptics: true,
+            history_limits: HistoryLimits::default(),
+            gestures: GestureSettings::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_older_saves_keep_defaults() {
+        let settings: Settings = serde_json::from_str(r#"{"theme":"Dark"}"#).unwrap();
+        assert_eq!(settings.theme, Theme::Dark);
+        assert!(settings.haptics);
+        assert_eq!(settings.history_limits, HistoryLimits::default());
+    }
+}
@@ -0,0 +1,65 @@
+//! Storage in the browser's localStorage
+//!
+//! localStorage is synchronous and holds a few megabytes per site, which is
+//! plenty for settings, progress and solve history; IndexedDB would only be
+//! worth its asynchronous API for much larger data.
+
+use super::{check_key, StorageBackend, StorageError};
+
+/// Prefix that keeps the app's keys apart from anything else on the site
+const PREFIX: &str = "cube-solver.";
+
+/// Backend that saves each key in the browser's localStorage
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    storage: web_sys::Storage,
+}
+
+impl LocalStorage {
+    /// Open the page's localStorage; fails when the browser blocks it
+    /// (e.g. some private browsing modes)
+    pub fn new() -> Result<Self, StorageError> {
+        let window = web_sys::window().ok_or_else(|| StorageError::Unavailable("No window".to_string()))?;
+        let storage = window
+            .local_storage()
+            .ok()
+            .flatten()
+            .ok_or_else(|| StorageError::Unavailable("localStorage is blocked".to_string()))?;
+        Ok(Self { storage })
+    }
+}
+
+fn js_error(e: wasm_bindgen::JsValue) -> StorageError {
+    StorageError::Io(format!("{:?}", e))
+}
+
+impl StorageBackend for LocalStorage {
+    fn name(&self) -> &str {
+        "localStorage"
+    }
+
+    fn read(&self, key: &str) -> Result<Option<String>, StorageError> {
+        check_key(key)?;
+        self.storage.get_item(&format!("{}{}", PREFIX, key)).map_err(js_error)
+    }
+
+    fn write(&mut self, key: &str, value: &str) -> Result<(), StorageError> {
+        check_key(key)?;
+        self.storage.set_item(&format!("{}{}", PREFIX, key), value).map_err(js_error)
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), StorageError> {
+        check_key(key)?;
+        self.storage.remove_item(&format!("{}{}", PREFIX, key)).map_err(js_error)
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        let count = self.storage.length().map_err(js_error)?;
+        let mut keys: Vec<String> = (0..count)
+            .filter_map(|index| self.storage.key(index).ok().flatten())
+            .filter_map(|key| key.strip_prefix(PREFIX).map(str::to_string))
+            .collect();
+        keys.sort();
+        Ok(keys)
+    }
+}
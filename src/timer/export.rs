@@ -0,0 +1,97 @@
+//! Exporting a session's solves
+//!
+//! CSV uses the columns of csTimer's own CSV export (`No.;Time;Comment;
+//! Scramble;Date;P.1`, separated by semicolons) so it opens the same way in
+//! spreadsheets. JSON is csTimer's session file, which csTimer imports.
+
+use super::session::TimingSession;
+use crate::interop::{export_cstimer, CsTimerSession, InteropError};
+use crate::state::format_time;
+
+/// Column headings of the CSV export
+const CSV_HEADER: &str = "No.;Time;Comment;Scramble;Date;P.1";
+
+/// A session's solves as CSV, one row per solve
+///
+/// Time includes the penalty ("14.34+", "DNF(12.34)"); P.1 is the raw time.
+pub fn to_csv(session: &TimingSession) -> String {
+    let mut lines = vec![CSV_HEADER.to_string()];
+    for (index, record) in session.records.iter().enumerate() {
+        let fields = [
+            (index + 1).to_string(),
+            record.display_time(),
+            record.comment.clone().unwrap_or_default(),
+            record.scramble.clone(),
+            record.date_time(),
+            format_time(record.time_ms),
+        ];
+        lines.push(fields.iter().map(|f| csv_field(f)).collect::<Vec<_>>().join(";"));
+    }
+    lines.join("\n")
+}
+
+/// Sessions as a csTimer JSON file
+///
+/// Fails if a session's cube size has no csTimer scramble type.
+pub fn to_cstimer_json(sessions: &[TimingSession]) -> Result<String, InteropError> {
+    let sessions: Vec<CsTimerSession> = sessions
+        .iter()
+        .enumerate()
+        .map(|(index, session)| CsTimerSession {
+            number: index as u32 + 1,
+            name: session.name.clone(),
+            cube_size: session.cube_size,
+            records: session.records.clone(),
+        })
+        .collect();
+    export_cstimer(&sessions)
+}
+
+/// Quote a field if it has a separator, quote or line break in it
+fn csv_field(field: &str) -> String {
+    if field.contains([';', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interop::import_cstimer;
+    use crate::state::{Penalty, SolveRecord};
+
+    fn session() -> TimingSession {
+        let mut session = TimingSession::new("session-1", 3, Some("CFOP"));
+        session.add(SolveRecord::new(12_340, "R U R' U'", 3, 1_700_000_000));
+        let mut plus_two = SolveRecord::new(9_990, "F2 D", 3, 1_700_000_100);
+        plus_two.penalty = Penalty::PlusTwo;
+        plus_two.set_comment("over inspection; \"close\"");
+        session.add(plus_two);
+        session
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = to_csv(&session());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines[1], "1;12.34;;R U R' U';2023-11-14 22:13:20;12.34");
+        assert_eq!(lines[2], "2;11.99+;\"over inspection; \"\"close\"\"\";F2 D;2023-11-14 22:15:00;9.99");
+    }
+
+    #[test]
+    fn test_cstimer_json_imports() {
+        let session = session();
+        let import = import_cstimer(&to_cstimer_json(std::slice::from_ref(&session)).unwrap()).unwrap();
+        assert_eq!(import.sessions[0].name, "3x3 CFOP");
+        assert_eq!(import.sessions[0].cube_size, 3);
+
+        let records = &import.sessions[0].records;
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].penalty, Penalty::PlusTwo);
+        assert_eq!(records[1].comment, session.records[1].comment);
+        assert_eq!(records[0].scramble, "R U R' U'");
+    }
+}
@@ -0,0 +1,16 @@
+//! Timing sessions
+//!
+//! Solves are grouped into named sessions, usually one per cube size and
+//! method ("3x3 CFOP", "4x4 Yau"). The [`SessionManager`] keeps every
+//! session and which one new solves go into, and a session's solves can be
+//! exported as CSV for spreadsheets or as a csTimer JSON file that csTimer
+//! (and this app) can import. [`SessionStats`] sums up a session with its
+//! best times and WCA averages.
+
+pub mod export;
+pub mod session;
+pub mod stats;
+
+pub use export::{to_cstimer_json, to_csv};
+pub use session::{SessionManager, TimingSession};
+pub use stats::{Histogram, SessionStats};
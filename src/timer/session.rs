@@ -0,0 +1,272 @@
+//! Named timing sessions and switching between them
+
+use crate::state::SolveRecord;
+use serde::{Deserialize, Serialize};
+
+/// A named group of solves on one cube size
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimingSession {
+    /// Stable identifier, stored in each record's `session_id`
+    pub id: String,
+    /// Name shown to the user, e.g. "3x3 CFOP"
+    pub name: String,
+    /// Cube size the session is for
+    pub cube_size: usize,
+    /// Solving method, if the user named one
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Solves, oldest first
+    #[serde(default)]
+    pub records: Vec<SolveRecord>,
+}
+
+impl TimingSession {
+    /// Create an empty session named after its size and method
+    pub fn new(id: impl Into<String>, cube_size: usize, method: Option<&str>) -> Self {
+        Self {
+            id: id.into(),
+            name: Self::default_name(cube_size, method),
+            cube_size,
+            method: method.map(str::to_string),
+            records: Vec::new(),
+        }
+    }
+
+    /// Name for a session on `cube_size` with `method`, e.g. "4x4 Yau"
+    pub fn default_name(cube_size: usize, method: Option<&str>) -> String {
+        match method {
+            Some(method) => format!("{n}x{n} {}", method, n = cube_size),
+            None => format!("{n}x{n}", n = cube_size),
+        }
+    }
+
+    /// Add a solve to the session
+    pub fn add(&mut self, mut record: SolveRecord) {
+        record.session_id = Some(self.id.clone());
+        self.records.push(record);
+    }
+
+    /// Replace a solve with an edited copy (after marking a penalty or
+    /// commenting on it); returns false if the solve isn't in the session
+    ///
+    /// The solve is found by when it finished and its scramble.
+    pub fn update(&mut self, record: SolveRecord) -> bool {
+        let found = self
+            .records
+            .iter_mut()
+            .rev()
+            .find(|r| r.timestamp == record.timestamp && r.scramble == record.scramble);
+        match found {
+            Some(existing) => {
+                *existing = SolveRecord { session_id: Some(self.id.clone()), ..record };
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Every timing session, and the one new solves go into
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionManager {
+    sessions: Vec<TimingSession>,
+    /// Id of the current session
+    current: String,
+    /// Number used for the next session id
+    next_id: u32,
+}
+
+impl Default for SessionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionManager {
+    /// Start with a single 3x3 session
+    pub fn new() -> Self {
+        let first = TimingSession::new("session-1", 3, None);
+        Self { current: first.id.clone(), sessions: vec![first], next_id: 2 }
+    }
+
+    /// All sessions, in the order they were created
+    pub fn sessions(&self) -> &[TimingSession] {
+        &self.sessions
+    }
+
+    /// The session new solves go into
+    pub fn current(&self) -> &TimingSession {
+        self.sessions.iter().find(|s| s.id == self.current).unwrap_or(&self.sessions[0])
+    }
+
+    /// Mutable access to the current session
+    pub fn current_mut(&mut self) -> &mut TimingSession {
+        let index = self.sessions.iter().position(|s| s.id == self.current).unwrap_or(0);
+        &mut self.sessions[index]
+    }
+
+    /// Get a session by id
+    pub fn get(&self, id: &str) -> Option<&TimingSession> {
+        self.sessions.iter().find(|s| s.id == id)
+    }
+
+    /// Create a session and switch to it; returns its id
+    pub fn create(&mut self, cube_size: usize, method: Option<&str>) -> String {
+        let id = format!("session-{}", self.next_id);
+        self.next_id += 1;
+        self.sessions.push(TimingSession::new(id.clone(), cube_size, method));
+        self.current = id.clone();
+        id
+    }
+
+    /// Switch to the first session for `cube_size` and `method`, creating
+    /// one if there is none; returns its id
+    pub fn switch_to_size(&mut self, cube_size: usize, method: Option<&str>) -> String {
+        let existing = self
+            .sessions
+            .iter()
+            .find(|s| s.cube_size == cube_size && s.method.as_deref() == method);
+        match existing {
+            Some(session) => {
+                self.current = session.id.clone();
+                self.current.clone()
+            }
+            None => self.create(cube_size, method),
+        }
+    }
+
+    /// Make `id` the current session; returns false if there is no such
+    /// session
+    pub fn switch_to(&mut self, id: &str) -> bool {
+        let exists = self.get(id).is_some();
+        if exists {
+            self.current = id.to_string();
+        }
+        exists
+    }
+
+    /// Rename a session; returns false if there is no such session
+    pub fn rename(&mut self, id: &str, name: &str) -> bool {
+        match self.sessions.iter_mut().find(|s| s.id == id) {
+            Some(session) => {
+                session.name = name.trim().to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Delete a session and its solves
+    ///
+    /// The last session can't be deleted. Deleting the current session
+    /// switches to the first one left.
+    pub fn remove(&mut self, id: &str) -> Option<TimingSession> {
+        if self.sessions.len() == 1 {
+            return None;
+        }
+        let index = self.sessions.iter().position(|s| s.id == id)?;
+        let removed = self.sessions.remove(index);
+        if self.current == id {
+            self.current = self.sessions[0].id.clone();
+        }
+        Some(removed)
+    }
+
+    /// Add a solve to the current session
+    pub fn record(&mut self, record: SolveRecord) {
+        self.current_mut().add(record);
+    }
+
+    /// Replace an edited solve in the session it belongs to; returns false
+    /// if it isn't in any session
+    pub fn update(&mut self, record: SolveRecord) -> bool {
+        let id = record.session_id.clone().unwrap_or_else(|| self.current.clone());
+        match self.sessions.iter_mut().find(|s| s.id == id) {
+            Some(session) => session.update(record),
+            None => false,
+        }
+    }
+
+    /// Serialize to a JSON string
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Deserialize from a JSON string
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Penalty;
+
+    #[test]
+    fn test_sessions_by_size_and_method() {
+        let mut manager = SessionManager::new();
+        assert_eq!(manager.current().name, "3x3");
+
+        let yau = manager.switch_to_size(4, Some("Yau"));
+        assert_eq!(manager.current().name, "4x4 Yau");
+        manager.record(SolveRecord::new(60_000, "Rw U", 4, 10));
+        assert_eq!(manager.current().records[0].session_id.as_deref(), Some(yau.as_str()));
+
+        // Switching back finds the existing sessions instead of making new ones
+        manager.switch_to_size(3, None);
+        assert_eq!(manager.current().id, "session-1");
+        assert_eq!(manager.switch_to_size(4, Some("Yau")), yau);
+        assert_eq!(manager.sessions().len(), 2);
+
+        assert!(manager.switch_to("session-1"));
+        assert!(!manager.switch_to("nope"));
+        assert_eq!(manager.current().id, "session-1");
+    }
+
+    #[test]
+    fn test_rename_and_remove() {
+        let mut manager = SessionManager::new();
+        assert!(manager.remove("session-1").is_none());
+
+        let oh = manager.create(3, Some("OH"));
+        assert!(manager.rename(&oh, "One-handed"));
+        assert_eq!(manager.current().name, "One-handed");
+
+        assert_eq!(manager.remove(&oh).map(|s| s.name), Some("One-handed".to_string()));
+        assert_eq!(manager.current().id, "session-1");
+        assert!(!manager.rename(&oh, "Gone"));
+
+        // Ids aren't reused
+        assert_eq!(manager.create(2, None), "session-3");
+    }
+
+    #[test]
+    fn test_update_edited_solve() {
+        let mut manager = SessionManager::new();
+        manager.record(SolveRecord::new(9_000, "R U", 3, 100));
+        manager.record(SolveRecord::new(8_000, "F D", 3, 200));
+        manager.create(2, None);
+
+        let mut edited = manager.get("session-1").unwrap().records[0].clone();
+        edited.toggle_penalty(Penalty::Dnf);
+        edited.set_comment("pop");
+        assert!(manager.update(edited));
+
+        let records = &manager.get("session-1").unwrap().records;
+        assert_eq!(records[0].penalty, Penalty::Dnf);
+        assert_eq!(records[0].comment.as_deref(), Some("pop"));
+        assert_eq!(records[1].penalty, Penalty::None);
+
+        assert!(!manager.update(SolveRecord::new(1, "B", 3, 5)));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut manager = SessionManager::new();
+        manager.record(SolveRecord::new(9_000, "R U", 3, 100));
+        manager.create(5, Some("Redux"));
+        let loaded = SessionManager::from_json(&manager.to_json().unwrap()).unwrap();
+        assert_eq!(loaded, manager);
+    }
+}
@@ -0,0 +1,213 @@
+//! Session statistics: averages, trends and the spread of solve times
+//!
+//! Averages follow the WCA rules: the best and worst 5% of the solves
+//! (rounded up, so one each for an ao5 or ao12) are dropped and the rest are
+//! averaged. DNFs count as the worst results, so an average with more DNFs
+//! than it drops is a DNF.
+
+use crate::state::SolveRecord;
+
+/// Bucket widths a histogram may use, in milliseconds
+const BUCKET_WIDTHS: [u64; 12] = [100, 200, 500, 1_000, 2_000, 5_000, 10_000, 15_000, 30_000, 60_000, 120_000, 300_000];
+
+/// Solves dropped from each end of an average of `n`
+pub fn trimmed(n: usize) -> usize {
+    n.div_ceil(20)
+}
+
+/// WCA average of `records`, or None if it is a DNF (or there are no
+/// solves left after trimming)
+pub fn average(records: &[SolveRecord]) -> Option<u64> {
+    let trim = trimmed(records.len());
+    let mut times: Vec<u64> = records.iter().filter_map(SolveRecord::effective_time_ms).collect();
+    if records.len() - times.len() > trim {
+        return None;
+    }
+    times.sort_unstable();
+    // DNFs are the worst results, so they are dropped first
+    let worst = trim - (records.len() - times.len());
+    if times.len() <= trim + worst {
+        return None;
+    }
+    let counted = &times[trim..times.len() - worst];
+    Some(counted.iter().sum::<u64>() / counted.len() as u64)
+}
+
+/// Plain mean of the finished solves
+pub fn mean(records: &[SolveRecord]) -> Option<u64> {
+    let times: Vec<u64> = records.iter().filter_map(SolveRecord::effective_time_ms).collect();
+    (!times.is_empty()).then(|| times.iter().sum::<u64>() / times.len() as u64)
+}
+
+/// Average of `n` ending at each solve from the `n`th on, as (index of the
+/// last solve, average); None averages are DNFs
+pub fn rolling_averages(records: &[SolveRecord], n: usize) -> Vec<(usize, Option<u64>)> {
+    if n == 0 || records.len() < n {
+        return Vec::new();
+    }
+    records.windows(n).enumerate().map(|(i, window)| (i + n - 1, average(window))).collect()
+}
+
+/// Average of the last `n` solves, if there are that many
+pub fn current_average(records: &[SolveRecord], n: usize) -> Option<u64> {
+    records.len().checked_sub(n).and_then(|start| average(&records[start..]))
+}
+
+/// Best average of `n` consecutive solves
+pub fn best_average(records: &[SolveRecord], n: usize) -> Option<u64> {
+    rolling_averages(records, n).into_iter().filter_map(|(_, avg)| avg).min()
+}
+
+/// Summary of a session's solves
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionStats {
+    /// Solves, DNFs included
+    pub count: usize,
+    pub dnf_count: usize,
+    pub best: Option<u64>,
+    pub worst: Option<u64>,
+    /// Mean of the finished solves
+    pub mean: Option<u64>,
+    /// Average of the last 5 and 12 solves
+    pub current_ao5: Option<u64>,
+    pub current_ao12: Option<u64>,
+    /// Best average of 5 and 12 in the session
+    pub best_ao5: Option<u64>,
+    pub best_ao12: Option<u64>,
+}
+
+impl SessionStats {
+    pub fn new(records: &[SolveRecord]) -> Self {
+        let times = records.iter().filter_map(SolveRecord::effective_time_ms);
+        Self {
+            count: records.len(),
+            dnf_count: records.iter().filter(|r| r.effective_time_ms().is_none()).count(),
+            best: times.clone().min(),
+            worst: times.max(),
+            mean: mean(records),
+            current_ao5: current_average(records, 5),
+            current_ao12: current_average(records, 12),
+            best_ao5: best_average(records, 5),
+            best_ao12: best_average(records, 12),
+        }
+    }
+}
+
+/// How many finished solves fell into each range of times
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    /// Lower bound of the first bucket
+    pub start_ms: u64,
+    /// Width of every bucket
+    pub bucket_ms: u64,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Bucket the finished solves of `records` into at most `max_buckets`
+    /// buckets of a round width (0.5s, 1s, 2s, ...)
+    pub fn new(records: &[SolveRecord], max_buckets: usize) -> Self {
+        let times: Vec<u64> = records.iter().filter_map(SolveRecord::effective_time_ms).collect();
+        let (Some(&min), Some(&max)) = (times.iter().min(), times.iter().max()) else {
+            return Self { start_ms: 0, bucket_ms: BUCKET_WIDTHS[0], counts: Vec::new() };
+        };
+        let max_buckets = max_buckets.max(1) as u64;
+        let bucket_ms = BUCKET_WIDTHS
+            .into_iter()
+            .find(|width| max / width - min / width < max_buckets)
+            .unwrap_or(BUCKET_WIDTHS[BUCKET_WIDTHS.len() - 1]);
+        let start_ms = min / bucket_ms * bucket_ms;
+        let mut counts = vec![0; ((max - start_ms) / bucket_ms + 1) as usize];
+        for time in times {
+            counts[((time - start_ms) / bucket_ms) as usize] += 1;
+        }
+        Self { start_ms, bucket_ms, counts }
+    }
+
+    /// Size of the fullest bucket
+    pub fn max_count(&self) -> usize {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Lower bound of each bucket with its count
+    pub fn buckets(&self) -> impl Iterator<Item = (u64, usize)> + '_ {
+        self.counts.iter().enumerate().map(|(i, count)| (self.start_ms + i as u64 * self.bucket_ms, *count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Penalty;
+
+    fn solves(times: &[u64]) -> Vec<SolveRecord> {
+        times.iter().enumerate().map(|(i, ms)| SolveRecord::new(*ms, "R U", 3, i as u64)).collect()
+    }
+
+    fn dnf(mut record: SolveRecord) -> SolveRecord {
+        record.penalty = Penalty::Dnf;
+        record
+    }
+
+    #[test]
+    fn test_average_drops_best_and_worst() {
+        assert_eq!(trimmed(5), 1);
+        assert_eq!(trimmed(12), 1);
+        assert_eq!(trimmed(100), 5);
+        assert_eq!(average(&solves(&[10_000, 12_000, 11_000, 30_000, 9_000])), Some(11_000));
+
+        // One DNF is dropped as the worst solve, two make a DNF average
+        let mut records = solves(&[10_000, 12_000, 11_000, 30_000, 9_000]);
+        records[3] = dnf(records[3].clone());
+        assert_eq!(average(&records), Some(11_000));
+        records[0] = dnf(records[0].clone());
+        assert_eq!(average(&records), None);
+
+        assert_eq!(mean(&solves(&[10_000, 20_000])), Some(15_000));
+        assert_eq!(mean(&[]), None);
+    }
+
+    #[test]
+    fn test_rolling_averages() {
+        let records = solves(&[10_000, 12_000, 11_000, 30_000, 9_000, 8_000, 8_000]);
+        let ao5 = rolling_averages(&records, 5);
+        assert_eq!(ao5.len(), 3);
+        assert_eq!(ao5[0], (4, Some(11_000)));
+        assert_eq!(ao5[2], (6, Some(9_333)));
+        assert_eq!(current_average(&records, 5), Some(9_333));
+        assert_eq!(best_average(&records, 5), Some(9_333));
+        assert_eq!(current_average(&records, 12), None);
+        assert!(rolling_averages(&records, 12).is_empty());
+    }
+
+    #[test]
+    fn test_session_stats() {
+        let mut records = solves(&[10_000, 12_000, 11_000, 30_000, 9_000]);
+        records[1].penalty = Penalty::PlusTwo;
+        records.push(dnf(SolveRecord::new(5_000, "R", 3, 9)));
+        let stats = SessionStats::new(&records);
+        assert_eq!(stats.count, 6);
+        assert_eq!(stats.dnf_count, 1);
+        assert_eq!(stats.best, Some(9_000));
+        assert_eq!(stats.worst, Some(30_000));
+        assert_eq!(stats.best_ao5, Some(11_666));
+        assert_eq!(stats.current_ao5, Some(18_333));
+        assert_eq!(stats.best_ao12, None);
+    }
+
+    #[test]
+    fn test_histogram_uses_round_buckets() {
+        let histogram = Histogram::new(&solves(&[10_400, 11_900, 12_100, 19_999, 10_000]), 10);
+        assert_eq!(histogram.bucket_ms, 1_000);
+        assert_eq!(histogram.start_ms, 10_000);
+        assert_eq!(histogram.counts, vec![2, 1, 1, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(histogram.max_count(), 2);
+        assert_eq!(histogram.buckets().nth(2), Some((12_000, 1)));
+
+        let wide = Histogram::new(&solves(&[10_000, 99_000]), 10);
+        assert_eq!(wide.bucket_ms, 10_000);
+        assert_eq!(wide.counts, vec![1, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+        assert!(Histogram::new(&[dnf(SolveRecord::new(1, "R", 3, 0))], 10).counts.is_empty());
+    }
+}
@@ -0,0 +1,275 @@
+//! Algorithm reference sheet
+//!
+//! Collects the algorithms taught in the lessons (OLL, PLL, F2L and 4x4
+//! parity) into one searchable list, with the case each one solves and a
+//! way to send it to practice.
+
+use crate::cube::{Cube, Move};
+use crate::tutorial::lessons::{
+    CornersLesson, F2lPairsLesson, FourByFourLesson, OllLesson, PllLesson, SecondLayerLesson,
+};
+use crate::tutorial::practice::{Difficulty, PracticeCase, PracticeType};
+
+/// Group an algorithm belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlgorithmSet {
+    /// Orient last layer
+    Oll,
+    /// Permute last layer
+    Pll,
+    /// First two layers (corners and edges)
+    F2l,
+    /// 4x4 parity fixes
+    Parity,
+}
+
+impl AlgorithmSet {
+    /// All sets, in sheet order
+    pub const ALL: [AlgorithmSet; 4] = [
+        AlgorithmSet::F2l,
+        AlgorithmSet::Oll,
+        AlgorithmSet::Pll,
+        AlgorithmSet::Parity,
+    ];
+
+    /// Short display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            AlgorithmSet::Oll => "OLL",
+            AlgorithmSet::Pll => "PLL",
+            AlgorithmSet::F2l => "F2L",
+            AlgorithmSet::Parity => "Parity",
+        }
+    }
+
+    /// Cube size the set is for
+    pub fn cube_size(&self) -> usize {
+        match self {
+            AlgorithmSet::Parity => 4,
+            _ => 3,
+        }
+    }
+}
+
+/// One algorithm on the sheet
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlgorithmEntry {
+    /// Stable identifier, used for favorites (e.g. "pll-ua-perm")
+    pub id: String,
+    /// Which set it belongs to
+    pub set: AlgorithmSet,
+    /// Case name
+    pub name: String,
+    /// When to use it
+    pub description: String,
+    /// The moves
+    pub algorithm: Vec<Move>,
+}
+
+impl AlgorithmEntry {
+    /// Create an entry; the id is derived from the set and name
+    pub fn new(
+        set: AlgorithmSet,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        algorithm: Vec<Move>,
+    ) -> Self {
+        let name = name.into();
+        Self {
+            id: slug(&format!("{} {}", set.label(), name)),
+            set,
+            name,
+            description: description.into(),
+            algorithm,
+        }
+    }
+
+    /// Algorithm in standard notation
+    pub fn to_notation(&self) -> String {
+        self.algorithm
+            .iter()
+            .map(|m| m.to_notation())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Moves that set up the case from a solved cube (the inverse algorithm)
+    pub fn setup_moves(&self) -> Vec<Move> {
+        self.algorithm.iter().rev().map(|m| m.inverse()).collect()
+    }
+
+    /// The case this algorithm solves
+    pub fn case_state(&self) -> Cube {
+        let mut cube = Cube::new(self.set.cube_size());
+        cube.apply_moves(&self.setup_moves());
+        cube
+    }
+
+    /// Check whether the entry matches a search query
+    ///
+    /// Matches the name, description, set or notation, ignoring case.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.name.to_lowercase().contains(&query)
+            || self.description.to_lowercase().contains(&query)
+            || self.set.label().to_lowercase() == query
+            || self.to_notation().to_lowercase().contains(&query)
+    }
+
+    /// Turn the entry into a practice case starting from its case state
+    pub fn to_practice_case(&self) -> PracticeCase {
+        PracticeCase::new(
+            self.id.clone(),
+            PracticeType::Algorithm(self.name.clone()),
+            Difficulty::Medium,
+            self.set.cube_size(),
+            self.setup_moves(),
+            format!("{}: {}", self.set.label(), self.name),
+            self.to_notation(),
+        )
+        .with_solution(self.algorithm.clone())
+    }
+}
+
+/// Lowercase words joined by dashes, e.g. "OLL Dot to L-Shape" -> "oll-dot-to-l-shape"
+fn slug(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Every algorithm taught in the lessons, in sheet order
+pub fn algorithm_library() -> Vec<AlgorithmEntry> {
+    let mut entries = Vec::new();
+
+    for case in CornersLesson::new().get_all_cases() {
+        entries.push(AlgorithmEntry::new(
+            AlgorithmSet::F2l,
+            &case.name,
+            &case.description,
+            case.algorithm.clone(),
+        ));
+    }
+    for case in SecondLayerLesson::new().get_all_cases() {
+        entries.push(AlgorithmEntry::new(
+            AlgorithmSet::F2l,
+            &case.name,
+            &case.description,
+            case.algorithm.clone(),
+        ));
+    }
+    for case in F2lPairsLesson::new().get_cases() {
+        entries.push(AlgorithmEntry::new(
+            AlgorithmSet::F2l,
+            format!("Pair {}", case.number),
+            case.group.description(),
+            case.algorithm.clone(),
+        ));
+    }
+    for case in OllLesson::new().get_cases() {
+        entries.push(AlgorithmEntry::new(
+            AlgorithmSet::Oll,
+            &case.name,
+            &case.description,
+            case.algorithm.clone(),
+        ));
+    }
+    for case in PllLesson::new().get_cases() {
+        entries.push(AlgorithmEntry::new(
+            AlgorithmSet::Pll,
+            &case.name,
+            &case.description,
+            case.algorithm.clone(),
+        ));
+    }
+    for case in FourByFourLesson::new().get_cases_by_category("parity") {
+        entries.push(AlgorithmEntry::new(
+            AlgorithmSet::Parity,
+            &case.name,
+            &case.description,
+            case.algorithm.clone(),
+        ));
+    }
+
+    // Lessons may teach the same algorithm for several cases; keep ids unique
+    let mut seen = std::collections::HashSet::new();
+    entries.retain(|entry| seen.insert(entry.id.clone()));
+    entries
+}
+
+/// Filter the library by query, set and favorites
+pub fn filter_algorithms<'a>(
+    entries: &'a [AlgorithmEntry],
+    query: &str,
+    set: Option<AlgorithmSet>,
+    favorites_only: Option<&[String]>,
+) -> Vec<&'a AlgorithmEntry> {
+    entries
+        .iter()
+        .filter(|entry| set.is_none_or(|set| entry.set == set))
+        .filter(|entry| favorites_only.is_none_or(|favorites| favorites.contains(&entry.id)))
+        .filter(|entry| entry.matches(query))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_library_covers_every_set() {
+        let library = algorithm_library();
+        for set in AlgorithmSet::ALL {
+            assert!(library.iter().any(|e| e.set == set), "no {:?} algorithms", set);
+        }
+    }
+
+    #[test]
+    fn test_ids_are_unique_and_slugged() {
+        let library = algorithm_library();
+        for (index, entry) in library.iter().enumerate() {
+            assert!(library[index + 1..].iter().all(|other| other.id != entry.id));
+            assert!(entry.id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+        }
+    }
+
+    #[test]
+    fn test_algorithm_solves_its_case() {
+        for entry in algorithm_library() {
+            let mut cube = entry.case_state();
+            cube.apply_moves(&entry.algorithm);
+            assert!(cube.is_solved(), "{} does not solve its case", entry.name);
+        }
+    }
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("OLL Dot to L-Shape (Alternative)"), "oll-dot-to-l-shape-alternative");
+    }
+
+    #[test]
+    fn test_filter() {
+        let library = algorithm_library();
+        let sune = filter_algorithms(&library, "sune", None, None);
+        assert!(!sune.is_empty());
+        assert!(sune.iter().all(|e| e.set == AlgorithmSet::Oll));
+
+        let pll = filter_algorithms(&library, "", Some(AlgorithmSet::Pll), None);
+        assert!(pll.iter().all(|e| e.set == AlgorithmSet::Pll));
+
+        let favorites = vec![library[0].id.clone()];
+        let only = filter_algorithms(&library, "", None, Some(&favorites));
+        assert_eq!(only.len(), 1);
+    }
+
+    #[test]
+    fn test_practice_case_is_solved_by_algorithm() {
+        let entry = &algorithm_library()[0];
+        let case = entry.to_practice_case();
+        assert!(case.check_solution(&entry.algorithm));
+        assert_eq!(case.practice_type, PracticeType::Algorithm(entry.name.clone()));
+    }
+}
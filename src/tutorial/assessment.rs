@@ -0,0 +1,269 @@
+//! Placement assessment
+//!
+//! Students who can already solve part of the cube should not have to sit
+//! through the lessons they know. The assessment asks a few practical
+//! questions (notation, colors, then which stages they can solve) and can
+//! check a scanned cube the student solved as far as they could. The first
+//! course lesson they have not shown they know is where they start.
+
+use crate::cube::{Cube, FaceName};
+use crate::state::{phases_done, LessonId, Placement, Progress};
+use crate::tutorial::curriculum::Curriculum;
+
+/// A multiple-choice question about one lesson
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacementQuestion {
+    /// Lesson the question checks
+    pub lesson: LessonId,
+    /// Question text
+    pub prompt: &'static str,
+    /// Possible answers
+    pub choices: &'static [&'static str],
+    /// Index of the right answer in `choices`
+    pub correct: usize,
+}
+
+/// The questions, in course order
+pub fn placement_questions() -> Vec<PlacementQuestion> {
+    let question = |lesson: LessonId, prompt: &'static str, choices: &'static [&'static str]| PlacementQuestion {
+        lesson,
+        prompt,
+        choices,
+        correct: 0,
+    };
+    const CAN_YOU: &[&str] = &["Yes", "No"];
+    vec![
+        question(
+            LessonId::Notation,
+            "Which move turns the right face clockwise, as seen from the right?",
+            &["R", "R'", "L", "U"],
+        ),
+        question(
+            LessonId::Notation,
+            "What does U2 mean?",
+            &["Turn the top face twice", "Turn the top face counter-clockwise", "Turn the top two layers", "Rotate the whole cube"],
+        ),
+        question(LessonId::Colors, "Which color is opposite white?", &["Yellow", "Green", "Red", "Blue"]),
+        question(
+            LessonId::Colors,
+            "With white on top and green in front, which color is on the right?",
+            &["Red", "Orange", "Blue", "Yellow"],
+        ),
+        question(LessonId::Cross, "Can you solve the white cross without help?", CAN_YOU),
+        question(LessonId::FirstLayerCorners, "Can you finish the whole first layer without help?", CAN_YOU),
+        question(LessonId::SecondLayer, "Can you solve the first two layers without help?", CAN_YOU),
+        question(LessonId::OLL, "Can you make the last face all one color without help?", CAN_YOU),
+        question(LessonId::PLL, "Can you solve the whole cube without help?", CAN_YOU),
+    ]
+}
+
+/// A placement assessment in progress
+#[derive(Debug, Clone)]
+pub struct Assessment {
+    curriculum: Curriculum,
+    questions: Vec<PlacementQuestion>,
+    /// Chosen answer for each question
+    answers: Vec<Option<usize>>,
+    /// Lessons shown on a scanned cube
+    demonstrated: Vec<LessonId>,
+}
+
+impl Assessment {
+    /// Start an assessment for the beginner course
+    pub fn new() -> Self {
+        let questions = placement_questions();
+        Self {
+            curriculum: Curriculum::beginner(),
+            answers: vec![None; questions.len()],
+            questions,
+            demonstrated: Vec::new(),
+        }
+    }
+
+    /// The questions to ask
+    pub fn questions(&self) -> &[PlacementQuestion] {
+        &self.questions
+    }
+
+    /// Record an answer; returns whether it was right
+    ///
+    /// Answering a question again replaces the earlier answer. Unknown
+    /// questions are ignored.
+    pub fn answer(&mut self, question: usize, choice: usize) -> bool {
+        match self.answers.get_mut(question) {
+            Some(answer) => {
+                *answer = Some(choice);
+                self.questions[question].correct == choice
+            }
+            None => false,
+        }
+    }
+
+    /// Check a 3x3 the student solved as far as they could
+    ///
+    /// Returns the lessons the cube shows they know. The cube may be held
+    /// any way up.
+    pub fn check_cube(&mut self, cube: &Cube) -> Result<Vec<LessonId>, String> {
+        if cube.size() != 3 {
+            return Err("The assessment needs a 3x3 cube".to_string());
+        }
+        cube.validate().map_err(|_| "The scanned cube is not in a valid state".to_string())?;
+
+        let phases = phases_done(cube);
+        let mut shown = Vec::new();
+        if phases >= 1 {
+            shown.push(LessonId::Cross);
+        }
+        if phases >= 2 || first_layer_done(cube) {
+            shown.push(LessonId::FirstLayerCorners);
+        }
+        shown.extend([LessonId::SecondLayer, LessonId::OLL, LessonId::PLL].into_iter().take(phases.saturating_sub(1)));
+
+        for lesson in &shown {
+            if !self.demonstrated.contains(lesson) {
+                self.demonstrated.push(lesson.clone());
+            }
+        }
+        Ok(shown)
+    }
+
+    /// Whether the student has shown they know `lesson`: on the scanned
+    /// cube, or by answering all of its questions right
+    pub fn knows(&self, lesson: &LessonId) -> bool {
+        if self.demonstrated.contains(lesson) {
+            return true;
+        }
+        let mut answers = self
+            .questions
+            .iter()
+            .zip(&self.answers)
+            .filter(|(question, _)| question.lesson == *lesson)
+            .peekable();
+        answers.peek().is_some() && answers.all(|(question, answer)| *answer == Some(question.correct))
+    }
+
+    /// Where the student should start
+    ///
+    /// Lessons are known only up to the first one that is not: someone who
+    /// can solve the last layer but not the cross starts with the cross.
+    pub fn placement(&self) -> Placement {
+        let lessons: Vec<&LessonId> = self.curriculum.entries().iter().map(|entry| &entry.lesson).collect();
+        let known = lessons.iter().take_while(|lesson| self.knows(lesson)).count();
+        Placement {
+            start: lessons.get(known).map(|lesson| (*lesson).clone()),
+            known: lessons[..known].iter().map(|lesson| (*lesson).clone()).collect(),
+        }
+    }
+
+    /// Store the placement in `progress` and return it
+    pub fn finish(&self, progress: &mut Progress) -> Placement {
+        let placement = self.placement();
+        progress.record_placement(placement.clone());
+        placement
+    }
+}
+
+impl Default for Assessment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether one face and the row next to it on each side are solved, for
+/// any face
+fn first_layer_done(cube: &Cube) -> bool {
+    const SIDES: [FaceName; 4] = [FaceName::F, FaceName::R, FaceName::B, FaceName::L];
+    cube.orientations().iter().any(|held| {
+        let bottom = held.get_face(FaceName::D);
+        bottom.is_solved()
+            && SIDES.iter().all(|side| {
+                let face = held.get_face(*side);
+                (0..3).all(|col| face.get(2, col) == face.get(1, 1))
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Move;
+
+    fn answer_all(assessment: &mut Assessment, lesson: LessonId, right: bool) {
+        for index in 0..assessment.questions().len() {
+            let question = &assessment.questions()[index];
+            if question.lesson == lesson {
+                let choice = if right { question.correct } else { question.correct + 1 };
+                assessment.answer(index, choice);
+            }
+        }
+    }
+
+    #[test]
+    fn test_questions_cover_the_course() {
+        let questions = placement_questions();
+        for entry in Curriculum::beginner().entries() {
+            assert!(questions.iter().any(|q| q.lesson == entry.lesson), "{:?}", entry.lesson);
+        }
+        assert!(questions.iter().all(|q| q.correct < q.choices.len()));
+    }
+
+    #[test]
+    fn test_new_student_starts_at_the_beginning() {
+        let assessment = Assessment::new();
+        let placement = assessment.placement();
+        assert_eq!(placement.start, Some(LessonId::Notation));
+        assert!(placement.known.is_empty());
+    }
+
+    #[test]
+    fn test_answers_place_the_student() {
+        let mut assessment = Assessment::new();
+        answer_all(&mut assessment, LessonId::Notation, true);
+        answer_all(&mut assessment, LessonId::Colors, true);
+        answer_all(&mut assessment, LessonId::Cross, false);
+        answer_all(&mut assessment, LessonId::OLL, true);
+        let placement = assessment.placement();
+        assert_eq!(placement.start, Some(LessonId::Cross));
+        assert_eq!(placement.known, vec![LessonId::Notation, LessonId::Colors]);
+
+        // One wrong notation answer is enough to start over
+        assert!(!assessment.answer(0, 2));
+        assert_eq!(assessment.placement().start, Some(LessonId::Notation));
+    }
+
+    #[test]
+    fn test_scanned_cube() {
+        let mut assessment = Assessment::new();
+        answer_all(&mut assessment, LessonId::Notation, true);
+        answer_all(&mut assessment, LessonId::Colors, true);
+
+        // Solved except for a last-layer turn: held upside down
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::U, Move::X2]);
+        let shown = assessment.check_cube(&cube).unwrap();
+        assert_eq!(shown, vec![LessonId::Cross, LessonId::FirstLayerCorners, LessonId::SecondLayer, LessonId::OLL]);
+
+        let mut progress = Progress::new();
+        let placement = assessment.finish(&mut progress);
+        assert_eq!(placement.start, Some(LessonId::PLL));
+        assert_eq!(progress.placement(), Some(&placement));
+        assert_eq!(Curriculum::beginner().next_lesson(&progress), Some(&LessonId::PLL));
+
+        assert!(assessment.check_cube(&Cube::new(2)).is_err());
+    }
+
+    #[test]
+    fn test_partly_solved_cubes() {
+        // An edge taken out of the second layer, first layer untouched
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::UPrime, Move::RPrime, Move::U, Move::R, Move::U, Move::FPrime, Move::UPrime, Move::F]);
+        let shown = Assessment::new().check_cube(&cube).unwrap();
+        assert_eq!(shown, vec![LessonId::Cross, LessonId::FirstLayerCorners]);
+
+        // A last-layer algorithm leaves the first two layers solved
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::U, Move::RPrime, Move::U, Move::R, Move::U2, Move::RPrime]);
+        let shown = Assessment::new().check_cube(&cube).unwrap();
+        assert_eq!(shown, vec![LessonId::Cross, LessonId::FirstLayerCorners, LessonId::SecondLayer]);
+    }
+}
@@ -0,0 +1,227 @@
+//! The beginner course as a whole
+//!
+//! Puts the lessons in order (notation, colors, cross, corners, second
+//! layer, OLL, PLL), says which lessons each one builds on, and unlocks a
+//! lesson once those are completed in the student's [`Progress`].
+//! Stages of the beginner solver map to the lesson that teaches them.
+
+use crate::solver::BeginnerStage;
+use crate::state::{LessonId, Progress};
+
+/// A lesson's place in the course
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurriculumEntry {
+    /// The lesson
+    pub lesson: LessonId,
+    /// Lessons to complete before this one unlocks
+    pub prerequisites: Vec<LessonId>,
+    /// What the student can do after the lesson
+    pub goal: &'static str,
+}
+
+/// Where a student stands with a lesson
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LessonStatus {
+    /// Prerequisites are not completed yet
+    Locked,
+    /// Ready to start
+    Available,
+    /// Already completed
+    Completed,
+}
+
+/// Lessons in course order, with their prerequisites
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Curriculum {
+    entries: Vec<CurriculumEntry>,
+}
+
+impl Curriculum {
+    /// The full beginner course for the 3x3
+    ///
+    /// Notation and colors can be learned in either order; every later
+    /// lesson needs the one before it.
+    pub fn beginner() -> Self {
+        let entry = |lesson: LessonId, prerequisites: &[LessonId], goal: &'static str| CurriculumEntry {
+            lesson,
+            prerequisites: prerequisites.to_vec(),
+            goal,
+        };
+        Self {
+            entries: vec![
+                entry(LessonId::Notation, &[], "Read and perform move notation"),
+                entry(LessonId::Colors, &[], "Know which colors sit opposite and next to each other"),
+                entry(
+                    LessonId::Cross,
+                    &[LessonId::Notation, LessonId::Colors],
+                    "Solve the white cross",
+                ),
+                entry(LessonId::FirstLayerCorners, &[LessonId::Cross], "Finish the first layer"),
+                entry(LessonId::SecondLayer, &[LessonId::FirstLayerCorners], "Finish the first two layers"),
+                entry(LessonId::OLL, &[LessonId::SecondLayer], "Make the top face yellow"),
+                entry(LessonId::PLL, &[LessonId::OLL], "Solve the whole cube"),
+            ],
+        }
+    }
+
+    /// Lesson that teaches a stage of the beginner solver's solution
+    ///
+    /// The yellow cross and yellow corners are both taught in OLL.
+    pub fn lesson_for_stage(stage: BeginnerStage) -> LessonId {
+        match stage {
+            BeginnerStage::Cross => LessonId::Cross,
+            BeginnerStage::FirstLayerCorners => LessonId::FirstLayerCorners,
+            BeginnerStage::SecondLayer => LessonId::SecondLayer,
+            BeginnerStage::YellowCross | BeginnerStage::OrientCorners => LessonId::OLL,
+            BeginnerStage::PermuteLastLayer => LessonId::PLL,
+        }
+    }
+
+    /// Lessons in course order
+    pub fn entries(&self) -> &[CurriculumEntry] {
+        &self.entries
+    }
+
+    /// Entry for a lesson, if it is part of the course
+    pub fn entry(&self, lesson: &LessonId) -> Option<&CurriculumEntry> {
+        self.entries.iter().find(|entry| entry.lesson == *lesson)
+    }
+
+    /// Prerequisites of `lesson` the student has not completed
+    pub fn missing_prerequisites(&self, lesson: &LessonId, progress: &Progress) -> Vec<LessonId> {
+        self.entry(lesson)
+            .map(|entry| {
+                entry
+                    .prerequisites
+                    .iter()
+                    .filter(|prerequisite| !progress.is_lesson_completed(prerequisite))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether the student may start `lesson`
+    ///
+    /// Lessons outside the course are always unlocked.
+    pub fn is_unlocked(&self, lesson: &LessonId, progress: &Progress) -> bool {
+        self.missing_prerequisites(lesson, progress).is_empty()
+    }
+
+    /// Where the student stands with `lesson`
+    pub fn status(&self, lesson: &LessonId, progress: &Progress) -> LessonStatus {
+        if progress.is_lesson_completed(lesson) {
+            LessonStatus::Completed
+        } else if self.is_unlocked(lesson, progress) {
+            LessonStatus::Available
+        } else {
+            LessonStatus::Locked
+        }
+    }
+
+    /// First lesson in course order that is available, or `None` once the
+    /// course is finished
+    pub fn next_lesson(&self, progress: &Progress) -> Option<&LessonId> {
+        self.entries
+            .iter()
+            .map(|entry| &entry.lesson)
+            .find(|lesson| self.status(lesson, progress) == LessonStatus::Available)
+    }
+
+    /// Number of course lessons completed
+    pub fn completed_count(&self, progress: &Progress) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| progress.is_lesson_completed(&entry.lesson))
+            .count()
+    }
+
+    /// Share of the course completed (0-100)
+    ///
+    /// Only lessons in the course count, so finishing the 2x2 lesson does
+    /// not move the beginner course along.
+    pub fn completion_percentage(&self, progress: &Progress) -> f32 {
+        if self.entries.is_empty() {
+            0.0
+        } else {
+            (self.completed_count(progress) as f32 / self.entries.len() as f32) * 100.0
+        }
+    }
+
+    /// Whether every course lesson is completed
+    pub fn is_complete(&self, progress: &Progress) -> bool {
+        self.completed_count(progress) == self.entries.len()
+    }
+}
+
+impl Default for Curriculum {
+    fn default() -> Self {
+        Self::beginner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_course_order() {
+        let course = Curriculum::beginner();
+        let lessons: Vec<&LessonId> = course.entries().iter().map(|entry| &entry.lesson).collect();
+        assert_eq!(lessons.len(), 7);
+        assert_eq!(lessons[0], &LessonId::Notation);
+        assert_eq!(lessons[6], &LessonId::PLL);
+
+        // Prerequisites always come earlier in the course
+        for (index, entry) in course.entries().iter().enumerate() {
+            for prerequisite in &entry.prerequisites {
+                assert!(lessons[..index].contains(&prerequisite), "{:?}", entry.lesson);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unlocking() {
+        let course = Curriculum::beginner();
+        let mut progress = Progress::new();
+        assert_eq!(course.status(&LessonId::Notation, &progress), LessonStatus::Available);
+        assert_eq!(course.status(&LessonId::Colors, &progress), LessonStatus::Available);
+        assert_eq!(course.status(&LessonId::Cross, &progress), LessonStatus::Locked);
+        assert_eq!(
+            course.missing_prerequisites(&LessonId::Cross, &progress),
+            vec![LessonId::Notation, LessonId::Colors]
+        );
+
+        progress.complete_lesson(LessonId::Colors);
+        assert_eq!(course.next_lesson(&progress), Some(&LessonId::Notation));
+        progress.complete_lesson(LessonId::Notation);
+        assert_eq!(course.status(&LessonId::Notation, &progress), LessonStatus::Completed);
+        assert_eq!(course.next_lesson(&progress), Some(&LessonId::Cross));
+        assert!(!course.is_unlocked(&LessonId::PLL, &progress));
+        assert!(course.is_unlocked(&LessonId::TwoByTwo, &progress));
+    }
+
+    #[test]
+    fn test_completion() {
+        let course = Curriculum::beginner();
+        let mut progress = Progress::new();
+        progress.complete_lesson(LessonId::TwoByTwo);
+        assert_eq!(course.completion_percentage(&progress), 0.0);
+
+        for entry in course.entries() {
+            progress.complete_lesson(entry.lesson.clone());
+        }
+        assert_eq!(course.completion_percentage(&progress), 100.0);
+        assert!(course.is_complete(&progress));
+        assert_eq!(course.next_lesson(&progress), None);
+    }
+
+    #[test]
+    fn test_solver_stages_map_to_course_lessons() {
+        let course = Curriculum::beginner();
+        let lessons: Vec<LessonId> = BeginnerStage::ALL.iter().map(|stage| Curriculum::lesson_for_stage(*stage)).collect();
+        assert!(lessons.iter().all(|lesson| course.entry(lesson).is_some()));
+        assert_eq!(lessons.first(), Some(&LessonId::Cross));
+        assert_eq!(lessons.last(), Some(&LessonId::PLL));
+    }
+}
@@ -0,0 +1,218 @@
+//! 3x3 tutorial: F2L Pairs
+//!
+//! The intuitive-to-algorithmic step after the beginner corners and edges:
+//! - Pairing a corner with its edge and inserting them together
+//! - The 41 standard cases, grouped by where the pair starts
+//! - Trainer cases and hints from the F2L pair solver
+
+use crate::cube::{Cube, Move};
+use crate::solver::{f2l_cases, solve_f2l_pair, F2lCase, F2lGroup, F2lSlot};
+use crate::tutorial::practice::{Difficulty, PracticeCase, PracticeType};
+
+/// Represents a single lesson step for the F2L pairs tutorial
+#[derive(Debug, Clone, PartialEq)]
+pub struct F2lPairsLessonStep {
+    /// Title of the step
+    pub title: String,
+    /// Description/explanation
+    pub description: String,
+    /// Optional example moves
+    pub example_moves: Option<Vec<Move>>,
+    /// Kid-friendly explanation
+    pub kid_friendly_text: String,
+    /// Optional visual cue or tip
+    pub tip: Option<String>,
+}
+
+/// The complete F2L pairs tutorial lesson
+#[derive(Debug, Clone)]
+pub struct F2lPairsLesson {
+    /// Lesson steps
+    pub steps: Vec<F2lPairsLessonStep>,
+    /// The 41 standard cases, in case-number order
+    pub cases: Vec<F2lCase>,
+}
+
+impl F2lPairsLesson {
+    /// Creates a new F2L pairs lesson with all steps and cases
+    pub fn new() -> Self {
+        Self {
+            steps: vec![
+                Self::intro_step(),
+                Self::pairing_step(),
+                Self::both_in_top_step(),
+                Self::stuck_pieces_step(),
+                Self::practice_step(),
+            ],
+            cases: f2l_cases(),
+        }
+    }
+
+    // Lesson steps
+
+    fn intro_step() -> F2lPairsLessonStep {
+        F2lPairsLessonStep {
+            title: "Welcome to F2L!".to_string(),
+            description: "F2L stands for 'First Two Layers'. Instead of solving a corner and then the edge above it, we join them into a pair and put both in at once. There are 41 ways a pair can sit, and each one has its own short algorithm.".to_string(),
+            example_moves: None,
+            kid_friendly_text: "Corners and edges are best friends! Find them, bring them together, and slide them home as a team.".to_string(),
+            tip: Some("Every case is shown for the front-right slot. Turn the whole cube to use it on another slot.".to_string()),
+        }
+    }
+
+    fn pairing_step() -> F2lPairsLessonStep {
+        let case = &f2l_cases()[3];
+        F2lPairsLessonStep {
+            title: "Making a Pair".to_string(),
+            description: "Find the corner with the cross color and its edge with the same two side colors. When they touch in the top layer with matching colors side by side, one easy trigger drops them into the slot.".to_string(),
+            example_moves: Some(case.algorithm.clone()),
+            kid_friendly_text: "When the corner and edge are holding hands, three moves put them in place!".to_string(),
+            tip: Some("Keep the cross on the bottom the whole time.".to_string()),
+        }
+    }
+
+    fn both_in_top_step() -> F2lPairsLessonStep {
+        F2lPairsLessonStep {
+            title: "Both Pieces on Top".to_string(),
+            description: "Cases 1 to 24 start with the corner and the edge both in the top layer. Turn the top layer until the pieces line up with one of these cases, then do its algorithm.".to_string(),
+            example_moves: None,
+            kid_friendly_text: "Spin the top until the picture matches a case you know, then go!".to_string(),
+            tip: Some("Look at where the cross color on the corner is facing first.".to_string()),
+        }
+    }
+
+    fn stuck_pieces_step() -> F2lPairsLessonStep {
+        F2lPairsLessonStep {
+            title: "Pieces Stuck in a Slot".to_string(),
+            description: "Cases 25 to 41 have the corner, the edge or both already in the slot, but the wrong way around. These algorithms take the piece out and put it back correctly in one go. If a piece is stuck in a different slot, take it out to the top first.".to_string(),
+            example_moves: None,
+            kid_friendly_text: "Sometimes a piece is home but upside down. These moves pop it out and put it back the right way!".to_string(),
+            tip: Some("Cases 37 to 41 are the hardest - save them for last.".to_string()),
+        }
+    }
+
+    fn practice_step() -> F2lPairsLessonStep {
+        F2lPairsLessonStep {
+            title: "Practice Time!".to_string(),
+            description: "Use the trainer to practice one group of cases at a time. Ask for a hint if you get stuck - it names the case and shows the algorithm.".to_string(),
+            example_moves: None,
+            kid_friendly_text: "Practice a few cases every day and soon you'll spot them in a flash!".to_string(),
+            tip: Some("Learn the cases with both pieces on top first. They come up the most.".to_string()),
+        }
+    }
+
+    /// Returns all lesson steps
+    pub fn get_steps(&self) -> &[F2lPairsLessonStep] {
+        &self.steps
+    }
+
+    /// Returns all 41 cases
+    pub fn get_cases(&self) -> &[F2lCase] {
+        &self.cases
+    }
+
+    /// Returns a case by its standard number (1-41)
+    pub fn get_case(&self, number: usize) -> Option<&F2lCase> {
+        self.cases.iter().find(|c| c.number == number)
+    }
+
+    /// Returns the cases where the pair starts in `group`
+    pub fn get_cases_by_group(&self, group: F2lGroup) -> Vec<&F2lCase> {
+        self.cases.iter().filter(|c| c.group == group).collect()
+    }
+
+    /// Trainer cases, optionally only one group
+    ///
+    /// Each case starts from a cube with only the front-right pair unsolved;
+    /// doing the case's algorithm solves it.
+    pub fn practice_cases(&self, group: Option<F2lGroup>) -> Vec<PracticeCase> {
+        self.cases
+            .iter()
+            .filter(|case| group.is_none_or(|group| case.group == group))
+            .map(|case| {
+                let difficulty = match case.group {
+                    F2lGroup::BothInTop => Difficulty::Medium,
+                    _ => Difficulty::Hard,
+                };
+                PracticeCase::new(
+                    format!("f2l-{}", case.number),
+                    PracticeType::Algorithm(case.name()),
+                    difficulty,
+                    3,
+                    case.setup_moves(),
+                    format!("{}: {}", case.name(), case.group.description()),
+                    case.to_notation(),
+                )
+                .with_solution(case.algorithm.clone())
+            })
+            .collect()
+    }
+
+    /// Hint for the pair in `slot`: which case it is and how to insert it
+    pub fn hint(&self, cube: &Cube, slot: F2lSlot) -> Result<String, String> {
+        let answer = solve_f2l_pair(cube, slot)?;
+        let moves = answer
+            .moves
+            .iter()
+            .map(|m| m.to_notation())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(match &answer.case {
+            Some(case) => format!("{} ({}): {}", case.name(), case.group.description(), moves),
+            None => format!("The {} pair is already solved", slot.label()),
+        })
+    }
+}
+
+impl Default for F2lPairsLesson {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lesson_has_all_cases() {
+        let lesson = F2lPairsLesson::new();
+        assert_eq!(lesson.get_cases().len(), 41);
+        assert_eq!(lesson.get_case(1).unwrap().number, 1);
+        assert!(lesson.get_case(42).is_none());
+        assert_eq!(lesson.get_cases_by_group(F2lGroup::BothInTop).len(), 24);
+        assert_eq!(lesson.get_cases_by_group(F2lGroup::BothInSlot).len(), 5);
+    }
+
+    #[test]
+    fn test_all_steps_have_content() {
+        let lesson = F2lPairsLesson::new();
+        for step in lesson.get_steps() {
+            assert!(!step.title.is_empty());
+            assert!(!step.description.is_empty());
+            assert!(!step.kid_friendly_text.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_practice_cases_are_solved_by_their_algorithm() {
+        let lesson = F2lPairsLesson::new();
+        let cases = lesson.practice_cases(Some(F2lGroup::EdgeInSlot));
+        assert_eq!(cases.len(), 6);
+        for case in &cases {
+            assert!(case.check_solution(&case.expected_solution), "{}", case.id);
+        }
+        assert_eq!(lesson.practice_cases(None).len(), 41);
+    }
+
+    #[test]
+    fn test_hint_names_the_case() {
+        let lesson = F2lPairsLesson::new();
+        let cube = lesson.get_case(12).unwrap().case_state();
+        let hint = lesson.hint(&cube, F2lSlot::FrontRight).unwrap();
+        assert!(hint.starts_with("F2L 12"), "{}", hint);
+
+        let solved = lesson.hint(&Cube::new(3), F2lSlot::FrontRight).unwrap();
+        assert!(solved.contains("already solved"));
+    }
+}
@@ -7,6 +7,7 @@ pub mod colors;
 pub mod cross;
 pub mod f2l_corners;
 pub mod f2l_edges;
+pub mod f2l_pairs;
 pub mod oll;
 pub mod pll;
 pub mod two_by_two;
@@ -17,6 +18,7 @@ pub use colors::{ColorLessonStep, ColorPair, ColorQuizQuestion, ColorsLesson};
 pub use cross::{CrossLesson, CrossLessonStep, CrossCase, CrossEdge, CrossPracticeExercise};
 pub use f2l_corners::{CornersLesson, CornersLessonStep, CornerCase, CornerPosition, CornersPracticeExercise};
 pub use f2l_edges::{SecondLayerLesson, SecondLayerLessonStep, EdgeCase, EdgePosition, SecondLayerPracticeExercise};
+pub use f2l_pairs::{F2lPairsLesson, F2lPairsLessonStep};
 pub use oll::{OllLesson, OllLessonStep, OllCase, OllPattern, OllPracticeExercise};
 pub use pll::{PllLesson, PllLessonStep, PllCase, PllPattern, PllPracticeExercise};
 pub use two_by_two::{TwoByTwoLesson, TwoByTwoLessonStep, OrtegaCase, TwoByTwoPracticeExercise};
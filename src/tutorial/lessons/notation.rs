@@ -6,6 +6,7 @@
 //! - Interactive examples
 
 use crate::cube::Move;
+use crate::i18n::{Locale, Localizer};
 
 /// Represents a single lesson step
 #[derive(Debug, Clone, PartialEq)]
@@ -30,130 +31,36 @@ pub struct NotationLesson {
 impl NotationLesson {
     /// Creates a new notation lesson with all steps
     pub fn new() -> Self {
+        Self::new_localized(Locale::English)
+    }
+
+    /// Creates the notation lesson with text in the given locale
+    pub fn new_localized(locale: Locale) -> Self {
+        let l10n = Localizer::new(locale);
         Self {
             steps: vec![
-                Self::intro_step(),
-                Self::face_names_step(),
-                Self::r_face_step(),
-                Self::l_face_step(),
-                Self::u_face_step(),
-                Self::d_face_step(),
-                Self::f_face_step(),
-                Self::b_face_step(),
-                Self::prime_moves_step(),
-                Self::double_moves_step(),
-                Self::practice_step(),
+                Self::step(&l10n, "intro", None),
+                Self::step(&l10n, "faces", None),
+                Self::step(&l10n, "r", Some(Move::R)),
+                Self::step(&l10n, "l", Some(Move::L)),
+                Self::step(&l10n, "u", Some(Move::U)),
+                Self::step(&l10n, "d", Some(Move::D)),
+                Self::step(&l10n, "f", Some(Move::F)),
+                Self::step(&l10n, "b", Some(Move::B)),
+                Self::step(&l10n, "prime", Some(Move::RPrime)),
+                Self::step(&l10n, "double", Some(Move::R2)),
+                Self::step(&l10n, "practice", None),
             ],
         }
     }
 
-    /// Introduction to notation
-    fn intro_step() -> LessonStep {
-        LessonStep {
-            title: "Welcome to Cube Notation!".to_string(),
-            description: "Every move on a Rubik's Cube has a special letter. Learning these letters helps you solve the cube faster!".to_string(),
-            example_move: None,
-            kid_friendly_text: "Think of it like learning the alphabet for your Rubik's Cube! Each face has its own letter name.".to_string(),
-        }
-    }
-
-    /// Explain the six face names
-    fn face_names_step() -> LessonStep {
-        LessonStep {
-            title: "The Six Faces".to_string(),
-            description: "A Rubik's Cube has 6 faces. Each face is named by its position when you hold the cube: Right (R), Left (L), Up (U), Down (D), Front (F), and Back (B).".to_string(),
-            example_move: None,
-            kid_friendly_text: "Hold your cube in front of you. The face pointing at you is Front (F). The top is Up (U). Try to find all six faces!".to_string(),
-        }
-    }
-
-    /// Explain R (Right) face
-    fn r_face_step() -> LessonStep {
-        LessonStep {
-            title: "R - Right Face".to_string(),
-            description: "R means turn the right face clockwise (like turning a doorknob to open a door).".to_string(),
-            example_move: Some(Move::R),
-            kid_friendly_text: "Imagine a clock on the right side. R means turn it the way clock hands go!".to_string(),
-        }
-    }
-
-    /// Explain L (Left) face
-    fn l_face_step() -> LessonStep {
-        LessonStep {
-            title: "L - Left Face".to_string(),
-            description: "L means turn the left face clockwise when you're looking at it from the left side.".to_string(),
-            example_move: Some(Move::L),
-            kid_friendly_text: "Point the left face toward you. Now turn it clockwise, just like you did with R!".to_string(),
-        }
-    }
-
-    /// Explain U (Up) face
-    fn u_face_step() -> LessonStep {
-        LessonStep {
-            title: "U - Up Face".to_string(),
-            description: "U means turn the top face clockwise (to the right) when you're looking down at it.".to_string(),
-            example_move: Some(Move::U),
-            kid_friendly_text: "Look down at the top of your cube. U means spin it to the right like a spinning top!".to_string(),
-        }
-    }
-
-    /// Explain D (Down) face
-    fn d_face_step() -> LessonStep {
-        LessonStep {
-            title: "D - Down Face".to_string(),
-            description: "D means turn the bottom face clockwise when you're looking at it from below.".to_string(),
-            example_move: Some(Move::D),
-            kid_friendly_text: "Turn your cube upside down to see the bottom. Now turn it clockwise!".to_string(),
-        }
-    }
-
-    /// Explain F (Front) face
-    fn f_face_step() -> LessonStep {
-        LessonStep {
-            title: "F - Front Face".to_string(),
-            description: "F means turn the front face clockwise. This is usually the easiest one to remember!".to_string(),
-            example_move: Some(Move::F),
-            kid_friendly_text: "Look at the face pointing at you. Turn it clockwise like you're turning a steering wheel to the right!".to_string(),
-        }
-    }
-
-    /// Explain B (Back) face
-    fn b_face_step() -> LessonStep {
-        LessonStep {
-            title: "B - Back Face".to_string(),
-            description: "B means turn the back face clockwise when you're looking at it from behind.".to_string(),
-            example_move: Some(Move::B),
-            kid_friendly_text: "This one is tricky! Turn the cube around and look at the back. Now turn it clockwise.".to_string(),
-        }
-    }
-
-    /// Explain prime (counter-clockwise) moves
-    fn prime_moves_step() -> LessonStep {
+    /// Build a step from its `notation.<id>.*` message keys
+    fn step(l10n: &Localizer, id: &str, example_move: Option<Move>) -> LessonStep {
         LessonStep {
-            title: "Prime Moves (')".to_string(),
-            description: "When you see a letter with an apostrophe (like R'), it means turn that face counter-clockwise (the opposite way).".to_string(),
-            example_move: Some(Move::RPrime),
-            kid_friendly_text: "The apostrophe is like a backwards arrow. R' means turn R backwards instead of forwards!".to_string(),
-        }
-    }
-
-    /// Explain double (180-degree) moves
-    fn double_moves_step() -> LessonStep {
-        LessonStep {
-            title: "Double Moves (2)".to_string(),
-            description: "When you see a letter with a 2 (like R2), it means turn that face twice, or 180 degrees. You can turn it either way!".to_string(),
-            example_move: Some(Move::R2),
-            kid_friendly_text: "R2 is like doing R two times in a row. Turn it halfway around - it doesn't matter which way!".to_string(),
-        }
-    }
-
-    /// Practice step
-    fn practice_step() -> LessonStep {
-        LessonStep {
-            title: "Let's Practice!".to_string(),
-            description: "Now try these moves on your cube: R U R' U'. This is a famous pattern called the 'Sexy Move'!".to_string(),
-            example_move: None,
-            kid_friendly_text: "Don't worry if you mess up! You can always reset your cube and try again. Practice makes perfect!".to_string(),
+            title: l10n.t(&format!("notation.{}.title", id)),
+            description: l10n.t(&format!("notation.{}.description", id)),
+            example_move,
+            kid_friendly_text: l10n.t(&format!("notation.{}.kid", id)),
         }
     }
 
@@ -258,4 +165,15 @@ mod tests {
             assert_ne!(step.kid_friendly_text, step.description);
         }
     }
+
+    #[test]
+    fn test_localized_lesson_matches_english_structure() {
+        let english = NotationLesson::new();
+        let spanish = NotationLesson::new_localized(Locale::Spanish);
+        assert_eq!(spanish.step_count(), english.step_count());
+        for (en, es) in english.get_all_steps().iter().zip(spanish.get_all_steps()) {
+            assert_eq!(en.example_move, es.example_move);
+            assert_ne!(en.title, es.title);
+        }
+    }
 }
@@ -0,0 +1,159 @@
+//! Blindfold memo practice
+//!
+//! Shows a scramble and asks for its Speffz memo: edges first, then
+//! corners, as letter pairs. Answers are checked pair by pair against the
+//! memo traced by the blind solver, so a student can see where their
+//! tracing went wrong.
+
+use crate::cube::{Cube, Move};
+use crate::solver::blind::{memorize, BlindMemo};
+use crate::tutorial::practice::{Difficulty, PracticeGenerator};
+
+/// Result of checking one half of a memo (edges or corners)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoCheck {
+    /// Correct letter pairs
+    pub expected: Vec<String>,
+    /// Letter pairs the student gave
+    pub given: Vec<String>,
+}
+
+impl MemoCheck {
+    /// Compare an answer with the expected letters
+    fn new(expected: &[char], answer: &str) -> Self {
+        Self {
+            expected: BlindMemo::pairs(expected),
+            given: BlindMemo::pairs(&parse_memo(answer)),
+        }
+    }
+
+    /// Whether the whole answer is right
+    pub fn is_correct(&self) -> bool {
+        self.expected == self.given
+    }
+
+    /// Number of pairs right from the start
+    pub fn correct_pairs(&self) -> usize {
+        self.expected
+            .iter()
+            .zip(&self.given)
+            .take_while(|(expected, given)| expected == given)
+            .count()
+    }
+
+    /// Index of the first wrong or missing pair, if any
+    pub fn first_mistake(&self) -> Option<usize> {
+        (!self.is_correct()).then(|| self.correct_pairs())
+    }
+}
+
+/// Letters of a typed memo: A-X, case and spacing ignored
+pub fn parse_memo(answer: &str) -> Vec<char> {
+    answer
+        .chars()
+        .map(|c| c.to_ascii_uppercase())
+        .filter(|c| ('A'..='X').contains(c))
+        .collect()
+}
+
+/// One memo exercise
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoDrill {
+    /// Scramble to apply to a solved cube
+    pub scramble: Vec<Move>,
+    /// The memo the student should find
+    pub memo: BlindMemo,
+}
+
+impl MemoDrill {
+    /// Create a drill from a 3x3 scramble
+    pub fn new(scramble: Vec<Move>) -> Result<Self, String> {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&scramble);
+        let memo = memorize(&cube)?;
+        Ok(Self { scramble, memo })
+    }
+
+    /// Create a drill from a random full-length scramble
+    pub fn random() -> Self {
+        let case = PracticeGenerator::generate_random_case(3, Difficulty::Expert);
+        Self::new(case.scramble).expect("3x3 scrambles always have a memo")
+    }
+
+    /// The scrambled cube
+    pub fn cube(&self) -> Cube {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&self.scramble);
+        cube
+    }
+
+    /// Scramble in standard notation
+    pub fn scramble_notation(&self) -> String {
+        self.scramble
+            .iter()
+            .map(|m| m.to_notation())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Check a typed edge memo, e.g. "CM KB E"
+    pub fn check_edges(&self, answer: &str) -> MemoCheck {
+        MemoCheck::new(&self.memo.edges, answer)
+    }
+
+    /// Check a typed corner memo
+    pub fn check_corners(&self, answer: &str) -> MemoCheck {
+        MemoCheck::new(&self.memo.corners, answer)
+    }
+
+    /// Hint shown when the student gives up on a half of the memo
+    pub fn hint(&self) -> String {
+        let parity = if self.memo.has_parity() { " (odd: parity!)" } else { "" };
+        format!(
+            "Edges start at the DF buffer (U), corners at the UBL buffer (A). {} edge targets{}, {} corner targets.",
+            self.memo.edges.len(),
+            parity,
+            self.memo.corners.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_memo() {
+        assert_eq!(parse_memo("cm kB, e"), vec!['C', 'M', 'K', 'B', 'E']);
+        assert_eq!(parse_memo("yz 12"), Vec::<char>::new());
+    }
+
+    #[test]
+    fn test_check_answer() {
+        let drill = MemoDrill::new(vec![Move::R, Move::U, Move::F]).unwrap();
+        let edges: String = drill.memo.edges.iter().collect();
+        assert!(drill.check_edges(&edges.to_lowercase()).is_correct());
+        assert_eq!(drill.check_edges(&edges).first_mistake(), None);
+
+        let wrong = drill.check_corners("XX");
+        assert!(!wrong.is_correct());
+        assert_eq!(wrong.first_mistake(), Some(0));
+    }
+
+    #[test]
+    fn test_partial_answer() {
+        let drill = MemoDrill::new(vec![Move::R, Move::U, Move::F, Move::D2, Move::L]).unwrap();
+        let first_pair = &drill.memo.edge_pairs()[0];
+        let check = drill.check_edges(first_pair);
+        assert_eq!(check.correct_pairs(), 1);
+        assert!(drill.memo.edges.len() <= 2 || check.first_mistake() == Some(1));
+    }
+
+    #[test]
+    fn test_random_drill() {
+        let drill = MemoDrill::random();
+        assert!(!drill.scramble.is_empty());
+        assert_eq!(memorize(&drill.cube()).unwrap(), drill.memo);
+        assert!(drill.hint().contains("edge targets"));
+    }
+}
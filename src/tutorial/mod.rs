@@ -1,9 +1,27 @@
 //! Tutorial system module
 //!
-//! This module implements the tutorial system (R6.x) from the PRD
+//! This module implements the tutorial system (R6.x) from the PRD. The
+//! curriculum orders the beginner lessons and unlocks them as they are
+//! completed; the placement assessment lets students skip what they know.
+//! Solve-along mode walks a student through solving their own scanned cube.
+//! Narration turns each move of a solution into a sentence to show or speak.
 
+pub mod algorithms;
+pub mod assessment;
+pub mod curriculum;
 pub mod lessons;
+pub mod memo;
+pub mod narration;
 pub mod practice;
+pub mod solve_along;
+pub mod tts;
 
+pub use algorithms::{algorithm_library, filter_algorithms, AlgorithmEntry, AlgorithmSet};
+pub use assessment::{placement_questions, Assessment, PlacementQuestion};
+pub use curriculum::{Curriculum, CurriculumEntry, LessonStatus};
 pub use lessons::{LessonStep, NotationLesson, ColorLessonStep, ColorPair, ColorQuizQuestion, ColorsLesson};
-pub use practice::{PracticeCase, PracticeSession, PracticeGenerator, PracticeType, Difficulty};
+pub use memo::{parse_memo, MemoCheck, MemoDrill};
+pub use narration::{narrate_move, narrate_solution, MoveNarration, Vocabulary};
+pub use practice::{AdaptivePerformance, Divergence, MoveCheck, MoveVerifier, PracticeCase, PracticeSession, PracticeGenerator, PracticeType, Difficulty};
+pub use solve_along::{GuidedSolve, StepCheck, TurnArrow};
+pub use tts::{LessonNarrator, Narratable, NarrationEvent, NarrationListener, NarrationState, SilentTtsBackend, TtsBackend, TtsError, default_backend};
@@ -0,0 +1,310 @@
+//! Spoken narration for each move of a solution
+//!
+//! Every move gets a templated sentence ("Turn the top toward your left
+//! hand") whose wording depends on how much cubing vocabulary the student
+//! knows: young beginners hear about sides and hands, older students hear
+//! face names and notation. The first move of each step is introduced with
+//! the step's description, and solver notes say what the moves are for.
+//! The solution player shows the sentences, and [`LessonNarrator`] can read
+//! them out.
+//!
+//! [`LessonNarrator`]: super::tts::LessonNarrator
+
+use super::practice::Difficulty;
+use super::solve_along::TurnArrow;
+use super::tts::Narratable;
+use crate::cube::Move;
+use crate::solver::Solution;
+
+/// How much cubing vocabulary a narration uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Vocabulary {
+    /// Sides of the cube and which hand they turn toward, no letters
+    #[default]
+    Simple,
+    /// Face names and clockwise, with the move's letter
+    Standard,
+    /// Move notation read out, with quarter and half turns
+    Technical,
+}
+
+impl Vocabulary {
+    /// All vocabularies, simplest first
+    pub const ALL: [Vocabulary; 3] = [Vocabulary::Simple, Vocabulary::Standard, Vocabulary::Technical];
+
+    /// Vocabulary suited to a student practicing at `difficulty`
+    pub fn for_difficulty(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Beginner | Difficulty::Easy => Vocabulary::Simple,
+            Difficulty::Medium => Vocabulary::Standard,
+            Difficulty::Hard | Difficulty::Expert => Vocabulary::Technical,
+        }
+    }
+
+    /// Label for settings
+    pub fn label(&self) -> &'static str {
+        match self {
+            Vocabulary::Simple => "Simple words",
+            Vocabulary::Standard => "Face names",
+            Vocabulary::Technical => "Notation",
+        }
+    }
+}
+
+/// Layer a move turns, as a letter ("R", "M", "x")
+fn layer(mv: Move) -> char {
+    mv.to_notation().chars().next().expect("every move has notation")
+}
+
+/// Name of the layer a move turns
+fn layer_name(layer: char) -> &'static str {
+    match layer {
+        'R' => "right face",
+        'L' => "left face",
+        'U' => "top face",
+        'D' => "bottom face",
+        'F' => "front face",
+        'B' => "back face",
+        'M' => "middle slice",
+        'E' => "equator slice",
+        'S' => "standing slice",
+        _ => "whole cube",
+    }
+}
+
+/// Face a slice or rotation turns along with
+fn follows(layer: char) -> Option<char> {
+    match layer {
+        'M' => Some('L'),
+        'E' => Some('D'),
+        'S' => Some('F'),
+        'x' => Some('R'),
+        'y' => Some('U'),
+        'z' => Some('F'),
+        _ => None,
+    }
+}
+
+/// Sentence with only everyday words: (clockwise, counter-clockwise)
+/// quarter turns, and what is turned for a half turn
+fn simple_phrases(layer: char) -> (&'static str, &'static str, &'static str) {
+    match layer {
+        'R' => ("Turn the right side up, away from you", "Turn the right side down, toward you", "the right side"),
+        'L' => ("Turn the left side down, toward you", "Turn the left side up, away from you", "the left side"),
+        'U' => ("Turn the top toward your left hand", "Turn the top toward your right hand", "the top"),
+        'D' => ("Turn the bottom toward your right hand", "Turn the bottom toward your left hand", "the bottom"),
+        'F' => ("Turn the front to the right, like a clock hand", "Turn the front to the left, backwards like a clock hand", "the front"),
+        'B' => ("Turn the back so its top goes to your left", "Turn the back so its top goes to your right", "the back"),
+        'M' => ("Turn the middle slice down, toward you", "Turn the middle slice up, away from you", "the middle slice"),
+        'E' => ("Turn the middle row toward your right hand", "Turn the middle row toward your left hand", "the middle row"),
+        'S' => ("Turn the middle layer to the right, like a clock hand", "Turn the middle layer to the left, backwards like a clock hand", "the middle layer"),
+        'x' => ("Roll the whole cube so the front goes to the top", "Roll the whole cube so the front goes to the bottom", "the whole cube over"),
+        'y' => ("Spin the whole cube toward your left hand", "Spin the whole cube toward your right hand", "the whole cube"),
+        _ => ("Tilt the whole cube to the right, like a clock hand", "Tilt the whole cube to the left, backwards like a clock hand", "the whole cube on its side"),
+    }
+}
+
+/// Notation as it is read out: "R prime", "U two"
+fn spoken_notation(mv: Move) -> String {
+    let notation = mv.to_notation();
+    let letter = layer(mv);
+    match TurnArrow::of(mv) {
+        TurnArrow::Clockwise => notation.to_string(),
+        TurnArrow::CounterClockwise => format!("{} prime", letter),
+        TurnArrow::Half => format!("{} two", letter),
+    }
+}
+
+/// Sentence telling the student how to turn the cube for one move
+pub fn narrate_move(mv: Move, vocabulary: Vocabulary) -> String {
+    let letter = layer(mv);
+    let arrow = TurnArrow::of(mv);
+    match vocabulary {
+        Vocabulary::Simple => {
+            let (clockwise, counter_clockwise, half) = simple_phrases(letter);
+            match arrow {
+                TurnArrow::Clockwise => clockwise.to_string(),
+                TurnArrow::CounterClockwise => counter_clockwise.to_string(),
+                TurnArrow::Half => format!("Turn {} halfway around", half),
+            }
+        }
+        Vocabulary::Standard => {
+            let way = match (arrow, follows(letter)) {
+                (TurnArrow::Half, _) => "twice".to_string(),
+                (TurnArrow::Clockwise, None) => "clockwise".to_string(),
+                (TurnArrow::CounterClockwise, None) => "counter-clockwise".to_string(),
+                (TurnArrow::Clockwise, Some(face)) => format!("the same way as the {}", layer_name(face)),
+                (TurnArrow::CounterClockwise, Some(face)) => format!("the opposite way to the {}", layer_name(face)),
+            };
+            format!("Turn the {} {} ({})", layer_name(letter), way, mv.to_notation())
+        }
+        Vocabulary::Technical => {
+            let turn = match arrow {
+                TurnArrow::Clockwise => "quarter turn clockwise",
+                TurnArrow::CounterClockwise => "quarter turn counter-clockwise",
+                TurnArrow::Half => "half turn",
+            };
+            let rotation = if letter.is_ascii_lowercase() { " rotation" } else { "" };
+            match follows(letter) {
+                Some(face) => format!("{}: {}{}, {}, following {}", spoken_notation(mv), layer_name(letter), rotation, turn, face),
+                None => format!("{}: {}, {}", spoken_notation(mv), layer_name(letter), turn),
+            }
+        }
+    }
+}
+
+/// Narration of one move of a solution
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveNarration {
+    /// Index of the move in the solution's moves
+    pub index: usize,
+    /// The move narrated
+    pub mv: Move,
+    /// How to turn the cube
+    pub sentence: String,
+    /// Description of the step this move starts, if it is a step's first
+    pub phase: Option<String>,
+    /// What this move is for, on the first move a solver note covers
+    pub purpose: Option<String>,
+}
+
+impl MoveNarration {
+    /// Everything to say for this move: the step, the purpose, then the turn
+    pub fn spoken(&self) -> String {
+        [self.phase.as_deref(), self.purpose.as_deref(), Some(self.sentence.as_str())]
+            .into_iter()
+            .flatten()
+            .map(|part| format!("{}.", part.trim_end_matches('.')))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Lets a [`LessonNarrator`](super::tts::LessonNarrator) read a solution
+/// out one move per step
+impl Narratable for MoveNarration {
+    fn narration_title(&self) -> &str {
+        self.phase.as_deref().unwrap_or_default()
+    }
+
+    fn narration_body(&self) -> &str {
+        &self.sentence
+    }
+
+    fn narration_text(&self) -> String {
+        self.spoken()
+    }
+}
+
+/// Narrate every move of a solution, in order
+pub fn narrate_solution(solution: &Solution, vocabulary: Vocabulary) -> Vec<MoveNarration> {
+    let mut narrations = Vec::new();
+    for step in &solution.steps {
+        for (offset, mv) in step.moves.iter().enumerate() {
+            let purpose = step
+                .notes
+                .iter()
+                .find(|note| note.start == offset && note.len > 0)
+                .map(|note| note.purpose.clone());
+            narrations.push(MoveNarration {
+                index: narrations.len(),
+                mv: *mv,
+                sentence: narrate_move(*mv, vocabulary),
+                phase: (offset == 0).then(|| step.description.clone()),
+                purpose,
+            });
+        }
+    }
+    narrations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::SolutionStep;
+    use crate::tutorial::tts::{LessonNarrator, SilentTtsBackend};
+
+    #[test]
+    fn test_simple_sentences() {
+        assert_eq!(narrate_move(Move::U, Vocabulary::Simple), "Turn the top toward your left hand");
+        assert_eq!(narrate_move(Move::UPrime, Vocabulary::Simple), "Turn the top toward your right hand");
+        assert_eq!(narrate_move(Move::R, Vocabulary::Simple), "Turn the right side up, away from you");
+        assert_eq!(narrate_move(Move::F2, Vocabulary::Simple), "Turn the front halfway around");
+        assert_eq!(narrate_move(Move::Y, Vocabulary::Simple), "Spin the whole cube toward your left hand");
+        // Slices turn like the face they follow
+        assert_eq!(narrate_move(Move::M, Vocabulary::Simple), "Turn the middle slice down, toward you");
+    }
+
+    #[test]
+    fn test_richer_vocabularies() {
+        assert_eq!(narrate_move(Move::RPrime, Vocabulary::Standard), "Turn the right face counter-clockwise (R')");
+        assert_eq!(narrate_move(Move::D2, Vocabulary::Standard), "Turn the bottom face twice (D2)");
+        assert_eq!(narrate_move(Move::E, Vocabulary::Standard), "Turn the equator slice the same way as the bottom face (E)");
+        assert_eq!(narrate_move(Move::RPrime, Vocabulary::Technical), "R prime: right face, quarter turn counter-clockwise");
+        assert_eq!(narrate_move(Move::X2, Vocabulary::Technical), "x two: whole cube rotation, half turn, following R");
+    }
+
+    #[test]
+    fn test_every_move_is_narrated() {
+        for vocabulary in Vocabulary::ALL {
+            let quarter_turns = [
+                Move::R, Move::L, Move::U, Move::D, Move::F, Move::B,
+                Move::M, Move::E, Move::S, Move::X, Move::Y, Move::Z,
+            ];
+            for mv in quarter_turns.into_iter().flat_map(|mv| [mv, mv.inverse()]) {
+                let sentence = narrate_move(mv, vocabulary);
+                assert!(!sentence.is_empty());
+                if vocabulary == Vocabulary::Simple {
+                    let notation = mv.to_notation();
+                    assert!(sentence.split_whitespace().all(|word| word != notation), "{:?} says its letter", mv);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_narrator_reads_moves() {
+        let solution = Solution::new(vec![SolutionStep::new("Yellow cross", vec![Move::F, Move::U])], 0);
+        let narrations = narrate_solution(&solution, Vocabulary::Standard);
+        let mut narrator = LessonNarrator::new(Box::new(SilentTtsBackend));
+        narrator.load_steps(&narrations);
+        assert_eq!(narrator.step_count(), 2);
+        assert_eq!(narrations[0].narration_text(), "Yellow cross. Turn the front face clockwise (F).");
+        assert_eq!(narrations[1].narration_text(), "Turn the top face clockwise (U).");
+    }
+
+    #[test]
+    fn test_vocabulary_follows_difficulty() {
+        assert_eq!(Vocabulary::for_difficulty(Difficulty::Beginner), Vocabulary::Simple);
+        assert_eq!(Vocabulary::for_difficulty(Difficulty::Medium), Vocabulary::Standard);
+        assert_eq!(Vocabulary::for_difficulty(Difficulty::Expert), Vocabulary::Technical);
+    }
+
+    #[test]
+    fn test_solution_narration() {
+        let solution = Solution::new(
+            vec![
+                SolutionStep::new("White cross", vec![Move::F, Move::R]),
+                SolutionStep::new("First layer", vec![Move::R, Move::U, Move::RPrime])
+                    .with_note(1, 2, "Bring the corner down"),
+            ],
+            0,
+        );
+        let narrations = narrate_solution(&solution, Vocabulary::Simple);
+        assert_eq!(narrations.len(), 5);
+        assert_eq!(narrations.iter().map(|n| n.index).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(narrations[0].phase.as_deref(), Some("White cross"));
+        assert_eq!(narrations[1].phase, None);
+        assert_eq!(narrations[2].phase.as_deref(), Some("First layer"));
+        assert_eq!(narrations[3].purpose.as_deref(), Some("Bring the corner down"));
+        assert_eq!(narrations[4].purpose, None);
+        assert_eq!(
+            narrations[3].spoken(),
+            "Bring the corner down. Turn the top toward your left hand."
+        );
+        assert_eq!(
+            narrations[2].spoken(),
+            "First layer. Turn the right side up, away from you."
+        );
+    }
+}
@@ -4,12 +4,29 @@
 //! - Generate specific practice cases
 //! - Hints available
 //! - Check if solution is correct
+//! - Adapt difficulty to how well the student is doing
+//! - Follow the student's moves against the expected algorithm, pointing out
+//!   where they went wrong and how to undo it
 //!
 //! The practice mode provides a comprehensive system for students to practice
 //! solving various cube scenarios with feedback, hints, and solution validation.
 
 use crate::cube::{Cube, Move};
+use crate::solver::setup::merge_turns;
 use crate::solver::{solve_2x2, solve_3x3};
+use std::collections::{HashMap, VecDeque};
+
+/// Number of recent results considered when adapting difficulty
+const ADAPTIVE_WINDOW: usize = 5;
+
+/// Minimum results at the current level before the difficulty may change
+const ADAPTIVE_MIN_ATTEMPTS: usize = 3;
+
+/// Success rate (0.0-1.0) at or above which the difficulty goes up
+const PROMOTE_THRESHOLD: f32 = 0.8;
+
+/// Success rate (0.0-1.0) at or below which the difficulty goes down
+const DEMOTE_THRESHOLD: f32 = 0.4;
 
 /// Difficulty level for practice cases
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,10 +65,30 @@ impl Difficulty {
             Difficulty::Expert => "Expert",
         }
     }
+
+    /// Get the next harder difficulty (Expert stays Expert)
+    pub fn harder(&self) -> Difficulty {
+        match self {
+            Difficulty::Beginner => Difficulty::Easy,
+            Difficulty::Easy => Difficulty::Medium,
+            Difficulty::Medium => Difficulty::Hard,
+            Difficulty::Hard | Difficulty::Expert => Difficulty::Expert,
+        }
+    }
+
+    /// Get the next easier difficulty (Beginner stays Beginner)
+    pub fn easier(&self) -> Difficulty {
+        match self {
+            Difficulty::Beginner | Difficulty::Easy => Difficulty::Beginner,
+            Difficulty::Medium => Difficulty::Easy,
+            Difficulty::Hard => Difficulty::Medium,
+            Difficulty::Expert => Difficulty::Hard,
+        }
+    }
 }
 
 /// Type of practice case
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum PracticeType {
     /// Practice solving a specific pattern (e.g., cross, OLL, PLL)
     Pattern(String),
@@ -142,6 +179,11 @@ impl PracticeCase {
         cube.is_solved()
     }
 
+    /// Follow the student's moves against the expected solution
+    pub fn verifier(&self) -> MoveVerifier {
+        MoveVerifier::new(&self.get_scrambled_cube(), self.expected_solution.clone())
+    }
+
     /// Generate a solution for this practice case
     pub fn generate_solution(&self) -> Result<Vec<Move>, String> {
         let cube = self.get_scrambled_cube();
@@ -153,7 +195,7 @@ impl PracticeCase {
             }
             3 => {
                 let solution = solve_3x3(&cube)?;
-                Ok(solution.moves)
+                Ok(solution.moves())
             }
             _ => Err(format!("Solver not implemented for {}x{} cubes", self.cube_size, self.cube_size)),
         }
@@ -249,10 +291,284 @@ impl PracticeSession {
     }
 }
 
+/// Where the student's moves left the expected algorithm
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// Expected moves done before going wrong
+    pub at: usize,
+    /// The move that was expected there (`None` past the end)
+    pub expected: Option<Move>,
+    /// Moves made since the last position on the algorithm
+    pub wrong: Vec<Move>,
+    /// Moves that undo `wrong`, with turns of the same layer merged
+    pub fix: Vec<Move>,
+}
+
+/// Result of checking the moves made so far
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveCheck {
+    /// The cube is where the algorithm has it after `done` moves
+    OnTrack { done: usize },
+    /// The whole algorithm is done
+    Complete,
+    /// The moves left the algorithm
+    Diverged(Divergence),
+}
+
+/// Checks moves as they are made (keyboard or smart cube) against an
+/// expected algorithm
+///
+/// Moves are compared by the cube state they lead to, so "U U" matches
+/// "U2" and an undone mistake is back on track. Halfway through a double
+/// turn ("U" of "U2", in either direction) still counts as on track.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveVerifier {
+    expected: Vec<Move>,
+    /// Cube after each number of expected moves
+    states: Vec<Cube>,
+    input: Vec<Move>,
+    cube: Cube,
+    /// Expected moves matched, and the number of input moves at that point
+    matched: usize,
+    matched_input: usize,
+}
+
+impl MoveVerifier {
+    /// Follow `expected` from `start`
+    pub fn new(start: &Cube, expected: Vec<Move>) -> Self {
+        let mut states = vec![start.clone()];
+        for mv in &expected {
+            let mut next = states[states.len() - 1].clone();
+            next.apply_move(*mv);
+            states.push(next);
+        }
+        Self {
+            expected,
+            states,
+            input: Vec::new(),
+            cube: start.clone(),
+            matched: 0,
+            matched_input: 0,
+        }
+    }
+
+    /// Moves made so far
+    pub fn input(&self) -> &[Move] {
+        &self.input
+    }
+
+    /// The expected move after the last position on the algorithm
+    pub fn next_expected(&self) -> Option<Move> {
+        self.expected.get(self.matched).copied()
+    }
+
+    /// Record a move and check where the cube is now
+    pub fn push(&mut self, mv: Move) -> MoveCheck {
+        self.cube.apply_move(mv);
+        self.input.push(mv);
+        let position = self
+            .states
+            .iter()
+            .enumerate()
+            .filter(|(_, state)| **state == self.cube)
+            .map(|(index, _)| index)
+            .min_by_key(|index| (index.abs_diff(self.matched), *index < self.matched));
+        if let Some(position) = position {
+            self.matched = position;
+            self.matched_input = self.input.len();
+        }
+        self.check()
+    }
+
+    /// Check where the cube is after the moves so far
+    pub fn check(&self) -> MoveCheck {
+        let wrong = &self.input[self.matched_input..];
+        if wrong.is_empty() {
+            return if self.matched == self.expected.len() {
+                MoveCheck::Complete
+            } else {
+                MoveCheck::OnTrack { done: self.matched }
+            };
+        }
+        if let ([mv], Some(next)) = (wrong, self.states.get(self.matched + 1)) {
+            let mut doubled = self.cube.clone();
+            doubled.apply_move(*mv);
+            if doubled == *next {
+                return MoveCheck::OnTrack { done: self.matched };
+            }
+        }
+        let undo: Vec<Move> = wrong.iter().rev().map(Move::inverse).collect();
+        MoveCheck::Diverged(Divergence {
+            at: self.matched,
+            expected: self.next_expected(),
+            wrong: wrong.to_vec(),
+            fix: merge_turns(&undo),
+        })
+    }
+
+    /// Forget the moves made and start over
+    pub fn reset(&mut self) {
+        self.cube = self.states[0].clone();
+        self.input.clear();
+        self.matched = 0;
+        self.matched_input = 0;
+    }
+}
+
+/// Recent performance for one practice type, used for adaptive difficulty
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptivePerformance {
+    /// Difficulty currently served for this practice type
+    pub difficulty: Difficulty,
+    /// Results since the last difficulty change (most recent last)
+    recent: VecDeque<bool>,
+    /// Total attempts recorded for this practice type
+    pub total_attempts: u32,
+    /// Total successes recorded for this practice type
+    pub total_successes: u32,
+}
+
+impl AdaptivePerformance {
+    /// Create empty performance data starting at the given difficulty
+    pub fn new(difficulty: Difficulty) -> Self {
+        Self {
+            difficulty,
+            recent: VecDeque::with_capacity(ADAPTIVE_WINDOW),
+            total_attempts: 0,
+            total_successes: 0,
+        }
+    }
+
+    /// Success rate (0.0-1.0) over the recent window
+    pub fn recent_success_rate(&self) -> f32 {
+        if self.recent.is_empty() {
+            0.0
+        } else {
+            self.recent.iter().filter(|&&ok| ok).count() as f32 / self.recent.len() as f32
+        }
+    }
+
+    /// Record a result and adjust the difficulty if needed
+    ///
+    /// Returns the new difficulty if it changed.
+    fn record(&mut self, success: bool) -> Option<Difficulty> {
+        self.total_attempts += 1;
+        if success {
+            self.total_successes += 1;
+        }
+
+        self.recent.push_back(success);
+        if self.recent.len() > ADAPTIVE_WINDOW {
+            self.recent.pop_front();
+        }
+
+        if self.recent.len() < ADAPTIVE_MIN_ATTEMPTS {
+            return None;
+        }
+
+        let rate = self.recent_success_rate();
+        let next = if rate >= PROMOTE_THRESHOLD {
+            self.difficulty.harder()
+        } else if rate <= DEMOTE_THRESHOLD {
+            self.difficulty.easier()
+        } else {
+            self.difficulty
+        };
+
+        if next != self.difficulty {
+            // Start a fresh window so the student proves themselves at the new level
+            self.difficulty = next;
+            self.recent.clear();
+            Some(next)
+        } else {
+            None
+        }
+    }
+}
+
 /// Practice case generator
-pub struct PracticeGenerator;
+///
+/// The associated functions generate cases at a fixed difficulty. A generator
+/// instance additionally tracks success rate per `PracticeType` and adapts the
+/// difficulty it serves: struggling students get shorter scrambles, students
+/// who succeed consistently get longer ones.
+#[derive(Debug, Clone)]
+pub struct PracticeGenerator {
+    /// Difficulty used for practice types without any recorded results
+    initial_difficulty: Difficulty,
+    /// Performance data per practice type
+    performance: HashMap<PracticeType, AdaptivePerformance>,
+}
+
+impl Default for PracticeGenerator {
+    fn default() -> Self {
+        Self::new(Difficulty::Beginner)
+    }
+}
 
 impl PracticeGenerator {
+    /// Create an adaptive generator starting at the given difficulty
+    pub fn new(initial_difficulty: Difficulty) -> Self {
+        Self {
+            initial_difficulty,
+            performance: HashMap::new(),
+        }
+    }
+
+    /// Get the difficulty currently served for a practice type
+    pub fn current_difficulty(&self, practice_type: &PracticeType) -> Difficulty {
+        self.performance
+            .get(practice_type)
+            .map(|perf| perf.difficulty)
+            .unwrap_or(self.initial_difficulty)
+    }
+
+    /// Get performance data for a practice type, if any results were recorded
+    pub fn performance(&self, practice_type: &PracticeType) -> Option<&AdaptivePerformance> {
+        self.performance.get(practice_type)
+    }
+
+    /// Get the overall success rate as a percentage (0-100) for a practice type
+    pub fn success_rate(&self, practice_type: &PracticeType) -> f32 {
+        match self.performance.get(practice_type) {
+            Some(perf) if perf.total_attempts > 0 => {
+                (perf.total_successes as f32 / perf.total_attempts as f32) * 100.0
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Record the outcome of a practice attempt
+    ///
+    /// Returns the new difficulty if this result caused it to change.
+    pub fn record_result(&mut self, practice_type: &PracticeType, success: bool) -> Option<Difficulty> {
+        let initial = self.initial_difficulty;
+        self.performance
+            .entry(practice_type.clone())
+            .or_insert_with(|| AdaptivePerformance::new(initial))
+            .record(success)
+    }
+
+    /// Record the outcome of a practice case using its practice type
+    pub fn record_case_result(&mut self, case: &PracticeCase, success: bool) -> Option<Difficulty> {
+        self.record_result(&case.practice_type, success)
+    }
+
+    /// Generate a random case at the difficulty adapted to the student's results
+    pub fn next_random_case(&self, cube_size: usize) -> PracticeCase {
+        Self::generate_random_case(cube_size, self.current_difficulty(&PracticeType::Random))
+    }
+
+    /// Generate a session of random cases at the adapted difficulty
+    pub fn next_session(&self, cube_size: usize, count: usize) -> PracticeSession {
+        Self::generate_session(cube_size, self.current_difficulty(&PracticeType::Random), count)
+    }
+
+    /// Forget all recorded results
+    pub fn reset(&mut self) {
+        self.performance.clear();
+    }
+
     /// Generate a random practice case for the given difficulty and cube size
     pub fn generate_random_case(
         cube_size: usize,
@@ -340,6 +656,80 @@ impl PracticeGenerator {
 mod tests {
     use super::*;
 
+    fn verifier(expected: &[Move]) -> MoveVerifier {
+        MoveVerifier::new(&Cube::new(3), expected.to_vec())
+    }
+
+    #[test]
+    fn test_verifier_follows_the_algorithm() {
+        let mut verifier = verifier(&[Move::R, Move::U2, Move::RPrime]);
+        assert_eq!(verifier.check(), MoveCheck::OnTrack { done: 0 });
+        assert_eq!(verifier.push(Move::R), MoveCheck::OnTrack { done: 1 });
+        // Halfway through U2, then "U U" counts as U2
+        assert_eq!(verifier.push(Move::U), MoveCheck::OnTrack { done: 1 });
+        assert_eq!(verifier.push(Move::U), MoveCheck::OnTrack { done: 2 });
+        assert_eq!(verifier.next_expected(), Some(Move::RPrime));
+        assert_eq!(verifier.push(Move::RPrime), MoveCheck::Complete);
+        assert_eq!(verifier.input().len(), 4);
+
+        verifier.reset();
+        assert_eq!(verifier.input(), &[] as &[Move]);
+        assert_eq!(verifier.push(Move::UPrime), MoveCheck::Diverged(Divergence {
+            at: 0,
+            expected: Some(Move::R),
+            wrong: vec![Move::UPrime],
+            fix: vec![Move::U],
+        }));
+    }
+
+    #[test]
+    fn test_verifier_offers_a_fix() {
+        let mut verifier = verifier(&[Move::R, Move::U, Move::RPrime, Move::UPrime]);
+        verifier.push(Move::R);
+        verifier.push(Move::F);
+        verifier.push(Move::F);
+        let MoveCheck::Diverged(divergence) = verifier.check() else {
+            panic!("Expected a divergence");
+        };
+        assert_eq!(divergence.at, 1);
+        assert_eq!(divergence.expected, Some(Move::U));
+        assert_eq!(divergence.wrong, vec![Move::F, Move::F]);
+        assert_eq!(divergence.fix, vec![Move::F2]);
+
+        // Making the fix gets back on track
+        for mv in divergence.fix {
+            verifier.push(mv);
+        }
+        assert_eq!(verifier.check(), MoveCheck::OnTrack { done: 1 });
+
+        // Moves after the end are mistakes too
+        for mv in [Move::U, Move::RPrime, Move::UPrime] {
+            verifier.push(mv);
+        }
+        assert_eq!(verifier.check(), MoveCheck::Complete);
+        assert!(matches!(verifier.push(Move::D), MoveCheck::Diverged(Divergence { at: 4, expected: None, .. })));
+    }
+
+    #[test]
+    fn test_case_verifier() {
+        let case = PracticeCase::new(
+            "verify".to_string(),
+            PracticeType::Algorithm("Sexy move".to_string()),
+            Difficulty::Beginner,
+            3,
+            vec![Move::U, Move::R, Move::UPrime, Move::RPrime],
+            "Undo the sexy move".to_string(),
+            "Reverse each move".to_string(),
+        )
+        .with_solution(vec![Move::R, Move::U, Move::RPrime, Move::UPrime]);
+
+        let mut verifier = case.verifier();
+        for mv in &case.expected_solution {
+            verifier.push(*mv);
+        }
+        assert_eq!(verifier.check(), MoveCheck::Complete);
+    }
+
     #[test]
     fn test_difficulty_levels() {
         assert_eq!(Difficulty::Beginner.name(), "Beginner");
@@ -580,6 +970,65 @@ mod tests {
         assert_eq!(case.expected_solution, vec![Move::RPrime]);
     }
 
+    #[test]
+    fn test_difficulty_harder_and_easier() {
+        assert_eq!(Difficulty::Beginner.harder(), Difficulty::Easy);
+        assert_eq!(Difficulty::Expert.harder(), Difficulty::Expert);
+        assert_eq!(Difficulty::Medium.easier(), Difficulty::Easy);
+        assert_eq!(Difficulty::Beginner.easier(), Difficulty::Beginner);
+    }
+
+    #[test]
+    fn test_adaptive_promotes_after_successes() {
+        let mut generator = PracticeGenerator::new(Difficulty::Easy);
+        let practice_type = PracticeType::Random;
+
+        assert_eq!(generator.record_result(&practice_type, true), None);
+        assert_eq!(generator.record_result(&practice_type, true), None);
+        assert_eq!(generator.record_result(&practice_type, true), Some(Difficulty::Medium));
+        assert_eq!(generator.current_difficulty(&practice_type), Difficulty::Medium);
+    }
+
+    #[test]
+    fn test_adaptive_demotes_after_failures() {
+        let mut generator = PracticeGenerator::new(Difficulty::Hard);
+        let practice_type = PracticeType::Pattern("Cross".to_string());
+
+        for _ in 0..3 {
+            generator.record_result(&practice_type, false);
+        }
+
+        assert_eq!(generator.current_difficulty(&practice_type), Difficulty::Medium);
+        // Other practice types are unaffected
+        assert_eq!(generator.current_difficulty(&PracticeType::Random), Difficulty::Hard);
+    }
+
+    #[test]
+    fn test_adaptive_mixed_results_keep_difficulty() {
+        let mut generator = PracticeGenerator::new(Difficulty::Medium);
+        let practice_type = PracticeType::Random;
+
+        for success in [true, false, true, false, true] {
+            generator.record_result(&practice_type, success);
+        }
+
+        assert_eq!(generator.current_difficulty(&practice_type), Difficulty::Medium);
+        assert!((generator.success_rate(&practice_type) - 60.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_next_random_case_uses_adapted_difficulty() {
+        let mut generator = PracticeGenerator::default();
+        for _ in 0..3 {
+            generator.record_result(&PracticeType::Random, true);
+        }
+
+        let case = generator.next_random_case(2);
+        assert_eq!(case.difficulty, Difficulty::Easy);
+        let (min, max) = Difficulty::Easy.scramble_length_range();
+        assert!(case.scramble.len() >= min && case.scramble.len() <= max);
+    }
+
     #[test]
     fn test_case_with_target() {
         let case = PracticeCase::new(
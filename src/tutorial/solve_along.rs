@@ -0,0 +1,264 @@
+//! Solve along with a physical cube
+//!
+//! Takes the student's scanned cube, plans a layer-by-layer solution and
+//! walks through it one move at a time. The engine keeps its own copy of the
+//! cube, turned the way the student is told to turn theirs, so a re-scan can
+//! be checked against where the student should be: still on track, a few
+//! moves behind or ahead, or off the plan (then it re-plans from the scan).
+
+use crate::cube::{Cube, Move};
+use crate::solver::{solve_layer_by_layer, Solution, SolutionStep};
+
+/// Which way the arrow for a move points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnArrow {
+    /// A quarter turn clockwise
+    Clockwise,
+    /// A quarter turn counter-clockwise
+    CounterClockwise,
+    /// A half turn
+    Half,
+}
+
+impl TurnArrow {
+    /// Arrow for a move, read from its notation
+    pub fn of(mv: Move) -> Self {
+        let notation = mv.to_notation();
+        if notation.ends_with('2') {
+            TurnArrow::Half
+        } else if notation.ends_with('\'') {
+            TurnArrow::CounterClockwise
+        } else {
+            TurnArrow::Clockwise
+        }
+    }
+
+    /// Arrow symbol to draw
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            TurnArrow::Clockwise => "↻",
+            TurnArrow::CounterClockwise => "↺",
+            TurnArrow::Half => "⟳",
+        }
+    }
+}
+
+/// How a scanned cube compares with where the student should be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepCheck {
+    /// The cube is where it should be
+    OnTrack,
+    /// The cube matches an earlier position, this many moves back
+    Behind(usize),
+    /// The cube matches a later position, this many moves ahead
+    Ahead(usize),
+    /// The cube matches no position on the plan; the number of stickers
+    /// that differ from the expected cube
+    OffTrack(usize),
+}
+
+/// A move-by-move walk through a solution of a scanned cube
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuidedSolve {
+    solution: Solution,
+    moves: Vec<Move>,
+    /// Index of the solution step each move belongs to
+    step_of: Vec<usize>,
+    /// Cube after each number of moves, from the scanned cube to solved
+    states: Vec<Cube>,
+    position: usize,
+}
+
+impl GuidedSolve {
+    /// Plan a solution for the scanned cube
+    ///
+    /// Returns an error if the cube is not a valid 3x3.
+    pub fn new(scanned: &Cube) -> Result<Self, String> {
+        let solution = solve_layer_by_layer(scanned)?;
+        let mut moves = Vec::new();
+        let mut step_of = Vec::new();
+        for (index, step) in solution.steps.iter().enumerate() {
+            moves.extend(&step.moves);
+            step_of.extend(std::iter::repeat_n(index, step.moves.len()));
+        }
+        let mut states = vec![scanned.clone()];
+        for mv in &moves {
+            let mut next = states[states.len() - 1].clone();
+            next.apply_move(*mv);
+            states.push(next);
+        }
+        Ok(Self { solution, moves, step_of, states, position: 0 })
+    }
+
+    /// The planned solution
+    pub fn solution(&self) -> &Solution {
+        &self.solution
+    }
+
+    /// Number of moves done
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Total number of moves
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Check whether the scanned cube was already solved
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Check whether every move has been done
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.moves.len()
+    }
+
+    /// The move to make next
+    pub fn current_move(&self) -> Option<Move> {
+        self.moves.get(self.position).copied()
+    }
+
+    /// The arrow for the move to make next
+    pub fn arrow(&self) -> Option<TurnArrow> {
+        self.current_move().map(TurnArrow::of)
+    }
+
+    /// The solution step the next move belongs to
+    pub fn current_step(&self) -> Option<&SolutionStep> {
+        self.step_of.get(self.position).map(|index| &self.solution.steps[*index])
+    }
+
+    /// How the student's cube should look now, before the next move
+    pub fn expected_cube(&self) -> &Cube {
+        &self.states[self.position]
+    }
+
+    /// The student made the move; returns false once finished
+    pub fn done(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        self.position += 1;
+        true
+    }
+
+    /// Go back one move; returns false at the start
+    pub fn back(&mut self) -> bool {
+        if self.position == 0 {
+            return false;
+        }
+        self.position -= 1;
+        true
+    }
+
+    /// Compare a re-scanned cube with the simulation
+    ///
+    /// When the cube matches several positions (a move and its undo), the
+    /// one closest to the current position is reported.
+    pub fn check(&self, scanned: &Cube) -> StepCheck {
+        if scanned.size() != self.expected_cube().size() {
+            return StepCheck::OffTrack(6 * scanned.size() * scanned.size());
+        }
+        let closest = self
+            .states
+            .iter()
+            .enumerate()
+            .filter(|(_, state)| *state == scanned)
+            .map(|(index, _)| index)
+            .min_by_key(|index| index.abs_diff(self.position));
+        match closest {
+            Some(index) if index == self.position => StepCheck::OnTrack,
+            Some(index) if index < self.position => StepCheck::Behind(self.position - index),
+            Some(index) => StepCheck::Ahead(index - self.position),
+            None => StepCheck::OffTrack(self.expected_cube().diff(scanned).len()),
+        }
+    }
+
+    /// Continue from a re-scanned cube
+    ///
+    /// A cube behind or ahead of the plan moves the position to match; a
+    /// cube off the plan gets a new solution from where it is. Returns how
+    /// the cube compared, or an error if it cannot be solved.
+    pub fn resync(&mut self, scanned: &Cube) -> Result<StepCheck, String> {
+        let check = self.check(scanned);
+        match check {
+            StepCheck::OnTrack => {}
+            StepCheck::Behind(moves) => self.position -= moves,
+            StepCheck::Ahead(moves) => self.position += moves,
+            StepCheck::OffTrack(_) => *self = Self::new(scanned)?,
+        }
+        Ok(check)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scrambled() -> Cube {
+        let mut cube = Cube::new(3);
+        cube.apply_moves(&[Move::R, Move::U, Move::F2, Move::DPrime, Move::L, Move::B]);
+        cube
+    }
+
+    #[test]
+    fn test_arrows() {
+        assert_eq!(TurnArrow::of(Move::R), TurnArrow::Clockwise);
+        assert_eq!(TurnArrow::of(Move::UPrime), TurnArrow::CounterClockwise);
+        assert_eq!(TurnArrow::of(Move::F2), TurnArrow::Half);
+        assert_eq!(TurnArrow::of(Move::YPrime).symbol(), "↺");
+    }
+
+    #[test]
+    fn test_walk_through() {
+        let scanned = scrambled();
+        let mut guide = GuidedSolve::new(&scanned).unwrap();
+        assert!(!guide.is_empty());
+        assert_eq!(guide.expected_cube(), &scanned);
+        assert_eq!(guide.current_step(), Some(&guide.solution().steps[0]));
+
+        let mut physical = scanned.clone();
+        while let Some(mv) = guide.current_move() {
+            physical.apply_move(mv);
+            assert!(guide.done());
+            assert_eq!(guide.check(&physical), StepCheck::OnTrack);
+        }
+        assert!(guide.is_finished());
+        assert!(physical.is_solved());
+        assert_eq!(guide.current_step(), None);
+        assert!(!guide.done());
+
+        assert!(guide.back());
+        assert_eq!(guide.position(), guide.len() - 1);
+    }
+
+    #[test]
+    fn test_check_and_resync() {
+        let scanned = scrambled();
+        let mut guide = GuidedSolve::new(&scanned).unwrap();
+        let first_moves: Vec<Move> = guide.solution().all_moves().into_iter().take(3).collect();
+
+        // Forgot to press "done"
+        let mut physical = scanned.clone();
+        physical.apply_moves(&first_moves[..2]);
+        assert_eq!(guide.check(&physical), StepCheck::Ahead(2));
+        assert_eq!(guide.resync(&physical), Ok(StepCheck::Ahead(2)));
+        assert_eq!(guide.position(), 2);
+
+        // Pressed "done" without turning
+        guide.done();
+        assert_eq!(guide.check(&physical), StepCheck::Behind(1));
+
+        // A wrong turn gets a new plan from the actual cube
+        physical.apply_move(Move::B);
+        assert!(matches!(guide.check(&physical), StepCheck::OffTrack(wrong) if wrong > 0));
+        assert!(matches!(guide.resync(&physical), Ok(StepCheck::OffTrack(_))));
+        assert_eq!(guide.position(), 0);
+        assert_eq!(guide.expected_cube(), &physical);
+
+        assert!(matches!(guide.check(&Cube::new(2)), StepCheck::OffTrack(24)));
+        assert!(GuidedSolve::new(&Cube::new(2)).is_err());
+    }
+}
@@ -0,0 +1,651 @@
+//! Audio narration hooks for lesson text
+//!
+//! Lesson steps can be read aloud through a pluggable text-to-speech backend:
+//! - Native platforms: the operating system's speech command (say, espeak, SAPI)
+//! - Web (WASM): the browser Web Speech API
+//!
+//! `LessonNarrator` walks through a lesson's steps, sends each step's
+//! kid-friendly text to the backend, and reports `NarrationEvent`s to any
+//! registered listeners so the lesson player can update its play/pause controls.
+
+use crate::tutorial::lessons::{
+    ColorLessonStep, CornersLessonStep, CrossLessonStep, FourByFourLessonStep, LessonStep,
+    OllLessonStep, PllLessonStep, SecondLayerLessonStep, TwoByTwoLessonStep,
+};
+
+/// Error types for text-to-speech operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TtsError {
+    /// No speech engine is available on this platform
+    Unavailable(String),
+    /// The speech engine failed to speak the text
+    SpeakFailed(String),
+}
+
+impl std::fmt::Display for TtsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TtsError::Unavailable(msg) => write!(f, "Text-to-speech unavailable: {}", msg),
+            TtsError::SpeakFailed(msg) => write!(f, "Text-to-speech failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for TtsError {}
+
+/// A text-to-speech engine that lesson narration can be sent to
+pub trait TtsBackend {
+    /// Human-readable backend name (for settings and diagnostics)
+    fn name(&self) -> &str;
+
+    /// Start speaking the text, interrupting anything currently being spoken
+    fn speak(&mut self, text: &str) -> Result<(), TtsError>;
+
+    /// Stop speaking immediately
+    fn stop(&mut self);
+
+    /// Pause speech, returning false if the backend cannot pause
+    ///
+    /// Backends that cannot pause are stopped instead, and the narrator
+    /// restarts the current step on resume.
+    fn pause(&mut self) -> bool {
+        false
+    }
+
+    /// Resume paused speech, returning false if the backend cannot resume
+    fn resume(&mut self) -> bool {
+        false
+    }
+}
+
+/// Backend that speaks nothing (used when narration is muted or unsupported)
+#[derive(Debug, Clone, Default)]
+pub struct SilentTtsBackend;
+
+impl TtsBackend for SilentTtsBackend {
+    fn name(&self) -> &str {
+        "Silent"
+    }
+
+    fn speak(&mut self, _text: &str) -> Result<(), TtsError> {
+        Ok(())
+    }
+
+    fn stop(&mut self) {}
+
+    fn pause(&mut self) -> bool {
+        true
+    }
+
+    fn resume(&mut self) -> bool {
+        true
+    }
+}
+
+/// Lesson content that can be read aloud
+pub trait Narratable {
+    /// Title of the lesson step
+    fn narration_title(&self) -> &str;
+
+    /// Kid-friendly text to read aloud
+    fn narration_body(&self) -> &str;
+
+    /// Full text sent to the speech engine (title followed by the body)
+    fn narration_text(&self) -> String {
+        format!("{}. {}", self.narration_title(), self.narration_body())
+    }
+}
+
+macro_rules! impl_narratable {
+    ($($step:ty),* $(,)?) => {
+        $(
+            impl Narratable for $step {
+                fn narration_title(&self) -> &str {
+                    &self.title
+                }
+
+                fn narration_body(&self) -> &str {
+                    &self.kid_friendly_text
+                }
+            }
+        )*
+    };
+}
+
+impl_narratable!(
+    LessonStep,
+    ColorLessonStep,
+    CrossLessonStep,
+    CornersLessonStep,
+    SecondLayerLessonStep,
+    OllLessonStep,
+    PllLessonStep,
+    TwoByTwoLessonStep,
+    FourByFourLessonStep,
+);
+
+/// Playback state of the narrator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NarrationState {
+    /// Nothing is being narrated
+    Idle,
+    /// A step is being read aloud
+    Playing,
+    /// Narration is paused part-way through a step
+    Paused,
+    /// The last step has been narrated
+    Finished,
+}
+
+/// Events emitted by the narrator
+#[derive(Debug, Clone, PartialEq)]
+pub enum NarrationEvent {
+    /// A step started playing
+    StepStarted { index: usize, text: String },
+    /// Narration was paused
+    Paused { index: usize },
+    /// Narration was resumed
+    Resumed { index: usize },
+    /// Narration was stopped by the user
+    Stopped,
+    /// The last step was reached and finished
+    Finished,
+    /// The backend reported an error
+    Error(TtsError),
+}
+
+/// Callback invoked for every narration event
+pub type NarrationListener = Box<dyn FnMut(&NarrationEvent)>;
+
+/// Reads lesson steps aloud through a `TtsBackend`
+pub struct LessonNarrator {
+    backend: Box<dyn TtsBackend>,
+    texts: Vec<String>,
+    current: usize,
+    state: NarrationState,
+    listeners: Vec<NarrationListener>,
+}
+
+impl LessonNarrator {
+    /// Create a narrator using the given backend
+    pub fn new(backend: Box<dyn TtsBackend>) -> Self {
+        Self {
+            backend,
+            texts: Vec::new(),
+            current: 0,
+            state: NarrationState::Idle,
+            listeners: Vec::new(),
+        }
+    }
+
+    /// Create a narrator using the default backend for this platform
+    pub fn with_default_backend() -> Self {
+        Self::new(default_backend())
+    }
+
+    /// Load the steps of a lesson, stopping any current narration
+    pub fn load_steps<S: Narratable>(&mut self, steps: &[S]) {
+        self.stop();
+        self.texts = steps.iter().map(|step| step.narration_text()).collect();
+        self.current = 0;
+        self.state = NarrationState::Idle;
+    }
+
+    /// Register a listener that receives every narration event
+    pub fn subscribe(&mut self, listener: NarrationListener) {
+        self.listeners.push(listener);
+    }
+
+    /// Get the name of the active backend
+    pub fn backend_name(&self) -> &str {
+        self.backend.name()
+    }
+
+    /// Get the current playback state
+    pub fn state(&self) -> NarrationState {
+        self.state
+    }
+
+    /// Get the index of the current step
+    pub fn current_step(&self) -> usize {
+        self.current
+    }
+
+    /// Get the number of loaded steps
+    pub fn step_count(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// Start (or restart) narrating the current step
+    pub fn play(&mut self) {
+        if self.state == NarrationState::Paused {
+            self.resume();
+            return;
+        }
+
+        let Some(text) = self.texts.get(self.current).cloned() else {
+            return;
+        };
+
+        match self.backend.speak(&text) {
+            Ok(()) => {
+                self.state = NarrationState::Playing;
+                self.emit(NarrationEvent::StepStarted {
+                    index: self.current,
+                    text,
+                });
+            }
+            Err(err) => {
+                self.state = NarrationState::Idle;
+                self.emit(NarrationEvent::Error(err));
+            }
+        }
+    }
+
+    /// Pause narration of the current step
+    pub fn pause(&mut self) {
+        if self.state != NarrationState::Playing {
+            return;
+        }
+
+        if !self.backend.pause() {
+            self.backend.stop();
+        }
+        self.state = NarrationState::Paused;
+        self.emit(NarrationEvent::Paused { index: self.current });
+    }
+
+    /// Resume paused narration
+    ///
+    /// Backends that cannot pause restart the current step from the beginning.
+    pub fn resume(&mut self) {
+        if self.state != NarrationState::Paused {
+            return;
+        }
+
+        if self.backend.resume() {
+            self.state = NarrationState::Playing;
+            self.emit(NarrationEvent::Resumed { index: self.current });
+        } else {
+            self.state = NarrationState::Idle;
+            self.play();
+        }
+    }
+
+    /// Toggle between playing and paused
+    pub fn toggle(&mut self) {
+        match self.state {
+            NarrationState::Playing => self.pause(),
+            _ => self.play(),
+        }
+    }
+
+    /// Stop narration entirely
+    pub fn stop(&mut self) {
+        if matches!(self.state, NarrationState::Playing | NarrationState::Paused) {
+            self.backend.stop();
+            self.state = NarrationState::Idle;
+            self.emit(NarrationEvent::Stopped);
+        }
+    }
+
+    /// Jump to a step, narrating it immediately if narration is active
+    pub fn go_to_step(&mut self, index: usize) {
+        if index >= self.texts.len() {
+            return;
+        }
+
+        let was_playing = self.state == NarrationState::Playing;
+        self.backend.stop();
+        self.current = index;
+        self.state = NarrationState::Idle;
+
+        if was_playing {
+            self.play();
+        }
+    }
+
+    /// Advance to the next step, or finish after the last one
+    ///
+    /// Lesson players call this when the user moves on, or when the backend
+    /// reports that the current utterance has ended.
+    pub fn next_step(&mut self) {
+        if self.current + 1 < self.texts.len() {
+            self.go_to_step(self.current + 1);
+        } else if !self.texts.is_empty() {
+            self.backend.stop();
+            self.state = NarrationState::Finished;
+            self.emit(NarrationEvent::Finished);
+        }
+    }
+
+    /// Go back to the previous step
+    pub fn previous_step(&mut self) {
+        if self.current > 0 {
+            self.go_to_step(self.current - 1);
+        }
+    }
+
+    fn emit(&mut self, event: NarrationEvent) {
+        for listener in self.listeners.iter_mut() {
+            listener(&event);
+        }
+    }
+}
+
+impl std::fmt::Debug for LessonNarrator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LessonNarrator")
+            .field("backend", &self.backend.name())
+            .field("steps", &self.texts.len())
+            .field("current", &self.current)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+// ============================================================================
+// NATIVE IMPLEMENTATION (system speech command)
+// ============================================================================
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native_impl {
+    use super::*;
+    use std::process::{Child, Command, Stdio};
+
+    /// Speaks text using the operating system's speech command
+    ///
+    /// - macOS: `say`
+    /// - Windows: System.Speech via PowerShell
+    /// - Linux and others: `espeak`
+    #[derive(Debug, Default)]
+    pub struct SystemTtsBackend {
+        child: Option<Child>,
+    }
+
+    impl SystemTtsBackend {
+        /// Create a new system speech backend
+        pub fn new() -> Self {
+            Self { child: None }
+        }
+
+        fn command(text: &str) -> Command {
+            #[cfg(target_os = "macos")]
+            {
+                let mut cmd = Command::new("say");
+                cmd.arg(text);
+                cmd
+            }
+
+            #[cfg(target_os = "windows")]
+            {
+                let script = format!(
+                    "Add-Type -AssemblyName System.Speech; \
+                     (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak('{}')",
+                    text.replace('\'', "''")
+                );
+                let mut cmd = Command::new("powershell");
+                cmd.args(["-NoProfile", "-Command", &script]);
+                cmd
+            }
+
+            #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+            {
+                let mut cmd = Command::new("espeak");
+                cmd.arg(text);
+                cmd
+            }
+        }
+    }
+
+    impl TtsBackend for SystemTtsBackend {
+        fn name(&self) -> &str {
+            "System speech"
+        }
+
+        fn speak(&mut self, text: &str) -> Result<(), TtsError> {
+            self.stop();
+
+            let child = Self::command(text)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| TtsError::Unavailable(format!("Failed to start speech command: {}", e)))?;
+
+            self.child = Some(child);
+            Ok(())
+        }
+
+        fn stop(&mut self) {
+            if let Some(mut child) = self.child.take() {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+        }
+    }
+
+    impl Drop for SystemTtsBackend {
+        fn drop(&mut self) {
+            self.stop();
+        }
+    }
+
+    /// Get the default speech backend for this platform
+    pub fn default_backend() -> Box<dyn TtsBackend> {
+        Box::new(SystemTtsBackend::new())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native_impl::*;
+
+// ============================================================================
+// WASM IMPLEMENTATION (Web Speech API)
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+mod wasm_impl {
+    use super::*;
+
+    /// Speaks text using the browser's Web Speech API
+    #[derive(Debug, Default)]
+    pub struct WebSpeechBackend;
+
+    impl WebSpeechBackend {
+        /// Create a new Web Speech backend
+        pub fn new() -> Self {
+            Self
+        }
+
+        fn synthesis() -> Result<web_sys::SpeechSynthesis, TtsError> {
+            let window = web_sys::window()
+                .ok_or_else(|| TtsError::Unavailable("No browser window".to_string()))?;
+            window
+                .speech_synthesis()
+                .map_err(|_| TtsError::Unavailable("Web Speech API not supported".to_string()))
+        }
+    }
+
+    impl TtsBackend for WebSpeechBackend {
+        fn name(&self) -> &str {
+            "Web Speech"
+        }
+
+        fn speak(&mut self, text: &str) -> Result<(), TtsError> {
+            let synthesis = Self::synthesis()?;
+            let utterance = web_sys::SpeechSynthesisUtterance::new_with_text(text)
+                .map_err(|_| TtsError::SpeakFailed("Could not create utterance".to_string()))?;
+            synthesis.cancel();
+            synthesis.speak(&utterance);
+            Ok(())
+        }
+
+        fn stop(&mut self) {
+            if let Ok(synthesis) = Self::synthesis() {
+                synthesis.cancel();
+            }
+        }
+
+        fn pause(&mut self) -> bool {
+            match Self::synthesis() {
+                Ok(synthesis) => {
+                    synthesis.pause();
+                    true
+                }
+                Err(_) => false,
+            }
+        }
+
+        fn resume(&mut self) -> bool {
+            match Self::synthesis() {
+                Ok(synthesis) => {
+                    synthesis.resume();
+                    true
+                }
+                Err(_) => false,
+            }
+        }
+    }
+
+    /// Get the default speech backend for this platform
+    pub fn default_backend() -> Box<dyn TtsBackend> {
+        Box::new(WebSpeechBackend::new())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub use wasm_impl::*;
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tutorial::lessons::NotationLesson;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Backend that records what it was asked to say
+    #[derive(Clone, Default)]
+    struct RecordingBackend {
+        spoken: Rc<RefCell<Vec<String>>>,
+        can_pause: bool,
+    }
+
+    impl TtsBackend for RecordingBackend {
+        fn name(&self) -> &str {
+            "Recording"
+        }
+
+        fn speak(&mut self, text: &str) -> Result<(), TtsError> {
+            self.spoken.borrow_mut().push(text.to_string());
+            Ok(())
+        }
+
+        fn stop(&mut self) {}
+
+        fn pause(&mut self) -> bool {
+            self.can_pause
+        }
+
+        fn resume(&mut self) -> bool {
+            self.can_pause
+        }
+    }
+
+    fn narrator_with(backend: RecordingBackend) -> LessonNarrator {
+        let mut narrator = LessonNarrator::new(Box::new(backend));
+        narrator.load_steps(&NotationLesson::new().steps);
+        narrator
+    }
+
+    #[test]
+    fn test_narration_text_uses_kid_friendly_text() {
+        let step = &NotationLesson::new().steps[0];
+        let text = step.narration_text();
+        assert!(text.starts_with(&step.title));
+        assert!(text.contains(&step.kid_friendly_text));
+    }
+
+    #[test]
+    fn test_play_speaks_current_step() {
+        let backend = RecordingBackend::default();
+        let spoken = backend.spoken.clone();
+        let mut narrator = narrator_with(backend);
+
+        narrator.play();
+
+        assert_eq!(narrator.state(), NarrationState::Playing);
+        assert_eq!(spoken.borrow().len(), 1);
+        assert!(spoken.borrow()[0].contains("Welcome to Cube Notation!"));
+    }
+
+    #[test]
+    fn test_pause_and_resume_with_native_pause() {
+        let backend = RecordingBackend { can_pause: true, ..Default::default() };
+        let spoken = backend.spoken.clone();
+        let mut narrator = narrator_with(backend);
+
+        narrator.play();
+        narrator.pause();
+        assert_eq!(narrator.state(), NarrationState::Paused);
+
+        narrator.resume();
+        assert_eq!(narrator.state(), NarrationState::Playing);
+        // Native resume continues without speaking again
+        assert_eq!(spoken.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_resume_restarts_step_without_native_pause() {
+        let backend = RecordingBackend::default();
+        let spoken = backend.spoken.clone();
+        let mut narrator = narrator_with(backend);
+
+        narrator.play();
+        narrator.toggle();
+        narrator.toggle();
+
+        assert_eq!(narrator.state(), NarrationState::Playing);
+        assert_eq!(spoken.borrow().len(), 2);
+        assert_eq!(spoken.borrow()[0], spoken.borrow()[1]);
+    }
+
+    #[test]
+    fn test_listeners_receive_events_until_finished() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let sink = events.clone();
+        let mut narrator = narrator_with(RecordingBackend::default());
+        narrator.subscribe(Box::new(move |event| sink.borrow_mut().push(event.clone())));
+
+        narrator.play();
+        for _ in 0..narrator.step_count() {
+            narrator.next_step();
+        }
+
+        assert_eq!(narrator.state(), NarrationState::Finished);
+        let events = events.borrow();
+        let started = events
+            .iter()
+            .filter(|e| matches!(e, NarrationEvent::StepStarted { .. }))
+            .count();
+        assert_eq!(started, narrator.step_count());
+        assert_eq!(events.last(), Some(&NarrationEvent::Finished));
+    }
+
+    #[test]
+    fn test_stop_returns_to_idle() {
+        let mut narrator = narrator_with(RecordingBackend::default());
+        narrator.play();
+        narrator.stop();
+        assert_eq!(narrator.state(), NarrationState::Idle);
+    }
+
+    #[test]
+    fn test_tts_error_display() {
+        let err = TtsError::Unavailable("no engine".to_string());
+        assert_eq!(err.to_string(), "Text-to-speech unavailable: no engine");
+    }
+}
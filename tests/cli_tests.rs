@@ -0,0 +1,114 @@
+//! Integration tests for the cube-solver-cli binary
+//!
+//! Tests the following acceptance criteria:
+//! - Scramble generation with size and count
+//! - Printable scramble sheets
+//! - Applying algorithms and printing ASCII nets
+//! - Solving from facelet strings
+//! - Optimal solving within a time budget
+//! - Optimizing move sequences
+//! - Helpful errors for bad input
+
+use std::process::{Command, Output};
+
+fn cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cube-solver-cli"))
+        .args(args)
+        .output()
+        .expect("failed to run cube-solver-cli")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_cli_001_scramble_count() {
+    let output = cli(&["scramble", "--size", "4", "--count", "3", "--length", "10"]);
+    assert!(output.status.success());
+
+    let text = stdout(&output);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("1. "));
+    assert_eq!(lines[2].split_whitespace().count(), 11);
+}
+
+#[test]
+fn test_cli_002_apply_and_undo_is_solved() {
+    let output = cli(&["apply", "--alg", "R U R' U' U R U' R'"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("Solved: yes"));
+}
+
+#[test]
+fn test_cli_003_apply_prints_net() {
+    let output = cli(&["apply", "--alg", "R", "--size", "2"]);
+    let text = stdout(&output);
+    assert!(text.contains("Facelets: "));
+    assert!(text.contains("Solved: no"));
+    assert!(text.lines().count() >= 6);
+}
+
+#[test]
+fn test_cli_004_solve_from_facelets() {
+    let applied = stdout(&cli(&["apply", "--alg", "R U"]));
+    let facelets = applied
+        .lines()
+        .find_map(|l| l.strip_prefix("Facelets: "))
+        .expect("facelets line")
+        .to_string();
+
+    let output = cli(&["solve", "--state", &facelets]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("Solution: U' R'"));
+}
+
+#[test]
+fn test_cli_005_errors() {
+    assert!(!cli(&[]).status.success());
+    assert!(!cli(&["bogus"]).status.success());
+    assert!(!cli(&["apply", "--alg", "M", "--size", "4"]).status.success());
+    assert!(!cli(&["solve", "--state", "WWW"]).status.success());
+}
+
+#[test]
+fn test_cli_006_scramble_sheet() {
+    let output = cli(&["sheet", "--size", "5", "--count", "4", "--title", "Class 3B"]);
+    assert!(output.status.success());
+
+    let html = stdout(&output);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h1>Class 3B</h1>"));
+    assert_eq!(html.matches("<li>").count(), 4);
+    assert_eq!(html.matches("<svg").count(), 4);
+}
+
+#[test]
+fn test_cli_007_optimize() {
+    let output = cli(&["optimize", "--alg", "R U U' R F y L"]);
+    assert!(output.status.success());
+
+    let text = stdout(&output);
+    assert!(text.contains("Cancellation: U U' -> nothing (-2)"));
+    assert!(text.contains("7 -> 2 moves (5 saved)"));
+    assert!(text.trim_end().ends_with("Optimized: R2 y"));
+    assert!(!cli(&["optimize", "--alg", "Rw"]).status.success());
+}
+
+#[test]
+fn test_cli_008_solve_optimal() {
+    let applied = stdout(&cli(&["apply", "--alg", "R U U F"]));
+    let facelets = applied
+        .lines()
+        .find_map(|l| l.strip_prefix("Facelets: "))
+        .expect("facelets line")
+        .to_string();
+
+    let output = cli(&["solve", "--state", &facelets, "--optimal", "30"]);
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert!(text.contains("using Two-Phase Search with 1 steps and 3 moves"));
+    assert!(text.contains("Proven optimal: yes"));
+    assert!(!cli(&["solve", "--state", &facelets, "--optimal", "soon"]).status.success());
+}
@@ -0,0 +1,84 @@
+/// Integration tests for the frame source abstraction
+///
+/// These tests run the scanning pipeline headlessly: frames come from
+/// generated cube faces and still images instead of a camera.
+
+use rubiks_cube_solver::camera::{
+    analyze_lighting, capture_fused, detect_colors_in_quad, detect_grid_size, scan_frame, ColorDetectionConfig,
+    FaceQuad, FaceRegion, FrameSource, LightingMonitor, StaticImageSource, SyntheticSource,
+};
+use rubiks_cube_solver::cube::Color;
+
+fn face(size: usize) -> Vec<Vec<Color>> {
+    let colors = [Color::White, Color::Yellow, Color::Red, Color::Orange, Color::Blue, Color::Green];
+    (0..size)
+        .map(|row| (0..size).map(|col| colors[(row * 5 + col * 2) % colors.len()]).collect())
+        .collect()
+}
+
+fn colors_of(grid: &[Vec<rubiks_cube_solver::camera::ColorDetectionResult>]) -> Vec<Vec<Color>> {
+    grid.iter().map(|row| row.iter().map(|cell| cell.color).collect()).collect()
+}
+
+#[test]
+fn test_pipeline_on_the_guide_region() {
+    // Size detection and sampling, as the scanner does with camera frames
+    for size in [2, 3, 5] {
+        let mut source = SyntheticSource::new(face(size), 320, 240).with_noise(15, size as u64);
+        source.start().unwrap();
+        let frame = source.next_frame().unwrap();
+
+        let region = FaceRegion::guide(frame.width, frame.height);
+        let estimate = detect_grid_size(&frame.data, frame.width, frame.height, region).unwrap();
+        assert_eq!(estimate.size as usize, size);
+
+        let quad = FaceQuad::from_region(region);
+        let config = ColorDetectionConfig::default();
+        let grid = detect_colors_in_quad(&frame.data, frame.width, frame.height, &quad, size, &config).unwrap();
+        assert_eq!(colors_of(&grid), face(size));
+    }
+}
+
+#[test]
+fn test_fused_capture_through_the_trait() {
+    let mut sources: Vec<Box<dyn FrameSource>> = vec![
+        Box::new(SyntheticSource::new(face(3), 120, 120).with_face_region(FaceRegion::full(120, 120))),
+        Box::new(
+            SyntheticSource::new(face(3), 120, 120)
+                .with_face_region(FaceRegion::full(120, 120))
+                .with_noise(40, 3),
+        ),
+    ];
+    for source in sources.iter_mut() {
+        source.start().unwrap();
+        let fused = capture_fused(source.as_mut(), 3, 4, &ColorDetectionConfig::default()).unwrap();
+        assert_eq!(colors_of(&fused), face(3));
+        source.stop().unwrap();
+        assert!(!source.is_streaming());
+    }
+}
+
+#[test]
+fn test_static_source_from_a_generated_frame() {
+    let mut synthetic = SyntheticSource::new(face(4), 160, 160).with_face_region(FaceRegion::full(160, 160));
+    synthetic.start().unwrap();
+    let mut source = StaticImageSource::new(synthetic.next_frame().unwrap());
+    source.start().unwrap();
+
+    let frame = source.next_frame().unwrap();
+    let scan = scan_frame(&frame, None, None, &ColorDetectionConfig::default()).unwrap();
+    assert_eq!(scan.size, 4);
+    assert_eq!(colors_of(&scan.colors), face(4));
+}
+
+#[test]
+fn test_dim_source_triggers_lighting_warning() {
+    let mut source = SyntheticSource::new(face(3), 90, 90).with_brightness(0.15);
+    source.start().unwrap();
+    let frame = source.next_frame().unwrap();
+    assert!(analyze_lighting(&frame.data, frame.width, frame.height).too_dark);
+
+    let mut monitor = LightingMonitor::new(1);
+    monitor.observe(&frame);
+    assert!(monitor.warning().is_some());
+}
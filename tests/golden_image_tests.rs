@@ -0,0 +1,123 @@
+//! Golden-image tests for the cube renderer
+//!
+//! Renders the cube off-screen and compares the pixels with the PNGs in
+//! `tests/golden/`. Run with `UPDATE_GOLDEN=1` to write them from the
+//! current renders after an intended visual change; otherwise a missing
+//! golden image fails the test. Small differences between GPUs and drivers
+//! are tolerated.
+//!
+//! Tests are skipped (and pass) when the machine has no GPU adapter at all,
+//! not even a software one.
+
+#![cfg(feature = "desktop_3d")]
+
+use image::RgbaImage;
+use rubiks_cube_solver::cube::{Cube, FaceName};
+use rubiks_cube_solver::renderer::{
+    Camera, CubeMesh, HighlightManager, MeshConfig, MeshRenderer, PieceId, RotationFace, WgpuContext,
+    WgpuContextConfig,
+};
+use std::path::PathBuf;
+
+const SIZE: u32 = 256;
+
+/// Channel difference above which a pixel counts as different
+const CHANNEL_TOLERANCE: u8 = 16;
+
+/// Share of pixels allowed to differ
+const MAX_DIFFERENT_PIXELS: f32 = 0.01;
+
+/// Renders `mesh` off-screen; `None` when there is no adapter
+fn render(mesh: &CubeMesh) -> Option<Vec<u8>> {
+    let config = WgpuContextConfig {
+        width: SIZE,
+        height: SIZE,
+        ..Default::default()
+    };
+    let mut context = match pollster::block_on(WgpuContext::new_offscreen(config)) {
+        Ok(context) => context,
+        Err(e) => {
+            eprintln!("Skipping golden-image test: {}", e);
+            return None;
+        }
+    };
+    let mut camera = Camera::new();
+    camera.set_aspect_ratio(1.0);
+    let mut renderer = MeshRenderer::new(&context);
+    renderer.render(&mut context, mesh, &camera).expect("off-screen frames are always available");
+    context.read_pixels()
+}
+
+/// Compares `pixels` with `tests/golden/<name>.png`, or writes it with
+/// `UPDATE_GOLDEN=1`
+fn assert_matches_golden(name: &str, pixels: Vec<u8>) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden");
+    let path = dir.join(format!("{}.png", name));
+    let actual = RgbaImage::from_raw(SIZE, SIZE, pixels).expect("read back a full frame");
+
+    if std::env::var("UPDATE_GOLDEN").is_ok_and(|value| value == "1") {
+        std::fs::create_dir_all(&dir).unwrap();
+        actual.save(&path).unwrap();
+        eprintln!("Wrote golden image {}", path.display());
+        return;
+    }
+
+    let expected = match image::open(&path) {
+        Ok(expected) => expected.to_rgba8(),
+        Err(e) => panic!("No golden image {} ({}); run with UPDATE_GOLDEN=1 to create it", path.display(), e),
+    };
+    assert_eq!(expected.dimensions(), actual.dimensions(), "{} changed size", name);
+    let different = expected
+        .pixels()
+        .zip(actual.pixels())
+        .filter(|(a, b)| a.0.iter().zip(b.0.iter()).any(|(x, y)| x.abs_diff(*y) > CHANNEL_TOLERANCE))
+        .count();
+    let share = different as f32 / (SIZE * SIZE) as f32;
+    if share > MAX_DIFFERENT_PIXELS {
+        let actual_path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("{}.actual.png", name));
+        actual.save(&actual_path).unwrap();
+        panic!(
+            "{} differs from its golden image in {:.1}% of pixels; see {}",
+            name,
+            share * 100.0,
+            actual_path.display()
+        );
+    }
+}
+
+#[test]
+fn test_solved_cube_matches_golden() {
+    let mesh = CubeMesh::generate(&Cube::new(3), &MeshConfig::default());
+    let Some(pixels) = render(&mesh) else { return };
+    assert_matches_golden("solved_3x3", pixels);
+}
+
+#[test]
+fn test_highlighted_sticker_matches_golden() {
+    let mut highlights = HighlightManager::new();
+    highlights.set_selected(Some(PieceId::new(FaceName::F, 1, 1)));
+    highlights.set_tutorial_pieces(vec![PieceId::new(FaceName::U, 2, 2), PieceId::new(FaceName::R, 0, 0)]);
+    let mesh = CubeMesh::generate_highlighted(&Cube::new(3), &MeshConfig::default(), &highlights);
+    let Some(pixels) = render(&mesh) else { return };
+    assert_matches_golden("highlighted_3x3", pixels);
+}
+
+#[test]
+fn test_mid_animation_matches_golden() {
+    let config = MeshConfig::default();
+    let mut mesh = CubeMesh::generate(&Cube::new(3), &config);
+    mesh.rotate_layer(RotationFace::R, 45.0, 3, &config);
+    let Some(pixels) = render(&mesh) else { return };
+    assert_matches_golden("r_turn_halfway", pixels);
+}
+
+#[test]
+fn test_offscreen_frames_differ_by_state() {
+    let config = MeshConfig::default();
+    let solved = CubeMesh::generate(&Cube::new(3), &config);
+    let mut turning = solved.clone();
+    turning.rotate_layer(RotationFace::R, 45.0, 3, &config);
+    let (Some(before), Some(after)) = (render(&solved), render(&turning)) else { return };
+    assert_eq!(before.len(), (SIZE * SIZE * 4) as usize);
+    assert_ne!(before, after);
+}
@@ -66,7 +66,7 @@ fn test_undo_redo_history_stack() {
     history.push(cube3.clone());
 
     let mut cube4 = cube3.clone();
-    cube4.set_sticker(FaceName::F, 1, 0, Color::Green);
+    cube4.set_sticker(FaceName::F, 1, 0, Color::Orange);
     history.push(cube4.clone());
 
     // Current should be cube4
@@ -164,7 +164,7 @@ fn test_linux_build_013_platform_agnostic_core() {
     for parsed_move in moves {
         match parsed_move {
             rubiks_cube_solver::cube::notation::ParsedMove::Basic(m) => cube.apply_move(m),
-            rubiks_cube_solver::cube::notation::ParsedMove::Wide(_) => {}, // Skip wide moves for 3x3
+            rubiks_cube_solver::cube::notation::ParsedMove::Wide(_) | rubiks_cube_solver::cube::notation::ParsedMove::Layer(_) => {}, // Skip wide and layer moves for 3x3
         }
     }
 
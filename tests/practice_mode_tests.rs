@@ -402,3 +402,24 @@ fn test_practice_020_multiple_hint_requests() {
 
     assert_eq!(session.hints_used, 3);
 }
+
+#[test]
+fn test_practice_021_adaptive_difficulty_follows_results() {
+    // Struggling students should get easier cases, fast learners harder ones
+    let mut generator = PracticeGenerator::new(Difficulty::Medium);
+
+    for _ in 0..3 {
+        let case = generator.next_random_case(2);
+        generator.record_case_result(&case, false);
+    }
+    assert_eq!(generator.current_difficulty(&PracticeType::Random), Difficulty::Easy);
+
+    for _ in 0..6 {
+        let case = generator.next_random_case(2);
+        generator.record_case_result(&case, true);
+    }
+    assert_eq!(generator.current_difficulty(&PracticeType::Random), Difficulty::Hard);
+
+    let case = generator.next_random_case(2);
+    assert_eq!(case.difficulty, Difficulty::Hard);
+}
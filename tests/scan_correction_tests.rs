@@ -159,7 +159,7 @@ fn test_scan_correction_003_mixed_colors_with_various_confidence() {
         vec![
             ColorDetectionResult {
                 color: Color::White,
-                confidence: 0.78,
+                confidence: 0.78, // Reliable
             },
             ColorDetectionResult {
                 color: Color::Blue,
@@ -185,7 +185,7 @@ fn test_scan_correction_003_mixed_colors_with_various_confidence() {
         .count();
 
     assert_eq!(uncertain_count, 2); // confidence < 0.6
-    assert_eq!(reliable_count, 6); // confidence >= 0.8
+    assert_eq!(reliable_count, 6); // confidence >= 0.75
 }
 
 #[test]
@@ -1,4 +1,5 @@
 use rubiks_cube_solver::cube::{
+    notation::ParsedMove,
     scramble::{generate_scramble, Scramble, ScrambleConfig},
     Cube, Move,
 };
@@ -18,10 +19,12 @@ fn test_scram_002_scramble_avoids_r_r_sequence() {
     for _ in 0..100 {
         let config = ScrambleConfig::new(20, 3);
         let scramble = generate_scramble(&config);
+        // 3x3 scrambles have no wide moves
+        let moves = scramble.basic_moves().unwrap();
 
-        for i in 1..scramble.moves.len() {
-            let prev_move = scramble.moves[i - 1];
-            let curr_move = scramble.moves[i];
+        for i in 1..moves.len() {
+            let prev_move = moves[i - 1];
+            let curr_move = moves[i];
 
             // Check that consecutive moves are not on the same face
             let prev_face = get_face_from_move(prev_move);
@@ -43,10 +46,12 @@ fn test_scram_003_scramble_avoids_r_rprime_sequence() {
     for _ in 0..100 {
         let config = ScrambleConfig::new(20, 3);
         let scramble = generate_scramble(&config);
+        // 3x3 scrambles have no wide moves
+        let moves = scramble.basic_moves().unwrap();
 
-        for i in 1..scramble.moves.len() {
-            let prev_move = scramble.moves[i - 1];
-            let curr_move = scramble.moves[i];
+        for i in 1..moves.len() {
+            let prev_move = moves[i - 1];
+            let curr_move = moves[i];
 
             // Since we're avoiding same face, R R' is already impossible
             // But let's verify this explicitly
@@ -141,18 +146,7 @@ fn test_scramble_2x2() {
     // 2x2 should not have slice moves
     for m in &scramble.moves {
         assert!(
-            !matches!(
-                m,
-                Move::M
-                    | Move::MPrime
-                    | Move::M2
-                    | Move::E
-                    | Move::EPrime
-                    | Move::E2
-                    | Move::S
-                    | Move::SPrime
-                    | Move::S2
-            ),
+            !matches!(m, ParsedMove::Basic(mv) if mv.is_slice()),
             "2x2 scramble should not contain slice moves"
         );
     }
@@ -169,18 +163,7 @@ fn test_scramble_4x4() {
     // 4x4 should not have slice moves (even cube)
     for m in &scramble.moves {
         assert!(
-            !matches!(
-                m,
-                Move::M
-                    | Move::MPrime
-                    | Move::M2
-                    | Move::E
-                    | Move::EPrime
-                    | Move::E2
-                    | Move::S
-                    | Move::SPrime
-                    | Move::S2
-            ),
+            !matches!(m, ParsedMove::Basic(mv) if mv.is_slice()),
             "4x4 scramble should not contain slice moves"
         );
     }
@@ -216,7 +199,7 @@ fn test_apply_scramble_manually() {
     // Create a fresh cube and apply moves manually
     let mut manual_cube = Cube::new(3);
     for m in &scramble.moves {
-        manual_cube.apply_move(*m);
+        manual_cube.apply_parsed_move(m);
     }
 
     // Should match the scramble's cube state
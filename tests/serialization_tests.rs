@@ -132,7 +132,7 @@ fn test_version_in_json() {
     // Parse as generic JSON to check version field
     let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
     let version = parsed["version"].as_u64().expect("Should have version field");
-    assert_eq!(version, 1);
+    assert_eq!(version, 2);
 }
 
 #[test]
@@ -156,7 +156,7 @@ fn test_wrong_version_error() {
     match result {
         Err(SerializationError::UnsupportedVersion { found, supported }) => {
             assert_eq!(found, 999);
-            assert_eq!(supported, 1);
+            assert_eq!(supported, 2);
         }
         _ => panic!("Expected UnsupportedVersion error"),
     }
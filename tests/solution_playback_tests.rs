@@ -179,8 +179,8 @@ fn test_empty_solution_handling() {
 
     // Should have at least one step indicating cube is solved
     assert!(solution.step_count() > 0);
-    assert!(solution.steps[0].description.contains("solved") ||
-            solution.steps[0].description.contains("already"));
+    assert!(solution.steps()[0].description.contains("solved") ||
+            solution.steps()[0].description.contains("already"));
 }
 
 #[test]
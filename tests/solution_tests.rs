@@ -153,10 +153,10 @@ fn test_3x3_solver_has_steps() {
 
     let solution = solve_3x3(&cube).expect("Should solve");
 
-    assert!(!solution.steps.is_empty(), "3x3 solution should have steps");
+    assert!(!solution.steps().is_empty(), "3x3 solution should have steps");
 
     // Verify each step has a description
-    for step in &solution.steps {
+    for step in &solution.steps() {
         assert!(!step.description.is_empty(), "Each step should have a description");
     }
 }
@@ -0,0 +1,72 @@
+//! Surface loss, device loss and resize handling of `WgpuContext`
+//!
+//! Runs off-screen, so it works in CI; skipped (and passing) when the
+//! machine has no GPU adapter at all.
+
+#![cfg(feature = "desktop_3d")]
+
+use rubiks_cube_solver::cube::Cube;
+use rubiks_cube_solver::renderer::{Camera, CubeMesh, MeshConfig, MeshRenderer, WgpuContext, WgpuContextConfig};
+use wgpu::SurfaceError;
+
+fn context(width: u32, height: u32) -> Option<WgpuContext> {
+    let config = WgpuContextConfig {
+        width,
+        height,
+        ..Default::default()
+    };
+    match pollster::block_on(WgpuContext::new_offscreen(config)) {
+        Ok(context) => Some(context),
+        Err(e) => {
+            eprintln!("Skipping surface recovery test: {}", e);
+            None
+        }
+    }
+}
+
+#[test]
+fn test_recoverable_surface_errors_keep_drawing() {
+    let Some(mut context) = context(64, 64) else { return };
+    assert!(context.recover_surface(SurfaceError::Lost).is_ok());
+    assert!(context.recover_surface(SurfaceError::Outdated).is_ok());
+    assert!(context.recover_surface(SurfaceError::Timeout).is_ok());
+    assert_eq!(context.recover_surface(SurfaceError::OutOfMemory), Err(SurfaceError::OutOfMemory));
+    assert!(context.read_pixels().is_some());
+}
+
+#[test]
+fn test_resize_and_dpi_change_recreate_target() {
+    let Some(mut context) = context(64, 48) else { return };
+    let mesh = CubeMesh::generate(&Cube::new(3), &MeshConfig::default());
+    let camera = Camera::new();
+    let mut renderer = MeshRenderer::new(&context);
+
+    context.resize(0, 0);
+    assert_eq!(context.size(), (64, 48));
+
+    context.set_scale_factor(2.0, (128, 96));
+    assert_eq!(context.scale_factor(), 2.0);
+    assert_eq!(context.size(), (128, 96));
+    renderer.render(&mut context, &mesh, &camera).unwrap();
+    assert_eq!(context.read_pixels().unwrap().len(), 128 * 96 * 4);
+
+    context.resize(u32::MAX, 32);
+    let max = context.device.limits().max_texture_dimension_2d;
+    assert_eq!(context.size(), (max, 32));
+}
+
+#[test]
+fn test_renderer_rebuilds_after_device_recovery() {
+    let Some(mut context) = context(64, 64) else { return };
+    let mesh = CubeMesh::generate(&Cube::new(3), &MeshConfig::default());
+    let camera = Camera::new();
+    let mut renderer = MeshRenderer::new(&context);
+    renderer.render(&mut context, &mesh, &camera).unwrap();
+    let before = context.read_pixels().unwrap();
+
+    context.recover_device().unwrap();
+    assert_eq!(context.generation(), 1);
+    assert!(!context.is_device_lost());
+    renderer.render(&mut context, &mesh, &camera).unwrap();
+    assert_eq!(context.read_pixels().unwrap(), before);
+}
@@ -2,11 +2,11 @@
 //!
 //! Tests based on test-plan.md validation tests (valid_001 through valid_009)
 //!
-//! Note: Full parity validation (twisted corners, flipped edges, permutation parity)
-//! is complex and requires tracking piece identities. The current implementation
-//! validates color counts, which catches many invalid states. Any cube reached
-//! through legal moves will pass validation.
+//! Validation checks color counts, then that every piece is real and that
+//! twists, flips and swaps add up to a state reachable by turning. Any cube
+//! reached through legal moves passes validation.
 
+use rubiks_cube_solver::cube::validation::ValidationError;
 use rubiks_cube_solver::cube::{Cube, Color, FaceName, Move};
 
 #[test]
@@ -50,10 +50,7 @@ fn valid_003_wrong_color_count_is_invalid() {
 }
 
 #[test]
-fn valid_004_twisted_single_corner_not_detectable_yet() {
-    // Note: Detecting twisted corners requires full parity validation
-    // which is not yet implemented. This test documents the limitation.
-
+fn valid_004_twisted_single_corner_is_invalid() {
     let mut cube = Cube::new(3);
 
     // Manually create a state with a single twisted corner
@@ -68,17 +65,14 @@ fn valid_004_twisted_single_corner_not_detectable_yet() {
     cube.get_face_mut(FaceName::F).set(0, 2, original_right);
     cube.get_face_mut(FaceName::R).set(0, 0, original_up);
 
-    // Currently passes validation (color counts are still correct)
-    // TODO: Should fail when parity validation is implemented
+    // Color counts are still correct, but the corner twist doesn't add up
     let result = cube.validate();
-    assert!(result.is_ok(), "Single twisted corner not yet detectable - passes for now");
+    assert!(result.is_err(), "Single twisted corner should be invalid");
+    assert_eq!(result, Err(ValidationError::CornerParity));
 }
 
 #[test]
-fn valid_005_flipped_single_edge_not_detectable_yet() {
-    // Note: Detecting flipped edges requires full parity validation
-    // which is not yet implemented. This test documents the limitation.
-
+fn valid_005_flipped_single_edge_is_invalid() {
     let mut cube = Cube::new(3);
 
     // Manually create a state with a single flipped edge
@@ -88,16 +82,14 @@ fn valid_005_flipped_single_edge_not_detectable_yet() {
     cube.get_face_mut(FaceName::U).set(2, 1, original_front);
     cube.get_face_mut(FaceName::F).set(0, 1, original_up);
 
-    // Currently passes validation (color counts are still correct)
-    // TODO: Should fail when parity validation is implemented
+    // Color counts are still correct, but edges can only flip in pairs
     let result = cube.validate();
-    assert!(result.is_ok(), "Single flipped edge not yet detectable - passes for now");
+    assert!(result.is_err(), "Single flipped edge should be invalid");
+    assert_eq!(result, Err(ValidationError::EdgeParity));
 }
 
 #[test]
-fn valid_006_two_swapped_edges_not_detectable_yet() {
-    // Note: Detecting permutation parity requires full parity validation
-
+fn valid_006_two_swapped_edges_is_invalid() {
     let mut cube = Cube::new(3);
 
     // Swap two edges
@@ -111,16 +103,15 @@ fn valid_006_two_swapped_edges_not_detectable_yet() {
     cube.get_face_mut(FaceName::U).set(1, 2, uf_up);
     cube.get_face_mut(FaceName::R).set(0, 1, uf_front);
 
-    // Currently passes validation (color counts are still correct)
-    // TODO: Should fail when parity validation is implemented
+    // Color counts are still correct, but swapping two edges alone has the
+    // wrong permutation parity
     let result = cube.validate();
-    assert!(result.is_ok(), "Two swapped edges not yet detectable - passes for now");
+    assert!(result.is_err(), "Two swapped edges should be invalid");
+    assert_eq!(result, Err(ValidationError::PermutationParity));
 }
 
 #[test]
-fn valid_007_two_swapped_corners_not_detectable_yet() {
-    // Note: Detecting permutation parity requires full parity validation
-
+fn valid_007_two_swapped_corners_is_invalid() {
     let mut cube = Cube::new(3);
 
     // Swap two corners
@@ -140,10 +131,15 @@ fn valid_007_two_swapped_corners_not_detectable_yet() {
     cube.get_face_mut(FaceName::F).set(0, 2, ufl_front);
     cube.get_face_mut(FaceName::R).set(0, 0, ufl_left);
 
-    // Currently passes validation (color counts are still correct)
-    // TODO: Should fail when parity validation is implemented
+    // Copying the stickers face by face also mirrors both corners, which is
+    // caught before the permutation parity
     let result = cube.validate();
-    assert!(result.is_ok(), "Two swapped corners not yet detectable - passes for now");
+    assert!(result.is_err(), "Two swapped corners should be invalid");
+    assert!(
+        matches!(result, Err(ValidationError::MirroredCorner { .. })),
+        "Expected a mirrored corner, got {:?}",
+        result
+    );
 }
 
 #[test]